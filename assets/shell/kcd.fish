@@ -0,0 +1,11 @@
+# Browse with kiorg and cd to the directory it was quit in.
+# Copy this file to ~/.config/fish/functions/, then run `kcd [DIR]`.
+function kcd
+    set -l cwd_file (mktemp -t kiorg-cwd.XXXXXX); or return
+    kiorg --choose-dir $cwd_file $argv
+    set -l cwd (cat -- $cwd_file)
+    rm -f -- $cwd_file
+    if test -n "$cwd"; and test "$cwd" != "$PWD"
+        cd -- $cwd
+    end
+end
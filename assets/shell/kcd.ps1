@@ -0,0 +1,11 @@
+# Browse with kiorg and cd to the directory it was quit in.
+# Dot-source this file from your $PROFILE, then run `kcd [DIR]`.
+function kcd {
+    $cwdFile = New-TemporaryFile
+    kiorg --choose-dir $cwdFile.FullName @args
+    $cwd = Get-Content -Path $cwdFile.FullName -Raw
+    Remove-Item -Path $cwdFile.FullName
+    if ($cwd -and $cwd.Trim() -ne $PWD.Path) {
+        Set-Location -LiteralPath $cwd.Trim()
+    }
+}
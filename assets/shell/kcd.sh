@@ -0,0 +1,12 @@
+# Browse with kiorg and cd to the directory it was quit in.
+# Source this file from ~/.bashrc or ~/.zshrc, then run `kcd [DIR]`.
+kcd() {
+    local cwd_file cwd
+    cwd_file="$(mktemp -t kiorg-cwd.XXXXXX)" || return
+    kiorg --choose-dir "$cwd_file" "$@"
+    cwd="$(cat -- "$cwd_file")"
+    rm -f -- "$cwd_file"
+    if [ -n "$cwd" ] && [ "$cwd" != "$PWD" ]; then
+        cd -- "$cwd" || return
+    fi
+}
@@ -6,22 +6,33 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::config::shortcuts::TraverseResult;
-use crate::config::{self, LEFT_PANEL_RATIO, PREVIEW_PANEL_RATIO, colors::AppColors};
+use crate::config::{
+    self, LEFT_PANEL_RATIO, PREVIEW_PANEL_RATIO, ZoomContent, ZoomMode, colors::AppColors,
+};
 use crate::input;
+use crate::models::action_history::{ActionType, CreateOperation};
+use crate::models::jump_list::Jump;
+use crate::models::jump_marks::JumpMarks;
 use crate::models::preview_content::PreviewContent;
 use crate::models::tab::{TabManager, TabManagerState};
-use crate::open_wrap::{open_that, open_with};
+use crate::models::window_state::WindowState;
+use crate::open_wrap::{open_in_terminal, open_system_terminal, open_that, open_with};
 use crate::ui::egui_notify::Toasts;
-use crate::ui::popup::delete::DeleteConfirmResult;
+use crate::ui::picker::{self, PickerMode};
+use crate::ui::popup::delete::{DeleteConfirmResult, DeleteConfirmState};
 use crate::ui::popup::{
-    PopupType, about, action_history, add_entry, bookmark, delete, exit, file_drop,
-    generic_message, open_with as open_with_popup, plugin, preview as popup_preview, sort_toggle,
-    teleport, theme,
+    PopupType, about, action_history, add_entry, archive_password, bookmark, checksum,
+    content_search, delete, duplicates, encryption, exit, fan_out_paste, file_drop, file_history,
+    generic_message, jump_marks, lan_receive, lan_send, open_with as open_with_popup, pdf_password,
+    plugin, preview as popup_preview, profile, properties, session, share, sort_toggle, split,
+    teleport, theme, touch, trash,
 };
+use crate::ui::preview::links::LinkTarget;
 use crate::ui::rename::Rename;
 use crate::ui::search_bar::{self, SearchBar};
 use crate::ui::separator;
@@ -29,7 +40,12 @@ use crate::ui::separator::SEPARATOR_PADDING;
 use crate::ui::terminal;
 use crate::ui::top_banner;
 use crate::ui::update;
-use crate::ui::{center_panel, help_window, left_panel, notification, preview, right_panel};
+use crate::ui::{
+    center_panel, dual_pane, help_window, left_panel, notification, preview, right_panel,
+};
+use crate::utils::format::format_count;
+use crate::utils::link::{self, LinkKind};
+use crate::utils::transfer::TransferKind;
 use crate::visit_history::{self, VisitHistoryEntry};
 
 /// Error type for Kiorg application
@@ -85,14 +101,20 @@ pub enum Clipboard {
 
 // Constants
 const STATE_FILE_NAME: &str = "state.json";
+// How often the app state is saved while running, so a crash loses little
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
 
 // Layout constants
 const PANEL_SPACING: f32 = 5.0; // Space between panels
 
+/// Paths changed in the watched directory since they were last handled
+type ChangedPaths = Arc<Mutex<Vec<PathBuf>>>;
+
 fn create_fs_watcher(
     watch_dir: &Path,
-) -> Result<(notify::RecommendedWatcher, Arc<AtomicBool>), std::io::Error> {
+) -> Result<(notify::RecommendedWatcher, Arc<AtomicBool>, ChangedPaths), std::io::Error> {
     let notify_fs_change = Arc::new(AtomicBool::new(false));
+    let changed_paths = ChangedPaths::default();
     let (tx, rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
 
     let mut fs_watcher = match notify::recommended_watcher(tx) {
@@ -105,6 +127,7 @@ fn create_fs_watcher(
     }
 
     let notify_fs_change_clone = notify_fs_change.clone();
+    let changed_paths_clone = changed_paths.clone();
     std::thread::spawn(move || {
         loop {
             for res in &rx {
@@ -113,6 +136,9 @@ fn create_fs_watcher(
                         notify::EventKind::Remove(_)
                         | notify::EventKind::Modify(_)
                         | notify::EventKind::Create(_) => {
+                            if let Ok(mut paths) = changed_paths_clone.lock() {
+                                paths.extend(event.paths);
+                            }
                             notify_fs_change_clone
                                 .store(true, std::sync::atomic::Ordering::Relaxed);
                         }
@@ -126,7 +152,7 @@ fn create_fs_watcher(
         }
     });
 
-    Ok((fs_watcher, notify_fs_change))
+    Ok((fs_watcher, notify_fs_change, changed_paths))
 }
 
 /// Returns the fallback directory path to use when no valid path is available.
@@ -139,15 +165,46 @@ fn fallback_initial_dir() -> PathBuf {
 #[derive(Serialize, Deserialize)]
 pub struct AppState {
     pub tab_manager: TabManagerState,
+    #[serde(default)]
+    pub window: Option<WindowState>,
+    // Additional windows open when the app exited
+    #[serde(default)]
+    pub windows: Vec<SecondaryWindowState>,
+    // Vim style marks set with `m{a-z}`
+    #[serde(default)]
+    pub jump_marks: JumpMarks,
     // Add more fields here in the future
 }
 
+/// Saved tabs and geometry of an additional window
+#[derive(Serialize, Deserialize)]
+pub struct SecondaryWindowState {
+    pub tab_manager: TabManagerState,
+    pub window: WindowState,
+}
+
+/// An additional window with its own tabs, drawn as an immediate viewport of the main window
+pub struct SecondaryWindow {
+    pub viewport_id: egui::ViewportId,
+    // Built once, changing it every frame would resize the window
+    builder: egui::ViewportBuilder,
+    pub app: Box<Kiorg>,
+}
+
 pub struct Kiorg {
     // Tab manager for file navigation
     pub tab_manager: TabManager,
     // Fields moved from AppState
-    pub bookmarks: Vec<PathBuf>,
+    pub bookmarks: Vec<crate::models::bookmark::Bookmark>,
+    // Positions saved under a letter, shared by all windows
+    pub jump_marks: JumpMarks,
+    // Cloud storage folders shown below the bookmarks, detected when the popup opens
+    pub cloud_folders: Vec<crate::utils::cloud::CloudFolder>,
     pub config_dir_override: Option<PathBuf>,
+    // Active profile, `None` for the default profile
+    pub profile: Option<String>,
+    // Named session last saved or restored in this window
+    pub session: Option<String>,
     // Application configuration
     pub config: config::Config,
     // Merged shortcuts (defaults + user overrides) for runtime use
@@ -162,6 +219,10 @@ pub struct Kiorg {
     pub prev_path: Option<PathBuf>, // Previous path for selection preservation
     pub cached_preview_path: Option<PathBuf>,
     pub preview_content: Option<PreviewContent>,
+    // Recursive size scan of the selected directory
+    pub dir_size_scan: Option<crate::ui::preview::directory::DirSizeScan>,
+    // Checksums of the selected file, computed on demand
+    pub checksum_job: Option<crate::ui::preview::checksum::ChecksumJob>,
     // fields that get reset after changing directories
     // TODO: will it crash the app if large amount of entries are deleted in the same dir?
     pub scroll_range: Option<std::ops::Range<usize>>,
@@ -169,8 +230,10 @@ pub struct Kiorg {
     pub show_popup: Option<PopupType>,
     pub clipboard: Option<Clipboard>,
     pub search_bar: SearchBar,
+    pub type_ahead: crate::ui::type_ahead::TypeAhead,
     pub terminal_ctx: Option<terminal::TerminalContext>,
     pub notify_fs_change: Arc<AtomicBool>,
+    fs_changed_paths: ChangedPaths,
     pub fs_watcher: notify::RecommendedWatcher,
     // Track files that are currently being opened
     pub files_being_opened: HashMap<PathBuf, Arc<AtomicBool>>,
@@ -183,6 +246,8 @@ pub struct Kiorg {
     pub scroll_left_panel: bool,
     // Global visit history tracking
     pub visit_history: HashMap<PathBuf, VisitHistoryEntry>,
+    // Files opened or previewed from kiorg
+    pub file_history: HashMap<PathBuf, VisitHistoryEntry>,
     // Async history saver for non-blocking save operations
     pub history_saver: visit_history::HistorySaver,
     // Drag and drop state - currently dragged file
@@ -191,6 +256,49 @@ pub struct Kiorg {
     pub plugin_manager: crate::plugins::PluginManager,
     // Inline rename
     pub inline_rename: Option<Rename>,
+    // Larger hit targets and drag to scroll for touchscreens
+    pub touch_mode: bool,
+    // Preview only static metadata of all files, see `config::Preview::safe_mode`
+    pub safe_preview: bool,
+    // Window geometry saved in the app state file
+    pub window_state: WindowState,
+    // Whether the restored window still needs to be checked against the monitor
+    window_fit_pending: bool,
+    // Whether this is the main window, which owns the plugins and the app state file
+    pub main_window: bool,
+    // Additional windows, only used by the main window
+    pub secondary_windows: Vec<SecondaryWindow>,
+    // Set to open another window, handled by the main window
+    pub new_window_requested: bool,
+    // Set when an additional window was closed and should be dropped
+    closed: bool,
+    // Compact file chooser layout, see `kiorg --pick`
+    pub picker: Option<PickerMode>,
+    // Where to write the current directory on quit, see `kiorg --choose-dir`
+    pub choose_dir: Option<crate::utils::choose_dir::ChooseDirOutput>,
+    // Sends and receives files over the local network, started on demand
+    pub lan: Option<crate::lan::LanService>,
+    // Long-running background operations, e.g. plugin bulk operations
+    pub operations: Vec<crate::ui::operations::Operation>,
+    // Pasted files being copied or moved in the background
+    pub transfers: crate::utils::transfer::TransferQueue,
+    // Show the recursive size of directories in the size column
+    pub show_dir_sizes: bool,
+    // Recursive directory sizes computed this session
+    pub dir_sizes: crate::utils::dir_size::DirSizeCache,
+    // Second file list shown instead of the preview panel
+    pub dual_pane: Option<crate::ui::dual_pane::DualPane>,
+    // Recursive listing of the current directory, shown instead of its entries
+    pub flatten: Option<crate::utils::flatten::FlattenWalk>,
+    // Path to select once the flattened listing found it
+    flatten_selection: Option<PathBuf>,
+    // Rest of a huge current directory, listed in the background
+    pub dir_stream: Option<crate::utils::dir_stream::DirStream>,
+    // Path to select once the directory listing found it
+    dir_stream_selection: Option<PathBuf>,
+    // App state written by the last autosave, to skip unchanged writes
+    autosaved_state: Option<String>,
+    last_autosave: Instant,
 }
 
 impl Kiorg {
@@ -198,6 +306,52 @@ impl Kiorg {
         cc: &eframe::CreationContext<'_>,
         initial_dir: Option<PathBuf>,
         config_dir_override: Option<PathBuf>,
+    ) -> Result<Self, KiorgError> {
+        let mut app = Self::create(&cc.egui_ctx, initial_dir, config_dir_override, None)?;
+        if let Some(window_state) = Self::load_window_state(app.config_dir_override.as_deref()) {
+            app.window_state = window_state;
+            app.window_fit_pending = true;
+        }
+        app.restore_secondary_windows(&cc.egui_ctx);
+        if app.config.lan.as_ref().and_then(|lan| lan.receive) == Some(true) {
+            app.lan_service(&cc.egui_ctx);
+        }
+        Ok(app)
+    }
+
+    /// Create the app of `kiorg --pick`
+    ///
+    /// The picker window doesn't restore or save the app state so it doesn't
+    /// interfere with the regular windows.
+    pub fn new_picker(
+        cc: &eframe::CreationContext<'_>,
+        initial_dir: Option<PathBuf>,
+        config_dir_override: Option<PathBuf>,
+        picker: PickerMode,
+    ) -> Result<Self, KiorgError> {
+        let mut app = Self::create(&cc.egui_ctx, initial_dir, config_dir_override, None)?;
+        // Start typing the file name right away when saving, otherwise search
+        if picker.save_name.is_some() {
+            picker::focus_save_name(&cc.egui_ctx);
+        } else {
+            app.search_bar.activate();
+        }
+        app.picker = Some(PickerMode {
+            standalone: true,
+            ..picker
+        });
+        Ok(app)
+    }
+
+    /// Create the app of a window
+    ///
+    /// Plugins are loaded by the main window and shared with additional windows
+    /// through `plugin_manager`.
+    fn create(
+        ctx: &egui::Context,
+        initial_dir: Option<PathBuf>,
+        config_dir_override: Option<PathBuf>,
+        plugin_manager: Option<crate::plugins::PluginManager>,
     ) -> Result<Self, KiorgError> {
         let config = config::load_config_with_override(config_dir_override.as_deref())?;
 
@@ -231,7 +385,20 @@ impl Kiorg {
 
         // Load colors based on theme name from config
         let colors = crate::theme::Theme::load_colors_from_config(&config);
-        cc.egui_ctx.set_visuals(colors.to_visuals());
+        ctx.set_visuals(colors.to_visuals());
+
+        let touch_mode = config.mouse.as_ref().is_some_and(|m| m.touch_mode);
+        let show_dir_sizes = config
+            .layout
+            .as_ref()
+            .and_then(|layout| layout.dir_sizes)
+            .unwrap_or(false);
+        crate::ui::style::apply_touch_mode(ctx, touch_mode);
+        let safe_preview = config
+            .preview
+            .as_ref()
+            .is_some_and(|p| p.safe_mode.unwrap_or(false));
+        crate::utils::format::set_number_format(config.number_format());
 
         // Determine the initial path and tab manager
         let (tab_manager, initial_path) = match initial_dir {
@@ -277,12 +444,14 @@ impl Kiorg {
             }
         };
 
-        let (fs_watcher, notify_fs_change) = match create_fs_watcher(initial_path.as_path()) {
-            Ok(watcher) => watcher,
-            Err(e) => return Err(KiorgError::WatcherError(e.to_string())),
-        };
+        let (fs_watcher, notify_fs_change, fs_changed_paths) =
+            match create_fs_watcher(initial_path.as_path()) {
+                Ok(watcher) => watcher,
+                Err(e) => return Err(KiorgError::WatcherError(e.to_string())),
+            };
 
         let bookmarks = bookmark::load_bookmarks(config_dir_override.as_deref());
+        let jump_marks = Self::load_jump_marks(config_dir_override.as_deref());
 
         // Load visit history
         let visit_history = visit_history::load_visit_history(config_dir_override.as_deref())
@@ -290,6 +459,11 @@ impl Kiorg {
                 tracing::error!(err =? e, "Failed to load visit history");
                 HashMap::new()
             });
+        let file_history = visit_history::load_file_history(config_dir_override.as_deref())
+            .unwrap_or_else(|e| {
+                tracing::error!(err =? e, "Failed to load file history");
+                HashMap::new()
+            });
 
         // Create async notification system
         let notification_system = notification::AsyncNotification::default();
@@ -298,54 +472,221 @@ impl Kiorg {
         let history_saver = visit_history::HistorySaver::new();
 
         // Initialize plugin system
-        let mut plugin_manager = crate::plugins::PluginManager::new(config_dir_override.as_deref());
-        match plugin_manager.load_plugins() {
-            Ok(()) => {
-                let loaded_plugins = plugin_manager.list_loaded();
-                tracing::info!("Loaded {} plugins", loaded_plugins.len());
-                tracing::debug!("Loaded plugin: {:?}", loaded_plugins.keys());
-            }
-            Err(e) => {
-                tracing::error!("Failed to load plugins: {}", e);
+        let main_window = plugin_manager.is_none();
+        let plugin_manager = plugin_manager.unwrap_or_else(|| {
+            let mut plugin_manager =
+                crate::plugins::PluginManager::new(config_dir_override.as_deref());
+            match plugin_manager.load_plugins() {
+                Ok(()) => {
+                    let loaded_plugins = plugin_manager.list_loaded();
+                    tracing::info!("Loaded {} plugins", loaded_plugins.len());
+                    tracing::debug!("Loaded plugin: {:?}", loaded_plugins.keys());
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load plugins: {}", e);
+                }
             }
-        }
+            plugin_manager
+        });
+
+        let profile = config::profile::name(&config::get_kiorg_config_dir(
+            config_dir_override.as_deref(),
+        ));
 
         let mut app = Self {
             tab_manager,
             bookmarks,
+            jump_marks,
+            cloud_folders: Vec::new(),
             config_dir_override, // Use the provided config_dir_override
             config,              // Store the loaded config
             merged_shortcuts,    // Initialize merged_shortcuts
             colors,              // Add the colors field here
+            profile,
+            session: None,
             toasts: Toasts::default().with_anchor(crate::ui::egui_notify::Anchor::BottomLeft),
             selection_changed: true,
             ensure_selected_visible: false,
             prev_path: None,
             cached_preview_path: None,
             preview_content: None,
+            dir_size_scan: None,
+            checksum_job: None,
             scroll_range: None,
             show_popup: None,
             clipboard: None,
             search_bar: SearchBar::new(),
+            type_ahead: crate::ui::type_ahead::TypeAhead::default(),
             files_being_opened: HashMap::new(),
             notification_system,
             key_buffer: Vec::new(),
             terminal_ctx: None,
             shutdown_requested: false,
             notify_fs_change,
+            fs_changed_paths,
             scroll_left_panel: false,
             fs_watcher,
             visit_history,
+            file_history,
             history_saver,
             dragged_file: None,
             plugin_manager,
             inline_rename: None,
+            touch_mode,
+            safe_preview,
+            window_state: WindowState::default(),
+            window_fit_pending: false,
+            main_window,
+            secondary_windows: Vec::new(),
+            new_window_requested: false,
+            closed: false,
+            picker: None,
+            choose_dir: None,
+            lan: None,
+            operations: Vec::new(),
+            transfers: {
+                let ctx = ctx.clone();
+                crate::utils::transfer::TransferQueue::new(move || ctx.request_repaint())
+            },
+            show_dir_sizes,
+            dual_pane: None,
+            flatten: None,
+            flatten_selection: None,
+            dir_stream: None,
+            dir_stream_selection: None,
+            dir_sizes: {
+                let ctx = ctx.clone();
+                crate::utils::dir_size::DirSizeCache::new(move || ctx.request_repaint())
+            },
+            autosaved_state: None,
+            last_autosave: Instant::now(),
         };
 
         app.refresh_entries();
         Ok(app)
     }
 
+    /// Switch to another profile, `None` being the default profile
+    ///
+    /// The app is recreated with the config and state of the profile after
+    /// saving the state of the current one, creating the profile if needed.
+    pub fn switch_profile(&mut self, ctx: &egui::Context, profile: Option<String>) {
+        if !self.main_window {
+            self.notify_error("Profiles can only be switched from the main window");
+            return;
+        }
+        let config_dir = config::get_kiorg_config_dir(self.config_dir_override.as_deref());
+        let base_dir = config::profile::base_dir(&config_dir);
+        let config_dir = match &profile {
+            Some(name) => config::profile::config_dir(&base_dir, name),
+            None => base_dir,
+        };
+
+        if let Err(errors) = config::migration::run_migrations(Some(&config_dir)) {
+            for e in errors {
+                tracing::error!("Failed to upgrade config file: {e}");
+            }
+        }
+        let mut app = match Self::create(ctx, None, Some(config_dir), None) {
+            Ok(app) => app,
+            Err(e) => {
+                // Creating the app may have applied the theme of the other profile
+                ctx.set_visuals(self.colors.to_visuals());
+                crate::ui::style::apply_touch_mode(ctx, self.touch_mode);
+                self.notify_error(format!("Failed to switch profile: {e}"));
+                return;
+            }
+        };
+
+        app.choose_dir = self.choose_dir.take();
+        self.graceful_shutdown();
+        app.window_state = self.window_state.clone();
+        app.restore_secondary_windows(ctx);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(config::profile::window_title(
+            app.profile.as_deref(),
+        )));
+        app.notify_info(format!(
+            "Switched to profile {}",
+            app.profile
+                .as_deref()
+                .unwrap_or(config::profile::DEFAULT_PROFILE_NAME)
+        ));
+        *self = app;
+    }
+
+    /// Save the tabs and marks of this window as the session `name`
+    pub fn save_session(&mut self, name: &str) {
+        let config_dir = config::get_kiorg_config_dir(self.config_dir_override.as_deref());
+        let session = config::session::Session {
+            tab_manager: self.tab_manager.to_state(),
+            jump_marks: self.jump_marks.clone(),
+        };
+        match config::session::save(&config_dir, name, &session) {
+            Ok(()) => {
+                self.session = Some(name.to_string());
+                self.notify_success(format!("Saved session {name}"));
+            }
+            Err(e) => self.notify_error(format!("Failed to save session: {e}")),
+        }
+    }
+
+    /// Replace the tabs and marks of this window with the session `name`
+    ///
+    /// Sort and hidden file preferences remembered per directory are kept.
+    pub fn restore_session(&mut self, name: &str) {
+        let config_dir = config::get_kiorg_config_dir(self.config_dir_override.as_deref());
+        let session = match config::session::load(&config_dir, name) {
+            Ok(session) => session,
+            Err(e) => {
+                self.notify_error(format!("Failed to restore session: {e}"));
+                return;
+            }
+        };
+        let mut tab_manager = TabManager::from_state(session.tab_manager);
+        let path = tab_manager.current_tab_ref().current_path.clone();
+        if !path.is_dir() {
+            self.notify_error(format!(
+                "Failed to restore session: '{}' doesn't exist anymore",
+                path.display()
+            ));
+            return;
+        }
+        tab_manager.dir_prefs = std::mem::take(&mut self.tab_manager.dir_prefs);
+        self.tab_manager = tab_manager;
+        self.jump_marks = session.jump_marks;
+        self.session = Some(name.to_string());
+        self.navigate_to_dir_without_history(path);
+        self.notify_info(format!("Restored session {name}"));
+    }
+
+    /// LAN service of this window, started on first use
+    ///
+    /// Only receives files when enabled in the `[lan]` config section.
+    pub fn lan_service(&mut self, ctx: &egui::Context) -> Option<&crate::lan::LanService> {
+        if self.lan.is_none() {
+            let lan_config = self.config.lan.clone().unwrap_or_default();
+            let name = lan_config
+                .device_name
+                .unwrap_or_else(crate::lan::default_device_name);
+            let receive_dir = lan_config.receive.unwrap_or(false).then(|| {
+                lan_config
+                    .receive_dir
+                    .or_else(dirs::download_dir)
+                    .unwrap_or_else(fallback_initial_dir)
+            });
+            match crate::lan::LanService::start(
+                ctx,
+                &name,
+                receive_dir,
+                self.notification_system.get_sender(),
+            ) {
+                Ok(lan) => self.lan = Some(lan),
+                Err(e) => self.notify_error(e),
+            }
+        }
+        self.lan.as_ref()
+    }
+
     /// Display an error notification with a consistent timeout
     pub fn notify_error<T: ToString>(&mut self, message: T) {
         notification::notify_error(&mut self.toasts, message);
@@ -366,6 +707,22 @@ impl Kiorg {
         notification::check_notifications(self);
     }
 
+    /// Remember the zoom mode the preview popup opens `content` with
+    fn save_zoom_mode(&mut self, content: ZoomContent, mode: ZoomMode) {
+        if self.config.zoom_mode(content) == mode {
+            return;
+        }
+        self.config
+            .preview
+            .get_or_insert_default()
+            .set_zoom_mode(content, mode);
+        if let Err(e) =
+            config::save_config_with_override(&self.config, self.config_dir_override.as_deref())
+        {
+            self.notify_error(format!("Failed to save preview settings: {e}"));
+        }
+    }
+
     pub fn poll_preview_content(&mut self, ctx: &egui::Context) {
         // Handle preview content loading
         let receiver = match &self.preview_content {
@@ -476,12 +833,67 @@ impl Kiorg {
     }
 
     pub fn refresh_entries(&mut self) {
-        self.tab_manager.refresh_entries();
+        // A flattened listing stays on in its directory and is listed again
+        let flatten_selection = self.flatten.as_ref().and_then(|walk| {
+            let tab = self.tab_manager.current_tab_ref();
+            (walk.root == tab.current_path).then(|| {
+                self.prev_path
+                    .clone()
+                    .or_else(|| tab.selected_entry().map(|entry| entry.meta.path.clone()))
+            })
+        });
+        let rest = self
+            .tab_manager
+            .load_entries(crate::utils::dir_stream::INITIAL_ENTRIES);
+        self.dir_stream = None;
+        self.dir_stream_selection = None;
+        match flatten_selection {
+            Some(selection) => self.start_flatten(selection),
+            None => {
+                self.flatten = None;
+                // Huge directories show their first entries while the rest is read
+                self.dir_stream = rest.map(|read_dir| {
+                    crate::utils::dir_stream::DirStream::start(
+                        self.tab_manager.current_tab_ref().current_path.clone(),
+                        read_dir,
+                        self.tab_manager.show_hidden,
+                    )
+                });
+                if self.dir_stream.is_none() {
+                    self.tab_manager
+                        .current_tab_mut()
+                        .reconcile_marked_entries();
+                }
+            }
+        }
+        // The other pane may show the same directory
+        if let Some(other) = crate::ui::dual_pane::other_tab(self) {
+            self.tab_manager.refresh_tab_entries(other);
+        }
         // tab_manager.refresh_entries() will refresh both parent and current directory entries
         // so always refocus left panel after refresh
         self.scroll_left_panel = true;
 
         // Restore search filter if it was active before refresh
+        self.reapply_search_filter();
+
+        // --- Start: Restore Selection Preservation (Post-Sort) ---
+        if let Some(prev_path) = &self.prev_path
+            && !self.tab_manager.select_child(prev_path)
+            && self.dir_stream.is_some()
+        {
+            self.dir_stream_selection = Some(prev_path.clone());
+        }
+        self.selection_changed = true;
+        // Clear prev_path after attempting to use it
+        self.prev_path = None;
+
+        // Always ensure selection is visible and invalidate preview cache
+        self.ensure_selected_visible = true;
+        self.cached_preview_path = None; // Invalidate preview cache
+    }
+
+    fn reapply_search_filter(&mut self) {
         if self.search_bar.query.is_some() {
             let case_insensitive = self.search_bar.case_insensitive;
             let tab = self.tab_manager.current_tab_mut();
@@ -490,19 +902,145 @@ impl Kiorg {
                 case_insensitive,
                 self.search_bar.fuzzy,
             );
+        } else if self.type_ahead.active() {
+            self.tab_manager.current_tab_mut().update_filtered_cache(
+                &self.type_ahead.query,
+                true,
+                false,
+            );
         }
+    }
 
-        // --- Start: Restore Selection Preservation (Post-Sort) ---
-        if let Some(prev_path) = &self.prev_path {
-            self.tab_manager.select_child(prev_path);
+    /// List every file under the current directory instead of its entries,
+    /// or go back to the regular listing
+    pub fn toggle_flatten(&mut self) {
+        let tab = self.tab_manager.current_tab_ref();
+        let selected = tab.selected_entry().map(|entry| entry.meta.path.clone());
+        if let Some(walk) = self.flatten.take() {
+            // Select the entry of the directory the selected file is in
+            self.prev_path = selected.and_then(|path| {
+                let first = path.strip_prefix(&walk.root).ok()?.components().next()?;
+                Some(walk.root.join(first))
+            });
+            self.refresh_entries();
+            return;
         }
+        self.start_flatten(selected);
+        self.notify_info("Listing files recursively");
+    }
+
+    /// (Re)start the flattened listing of the current directory, selecting
+    /// `selection` once it's listed
+    fn start_flatten(&mut self, selection: Option<PathBuf>) {
+        let root = self.tab_manager.current_tab_ref().current_path.clone();
+        self.flatten = Some(crate::utils::flatten::FlattenWalk::start(
+            root,
+            self.tab_manager.show_hidden,
+        ));
+        self.flatten_selection = selection;
+        self.tab_manager.clear_entries();
         self.selection_changed = true;
-        // Clear prev_path after attempting to use it
-        self.prev_path = None;
+    }
 
-        // Always ensure selection is visible and invalidate preview cache
-        self.ensure_selected_visible = true;
-        self.cached_preview_path = None; // Invalidate preview cache
+    /// Add the entries of a huge directory read since the last frame
+    fn poll_dir_stream(&mut self, ctx: &egui::Context) {
+        let Some(stream) = &mut self.dir_stream else {
+            return;
+        };
+        // The listing belongs to a directory the tab has since left
+        if stream.dir != self.tab_manager.current_tab_ref().current_path {
+            self.dir_stream = None;
+            self.dir_stream_selection = None;
+            return;
+        }
+        let entries = stream.poll();
+        let done = stream.done;
+        if done {
+            self.dir_stream = None;
+            self.dir_stream_selection = None;
+        } else {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        if entries.is_empty() {
+            if done {
+                self.tab_manager
+                    .current_tab_mut()
+                    .reconcile_marked_entries();
+            }
+            return;
+        }
+
+        let selected = self
+            .tab_manager
+            .current_tab_ref()
+            .selected_entry()
+            .map(|entry| entry.meta.path.clone());
+        self.tab_manager.extend_entries(entries);
+        self.reapply_search_filter();
+        if let Some(path) = self.dir_stream_selection.clone()
+            && self.tab_manager.select_child(&path)
+        {
+            self.dir_stream_selection = None;
+        }
+        if done {
+            self.tab_manager
+                .current_tab_mut()
+                .reconcile_marked_entries();
+        }
+        let tab = self.tab_manager.current_tab_ref();
+        if tab.selected_entry().map(|entry| &entry.meta.path) != selected.as_ref() {
+            self.selection_changed = true;
+            self.ensure_selected_visible = true;
+        }
+    }
+
+    /// Add the files the flattened listing found since the last frame
+    fn poll_flatten(&mut self, ctx: &egui::Context) {
+        let Some(walk) = &mut self.flatten else {
+            return;
+        };
+        let was_done = walk.done;
+        let entries = walk.poll();
+        if !walk.done {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        let finished_message = (!was_done && walk.done).then(|| {
+            if walk.is_truncated() {
+                format!(
+                    "Showing the first {} files",
+                    format_count(crate::utils::flatten::MAX_ENTRIES as u64)
+                )
+            } else {
+                format!("Listed {} files", format_count(walk.count as u64))
+            }
+        });
+
+        if !entries.is_empty() {
+            let selected = self
+                .tab_manager
+                .current_tab_ref()
+                .selected_entry()
+                .map(|entry| entry.meta.path.clone());
+            self.tab_manager.extend_entries(entries);
+            self.reapply_search_filter();
+            if let Some(index) = self
+                .flatten_selection
+                .as_deref()
+                .and_then(|path| self.tab_manager.get_entry_index_by_path(path))
+            {
+                self.flatten_selection = None;
+                self.set_selection(index);
+            }
+            let tab = self.tab_manager.current_tab_ref();
+            if tab.selected_entry().map(|entry| &entry.meta.path) != selected.as_ref() {
+                self.selection_changed = true;
+                self.ensure_selected_visible = true;
+            }
+        }
+        if let Some(message) = finished_message {
+            self.flatten_selection = None;
+            self.notify_info(message);
+        }
     }
 
     pub fn set_selection(&mut self, index: usize) {
@@ -515,7 +1053,18 @@ impl Kiorg {
         self.selection_changed = true;
     }
 
+    /// Ask to move the marked entries, or the selected one, to the trash
     pub fn delete_selected_entry(&mut self) {
+        self.confirm_delete_selected_entries(DeleteConfirmState::Trash);
+    }
+
+    /// Ask to delete the marked entries, or the selected one, bypassing the
+    /// trash
+    pub fn delete_selected_entry_permanently(&mut self) {
+        self.confirm_delete_selected_entries(DeleteConfirmState::Initial);
+    }
+
+    fn confirm_delete_selected_entries(&mut self, state: DeleteConfirmState) {
         let tab = self.tab_manager.current_tab_mut();
 
         if tab.is_range_selection_active() {
@@ -534,19 +1083,71 @@ impl Kiorg {
             return;
         };
 
-        self.show_popup = Some(PopupType::Delete(
-            crate::ui::popup::delete::DeleteConfirmState::Initial,
-            entries_to_delete,
-        ));
+        self.show_popup = Some(PopupType::Delete(state, entries_to_delete));
+    }
+
+    /// Paths of `count` entries starting at the selected one, in the order
+    /// they're shown
+    pub fn entries_from_selection(&self, count: usize) -> Vec<PathBuf> {
+        let tab = self.tab_manager.current_tab_ref();
+        let entries = tab.get_cached_filtered_entries();
+        let Some(start) = entries
+            .iter()
+            .position(|index| *index == tab.selected_index)
+        else {
+            return Vec::new();
+        };
+        entries[start..]
+            .iter()
+            .take(count)
+            .filter_map(|index| tab.entries.get(*index))
+            .map(|entry| entry.meta.path.clone())
+            .collect()
+    }
+
+    /// Ask to trash `count` entries starting at the selected one, as `3d`
+    /// does, or to delete them permanently
+    pub fn delete_entries_from_selection(&mut self, count: usize, permanently: bool) {
+        let entries_to_delete = self.entries_from_selection(count);
+        if entries_to_delete.is_empty() {
+            return;
+        }
+        let state = if permanently {
+            DeleteConfirmState::Initial
+        } else {
+            DeleteConfirmState::Trash
+        };
+        self.show_popup = Some(PopupType::Delete(state, entries_to_delete));
+    }
+
+    /// Select the entry shown at 1-based `number`, or the last one
+    pub fn go_to_entry_number(&mut self, number: usize) {
+        self.record_jump();
+        let tab = self.tab_manager.current_tab_mut();
+        let entries = tab.get_cached_filtered_entries();
+        let Some(&index) = entries
+            .get(number.saturating_sub(1))
+            .or_else(|| entries.last())
+        else {
+            return;
+        };
+        tab.update_selection(index);
+        self.ensure_selected_visible = true;
+        self.selection_changed = true;
     }
 
     pub fn rename_selected_entry(&mut self) {
         let tab = self.tab_manager.current_tab_mut();
         if let Some(entry) = tab.selected_entry() {
+            // Flattened entries are named by their relative path
+            let name = entry.meta.path.file_name().map_or_else(
+                || entry.name.clone(),
+                |name| name.to_string_lossy().into_owned(),
+            );
             self.inline_rename = Some(Rename {
                 original_index: tab.selected_index,
-                original_name: entry.name.clone(),
-                new_name: entry.name.clone(),
+                original_name: name.clone(),
+                new_name: name,
             });
         }
     }
@@ -588,6 +1189,166 @@ impl Kiorg {
         self.inline_rename = None;
     }
 
+    /// Queue copying or moving the clipboard entries into `dest_dir`
+    ///
+    /// The paste runs in the background, the operations panel records it in
+    /// the action history and refreshes the entries once it finished.
+    /// Returns false if the clipboard is empty.
+    pub fn paste_clipboard(&mut self, dest_dir: PathBuf) -> bool {
+        let (kind, paths) = match self.clipboard.take() {
+            Some(Clipboard::Copy(paths)) => (TransferKind::Copy, paths),
+            Some(Clipboard::Cut(paths)) => (TransferKind::Move, paths),
+            None => return false,
+        };
+        self.transfers.enqueue(kind, paths, dest_dir);
+        true
+    }
+
+    /// Marked directories of the current tab a copied clipboard can be pasted into
+    ///
+    /// Returns `None` unless several entries are marked and all of them are
+    /// directories, moving only ever targets a single directory.
+    pub fn fan_out_paste_dirs(&self) -> Option<Vec<PathBuf>> {
+        if !matches!(self.clipboard, Some(Clipboard::Copy(_))) {
+            return None;
+        }
+        let marked = &self.tab_manager.current_tab_ref().marked_entries;
+        if marked.len() < 2 || !marked.iter().all(|path| path.is_dir()) {
+            return None;
+        }
+        let mut dirs: Vec<PathBuf> = marked.iter().cloned().collect();
+        dirs.sort();
+        Some(dirs)
+    }
+
+    /// Queue copying the clipboard entries into each of `dest_dirs`
+    ///
+    /// Every destination is a separate job with its own progress row in the
+    /// operations panel. Returns false unless the clipboard holds copied entries.
+    pub fn paste_clipboard_into_each(&mut self, dest_dirs: Vec<PathBuf>) -> bool {
+        let Some(Clipboard::Copy(paths)) = &self.clipboard else {
+            return false;
+        };
+        let paths = paths.clone();
+        self.clipboard = None;
+        for dest_dir in dest_dirs {
+            self.transfers
+                .enqueue(TransferKind::Copy, paths.clone(), dest_dir);
+        }
+        true
+    }
+
+    /// Link the clipboard entries into `dest_dir` instead of copying them
+    ///
+    /// The clipboard is kept so the same entries can be linked elsewhere.
+    /// Returns false if the clipboard is empty.
+    pub fn paste_clipboard_as_link(&mut self, dest_dir: PathBuf, kind: LinkKind) -> bool {
+        let paths = match &self.clipboard {
+            Some(Clipboard::Copy(paths) | Clipboard::Cut(paths)) => paths.clone(),
+            None => return false,
+        };
+        let summary = link::link_entries(kind, &paths, &dest_dir);
+        self.record_created_links(&dest_dir, &summary.created);
+        if !summary.errors.is_empty() {
+            self.notify_error(format!(
+                "Failed to create {}: {}",
+                kind.name(),
+                summary.errors.join("; ")
+            ));
+        }
+        true
+    }
+
+    /// Create a symlink to the selected entry next to it and start renaming it
+    pub fn create_symlink_to_selected(&mut self) {
+        let tab = self.tab_manager.current_tab_ref();
+        let Some(entry) = tab.selected_entry() else {
+            return;
+        };
+        let target = entry.meta.path.clone();
+        let dir = tab.current_path.clone();
+        let summary = link::link_entries(LinkKind::Symlink, std::slice::from_ref(&target), &dir);
+        if let Some(error) = summary.errors.first() {
+            self.notify_error(format!("Failed to create symlink: {error}"));
+            return;
+        }
+        self.record_created_links(&dir, &summary.created);
+        self.rename_selected_entry();
+    }
+
+    /// Select the entry the selected symlink points to, in its own directory
+    pub fn follow_selected_symlink(&mut self) {
+        let Some(entry) = self.tab_manager.current_tab_ref().selected_entry() else {
+            return;
+        };
+        let name = entry.name.clone();
+        match link::symlink_target(&entry.meta.path) {
+            Ok((_, Some(resolved))) => {
+                self.record_jump();
+                self.reveal_file(resolved);
+            }
+            Ok((target, None)) => self.notify_error(format!(
+                "Cannot follow '{name}': '{}' doesn't exist",
+                target.display()
+            )),
+            Err(_) => self.notify_error(format!("'{name}' is not a symbolic link")),
+        }
+    }
+
+    /// Show where the selected symlink points to and copy the resolved path
+    pub fn show_selected_link_target(&mut self, ctx: &egui::Context) {
+        let Some(entry) = self.tab_manager.current_tab_ref().selected_entry() else {
+            return;
+        };
+        let name = entry.name.clone();
+        let (target, resolved) = match link::symlink_target(&entry.meta.path) {
+            Ok(target) => target,
+            Err(_) => {
+                self.notify_error(format!("'{name}' is not a symbolic link"));
+                return;
+            }
+        };
+        let message = match resolved {
+            Some(resolved) => {
+                let resolved = resolved.to_string_lossy().into_owned();
+                ctx.output_mut(|o| {
+                    o.commands
+                        .push(egui::OutputCommand::CopyText(resolved.clone()))
+                });
+                format!(
+                    "{name} → {}\n\nResolved path, copied to the clipboard:\n{resolved}",
+                    target.display()
+                )
+            }
+            None => format!("{name} → {}\n\nThe target doesn't exist", target.display()),
+        };
+        self.show_popup = Some(PopupType::GenericMessage(
+            "Link Target".to_string(),
+            message,
+        ));
+    }
+
+    /// Record new links in the action history and select the last one
+    fn record_created_links(&mut self, dir: &Path, links: &[PathBuf]) {
+        let Some(last) = links.last() else {
+            return;
+        };
+        let operations = links
+            .iter()
+            .map(|path| CreateOperation {
+                path: path.clone(),
+                // Removing a directory link never touches the linked directory
+                is_dir: path.is_dir(),
+            })
+            .collect();
+        self.tab_manager
+            .tab_for_path_mut(dir)
+            .action_history
+            .add_action(ActionType::Create { operations });
+        self.prev_path = Some(last.clone());
+        self.refresh_entries();
+    }
+
     /// Common logic for copy/cut operations
     /// Returns the paths to operate on, handling range selection and marked entries
     fn prepare_clipboard_operation(&mut self) -> Vec<PathBuf> {
@@ -642,6 +1403,107 @@ impl Kiorg {
         }
     }
 
+    /// Decompress the selected single compressed file (e.g. `.gz`) into the current directory
+    pub fn decompress_selected_entry(&mut self) {
+        let Some(path) = self
+            .tab_manager
+            .current_tab_ref()
+            .selected_entry()
+            .map(|entry| entry.meta.path.clone())
+        else {
+            return;
+        };
+
+        let notification_sender = self.notification_system.get_sender();
+        std::thread::spawn(move || {
+            let message = match crate::utils::archive::decompress_in_place(&path) {
+                Ok(target) => notification::NotificationMessage::Success(format!(
+                    "Decompressed to {}",
+                    target.file_name().unwrap_or_default().to_string_lossy()
+                )),
+                Err(e) => notification::NotificationMessage::Error(e),
+            };
+            let _ = notification_sender.send(message);
+        });
+    }
+
+    pub fn print_selected_entry(&mut self) {
+        let Some(path) = self
+            .tab_manager
+            .current_tab_ref()
+            .selected_entry()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| entry.meta.path.clone())
+        else {
+            return;
+        };
+
+        let notification_sender = self.notification_system.get_sender();
+        std::thread::spawn(move || {
+            let message = match crate::utils::print::print_file(&path) {
+                Ok(_) => notification::NotificationMessage::Success(format!(
+                    "Sent {} to the printer",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                )),
+                Err(e) => notification::NotificationMessage::Error(e),
+            };
+            let _ = notification_sender.send(message);
+        });
+    }
+
+    /// Set the selected image as wallpaper of the display at `monitor`, or of all displays
+    pub fn set_selected_as_wallpaper(&mut self, monitor: Option<usize>) {
+        let Some(path) = self
+            .tab_manager
+            .current_tab_ref()
+            .selected_entry()
+            .map(|entry| entry.meta.path.clone())
+        else {
+            return;
+        };
+
+        let notification_sender = self.notification_system.get_sender();
+        std::thread::spawn(move || {
+            let message = match crate::utils::wallpaper::set_wallpaper(&path, monitor) {
+                Ok(()) => {
+                    notification::NotificationMessage::Success("Wallpaper updated".to_string())
+                }
+                Err(e) => notification::NotificationMessage::Error(format!(
+                    "Failed to set wallpaper: {e}"
+                )),
+            };
+            let _ = notification_sender.send(message);
+        });
+    }
+
+    /// Download the marked, or selected, online-only files in the background
+    /// and keep them on this device
+    pub fn download_selected_placeholders(&mut self) {
+        let tab = self.tab_manager.current_tab_ref();
+        let mut paths: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+        if paths.is_empty() {
+            paths.extend(tab.selected_entry().map(|entry| entry.meta.path.clone()));
+        }
+        if paths.is_empty() {
+            return;
+        }
+
+        self.toasts.info("Downloading online-only files...");
+        let notification_sender = self.notification_system.get_sender();
+        std::thread::spawn(move || {
+            let message = match crate::utils::cloud::download(&paths) {
+                Ok(0) => notification::NotificationMessage::Info(
+                    "All files are already on this device".to_string(),
+                ),
+                Ok(count) => notification::NotificationMessage::Success(format!(
+                    "Downloaded {count} file(s)"
+                )),
+                Err(e) => notification::NotificationMessage::Error(e),
+            };
+            let _ = notification_sender.send(message);
+        });
+    }
+
     pub fn select_all_entries(&mut self) {
         let tab = self.tab_manager.current_tab_mut();
         tab.marked_entries.clear();
@@ -689,15 +1551,7 @@ impl Kiorg {
 
         // Use the existing cut/move functionality
         self.clipboard = Some(Clipboard::Cut(vec![dragged_item]));
-        let tab = self.tab_manager.current_tab_mut();
-        if crate::ui::center_panel::handle_clipboard_operations(
-            &mut self.clipboard,
-            &target_folder,
-            &mut tab.action_history,
-            &mut self.toasts,
-        ) {
-            self.refresh_entries();
-        }
+        self.paste_clipboard(target_folder);
     }
 
     pub fn move_selection(&mut self, delta: isize) {
@@ -714,12 +1568,14 @@ impl Kiorg {
             .position(|original_index| *original_index == tab.selected_index);
 
         if let Some(current_idx) = current_filtered_index {
-            let new_filtered_index = current_idx as isize + delta;
-
             // Clamp the new index to the bounds of the filtered list
-            if new_filtered_index >= 0 && new_filtered_index < entries.len() as isize {
+            let new_filtered_index = current_idx
+                .saturating_add_signed(delta)
+                .min(entries.len() - 1);
+
+            if new_filtered_index != current_idx {
                 // Get the original index from the new position in the filtered list
-                let new_original_index = entries[new_filtered_index as usize];
+                let new_original_index = entries[new_filtered_index];
                 tab.update_selection(new_original_index);
                 self.ensure_selected_visible = true;
                 self.selection_changed = true;
@@ -786,7 +1642,12 @@ impl Kiorg {
         self.scroll_range = None;
         // Exit range selection mode when changing directories
         tab.range_selection_start = None;
+        // Marks stay behind unless they're staged across directories
+        if !self.config.keep_marks_across_directories() {
+            tab.marked_entries.clear();
+        }
         self.search_bar.close();
+        self.type_ahead.close();
         // Reset filter when closing search bar
         tab.update_filtered_cache(&None, false, false);
 
@@ -814,17 +1675,29 @@ impl Kiorg {
             ));
             return;
         }
+        self.record_jump();
         self.navigate_to_dir_without_history(path.clone());
 
         // Track visit in global history
-        visit_history::update_visit_history(&mut self.visit_history, &path);
-        // Save visit history asynchronously (non-blocking)
-        self.history_saver
-            .save_async(&self.visit_history, self.config_dir_override.as_deref());
+        let history_config = self.config.history.clone().unwrap_or_default();
+        if visit_history::record_visit(&mut self.visit_history, &path, &history_config) {
+            // Save visit history asynchronously (non-blocking)
+            self.history_saver
+                .save_async(&self.visit_history, self.config_dir_override.as_deref());
+        }
 
         self.tab_manager.current_tab_mut().add_to_history(path);
     }
 
+    /// Track a file opened or previewed from kiorg in the file history
+    pub fn record_file_history(&mut self, path: &Path) {
+        let history_config = self.config.history.clone().unwrap_or_default();
+        if visit_history::record_visit(&mut self.file_history, path, &history_config) {
+            self.history_saver
+                .save_files_async(&self.file_history, self.config_dir_override.as_deref());
+        }
+    }
+
     pub fn show_goto_path_popup(&mut self) {
         let mut path = self
             .tab_manager
@@ -840,9 +1713,23 @@ impl Kiorg {
         self.show_popup = Some(crate::ui::popup::PopupType::GoToPath(state));
     }
 
+    pub fn set_touch_mode(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.touch_mode = enabled;
+        crate::ui::style::apply_touch_mode(ctx, enabled);
+        self.ensure_selected_visible = true;
+    }
+
+    pub fn set_safe_preview(&mut self, enabled: bool) {
+        self.safe_preview = enabled;
+        // Reload the preview of the selected file
+        self.cached_preview_path = None;
+        self.selection_changed = true;
+    }
+
     pub fn navigate_history_back(&mut self) {
         let tab = self.tab_manager.current_tab_mut();
         if let Some(path) = tab.history_back() {
+            self.record_jump();
             self.navigate_to_dir_without_history(path);
         }
     }
@@ -850,10 +1737,88 @@ impl Kiorg {
     pub fn navigate_history_forward(&mut self) {
         let tab = self.tab_manager.current_tab_mut();
         if let Some(path) = tab.history_forward() {
+            self.record_jump();
             self.navigate_to_dir_without_history(path);
         }
     }
 
+    /// Current selection position of the current tab
+    fn current_jump(&self) -> Jump {
+        let tab = self.tab_manager.current_tab_ref();
+        Jump {
+            dir: tab.current_path.clone(),
+            selected: tab.selected_entry().map(|entry| entry.meta.path.clone()),
+        }
+    }
+
+    /// Remember the selection before jumping away from it
+    pub fn record_jump(&mut self) {
+        let jump = self.current_jump();
+        self.tab_manager.current_tab_mut().jump_list.record(jump);
+    }
+
+    /// Go back to the selection before the last jump, like Ctrl+O in vim
+    pub fn jump_back(&mut self) {
+        let current = self.current_jump();
+        if let Some(jump) = self.tab_manager.current_tab_mut().jump_list.back(current) {
+            self.go_to_jump(jump);
+        }
+    }
+
+    /// Undo [`Self::jump_back`], like Ctrl+I in vim
+    pub fn jump_forward(&mut self) {
+        if let Some(jump) = self.tab_manager.current_tab_mut().jump_list.forward() {
+            self.go_to_jump(jump);
+        }
+    }
+
+    /// Save the current selection as mark `name`, like `ma` in vim
+    pub fn set_jump_mark(&mut self, name: char) {
+        let jump = self.current_jump();
+        self.jump_marks.set(name, jump);
+        self.notify_info(format!("Set mark '{name}'"));
+    }
+
+    /// Go to the selection saved as mark `name`, like `'a` in vim, or to the
+    /// bookmark with that key when no mark uses it
+    pub fn jump_to_mark(&mut self, name: char) {
+        let jump = self.jump_marks.get(name).cloned().or_else(|| {
+            crate::models::bookmark::find_by_key(&self.bookmarks, name).map(|bookmark| Jump {
+                dir: bookmark.path.clone(),
+                selected: None,
+            })
+        });
+        let Some(jump) = jump else {
+            self.notify_error(format!("Mark '{name}' is not set"));
+            return;
+        };
+        self.record_jump();
+        self.go_to_jump(jump);
+    }
+
+    fn go_to_jump(&mut self, jump: Jump) {
+        if jump.dir != self.tab_manager.current_tab_ref().current_path {
+            if !jump.dir.is_dir() {
+                self.notify_error(format!(
+                    "Cannot jump to '{}': Directory doesn't exist anymore",
+                    jump.dir.display()
+                ));
+                return;
+            }
+            self.navigate_to_dir_without_history(jump.dir.clone());
+            self.tab_manager.current_tab_mut().add_to_history(jump.dir);
+        }
+        let tab = self.tab_manager.current_tab_mut();
+        if let Some(index) = jump
+            .selected
+            .and_then(|selected| tab.get_index_by_path(&selected))
+        {
+            tab.update_selection(index);
+            self.ensure_selected_visible = true;
+            self.selection_changed = true;
+        }
+    }
+
     /// Helper function to handle common file opening logic
     fn open_file_internal<F, E>(&mut self, path: PathBuf, open_fn: F)
     where
@@ -884,6 +1849,7 @@ impl Kiorg {
 
     /// Open a file with the default application
     pub fn open_file(&mut self, path: PathBuf) {
+        self.record_file_history(&path);
         let path_clone = path.clone();
         self.open_file_internal(path, move || {
             open_that(&path_clone).map_err(|e| format!("Failed to open file: {e}"))
@@ -892,6 +1858,7 @@ impl Kiorg {
 
     /// Open a file with a custom command
     pub fn open_file_with_command(&mut self, path: PathBuf, command: String) {
+        self.record_file_history(&path);
         let path_clone = path.clone();
         let command_clone = command.clone();
         self.open_file_internal(path, move || {
@@ -900,16 +1867,99 @@ impl Kiorg {
         });
     }
 
+    /// Open a file with a console application, in a terminal emulator
+    pub fn open_file_in_terminal(&mut self, path: PathBuf, exec: String) {
+        self.record_file_history(&path);
+        let path_clone = path.clone();
+        let emulator = self.config.terminal_emulator().map(str::to_string);
+        self.open_file_internal(path, move || {
+            open_in_terminal(&path_clone, &exec, emulator.as_deref())
+                .map_err(|e| format!("Failed to open file in a terminal with '{exec}': {e}"))
+        });
+    }
+
+    /// Open a terminal window in the selected directory or the current one
+    pub fn open_system_terminal(&mut self) {
+        let tab = self.tab_manager.current_tab_ref();
+        let dir = match tab.selected_entry() {
+            Some(entry) if entry.is_dir => entry.meta.path.clone(),
+            _ => tab.current_path.clone(),
+        };
+        self.open_system_terminal_in(&dir);
+    }
+
+    /// Open a terminal window in `dir` with the configured command
+    pub fn open_system_terminal_in(&mut self, dir: &Path) {
+        if let Err(e) = open_system_terminal(dir, self.config.terminal_command()) {
+            self.notify_error(format!("Failed to open a terminal: {e}"));
+        }
+    }
+
+    /// Follow a link clicked in a text preview
+    ///
+    /// URLs open in the browser. Directories are opened in the current tab,
+    /// files are selected in their parent directory so they get previewed.
+    pub fn open_link(&mut self, target: LinkTarget) {
+        match target {
+            LinkTarget::Url(url) => {
+                let notification_sender = self.notification_system.get_sender();
+                std::thread::spawn(move || {
+                    if let Err(e) = open_that(&url) {
+                        let _ = notification_sender.send(notification::NotificationMessage::Error(
+                            format!("Failed to open {url}: {e}"),
+                        ));
+                    }
+                });
+            }
+            LinkTarget::Path(path) if path.is_dir() => {
+                self.show_popup = None;
+                self.navigate_to_dir(path);
+            }
+            LinkTarget::Path(path) => self.reveal_file(path),
+        }
+    }
+
+    /// Navigate to the parent directory of a file and select it
+    pub fn reveal_file(&mut self, path: PathBuf) {
+        // Resolve `..` so the parent matches the tab path
+        let path = if path
+            .components()
+            .any(|c| c == std::path::Component::ParentDir)
+        {
+            path.canonicalize().unwrap_or(path)
+        } else {
+            path
+        };
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        if parent != self.tab_manager.current_tab_ref().current_path {
+            self.navigate_to_dir(parent.to_path_buf());
+        }
+        if self.tab_manager.select_child(&path) {
+            self.ensure_selected_visible = true;
+            self.selection_changed = true;
+        }
+    }
+
     pub fn process_input(&mut self, ctx: &egui::Context) {
         // Let terminal widget process all the inputs
         if self.terminal_ctx.is_some() {
             return;
         }
 
+        // Let the file name field of the save picker take the keys
+        if self.picker.is_some() && picker::save_name_focused(ctx) {
+            return;
+        }
+
         // Prioritize Search Mode Input
         if search_bar::handle_key_press(ctx, self) {
             return;
         }
+        if crate::ui::type_ahead::handle_key_press(ctx, self) {
+            return;
+        }
 
         input::process_input_events(self, ctx);
     }
@@ -978,16 +2028,32 @@ impl Kiorg {
     pub fn graceful_shutdown(&mut self) {
         self.history_saver.shutdown();
 
-        // Shutdown plugins
-        if let Err(e) = self.plugin_manager.shutdown() {
-            tracing::warn!("Error shutting down plugins: {}", e);
+        // Plugins, caches and the app state file belong to the main window
+        if !self.main_window {
+            self.closed = true;
+            return;
         }
 
-        // Save application state before shutting down
-        if let Err(e) = self.save_app_state() {
+        if let Some(output) = self.choose_dir.take()
+            && let Err(e) = output.write(&self.tab_manager.current_tab_ref().current_path)
+        {
+            tracing::error!("Failed to write the current directory: {e}");
+        }
+
+        // Save application state, including the additional windows, before shutting them down
+        let standalone_picker = self.picker.as_ref().is_some_and(|picker| picker.standalone);
+        if !standalone_picker && let Err(e) = self.save_app_state() {
             self.toasts
                 .error(format!("Failed to save application state: {e}"));
         }
+        for window in &mut self.secondary_windows {
+            window.app.graceful_shutdown();
+        }
+
+        // Shutdown plugins
+        if let Err(e) = self.plugin_manager.shutdown() {
+            tracing::warn!("Error shutting down plugins: {}", e);
+        }
 
         pdfium_bind::cleanup_cache();
 
@@ -996,22 +2062,199 @@ impl Kiorg {
     }
 
     fn save_app_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_app_state(&self.app_state_json()?)
+    }
+
+    /// Save the app state every [`AUTOSAVE_INTERVAL`] if it changed, it's
+    /// otherwise only saved on exit
+    fn autosave(&mut self, ctx: &egui::Context) {
+        let standalone_picker = self.picker.as_ref().is_some_and(|picker| picker.standalone);
+        if !self.main_window || standalone_picker {
+            return;
+        }
+        ctx.request_repaint_after(AUTOSAVE_INTERVAL);
+        if self.last_autosave.elapsed() < AUTOSAVE_INTERVAL {
+            return;
+        }
+        self.last_autosave = Instant::now();
+
+        let state_json = match self.app_state_json() {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize application state: {e}");
+                return;
+            }
+        };
+        if self.autosaved_state.as_ref() == Some(&state_json) {
+            return;
+        }
+        match self.write_app_state(&state_json) {
+            Ok(()) => self.autosaved_state = Some(state_json),
+            Err(e) => tracing::warn!("Failed to autosave application state: {e}"),
+        }
+    }
+
+    fn write_app_state(&self, state_json: &str) -> Result<(), Box<dyn std::error::Error>> {
         let config_dir = config::get_kiorg_config_dir(self.config_dir_override.as_deref());
 
         if !config_dir.exists() {
             std::fs::create_dir_all(&config_dir)?;
         }
 
-        // Save app state with tab_manager as a top-level key
         let state_path = config_dir.join(STATE_FILE_NAME);
+        crate::utils::atomic_write::write(&state_path, state_json)?;
+
+        Ok(())
+    }
+
+    fn app_state_json(&self) -> serde_json::Result<String> {
+        // Save app state with tab_manager as a top-level key
         let app_state = AppState {
             tab_manager: self.tab_manager.to_state(),
+            window: Some(self.window_state.clone()),
+            windows: self
+                .secondary_windows
+                .iter()
+                .map(|window| SecondaryWindowState {
+                    tab_manager: window.app.tab_manager.to_state(),
+                    window: window.app.window_state.clone(),
+                })
+                .collect(),
+            jump_marks: self.jump_marks.clone(),
             // Add more fields here in the future
         };
-        let state_json = serde_json::to_string_pretty(&app_state)?;
-        std::fs::write(&state_path, state_json)?;
+        serde_json::to_string_pretty(&app_state)
+    }
 
-        Ok(())
+    /// Create the app of an additional window, starting in the current directory
+    /// unless saved tabs are provided
+    fn new_secondary(
+        &self,
+        ctx: &egui::Context,
+        saved: Option<SecondaryWindowState>,
+    ) -> Result<Self, KiorgError> {
+        let current_path = self.tab_manager.current_tab_ref().current_path.clone();
+        let mut app = Self::create(
+            ctx,
+            Some(current_path),
+            self.config_dir_override.clone(),
+            Some(self.plugin_manager.share()),
+        )?;
+        app.bookmarks.clone_from(&self.bookmarks);
+        app.jump_marks.clone_from(&self.jump_marks);
+
+        if let Some(saved) = saved {
+            app.window_state = saved.window;
+            let tab_manager = TabManager::from_state(saved.tab_manager);
+            let path = tab_manager.current_tab_ref().current_path.clone();
+            // Keep the current directory if the saved one is gone
+            if path.is_dir() {
+                app.tab_manager = tab_manager;
+                app.navigate_to_dir_without_history(path);
+            }
+        }
+        Ok(app)
+    }
+
+    fn add_secondary_window(&mut self, ctx: &egui::Context, saved: Option<SecondaryWindowState>) {
+        match self.new_secondary(ctx, saved) {
+            Ok(app) => {
+                let builder = app.window_state.apply_to_builder(
+                    egui::ViewportBuilder::default()
+                        .with_title(config::profile::window_title(self.profile.as_deref()))
+                        .with_min_inner_size(crate::models::window_state::MIN_INNER_SIZE)
+                        .with_app_id("kiorg"),
+                );
+                let viewport_id = egui::ViewportId::from_hash_of(uuid::Uuid::new_v4());
+                self.secondary_windows.push(SecondaryWindow {
+                    viewport_id,
+                    builder,
+                    app: Box::new(app),
+                });
+            }
+            Err(e) => self.notify_error(format!("Failed to open new window: {e}")),
+        }
+    }
+
+    /// Reopen the additional windows that were open when the app exited
+    fn restore_secondary_windows(&mut self, ctx: &egui::Context) {
+        let config_dir = config::get_kiorg_config_dir(self.config_dir_override.as_deref());
+        let Ok(json_str) = std::fs::read_to_string(config_dir.join(STATE_FILE_NAME)) else {
+            return;
+        };
+        let Ok(app_state) = serde_json::from_str::<AppState>(&json_str) else {
+            return;
+        };
+        for saved in app_state.windows {
+            self.add_secondary_window(ctx, Some(saved));
+        }
+    }
+
+    /// Draw the additional windows and open or drop windows as requested
+    fn draw_secondary_windows(&mut self, ctx: &egui::Context) {
+        for window in &mut self.secondary_windows {
+            // Bookmarks and marks are shared by all windows
+            window.app.bookmarks.clone_from(&self.bookmarks);
+            window.app.jump_marks.clone_from(&self.jump_marks);
+            let app = &mut window.app;
+            ctx.show_viewport_immediate(window.viewport_id, window.builder.clone(), |ui, _| {
+                if ui.input(|i| i.viewport().close_requested()) {
+                    app.graceful_shutdown();
+                    return;
+                }
+                app.draw(ui);
+            });
+            if window.app.bookmarks != self.bookmarks {
+                self.bookmarks.clone_from(&window.app.bookmarks);
+            }
+            if window.app.jump_marks != self.jump_marks {
+                self.jump_marks.clone_from(&window.app.jump_marks);
+            }
+        }
+        self.secondary_windows.retain(|window| !window.app.closed);
+
+        let requested = std::mem::take(&mut self.new_window_requested)
+            | self
+                .secondary_windows
+                .iter_mut()
+                .any(|window| std::mem::take(&mut window.app.new_window_requested));
+        if requested {
+            self.add_secondary_window(ctx, None);
+        }
+    }
+
+    /// Load the window geometry saved by the previous session
+    #[must_use]
+    pub fn load_window_state(config_dir_override: Option<&std::path::Path>) -> Option<WindowState> {
+        let config_dir = config::get_kiorg_config_dir(config_dir_override);
+        let json_str = std::fs::read_to_string(config_dir.join(STATE_FILE_NAME)).ok()?;
+        serde_json::from_str::<AppState>(&json_str).ok()?.window
+    }
+
+    /// Load the marks saved by the previous session
+    fn load_jump_marks(config_dir_override: Option<&std::path::Path>) -> JumpMarks {
+        let config_dir = config::get_kiorg_config_dir(config_dir_override);
+        std::fs::read_to_string(config_dir.join(STATE_FILE_NAME))
+            .ok()
+            .and_then(|json_str| serde_json::from_str::<AppState>(&json_str).ok())
+            .map(|app_state| app_state.jump_marks)
+            .unwrap_or_default()
+    }
+
+    /// Track the window geometry so it can be restored on the next launch
+    fn track_window_state(&mut self, ctx: &egui::Context) {
+        let info = ctx.input(|i| i.viewport().clone());
+        if self.window_fit_pending
+            && let Some(monitor_size) = info.monitor_size
+        {
+            self.window_fit_pending = false;
+            if let Some(rect) = self.window_state.fit_to_monitor(monitor_size) {
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(rect.size()));
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(rect.min));
+                return;
+            }
+        }
+        self.window_state.update_from_viewport(&info);
     }
 
     fn load_app_state(config_dir_override: Option<&std::path::Path>) -> Option<TabManager> {
@@ -1024,26 +2267,16 @@ impl Kiorg {
 
         match std::fs::read_to_string(&state_path) {
             Ok(json_str) => {
-                // First try to parse as the new format (AppState)
+                // Files in the old format are upgraded by `config::migration` on startup
                 match serde_json::from_str::<AppState>(&json_str) {
                     Ok(app_state) => {
                         // Convert TabManagerState to TabManager
                         let tab_manager = TabManager::from_state(app_state.tab_manager);
                         Some(tab_manager)
                     }
-                    Err(_) => {
-                        // If that fails, try the old format (direct TabManagerState)
-                        match serde_json::from_str::<TabManagerState>(&json_str) {
-                            Ok(tab_manager_state) => {
-                                // Convert TabManagerState to TabManager
-                                let tab_manager = TabManager::from_state(tab_manager_state);
-                                Some(tab_manager)
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to parse app state: {e}");
-                                None
-                            }
-                        }
+                    Err(e) => {
+                        eprintln!("Failed to parse app state: {e}");
+                        None
                     }
                 }
             }
@@ -1057,17 +2290,44 @@ impl Kiorg {
 
 impl eframe::App for Kiorg {
     fn ui(&mut self, ui: &mut egui::Ui, _: &mut eframe::Frame) {
+        self.draw(ui);
+        let ctx = ui.ctx().clone();
+        self.draw_secondary_windows(&ctx);
+        self.autosave(&ctx);
+    }
+}
+
+impl Kiorg {
+    /// Draw the window of this app
+    fn draw(&mut self, ui: &mut egui::Ui) {
         #[cfg(feature = "debug")]
         ui.set_debug_on_hover(true);
 
+        self.track_window_state(ui);
         self.poll_preview_content(ui);
         self.poll_popup_viewers(ui);
+        if let Some(path) = preview::pdf::take_password_request(ui) {
+            pdf_password::prompt(self, ui, path);
+        }
         self.check_notifications();
+        lan_receive::poll(self);
+        self.poll_flatten(ui);
+        self.poll_dir_stream(ui);
 
         if self
             .notify_fs_change
             .load(std::sync::atomic::Ordering::Relaxed)
         {
+            // Sizes of the changed directories and the ones containing them are stale
+            let changed_paths = self
+                .fs_changed_paths
+                .lock()
+                .map(|mut paths| std::mem::take(&mut *paths))
+                .unwrap_or_default();
+            for path in changed_paths {
+                self.dir_sizes.invalidate(&path);
+            }
+
             // Store the currently selected file path in prev_path for refresh_entries to handle
             self.prev_path = {
                 let tab = self.tab_manager.current_tab_ref();
@@ -1075,6 +2335,8 @@ impl eframe::App for Kiorg {
             };
 
             self.refresh_entries();
+            // Plugins may send new images under the same uids
+            crate::ui::preview::plugin::invalidate_textures(ui.ctx());
 
             self.notify_fs_change
                 .store(false, std::sync::atomic::Ordering::Relaxed);
@@ -1087,6 +2349,7 @@ impl eframe::App for Kiorg {
         }
 
         terminal::draw(ui, self);
+        crate::ui::operations::draw(ui, self);
 
         self.process_input(ui);
 
@@ -1109,6 +2372,9 @@ impl eframe::App for Kiorg {
             Some(PopupType::GenericMessage(_, _)) => {
                 generic_message::show_generic_message_popup(ui, self);
             }
+            Some(PopupType::FanOutPaste(_)) => {
+                fan_out_paste::draw(ui, self);
+            }
             Some(PopupType::Exit) => {
                 exit::draw(ui, self);
             }
@@ -1118,12 +2384,30 @@ impl eframe::App for Kiorg {
             Some(PopupType::DeleteProgress(_)) => {
                 delete::handle_delete_progress(ui, self);
             }
+            Some(PopupType::Checksum(_)) => {
+                checksum::handle_checksum_progress(ui, self);
+            }
+            Some(PopupType::SplitProgress(_)) => {
+                split::handle_split_progress(ui, self);
+            }
+            Some(PopupType::ArchivePassword(_)) => {
+                archive_password::draw(ui, self);
+            }
+            Some(PopupType::PdfPassword(_)) => {
+                pdf_password::draw(ui, self);
+            }
             Some(PopupType::OpenWith) => {
                 open_with_popup::draw(ui, self);
             }
             Some(PopupType::AddEntry(_)) => {
                 add_entry::draw(ui, self);
             }
+            Some(PopupType::Touch(_)) => {
+                touch::draw(ui, self);
+            }
+            Some(PopupType::EditBookmark(_)) => {
+                bookmark::draw_edit(ui, self);
+            }
             Some(PopupType::Bookmarks(_)) => {
                 // Handle bookmark popup
                 let bookmark_action = bookmark::show_bookmark_popup(ui, self);
@@ -1168,9 +2452,18 @@ impl eframe::App for Kiorg {
             }
             #[allow(clippy::collapsible_match)]
             Some(PopupType::Pdf(pdf_viewer)) => {
-                if !pdf_viewer.draw(ui, &self.colors) {
+                let mut zoom_mode = self.config.zoom_mode(ZoomContent::Pdf);
+                let background = self
+                    .config
+                    .preview
+                    .as_ref()
+                    .and_then(|preview| preview.pdf_background)
+                    .unwrap_or_default();
+                let keep_open = pdf_viewer.draw(ui, &self.colors, &mut zoom_mode, background);
+                if !keep_open {
                     self.show_popup = None;
                 }
+                self.save_zoom_mode(ZoomContent::Pdf, zoom_mode);
             }
             #[allow(clippy::collapsible_match)]
             Some(PopupType::Ebook(ebook_viewer)) => {
@@ -1180,9 +2473,12 @@ impl eframe::App for Kiorg {
             }
             #[allow(clippy::collapsible_match)]
             Some(PopupType::Image(image_viewer)) => {
-                if !image_viewer.draw(ui, &self.colors) {
+                let mut zoom_mode = self.config.zoom_mode(ZoomContent::Image);
+                let keep_open = image_viewer.draw(ui, &self.colors, &mut zoom_mode);
+                if !keep_open {
                     self.show_popup = None;
                 }
+                self.save_zoom_mode(ZoomContent::Image, zoom_mode);
             }
             #[allow(clippy::collapsible_match)]
             Some(PopupType::Video(video_viewer)) => {
@@ -1192,9 +2488,12 @@ impl eframe::App for Kiorg {
             }
             #[allow(clippy::collapsible_match)]
             Some(PopupType::Plugin(plugin_viewer)) => {
-                if !plugin_viewer.draw(ui, &self.colors) {
+                let mut zoom_mode = self.config.zoom_mode(ZoomContent::Plugin);
+                let keep_open = plugin_viewer.draw(ui, &self.colors, &mut zoom_mode);
+                if !keep_open {
                     self.show_popup = None;
                 }
+                self.save_zoom_mode(ZoomContent::Plugin, zoom_mode);
             }
             Some(PopupType::Themes(_)) => {
                 theme::draw(self, ui);
@@ -1208,6 +2507,45 @@ impl eframe::App for Kiorg {
             Some(PopupType::Teleport(_)) => {
                 teleport::draw(ui, self);
             }
+            Some(PopupType::FileHistory(_)) => {
+                file_history::draw(ui, self);
+            }
+            Some(PopupType::Profiles(_)) => {
+                profile::draw(ui, self);
+            }
+            Some(PopupType::Sessions(_)) => {
+                session::draw(ui, self);
+            }
+            Some(PopupType::Share(_)) => {
+                share::draw(ui, self);
+            }
+            Some(PopupType::Trash(_)) => {
+                trash::draw(ui, self);
+            }
+            Some(PopupType::Split(_)) => {
+                split::draw(ui, self);
+            }
+            Some(PopupType::Duplicates(_)) => {
+                duplicates::draw(ui, self);
+            }
+            Some(PopupType::ContentSearch(_)) => {
+                content_search::draw(ui, self);
+            }
+            Some(PopupType::LanSend(_)) => {
+                lan_send::draw(ui, self);
+            }
+            Some(PopupType::LanReceive(_)) => {
+                lan_receive::draw(ui, self);
+            }
+            Some(PopupType::Properties(_)) => {
+                properties::draw(ui, self);
+            }
+            Some(PopupType::Encrypt(_)) => {
+                encryption::draw_encrypt(ui, self);
+            }
+            Some(PopupType::Decrypt(_)) => {
+                encryption::draw_decrypt(ui, self);
+            }
             Some(PopupType::SortToggle) => {
                 sort_toggle::show_sort_toggle_popup(self, ui);
             }
@@ -1223,6 +2561,9 @@ impl eframe::App for Kiorg {
             Some(PopupType::ActionHistory) => {
                 action_history::draw(ui, self);
             }
+            Some(PopupType::JumpMarks) => {
+                jump_marks::draw(ui, self);
+            }
             Some(PopupType::GoToPath(_)) => {
                 crate::ui::popup::goto_path::draw(ui, self);
             }
@@ -1250,6 +2591,11 @@ impl eframe::App for Kiorg {
         egui::CentralPanel::default().show_inside(&mut stable_ui, |ui| {
             let total_available_height = ui.available_height();
 
+            if self.picker.is_some() {
+                picker::draw(self, ui, total_available_height);
+                return;
+            }
+
             // Draw top banner and measure its height
             let top_banner_response = ui.scope(|ui| {
                 top_banner::draw(self, ui);
@@ -1276,11 +2622,27 @@ impl eframe::App for Kiorg {
                 }
                 separator::draw_vertical_separator(ui);
 
-                center_panel::draw(self, ui, center_width, content_height);
-                separator::draw_vertical_separator(ui);
+                dual_pane::sync(self);
+                if let Some(pane) = self.dual_pane {
+                    // Both panes share the space of the center and preview panels
+                    let pane_width = (center_width + right_width) / 2.0;
+                    for index in 0..2 {
+                        if index == pane.focus {
+                            center_panel::draw(self, ui, pane_width, content_height);
+                        } else {
+                            dual_pane::draw(self, ui, pane_width, content_height);
+                        }
+                        if index == 0 {
+                            separator::draw_vertical_separator(ui);
+                        }
+                    }
+                } else {
+                    center_panel::draw(self, ui, center_width, content_height);
+                    separator::draw_vertical_separator(ui);
 
-                let ctx = ui.ctx().clone();
-                right_panel::draw(self, &ctx, ui, right_width, content_height);
+                    let ctx = ui.ctx().clone();
+                    right_panel::draw(self, &ctx, ui, right_width, content_height);
+                }
                 ui.add_space(PANEL_SPACING);
             });
         });
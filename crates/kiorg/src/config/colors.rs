@@ -191,6 +191,8 @@ impl AppColors {
 
         // scrollbar and text edit background
         visuals.extreme_bg_color = self.bg_extreme;
+        // Row striping of tables and grids, matching the file list
+        visuals.faint_bg_color = self.bg_light;
         visuals.text_cursor.stroke.color = self.link_underscore;
 
         visuals
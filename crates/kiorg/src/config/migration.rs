@@ -0,0 +1,371 @@
+//! Versioned upgrades of the files kiorg keeps in its config directory
+//!
+//! The schema version of every file is recorded in `versions.toml`, files
+//! written before versioning are at version 0. On startup each outdated file
+//! is backed up as `<name>.v<version>.bak` and upgraded in place by running
+//! the migrations above its version in order. Files written by a newer kiorg
+//! are left alone.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use super::{Config, get_kiorg_config_dir, load_config_with_override};
+
+const VERSIONS_FILE_NAME: &str = "versions.toml";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Upgrade of one file to the next schema version
+pub struct Migration {
+    /// Name of the file in the config directory
+    pub file: &'static str,
+    /// Version the file is at after this migration
+    pub version: u32,
+    pub description: &'static str,
+    /// Convert the file contents from the previous version
+    pub apply: fn(&str) -> Result<String, String>,
+}
+
+/// All migrations, ordered by version for each file
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    file: "state.json",
+    version: 1,
+    description: "Move the saved tabs under the tab_manager key",
+    apply: nest_tab_manager_state,
+}];
+
+/// Config keys that are no longer read, with what replaces them
+///
+/// Add an entry along with a `config.toml` migration when a key is renamed or
+/// removed so `--check-config` can point users to the replacement.
+pub const DEPRECATED_KEYS: &[(&str, &str)] = &[];
+
+/// Files with a schema version, in the order they are checked
+pub const VERSIONED_FILES: &[&str] = &[
+    CONFIG_FILE_NAME,
+    "state.json",
+    "bookmarks.txt",
+    "history.csv",
+    "file_history.csv",
+];
+
+/// Schema version written by this release
+#[must_use]
+pub fn current_version(file: &str) -> u32 {
+    MIGRATIONS
+        .iter()
+        .filter(|migration| migration.file == file)
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+fn load_versions(config_dir: &Path) -> BTreeMap<String, u32> {
+    let Ok(content) = fs::read_to_string(config_dir.join(VERSIONS_FILE_NAME)) else {
+        return BTreeMap::new();
+    };
+    toml::from_str(&content).unwrap_or_else(|e| {
+        tracing::error!(err = ?e, "Failed to parse {VERSIONS_FILE_NAME}");
+        BTreeMap::new()
+    })
+}
+
+fn save_versions(config_dir: &Path, versions: &BTreeMap<String, u32>) -> Result<(), String> {
+    let content = toml::to_string(versions).map_err(|e| e.to_string())?;
+    fs::write(config_dir.join(VERSIONS_FILE_NAME), content)
+        .map_err(|e| format!("Failed to write {VERSIONS_FILE_NAME}: {e}"))
+}
+
+fn pending_migrations(file: &str, version: u32) -> impl Iterator<Item = &'static Migration> + '_ {
+    MIGRATIONS
+        .iter()
+        .filter(move |migration| migration.file == file && migration.version > version)
+}
+
+/// Upgrade one file, keeping a backup of the old contents
+fn migrate_file(config_dir: &Path, file: &str, version: u32) -> Result<(), String> {
+    let path = config_dir.join(file);
+    let mut content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {file}: {e}"))?;
+    for migration in pending_migrations(file, version) {
+        content = (migration.apply)(&content)
+            .map_err(|e| format!("{file}: {}: {e}", migration.description))?;
+    }
+
+    let backup = config_dir.join(format!("{file}.v{version}.bak"));
+    fs::copy(&path, &backup).map_err(|e| format!("Failed to back up {file}: {e}"))?;
+    // Write next to the file first so an interrupted upgrade never leaves it half written
+    let temp = config_dir.join(format!("{file}.migrating"));
+    fs::write(&temp, content)
+        .and_then(|()| fs::rename(&temp, &path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            format!("Failed to write {file}: {e}")
+        })
+}
+
+/// Upgrade outdated files in the config directory
+///
+/// Returns a line for every upgraded file. A file that fails to upgrade is
+/// left untouched and reported as an error, the other files still upgrade.
+pub fn run_migrations(config_dir_override: Option<&Path>) -> Result<Vec<String>, Vec<String>> {
+    let config_dir = get_kiorg_config_dir(config_dir_override);
+    if !config_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut versions = load_versions(&config_dir);
+    let mut changed = false;
+    let mut upgraded = Vec::new();
+    let mut errors = Vec::new();
+
+    for file in VERSIONED_FILES {
+        let current = current_version(file);
+        let version = versions.get(*file).copied().unwrap_or(0);
+        if version >= current && versions.contains_key(*file) {
+            continue;
+        }
+        // Missing files get created in the current format
+        if version < current && config_dir.join(file).exists() {
+            if let Err(e) = migrate_file(&config_dir, file, version) {
+                errors.push(e);
+                continue;
+            }
+            upgraded.push(format!(
+                "Upgraded {file} from version {version} to {current}"
+            ));
+        }
+        versions.insert((*file).to_string(), current);
+        changed = true;
+    }
+
+    if changed && let Err(e) = save_versions(&config_dir, &versions) {
+        errors.push(e);
+    }
+    if errors.is_empty() {
+        Ok(upgraded)
+    } else {
+        Err(errors)
+    }
+}
+
+/// `state.json` v1: older releases saved the tab state at the top level
+fn nest_tab_manager_state(content: &str) -> Result<String, String> {
+    let state: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let serde_json::Value::Object(map) = &state else {
+        return Err("expected a JSON object".to_string());
+    };
+    if map.contains_key("tab_manager") || !map.contains_key("tab_states") {
+        return Ok(content.to_string());
+    }
+    let nested = serde_json::json!({ "tab_manager": state });
+    serde_json::to_string_pretty(&nested).map_err(|e| e.to_string())
+}
+
+/// Dotted paths of keys in `raw` that are missing from `known`
+fn unknown_keys(raw: &toml::Table, known: &toml::Table, prefix: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    for (key, value) in raw {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match (value, known.get(key)) {
+            (_, None) => keys.push(path),
+            (toml::Value::Table(raw), Some(toml::Value::Table(known))) => {
+                keys.extend(unknown_keys(raw, known, &path));
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// Deprecated keys set in `raw`, with their replacement
+fn deprecated_keys<'a>(
+    raw: &toml::Table,
+    deprecated: &'a [(&'a str, &'a str)],
+) -> Vec<(&'a str, &'a str)> {
+    deprecated
+        .iter()
+        .filter(|(key, _)| {
+            let mut table = Some(raw);
+            let mut parts = key.split('.').peekable();
+            while let Some(part) = parts.next() {
+                let Some(value) = table.and_then(|t| t.get(part)) else {
+                    return false;
+                };
+                if parts.peek().is_none() {
+                    return true;
+                }
+                table = value.as_table();
+            }
+            false
+        })
+        .copied()
+        .collect()
+}
+
+/// Report of `--check-config`, returns whether everything is fine
+pub fn check_config(config_dir_override: Option<&Path>, out: &mut String) -> bool {
+    let config_dir = get_kiorg_config_dir(config_dir_override);
+    let mut ok = true;
+    let _ = writeln!(out, "Config directory: {}", config_dir.display());
+
+    let config_path = config_dir.join(CONFIG_FILE_NAME);
+    match fs::read_to_string(&config_path) {
+        Err(_) => {
+            let _ = writeln!(out, "{CONFIG_FILE_NAME}: not found, using defaults");
+        }
+        Ok(content) => match load_config_with_override(config_dir_override) {
+            Err(e) => {
+                ok = false;
+                let _ = writeln!(out, "{CONFIG_FILE_NAME}: error: {e}");
+            }
+            Ok(config) => {
+                let _ = writeln!(out, "{CONFIG_FILE_NAME}: ok");
+                ok &= report_keys(&content, &config, out);
+            }
+        },
+    }
+
+    let versions = load_versions(&config_dir);
+    for file in VERSIONED_FILES {
+        if !config_dir.join(file).exists() {
+            continue;
+        }
+        let current = current_version(file);
+        let version = versions.get(*file).copied().unwrap_or(0);
+        if version > current {
+            ok = false;
+            let _ = writeln!(
+                out,
+                "{file}: version {version} was written by a newer kiorg, this release reads version {current}"
+            );
+        } else if version < current {
+            let _ = writeln!(out, "{file}: will be upgraded on the next start:");
+            for migration in pending_migrations(file, version) {
+                let _ = writeln!(out, "  - {}", migration.description);
+            }
+        }
+    }
+    ok
+}
+
+fn report_keys(content: &str, config: &Config, out: &mut String) -> bool {
+    let (Ok(raw), Ok(known)) = (
+        toml::from_str::<toml::Table>(content),
+        toml::Table::try_from(config),
+    ) else {
+        return true;
+    };
+    let mut ok = true;
+    for (key, replacement) in deprecated_keys(&raw, DEPRECATED_KEYS) {
+        ok = false;
+        let _ = writeln!(out, "  deprecated key `{key}`: {replacement}");
+    }
+    for key in unknown_keys(&raw, &known, "") {
+        if DEPRECATED_KEYS
+            .iter()
+            .any(|(deprecated, _)| *deprecated == key)
+        {
+            continue;
+        }
+        ok = false;
+        let _ = writeln!(out, "  unknown key `{key}` is ignored");
+    }
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nest_tab_manager_state() {
+        let legacy = r#"{"tab_states": [{"current_path": "/tmp"}], "current_tab_index": 0}"#;
+        let migrated: serde_json::Value =
+            serde_json::from_str(&nest_tab_manager_state(legacy).unwrap()).unwrap();
+        assert_eq!(
+            migrated["tab_manager"]["tab_states"][0]["current_path"],
+            "/tmp"
+        );
+
+        let current = r#"{"tab_manager": {"tab_states": []}}"#;
+        assert_eq!(nest_tab_manager_state(current).unwrap(), current);
+        assert!(nest_tab_manager_state("not json").is_err());
+    }
+
+    #[test]
+    fn test_run_migrations_backs_up_and_records_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let legacy = r#"{"tab_states": [], "current_tab_index": 0}"#;
+        fs::write(dir.join("state.json"), legacy).unwrap();
+
+        let upgraded = run_migrations(Some(dir)).unwrap();
+        assert_eq!(upgraded, ["Upgraded state.json from version 0 to 1"]);
+        assert_eq!(
+            fs::read_to_string(dir.join("state.json.v0.bak")).unwrap(),
+            legacy
+        );
+        let state = fs::read_to_string(dir.join("state.json")).unwrap();
+        assert!(state.contains("tab_manager"));
+        assert_eq!(load_versions(dir).get("state.json"), Some(&1));
+
+        // Nothing left to do on the next start
+        assert!(run_migrations(Some(dir)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failed_migration_leaves_file_untouched() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("state.json"), "{broken").unwrap();
+
+        assert!(run_migrations(Some(dir)).is_err());
+        assert_eq!(
+            fs::read_to_string(dir.join("state.json")).unwrap(),
+            "{broken"
+        );
+        assert!(!dir.join("state.json.v0.bak").exists());
+        assert_eq!(load_versions(dir).get("state.json"), None);
+    }
+
+    #[test]
+    fn test_config_key_checks() {
+        let raw: toml::Table = toml::from_str(
+            "theme = \"dark\"\ncolour = \"red\"\n[layout]\npreview = 0.4\nold_width = 3\n",
+        )
+        .unwrap();
+        let config: Config = toml::from_str(&toml::to_string(&raw).unwrap()).unwrap();
+        let known = toml::Table::try_from(&config).unwrap();
+        assert_eq!(
+            unknown_keys(&raw, &known, ""),
+            ["colour".to_string(), "layout.old_width".to_string()]
+        );
+
+        let deprecated = [
+            ("layout.old_width", "use layout.preview"),
+            ("layout.gone", ""),
+        ];
+        assert_eq!(
+            deprecated_keys(&raw, &deprecated),
+            [("layout.old_width", "use layout.preview")]
+        );
+    }
+
+    #[test]
+    fn test_check_config_report() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config.toml"), "theme = \"dark\"\nthme = \"x\"\n").unwrap();
+        fs::write(dir.join("state.json"), r#"{"tab_states": []}"#).unwrap();
+
+        let mut report = String::new();
+        assert!(!check_config(Some(dir), &mut report));
+        assert!(report.contains("unknown key `thme`"));
+        assert!(report.contains("state.json: will be upgraded"));
+    }
+}
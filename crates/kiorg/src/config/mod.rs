@@ -1,15 +1,20 @@
 pub mod colors;
+pub mod migration;
+pub mod portable;
+pub mod profile;
+pub mod session;
 pub mod shortcuts;
 
 use crate::models::tab::{SortColumn, SortOrder};
 use crate::theme::Theme;
+use crate::utils::format::NumberFormat;
 use serde::{Deserialize, Serialize};
 
 use std::error::Error;
 use std::fmt;
 use std::fs;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // Panel size ratios (relative to usable width)
 pub const LEFT_PANEL_RATIO: f32 = 0.15;
@@ -44,6 +49,260 @@ pub struct SortPreference {
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Layout {
     pub preview: Option<f32>,
+    /// Show the space files take up on disk next to their size
+    pub size_on_disk_column: Option<bool>,
+    /// Scan directories in the background to show their recursive size
+    pub dir_sizes: Option<bool>,
+}
+
+/// What double-clicking (or single-clicking in single click mode) an entry does
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    /// Enter directories and open files with the default application
+    #[default]
+    Open,
+    /// Show files in the preview popup, directories are still entered
+    Preview,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Mouse {
+    #[serde(default)]
+    pub double_click: ClickAction,
+    /// Activate entries with a single click and select them on hover
+    #[serde(default)]
+    pub single_click: bool,
+    /// Open the context menu by pressing and holding, for touchscreens
+    #[serde(default)]
+    pub long_press_menu: bool,
+    /// Start in touch mode with larger rows and buttons and drag to scroll lists
+    #[serde(default)]
+    pub touch_mode: bool,
+}
+
+/// Limits that keep large or slow files from stalling the preview panel, and
+/// how text is shown in the preview popup
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Preview {
+    /// Text files larger than this are previewed truncated
+    pub max_text_bytes: Option<u64>,
+    /// Images with more pixels than this show a placeholder until clicked
+    pub max_image_pixels: Option<u64>,
+    /// Extensions that show a placeholder until clicked, e.g. `["iso", "vmdk"]`
+    #[serde(default)]
+    pub disabled_types: Vec<String>,
+    /// Show line numbers next to text in the preview popup
+    pub line_numbers: Option<bool>,
+    /// Soft wrap long lines of text in the preview popup
+    pub wrap_lines: Option<bool>,
+    /// Preview online-only cloud files automatically, which downloads them
+    pub download_online_only: Option<bool>,
+    /// Rotate and flip images as their EXIF orientation says, on by default
+    pub exif_orientation: Option<bool>,
+    /// Zoom the preview popup opens images with, the last one used
+    pub image_zoom: Option<ZoomMode>,
+    /// Zoom the preview popup opens PDF pages with, the last one used
+    pub pdf_zoom: Option<ZoomMode>,
+    /// Zoom the preview popup opens plugin images with, the last one used
+    pub plugin_zoom: Option<ZoomMode>,
+    /// What the PDF viewer draws pages on, white by default
+    pub pdf_background: Option<PdfBackground>,
+    /// Show only static metadata of files everywhere, instead of decoding them
+    pub safe_mode: Option<bool>,
+    /// Directories whose files are previewed in safe mode, `~` is the home directory
+    #[serde(default)]
+    pub untrusted_dirs: Vec<PathBuf>,
+}
+
+/// Background of PDF pages in the PDF viewer
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PdfBackground {
+    #[default]
+    White,
+    /// The background color of the theme, black text can be hard to read on
+    /// dark themes
+    Theme,
+    /// The popup shows through where pages have no content of their own
+    Transparent,
+}
+
+/// How the preview popup fits content in the view when opening it
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZoomMode {
+    /// The whole content is visible, small images aren't enlarged
+    #[default]
+    FitPage,
+    /// The content is as wide as the view, starting from its top
+    FitWidth,
+    /// The content at its actual size, 100%
+    ActualSize,
+}
+
+/// Kinds of content whose zoom mode is remembered separately
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoomContent {
+    Image,
+    Pdf,
+    Plugin,
+}
+
+impl Preview {
+    pub const DEFAULT_MAX_TEXT_BYTES: u64 = 1_048_576;
+
+    #[must_use]
+    pub fn max_text_bytes(&self) -> u64 {
+        self.max_text_bytes.unwrap_or(Self::DEFAULT_MAX_TEXT_BYTES)
+    }
+
+    #[must_use]
+    pub fn exif_orientation(&self) -> bool {
+        self.exif_orientation.unwrap_or(true)
+    }
+
+    #[must_use]
+    pub fn zoom_mode(&self, content: ZoomContent) -> ZoomMode {
+        match content {
+            ZoomContent::Image => self.image_zoom,
+            ZoomContent::Pdf => self.pdf_zoom,
+            ZoomContent::Plugin => self.plugin_zoom,
+        }
+        .unwrap_or_default()
+    }
+
+    pub fn set_zoom_mode(&mut self, content: ZoomContent, mode: ZoomMode) {
+        let zoom = match content {
+            ZoomContent::Image => &mut self.image_zoom,
+            ZoomContent::Pdf => &mut self.pdf_zoom,
+            ZoomContent::Plugin => &mut self.plugin_zoom,
+        };
+        *zoom = Some(mode);
+    }
+
+    /// Whether automatic previews are disabled for an extension as returned by `path_to_ext_info`
+    #[must_use]
+    pub fn is_disabled(&self, ext: &str) -> bool {
+        self.disabled_types
+            .iter()
+            .any(|disabled| disabled.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    }
+
+    /// Whether `path` is inside one of the untrusted directories
+    #[must_use]
+    pub fn is_untrusted(&self, path: &Path) -> bool {
+        is_inside_any(path, &self.untrusted_dirs)
+    }
+}
+
+/// Whether `path` is inside one of `dirs`, where `~` is the home directory
+fn is_inside_any(path: &Path, dirs: &[PathBuf]) -> bool {
+    dirs.iter()
+        .any(|dir| match (dir.strip_prefix("~"), dirs::home_dir()) {
+            (Ok(rest), Some(home)) => path.starts_with(home.join(rest)),
+            _ => path.starts_with(dir),
+        })
+}
+
+/// Sending and receiving files between kiorg instances on the local network
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Lan {
+    /// Announce this device and accept offers from other devices on startup
+    pub receive: Option<bool>,
+    /// Where received files are saved, defaults to the downloads directory
+    pub receive_dir: Option<PathBuf>,
+    /// Name shown to other devices, defaults to the host name
+    pub device_name: Option<String>,
+}
+
+/// How marked entries behave
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Marks {
+    /// Keep the marked entries when leaving a directory, to copy or move
+    /// entries of several directories at once
+    #[serde(default)]
+    pub keep_across_directories: bool,
+}
+
+/// What gets recorded in the directory visit history and the recent files list
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct History {
+    /// Paths under these directories are never recorded, `~` is the home directory
+    #[serde(default)]
+    pub exclude: Vec<PathBuf>,
+    /// Keep at most this many entries in each history, forgetting the least recently used
+    pub max_entries: Option<usize>,
+    /// Days after which a visit counts half as much when ranking teleport results
+    pub frecency_half_life_days: Option<f64>,
+}
+
+impl History {
+    pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+    pub const DEFAULT_FRECENCY_HALF_LIFE_DAYS: f64 = 14.0;
+
+    #[must_use]
+    pub fn max_entries(&self) -> usize {
+        self.max_entries.unwrap_or(Self::DEFAULT_MAX_ENTRIES)
+    }
+
+    /// Half-life of a visit in seconds, the default one unless a positive one is set
+    #[must_use]
+    pub fn frecency_half_life_secs(&self) -> f64 {
+        let days = self
+            .frecency_half_life_days
+            .filter(|days| days.is_finite() && *days > 0.0)
+            .unwrap_or(Self::DEFAULT_FRECENCY_HALF_LIFE_DAYS);
+        days * 24.0 * 60.0 * 60.0
+    }
+
+    /// Whether `path` is inside one of the excluded directories
+    #[must_use]
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        is_inside_any(path, &self.exclude)
+    }
+}
+
+/// Terminal emulator running console applications and opened as a window
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Terminal {
+    /// Command prefix running the command appended to it, e.g. `alacritty -e`
+    pub emulator: Option<String>,
+    /// Command opening a terminal window, `%d` is replaced by the directory
+    pub command: Option<String>,
+}
+
+/// Files offered as templates when adding a new file
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Templates {
+    /// Directory holding the templates, defaults to `templates` in the config directory
+    pub dir: Option<PathBuf>,
+}
+
+/// Separators of the numbers shown in the UI, detected from the locale unless set
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Numbers {
+    /// Locale to take the separators from instead of the environment, e.g. `de_DE`
+    pub locale: Option<String>,
+    pub thousands_separator: Option<String>,
+    pub decimal_separator: Option<String>,
+}
+
+impl Numbers {
+    #[must_use]
+    pub fn number_format(&self) -> NumberFormat {
+        let mut format = self
+            .locale
+            .as_deref()
+            .map_or_else(NumberFormat::from_env, NumberFormat::from_locale);
+        if let Some(thousands) = &self.thousands_separator {
+            format.thousands = thousands.clone().into();
+        }
+        if let Some(decimal) = &self.decimal_separator {
+            format.decimal = decimal.clone().into();
+        }
+        format
+    }
 }
 
 #[derive(Deserialize, Serialize, Default, Debug)]
@@ -53,6 +312,14 @@ pub struct Config {
     pub shortcuts: Option<shortcuts::Shortcuts>,
     pub custom_themes: Option<Vec<Theme>>,
     pub layout: Option<Layout>,
+    pub mouse: Option<Mouse>,
+    pub preview: Option<Preview>,
+    pub lan: Option<Lan>,
+    pub history: Option<History>,
+    pub marks: Option<Marks>,
+    pub numbers: Option<Numbers>,
+    pub terminal: Option<Terminal>,
+    pub templates: Option<Templates>,
 }
 
 impl Config {
@@ -63,8 +330,64 @@ impl Config {
             shortcuts: None,
             custom_themes: None,
             layout: None,
+            mouse: None,
+            preview: None,
+            lan: None,
+            history: None,
+            marks: None,
+            numbers: None,
+            terminal: None,
+            templates: None,
+        }
+    }
+
+    /// Zoom mode the preview popup opens `content` with
+    #[must_use]
+    pub fn zoom_mode(&self, content: ZoomContent) -> ZoomMode {
+        self.preview
+            .as_ref()
+            .map_or_else(ZoomMode::default, |preview| preview.zoom_mode(content))
+    }
+
+    /// Whether marked entries survive navigating to another directory
+    #[must_use]
+    pub fn keep_marks_across_directories(&self) -> bool {
+        self.marks
+            .as_ref()
+            .is_some_and(|marks| marks.keep_across_directories)
+    }
+
+    /// Configured terminal emulator command prefix
+    #[must_use]
+    pub fn terminal_emulator(&self) -> Option<&str> {
+        self.terminal.as_ref()?.emulator.as_deref()
+    }
+
+    /// Configured command opening a terminal window
+    #[must_use]
+    pub fn terminal_command(&self) -> Option<&str> {
+        self.terminal.as_ref()?.command.as_deref()
+    }
+
+    /// Directory of the new file templates, `~` is the home directory
+    #[must_use]
+    pub fn templates_dir(&self, config_dir: &Path) -> PathBuf {
+        let Some(dir) = self.templates.as_ref().and_then(|t| t.dir.as_ref()) else {
+            return config_dir.join("templates");
+        };
+        match (dir.strip_prefix("~"), dirs::home_dir()) {
+            (Ok(rest), Some(home)) => home.join(rest),
+            _ => dir.clone(),
         }
     }
+
+    /// Separators of the numbers shown in the UI
+    #[must_use]
+    pub fn number_format(&self) -> NumberFormat {
+        self.numbers
+            .as_ref()
+            .map_or_else(NumberFormat::from_env, Numbers::number_format)
+    }
 }
 
 // Define a custom error type that can represent both TOML parsing errors and shortcut conflicts
@@ -167,6 +490,48 @@ pub fn load_config_with_override(
         ));
     }
 
+    if let Some(preview) = &user_config.preview
+        && (preview.max_text_bytes == Some(0) || preview.max_image_pixels == Some(0))
+    {
+        return Err(ConfigError::ValueError(
+            "Preview limits must be greater than zero".to_string(),
+            config_path,
+        ));
+    }
+
+    if let Some(numbers) = &user_config.numbers {
+        let format = numbers.number_format();
+        if format.decimal.is_empty() || format.decimal == format.thousands {
+            return Err(ConfigError::ValueError(
+                "Decimal separator must be set and differ from the thousands separator".to_string(),
+                config_path,
+            ));
+        }
+    }
+
+    if let Some(terminal) = &user_config.terminal {
+        if terminal
+            .emulator
+            .as_deref()
+            .is_some_and(|emulator| emulator.trim().is_empty())
+        {
+            return Err(ConfigError::ValueError(
+                "Terminal emulator command must not be empty".to_string(),
+                config_path,
+            ));
+        }
+        if terminal
+            .command
+            .as_deref()
+            .is_some_and(|command| command.trim().is_empty())
+        {
+            return Err(ConfigError::ValueError(
+                "Terminal command must not be empty".to_string(),
+                config_path,
+            ));
+        }
+    }
+
     Ok(user_config)
 }
 
@@ -186,7 +551,7 @@ pub fn save_config_with_override(
 
     let config_path = config_dir.join("config.toml");
     let toml_str = toml::to_string_pretty(config).unwrap_or_default();
-    fs::write(&config_path, toml_str)
+    crate::utils::atomic_write::write(&config_path, toml_str)
 }
 
 #[must_use]
@@ -199,6 +564,8 @@ pub fn get_config_path_with_override(config_dir_override: Option<&std::path::Pat
 pub fn get_kiorg_config_dir(override_path: Option<&std::path::Path>) -> PathBuf {
     if let Some(dir) = override_path {
         dir.to_path_buf()
+    } else if let Some(dir) = portable::config_dir() {
+        dir
     } else {
         // For macOS, prioritize ~/.config/kiorg for easier config management and terminal access
         #[cfg(target_os = "macos")]
@@ -0,0 +1,69 @@
+//! Portable mode keeps config, state, plugins and caches in a `kiorg-data`
+//! directory next to the executable instead of the user profile, e.g. to
+//! carry kiorg on a USB stick.
+//!
+//! It is enabled with `--portable` or by placing a `kiorg.portable` file next
+//! to the executable.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Directory next to the executable holding all data in portable mode
+pub const DATA_DIR_NAME: &str = "kiorg-data";
+/// File next to the executable that turns on portable mode
+pub const SENTINEL_FILE_NAME: &str = "kiorg.portable";
+
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Turn on portable mode for this process, called for `--portable`
+pub fn enable() {
+    REQUESTED.store(true, Ordering::Relaxed);
+}
+
+fn exe_dir() -> Option<&'static Path> {
+    static EXE_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+    EXE_DIR
+        .get_or_init(|| {
+            let exe = std::env::current_exe().ok()?;
+            let exe = exe.canonicalize().unwrap_or(exe);
+            exe.parent().map(Path::to_path_buf)
+        })
+        .as_deref()
+}
+
+fn has_sentinel(dir: &Path) -> bool {
+    dir.join(SENTINEL_FILE_NAME).is_file()
+}
+
+/// Data directory when running in portable mode
+#[must_use]
+pub fn data_dir() -> Option<PathBuf> {
+    let dir = exe_dir()?;
+    (REQUESTED.load(Ordering::Relaxed) || has_sentinel(dir)).then(|| dir.join(DATA_DIR_NAME))
+}
+
+/// Config directory in portable mode, also holds the state and plugins
+#[must_use]
+pub fn config_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("config"))
+}
+
+/// Preview cache directory in portable mode
+#[must_use]
+pub fn cache_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("cache"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentinel_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(!has_sentinel(temp_dir.path()));
+        std::fs::write(temp_dir.path().join(SENTINEL_FILE_NAME), "").unwrap();
+        assert!(has_sentinel(temp_dir.path()));
+    }
+}
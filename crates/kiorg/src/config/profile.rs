@@ -0,0 +1,128 @@
+//! Profiles are isolated sets of config, state, bookmarks, history and
+//! plugins, e.g. to keep "work" and "personal" apart.
+//!
+//! The default profile uses the config directory itself, other profiles live
+//! in `profiles/<name>` below it. A profile is selected with `--profile` or
+//! switched at runtime from the profiles popup.
+
+use std::path::{Path, PathBuf};
+
+/// Directory in the config directory holding the named profiles
+pub const PROFILES_DIR_NAME: &str = "profiles";
+/// Name shown for the default profile
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// Check that `name` can be used as a profile directory name
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Profile name can't be empty".to_string());
+    }
+    if name.starts_with('.') {
+        return Err(format!("Invalid profile name '{name}'"));
+    }
+    if name == DEFAULT_PROFILE_NAME {
+        return Err(format!("'{name}' is the name of the default profile"));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| std::path::is_separator(*c) || c.is_control() || ":*?\"<>|".contains(*c))
+    {
+        return Err(format!("Profile name can't contain '{c}'"));
+    }
+    Ok(())
+}
+
+/// Config directory of the profile `name` below the default config directory
+#[must_use]
+pub fn config_dir(base_dir: &Path, name: &str) -> PathBuf {
+    base_dir.join(PROFILES_DIR_NAME).join(name)
+}
+
+/// Name of the profile using `config_dir`, `None` for the default profile
+#[must_use]
+pub fn name(config_dir: &Path) -> Option<String> {
+    let parent = config_dir.parent()?;
+    if parent.file_name()? != PROFILES_DIR_NAME {
+        return None;
+    }
+    config_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+/// Config directory of the default profile, given the config directory of any profile
+#[must_use]
+pub fn base_dir(config_dir: &Path) -> PathBuf {
+    match name(config_dir) {
+        Some(_) => config_dir
+            .parent()
+            .and_then(Path::parent)
+            .map_or_else(|| config_dir.to_path_buf(), Path::to_path_buf),
+        None => config_dir.to_path_buf(),
+    }
+}
+
+/// Names of the existing profiles, sorted, without the default profile
+#[must_use]
+pub fn list(base_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(base_dir.join(PROFILES_DIR_NAME)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| validate_name(name).is_ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Title of the main window, showing the active profile
+#[must_use]
+pub fn window_title(profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("Kiorg [{name}]"),
+        None => "Kiorg".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("work").is_ok());
+        assert!(validate_name("my profile").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("  ").is_err());
+        assert!(validate_name("..").is_err());
+        assert!(validate_name("default").is_err());
+        assert!(validate_name(".hidden").is_err());
+        assert!(validate_name("a/b").is_err());
+        assert!(validate_name("a:b").is_err());
+    }
+
+    #[test]
+    fn test_profile_dirs() {
+        let base = Path::new("/home/user/.config/kiorg");
+        let work = config_dir(base, "work");
+        assert_eq!(work, base.join("profiles").join("work"));
+        assert_eq!(name(&work).as_deref(), Some("work"));
+        assert_eq!(base_dir(&work), base);
+        assert_eq!(name(base), None);
+        assert_eq!(base_dir(base), base);
+    }
+
+    #[test]
+    fn test_list() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(list(temp_dir.path()).is_empty());
+        for name in ["work", "personal", ".trash"] {
+            std::fs::create_dir_all(config_dir(temp_dir.path(), name)).unwrap();
+        }
+        std::fs::write(temp_dir.path().join(PROFILES_DIR_NAME).join("file"), "").unwrap();
+        assert_eq!(list(temp_dir.path()), vec!["personal", "work"]);
+    }
+}
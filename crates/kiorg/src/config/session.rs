@@ -0,0 +1,132 @@
+//! Named sessions are saved sets of tabs and marks, e.g. one per project, that
+//! can be restored later.
+//!
+//! Sessions are stored as `sessions/<name>.json` in the config directory,
+//! next to the app state. A session is restored with `--session` or from the
+//! sessions popup.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::models::jump_marks::JumpMarks;
+use crate::models::tab::TabManagerState;
+
+/// Directory in the config directory holding the named sessions
+pub const SESSIONS_DIR_NAME: &str = "sessions";
+
+/// Tabs and marks saved under a name
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub tab_manager: TabManagerState,
+    #[serde(default)]
+    pub jump_marks: JumpMarks,
+}
+
+/// Check that `name` can be used as a session file name
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Session name can't be empty".to_string());
+    }
+    if name.starts_with('.') {
+        return Err(format!("Invalid session name '{name}'"));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| std::path::is_separator(*c) || c.is_control() || ":*?\"<>|".contains(*c))
+    {
+        return Err(format!("Session name can't contain '{c}'"));
+    }
+    Ok(())
+}
+
+/// File of the session `name` in `config_dir`
+#[must_use]
+pub fn path(config_dir: &Path, name: &str) -> PathBuf {
+    config_dir
+        .join(SESSIONS_DIR_NAME)
+        .join(format!("{name}.json"))
+}
+
+/// Names of the saved sessions, sorted
+#[must_use]
+pub fn list(config_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(config_dir.join(SESSIONS_DIR_NAME)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .filter(|name| validate_name(name).is_ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Save `session` as `name`, replacing a session with the same name
+pub fn save(config_dir: &Path, name: &str, session: &Session) -> Result<(), String> {
+    validate_name(name)?;
+    let json = serde_json::to_string_pretty(session).map_err(|e| e.to_string())?;
+    let path = path(config_dir, name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    crate::utils::atomic_write::write(&path, json).map_err(|e| e.to_string())
+}
+
+/// Load the session `name`
+pub fn load(config_dir: &Path, name: &str) -> Result<Session, String> {
+    validate_name(name)?;
+    let json = std::fs::read_to_string(path(config_dir, name)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("No session named '{name}'"),
+        _ => e.to_string(),
+    })?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid session '{name}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tab::TabManager;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("kiorg").is_ok());
+        assert!(validate_name("side project").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name(".hidden").is_err());
+        assert!(validate_name("a/b").is_err());
+        assert!(validate_name("a:b").is_err());
+    }
+
+    #[test]
+    fn test_save_list_and_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(list(temp_dir.path()).is_empty());
+        assert!(load(temp_dir.path(), "missing").is_err());
+
+        let tab_manager = TabManager::new(temp_dir.path().to_path_buf());
+        let session = Session {
+            tab_manager: tab_manager.to_state(),
+            jump_marks: JumpMarks::default(),
+        };
+        save(temp_dir.path(), "work", &session).unwrap();
+        save(temp_dir.path(), "home", &session).unwrap();
+        std::fs::write(
+            temp_dir.path().join(SESSIONS_DIR_NAME).join("notes.txt"),
+            "",
+        )
+        .unwrap();
+        assert_eq!(list(temp_dir.path()), vec!["home", "work"]);
+
+        let loaded = load(temp_dir.path(), "work").unwrap();
+        let restored = TabManager::from_state(loaded.tab_manager);
+        assert_eq!(restored.current_tab_ref().current_path, temp_dir.path());
+    }
+}
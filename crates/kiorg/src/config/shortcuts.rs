@@ -9,6 +9,8 @@ pub enum ShortcutTreeNode {
     Children(HashMap<ShortcutKey, ShortcutTreeNode>),
     // A leaf node that has an action but no children
     Action(ShortcutAction),
+    // A leaf node whose action takes the letter typed after it, like `ma` in vim
+    Namespace(ShortcutAction),
 }
 
 // Represents a single key with modifiers
@@ -18,6 +20,39 @@ pub struct ShortcutKey {
     pub modifiers: Modifiers,
 }
 
+// Value of a digit key pressed without modifiers
+fn count_digit(shortcut_key: &ShortcutKey) -> Option<usize> {
+    if !shortcut_key.modifiers.is_none() {
+        return None;
+    }
+    let digit = match shortcut_key.key {
+        Key::Num0 => 0,
+        Key::Num1 => 1,
+        Key::Num2 => 2,
+        Key::Num3 => 3,
+        Key::Num4 => 4,
+        Key::Num5 => 5,
+        Key::Num6 => 6,
+        Key::Num7 => 7,
+        Key::Num8 => 8,
+        Key::Num9 => 9,
+        _ => return None,
+    };
+    Some(digit)
+}
+
+// Lowercase letter of a key pressed without modifiers, the argument of a namespace
+pub(crate) fn argument_letter(shortcut_key: &ShortcutKey) -> Option<char> {
+    if !shortcut_key.modifiers.is_none() {
+        return None;
+    }
+    let mut chars = shortcut_key.key.name().chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Some(c.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
 #[inline]
 pub fn check_blacklisted_shortcut(_key: &ShortcutKey) -> Result<(), String> {
     #[cfg(target_os = "windows")]
@@ -35,6 +70,8 @@ pub fn check_blacklisted_shortcut(_key: &ShortcutKey) -> Result<(), String> {
 pub enum TraverseResult {
     // Found a complete action to execute
     Action(ShortcutAction),
+    // Found an action along with the letter typed after its namespace
+    ActionWithArgument(ShortcutAction, char),
     // Partial match - wait for more keys
     Partial,
     // No match found
@@ -176,6 +213,19 @@ impl KeyboardShortcut {
             "]" => Some(Key::CloseBracket),
             "-" => Some(Key::Minus),
             "," => Some(Key::Comma),
+            "'" | "quote" => Some(Key::Quote),
+            "f1" => Some(Key::F1),
+            "f2" => Some(Key::F2),
+            "f3" => Some(Key::F3),
+            "f4" => Some(Key::F4),
+            "f5" => Some(Key::F5),
+            "f6" => Some(Key::F6),
+            "f7" => Some(Key::F7),
+            "f8" => Some(Key::F8),
+            "f9" => Some(Key::F9),
+            "f10" => Some(Key::F10),
+            "f11" => Some(Key::F11),
+            "f12" => Some(Key::F12),
             _ => None,
         }
     }
@@ -225,6 +275,7 @@ impl KeyboardShortcut {
             ']' => Some(Key::CloseBracket),
             '-' => Some(Key::Minus),
             ',' => Some(Key::Comma),
+            '\'' => Some(Key::Quote),
             _ => {
                 tracing::warn!("Unsupported character: {}", c);
                 None
@@ -246,6 +297,8 @@ pub enum ShortcutAction {
     GoToLastEntry,
     GoBackInHistory,
     GoForwardInHistory,
+    JumpBack,
+    JumpForward,
     SwitchToNextTab,
     SwitchToPreviousTab,
     PageUp,
@@ -253,17 +306,28 @@ pub enum ShortcutAction {
 
     // File operations
     DeleteEntry,
+    DeleteEntryPermanently,
     RenameEntry,
     AddEntry,
+    PasteClipboardAsFile,
+    TouchEntry,
     SelectEntry,
     SelectAllEntries,
     CopyEntry,
     CutEntry,
     PasteEntry,
+    PasteSymlink,
+    PasteHardlink,
+    CreateSymlink,
+    FollowSymlink,
+    ShowLinkTarget,
+    ShareEntry,
     OpenWithCommand,
+    ShowProperties,
 
     // Tabs
     CreateTab,
+    NewWindow,
     SwitchToTab1,
     SwitchToTab2,
     SwitchToTab3,
@@ -275,10 +339,21 @@ pub enum ShortcutAction {
     SwitchToTab9,
     CloseCurrentTab,
 
+    // Dual pane
+    ToggleDualPane,
+    SwitchPaneFocus,
+    CopyToOtherPane,
+    MoveToOtherPane,
+
     // Bookmarks
     ToggleBookmark,
     ShowBookmarks,
 
+    // Marks
+    SetMark,
+    JumpToMark,
+    ShowMarks,
+
     #[cfg(target_os = "windows")]
     ShowWindowsDrives,
 
@@ -287,20 +362,44 @@ pub enum ShortcutAction {
 
     // UI interaction
     ActivateSearch,
+    TypeAheadFilter,
+    SearchContents,
     ShowHelp,
     OpenTerminal,
+    OpenSystemTerminal,
     ShowFilePreview,
+    ShowDiff,
     ShowTeleport,
+    ShowFileHistory,
+    ShowProfiles,
+    ShowSessions,
     ShowSortToggle,
     ShowActionHistory,
+    ShowTrash,
     Undo,
     Redo,
     Exit,
     ToggleRangeSelection,
     ToggleHiddenFiles,
+    ToggleHiddenFilesDefault,
+    ToggleTouchMode,
+    ToggleDirSizes,
+    ToggleFlatten,
+    TogglePickerMode,
     CopyPath,
     CopyName,
     GoToPath,
+    CycleImageZoom,
+    RotatePreview,
+    ToggleSafePreview,
+}
+
+impl ShortcutAction {
+    // Whether the action takes the letter typed after its shortcut
+    #[must_use]
+    pub const fn takes_argument(self) -> bool {
+        matches!(self, Self::SetMark | Self::JumpToMark)
+    }
 }
 
 // Define a struct for the shortcuts map using a prefix tree
@@ -365,7 +464,10 @@ impl Shortcuts {
                     ShortcutTreeNode::Children(children) => {
                         // Check if this key already exists and is an action (conflict)
                         if let Some(existing_node) = children.get(key) {
-                            if matches!(existing_node, ShortcutTreeNode::Action(_)) {
+                            if matches!(
+                                existing_node,
+                                ShortcutTreeNode::Action(_) | ShortcutTreeNode::Namespace(_)
+                            ) {
                                 // This is a prefix conflict - action already exists at this key
                                 return Err(
                                     "Shortcut conflict: key sequence conflicts with existing shortcut".to_string()
@@ -376,11 +478,13 @@ impl Shortcuts {
                                     "Prefix conflict: shortcut conflicts with existing longer shortcut".to_string()
                                 );
                             }
+                        } else if action.takes_argument() {
+                            children.insert(key.clone(), ShortcutTreeNode::Namespace(action));
                         } else {
                             children.insert(key.clone(), ShortcutTreeNode::Action(action));
                         }
                     }
-                    ShortcutTreeNode::Action(_) => {
+                    ShortcutTreeNode::Action(_) | ShortcutTreeNode::Namespace(_) => {
                         // Cannot insert into an action node - this is a structural conflict
                         return Err(
                             "Prefix conflict: cannot insert shortcut through existing action node"
@@ -397,7 +501,7 @@ impl Shortcuts {
                             .or_insert_with(ShortcutTreeNode::new);
                         current_node = entry;
                     }
-                    ShortcutTreeNode::Action(_) => {
+                    ShortcutTreeNode::Action(_) | ShortcutTreeNode::Namespace(_) => {
                         // Cannot traverse through an action node - this is a prefix conflict
                         return Err(
                             "Prefix conflict: cannot traverse through existing action node"
@@ -411,12 +515,35 @@ impl Shortcuts {
         Ok(())
     }
 
+    // Split a count prefix off a key buffer, e.g. `5j` into 5 and `j`. Digits
+    // bound to a shortcut of their own don't start a count, and neither does 0.
+    #[must_use]
+    pub fn split_count<'a>(
+        &self,
+        key_buffer: &'a [ShortcutKey],
+    ) -> (Option<usize>, &'a [ShortcutKey]) {
+        let mut count: Option<usize> = None;
+        for (i, shortcut_key) in key_buffer.iter().enumerate() {
+            let digit = count_digit(shortcut_key).filter(|&digit| {
+                count.is_some()
+                    || (digit != 0
+                        && self.traverse_tree(std::slice::from_ref(shortcut_key))
+                            == TraverseResult::NoMatch)
+            });
+            let Some(digit) = digit else {
+                return (count, &key_buffer[i..]);
+            };
+            count = Some(count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+        (count, &[])
+    }
+
     // Traverse the shortcut tree with a key buffer, returning the result in a single traversal
     #[must_use]
     pub fn traverse_tree(&self, key_buffer: &[ShortcutKey]) -> TraverseResult {
         let mut current_node = &self.shortcut_tree;
 
-        for shortcut_key in key_buffer {
+        for (i, shortcut_key) in key_buffer.iter().enumerate() {
             match current_node {
                 ShortcutTreeNode::Children(children) => {
                     if let Some(child_node) = children.get(shortcut_key) {
@@ -426,6 +553,15 @@ impl Shortcuts {
                         return TraverseResult::NoMatch;
                     }
                 }
+                ShortcutTreeNode::Namespace(action) => {
+                    // A namespace takes exactly one letter after it
+                    return match argument_letter(shortcut_key) {
+                        Some(letter) if i == key_buffer.len() - 1 => {
+                            TraverseResult::ActionWithArgument(*action, letter)
+                        }
+                        _ => TraverseResult::NoMatch,
+                    };
+                }
                 ShortcutTreeNode::Action(_) => {
                     // Cannot traverse through action node
                     return TraverseResult::NoMatch;
@@ -436,6 +572,8 @@ impl Shortcuts {
         // Check what we found at the end of traversal
         match current_node {
             ShortcutTreeNode::Action(action) => TraverseResult::Action(*action),
+            // Wait for the letter the action applies to
+            ShortcutTreeNode::Namespace(_) => TraverseResult::Partial,
             ShortcutTreeNode::Children(children) => {
                 if children.is_empty() {
                     TraverseResult::NoMatch
@@ -503,7 +641,7 @@ impl Shortcuts {
         // Check if tree is empty (happens after deserialization)
         let tree_is_empty = match &self.shortcut_tree {
             ShortcutTreeNode::Children(children) => children.is_empty(),
-            ShortcutTreeNode::Action(_) => {
+            ShortcutTreeNode::Action(_) | ShortcutTreeNode::Namespace(_) => {
                 // Root node should never be an action node - this indicates a structural error
                 return Err(
                     "Invalid shortcut definition resulting in action without associated key"
@@ -601,12 +739,35 @@ pub fn default_shortcuts() -> Shortcuts {
         ShortcutAction::GoForwardInHistory,
     );
 
+    // Jump list navigation, returns to the selected entries too
+    add_shortcut(
+        KeyboardShortcut::new("o").with_alt(),
+        ShortcutAction::JumpBack,
+    );
+
+    add_shortcut(
+        KeyboardShortcut::new("i").with_alt(),
+        ShortcutAction::JumpForward,
+    );
+
     // File operations
     add_shortcut(KeyboardShortcut::new("d"), ShortcutAction::DeleteEntry);
+    add_shortcut(
+        KeyboardShortcut::new("d").with_shift(),
+        ShortcutAction::DeleteEntryPermanently,
+    );
 
     add_shortcut(KeyboardShortcut::new("r"), ShortcutAction::RenameEntry);
 
     add_shortcut(KeyboardShortcut::new("a"), ShortcutAction::AddEntry);
+    add_shortcut(
+        KeyboardShortcut::new("v").with_alt(),
+        ShortcutAction::PasteClipboardAsFile,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("t").with_alt(),
+        ShortcutAction::TouchEntry,
+    );
 
     add_shortcut(KeyboardShortcut::new("space"), ShortcutAction::SelectEntry);
 
@@ -632,10 +793,42 @@ pub fn default_shortcuts() -> Shortcuts {
         KeyboardShortcut::new("v").with_ctrl(),
         ShortcutAction::PasteEntry,
     );
+    add_shortcut(
+        KeyboardShortcut::new("p").with_alt(),
+        ShortcutAction::PasteSymlink,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("p").with_alt().with_shift(),
+        ShortcutAction::PasteHardlink,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("l").with_alt(),
+        ShortcutAction::CreateSymlink,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("s").with_alt().with_shift(),
+        ShortcutAction::ShareEntry,
+    );
 
     // Tabs
     add_shortcut(KeyboardShortcut::new("t"), ShortcutAction::CreateTab);
 
+    // Dual pane
+    add_shortcut(
+        KeyboardShortcut::new("d").with_alt(),
+        ShortcutAction::ToggleDualPane,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("tab"),
+        ShortcutAction::SwitchPaneFocus,
+    );
+    add_shortcut(KeyboardShortcut::new("f5"), ShortcutAction::CopyToOtherPane);
+    add_shortcut(KeyboardShortcut::new("f6"), ShortcutAction::MoveToOtherPane);
+    add_shortcut(
+        KeyboardShortcut::new("n").with_ctrl(),
+        ShortcutAction::NewWindow,
+    );
+
     // Tab switching shortcuts: Ctrl+number on Windows/Linux, Cmd+number on Mac
     #[cfg(target_os = "macos")]
     {
@@ -729,6 +922,11 @@ pub fn default_shortcuts() -> Shortcuts {
         ShortcutAction::ShowBookmarks,
     );
 
+    // Marks
+    add_shortcut(KeyboardShortcut::new("m"), ShortcutAction::SetMark);
+    add_shortcut(KeyboardShortcut::new("'"), ShortcutAction::JumpToMark);
+    add_shortcut(KeyboardShortcut::new("gm"), ShortcutAction::ShowMarks);
+
     // Volumes
     #[cfg(target_os = "macos")]
     add_shortcut(
@@ -748,6 +946,10 @@ pub fn default_shortcuts() -> Shortcuts {
         KeyboardShortcut::new("t").with_shift(),
         ShortcutAction::OpenTerminal,
     );
+    add_shortcut(
+        KeyboardShortcut::new("t").with_alt().with_shift(),
+        ShortcutAction::OpenSystemTerminal,
+    );
 
     add_shortcut(
         KeyboardShortcut::new("?").with_shift(),
@@ -763,16 +965,29 @@ pub fn default_shortcuts() -> Shortcuts {
         KeyboardShortcut::new("f").with_ctrl(),
         ShortcutAction::ActivateSearch,
     );
+    add_shortcut(KeyboardShortcut::new("f"), ShortcutAction::TypeAheadFilter);
+    add_shortcut(
+        KeyboardShortcut::new("f").with_ctrl().with_shift(),
+        ShortcutAction::SearchContents,
+    );
     add_shortcut(
         KeyboardShortcut::new("p").with_ctrl(),
         ShortcutAction::ShowTeleport,
     );
+    add_shortcut(
+        KeyboardShortcut::new("e").with_ctrl(),
+        ShortcutAction::ShowFileHistory,
+    );
 
     // Action history shortcuts
     add_shortcut(
         KeyboardShortcut::new("h").with_ctrl().with_shift(),
         ShortcutAction::ShowActionHistory,
     );
+    add_shortcut(
+        KeyboardShortcut::new("t").with_ctrl().with_shift(),
+        ShortcutAction::ShowTrash,
+    );
     add_shortcut(KeyboardShortcut::new("u"), ShortcutAction::Undo);
     add_shortcut(KeyboardShortcut::new("r").with_ctrl(), ShortcutAction::Redo);
 
@@ -789,6 +1004,11 @@ pub fn default_shortcuts() -> Shortcuts {
         ShortcutAction::OpenWithCommand,
     );
 
+    add_shortcut(
+        KeyboardShortcut::new("enter").with_alt(),
+        ShortcutAction::ShowProperties,
+    );
+
     add_shortcut(
         KeyboardShortcut::new("u").with_ctrl(),
         ShortcutAction::PageUp,
@@ -809,12 +1029,47 @@ pub fn default_shortcuts() -> Shortcuts {
         KeyboardShortcut::new("h").with_ctrl(),
         ShortcutAction::ToggleHiddenFiles,
     );
+    add_shortcut(
+        KeyboardShortcut::new("h").with_alt(),
+        ShortcutAction::ToggleHiddenFilesDefault,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("m").with_ctrl().with_shift(),
+        ShortcutAction::ToggleTouchMode,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("s").with_ctrl().with_shift(),
+        ShortcutAction::ToggleSafePreview,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("s").with_alt(),
+        ShortcutAction::ToggleDirSizes,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("f").with_alt(),
+        ShortcutAction::ToggleFlatten,
+    );
+    add_shortcut(
+        KeyboardShortcut::new("p").with_ctrl().with_shift(),
+        ShortcutAction::TogglePickerMode,
+    );
 
     // Copy operations to system clipboard
     add_shortcut(KeyboardShortcut::new("cp"), ShortcutAction::CopyPath);
     add_shortcut(KeyboardShortcut::new("cn"), ShortcutAction::CopyName);
 
     add_shortcut(KeyboardShortcut::new("gl"), ShortcutAction::GoToPath);
+    add_shortcut(KeyboardShortcut::new("gf"), ShortcutAction::FollowSymlink);
+    add_shortcut(KeyboardShortcut::new("gr"), ShortcutAction::ShowLinkTarget);
+    add_shortcut(KeyboardShortcut::new("gp"), ShortcutAction::ShowProfiles);
+    add_shortcut(KeyboardShortcut::new("gs"), ShortcutAction::ShowSessions);
+    add_shortcut(KeyboardShortcut::new("gd"), ShortcutAction::ShowDiff);
+
+    add_shortcut(KeyboardShortcut::new("z"), ShortcutAction::CycleImageZoom);
+    add_shortcut(
+        KeyboardShortcut::new("r").with_shift(),
+        ShortcutAction::RotatePreview,
+    );
 
     shortcuts
 }
@@ -894,6 +1149,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_namespace_takes_a_letter() {
+        use crate::config::shortcuts::TraverseResult;
+
+        let mut shortcuts = Shortcuts::new();
+        shortcuts
+            .add_shortcut(KeyboardShortcut::new("m"), ShortcutAction::SetMark)
+            .unwrap();
+        let key = |key| ShortcutKey {
+            key,
+            modifiers: Modifiers::NONE,
+        };
+
+        assert_eq!(
+            shortcuts.traverse_tree(&[key(Key::M)]),
+            TraverseResult::Partial
+        );
+        assert_eq!(
+            shortcuts.traverse_tree(&[key(Key::M), key(Key::A)]),
+            TraverseResult::ActionWithArgument(ShortcutAction::SetMark, 'a')
+        );
+        // Only a single letter completes the namespace
+        assert_eq!(
+            shortcuts.traverse_tree(&[key(Key::M), key(Key::Num1)]),
+            TraverseResult::NoMatch
+        );
+        assert_eq!(
+            shortcuts.traverse_tree(&[key(Key::M), key(Key::A), key(Key::B)]),
+            TraverseResult::NoMatch
+        );
+        // A namespace conflicts with longer shortcuts like an action does
+        let result = shortcuts.add_shortcut(KeyboardShortcut::new("mm"), ShortcutAction::MoveDown);
+        assert!(result.is_err(), "Expected conflict with the 'm' namespace");
+    }
+
     #[test]
     fn test_special_key_no_prefix_conflict() {
         let mut shortcuts = Shortcuts::new();
@@ -975,6 +1265,41 @@ mod tests {
 
         assert_eq!(no_match_command_only, TraverseResult::NoMatch);
     }
+
+    #[test]
+    fn test_split_count() {
+        let key = |key| ShortcutKey {
+            key,
+            modifiers: Modifiers::NONE,
+        };
+        let mut shortcuts = Shortcuts::new();
+        shortcuts
+            .add_shortcut(KeyboardShortcut::new("j"), ShortcutAction::MoveDown)
+            .unwrap();
+        shortcuts
+            .add_shortcut(KeyboardShortcut::new("9"), ShortcutAction::GoToLastEntry)
+            .unwrap();
+
+        let buffer = [key(Key::Num1), key(Key::Num2), key(Key::J)];
+        assert_eq!(shortcuts.split_count(&buffer), (Some(12), &buffer[2..]));
+
+        // Digits bound to a shortcut and a leading 0 aren't counts
+        let buffer = [key(Key::Num9), key(Key::J)];
+        assert_eq!(shortcuts.split_count(&buffer), (None, &buffer[..]));
+        let buffer = [key(Key::Num0), key(Key::J)];
+        assert_eq!(shortcuts.split_count(&buffer), (None, &buffer[..]));
+
+        // Once a count started, any digit continues it
+        let buffer = [key(Key::Num1), key(Key::Num9), key(Key::Num0)];
+        assert_eq!(shortcuts.split_count(&buffer), (Some(190), &buffer[3..]));
+
+        // Digits with modifiers are shortcuts
+        let ctrl_one = ShortcutKey {
+            key: Key::Num1,
+            modifiers: Modifiers::CTRL,
+        };
+        assert_eq!(shortcuts.split_count(&[ctrl_one]).0, None);
+    }
 }
 
 // Helper functions for the Shortcuts type
@@ -1039,6 +1364,12 @@ pub mod shortcuts_helpers {
                         "pageup" => "PageUp".to_string(),
                         "pagedown" => "PageDown".to_string(),
                         "insert" => "Insert".to_string(),
+                        key if key.len() > 1
+                            && key.starts_with('f')
+                            && KeyboardShortcut::parse_special_key(key).is_some() =>
+                        {
+                            key.to_uppercase()
+                        }
                         // If not a special key, use the key as-is (could be multi-character like "gg")
                         _ => shortcut.key.clone(),
                     }
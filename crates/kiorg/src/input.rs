@@ -1,9 +1,14 @@
 use crate::config::shortcuts::ShortcutKey;
-use crate::config::shortcuts::{ShortcutAction, TraverseResult};
-use crate::ui::center_panel;
-use crate::ui::popup::{add_entry, bookmark, file_drop, preview as popup_preview, sort_toggle};
-use crate::ui::terminal;
+use crate::config::shortcuts::{ShortcutAction, TraverseResult, argument_letter};
+use crate::ui::popup::{
+    add_entry, bookmark, content_search, file_drop, preview as popup_preview, properties,
+    sort_toggle, touch,
+};
+use crate::ui::{dual_pane, terminal};
+use crate::utils::link::LinkKind;
+use crate::utils::transfer::TransferKind;
 use egui::{Key, Modifiers};
+use std::path::Path;
 
 use super::app::Kiorg;
 use super::ui::popup::PopupType;
@@ -13,13 +18,56 @@ fn is_cancel_keys(key: Key) -> bool {
     key == Key::Escape || key == Key::Q
 }
 
-// Helper function to handle a shortcut action
+// Mark an entry, or unmark it and drop it from the clipboard
+fn toggle_mark(app: &mut Kiorg, path: &Path) {
+    let tab = app.tab_manager.current_tab_mut();
+    if tab.marked_entries.contains(path) {
+        // Unmark the entry
+        tab.marked_entries.remove(path);
+
+        // If this entry is in the clipboard as a cut or copy operation, remove it
+        match &mut app.clipboard {
+            Some(crate::app::Clipboard::Cut(paths) | crate::app::Clipboard::Copy(paths)) => {
+                // Remove the path from the clipboard's paths list
+                paths.retain(|p| p != path);
+
+                // If the clipboard's paths list becomes empty, set the clipboard to None
+                if paths.is_empty() {
+                    app.clipboard = None;
+                }
+            }
+            None => {}
+        }
+    } else {
+        // Mark the entry
+        tab.marked_entries.insert(path.to_path_buf());
+    }
+}
+
+// Helper function to handle an action taking the letter typed after its shortcut
+fn handle_argument_action(app: &mut Kiorg, action: ShortcutAction, letter: char) {
+    match action {
+        ShortcutAction::SetMark => app.set_jump_mark(letter),
+        ShortcutAction::JumpToMark => app.jump_to_mark(letter),
+        _ => {}
+    }
+}
+
+// Helper function to handle a shortcut action, `count` is the number typed
+// before the shortcut, e.g. 5 for `5j`
 #[allow(clippy::too_many_lines)]
-fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &ShortcutAction) {
+fn handle_shortcut_action(
+    app: &mut Kiorg,
+    ctx: &egui::Context,
+    action: &ShortcutAction,
+    count: Option<usize>,
+) {
+    let repeat = count.unwrap_or(1).min(isize::MAX as usize) as isize;
     match action {
         ShortcutAction::ShowFilePreview => popup_preview::handle_show_file_popup(app, ctx),
-        ShortcutAction::MoveDown => app.move_selection(1),
-        ShortcutAction::MoveUp => app.move_selection(-1),
+        ShortcutAction::ShowDiff => popup_preview::diff::open(app),
+        ShortcutAction::MoveDown => app.move_selection(repeat),
+        ShortcutAction::MoveUp => app.move_selection(-repeat),
         ShortcutAction::GoToParentDirectory => {
             let parent_path = app
                 .tab_manager
@@ -40,6 +88,9 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
                 }
             }
         }
+        ShortcutAction::OpenDirectoryOrFile if app.picker.is_some() => {
+            crate::ui::picker::open_or_pick(app, ctx);
+        }
         ShortcutAction::OpenDirectoryOrFile => {
             let tab = app.tab_manager.current_tab_ref();
             if let Some(selected_entry) = tab.entries.get(tab.selected_index) {
@@ -53,7 +104,12 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
                 }
             }
         }
+        // With a count, `5gg` and `5G` go to the fifth entry like in vim
+        ShortcutAction::GoToFirstEntry | ShortcutAction::GoToLastEntry if count.is_some() => {
+            app.go_to_entry_number(count.unwrap_or(1));
+        }
         ShortcutAction::GoToFirstEntry => {
+            app.record_jump();
             let tab = app.tab_manager.current_tab_mut();
             if !tab.entries.is_empty() {
                 if let Some(&index) = tab.get_cached_filtered_entries().first() {
@@ -64,6 +120,7 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
             }
         }
         ShortcutAction::GoToLastEntry => {
+            app.record_jump();
             let tab = app.tab_manager.current_tab_mut();
             if !tab.entries.is_empty() {
                 if let Some(&index) = tab.get_cached_filtered_entries().last() {
@@ -73,58 +130,52 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
                 }
             }
         }
-        ShortcutAction::DeleteEntry => app.delete_selected_entry(),
+        ShortcutAction::DeleteEntry => match count {
+            Some(count) => app.delete_entries_from_selection(count, false),
+            None => app.delete_selected_entry(),
+        },
+        ShortcutAction::DeleteEntryPermanently => match count {
+            Some(count) => app.delete_entries_from_selection(count, true),
+            None => app.delete_selected_entry_permanently(),
+        },
         ShortcutAction::RenameEntry => app.rename_selected_entry(),
-        ShortcutAction::AddEntry => app.show_popup = Some(PopupType::AddEntry(String::new())),
+        ShortcutAction::AddEntry => add_entry::open(app),
+        ShortcutAction::PasteClipboardAsFile => add_entry::open_paste(app),
+        ShortcutAction::TouchEntry => touch::open(app),
+        ShortcutAction::ShareEntry => crate::ui::popup::share::open(app),
         ShortcutAction::SelectEntry => {
-            let tab = app.tab_manager.current_tab_mut();
-
-            if tab.is_range_selection_active() {
+            if app
+                .tab_manager
+                .current_tab_ref()
+                .is_range_selection_active()
+            {
                 return;
             }
-
-            if let Some(entry) = tab.entries.get(tab.selected_index) {
-                let path = &entry.meta.path;
-                if tab.marked_entries.contains(path) {
-                    // Unmark the entry
-                    tab.marked_entries.remove(path);
-
-                    // If this entry is in the clipboard as a cut or copy operation, remove it
-                    match &mut app.clipboard {
-                        Some(
-                            crate::app::Clipboard::Cut(paths) | crate::app::Clipboard::Copy(paths),
-                        ) => {
-                            // Remove the path from the clipboard's paths list
-                            paths.retain(|p| p != path);
-
-                            // If the clipboard's paths list becomes empty, set the clipboard to None
-                            if paths.is_empty() {
-                                app.clipboard = None;
-                            }
-                        }
-                        None => {}
-                    }
-                } else {
-                    // Mark the entry
-                    tab.marked_entries.insert(path.clone());
-                }
+            // With a count, `3 space` toggles the next three entries and
+            // selects the last of them
+            let paths = app.entries_from_selection(count.unwrap_or(1));
+            for path in &paths {
+                toggle_mark(app, path);
+            }
+            if paths.len() > 1 {
+                app.move_selection(paths.len() as isize - 1);
             }
         }
         ShortcutAction::CopyEntry => app.copy_selected_entries(),
         ShortcutAction::CutEntry => app.cut_selected_entries(),
-        ShortcutAction::PasteEntry => {
-            let tab = app.tab_manager.current_tab_mut();
-            // Clear marked entries after paste operation
-            tab.marked_entries.clear();
-            if center_panel::handle_clipboard_operations(
-                &mut app.clipboard,
-                &tab.current_path,
-                &mut tab.action_history,
-                &mut app.toasts,
-            ) {
-                app.refresh_entries();
-            }
+        ShortcutAction::PasteEntry => crate::ui::popup::fan_out_paste::paste(app),
+        ShortcutAction::PasteSymlink => {
+            let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+            app.paste_clipboard_as_link(current_path, LinkKind::Symlink);
+        }
+        ShortcutAction::PasteHardlink => {
+            let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+            app.paste_clipboard_as_link(current_path, LinkKind::Hardlink);
         }
+        ShortcutAction::CreateSymlink => app.create_symlink_to_selected(),
+        ShortcutAction::FollowSymlink => app.follow_selected_symlink(),
+        ShortcutAction::ShowLinkTarget => app.show_selected_link_target(ctx),
+        ShortcutAction::NewWindow => app.new_window_requested = true,
         ShortcutAction::CreateTab => {
             let current_path = app.tab_manager.current_tab_ref().current_path.clone();
             app.tab_manager.add_tab(current_path);
@@ -173,7 +224,10 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
             }
         }
         ShortcutAction::ToggleBookmark => bookmark::toggle_bookmark(app),
-        ShortcutAction::ShowBookmarks => app.show_popup = Some(PopupType::Bookmarks(0)),
+        ShortcutAction::ShowBookmarks => crate::ui::popup::bookmark::open(app),
+        ShortcutAction::ShowMarks => app.show_popup = Some(PopupType::JumpMarks),
+        // Only triggered along with the letter typed after them
+        ShortcutAction::SetMark | ShortcutAction::JumpToMark => {}
         #[cfg(target_os = "windows")]
         ShortcutAction::ShowWindowsDrives => app.show_popup = Some(PopupType::WindowsDrives(0)),
         #[cfg(target_os = "macos")]
@@ -190,6 +244,7 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
                 }
             }
         }
+        ShortcutAction::OpenSystemTerminal => app.open_system_terminal(),
         ShortcutAction::ShowHelp => {
             // Toggle help popup
             if matches!(app.show_popup, Some(PopupType::Help)) {
@@ -198,22 +253,47 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
                 app.show_popup = Some(PopupType::Help);
             }
         }
+        ShortcutAction::Exit if app.picker.is_some() => crate::ui::picker::cancel(app, ctx),
         ShortcutAction::Exit => app.show_popup = Some(PopupType::Exit),
-        ShortcutAction::ActivateSearch => app.search_bar.activate(),
+        ShortcutAction::ActivateSearch => {
+            app.type_ahead.close();
+            app.search_bar.activate();
+        }
+        ShortcutAction::TypeAheadFilter => crate::ui::type_ahead::activate(app),
+        ShortcutAction::SearchContents => {
+            let pattern = app.search_bar.query.clone().unwrap_or_default();
+            content_search::open(app, pattern);
+        }
         ShortcutAction::ShowTeleport => {
             app.show_popup = Some(PopupType::Teleport(
                 crate::ui::popup::teleport::TeleportState::default(),
             ));
         }
+        ShortcutAction::ShowFileHistory => {
+            app.show_popup = Some(PopupType::FileHistory(
+                crate::ui::popup::file_history::FileHistoryState::default(),
+            ));
+        }
         ShortcutAction::GoToPath => {
             app.show_goto_path_popup();
         }
+        ShortcutAction::ShowProfiles => {
+            app.show_popup = Some(PopupType::Profiles(
+                crate::ui::popup::profile::ProfileState::default(),
+            ));
+        }
+        ShortcutAction::ShowSessions => {
+            app.show_popup = Some(PopupType::Sessions(
+                crate::ui::popup::session::SessionState::default(),
+            ));
+        }
         ShortcutAction::ShowSortToggle => {
             app.show_popup = Some(PopupType::SortToggle);
         }
         ShortcutAction::ShowActionHistory => {
             app.show_popup = Some(PopupType::ActionHistory);
         }
+        ShortcutAction::ShowTrash => crate::ui::popup::trash::open(app),
         ShortcutAction::Undo => {
             crate::ui::popup::action_history::undo_last_action(app);
         }
@@ -222,6 +302,18 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
         }
         ShortcutAction::GoBackInHistory => app.navigate_history_back(),
         ShortcutAction::GoForwardInHistory => app.navigate_history_forward(),
+        ShortcutAction::JumpBack => app.jump_back(),
+        ShortcutAction::JumpForward => app.jump_forward(),
+        ShortcutAction::ToggleDualPane => dual_pane::toggle(app),
+        ShortcutAction::SwitchPaneFocus => {
+            // Without a second pane Tab keeps moving the keyboard focus between widgets
+            if app.dual_pane.is_some() {
+                ctx.memory_mut(|m| m.move_focus(egui::FocusDirection::None));
+                dual_pane::switch_focus(app);
+            }
+        }
+        ShortcutAction::CopyToOtherPane => dual_pane::transfer_to_other(app, TransferKind::Copy),
+        ShortcutAction::MoveToOtherPane => dual_pane::transfer_to_other(app, TransferKind::Move),
         ShortcutAction::SwitchToNextTab => {
             let current_index = app.tab_manager.get_current_tab_index();
             let total_tabs = app.tab_manager.get_tab_count();
@@ -248,6 +340,7 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
                 app.show_popup = Some(PopupType::OpenWith);
             }
         }
+        ShortcutAction::ShowProperties => properties::open(app, ctx),
         ShortcutAction::PageUp => app.move_selection_by_page(-1),
         ShortcutAction::PageDown => app.move_selection_by_page(1),
         ShortcutAction::SelectAllEntries => app.select_all_entries(),
@@ -265,6 +358,52 @@ fn handle_shortcut_action(app: &mut Kiorg, ctx: &egui::Context, action: &Shortcu
             app.tab_manager.toggle_show_hidden();
             app.refresh_entries();
         }
+        ShortcutAction::ToggleHiddenFilesDefault => {
+            app.tab_manager.toggle_default_show_hidden();
+            app.refresh_entries();
+        }
+        ShortcutAction::TogglePickerMode => {
+            if app.picker.is_some() {
+                crate::ui::picker::cancel(app, ctx);
+            } else {
+                crate::ui::picker::enter(app, ctx);
+            }
+        }
+        ShortcutAction::ToggleTouchMode => {
+            app.set_touch_mode(ctx, !app.touch_mode);
+            if app.touch_mode {
+                app.toasts.info("Touch mode enabled");
+            } else {
+                app.toasts.info("Touch mode disabled");
+            }
+        }
+        ShortcutAction::ToggleFlatten => app.toggle_flatten(),
+        ShortcutAction::ToggleDirSizes => {
+            app.show_dir_sizes = !app.show_dir_sizes;
+            if app.show_dir_sizes {
+                app.toasts.info("Directory sizes enabled");
+            } else {
+                app.toasts.info("Directory sizes disabled");
+            }
+        }
+        ShortcutAction::ToggleSafePreview => {
+            app.set_safe_preview(!app.safe_preview);
+            if app.safe_preview {
+                app.toasts.info("Safe preview enabled");
+            } else {
+                app.toasts.info("Safe preview disabled");
+            }
+        }
+        ShortcutAction::CycleImageZoom => {
+            if let Some(crate::models::preview_content::PreviewContent::Image(_)) =
+                &app.preview_content
+            {
+                crate::ui::preview::image::cycle_panel_zoom(ctx);
+            }
+        }
+        ShortcutAction::RotatePreview => {
+            // Only handled by the PDF viewer popup
+        }
         ShortcutAction::CopyPath => {
             let tab = app.tab_manager.current_tab_ref();
             if let Some(selected_entry) = tab.entries.get(tab.selected_index) {
@@ -304,19 +443,101 @@ fn process_key(
     // Handle special modal states first based on the show_popup field
     match &app.show_popup {
         #[allow(clippy::collapsible_match)]
-        Some(PopupType::Preview) | Some(PopupType::Pdf(_)) | Some(PopupType::Ebook(_)) => {
+        Some(PopupType::Preview) => {
+            if popup_preview::find::handle_key(ctx, key, modifiers)
+                || popup_preview::text_view::handle_key(ctx, key, modifiers)
+            {
+                return;
+            }
+            if key == Key::P
+                && modifiers.command
+                && app
+                    .preview_content
+                    .as_ref()
+                    .is_some_and(crate::models::preview_content::PreviewContent::is_printable)
+            {
+                app.print_selected_entry();
+                return;
+            }
+            if is_cancel_keys(key) {
+                popup_preview::find::close(ctx);
+                popup_preview::text_view::reset(ctx);
+                popup_preview::close_popup(app);
+                return;
+            }
+        }
+        Some(PopupType::Pdf(_)) | Some(PopupType::Ebook(_)) => {
+            if key == Key::P
+                && modifiers.command
+                && matches!(app.show_popup, Some(PopupType::Pdf(_)))
+            {
+                app.print_selected_entry();
+                return;
+            }
             if is_cancel_keys(key) {
                 popup_preview::close_popup(app);
                 return;
             }
             // Special handling for PDF navigation follows below
         }
-        Some(PopupType::Image(_)) | Some(PopupType::Plugin(_)) | Some(PopupType::Video(_)) => {
+        Some(PopupType::Plugin(_)) => {
+            if popup_preview::find::handle_key(ctx, key, modifiers) {
+                return;
+            }
             if is_cancel_keys(key) {
+                popup_preview::find::close(ctx);
                 popup_preview::close_popup(app);
             }
             return;
         }
+        Some(PopupType::Image(_)) | Some(PopupType::Video(_)) => {
+            if key == Key::P
+                && modifiers.command
+                && matches!(app.show_popup, Some(PopupType::Image(_)))
+            {
+                app.print_selected_entry();
+            } else if is_cancel_keys(key) {
+                popup_preview::close_popup(app);
+            }
+            return;
+        }
+        Some(PopupType::FanOutPaste(_)) => {
+            if key == Key::Enter {
+                crate::ui::popup::fan_out_paste::confirm(app);
+            } else if is_cancel_keys(key) {
+                crate::ui::popup::fan_out_paste::cancel(app);
+            }
+            return;
+        }
+        Some(PopupType::ContentSearch(_)) => {
+            // Q is typed into the pattern, only Escape closes
+            match key {
+                Key::Enter => content_search::confirm(app, ctx),
+                Key::ArrowDown => content_search::move_selection(app, 1),
+                Key::ArrowUp => content_search::move_selection(app, -1),
+                Key::Escape => app.show_popup = None,
+                _ => {}
+            }
+            return;
+        }
+        Some(PopupType::EditBookmark(_)) => {
+            // Letters are typed into the fields, only Escape goes back to the list
+            if key == Key::Enter {
+                bookmark::confirm_edit(app);
+            } else if key == Key::Escape {
+                bookmark::cancel_edit(app);
+            }
+            return;
+        }
+        Some(PopupType::Touch(_)) => {
+            // Q is typed into the name, only Escape closes
+            if key == Key::Enter {
+                touch::confirm(app);
+            } else if key == Key::Escape {
+                app.show_popup = None;
+            }
+            return;
+        }
         Some(PopupType::Exit) => {
             if key == Key::Enter {
                 crate::ui::popup::exit::confirm_exit(app);
@@ -347,7 +568,7 @@ fn process_key(
             }
             return;
         }
-        Some(PopupType::About) => {
+        Some(PopupType::About) | Some(PopupType::Properties(_)) => {
             if is_cancel_keys(key) {
                 app.show_popup = None;
             }
@@ -359,6 +580,16 @@ fn process_key(
             }
             return;
         }
+        Some(PopupType::JumpMarks) => {
+            // Letters name marks, so only Escape closes the popup
+            if key == Key::Escape {
+                app.show_popup = None;
+            } else if let Some(letter) = argument_letter(&ShortcutKey { key, modifiers }) {
+                app.show_popup = None;
+                app.jump_to_mark(letter);
+            }
+            return;
+        }
         #[allow(clippy::collapsible_match)]
         Some(PopupType::AddEntry(_)) => {
             if add_entry::handle_key_press(ctx, app) {
@@ -387,18 +618,91 @@ fn process_key(
             // Volumes popup input is handled in show_volumes_popup
             return;
         }
+        Some(PopupType::ArchivePassword(_)) => {
+            // Escape is handled by the text input popup itself
+            if key == Key::Enter {
+                crate::ui::popup::archive_password::handle_confirm(app, ctx);
+            }
+            return;
+        }
+        Some(PopupType::Decrypt(_)) => {
+            // Escape is handled by the text input popup itself
+            if key == Key::Enter {
+                crate::ui::popup::encryption::handle_decrypt_confirm(app, ctx);
+            }
+            return;
+        }
+        Some(PopupType::Encrypt(_)) => {
+            // Encrypt popup handles its own input - just return
+            return;
+        }
+        Some(PopupType::PdfPassword(_)) => {
+            // Escape is handled by the text input popup itself
+            if key == Key::Enter {
+                crate::ui::popup::pdf_password::handle_confirm(app, ctx);
+            }
+            return;
+        }
+        Some(PopupType::Checksum(_)) => {
+            if is_cancel_keys(key) {
+                crate::ui::popup::checksum::cancel(app);
+            }
+            return;
+        }
+        Some(PopupType::SplitProgress(_)) => {
+            if is_cancel_keys(key) {
+                crate::ui::popup::split::cancel(app);
+            }
+            return;
+        }
+        Some(PopupType::Duplicates(_)) => {
+            // Marking and removal are done with the mouse
+            if is_cancel_keys(key) {
+                crate::ui::popup::duplicates::close(app);
+            }
+            return;
+        }
+        Some(PopupType::Split(_)) => {
+            // Escape is handled by the text input popup itself
+            if key == Key::Enter {
+                crate::ui::popup::split::handle_confirm(app, ctx);
+            }
+            return;
+        }
         Some(PopupType::DeleteProgress(_)) => {
-            // Delete progress popup doesn't handle input - just return
+            // Progress popups don't handle input - just return
             return;
         }
-        Some(PopupType::Teleport(_)) => {
-            // Teleport popup handles its own input - just return
+        Some(
+            PopupType::Teleport(_)
+            | PopupType::FileHistory(_)
+            | PopupType::Profiles(_)
+            | PopupType::Sessions(_)
+            | PopupType::Trash(_),
+        ) => {
+            // Fuzzy search popups handle their own input - just return
             return;
         }
         Some(PopupType::GoToPath(_)) => {
             // GoToPath popup handles its own input - just return
             return;
         }
+        Some(PopupType::Share(_)) => {
+            // Share popup handles its own input - just return
+            return;
+        }
+        Some(PopupType::LanSend(_)) => {
+            // Send to device popup handles its own input - just return
+            return;
+        }
+        Some(PopupType::LanReceive(_)) => {
+            if key == Key::Enter {
+                crate::ui::popup::lan_receive::accept(app);
+            } else if is_cancel_keys(key) {
+                crate::ui::popup::lan_receive::decline(app);
+            }
+            return;
+        }
         Some(PopupType::SortToggle) => {
             if is_cancel_keys(key) {
                 app.show_popup = None;
@@ -433,6 +737,14 @@ fn process_key(
         return;
     }
 
+    if let Some(PopupType::Ebook(ebook_viewer)) = &mut app.show_popup {
+        use crate::ui::popup::ebook_viewer;
+        if let ebook_viewer::EbookViewer::Loaded(content) = ebook_viewer.as_mut() {
+            ebook_viewer::handle_preview_popup_input_ebook(content, key, modifiers);
+        }
+        return;
+    }
+
     // Handle ESC key to clear search filter when search is active but not focused
     if key == Key::Escape && app.search_bar.query.is_some() && !app.search_bar.focus {
         app.search_bar.close();
@@ -442,13 +754,31 @@ fn process_key(
         return;
     }
 
+    // Clear the type-ahead filter kept after typing it
+    if key == Key::Escape && app.type_ahead.active() {
+        crate::ui::type_ahead::close(app);
+        return;
+    }
+
     // Add current key with modifiers to buffer for sequence matching
     app.key_buffer.push(ShortcutKey { key, modifiers });
 
-    match app.get_shortcuts().traverse_tree(&app.key_buffer) {
+    let shortcuts = app.get_shortcuts();
+    let (count, keys) = shortcuts.split_count(&app.key_buffer);
+    let result = if keys.is_empty() {
+        // Only a count so far, wait for the action it applies to
+        TraverseResult::Partial
+    } else {
+        shortcuts.traverse_tree(keys)
+    };
+    match result {
         TraverseResult::Action(action) => {
             app.key_buffer.clear();
-            handle_shortcut_action(app, ctx, &action);
+            handle_shortcut_action(app, ctx, &action, count);
+        }
+        TraverseResult::ActionWithArgument(action, letter) => {
+            app.key_buffer.clear();
+            handle_argument_action(app, action, letter);
         }
         TraverseResult::Partial => {
             // Keep buffer as is, wait for more keys
@@ -0,0 +1,373 @@
+//! Just enough of multicast DNS (RFC 6762) and DNS-SD (RFC 6763) to announce
+//! and browse a single service type
+//!
+//! Responses carry the PTR, SRV, TXT and A records of the instance in the
+//! answer section, so one packet is enough to find a peer.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+pub const MDNS_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+pub const MDNS_PORT: u16 = 5353;
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_SRV: u16 = 33;
+const CLASS_IN: u16 = 1;
+/// Cache flush bit of records that are unique to the host
+const CACHE_FLUSH: u16 = 0x8000;
+const FLAGS_RESPONSE: u16 = 0x8400;
+const TTL: u32 = 120;
+/// Longest DNS label in bytes
+const MAX_LABEL_LEN: usize = 63;
+/// Compression pointers followed before a name is considered malformed
+const MAX_JUMPS: usize = 16;
+
+/// A service instance announced by this host
+#[derive(Debug, Clone)]
+pub struct Announcement {
+    /// Instance name shown to peers
+    pub instance: String,
+    /// Host name without the `.local` suffix
+    pub host: String,
+    pub port: u16,
+    pub addrs: Vec<Ipv4Addr>,
+}
+
+/// A service instance found on the network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found {
+    pub instance: String,
+    pub addr: SocketAddr,
+}
+
+fn labels(name: &str) -> Vec<String> {
+    name.split('.')
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Truncate `label` to the longest label DNS allows
+pub fn truncate_label(label: &str) -> &str {
+    if label.len() <= MAX_LABEL_LEN {
+        return label;
+    }
+    let mut end = MAX_LABEL_LEN;
+    while !label.is_char_boundary(end) {
+        end -= 1;
+    }
+    &label[..end]
+}
+
+fn same_name(a: &[String], b: &[String]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq_ignore_ascii_case(b))
+}
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn new(flags: u16, questions: u16, answers: u16) -> Self {
+        let mut writer = Self { buf: Vec::new() };
+        for value in [0, flags, questions, answers, 0, 0] {
+            writer.u16(value);
+        }
+        writer
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn name(&mut self, labels: &[String]) {
+        for label in labels {
+            let label = truncate_label(label);
+            self.buf.push(label.len() as u8);
+            self.buf.extend_from_slice(label.as_bytes());
+        }
+        self.buf.push(0);
+    }
+
+    /// Write a resource record, `rdata` fills in the record data
+    fn record(&mut self, name: &[String], kind: u16, class: u16, rdata: impl FnOnce(&mut Self)) {
+        self.name(name);
+        self.u16(kind);
+        self.u16(class);
+        self.u32(TTL);
+        let len_at = self.buf.len();
+        self.u16(0);
+        rdata(self);
+        let len = (self.buf.len() - len_at - 2) as u16;
+        self.buf[len_at..len_at + 2].copy_from_slice(&len.to_be_bytes());
+    }
+}
+
+/// Query for the instances of `service`, e.g. `_kiorg-send._tcp.local`
+#[must_use]
+pub fn query(service: &str) -> Vec<u8> {
+    let mut writer = Writer::new(0, 1, 0);
+    writer.name(&labels(service));
+    writer.u16(TYPE_PTR);
+    writer.u16(CLASS_IN);
+    writer.buf
+}
+
+/// Response announcing `announcement` as an instance of `service`
+#[must_use]
+pub fn response(service: &str, announcement: &Announcement) -> Vec<u8> {
+    let service = labels(service);
+    let mut instance = vec![announcement.instance.clone()];
+    instance.extend(service.iter().cloned());
+    let host = vec![announcement.host.clone(), "local".to_string()];
+
+    let mut writer = Writer::new(FLAGS_RESPONSE, 0, 3 + announcement.addrs.len() as u16);
+    writer.record(&service, TYPE_PTR, CLASS_IN, |w| w.name(&instance));
+    writer.record(&instance, TYPE_SRV, CLASS_IN | CACHE_FLUSH, |w| {
+        w.u16(0);
+        w.u16(0);
+        w.u16(announcement.port);
+        w.name(&host);
+    });
+    writer.record(&instance, TYPE_TXT, CLASS_IN | CACHE_FLUSH, |w| {
+        w.buf.push(3);
+        w.buf.extend_from_slice(b"v=1");
+    });
+    for addr in &announcement.addrs {
+        writer.record(&host, TYPE_A, CLASS_IN | CACHE_FLUSH, |w| {
+            w.buf.extend_from_slice(&addr.octets());
+        });
+    }
+    writer.buf
+}
+
+struct Reader<'a> {
+    packet: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn u16(&mut self) -> Option<u16> {
+        let bytes = self.packet.get(self.pos..self.pos + 2)?;
+        self.pos += 2;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.packet.get(self.pos..self.pos + 4)?;
+        self.pos += 4;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read a possibly compressed name at the current position
+    fn name(&mut self) -> Option<Vec<String>> {
+        let mut labels = Vec::new();
+        let mut pos = self.pos;
+        let mut jumps = 0;
+        loop {
+            let len = *self.packet.get(pos)? as usize;
+            if len & 0xC0 == 0xC0 {
+                let low = *self.packet.get(pos + 1)? as usize;
+                if jumps == 0 {
+                    self.pos = pos + 2;
+                }
+                jumps += 1;
+                if jumps > MAX_JUMPS {
+                    return None;
+                }
+                pos = ((len & 0x3F) << 8) | low;
+                continue;
+            }
+            if len == 0 {
+                if jumps == 0 {
+                    self.pos = pos + 1;
+                }
+                return Some(labels);
+            }
+            let label = self.packet.get(pos + 1..pos + 1 + len)?;
+            labels.push(String::from_utf8_lossy(label).to_string());
+            pos += 1 + len;
+        }
+    }
+}
+
+/// Whether `packet` is a query asking for instances of `service`
+#[must_use]
+pub fn is_query_for(packet: &[u8], service: &str) -> bool {
+    let service = labels(service);
+    let mut reader = Reader { packet, pos: 2 };
+    let (Some(flags), Some(questions)) = (reader.u16(), reader.u16()) else {
+        return false;
+    };
+    if flags & 0x8000 != 0 {
+        return false;
+    }
+    reader.pos = 12;
+    for _ in 0..questions {
+        let Some(name) = reader.name() else {
+            return false;
+        };
+        let (Some(kind), Some(_class)) = (reader.u16(), reader.u16()) else {
+            return false;
+        };
+        if same_name(&name, &service) && (kind == TYPE_PTR || kind == 255) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Instances of `service` announced in the response `packet` from `source`
+///
+/// Instances whose address record is missing are assumed to live at `source`.
+/// Records with a TTL of zero announce that an instance went away and are
+/// ignored.
+#[must_use]
+pub fn parse_response(packet: &[u8], service: &str, source: IpAddr) -> Vec<Found> {
+    parse(packet, service, source).unwrap_or_default()
+}
+
+fn parse(packet: &[u8], service: &str, source: IpAddr) -> Option<Vec<Found>> {
+    let service = labels(service);
+    let mut reader = Reader { packet, pos: 2 };
+    let flags = reader.u16()?;
+    if flags & 0x8000 == 0 {
+        return None;
+    }
+    let questions = reader.u16()?;
+    let records = reader.u16()? as usize + reader.u16()? as usize + reader.u16()? as usize;
+    for _ in 0..questions {
+        reader.name()?;
+        reader.pos += 4;
+    }
+
+    let mut instances = Vec::new();
+    let mut services: HashMap<String, (u16, String)> = HashMap::new();
+    let mut addrs: HashMap<String, Ipv4Addr> = HashMap::new();
+    for _ in 0..records {
+        let name = reader.name()?;
+        let kind = reader.u16()?;
+        let _class = reader.u16()?;
+        let ttl = reader.u32()?;
+        let len = reader.u16()? as usize;
+        let end = reader.pos + len;
+        if end > packet.len() {
+            return None;
+        }
+        if ttl > 0 {
+            match kind {
+                TYPE_PTR if same_name(&name, &service) => {
+                    let instance = reader.name()?;
+                    if instance.len() == service.len() + 1 && same_name(&instance[1..], &service) {
+                        instances.push(instance[0].clone());
+                    }
+                }
+                TYPE_SRV if name.len() == service.len() + 1 && same_name(&name[1..], &service) => {
+                    reader.pos += 4;
+                    let port = reader.u16()?;
+                    let target = reader.name()?.join(".").to_lowercase();
+                    services.insert(name[0].clone(), (port, target));
+                }
+                TYPE_A if len == 4 => {
+                    let b = &packet[reader.pos..end];
+                    addrs
+                        .entry(name.join(".").to_lowercase())
+                        .or_insert(Ipv4Addr::new(b[0], b[1], b[2], b[3]));
+                }
+                _ => {}
+            }
+        }
+        reader.pos = end;
+    }
+
+    Some(
+        instances
+            .into_iter()
+            .filter_map(|instance| {
+                let (port, target) = services.get(&instance)?;
+                let ip = addrs.get(target).map_or(source, |addr| IpAddr::V4(*addr));
+                Some(Found {
+                    instance,
+                    addr: SocketAddr::new(ip, *port),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVICE: &str = "_kiorg-send._tcp.local";
+
+    #[test]
+    fn test_query() {
+        let packet = query(SERVICE);
+        assert!(is_query_for(&packet, SERVICE));
+        assert!(!is_query_for(&packet, "_http._tcp.local"));
+    }
+
+    #[test]
+    fn test_response_round_trip() {
+        let announcement = Announcement {
+            instance: "Alice's laptop.home".to_string(),
+            host: "alice".to_string(),
+            port: 41000,
+            addrs: vec![Ipv4Addr::new(192, 168, 1, 20)],
+        };
+        let packet = response(SERVICE, &announcement);
+        assert!(!is_query_for(&packet, SERVICE));
+
+        let source = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(
+            parse_response(&packet, SERVICE, source),
+            vec![Found {
+                instance: "Alice's laptop.home".to_string(),
+                addr: "192.168.1.20:41000".parse().unwrap(),
+            }]
+        );
+        assert!(parse_response(&packet, "_http._tcp.local", source).is_empty());
+    }
+
+    #[test]
+    fn test_compressed_names() {
+        // PTR record whose instance name points back at the question
+        let mut packet = vec![0, 0, 0x84, 0, 0, 1, 0, 2, 0, 0, 0, 0];
+        for label in ["_kiorg-send", "_tcp", "local"] {
+            packet.push(label.len() as u8);
+            packet.extend_from_slice(label.as_bytes());
+        }
+        packet.extend_from_slice(&[0, 0, 12, 0, 1]);
+        // PTR: name -> pointer to offset 12
+        packet.extend_from_slice(&[0xC0, 12, 0, 12, 0, 1, 0, 0, 0, 120, 0, 6]);
+        packet.extend_from_slice(&[3, b'p', b'c', b'1', 0xC0, 12]);
+        // SRV: pc1 + pointer, port 9000, target pointer to the service name
+        let srv_name = packet.len() - 6;
+        packet.extend_from_slice(&[0xC0, srv_name as u8, 0, 33, 0, 1, 0, 0, 0, 120, 0, 8]);
+        packet.extend_from_slice(&[0, 0, 0, 0, 0x23, 0x28, 0xC0, 12]);
+
+        let source = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7));
+        assert_eq!(
+            parse_response(&packet, SERVICE, source),
+            vec![Found {
+                instance: "pc1".to_string(),
+                addr: "10.0.0.7:9000".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_pointer_loop() {
+        let mut packet = vec![0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 0];
+        packet.extend_from_slice(&[0xC0, 12, 0, 12, 0, 1, 0, 0, 0, 120, 0, 0]);
+        let source = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert!(parse_response(&packet, SERVICE, source).is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+//! Send files to kiorg running on other machines in the local network
+//!
+//! Receiving devices announce themselves over mDNS, senders browse for them
+//! and transfer files over a single TCP connection. See [`protocol`] for the
+//! wire format.
+
+pub mod mdns;
+pub mod protocol;
+
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+use crate::ui::notification::NotificationMessage;
+use mdns::{Announcement, MDNS_ADDR, MDNS_PORT};
+use protocol::{Offer, SERVICE_TYPE};
+
+/// Peers that weren't announced for this long are dropped
+const PEER_TTL: Duration = Duration::from_secs(120);
+
+/// Incoming connections handled at once, further ones are declined right away
+const MAX_CONNECTIONS: usize = 4;
+
+/// Another device that accepts files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Peer {
+    pub name: String,
+    pub addr: SocketAddr,
+}
+
+/// Files offered by another device, waiting for the user to accept them
+#[derive(Debug, Clone)]
+pub struct IncomingOffer {
+    pub offer: Offer,
+    pub from: SocketAddr,
+    reply: mpsc::Sender<bool>,
+}
+
+impl IncomingOffer {
+    /// Accept or decline the offer, files are received in the background
+    pub fn respond(&self, accept: bool) {
+        let _ = self.reply.send(accept);
+    }
+}
+
+type Peers = Arc<Mutex<HashMap<String, (Peer, Instant)>>>;
+
+/// Browses for peers and, when receiving, announces this device and accepts
+/// incoming transfers
+pub struct LanService {
+    name: String,
+    socket: UdpSocket,
+    peers: Peers,
+    offers: mpsc::Receiver<IncomingOffer>,
+    notifications: mpsc::Sender<NotificationMessage>,
+}
+
+impl LanService {
+    /// Start browsing, and receiving into `receive_dir` when set
+    pub fn start(
+        ctx: &egui::Context,
+        name: &str,
+        receive_dir: Option<PathBuf>,
+        notifications: mpsc::Sender<NotificationMessage>,
+    ) -> Result<Self, String> {
+        let name = mdns::truncate_label(name).to_string();
+        let socket =
+            multicast_socket().map_err(|e| format!("Failed to open the mDNS socket: {e}"))?;
+        let (offer_sender, offers) = mpsc::channel();
+
+        let announcement = match receive_dir {
+            Some(dir) => {
+                let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0))
+                    .map_err(|e| format!("Failed to listen for transfers: {e}"))?;
+                let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+                let ctx = ctx.clone();
+                let notifications = notifications.clone();
+                std::thread::spawn(move || {
+                    // Every connection thread holds a clone until it's done
+                    let slots = Arc::new(());
+                    for mut stream in listener.incoming().flatten() {
+                        if Arc::strong_count(&slots) > MAX_CONNECTIONS {
+                            protocol::reject(&mut stream);
+                            continue;
+                        }
+                        let slot = slots.clone();
+                        let dir = dir.clone();
+                        let offer_sender = offer_sender.clone();
+                        let notifications = notifications.clone();
+                        let ctx = ctx.clone();
+                        std::thread::spawn(move || {
+                            let _slot = slot;
+                            handle_connection(stream, &dir, &offer_sender, &notifications, &ctx);
+                        });
+                    }
+                });
+                Some(Announcement {
+                    instance: name.clone(),
+                    host: host_label(&name),
+                    port,
+                    addrs: local_ipv4(),
+                })
+            }
+            None => None,
+        };
+
+        let peers = Peers::default();
+        {
+            let socket = socket.try_clone().map_err(|e| e.to_string())?;
+            let announcement = announcement.clone();
+            let peers = peers.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || run_mdns(&socket, announcement.as_ref(), &peers, &ctx));
+        }
+        if let Some(announcement) = &announcement {
+            let _ = socket.send_to(
+                &mdns::response(SERVICE_TYPE, announcement),
+                (MDNS_ADDR, MDNS_PORT),
+            );
+        }
+
+        Ok(Self {
+            name,
+            socket,
+            peers,
+            offers,
+            notifications,
+        })
+    }
+
+    /// Ask the devices in the network to announce themselves
+    pub fn browse(&self) {
+        self.peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, (_, seen)| seen.elapsed() < PEER_TTL);
+        if let Err(e) = self
+            .socket
+            .send_to(&mdns::query(SERVICE_TYPE), (MDNS_ADDR, MDNS_PORT))
+        {
+            tracing::warn!("Failed to send mDNS query: {e}");
+        }
+    }
+
+    /// Devices found so far, sorted by name
+    #[must_use]
+    pub fn peers(&self) -> Vec<Peer> {
+        let mut peers: Vec<Peer> = self
+            .peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|(_, seen)| seen.elapsed() < PEER_TTL)
+            .map(|(peer, _)| peer.clone())
+            .collect();
+        peers.sort_by(|a, b| a.name.cmp(&b.name));
+        peers
+    }
+
+    /// Next offer waiting for an answer, if any
+    #[must_use]
+    pub fn try_recv_offer(&self) -> Option<IncomingOffer> {
+        self.offers.try_recv().ok()
+    }
+
+    /// Send `paths` to `peer` in the background
+    pub fn send(&self, peer: &Peer, paths: Vec<PathBuf>) {
+        let name = self.name.clone();
+        let peer = peer.clone();
+        let notifications = self.notifications.clone();
+        std::thread::spawn(move || {
+            let message = match protocol::send_files(peer.addr, &name, &paths) {
+                Ok(()) => NotificationMessage::Success(format!(
+                    "Sent {} to {}",
+                    crate::utils::share::describe(&paths),
+                    peer.name
+                )),
+                Err(e) => {
+                    NotificationMessage::Error(format!("Failed to send to {}: {e}", peer.name))
+                }
+            };
+            let _ = notifications.send(message);
+        });
+    }
+}
+
+fn multicast_socket() -> std::io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    // Other mDNS responders on the host listen on the same port
+    socket.set_reuse_address(true)?;
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    socket.set_reuse_port(true)?;
+    socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, MDNS_PORT)).into())?;
+    socket.join_multicast_v4(&MDNS_ADDR, &Ipv4Addr::UNSPECIFIED)?;
+    socket.set_multicast_loop_v4(true)?;
+    Ok(socket.into())
+}
+
+fn run_mdns(
+    socket: &UdpSocket,
+    announcement: Option<&Announcement>,
+    peers: &Peers,
+    ctx: &egui::Context,
+) {
+    let mut buf = [0u8; 9000];
+    loop {
+        let (len, source) = match socket.recv_from(&mut buf) {
+            Ok(received) => received,
+            Err(e) => {
+                tracing::debug!("mDNS receive failed: {e}");
+                std::thread::sleep(Duration::from_secs(1));
+                continue;
+            }
+        };
+        let packet = &buf[..len];
+        if let Some(announcement) = announcement
+            && mdns::is_query_for(packet, SERVICE_TYPE)
+        {
+            let _ = socket.send_to(
+                &mdns::response(SERVICE_TYPE, announcement),
+                (MDNS_ADDR, MDNS_PORT),
+            );
+            continue;
+        }
+
+        let found = mdns::parse_response(packet, SERVICE_TYPE, source.ip());
+        if found.is_empty() {
+            continue;
+        }
+        let own = announcement.map(|a| (a.instance.as_str(), a.port));
+        let mut peers = peers.lock().unwrap_or_else(|e| e.into_inner());
+        for found in found {
+            if own == Some((found.instance.as_str(), found.addr.port())) {
+                continue;
+            }
+            let peer = Peer {
+                name: found.instance.clone(),
+                addr: found.addr,
+            };
+            peers.insert(found.instance, (peer, Instant::now()));
+        }
+        ctx.request_repaint();
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    dir: &Path,
+    offers: &mpsc::Sender<IncomingOffer>,
+    notifications: &mpsc::Sender<NotificationMessage>,
+    ctx: &egui::Context,
+) {
+    let _ = stream.set_read_timeout(Some(protocol::ANSWER_TIMEOUT));
+    let (Ok(reader), Ok(from)) = (stream.try_clone(), stream.peer_addr()) else {
+        return;
+    };
+    let mut reader = BufReader::new(reader);
+    let offer = match protocol::read_offer(&mut reader) {
+        Ok(offer) => offer,
+        Err(e) => {
+            tracing::warn!("Ignoring transfer from {from}: {e}");
+            return;
+        }
+    };
+
+    let (reply, answer) = mpsc::channel();
+    let incoming = IncomingOffer {
+        offer: offer.clone(),
+        from,
+        reply,
+    };
+    if offers.send(incoming).is_err() {
+        protocol::reject(&mut stream);
+        return;
+    }
+    ctx.request_repaint();
+    if answer.recv_timeout(protocol::ANSWER_TIMEOUT) != Ok(true) {
+        protocol::reject(&mut stream);
+        return;
+    }
+
+    let message = match protocol::receive_files(&mut reader, &mut stream, &offer, dir) {
+        Ok(files) => NotificationMessage::Success(format!(
+            "Received {} from {}",
+            crate::utils::share::describe(&files),
+            offer.sender
+        )),
+        Err(e) => NotificationMessage::Error(format!("Failed to receive files: {e}")),
+    };
+    let _ = notifications.send(message);
+    ctx.request_repaint();
+}
+
+/// Address other devices reach this host at
+///
+/// Connecting a UDP socket sends nothing, it only picks the outgoing interface.
+fn local_ipv4() -> Vec<Ipv4Addr> {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((MDNS_ADDR, MDNS_PORT))?;
+            socket.local_addr()
+        })
+        .ok()
+        .and_then(|addr| match addr.ip() {
+            std::net::IpAddr::V4(ip) if !ip.is_unspecified() => Some(ip),
+            _ => None,
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Host name label derived from the device name, e.g. `Bob's PC` -> `Bob-s-PC`
+fn host_label(name: &str) -> String {
+    let label: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let label = label.trim_matches('-');
+    if label.is_empty() {
+        "kiorg".to_string()
+    } else {
+        format!("{label}-kiorg")
+    }
+}
+
+/// Name shown to other devices when none is configured
+#[must_use]
+pub fn default_device_name() -> String {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "kiorg".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_label() {
+        assert_eq!(host_label("Bob's PC"), "Bob-s-PC-kiorg");
+        assert_eq!(host_label("ノート"), "kiorg");
+    }
+}
@@ -0,0 +1,368 @@
+//! Kiorg LAN transfer protocol, version 1
+//!
+//! Receivers advertise the [`SERVICE_TYPE`] DNS-SD service over mDNS with the
+//! TCP port they listen on. The instance name is the device name shown to
+//! senders. A transfer is a single TCP connection:
+//!
+//! 1. Sender: the line `KIORG-SEND/1`, then the offer as one line of JSON,
+//!    e.g. `{"sender":"laptop","files":[{"name":"a.txt","size":12}]}`
+//! 2. Receiver: the line `ACCEPT` or `REJECT`, a rejected connection is closed
+//! 3. Sender: the bytes of every file back to back, in the order of the offer
+//! 4. Receiver: the line `DONE` once all files are written
+//!
+//! Lines end with `\n`. File names are plain names, receivers drop any
+//! directory part and never overwrite existing files.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::file_operations::unique_path;
+
+/// DNS-SD service type advertised by receivers
+pub const SERVICE_TYPE: &str = "_kiorg-send._tcp.local";
+
+const MAGIC: &str = "KIORG-SEND/1";
+const ACCEPT: &str = "ACCEPT";
+const REJECT: &str = "REJECT";
+const DONE: &str = "DONE";
+/// Longest offer line a receiver reads
+const MAX_OFFER_LEN: u64 = 1024 * 1024;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long the receiver has to accept an offer
+pub const ANSWER_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfferedFile {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Files a sender wants to transfer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Offer {
+    pub sender: String,
+    pub files: Vec<OfferedFile>,
+}
+
+impl Offer {
+    #[must_use]
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+fn read_line(reader: &mut impl BufRead, max_len: u64) -> Result<String, String> {
+    let mut line = String::new();
+    reader
+        .take(max_len)
+        .read_line(&mut line)
+        .map_err(|e| format!("Connection failed: {e}"))?;
+    if !line.ends_with('\n') {
+        return Err("Connection closed unexpectedly".to_string());
+    }
+    line.pop();
+    Ok(line)
+}
+
+/// Send regular files to the receiver at `addr`, blocks until it's done
+pub fn send_files(addr: SocketAddr, sender: &str, paths: &[PathBuf]) -> Result<(), String> {
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        if !metadata.is_file() {
+            return Err(format!("Only files can be sent: {}", path.display()));
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        files.push(OfferedFile {
+            name,
+            size: metadata.len(),
+        });
+    }
+    let offer = Offer {
+        sender: sender.to_string(),
+        files,
+    };
+
+    let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        .map_err(|e| format!("Failed to connect to {addr}: {e}"))?;
+    let offer_json = serde_json::to_string(&offer).map_err(|e| e.to_string())?;
+    stream
+        .write_all(format!("{MAGIC}\n{offer_json}\n").as_bytes())
+        .map_err(|e| format!("Failed to send the offer: {e}"))?;
+
+    // Waiting for the user on the other side
+    let _ = stream.set_read_timeout(Some(ANSWER_TIMEOUT + CONNECT_TIMEOUT));
+    let mut reader = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?);
+    match read_line(&mut reader, 64)?.as_str() {
+        ACCEPT => {}
+        REJECT => return Err("The transfer was declined".to_string()),
+        other => return Err(format!("Unexpected answer: {other}")),
+    }
+
+    for (path, file) in paths.iter().zip(&offer.files) {
+        let source =
+            File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+        let copied = io::copy(&mut source.take(file.size), &mut stream)
+            .map_err(|e| format!("Failed to send {}: {e}", file.name))?;
+        if copied != file.size {
+            return Err(format!("{} changed while it was sent", file.name));
+        }
+    }
+    stream.flush().map_err(|e| e.to_string())?;
+
+    match read_line(&mut reader, 64)?.as_str() {
+        DONE => Ok(()),
+        other => Err(format!("Unexpected answer: {other}")),
+    }
+}
+
+/// Read the offer at the start of an incoming connection
+pub fn read_offer(reader: &mut impl BufRead) -> Result<Offer, String> {
+    if read_line(reader, MAGIC.len() as u64 + 1)? != MAGIC {
+        return Err("Not a kiorg transfer".to_string());
+    }
+    let line = read_line(reader, MAX_OFFER_LEN)?;
+    serde_json::from_str(&line).map_err(|e| format!("Invalid offer: {e}"))
+}
+
+/// Decline an offer
+pub fn reject(stream: &mut TcpStream) {
+    let _ = stream.write_all(format!("{REJECT}\n").as_bytes());
+}
+
+/// Accept an offer and write its files into `dest_dir`
+///
+/// `reader` reads from `stream` and may hold already buffered file data. The
+/// offer is declined when any of its names is unusable, and files written
+/// before a transfer fails are removed again.
+pub fn receive_files(
+    reader: &mut impl Read,
+    stream: &mut TcpStream,
+    offer: &Offer,
+    dest_dir: &Path,
+) -> Result<Vec<PathBuf>, String> {
+    let names = offer
+        .files
+        .iter()
+        .map(|file| {
+            sanitize_name(&file.name).ok_or_else(|| format!("Invalid file name: {}", file.name))
+        })
+        .collect::<Result<Vec<_>, _>>();
+    let names = match names {
+        Ok(names) => names,
+        Err(e) => {
+            reject(stream);
+            return Err(e);
+        }
+    };
+    stream
+        .write_all(format!("{ACCEPT}\n").as_bytes())
+        .map_err(|e| format!("Connection failed: {e}"))?;
+
+    let mut received = Vec::with_capacity(names.len());
+    let result = offer.files.iter().zip(&names).try_for_each(|(file, name)| {
+        let path = unique_path(dest_dir, name);
+        let mut target = File::create_new(&path)
+            .map_err(|e| format!("Failed to create {}: {e}", path.display()))?;
+        received.push(path);
+        match io::copy(&mut reader.take(file.size), &mut target) {
+            Ok(size) if size == file.size => Ok(()),
+            _ => Err(format!("Transfer of {name} was interrupted")),
+        }
+    });
+    if let Err(e) = result {
+        for path in &received {
+            let _ = std::fs::remove_file(path);
+        }
+        return Err(e);
+    }
+
+    stream
+        .write_all(format!("{DONE}\n").as_bytes())
+        .map_err(|e| format!("Connection failed: {e}"))?;
+    Ok(received)
+}
+
+/// File name part of an offered name, `None` when nothing usable is left
+///
+/// Names that can't be created on Windows are rejected as well: `:` would
+/// write to an alternate data stream and device names like `NUL.txt` don't
+/// refer to a file.
+fn sanitize_name(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next()?.trim();
+    if name.is_empty() || name == "." || name == ".." || name.contains(':') {
+        return None;
+    }
+    if is_reserved_device_name(name) {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Whether Windows maps `name` to a device, with or without an extension
+fn is_reserved_device_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or_default().trim_end();
+    let stem = stem.to_ascii_uppercase();
+    match stem.as_str() {
+        "CON" | "PRN" | "AUX" | "NUL" => true,
+        _ => stem
+            .strip_prefix("COM")
+            .or_else(|| stem.strip_prefix("LPT"))
+            .is_some_and(|n| matches!(n.as_bytes(), [b'1'..=b'9'])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("a.txt").as_deref(), Some("a.txt"));
+        assert_eq!(sanitize_name("../../etc/passwd").as_deref(), Some("passwd"));
+        assert_eq!(sanitize_name("C:\\dir\\b.txt").as_deref(), Some("b.txt"));
+        assert_eq!(sanitize_name("dir/.."), None);
+        assert_eq!(sanitize_name(""), None);
+        assert_eq!(sanitize_name("C:b.txt"), None);
+        assert_eq!(sanitize_name("a.txt:stream"), None);
+        assert_eq!(sanitize_name("CON"), None);
+        assert_eq!(sanitize_name("nul.txt"), None);
+        assert_eq!(sanitize_name("Aux.tar.gz"), None);
+        assert_eq!(sanitize_name("com1"), None);
+        assert_eq!(sanitize_name("LPT9.log"), None);
+        assert_eq!(sanitize_name("COM0").as_deref(), Some("COM0"));
+        assert_eq!(sanitize_name("LPT10").as_deref(), Some("LPT10"));
+        assert_eq!(sanitize_name("console.txt").as_deref(), Some("console.txt"));
+        assert_eq!(sanitize_name("my.con").as_deref(), Some("my.con"));
+    }
+
+    #[test]
+    fn test_transfer() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let dest_dir = tempfile::tempdir().unwrap();
+        let a = source_dir.path().join("a.txt");
+        let b = source_dir.path().join("b.bin");
+        std::fs::write(&a, "hello").unwrap();
+        std::fs::write(&b, vec![7u8; 100_000]).unwrap();
+        std::fs::write(dest_dir.path().join("a.txt"), "existing").unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let dest = dest_dir.path().to_path_buf();
+        let receiver = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let offer = read_offer(&mut reader).unwrap();
+            assert_eq!(offer.sender, "laptop");
+            assert_eq!(offer.total_size(), 100_005);
+            receive_files(&mut reader, &mut stream, &offer, &dest).unwrap()
+        });
+
+        send_files(addr, "laptop", &[a, b]).unwrap();
+        let received = receiver.join().unwrap();
+        assert_eq!(
+            received,
+            vec![
+                dest_dir.path().join("a_1.txt"),
+                dest_dir.path().join("b.bin")
+            ]
+        );
+        assert_eq!(std::fs::read_to_string(&received[0]).unwrap(), "hello");
+        assert_eq!(std::fs::read(&received[1]).unwrap().len(), 100_000);
+    }
+
+    /// Connect to `addr` and send a raw offer, returning the receiver's answer
+    fn offer_raw(addr: SocketAddr, files: &[(&str, u64)], data: &[u8]) -> String {
+        let offer = Offer {
+            sender: "laptop".to_string(),
+            files: files
+                .iter()
+                .map(|(name, size)| OfferedFile {
+                    name: name.to_string(),
+                    size: *size,
+                })
+                .collect(),
+        };
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let offer_json = serde_json::to_string(&offer).unwrap();
+        write!(stream, "{MAGIC}\n{offer_json}\n").unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let answer = read_line(&mut reader, 64).unwrap();
+        if answer == ACCEPT {
+            stream.write_all(data).unwrap();
+        }
+        answer
+    }
+
+    fn receive_one(listener: TcpListener, dest: PathBuf) -> Result<Vec<PathBuf>, String> {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let offer = read_offer(&mut reader).unwrap();
+        receive_files(&mut reader, &mut stream, &offer, &dest)
+    }
+
+    #[test]
+    fn test_invalid_name_declines_offer() {
+        let dest_dir = tempfile::tempdir().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let dest = dest_dir.path().to_path_buf();
+        let receiver = std::thread::spawn(move || receive_one(listener, dest));
+
+        let answer = offer_raw(addr, &[("a.txt", 5), ("NUL", 5)], b"");
+        assert_eq!(answer, REJECT);
+        assert_eq!(
+            receiver.join().unwrap(),
+            Err("Invalid file name: NUL".to_string())
+        );
+        assert_eq!(std::fs::read_dir(dest_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_interrupted_transfer_removes_files() {
+        let dest_dir = tempfile::tempdir().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let dest = dest_dir.path().to_path_buf();
+        let receiver = std::thread::spawn(move || receive_one(listener, dest));
+
+        // The second file ends early when the connection closes
+        let answer = offer_raw(addr, &[("a.txt", 5), ("b.txt", 10)], b"helloabc");
+        assert_eq!(answer, ACCEPT);
+        assert_eq!(
+            receiver.join().unwrap(),
+            Err("Transfer of b.txt was interrupted".to_string())
+        );
+        assert_eq!(std::fs::read_dir(dest_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_rejected_transfer() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let a = source_dir.path().join("a.txt");
+        std::fs::write(&a, "hello").unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let receiver = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            read_offer(&mut reader).unwrap();
+            reject(&mut stream);
+        });
+
+        let result = send_files(addr, "laptop", &[a]);
+        receiver.join().unwrap();
+        assert_eq!(result, Err("The transfer was declined".to_string()));
+    }
+}
@@ -2,9 +2,12 @@ pub mod app;
 pub mod config;
 pub mod font;
 pub mod input;
+pub mod lan;
 pub mod models;
 pub mod open_wrap;
 pub mod plugins;
+#[cfg(target_os = "linux")]
+pub mod portal;
 pub mod startup_error;
 pub mod theme;
 pub mod ui;
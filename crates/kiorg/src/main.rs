@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use tracing_subscriber::{EnvFilter, fmt};
 
 use kiorg::app::Kiorg;
+use kiorg::ui::picker;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -17,6 +18,18 @@ struct Args {
     #[arg(short, long, env = "KIORG_CONFIG_DIR")]
     config_dir: Option<PathBuf>,
 
+    /// Use the isolated config, state and bookmarks of a profile, e.g. "work"
+    #[arg(long, value_name = "NAME", env = "KIORG_PROFILE")]
+    profile: Option<String>,
+
+    /// Restore the tabs and marks of a named session, e.g. "kiorg"
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["directory", "pick"])]
+    session: Option<String>,
+
+    /// Keep config, state, plugins and caches in kiorg-data next to the executable
+    #[arg(long)]
+    portable: bool,
+
     /// Clear the preview cache before starting
     #[arg(long)]
     clear_cache: bool,
@@ -24,6 +37,55 @@ struct Args {
     /// Print the cache and config directory, then exit
     #[arg(long)]
     print_dirs: bool,
+
+    /// Validate the config files, list unknown or deprecated keys and pending upgrades, then exit
+    #[arg(long)]
+    check_config: bool,
+
+    /// Add the directories known to zoxide or autojump to the visit history, then exit
+    #[arg(long, value_name = "SOURCE")]
+    import_history: Option<kiorg::utils::history_import::Source>,
+
+    /// Database to import instead of the tool's default one
+    #[arg(long, value_name = "FILE", requires = "import_history")]
+    import_path: Option<PathBuf>,
+
+    /// Print the current directory on quit, or write it to FILE, so a shell function can cd there
+    #[arg(long, value_name = "FILE", num_args = 0..=1, conflicts_with = "pick")]
+    choose_dir: Option<Option<PathBuf>>,
+
+    /// Open a compact picker window, print the picked paths and exit
+    #[arg(long)]
+    pick: bool,
+
+    /// Copy the picked paths to the clipboard instead of printing them
+    #[arg(long, requires = "pick")]
+    copy: bool,
+
+    /// Terminate each picked path with a NUL byte instead of a newline
+    #[arg(long, requires = "pick", conflicts_with = "copy")]
+    print0: bool,
+
+    /// Pick directories instead of files
+    #[arg(long, requires = "pick")]
+    directories: bool,
+
+    /// Pick a single entry even if several are marked
+    #[arg(long, requires = "pick")]
+    single: bool,
+
+    /// Pick a path to save a new file, with NAME as the suggested file name
+    #[arg(long, value_name = "NAME", requires = "pick")]
+    save: Option<String>,
+
+    /// Title of the picker window
+    #[arg(long, requires = "pick")]
+    title: Option<String>,
+
+    /// Serve the XDG desktop portal file chooser over D-Bus
+    #[cfg(target_os = "linux")]
+    #[arg(long)]
+    portal: bool,
 }
 
 fn init_tracing() {
@@ -55,7 +117,25 @@ fn main() -> Result<(), eframe::Error> {
     cmd = cmd.after_help(help_extra);
 
     let matches = cmd.get_matches();
-    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if args.portable {
+        kiorg::config::portable::enable();
+    }
+    // A profile is a config directory below the regular one
+    if let Some(profile) = &args.profile {
+        if let Err(e) = kiorg::config::profile::validate_name(profile) {
+            eprintln!("error: {e}");
+            std::process::exit(2);
+        }
+        let base_dir = kiorg::config::get_kiorg_config_dir(args.config_dir.as_deref());
+        args.config_dir = Some(kiorg::config::profile::config_dir(&base_dir, profile));
+    }
+    if let Some(session) = &args.session
+        && let Err(e) = kiorg::config::session::validate_name(session)
+    {
+        eprintln!("error: {e}");
+        std::process::exit(2);
+    }
 
     if args.print_dirs {
         let config_dir = kiorg::config::get_kiorg_config_dir(args.config_dir.as_deref());
@@ -65,8 +145,64 @@ fn main() -> Result<(), eframe::Error> {
         return Ok(());
     }
 
+    if args.check_config {
+        let mut report = String::new();
+        let ok = kiorg::config::migration::check_config(args.config_dir.as_deref(), &mut report);
+        print!("{report}");
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    // Upgrade files written by older releases before anything reads them
+    match kiorg::config::migration::run_migrations(args.config_dir.as_deref()) {
+        Ok(upgraded) => {
+            for line in upgraded {
+                tracing::info!("{line}");
+            }
+        }
+        Err(errors) => {
+            for e in errors {
+                tracing::error!("Failed to upgrade config file: {e}");
+            }
+        }
+    }
+
+    if let Some(source) = args.import_history {
+        match kiorg::utils::history_import::import(
+            source,
+            args.import_path.as_deref(),
+            args.config_dir.as_deref(),
+        ) {
+            Ok(count) => {
+                println!("Imported {count} directories from {}", source.name());
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!(
+                    "error: failed to import history from {}: {e}",
+                    source.name()
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    if args.portal {
+        if let Err(e) = kiorg::portal::run() {
+            eprintln!("File chooser portal failed: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     if args.clear_cache {
         kiorg::utils::preview_cache::purge_cache_dir();
+    } else {
+        std::thread::spawn(|| {
+            kiorg::utils::preview_cache::prune_cache_dir(
+                kiorg::utils::preview_cache::MAX_CACHE_SIZE,
+            );
+        });
     }
 
     // If a directory is provided, validate and canonicalize it
@@ -109,16 +245,50 @@ fn main() -> Result<(), eframe::Error> {
     // Load the app icon from embedded data
     let icon_data = kiorg::utils::icon::load_app_icon();
 
+    let viewport = if args.pick {
+        egui::ViewportBuilder::default()
+            .with_title(args.title.as_deref().unwrap_or("Kiorg Picker"))
+            .with_inner_size(picker::PICKER_INNER_SIZE)
+            .with_min_inner_size(picker::PICKER_MIN_INNER_SIZE)
+            .with_window_level(egui::WindowLevel::AlwaysOnTop)
+            .with_icon(icon_data)
+            .with_app_id("kiorg")
+    } else {
+        // Restore the window geometry of the previous session
+        let window_state = Kiorg::load_window_state(args.config_dir.as_deref()).unwrap_or_default();
+        window_state.apply_to_builder(
+            egui::ViewportBuilder::default()
+                .with_title(kiorg::config::profile::window_title(
+                    args.profile.as_deref(),
+                ))
+                .with_min_inner_size(kiorg::models::window_state::MIN_INNER_SIZE)
+                .with_icon(icon_data)
+                .with_app_id("kiorg"),
+        )
+    };
+
     let options = eframe::NativeOptions {
         renderer: eframe::Renderer::Wgpu,
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1280.0, 800.0])
-            .with_min_inner_size([800.0, 600.0])
-            .with_icon(icon_data)
-            .with_app_id("kiorg"),
+        viewport,
         ..Default::default()
     };
 
+    let picker_mode = args.pick.then(|| {
+        let output = if args.copy {
+            picker::PickOutput::Copy
+        } else if args.print0 {
+            picker::PickOutput::PrintNull
+        } else {
+            picker::PickOutput::Print
+        };
+        picker::PickerMode {
+            directories: args.directories,
+            multiple: !args.single,
+            save_name: args.save,
+            ..picker::PickerMode::new(output)
+        }
+    });
+
     eframe::run_native(
         "Kiorg",
         options,
@@ -132,7 +302,19 @@ fn main() -> Result<(), eframe::Error> {
             // Configure fonts for proper emoji and system font rendering
             kiorg::font::configure_egui_fonts(&cc.egui_ctx);
 
-            match Kiorg::new(cc, initial_dir, args.config_dir) {
+            let app = match picker_mode {
+                Some(mode) => Kiorg::new_picker(cc, initial_dir, args.config_dir, mode),
+                None => Kiorg::new(cc, initial_dir, args.config_dir).map(|mut app| {
+                    if let Some(session) = &args.session {
+                        app.restore_session(session);
+                    }
+                    app.choose_dir = args
+                        .choose_dir
+                        .map(kiorg::utils::choose_dir::ChooseDirOutput::from_arg);
+                    app
+                }),
+            };
+            match app {
                 Ok(app) => Ok(Box::new(app)),
                 Err(e) => {
                     // Show the error in a startup error dialog instead of exiting
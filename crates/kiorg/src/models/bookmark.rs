@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// A bookmarked directory, optionally shown under a label and jumped to with
+/// `'` and its key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub path: PathBuf,
+    pub label: Option<String>,
+    pub key: Option<char>,
+}
+
+impl Bookmark {
+    #[must_use]
+    pub const fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            label: None,
+            key: None,
+        }
+    }
+
+    /// Whether `key` can jump to a bookmark, keys are letters like mark names
+    #[must_use]
+    pub const fn is_valid_key(key: char) -> bool {
+        key.is_ascii_lowercase()
+    }
+
+    /// Name shown for the bookmark, its label or the folder name
+    #[must_use]
+    pub fn name(&self) -> Cow<'_, str> {
+        match &self.label {
+            Some(label) => Cow::Borrowed(label),
+            None => self.path.file_name().map_or_else(
+                || self.path.to_string_lossy(),
+                |name| name.to_string_lossy(),
+            ),
+        }
+    }
+
+    /// Parse a line of the bookmarks file, the path optionally followed by
+    /// the label and the key, separated by tabs
+    #[must_use]
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        // Paths may legitimately start or end with spaces, keep them as is
+        let path = fields.next()?;
+        if path.trim().is_empty() {
+            return None;
+        }
+        let label = fields
+            .next()
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(str::to_string);
+        let key = fields.next().and_then(|key| {
+            let mut chars = key.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some(key), None) if Self::is_valid_key(key) => Some(key),
+                _ => None,
+            }
+        });
+        Some(Self {
+            path: PathBuf::from(path),
+            label,
+            key,
+        })
+    }
+
+    /// Line of the bookmarks file, just the path for plain bookmarks
+    #[must_use]
+    pub fn to_line(&self) -> String {
+        let path = self.path.to_string_lossy();
+        if self.label.is_none() && self.key.is_none() {
+            return path.into_owned();
+        }
+        format!(
+            "{path}\t{}\t{}",
+            self.label.as_deref().unwrap_or_default(),
+            self.key.map(String::from).unwrap_or_default()
+        )
+    }
+}
+
+/// Whether `path` is bookmarked
+#[must_use]
+pub fn is_bookmarked(bookmarks: &[Bookmark], path: &Path) -> bool {
+    bookmarks.iter().any(|bookmark| bookmark.path == path)
+}
+
+/// Bookmark jumped to with `key`
+#[must_use]
+pub fn find_by_key(bookmarks: &[Bookmark], key: char) -> Option<&Bookmark> {
+    bookmarks.iter().find(|bookmark| bookmark.key == Some(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_round_trip() {
+        let plain = Bookmark::new(PathBuf::from("/home/user/projects"));
+        assert_eq!(plain.to_line(), "/home/user/projects");
+        assert_eq!(Bookmark::from_line(&plain.to_line()), Some(plain.clone()));
+        assert_eq!(plain.name(), "projects");
+
+        let labeled = Bookmark {
+            path: PathBuf::from("/home/user/projects"),
+            label: Some("Home projects".to_string()),
+            key: Some('h'),
+        };
+        assert_eq!(labeled.to_line(), "/home/user/projects\tHome projects\th");
+        assert_eq!(
+            Bookmark::from_line(&labeled.to_line()),
+            Some(labeled.clone())
+        );
+        assert_eq!(labeled.name(), "Home projects");
+
+        let key_only = Bookmark {
+            label: None,
+            ..labeled.clone()
+        };
+        assert_eq!(Bookmark::from_line(&key_only.to_line()), Some(key_only));
+
+        // Invalid keys are dropped, empty lines skipped
+        let invalid_key = Bookmark::from_line("/tmp\tTemp\tHH").unwrap();
+        assert_eq!(invalid_key.key, None);
+        assert_eq!(invalid_key.label.as_deref(), Some("Temp"));
+        assert_eq!(Bookmark::from_line("  "), None);
+
+        // Only the label and key are trimmed
+        let spaced = Bookmark::from_line("/tmp/ notes \t Notes \t n ").unwrap();
+        assert_eq!(spaced.path, PathBuf::from("/tmp/ notes "));
+        assert_eq!(spaced.label.as_deref(), Some("Notes"));
+        assert_eq!(spaced.key, Some('n'));
+
+        let bookmarks = [plain, labeled];
+        assert!(is_bookmarked(&bookmarks, Path::new("/home/user/projects")));
+        assert!(!is_bookmarked(&bookmarks, Path::new("/tmp")));
+        assert_eq!(
+            find_by_key(&bookmarks, 'h').and_then(|b| b.label.as_deref()),
+            Some("Home projects")
+        );
+        assert!(find_by_key(&bookmarks, 'x').is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -17,10 +17,17 @@ pub struct DirEntry {
     pub is_dir: bool,
     pub is_symlink: bool,
     pub size: u64,
+    /// Cloud placeholder whose content is downloaded when it's read
+    #[serde(default)]
+    pub is_placeholder: bool,
     #[serde(skip)]
     pub(crate) formatted_size: OnceLock<String>,
     #[serde(skip)]
     pub(crate) formatted_modified: OnceLock<String>,
+    #[serde(skip)]
+    pub(crate) formatted_size_on_disk: OnceLock<String>,
+    #[serde(skip)]
+    pub(crate) link_target: OnceLock<Option<PathBuf>>,
 }
 
 impl DirEntry {
@@ -30,6 +37,7 @@ impl DirEntry {
         is_dir: bool,
         is_symlink: bool,
         size: u64,
+        is_placeholder: bool,
     ) -> Self {
         Self {
             name,
@@ -37,8 +45,11 @@ impl DirEntry {
             is_dir,
             is_symlink,
             size,
+            is_placeholder,
             formatted_size: OnceLock::new(),
             formatted_modified: OnceLock::new(),
+            formatted_size_on_disk: OnceLock::new(),
+            link_target: OnceLock::new(),
         }
     }
 
@@ -47,6 +58,28 @@ impl DirEntry {
             .get_or_init(|| crate::utils::format::format_size(self.size, self.is_dir))
     }
 
+    /// Allocated size, read from the file system on first use
+    pub fn formatted_size_on_disk(&self) -> &str {
+        self.formatted_size_on_disk.get_or_init(|| {
+            if self.is_dir {
+                return String::new();
+            }
+            crate::utils::disk_size::size_on_disk_of(&self.meta.path)
+                .map(|size| crate::utils::format::format_size(size, false))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Target of a symlink as stored in the link, read on first use
+    pub fn link_target(&self) -> Option<&Path> {
+        if !self.is_symlink {
+            return None;
+        }
+        self.link_target
+            .get_or_init(|| std::fs::read_link(&self.meta.path).ok())
+            .as_deref()
+    }
+
     pub fn formatted_modified(&self) -> &str {
         self.formatted_modified
             .get_or_init(|| crate::utils::format::format_modified(self.meta.modified))
@@ -54,24 +87,21 @@ impl DirEntry {
 
     pub fn accessibility_text(&self) -> String {
         let file_type = if self.is_dir { "folder" } else { "file" };
-
+        let mut kind = String::new();
         if self.is_symlink {
-            format!(
-                "{} {}, symbolic link, modified {}, size {}",
-                file_type,
-                self.name,
-                self.formatted_modified(),
-                self.formatted_size()
-            )
-        } else {
-            format!(
-                "{} {}, modified {}, size {}",
-                file_type,
-                self.name,
-                self.formatted_modified(),
-                self.formatted_size()
-            )
+            kind.push_str(", symbolic link");
+        }
+        if self.is_placeholder {
+            kind.push_str(", online only");
         }
+        format!(
+            "{} {}{}, modified {}, size {}",
+            file_type,
+            self.name,
+            kind,
+            self.formatted_modified(),
+            self.formatted_size()
+        )
     }
 }
 
@@ -93,8 +123,11 @@ mod tests {
             is_dir: false,
             is_symlink: false,
             size: 100,
+            is_placeholder: false,
             formatted_size: OnceLock::new(),
             formatted_modified: OnceLock::new(),
+            formatted_size_on_disk: OnceLock::new(),
+            link_target: OnceLock::new(),
         };
 
         assert_eq!(entry.name, "test.txt");
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::group_by::GroupBy;
+use super::tab::{SortColumn, SortOrder};
+
+/// How the entries of a directory are listed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewPrefs {
+    pub sort_column: SortColumn,
+    pub sort_order: SortOrder,
+    pub show_hidden: bool,
+    #[serde(default)]
+    pub group_by: GroupBy,
+}
+
+/// View preferences remembered per directory, least recently used first
+///
+/// Only the most recently used [`DirPrefs::MAX_DIRS`] directories are kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DirPrefs(Vec<(PathBuf, ViewPrefs)>);
+
+impl DirPrefs {
+    pub const MAX_DIRS: usize = 500;
+
+    /// Preferences of `path`, marking them as recently used
+    pub fn get(&mut self, path: &Path) -> Option<ViewPrefs> {
+        let index = self.0.iter().position(|(dir, _)| dir == path)?;
+        let entry = self.0.remove(index);
+        let prefs = entry.1;
+        self.0.push(entry);
+        Some(prefs)
+    }
+
+    /// Remember the preferences of `path`, forgetting the least recently used
+    /// directory beyond the cap
+    pub fn set(&mut self, path: &Path, prefs: ViewPrefs) {
+        self.0.retain(|(dir, _)| dir != path);
+        self.0.push((path.to_path_buf(), prefs));
+        if self.0.len() > Self::MAX_DIRS {
+            self.0.drain(..self.0.len() - Self::MAX_DIRS);
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BY_DATE: ViewPrefs = ViewPrefs {
+        sort_column: SortColumn::Modified,
+        sort_order: SortOrder::Descending,
+        show_hidden: false,
+        group_by: GroupBy::None,
+    };
+
+    #[test]
+    fn test_least_recently_used_dirs_are_dropped() {
+        let mut prefs = DirPrefs::default();
+        for index in 0..DirPrefs::MAX_DIRS {
+            prefs.set(&PathBuf::from(format!("/dir{index}")), BY_DATE);
+        }
+        // Using the oldest directory keeps it over the next one
+        assert_eq!(prefs.get(Path::new("/dir0")), Some(BY_DATE));
+        prefs.set(Path::new("/new"), BY_DATE);
+        assert_eq!(prefs.len(), DirPrefs::MAX_DIRS);
+        assert_eq!(prefs.get(Path::new("/dir0")), Some(BY_DATE));
+        assert_eq!(prefs.get(Path::new("/dir1")), None);
+
+        // Setting a directory again replaces its preferences
+        let hidden = ViewPrefs {
+            show_hidden: true,
+            ..BY_DATE
+        };
+        prefs.set(Path::new("/new"), hidden);
+        assert_eq!(prefs.len(), DirPrefs::MAX_DIRS);
+        assert_eq!(prefs.get(Path::new("/new")), Some(hidden));
+    }
+}
@@ -0,0 +1,203 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+use super::dir_entry::DirEntry;
+
+/// How the entries of the file list are grouped under headers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GroupBy {
+    #[default]
+    None,
+    /// Folders, then files by extension
+    Type,
+    /// Today, yesterday, this week or older, by modification date
+    Date,
+    /// First letter of the name
+    Letter,
+}
+
+/// A row of the grouped file list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GroupRow {
+    Header(String),
+    /// Index of the entry in the tab's entries
+    Entry(usize),
+}
+
+impl GroupBy {
+    /// Grouping selected after this one when cycling through them
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::None => Self::Type,
+            Self::Type => Self::Date,
+            Self::Date => Self::Letter,
+            Self::Letter => Self::None,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Type => "Type",
+            Self::Date => "Date",
+            Self::Letter => "First letter",
+        }
+    }
+
+    /// Group of `entry` at the time `now`, groups are ordered by their rank
+    /// then header
+    #[must_use]
+    pub fn group_of(self, entry: &DirEntry, now: &DateTime<Local>) -> Option<(u8, String)> {
+        match self {
+            Self::None => None,
+            Self::Type => Some(type_group(entry)),
+            Self::Date => Some(date_group(entry.meta.modified, now)),
+            Self::Letter => Some(letter_group(&entry.name)),
+        }
+    }
+
+    /// Move the entries of each group together, keeping their order within a
+    /// group
+    pub fn sort(self, entries: &mut [DirEntry]) {
+        if self != Self::None {
+            let now = Local::now();
+            // Stable, so the sort column still orders entries within a group
+            entries.sort_by_cached_key(|entry| self.group_of(entry, &now));
+        }
+    }
+
+    /// Rows listing the entries at `indices`, with a header above each group
+    #[must_use]
+    pub fn rows(self, entries: &[DirEntry], indices: &[usize]) -> Vec<GroupRow> {
+        let now = Local::now();
+        let mut rows = Vec::with_capacity(indices.len());
+        let mut current = None;
+        for &index in indices {
+            let group = self.group_of(&entries[index], &now);
+            if group != current {
+                if let Some((_, header)) = &group {
+                    rows.push(GroupRow::Header(header.clone()));
+                }
+                current = group;
+            }
+            rows.push(GroupRow::Entry(index));
+        }
+        rows
+    }
+}
+
+fn type_group(entry: &DirEntry) -> (u8, String) {
+    if entry.is_dir {
+        return (0, "Folders".to_string());
+    }
+    match entry.meta.path.extension() {
+        Some(ext) => (1, ext.to_string_lossy().to_uppercase()),
+        None => (2, "No extension".to_string()),
+    }
+}
+
+fn date_group(modified: SystemTime, now: &DateTime<Local>) -> (u8, String) {
+    let modified = DateTime::<Local>::from(modified);
+    let (rank, header) = match (now.date_naive() - modified.date_naive()).num_days() {
+        // Modified in the future counts as today
+        ..=0 => (0, "Today"),
+        1 => (1, "Yesterday"),
+        2..=6 => (2, "This week"),
+        _ => (3, "Older"),
+    };
+    (rank, header.to_string())
+}
+
+fn letter_group(name: &str) -> (u8, String) {
+    match name.chars().next() {
+        Some(c) if c.is_alphabetic() => (1, c.to_uppercase().collect()),
+        // Digits and symbols before letters
+        _ => (0, "#".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dir_entry::DirEntryMeta;
+    use chrono::{Duration, TimeZone};
+    use std::path::PathBuf;
+
+    fn entry(name: &str, is_dir: bool) -> DirEntry {
+        let meta = DirEntryMeta {
+            path: PathBuf::from("/test").join(name),
+            modified: SystemTime::UNIX_EPOCH,
+        };
+        DirEntry::new(name.to_string(), meta, is_dir, false, 0, false)
+    }
+
+    #[test]
+    fn test_group_by_type() {
+        let mut entries = vec![
+            entry("notes.txt", false),
+            entry("Makefile", false),
+            entry("main.rs", false),
+            entry("src", true),
+            entry("README.TXT", false),
+        ];
+        GroupBy::Type.sort(&mut entries);
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["src", "main.rs", "notes.txt", "README.TXT", "Makefile"]
+        );
+
+        let rows = GroupBy::Type.rows(&entries, &[0, 1, 3, 4]);
+        assert_eq!(
+            rows,
+            vec![
+                GroupRow::Header("Folders".to_string()),
+                GroupRow::Entry(0),
+                GroupRow::Header("RS".to_string()),
+                GroupRow::Entry(1),
+                GroupRow::Header("TXT".to_string()),
+                GroupRow::Entry(3),
+                GroupRow::Header("No extension".to_string()),
+                GroupRow::Entry(4),
+            ]
+        );
+        assert_eq!(GroupBy::None.rows(&entries, &[0, 1]).len(), 2);
+    }
+
+    #[test]
+    fn test_date_group() {
+        let now = Local.with_ymd_and_hms(2024, 5, 10, 9, 0, 0).unwrap();
+        let group = |time: DateTime<Local>| date_group(time.into(), &now).1;
+        assert_eq!(group(now + Duration::hours(2)), "Today");
+        assert_eq!(group(now - Duration::hours(9)), "Today");
+        assert_eq!(group(now - Duration::hours(10)), "Yesterday");
+        assert_eq!(group(now - Duration::days(6)), "This week");
+        assert_eq!(group(now - Duration::days(7)), "Older");
+    }
+
+    #[test]
+    fn test_letter_group() {
+        let mut entries = vec![
+            entry("beta", false),
+            entry("Alpha", true),
+            entry("2024", false),
+            entry("apple", false),
+            entry("émile", false),
+        ];
+        GroupBy::Letter.sort(&mut entries);
+        let rows = GroupBy::Letter.rows(&entries, &[0, 1, 2, 3, 4]);
+        let headers: Vec<_> = rows
+            .iter()
+            .filter_map(|row| match row {
+                GroupRow::Header(header) => Some(header.as_str()),
+                GroupRow::Entry(_) => None,
+            })
+            .collect();
+        assert_eq!(headers, vec!["#", "A", "B", "É"]);
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["2024", "Alpha", "apple", "beta", "émile"]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Selection positions jumped away from, like the vim jump list
+//!
+//! Unlike the directory history, a jump remembers the selected entry too, so
+//! going back returns to the exact position in a directory.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Oldest jumps are forgotten beyond this
+const MAX_JUMPS: usize = 100;
+
+/// A selection position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jump {
+    pub dir: PathBuf,
+    /// Selected entry, `None` in an empty directory
+    pub selected: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JumpList {
+    jumps: Vec<Jump>,
+    /// Index of the jump the selection is at, `jumps.len()` unless going
+    /// through the list
+    position: usize,
+}
+
+impl JumpList {
+    /// Remember the position a jump starts from, forgetting the jumps after
+    /// the current one
+    pub fn record(&mut self, jump: Jump) {
+        self.jumps.truncate(self.position);
+        self.push(jump);
+    }
+
+    /// Previous position, `current` is remembered for going forward again
+    pub fn back(&mut self, current: Jump) -> Option<Jump> {
+        if self.position == self.jumps.len() {
+            self.push(current);
+            self.position = self.jumps.len() - 1;
+        }
+        if self.position == 0 {
+            return None;
+        }
+        self.position -= 1;
+        Some(self.jumps[self.position].clone())
+    }
+
+    /// Next position after going back
+    pub fn forward(&mut self) -> Option<Jump> {
+        if self.position + 1 >= self.jumps.len() {
+            return None;
+        }
+        self.position += 1;
+        Some(self.jumps[self.position].clone())
+    }
+
+    /// Add a jump to the end, moving it there if it's already in the list
+    fn push(&mut self, jump: Jump) {
+        self.jumps.retain(|existing| *existing != jump);
+        self.jumps.push(jump);
+        if self.jumps.len() > MAX_JUMPS {
+            self.jumps.drain(..self.jumps.len() - MAX_JUMPS);
+        }
+        self.position = self.jumps.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jump(dir: &str, selected: &str) -> Jump {
+        Jump {
+            dir: PathBuf::from(dir),
+            selected: Some(PathBuf::from(dir).join(selected)),
+        }
+    }
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut list = JumpList::default();
+        assert_eq!(list.back(jump("/a", "x")), None);
+
+        list.record(jump("/a", "x"));
+        list.record(jump("/b", "y"));
+        assert_eq!(list.back(jump("/c", "z")), Some(jump("/b", "y")));
+        assert_eq!(list.back(jump("/b", "y")), Some(jump("/a", "x")));
+        assert_eq!(list.back(jump("/a", "x")), None);
+        assert_eq!(list.forward(), Some(jump("/b", "y")));
+        assert_eq!(list.forward(), Some(jump("/c", "z")));
+        assert_eq!(list.forward(), None);
+    }
+
+    #[test]
+    fn test_record_after_going_back() {
+        let mut list = JumpList::default();
+        list.record(jump("/a", "x"));
+        list.record(jump("/b", "y"));
+        list.back(jump("/c", "z"));
+        list.back(jump("/b", "y"));
+
+        // A new jump from /a forgets the jumps after it
+        list.record(jump("/a", "w"));
+        assert_eq!(list.forward(), None);
+        assert_eq!(list.back(jump("/d", "v")), Some(jump("/a", "w")));
+    }
+
+    #[test]
+    fn test_duplicates_and_limit() {
+        let mut list = JumpList::default();
+        list.record(jump("/a", "x"));
+        list.record(jump("/b", "y"));
+        list.record(jump("/a", "x"));
+        assert_eq!(list.back(jump("/c", "z")), Some(jump("/a", "x")));
+        assert_eq!(list.back(jump("/a", "x")), Some(jump("/b", "y")));
+        assert_eq!(list.back(jump("/b", "y")), None);
+
+        let mut list = JumpList::default();
+        for i in 0..MAX_JUMPS + 10 {
+            list.record(jump("/a", &i.to_string()));
+        }
+        assert_eq!(list.jumps.len(), MAX_JUMPS);
+        assert_eq!(list.jumps[0], jump("/a", "10"));
+    }
+}
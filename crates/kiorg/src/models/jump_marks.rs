@@ -0,0 +1,93 @@
+//! Selection positions saved under a letter, like marks in vim
+//!
+//! `ma` remembers the current directory and selected entry as mark `a`, `'a`
+//! goes back to it from anywhere.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::jump_list::Jump;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JumpMarks(BTreeMap<char, Jump>);
+
+impl JumpMarks {
+    /// Marks are named by a lowercase letter
+    #[must_use]
+    pub const fn is_valid_name(name: char) -> bool {
+        name.is_ascii_lowercase()
+    }
+
+    /// Save `jump` as mark `name`, replacing the previous one
+    pub fn set(&mut self, name: char, jump: Jump) {
+        if Self::is_valid_name(name) {
+            self.0.insert(name, jump);
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, name: char) -> Option<&Jump> {
+        self.0.get(&name)
+    }
+
+    pub fn remove(&mut self, name: char) -> Option<Jump> {
+        self.0.remove(&name)
+    }
+
+    /// Marks in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (char, &Jump)> {
+        self.0.iter().map(|(name, jump)| (*name, jump))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn jump(dir: &str, selected: Option<&str>) -> Jump {
+        Jump {
+            dir: PathBuf::from(dir),
+            selected: selected.map(PathBuf::from),
+        }
+    }
+
+    #[test]
+    fn test_marks_round_trip_through_json() {
+        let mut marks = JumpMarks::default();
+        marks.set('b', jump("/tmp", None));
+        marks.set('a', jump("/home", Some("/home/notes.txt")));
+        // Only lowercase letters name a mark
+        marks.set('A', jump("/ignored", None));
+        marks.set('1', jump("/ignored", None));
+        assert_eq!(
+            marks.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!['a', 'b']
+        );
+
+        // Setting a mark again moves it
+        marks.set('b', jump("/var", None));
+        assert_eq!(marks.get('b'), Some(&jump("/var", None)));
+
+        let json = serde_json::to_string(&marks).unwrap();
+        let restored: JumpMarks = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, marks);
+
+        assert_eq!(
+            marks.remove('a'),
+            Some(jump("/home", Some("/home/notes.txt")))
+        );
+        assert_eq!(marks.len(), 1);
+    }
+}
@@ -1,4 +1,11 @@
 pub mod action_history;
+pub mod bookmark;
 pub mod dir_entry;
+pub mod dir_prefs;
+pub mod group_by;
+pub mod jump_list;
+pub mod jump_marks;
 pub mod preview_content;
+pub mod query;
 pub mod tab;
+pub mod window_state;
@@ -21,6 +21,9 @@ pub mod metadata {
     pub const IMG_FILE_SIZE: &str = "File Size";
     pub const IMG_FORMAT: &str = "Format";
 
+    // EXIF Metadata
+    pub const EXIF_GPS_COORDINATES: &str = "GPSCoordinates";
+
     // PDF Ebook Metadata
     pub const PDF_PAGE_COUNT: &str = "Page Count";
     pub const PDF_VERSION: &str = "PDF Version";
@@ -33,6 +36,8 @@ pub mod metadata {
     pub const PDF_TRAPPED: &str = "Trapped";
     pub const PDF_CREATION_DATE: &str = "CreationDate";
     pub const PDF_MOD_DATE: &str = "ModDate";
+    pub const PDF_FORM: &str = "Form";
+    pub const PDF_FORM_FIELDS: &str = "Form Fields";
 }
 
 /// Type alias for the async preview content receiver
@@ -180,6 +185,31 @@ impl EbookMeta {
     }
 }
 
+/// Rendered HTML page or MHTML archive
+#[derive(Clone)]
+pub struct HtmlMeta {
+    pub document: crate::utils::html::Document,
+    /// Markup of the page, shown when the source is toggled on
+    pub source: String,
+    /// Directory of the file, relative links and images are resolved against it
+    pub dir: PathBuf,
+    /// URL an MHTML archive was saved from, relative links point there
+    pub location: Option<String>,
+    /// Image URI and bytes by the `src` used in the page
+    pub images: HashMap<String, (String, egui::load::Bytes)>,
+}
+
+impl std::fmt::Debug for HtmlMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HtmlMeta")
+            .field("document", &self.document)
+            .field("dir", &self.dir)
+            .field("location", &self.location)
+            .field("images", &self.images.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 pub use ffmpeg_sidecar::event::{AudioStream, VideoStream};
 
 /// Stream type specific metadata
@@ -272,6 +302,27 @@ pub struct ImageMeta {
     pub image: egui::Image<'static>,
     /// Keep the texture handle alive to prevent GPU texture from being freed
     pub _texture_handle: Option<egui::TextureHandle>,
+    /// Draw a checkerboard behind transparent areas, used for SVGs
+    pub checkerboard: bool,
+    /// Color histogram, not computed for animated images
+    pub stats: Option<ImageStats>,
+}
+
+/// Value distribution of one color channel of an image
+#[derive(Clone, Debug, PartialEq, Archive, Deserialize, Serialize, CheckBytes)]
+pub struct ChannelStats {
+    /// Number of pixels for each of the 256 values
+    pub histogram: Vec<u32>,
+    pub min: u8,
+    pub max: u8,
+    pub mean: f32,
+}
+
+/// Color histogram and basic statistics of an image
+#[derive(Clone, Debug, PartialEq, Archive, Deserialize, Serialize, CheckBytes)]
+pub struct ImageStats {
+    /// Red, green, blue and luminance, in this order
+    pub channels: Vec<ChannelStats>,
 }
 
 // Manual implementation of Debug for ImageMeta
@@ -286,6 +337,8 @@ impl std::fmt::Debug for ImageMeta {
                 "_texture_handle",
                 &self._texture_handle.as_ref().map(|_| "TextureHandle"),
             )
+            .field("checkerboard", &self.checkerboard)
+            .field("stats", &self.stats.as_ref().map(|_| "ImageStats"))
             .finish()
     }
 }
@@ -305,6 +358,8 @@ impl ImageMeta {
             exif_data,
             image,
             _texture_handle: Some(texture),
+            checkerboard: false,
+            stats: None,
         }
     }
 
@@ -322,6 +377,8 @@ impl ImageMeta {
             exif_data,
             image,
             _texture_handle: None, // No texture handle for URI-based images
+            checkerboard: false,
+            stats: None,
         }
     }
 }
@@ -350,8 +407,16 @@ pub enum PreviewContent {
     Pdf(PdfMeta),
     /// Ebook document without page navigation
     Ebook(EbookMeta),
+    /// HTML page rendered as formatted text
+    Html(HtmlMeta),
+    /// Headers of an executable or library
+    Binary(BinaryMeta),
     /// Directory content with a list of entries
     Directory(Vec<DirectoryEntry>),
+    /// Differences between the two marked files, only shown in the popup
+    Diff(Box<crate::ui::popup::preview::diff::DiffState>),
+    /// Placeholder for a file that is only previewed on request, e.g. a huge image
+    Deferred { path: PathBuf, reason: String },
     Loading {
         path: PathBuf,
         receiver: PreviewReceiver,
@@ -359,6 +424,17 @@ pub enum PreviewContent {
     },
 }
 
+/// Headers of an ELF, PE or Mach-O binary
+#[derive(Clone, Debug, Default)]
+pub struct BinaryMeta {
+    /// Format and kind, e.g. "ELF 64-bit shared library"
+    pub format: String,
+    /// Label and value pairs such as the architecture and version
+    pub details: Vec<(String, String)>,
+    /// Libraries the binary links against
+    pub libraries: Vec<String>,
+}
+
 /// Represents an entry in a directory listing for preview
 #[derive(Clone, Debug)]
 pub struct DirectoryEntry {
@@ -399,14 +475,205 @@ fn load_into_texture(
 ) -> (egui::widgets::ImageSource<'static>, egui::TextureHandle) {
     let rgba8 = dynamic_image.to_rgba8();
     let size = [rgba8.width() as usize, rgba8.height() as usize];
-    let color_image =
-        egui::ColorImage::from_rgba_unmultiplied(size, rgba8.as_flat_samples().as_slice());
+    load_rgba_into_texture(ctx, size, rgba8.as_flat_samples().as_slice(), name)
+}
+
+fn load_rgba_into_texture(
+    ctx: &egui::Context,
+    size: [usize; 2],
+    rgba: &[u8],
+    name: String,
+) -> (egui::widgets::ImageSource<'static>, egui::TextureHandle) {
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, rgba);
     let texture = ctx.load_texture(name, color_image, Default::default());
     let source =
         egui::widgets::ImageSource::Texture(egui::load::SizedTexture::from_handle(&texture));
     (source, texture)
 }
 
+/// Data of a plugin image kept to save the preview in the disk cache
+enum PluginImageData {
+    /// Path of an image file
+    File(String),
+    /// Encoded image
+    Encoded(Vec<u8>),
+    Rgba {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    },
+}
+
+impl PluginImageData {
+    /// Bytes that `image::load_from_memory` can decode
+    fn into_encoded(self) -> Option<Vec<u8>> {
+        match self {
+            Self::File(path) => std::fs::read(path).ok(),
+            Self::Encoded(data) => Some(data),
+            Self::Rgba {
+                width,
+                height,
+                pixels,
+            } => {
+                let image = image::RgbaImage::from_raw(width, height, pixels)?;
+                let mut png_bytes = Vec::new();
+                image
+                    .write_to(
+                        &mut std::io::Cursor::new(&mut png_bytes),
+                        image::ImageFormat::Png,
+                    )
+                    .ok()?;
+                Some(png_bytes)
+            }
+        }
+    }
+}
+
+struct LoadedPluginImage {
+    uid: String,
+    source: egui::widgets::ImageSource<'static>,
+    texture: egui::TextureHandle,
+    data: PluginImageData,
+}
+
+/// Load the texture of a plugin image
+///
+/// An image sent again with the same uid and data reuses the texture loaded
+/// the first time instead of being decoded again.
+fn load_plugin_image(
+    ctx: &egui::Context,
+    source: kiorg_plugin::ImageSource,
+) -> Result<LoadedPluginImage, String> {
+    use crate::ui::preview::plugin::{cache_texture, cached_texture, fingerprint};
+
+    let reused = |uid: &str, fp: u64| {
+        cached_texture(ctx, uid, fp).map(|texture| {
+            let source = egui::widgets::ImageSource::Texture(
+                egui::load::SizedTexture::from_handle(&texture),
+            );
+            (source, texture)
+        })
+    };
+
+    match source {
+        kiorg_plugin::ImageSource::Path(path) => {
+            let uid = format!("plugin_preview_path_{}", path);
+            // The file is fingerprinted by its size and modification time
+            let file_fingerprint = std::fs::metadata(&path)
+                .ok()
+                .map(|m| fingerprint(format!("{}:{:?}", m.len(), m.modified().ok()).as_bytes()));
+            let (source, texture) = match file_fingerprint.and_then(|fp| reused(&uid, fp)) {
+                Some(reused) => reused,
+                None => {
+                    let dynamic_image = image::open(&path).map_err(|e| {
+                        format!("Failed to load image from path: {}\nError: {}", path, e)
+                    })?;
+                    let (source, texture) = load_into_texture(ctx, dynamic_image, uid.clone());
+                    if let Some(fp) = file_fingerprint {
+                        cache_texture(ctx, &uid, fp, texture.clone());
+                    }
+                    (source, texture)
+                }
+            };
+            Ok(LoadedPluginImage {
+                uid,
+                source,
+                texture,
+                data: PluginImageData::File(path),
+            })
+        }
+        kiorg_plugin::ImageSource::Bytes { format, data, uid } => {
+            let fp = fingerprint(&data);
+            let (source, texture) = match reused(&uid, fp) {
+                Some(reused) => reused,
+                None => {
+                    let dynamic_image = image::load_from_memory_with_format(&data, format)
+                        .map_err(|e| {
+                            format!(
+                                "Failed to decode image (format: {:?}, uid: {}\nError: {}",
+                                format, uid, e
+                            )
+                        })?;
+                    let (source, texture) = load_into_texture(ctx, dynamic_image, uid.clone());
+                    cache_texture(ctx, &uid, fp, texture.clone());
+                    (source, texture)
+                }
+            };
+            Ok(LoadedPluginImage {
+                uid,
+                source,
+                texture,
+                data: PluginImageData::Encoded(data),
+            })
+        }
+        kiorg_plugin::ImageSource::Shared {
+            path,
+            width,
+            height,
+            uid,
+        } => {
+            let rgba = kiorg_plugin::take_shared_image(&path, width, height)
+                .map_err(|e| format!("Failed to load shared image (uid: {})\nError: {}", uid, e))?;
+            let fp = fingerprint(&rgba);
+            let (source, texture) = match reused(&uid, fp) {
+                Some(reused) => reused,
+                None => {
+                    let size = [width as usize, height as usize];
+                    let (source, texture) = load_rgba_into_texture(ctx, size, &rgba, uid.clone());
+                    cache_texture(ctx, &uid, fp, texture.clone());
+                    (source, texture)
+                }
+            };
+            Ok(LoadedPluginImage {
+                uid,
+                source,
+                texture,
+                data: PluginImageData::Rgba {
+                    width,
+                    height,
+                    pixels: rgba,
+                },
+            })
+        }
+    }
+}
+
+/// Plugin preview waiting to be saved in the disk cache
+pub struct PluginPreviewCache {
+    components: Vec<CachedRenderedComponent>,
+    images: Vec<(usize, String, bool, PluginImageData)>,
+    /// An image failed to load, the preview is not cached so it's retried
+    incomplete: bool,
+}
+
+impl PluginPreviewCache {
+    /// Encode the images and save the preview, meant for a background thread
+    pub fn save(self, key: &str) {
+        if self.incomplete {
+            return;
+        }
+        let mut components = self.components;
+        // Images are inserted in order, so earlier indices stay valid
+        for (index, uid, interactive, data) in self.images {
+            let Some(cache_bytes) = data.into_encoded() else {
+                return;
+            };
+            components.insert(
+                index,
+                CachedRenderedComponent::Image(CachedRenderedImageComponent {
+                    uid,
+                    interactive,
+                    cache_bytes,
+                }),
+            );
+        }
+        let cached = CachedPreviewContent::PluginPreview { components };
+        if let Err(e) = crate::utils::preview_cache::save_preview(key, &cached) {
+            tracing::warn!("Failed to save plugin preview cache: {}", e);
+        }
+    }
+}
+
 /// Rendered version of plugin components that can hold processed data like textures
 #[derive(Clone, Debug)]
 pub enum RenderedComponent {
@@ -636,6 +903,7 @@ pub struct CachedImageMeta {
     pub exif_data: Option<HashMap<String, String>>,
     pub cache_bytes: Option<Vec<u8>>,
     pub uri: Option<String>,
+    pub stats: Option<ImageStats>,
 }
 
 #[derive(Archive, Deserialize, Serialize, CheckBytes)]
@@ -674,82 +942,86 @@ impl PreviewContent {
         Self::Text(content.into())
     }
 
+    /// Whether the previewed file is a text, image or PDF the printer can take
+    #[must_use]
+    pub fn is_printable(&self) -> bool {
+        matches!(
+            self,
+            Self::Text(_) | Self::HighlightedCode { .. } | Self::Image(_) | Self::Pdf(_)
+        )
+    }
+
     /// Creates a new plugin preview content by processing plugin components
     pub fn plugin_preview_from_components(
         components: Vec<kiorg_plugin::Component>,
         ctx: &egui::Context,
     ) -> Self {
+        Self::plugin_preview_with_cache(components, ctx).0
+    }
+
+    /// Creates a new plugin preview content along with what's needed to
+    /// save it in the disk cache
+    pub fn plugin_preview_with_cache(
+        components: Vec<kiorg_plugin::Component>,
+        ctx: &egui::Context,
+    ) -> (Self, PluginPreviewCache) {
         let mut rendered_components = Vec::with_capacity(components.len());
+        let mut cache = PluginPreviewCache {
+            components: Vec::with_capacity(components.len()),
+            images: Vec::new(),
+            incomplete: false,
+        };
 
-        for component in components {
+        for (index, component) in components.into_iter().enumerate() {
             match component {
                 kiorg_plugin::Component::Title(t) => {
+                    cache
+                        .components
+                        .push(CachedRenderedComponent::Title(t.clone()));
                     rendered_components.push(RenderedComponent::Title(t))
                 }
                 kiorg_plugin::Component::Text(t) => {
+                    cache
+                        .components
+                        .push(CachedRenderedComponent::Text(t.clone()));
                     rendered_components.push(RenderedComponent::Text(t))
                 }
                 kiorg_plugin::Component::Table(t) => {
+                    cache
+                        .components
+                        .push(CachedRenderedComponent::Table(t.clone()));
                     rendered_components.push(RenderedComponent::Table(t))
                 }
-                kiorg_plugin::Component::Image(img) => match img.source {
-                    kiorg_plugin::ImageSource::Path(path) => match image::open(&path) {
-                        Ok(dynamic_image) => {
-                            let uid = format!("plugin_preview_path_{}", path);
-                            let (image, texture_handle) =
-                                load_into_texture(ctx, dynamic_image, uid.clone());
-                            rendered_components.push(RenderedComponent::Image(
-                                RenderedImageComponent {
-                                    uid,
-                                    image: egui::Image::new(image),
-                                    interactive: img.interactive,
-                                    _texture_handle: texture_handle,
-                                },
+                kiorg_plugin::Component::Image(img) => {
+                    let interactive = img.interactive;
+                    let component = match load_plugin_image(ctx, img.source) {
+                        Ok(loaded) => {
+                            cache.images.push((
+                                index,
+                                loaded.uid.clone(),
+                                interactive,
+                                loaded.data,
                             ));
+                            RenderedComponent::Image(RenderedImageComponent {
+                                uid: loaded.uid,
+                                image: egui::Image::new(loaded.source),
+                                interactive,
+                                _texture_handle: loaded.texture,
+                            })
                         }
-                        Err(e) => {
-                            rendered_components.push(RenderedComponent::Text(
-                                kiorg_plugin::TextComponent {
-                                    text: format!(
-                                        "Failed to load image from path: {}\nError: {}",
-                                        path, e
-                                    ),
-                                },
-                            ));
+                        Err(text) => {
+                            cache.incomplete = true;
+                            RenderedComponent::Text(kiorg_plugin::TextComponent { text })
                         }
-                    },
-                    kiorg_plugin::ImageSource::Bytes { format, data, uid } => {
-                        match image::load_from_memory_with_format(&data, format) {
-                            Ok(dynamic_image) => {
-                                let (image, texture_handle) =
-                                    load_into_texture(ctx, dynamic_image, uid.clone());
-                                rendered_components.push(RenderedComponent::Image(
-                                    RenderedImageComponent {
-                                        uid,
-                                        image: egui::Image::new(image),
-                                        interactive: img.interactive,
-                                        _texture_handle: texture_handle,
-                                    },
-                                ));
-                            }
-                            Err(e) => {
-                                rendered_components.push(RenderedComponent::Text(
-                                    kiorg_plugin::TextComponent {
-                                        text: format!(
-                                            "Failed to decode image (format: {:?}, uid: {}\nError: {}",
-                                            format, uid, e
-                                        ),
-                                    },
-                                ));
-                            }
-                        }
-                    }
-                },
+                    };
+                    rendered_components.push(component);
+                }
             }
         }
-        Self::PluginPreview {
+        let content = Self::PluginPreview {
             components: rendered_components,
-        }
+        };
+        (content, cache)
     }
 
     /// Creates a new zip preview content from a list of entries
@@ -833,6 +1105,8 @@ impl CachedPreviewContent {
                     exif_data: meta.exif_data,
                     image,
                     _texture_handle,
+                    checkerboard: false,
+                    stats: meta.stats,
                 };
                 Ok(PreviewContent::Image(image_meta))
             }
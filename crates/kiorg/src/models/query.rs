@@ -0,0 +1,265 @@
+//! Search bar queries with filters on entry attributes
+//!
+//! A query is made of whitespace separated terms. Terms like `ext:pdf`,
+//! `size>10mb`, `modified<7d` or `type:dir` filter entries, all other terms
+//! form the name query. Every filter must match.
+
+use std::time::{Duration, SystemTime};
+
+use super::dir_entry::DirEntry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Split a leading comparison operator off `s`
+    fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+        [
+            ("<=", Self::LessOrEqual),
+            (">=", Self::GreaterOrEqual),
+            ("<", Self::Less),
+            (">", Self::Greater),
+            ("=", Self::Equal),
+            (":", Self::Equal),
+        ]
+        .into_iter()
+        .find_map(|(op, comparison)| s.strip_prefix(op).map(|rest| (comparison, rest)))
+    }
+
+    fn compare<T: Ord>(self, value: T, bound: T) -> bool {
+        match self {
+            Self::Less => value < bound,
+            Self::LessOrEqual => value <= bound,
+            Self::Equal => value == bound,
+            Self::GreaterOrEqual => value >= bound,
+            Self::Greater => value > bound,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryType {
+    Dir,
+    File,
+    Symlink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// One of the lowercase extensions, `ext:jpg,png`
+    Extension(Vec<String>),
+    /// Size of files in bytes, `size>10mb`
+    Size(Comparison, u64),
+    /// Time since the last modification, `modified<7d` lists entries changed
+    /// in the last week
+    Age(Comparison, Duration),
+    Type(EntryType),
+}
+
+impl Filter {
+    fn parse(term: &str) -> Option<Self> {
+        if let Some(extensions) = term.strip_prefix("ext:") {
+            let extensions: Vec<String> = extensions
+                .split(',')
+                .map(|ext| ext.trim_start_matches('.').to_lowercase())
+                .filter(|ext| !ext.is_empty())
+                .collect();
+            return (!extensions.is_empty()).then_some(Self::Extension(extensions));
+        }
+        if let Some(entry_type) = term.strip_prefix("type:") {
+            let entry_type = match entry_type {
+                "d" | "dir" => EntryType::Dir,
+                "f" | "file" => EntryType::File,
+                "l" | "link" | "symlink" => EntryType::Symlink,
+                _ => return None,
+            };
+            return Some(Self::Type(entry_type));
+        }
+        if let Some(rest) = term.strip_prefix("size") {
+            let (comparison, size) = Comparison::parse_prefix(rest)?;
+            return Some(Self::Size(comparison, parse_size(size)?));
+        }
+        if let Some(rest) = term.strip_prefix("modified") {
+            let (comparison, age) = Comparison::parse_prefix(rest)?;
+            return Some(Self::Age(comparison, parse_age(age)?));
+        }
+        None
+    }
+
+    fn matches(&self, entry: &DirEntry, now: SystemTime) -> bool {
+        match self {
+            Self::Extension(extensions) => entry
+                .meta
+                .path
+                .extension()
+                .is_some_and(|ext| extensions.contains(&ext.to_string_lossy().to_lowercase())),
+            Self::Size(comparison, size) => !entry.is_dir && comparison.compare(entry.size, *size),
+            Self::Age(comparison, age) => {
+                // Entries from the future count as just modified
+                let entry_age = now
+                    .duration_since(entry.meta.modified)
+                    .unwrap_or(Duration::ZERO);
+                comparison.compare(entry_age, *age)
+            }
+            Self::Type(EntryType::Dir) => entry.is_dir,
+            Self::Type(EntryType::File) => !entry.is_dir,
+            Self::Type(EntryType::Symlink) => entry.is_symlink,
+        }
+    }
+}
+
+/// Parse sizes like `512`, `10k`, `10kb` or `1.5g`, units are powers of 1024
+fn parse_size(s: &str) -> Option<u64> {
+    let s = s.to_lowercase();
+    let s = s.strip_suffix('b').unwrap_or(&s);
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let multiplier: u64 = match unit {
+        "" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        "t" => 1 << 40,
+        _ => return None,
+    };
+    let number: f64 = number.parse().ok()?;
+    Some((number * multiplier as f64) as u64)
+}
+
+/// Parse ages like `30s`, `15min`, `2h`, `7d`, `2w`, `6mo` or `1y`
+fn parse_age(s: &str) -> Option<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let seconds: u64 = match unit.to_lowercase().as_str() {
+        "s" => 1,
+        "min" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "mo" => 30 * 24 * 60 * 60,
+        "y" => 365 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let number: u64 = number.parse().ok()?;
+    Some(Duration::from_secs(number.checked_mul(seconds)?))
+}
+
+/// Search bar query split into the name query and filters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    /// Terms that aren't filters, matched against entry names
+    pub name: String,
+    pub filters: Vec<Filter>,
+}
+
+impl Query {
+    /// Parse a query, terms that aren't valid filters are part of the name
+    #[must_use]
+    pub fn parse(query: &str) -> Self {
+        let mut filters = Vec::new();
+        let mut name = Vec::new();
+        for term in query.split_whitespace() {
+            match Filter::parse(term) {
+                Some(filter) => filters.push(filter),
+                None => name.push(term),
+            }
+        }
+        if filters.is_empty() {
+            // Keep the name query as typed, spaces included
+            return Self {
+                name: query.to_string(),
+                filters,
+            };
+        }
+        Self {
+            name: name.join(" "),
+            filters,
+        }
+    }
+
+    /// Whether `entry` passes every filter, the name isn't checked
+    #[must_use]
+    pub fn matches_filters(&self, entry: &DirEntry, now: SystemTime) -> bool {
+        self.filters.iter().all(|filter| filter.matches(entry, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dir_entry::DirEntryMeta;
+    use std::path::PathBuf;
+
+    fn entry(name: &str, is_dir: bool, size: u64, age: Duration, now: SystemTime) -> DirEntry {
+        DirEntry::new(
+            name.to_string(),
+            DirEntryMeta {
+                path: PathBuf::from("/tmp").join(name),
+                modified: now - age,
+            },
+            is_dir,
+            false,
+            size,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = Query::parse("report ext:pdf,.DOCX size>10mb modified<=7d type:dir final");
+        assert_eq!(query.name, "report final");
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter::Extension(vec!["pdf".to_string(), "docx".to_string()]),
+                Filter::Size(Comparison::Greater, 10 << 20),
+                Filter::Age(
+                    Comparison::LessOrEqual,
+                    Duration::from_secs(7 * 24 * 60 * 60)
+                ),
+                Filter::Type(EntryType::Dir),
+            ]
+        );
+
+        // Invalid filters are searched for by name
+        let query = Query::parse("size>lots type:pipe  a");
+        assert_eq!(query.name, "size>lots type:pipe  a");
+        assert!(query.filters.is_empty());
+
+        assert_eq!(parse_size("1.5k"), Some(1536));
+        assert_eq!(parse_size("42"), Some(42));
+        assert_eq!(parse_age("15min"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_age("3x"), None);
+    }
+
+    #[test]
+    fn test_filters_match() {
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let small = entry("small.PDF", false, 100, day, now);
+        let large = entry("large.pdf", false, 20 << 20, 10 * day, now);
+        let dir = entry("docs", true, 0, day, now);
+
+        let query = Query::parse("ext:pdf size>10mb");
+        assert!(!query.matches_filters(&small, now));
+        assert!(query.matches_filters(&large, now));
+
+        let query = Query::parse("modified<7d");
+        assert!(query.matches_filters(&small, now));
+        assert!(!query.matches_filters(&large, now));
+        assert!(query.matches_filters(&dir, now));
+
+        let query = Query::parse("type:dir size<1k");
+        assert!(!query.matches_filters(&dir, now), "Sizes only match files");
+        assert!(Query::parse("type:d").matches_filters(&dir, now));
+        assert!(!Query::parse("type:file").matches_filters(&dir, now));
+    }
+}
@@ -1,6 +1,10 @@
 use crate::config::Config as AppConfig;
 use crate::models::action_history::TabActionHistory;
 use crate::models::dir_entry::DirEntry;
+use crate::models::dir_prefs::{DirPrefs, ViewPrefs};
+use crate::models::group_by::GroupBy;
+use crate::models::jump_list::JumpList;
+use crate::models::query::Query;
 use nucleo::{Config as NucleoConfig, Matcher, Utf32Str};
 use std::path::PathBuf;
 
@@ -9,6 +13,7 @@ pub enum SortColumn {
     Name,
     Modified,
     Size,
+    Extension,
     None,
 }
 
@@ -22,6 +27,9 @@ pub enum SortOrder {
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct TabState {
     pub current_path: PathBuf,
+    /// Hidden files setting of the tab, `None` follows the directory's one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_hidden: Option<bool>,
 }
 
 // Tab contains the full runtime state, but only TabState is persisted
@@ -33,11 +41,15 @@ pub struct Tab {
     pub selected_index: usize,
     pub parent_selected_index: usize,
     pub marked_entries: std::collections::HashSet<PathBuf>,
+    // Hidden files setting of this tab, overriding the directory's one
+    pub show_hidden: Option<bool>,
     // Range selection mode
     pub range_selection_start: Option<usize>,
     // History of visited directories
     pub history: Vec<PathBuf>,
     pub history_position: usize,
+    // Selection positions jumped away from, in this and other directories
+    pub jump_list: JumpList,
     // Action history for tracking mutations and rollback
     pub action_history: TabActionHistory,
     // Reverse index mapping DirEntry path to index in entries (private)
@@ -52,6 +64,7 @@ fn sort_entries_by(entries: &mut [DirEntry], sort_column: SortColumn, sort_order
         SortColumn::Name => |a: &DirEntry, b: &DirEntry| a.name.cmp(&b.name),
         SortColumn::Modified => |a: &DirEntry, b: &DirEntry| a.meta.modified.cmp(&b.meta.modified),
         SortColumn::Size => |a: &DirEntry, b: &DirEntry| a.size.cmp(&b.size),
+        SortColumn::Extension => extension_order,
         SortColumn::None => {
             return;
         }
@@ -82,6 +95,28 @@ fn sort_entries_by(entries: &mut [DirEntry], sort_column: SortColumn, sort_order
     }
 }
 
+// Order by extension ignoring case, files without one last, then by name
+fn extension_order(a: &DirEntry, b: &DirEntry) -> std::cmp::Ordering {
+    let (ext_a, ext_b) = (extension_bytes(a), extension_bytes(b));
+    ext_a
+        .is_none()
+        .cmp(&ext_b.is_none())
+        .then_with(|| {
+            let ext_b = ext_b.unwrap_or_default().iter().map(u8::to_ascii_lowercase);
+            let ext_a = ext_a.unwrap_or_default().iter().map(u8::to_ascii_lowercase);
+            ext_a.cmp(ext_b)
+        })
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+fn extension_bytes(entry: &DirEntry) -> Option<&[u8]> {
+    entry
+        .meta
+        .path
+        .extension()
+        .map(std::ffi::OsStr::as_encoded_bytes)
+}
+
 // Build the reverse index mapping paths to indices
 fn refresh_path_to_index(tab: &mut Tab) {
     tab.path_to_index.clear();
@@ -93,7 +128,10 @@ fn refresh_path_to_index(tab: &mut Tab) {
 impl TabState {
     #[must_use]
     pub const fn new(path: PathBuf) -> Self {
-        Self { current_path: path }
+        Self {
+            current_path: path,
+            show_hidden: None,
+        }
     }
 }
 
@@ -103,6 +141,7 @@ impl Tab {
         // Create new in-memory action history
         let action_history = TabActionHistory::new();
 
+        let show_hidden = None;
         let mut tab = Self {
             current_path: path.clone(),
             entries: Vec::new(),
@@ -110,9 +149,11 @@ impl Tab {
             selected_index: 0,
             parent_selected_index: 0,
             marked_entries: std::collections::HashSet::new(),
+            show_hidden,
             range_selection_start: None,
             history: Vec::new(),
             history_position: 0,
+            jump_list: JumpList::default(),
             action_history,
             path_to_index: std::collections::HashMap::new(),
             cached_filtered_entries: Vec::new(),
@@ -127,6 +168,7 @@ impl Tab {
     pub fn to_state(&self) -> TabState {
         TabState {
             current_path: self.current_path.clone(),
+            show_hidden: self.show_hidden,
         }
     }
 
@@ -138,6 +180,7 @@ impl Tab {
         // Create new in-memory action history
         let action_history = TabActionHistory::new();
 
+        let show_hidden = state.show_hidden;
         let mut tab = Self {
             current_path: state.current_path,
             entries: Vec::new(),
@@ -145,9 +188,11 @@ impl Tab {
             selected_index: 0,
             parent_selected_index: 0,
             marked_entries: std::collections::HashSet::new(),
+            show_hidden,
             range_selection_start: None,
             history: Vec::new(),
             history_position: 0,
+            jump_list: JumpList::default(),
             action_history,
             path_to_index: std::collections::HashMap::new(),
             cached_filtered_entries: Vec::new(),
@@ -261,6 +306,27 @@ impl Tab {
         }
     }
 
+    /// Drop the marks of entries of the current directory that are gone, e.g.
+    /// deleted or renamed outside kiorg
+    ///
+    /// Marks in other directories are kept, the entries must be fully listed.
+    pub fn reconcile_marked_entries(&mut self) {
+        let current_path = &self.current_path;
+        let path_to_index = &self.path_to_index;
+        self.marked_entries.retain(|path| {
+            path.parent() != Some(current_path.as_path()) || path_to_index.contains_key(path)
+        });
+    }
+
+    /// Number of marked entries outside the current directory
+    #[must_use]
+    pub fn marked_elsewhere_count(&self) -> usize {
+        self.marked_entries
+            .iter()
+            .filter(|path| !self.path_to_index.contains_key(*path))
+            .count()
+    }
+
     // Get the index of an entry by its path using the reverse index
     #[must_use]
     pub fn get_index_by_path(&self, path: &std::path::Path) -> Option<usize> {
@@ -305,8 +371,14 @@ impl Tab {
         case_insensitive: bool,
         fuzzy: bool,
     ) {
+        // Filters like `size>10mb` narrow down the entries matching the name query
+        let query = query.as_deref().map(Query::parse);
+        let name_query = query
+            .as_ref()
+            .map(|query| query.name.as_str())
+            .filter(|name| !name.is_empty());
         // Inline the filtering logic instead of calling get_filtered_entries_with_indices_and_case
-        let filtered_indices = match query.as_ref() {
+        let mut filtered_indices: Vec<usize> = match name_query {
             Some(q) if fuzzy => {
                 let mut config = NucleoConfig::DEFAULT;
                 config.ignore_case = case_insensitive;
@@ -351,6 +423,10 @@ impl Tab {
                 .collect(),
             None => (0..self.entries.len()).collect(),
         };
+        if let Some(query) = query.filter(|query| !query.filters.is_empty()) {
+            let now = std::time::SystemTime::now();
+            filtered_indices.retain(|&index| query.matches_filters(&self.entries[index], now));
+        }
 
         self.cached_filtered_entries = filtered_indices;
     }
@@ -363,68 +439,88 @@ impl Tab {
 }
 
 fn read_dir_entries(path: &std::path::Path, show_hidden: bool) -> Vec<DirEntry> {
-    if let Ok(read_dir) = std::fs::read_dir(path) {
-        read_dir
-            .filter_map(|entry| {
-                let entry = entry.ok()?;
-                let path = entry.path();
-                let name = entry.file_name().to_string_lossy().into_owned();
-
-                let file_type = entry.file_type().ok()?;
-                let is_symlink = file_type.is_symlink();
-
-                // Filter out hidden files if not requested
-                if !show_hidden {
-                    // For Windows, check the "hidden" file attribute.
-                    #[cfg(windows)]
-                    {
-                        use std::os::windows::fs::MetadataExt;
-                        if let Ok(metadata) = entry.metadata() {
-                            const HIDDEN_ATTRIBUTE: u32 = 0x2;
-                            if (metadata.file_attributes() & HIDDEN_ATTRIBUTE) != 0 {
-                                return None;
-                            }
-                        }
-                    }
+    read_dir_entries_up_to(path, show_hidden, usize::MAX).0
+}
 
-                    // For Unix-like systems, check for a leading dot.
-                    #[cfg(not(windows))]
-                    {
-                        if name.starts_with('.') {
-                            return None;
-                        }
-                    }
+/// Read up to `limit` entries of `path`, also returning the rest of the
+/// listing unless it was read to the end
+fn read_dir_entries_up_to(
+    path: &std::path::Path,
+    show_hidden: bool,
+    limit: usize,
+) -> (Vec<DirEntry>, Option<std::fs::ReadDir>) {
+    let Ok(mut read_dir) = std::fs::read_dir(path) else {
+        return (Vec::new(), None);
+    };
+    let mut entries = Vec::new();
+    for _ in 0..limit {
+        let Some(entry) = read_dir.next() else {
+            return (entries, None);
+        };
+        if let Some(entry) = entry.ok().and_then(|entry| read_entry(&entry, show_hidden)) {
+            entries.push(entry);
+        }
+    }
+    (entries, Some(read_dir))
+}
+
+/// Entry of a directory listing, `None` if it's hidden or can't be read
+pub(crate) fn read_entry(entry: &std::fs::DirEntry, show_hidden: bool) -> Option<DirEntry> {
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().into_owned();
+
+    let file_type = entry.file_type().ok()?;
+    let is_symlink = file_type.is_symlink();
+
+    // Filter out hidden files if not requested
+    if !show_hidden {
+        // For Windows, check the "hidden" file attribute.
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::MetadataExt;
+            if let Ok(metadata) = entry.metadata() {
+                const HIDDEN_ATTRIBUTE: u32 = 0x2;
+                if (metadata.file_attributes() & HIDDEN_ATTRIBUTE) != 0 {
+                    return None;
                 }
+            }
+        }
 
-                // For non-symlinks, we can determine is_dir without additional syscalls
-                let is_dir = if is_symlink {
-                    // For symlinks, we need to follow the link to determine if target is a directory
-                    // This is the only case where we need the additional syscall
-                    path.is_dir()
-                } else {
-                    // For regular files/directories, use the file_type directly
-                    file_type.is_dir()
-                };
+        // For Unix-like systems, check for a leading dot.
+        #[cfg(not(windows))]
+        {
+            if name.starts_with('.') {
+                return None;
+            }
+        }
+    }
 
-                // Get metadata for size and modification time
-                let metadata = entry.metadata().ok()?;
-                let modified = metadata
-                    .modified()
-                    .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                let size = if is_dir { 0 } else { metadata.len() };
-
-                Some(DirEntry::new(
-                    name,
-                    crate::models::dir_entry::DirEntryMeta { path, modified },
-                    is_dir,
-                    is_symlink,
-                    size,
-                ))
-            })
-            .collect()
+    // For non-symlinks, we can determine is_dir without additional syscalls
+    let is_dir = if is_symlink {
+        // For symlinks, we need to follow the link to determine if target is a directory
+        // This is the only case where we need the additional syscall
+        path.is_dir()
     } else {
-        Vec::new()
-    }
+        // For regular files/directories, use the file_type directly
+        file_type.is_dir()
+    };
+
+    // Get metadata for size and modification time
+    let metadata = entry.metadata().ok()?;
+    let modified = metadata
+        .modified()
+        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+    let size = if is_dir { 0 } else { metadata.len() };
+    let is_placeholder = crate::utils::cloud::is_placeholder(&path, &metadata);
+
+    Some(DirEntry::new(
+        name,
+        crate::models::dir_entry::DirEntryMeta { path, modified },
+        is_dir,
+        is_symlink,
+        size,
+        is_placeholder,
+    ))
 }
 
 // TabManagerState is the minimal state that gets serialized/deserialized
@@ -436,15 +532,25 @@ pub struct TabManagerState {
     pub sort_order: SortOrder,
     #[serde(default)]
     pub show_hidden: bool,
+    #[serde(default)]
+    pub group_by: GroupBy,
+    #[serde(default)]
+    pub dir_prefs: DirPrefs,
 }
 
 #[derive(Clone)]
 pub struct TabManager {
     tabs: Vec<Tab>,
     current_tab_index: usize,
+    // Settings of the current directory
     pub sort_column: SortColumn,
     pub sort_order: SortOrder,
     pub show_hidden: bool,
+    pub group_by: GroupBy,
+    // Settings changed last, used in directories without remembered ones
+    default_prefs: ViewPrefs,
+    // Settings remembered per directory
+    pub dir_prefs: DirPrefs,
 }
 
 impl TabManager {
@@ -470,6 +576,14 @@ impl TabManager {
             sort_column,
             sort_order,
             show_hidden: false,
+            group_by: GroupBy::None,
+            default_prefs: ViewPrefs {
+                sort_column,
+                sort_order,
+                show_hidden: false,
+                group_by: GroupBy::None,
+            },
+            dir_prefs: DirPrefs::default(),
         }
     }
 
@@ -479,9 +593,11 @@ impl TabManager {
         TabManagerState {
             tab_states: self.tabs.iter().map(Tab::to_state).collect(),
             current_tab_index: self.current_tab_index,
-            sort_column: self.sort_column,
-            sort_order: self.sort_order,
-            show_hidden: self.show_hidden,
+            sort_column: self.default_prefs.sort_column,
+            sort_order: self.default_prefs.sort_order,
+            show_hidden: self.default_prefs.show_hidden,
+            group_by: self.default_prefs.group_by,
+            dir_prefs: self.dir_prefs.clone(),
         }
     }
 
@@ -493,6 +609,14 @@ impl TabManager {
             sort_column: state.sort_column,
             sort_order: state.sort_order,
             show_hidden: state.show_hidden,
+            group_by: state.group_by,
+            default_prefs: ViewPrefs {
+                sort_column: state.sort_column,
+                sort_order: state.sort_order,
+                show_hidden: state.show_hidden,
+                group_by: state.group_by,
+            },
+            dir_prefs: state.dir_prefs,
         }
     }
 
@@ -536,6 +660,26 @@ impl TabManager {
         &self.tabs[self.current_tab_index]
     }
 
+    /// The tab at `index`, which must exist
+    #[must_use]
+    pub fn tab_ref(&self, index: usize) -> &Tab {
+        &self.tabs[index]
+    }
+
+    /// The tab showing `path`, preferring the current tab, or the current tab
+    /// if no tab shows it
+    pub fn tab_for_path_mut(&mut self, path: &std::path::Path) -> &mut Tab {
+        let index = if self.current_tab_ref().current_path == path {
+            self.current_tab_index
+        } else {
+            self.tabs
+                .iter()
+                .position(|tab| tab.current_path == path)
+                .unwrap_or(self.current_tab_index)
+        };
+        &mut self.tabs[index]
+    }
+
     // Get the current tab index
     #[must_use]
     pub const fn get_current_tab_index(&self) -> usize {
@@ -585,25 +729,147 @@ impl TabManager {
             self.sort_order = SortOrder::Descending;
         }
 
-        let (column, order) = (self.sort_column, self.sort_order);
+        let (column, order, group_by) = (self.sort_column, self.sort_order, self.group_by);
         let tab = self.current_tab_mut();
         sort_entries_by(&mut tab.entries, column, order);
+        group_by.sort(&mut tab.entries);
         sort_entries_by(&mut tab.parent_entries, column, order);
         refresh_path_to_index(tab);
 
         // Reset filter cache to show all entries when sort order changes
         tab.update_filtered_cache(&None, false, false);
+        self.remember_view_prefs();
     }
 
+    /// Group the entries of the current directory under headers
+    pub fn set_group_by(&mut self, group_by: GroupBy) {
+        self.group_by = group_by;
+        let (column, order) = (self.sort_column, self.sort_order);
+        let tab = self.current_tab_mut();
+        let selected = tab.selected_entry().map(|entry| entry.meta.path.clone());
+        // Sort again first to restore the order within groups
+        sort_entries_by(&mut tab.entries, column, order);
+        group_by.sort(&mut tab.entries);
+        refresh_path_to_index(tab);
+        tab.update_filtered_cache(&None, false, false);
+        if let Some(index) = selected.and_then(|path| tab.get_index_by_path(&path)) {
+            tab.selected_index = index;
+        }
+        self.remember_view_prefs();
+    }
+
+    /// Show or hide hidden files in the current tab only
     pub fn toggle_show_hidden(&mut self) {
         self.show_hidden = !self.show_hidden;
+        let show_hidden = self.show_hidden;
+        self.current_tab_mut().show_hidden = Some(show_hidden);
+    }
+
+    /// Show or hide hidden files in the current directory and by default, in
+    /// tabs without their own setting
+    pub fn toggle_default_show_hidden(&mut self) {
+        let path = self.current_tab_ref().current_path.clone();
+        let prefs = self.dir_prefs.get(&path).unwrap_or(self.default_prefs);
+        self.current_tab_mut().show_hidden = None;
+        self.show_hidden = !prefs.show_hidden;
+        self.remember_view_prefs();
+    }
+
+    /// Sort and hidden files settings of the current directory
+    #[must_use]
+    pub const fn view_prefs(&self) -> ViewPrefs {
+        ViewPrefs {
+            sort_column: self.sort_column,
+            sort_order: self.sort_order,
+            show_hidden: self.show_hidden,
+            group_by: self.group_by,
+        }
+    }
+
+    const fn set_view_prefs(&mut self, prefs: ViewPrefs) {
+        self.sort_column = prefs.sort_column;
+        self.sort_order = prefs.sort_order;
+        self.show_hidden = prefs.show_hidden;
+        self.group_by = prefs.group_by;
+    }
+
+    // Remember the settings for the current directory, they're also used in
+    // directories visited for the first time
+    fn remember_view_prefs(&mut self) {
+        let mut prefs = self.view_prefs();
+        let path = self.current_tab_ref().current_path.clone();
+        // The tab's own hidden files setting isn't one of the directory
+        if self.current_tab_ref().show_hidden.is_some() {
+            prefs.show_hidden = self
+                .dir_prefs
+                .get(&path)
+                .unwrap_or(self.default_prefs)
+                .show_hidden;
+        }
+        self.dir_prefs.set(&path, prefs);
+        self.default_prefs = prefs;
+    }
+
+    /// Add entries to the current tab in sort order, keeping the selected entry
+    ///
+    /// Used by listings that stream in, e.g. the flattened one.
+    pub fn extend_entries(&mut self, entries: Vec<DirEntry>) {
+        let (column, order, group_by) = (self.sort_column, self.sort_order, self.group_by);
+        let tab = self.current_tab_mut();
+        let selected = tab.selected_entry().map(|entry| entry.meta.path.clone());
+        tab.entries.extend(entries);
+        sort_entries_by(&mut tab.entries, column, order);
+        group_by.sort(&mut tab.entries);
+        refresh_path_to_index(tab);
+        tab.update_filtered_cache(&None, false, false);
+        if let Some(index) = selected.and_then(|path| tab.get_index_by_path(&path)) {
+            tab.selected_index = index;
+        }
+    }
+
+    /// Remove all entries of the current tab
+    pub fn clear_entries(&mut self) {
+        let tab = self.current_tab_mut();
+        tab.entries.clear();
+        tab.selected_index = 0;
+        refresh_path_to_index(tab);
+        tab.update_filtered_cache(&None, false, false);
+    }
+
+    /// Reload the entries of the tab at `index`, keeping the current tab
+    pub fn refresh_tab_entries(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        let current = std::mem::replace(&mut self.current_tab_index, index);
+        let prefs = self.view_prefs();
+        self.refresh_entries();
+        self.set_view_prefs(prefs);
+        self.current_tab_index = current;
     }
 
     pub fn refresh_entries(&mut self) {
+        self.load_entries(usize::MAX);
+    }
+
+    /// Reload the entries like [`Self::refresh_entries`], reading at most
+    /// `limit` entries of the current directory
+    ///
+    /// The rest of the listing is returned when there are more entries, to be
+    /// added with [`Self::extend_entries`].
+    pub fn load_entries(&mut self, limit: usize) -> Option<std::fs::ReadDir> {
+        let path = self.current_tab_ref().current_path.clone();
+        let mut prefs = self.dir_prefs.get(&path).unwrap_or(self.default_prefs);
+        if let Some(show_hidden) = self.current_tab_ref().show_hidden {
+            prefs.show_hidden = show_hidden;
+        }
+        self.set_view_prefs(prefs);
+
         // Store sort settings before borrowing self mutably
         let sort_column = self.sort_column;
         let sort_order = self.sort_order;
         let show_hidden = self.show_hidden;
+        let group_by = self.group_by;
 
         let tab = self.current_tab_mut();
         let current_path = tab.current_path.clone(); // Get current path from the tab
@@ -630,9 +896,11 @@ impl TabManager {
         // --- End: Parent Directory Logic ---
 
         // --- Start: Current Directory Logic ---
-        tab.entries = read_dir_entries(&current_path, show_hidden); // Read entries for the current path
+        let (entries, rest) = read_dir_entries_up_to(&current_path, show_hidden, limit);
+        tab.entries = entries; // Read entries for the current path
         // Sort entries using the global sort settings
         sort_entries_by(&mut tab.entries, sort_column, sort_order);
+        group_by.sort(&mut tab.entries);
         refresh_path_to_index(tab);
 
         // Reset filter cache to show all entries when entries change
@@ -642,6 +910,7 @@ impl TabManager {
         if tab.selected_index >= tab.entries.len() && !tab.entries.is_empty() {
             tab.selected_index = 0;
         }
+        rest
     }
 }
 
@@ -663,6 +932,7 @@ mod tests {
             is_dir,
             false, // is_symlink
             actual_size,
+            false, // is_placeholder
         )
     }
 
@@ -823,6 +1093,27 @@ mod tests {
         assert_eq!(get_names(&entries), vec!["file_c", "file_b", "file_a"]);
     }
 
+    #[test]
+    fn test_sort_extension() {
+        let mut entries = vec![
+            create_entry("notes.txt", false, 10, 100),
+            create_entry("Makefile", false, 20, 200),
+            create_entry("b.RS", false, 5, 50),
+            create_entry("src", true, 15, 0),
+            create_entry("a.rs", false, 5, 50),
+        ];
+        sort_entries_by(&mut entries, SortColumn::Extension, SortOrder::Ascending);
+        assert_eq!(
+            get_names(&entries),
+            vec!["src", "a.rs", "b.RS", "notes.txt", "Makefile"]
+        );
+        sort_entries_by(&mut entries, SortColumn::Extension, SortOrder::Descending);
+        assert_eq!(
+            get_names(&entries),
+            vec!["src", "Makefile", "notes.txt", "b.RS", "a.rs"]
+        );
+    }
+
     #[test]
     fn test_sort_stability_equal_primary_key() {
         // Test stability when primary sort key is the same (e.g., two files with the same name)
@@ -932,6 +1223,57 @@ mod tests {
         assert_eq!(new_tab.parent_selected_index, 0);
     }
 
+    #[test]
+    fn test_view_prefs_per_directory() {
+        let mut manager = TabManager::new(PathBuf::from("/downloads"));
+        manager.toggle_sort(SortColumn::Modified);
+        manager.current_tab_mut().current_path = PathBuf::from("/code");
+        manager.refresh_entries();
+        // Directories without remembered settings get the last ones
+        assert_eq!(manager.sort_column, SortColumn::Modified);
+
+        manager.toggle_sort(SortColumn::Name);
+        manager.current_tab_mut().current_path = PathBuf::from("/downloads");
+        manager.refresh_entries();
+        assert_eq!(
+            (manager.sort_column, manager.sort_order),
+            (SortColumn::Modified, SortOrder::Descending)
+        );
+
+        // Remembered across restarts
+        let state = manager.to_state();
+        assert_eq!(state.sort_column, SortColumn::Name);
+        let mut restored = TabManager::from_state(state);
+        restored.refresh_entries();
+        assert_eq!(restored.sort_column, SortColumn::Modified);
+        restored.current_tab_mut().current_path = PathBuf::from("/code");
+        restored.refresh_entries();
+        assert_eq!(restored.sort_column, SortColumn::Name);
+    }
+
+    #[test]
+    fn test_load_entries_up_to_limit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        let mut manager = TabManager::new(temp_dir.path().to_path_buf());
+
+        let rest = manager.load_entries(2).unwrap();
+        assert_eq!(manager.current_tab_ref().entries.len(), 2);
+        let rest: Vec<_> = rest
+            .flatten()
+            .filter_map(|entry| read_entry(&entry, false))
+            .collect();
+        manager.extend_entries(rest);
+        let mut names = get_names(&manager.current_tab_ref().entries);
+        names.sort();
+        assert_eq!(names, vec!["a.txt", "b.txt", "c.txt"]);
+
+        assert!(manager.load_entries(3).is_some());
+        assert!(manager.load_entries(4).is_none());
+    }
+
     #[test]
     fn test_fuzzy_search_functionality() {
         // Create a tab with sample file entries
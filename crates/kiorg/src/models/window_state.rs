@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+/// Window size used on first launch
+pub const DEFAULT_INNER_SIZE: [f32; 2] = [1280.0, 800.0];
+/// Smallest window size that is restored, matching the viewport's minimum size
+pub const MIN_INNER_SIZE: [f32; 2] = [800.0, 600.0];
+
+/// Window geometry persisted in the app state file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    /// Size of the window when it's neither maximized nor fullscreen
+    pub inner_size: [f32; 2],
+    /// Outer position of the window, unavailable on some platforms like Wayland
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// Size of the monitor the window was on
+    #[serde(default)]
+    pub monitor_size: Option<[f32; 2]>,
+}
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            inner_size: DEFAULT_INNER_SIZE,
+            position: None,
+            maximized: false,
+            fullscreen: false,
+            monitor_size: None,
+        }
+    }
+}
+
+impl WindowState {
+    /// Record the current geometry of the window
+    ///
+    /// Size and position are only updated while the window is in its normal
+    /// state so un-maximizing after a restart goes back to the previous size.
+    pub fn update_from_viewport(&mut self, info: &egui::ViewportInfo) {
+        if info.minimized == Some(true) {
+            return;
+        }
+        self.maximized = info.maximized.unwrap_or(self.maximized);
+        self.fullscreen = info.fullscreen.unwrap_or(self.fullscreen);
+        if let Some(monitor_size) = info.monitor_size {
+            self.monitor_size = Some(monitor_size.into());
+        }
+        if self.maximized || self.fullscreen {
+            return;
+        }
+        if let Some(inner_rect) = info.inner_rect {
+            self.inner_size = inner_rect.size().into();
+        }
+        if let Some(outer_rect) = info.outer_rect {
+            self.position = Some(outer_rect.min.into());
+        }
+    }
+
+    /// Apply the saved geometry to the builder of the main window
+    #[must_use]
+    pub fn apply_to_builder(&self, mut builder: egui::ViewportBuilder) -> egui::ViewportBuilder {
+        let [width, height] = self.inner_size;
+        if width.is_finite() && height.is_finite() {
+            builder = builder
+                .with_inner_size([width.max(MIN_INNER_SIZE[0]), height.max(MIN_INNER_SIZE[1])]);
+        }
+        if let Some(position) = self.position
+            && position.iter().all(|v| v.is_finite())
+        {
+            builder = builder.with_position(position);
+        }
+        builder
+            .with_maximized(self.maximized)
+            .with_fullscreen(self.fullscreen)
+    }
+
+    /// Geometry that moves the restored window onto the current monitor
+    ///
+    /// The window is restored where it was, which puts it back on its monitor.
+    /// If it ended up on a monitor with a different size, e.g. because the
+    /// original monitor was disconnected, returns the outer rect that shrinks
+    /// it to fit and centers it.
+    #[must_use]
+    pub fn fit_to_monitor(&self, monitor_size: egui::Vec2) -> Option<egui::Rect> {
+        let saved_monitor = egui::Vec2::from(self.monitor_size?);
+        if self.maximized || self.fullscreen || saved_monitor == monitor_size {
+            return None;
+        }
+
+        let size = egui::Vec2::from(self.inner_size)
+            .min(monitor_size * 0.9)
+            .max(MIN_INNER_SIZE.into());
+        let position = ((monitor_size - size) / 2.0).max(egui::Vec2::ZERO);
+        Some(egui::Rect::from_min_size(position.to_pos2(), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport_info(maximized: bool) -> egui::ViewportInfo {
+        egui::ViewportInfo {
+            inner_rect: Some(egui::Rect::from_min_size(
+                egui::pos2(110.0, 130.0),
+                egui::vec2(1000.0, 700.0),
+            )),
+            outer_rect: Some(egui::Rect::from_min_size(
+                egui::pos2(100.0, 100.0),
+                egui::vec2(1020.0, 730.0),
+            )),
+            maximized: Some(maximized),
+            fullscreen: Some(false),
+            minimized: Some(false),
+            monitor_size: Some(egui::vec2(2560.0, 1440.0)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_update_from_viewport() {
+        let mut state = WindowState::default();
+        state.update_from_viewport(&viewport_info(false));
+        assert_eq!(state.inner_size, [1000.0, 700.0]);
+        assert_eq!(state.position, Some([100.0, 100.0]));
+        assert_eq!(state.monitor_size, Some([2560.0, 1440.0]));
+        assert!(!state.maximized);
+
+        // Maximizing keeps the normal geometry
+        let mut maximized = viewport_info(true);
+        maximized.inner_rect = Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(2560.0, 1400.0),
+        ));
+        state.update_from_viewport(&maximized);
+        assert!(state.maximized);
+        assert_eq!(state.inner_size, [1000.0, 700.0]);
+    }
+
+    #[test]
+    fn test_fit_to_monitor() {
+        let state = WindowState {
+            inner_size: [2400.0, 1300.0],
+            position: Some([2560.0, 0.0]),
+            maximized: false,
+            fullscreen: false,
+            monitor_size: Some([2560.0, 1440.0]),
+        };
+
+        // Same monitor, nothing to do
+        assert_eq!(state.fit_to_monitor(egui::vec2(2560.0, 1440.0)), None);
+
+        let rect = state.fit_to_monitor(egui::vec2(1920.0, 1080.0)).unwrap();
+        assert!((rect.width() - 1728.0).abs() < 0.01);
+        assert!((rect.height() - 972.0).abs() < 0.01);
+        assert!((rect.min.x - 96.0).abs() < 0.01);
+        assert!((rect.min.y - 54.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_deserialize_partial() {
+        let state: WindowState = serde_json::from_str(r#"{"inner_size":[900.0,700.0]}"#).unwrap();
+        assert_eq!(state.inner_size, [900.0, 700.0]);
+        assert_eq!(state.position, None);
+        assert!(!state.maximized);
+    }
+}
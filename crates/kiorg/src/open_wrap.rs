@@ -1,4 +1,8 @@
 #[cfg(not(any(test, feature = "testing")))]
+pub use crate::utils::terminal::{open_in_terminal, open_system_terminal};
+#[cfg(not(any(test, feature = "testing")))]
+pub use mimeapps::set_default_app_for_file as set_default_app;
+#[cfg(not(any(test, feature = "testing")))]
 pub use open::{that as open_that, with as open_with};
 
 #[cfg(any(test, feature = "testing"))]
@@ -7,6 +11,9 @@ pub mod mock_open {
 
     static OPEN_WITH_CALLS: OnceLock<Mutex<Vec<OpenCall>>> = OnceLock::new();
     static OPEN_THAT_CALLS: OnceLock<Mutex<Vec<OpenCall>>> = OnceLock::new();
+    static SET_DEFAULT_CALLS: OnceLock<Mutex<Vec<OpenCall>>> = OnceLock::new();
+    static TERMINAL_CALLS: OnceLock<Mutex<Vec<OpenCall>>> = OnceLock::new();
+    static SYSTEM_TERMINAL_CALLS: OnceLock<Mutex<Vec<OpenCall>>> = OnceLock::new();
     static TEST_SERIALIZATION_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
     fn get_open_with_calls_storage() -> &'static Mutex<Vec<OpenCall>> {
@@ -17,6 +24,18 @@ pub mod mock_open {
         OPEN_THAT_CALLS.get_or_init(|| Mutex::new(Vec::new()))
     }
 
+    fn get_set_default_calls_storage() -> &'static Mutex<Vec<OpenCall>> {
+        SET_DEFAULT_CALLS.get_or_init(|| Mutex::new(Vec::new()))
+    }
+
+    fn get_terminal_calls_storage() -> &'static Mutex<Vec<OpenCall>> {
+        TERMINAL_CALLS.get_or_init(|| Mutex::new(Vec::new()))
+    }
+
+    fn get_system_terminal_calls_storage() -> &'static Mutex<Vec<OpenCall>> {
+        SYSTEM_TERMINAL_CALLS.get_or_init(|| Mutex::new(Vec::new()))
+    }
+
     fn get_test_serialization_lock() -> &'static Mutex<()> {
         TEST_SERIALIZATION_LOCK.get_or_init(|| Mutex::new(()))
     }
@@ -43,12 +62,36 @@ pub mod mock_open {
         calls.clone()
     }
 
+    pub fn get_set_default_calls() -> Vec<OpenCall> {
+        let calls = get_set_default_calls_storage().lock().unwrap();
+        calls.clone()
+    }
+
+    pub fn get_terminal_calls() -> Vec<OpenCall> {
+        let calls = get_terminal_calls_storage().lock().unwrap();
+        calls.clone()
+    }
+
+    pub fn get_system_terminal_calls() -> Vec<OpenCall> {
+        let calls = get_system_terminal_calls_storage().lock().unwrap();
+        calls.clone()
+    }
+
     pub fn clear_open_calls() {
         let mut with_calls = get_open_with_calls_storage().lock().unwrap();
         with_calls.clear();
 
         let mut that_calls = get_open_that_calls_storage().lock().unwrap();
         that_calls.clear();
+
+        let mut default_calls = get_set_default_calls_storage().lock().unwrap();
+        default_calls.clear();
+
+        let mut terminal_calls = get_terminal_calls_storage().lock().unwrap();
+        terminal_calls.clear();
+
+        let mut system_terminal_calls = get_system_terminal_calls_storage().lock().unwrap();
+        system_terminal_calls.clear();
     }
 
     pub fn open_with(
@@ -73,10 +116,48 @@ pub mod mock_open {
 
         Ok(())
     }
+
+    pub fn open_in_terminal(
+        path: &std::path::Path,
+        exec: &str,
+        _emulator: Option<&str>,
+    ) -> std::io::Result<()> {
+        let mut calls = get_terminal_calls_storage().lock().unwrap();
+        calls.push(OpenCall {
+            path: path.as_os_str().to_owned(),
+            app: Some(exec.to_string()),
+        });
+
+        Ok(())
+    }
+
+    pub fn open_system_terminal(
+        dir: &std::path::Path,
+        command: Option<&str>,
+    ) -> std::io::Result<()> {
+        let mut calls = get_system_terminal_calls_storage().lock().unwrap();
+        calls.push(OpenCall {
+            path: dir.as_os_str().to_owned(),
+            app: command.map(str::to_string),
+        });
+
+        Ok(())
+    }
+
+    pub fn set_default_app(path: &std::path::Path, app: &mimeapps::AppInfo) -> std::io::Result<()> {
+        let mut calls = get_set_default_calls_storage().lock().unwrap();
+        calls.push(OpenCall {
+            path: path.as_os_str().to_owned(),
+            app: Some(app.id.clone()),
+        });
+
+        Ok(())
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
 pub use mock_open::{
-    acquire_open_test_lock, clear_open_calls, get_open_that_calls, get_open_with_calls, open_that,
-    open_with,
+    acquire_open_test_lock, clear_open_calls, get_open_that_calls, get_open_with_calls,
+    get_set_default_calls, get_system_terminal_calls, get_terminal_calls, open_in_terminal,
+    open_system_terminal, open_that, open_with, set_default_app,
 };
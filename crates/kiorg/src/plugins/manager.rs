@@ -2,19 +2,31 @@
 //!
 //! The PluginManager is responsible for:
 //! - Discovering plugins in specified directories
+//! - Pinning the executable of every trusted plugin by its digest
 //! - Managing basic plugin metadata
 //! - Simple plugin operations without complex async execution
 
-use kiorg_plugin::{CallId, EngineCommand, EngineMessage, PluginMetadata};
+use kiorg_plugin::{
+    BulkOperationCapability, CallId, EngineCommand, EngineMessage, PluginMetadata, PluginResponse,
+};
 use snafu::Snafu;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+use crate::utils::hashing::{self, HashAlgorithm};
+
 /// Plugin executable prefix
 const PLUGIN_PREFIX: &str = "kiorg_plugin_";
+/// File in the plugin directory with the BLAKE3 digest of every trusted
+/// plugin executable, by file name
+const TRUST_FILE_NAME: &str = "trusted.json";
+/// How long a plugin gets to wrap up a cancelled bulk operation before it's killed
+const BULK_CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(5);
 
 /// Error types for plugin management
 #[derive(Debug, Snafu)]
@@ -32,6 +44,10 @@ pub enum PluginError {
     },
     #[snafu(display("IO error: {}", source))]
     IoError { source: std::io::Error },
+    #[snafu(display("Plugin executable changed since it was trusted"))]
+    Untrusted,
+    #[snafu(display("Operation cancelled"))]
+    Cancelled,
 }
 
 /// A failed plugin load attempt
@@ -41,6 +57,9 @@ pub struct FailedPlugin {
     pub path: PathBuf,
     /// Error message
     pub error: String,
+    /// Whether the plugin wasn't started because its executable changed
+    /// since it was trusted
+    pub changed: bool,
 }
 
 /// A simple loaded plugin reference with running process
@@ -56,6 +75,37 @@ pub struct LoadedPlugin {
     pub load_time: std::time::Duration,
     /// Compiled regex for preview file pattern matching
     pub preview_regex: Option<regex::Regex>,
+    /// Bulk operations offered by the plugin
+    pub bulk_operations: Vec<BulkOperation>,
+}
+
+/// A bulk operation declared in the plugin capabilities
+#[derive(Debug)]
+pub struct BulkOperation {
+    pub capability: BulkOperationCapability,
+    /// Compiled regex of the file pattern, matching any file if not set
+    file_regex: Option<regex::Regex>,
+}
+
+impl BulkOperation {
+    fn new(capability: BulkOperationCapability) -> Result<Self, regex::Error> {
+        let file_regex = capability
+            .file_pattern
+            .as_deref()
+            .map(regex::Regex::new)
+            .transpose()?;
+        Ok(Self {
+            capability,
+            file_regex,
+        })
+    }
+
+    /// Whether the operation can run on the file named `file_name`
+    pub fn matches(&self, file_name: &str) -> bool {
+        self.file_regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(file_name))
+    }
 }
 
 /// State of the running plugin
@@ -161,6 +211,136 @@ impl LoadedPlugin {
             }
         }
     }
+
+    /// Run the bulk operation `operation` on `paths`
+    ///
+    /// The operation runs in a separate plugin process so previews aren't
+    /// blocked meanwhile. `on_progress` is called with the progress streamed by
+    /// the plugin. Once `cancel` is set the plugin is asked to stop, and killed
+    /// if it doesn't finish within [`BULK_CANCEL_GRACE_PERIOD`]. Returns the
+    /// summary sent by the plugin.
+    pub fn run_bulk_operation(
+        &self,
+        operation: &str,
+        paths: &[PathBuf],
+        cancel: &AtomicBool,
+        mut on_progress: impl FnMut(u64, u64, Option<String>),
+    ) -> Result<String, PluginError> {
+        let mut child = Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| PluginError::ExecutionError {
+                message: format!("Failed to spawn plugin process: {}", e),
+            })?;
+
+        let result =
+            self.drive_bulk_operation(&mut child, operation, paths, cancel, &mut on_progress);
+
+        // Closing stdin lets the plugin exit on its own, but don't wait for it
+        drop(child.stdin.take());
+        let _ = child.kill();
+        let _ = child.wait();
+        result
+    }
+
+    fn drive_bulk_operation(
+        &self,
+        child: &mut Child,
+        operation: &str,
+        paths: &[PathBuf],
+        cancel: &AtomicBool,
+        on_progress: &mut impl FnMut(u64, u64, Option<String>),
+    ) -> Result<String, PluginError> {
+        PluginManager::perform_hello_handshake(child, &self.path)?;
+
+        let mut stdin = child.stdin.take().ok_or(PluginError::ExecutionError {
+            message: "Plugin stdin not available".to_string(),
+        })?;
+        let mut stdout = child.stdout.take().ok_or(PluginError::ExecutionError {
+            message: "Plugin stdout not available".to_string(),
+        })?;
+
+        let send = |stdin: &mut std::process::ChildStdin, command: EngineCommand| {
+            let message = EngineMessage {
+                id: CallId::new(),
+                command,
+            };
+            kiorg_plugin::send_message_to_writer(stdin, &message).map_err(|e| {
+                PluginError::ProtocolError {
+                    message: format!("Failed to send message: {}", e),
+                }
+            })
+        };
+        send(
+            &mut stdin,
+            EngineCommand::BulkOperation {
+                operation: operation.to_string(),
+                paths: paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect(),
+            },
+        )?;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            loop {
+                let result: Result<PluginResponse, _> =
+                    kiorg_plugin::read_message_from_reader(&mut stdout);
+                let failed = result.is_err();
+                if tx.send(result.map_err(|e| e.to_string())).is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        let plugin_name = &self.metadata.name;
+        let mut cancel_deadline = None;
+        loop {
+            if cancel_deadline.is_none() && cancel.load(Ordering::Relaxed) {
+                debug!(
+                    "Cancelling bulk operation '{}' of plugin '{}'",
+                    operation, plugin_name
+                );
+                send(&mut stdin, EngineCommand::Cancel)?;
+                cancel_deadline = Some(Instant::now() + BULK_CANCEL_GRACE_PERIOD);
+            }
+            if cancel_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                warn!("Plugin '{}' didn't stop after being cancelled", plugin_name);
+                return Err(PluginError::Cancelled);
+            }
+
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(Ok(PluginResponse::Progress {
+                    done,
+                    total,
+                    message,
+                })) => on_progress(done, total, message),
+                Ok(Ok(PluginResponse::BulkOperationDone { message })) => return Ok(message),
+                Ok(Ok(PluginResponse::Error { message })) => {
+                    return Err(PluginError::ExecutionError { message });
+                }
+                Ok(Ok(_)) => {
+                    return Err(PluginError::ProtocolError {
+                        message: "Expected bulk operation response from plugin".to_string(),
+                    });
+                }
+                Ok(Err(message)) => {
+                    return Err(PluginError::ProtocolError {
+                        message: format!("Failed to read response: {}", message),
+                    });
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(PluginError::ExecutionError {
+                        message: "Plugin response thread disconnected unexpectedly".to_string(),
+                    });
+                }
+            }
+        }
+    }
 }
 
 /// Helper to handle communication with a plugin process
@@ -285,6 +465,17 @@ impl PluginManager {
         }
     }
 
+    /// Manager of additional windows, sharing the plugins loaded by this one
+    ///
+    /// Only the original manager should be shut down.
+    pub fn share(&self) -> Self {
+        Self {
+            plugin_dir: self.plugin_dir.clone(),
+            loaded: self.loaded.clone(),
+            failed: self.failed.clone(),
+        }
+    }
+
     /// Load all plugins found in configured directories
     pub fn load_plugins(&mut self) -> Result<(), PluginError> {
         if !self.plugin_dir.exists() {
@@ -317,56 +508,98 @@ impl PluginManager {
 
         info!("Loading {} plugins in parallel", paths.len());
 
+        // Plugins are trusted the first time they are found, a changed
+        // executable isn't started until it's trusted again
+        let trust_file = self.plugin_dir.join(TRUST_FILE_NAME);
+        let mut pins = load_pins(&trust_file);
         let mut handles = Vec::new();
         for path in paths.into_iter() {
+            let pinned = pins.get(&pin_key(&path)).cloned();
             let handle = std::thread::spawn(move || {
-                let result = Self::load_single_plugin(&path);
+                let result = plugin_digest(&path).and_then(|digest| {
+                    if pinned.is_some_and(|pinned| pinned != digest) {
+                        return Err(PluginError::Untrusted);
+                    }
+                    Self::load_single_plugin(&path).map(|plugin| (plugin, digest))
+                });
                 (path, result)
             });
             handles.push(handle);
         }
 
+        let pin_count = pins.len();
         for handle in handles {
             match handle.join() {
-                Ok((path, result)) => match result {
-                    Ok(plugin) => {
-                        let name = plugin.metadata.name.clone();
-
-                        // Skip if already loaded
-                        if self.loaded.contains_key(&name) {
-                            debug!("Plugin '{}' already loaded, skipping", name);
-                            continue;
-                        }
-
-                        debug!(
-                            "Plugin '{}' loaded successfully in {:?}",
-                            name, plugin.load_time
-                        );
-                        self.loaded.insert(name.clone(), Arc::new(plugin));
-
-                        // Remove from failed if it was there previously (by path)
-                        self.failed
-                            .retain(|failed_plugin| failed_plugin.path != path);
-                    }
-                    Err(e) => {
-                        warn!("Failed to load plugin from '{:?}': {}", path, e);
-                        // Remove existing failure for this path to avoid duplicates
-                        self.failed.retain(|p| p.path != path);
-                        self.failed.push(FailedPlugin {
-                            path,
-                            error: e.to_string(),
-                        });
-                    }
-                },
+                Ok((path, Ok((plugin, digest)))) => {
+                    pins.entry(pin_key(&path)).or_insert(digest);
+                    self.add_loaded(path, plugin);
+                }
+                Ok((path, Err(e))) => self.add_failed(path, &e),
                 Err(err) => {
                     error!(err =? err, "Plugin loading thread panicked");
                 }
             }
         }
+        if pins.len() != pin_count
+            && let Err(e) = save_pins(&trust_file, &pins)
+        {
+            warn!("Failed to save trusted plugins: {}", e);
+        }
 
         Ok(())
     }
 
+    /// Trust the current executable of a plugin that changed and load it
+    pub fn trust_plugin(&mut self, path: &Path) -> Result<(), PluginError> {
+        let digest = plugin_digest(path)?;
+        let trust_file = self.plugin_dir.join(TRUST_FILE_NAME);
+        let mut pins = load_pins(&trust_file);
+        pins.insert(pin_key(path), digest);
+        save_pins(&trust_file, &pins).map_err(|e| PluginError::IoError { source: e })?;
+
+        match Self::load_single_plugin(path) {
+            Ok(plugin) => {
+                self.add_loaded(path.to_path_buf(), plugin);
+                Ok(())
+            }
+            Err(e) => {
+                self.add_failed(path.to_path_buf(), &e);
+                Err(e)
+            }
+        }
+    }
+
+    fn add_loaded(&mut self, path: PathBuf, plugin: LoadedPlugin) {
+        let name = plugin.metadata.name.clone();
+
+        // Skip if already loaded
+        if self.loaded.contains_key(&name) {
+            debug!("Plugin '{}' already loaded, skipping", name);
+            return;
+        }
+
+        debug!(
+            "Plugin '{}' loaded successfully in {:?}",
+            name, plugin.load_time
+        );
+        self.loaded.insert(name.clone(), Arc::new(plugin));
+
+        // Remove from failed if it was there previously (by path)
+        self.failed
+            .retain(|failed_plugin| failed_plugin.path != path);
+    }
+
+    fn add_failed(&mut self, path: PathBuf, e: &PluginError) {
+        warn!("Failed to load plugin from '{:?}': {}", path, e);
+        // Remove existing failure for this path to avoid duplicates
+        self.failed.retain(|p| p.path != path);
+        self.failed.push(FailedPlugin {
+            path,
+            error: e.to_string(),
+            changed: matches!(e, PluginError::Untrusted),
+        });
+    }
+
     /// Load a single plugin from the given path
     fn load_single_plugin(path: &std::path::Path) -> Result<LoadedPlugin, PluginError> {
         // Start the plugin process
@@ -387,16 +620,13 @@ impl PluginManager {
             Err(PluginError::Incompatible {
                 protocol_version,
                 metadata,
-            }) => {
-                let major_version = protocol_version.split('.').next().unwrap_or("0");
-                (
-                    *metadata,
-                    Some(format!(
-                        "Incompatible protocol version. Plugin built for protocol major version: {}",
-                        major_version
-                    )),
-                )
-            }
+            }) => (
+                *metadata,
+                Some(format!(
+                    "Incompatible protocol version. Plugin built for protocol version: {}",
+                    protocol_version
+                )),
+            ),
             Err(e) => {
                 let _ = child.kill();
                 return Err(e);
@@ -420,6 +650,23 @@ impl PluginManager {
             None
         };
 
+        let bulk_operations = match metadata
+            .capabilities
+            .bulk_operations
+            .iter()
+            .cloned()
+            .map(BulkOperation::new)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(operations) => operations,
+            Err(e) => {
+                let _ = child.kill();
+                return Err(PluginError::ExecutionError {
+                    message: format!("Invalid regex pattern: {}", e),
+                });
+            }
+        };
+
         Ok(LoadedPlugin {
             metadata,
             path: path.to_path_buf(),
@@ -429,6 +676,7 @@ impl PluginManager {
             }),
             load_time,
             preview_regex,
+            bulk_operations,
         })
     }
 
@@ -503,6 +751,38 @@ impl PluginManager {
             .cloned()
     }
 
+    /// Bulk operations applicable to all of `paths`, as the plugin and the
+    /// index into its [`LoadedPlugin::bulk_operations`], sorted by plugin name
+    pub fn bulk_operations_for(&self, paths: &[PathBuf]) -> Vec<(Arc<LoadedPlugin>, usize)> {
+        if paths.is_empty() {
+            return Vec::new();
+        }
+        let file_names: Vec<_> = paths
+            .iter()
+            .map(|path| path.file_name().map(|n| n.to_string_lossy()))
+            .collect();
+
+        let mut plugins: Vec<_> = self
+            .loaded
+            .values()
+            .filter(|plugin| !plugin.bulk_operations.is_empty())
+            .collect();
+        plugins.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+
+        let mut operations = Vec::new();
+        for plugin in plugins {
+            for (index, operation) in plugin.bulk_operations.iter().enumerate() {
+                let applicable = file_names
+                    .iter()
+                    .all(|name| name.as_deref().is_some_and(|name| operation.matches(name)));
+                if applicable {
+                    operations.push((plugin.clone(), index));
+                }
+            }
+        }
+        operations
+    }
+
     /// Shutdown plugin manager
     pub fn shutdown(&mut self) -> Result<(), PluginError> {
         // Unload all plugins
@@ -518,3 +798,93 @@ impl PluginManager {
         Ok(())
     }
 }
+
+/// Key of a plugin in the trust file, plugins are trusted by file name so
+/// the plugin directory can move
+fn pin_key(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// BLAKE3 digest of a plugin executable
+fn plugin_digest(path: &Path) -> Result<String, PluginError> {
+    let digests = hashing::hash_file(
+        path,
+        &[HashAlgorithm::Blake3],
+        &AtomicBool::new(false),
+        |_, _| {},
+    )
+    .unwrap_or_else(|| Err("Hashing was cancelled".to_string()))
+    .map_err(|message| PluginError::ExecutionError { message })?;
+    Ok(digests
+        .into_iter()
+        .map(|(_, digest)| digest)
+        .next()
+        .unwrap_or_default())
+}
+
+/// Digests of the trusted plugins, empty if nothing was trusted yet
+fn load_pins(trust_file: &Path) -> HashMap<String, String> {
+    std::fs::read_to_string(trust_file)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_pins(trust_file: &Path, pins: &HashMap<String, String>) -> std::io::Result<()> {
+    crate::utils::atomic_write::write(trust_file, serde_json::to_string_pretty(pins)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_operation_matches() {
+        let operation = BulkOperation::new(BulkOperationCapability {
+            id: "to_jpeg".to_string(),
+            label: "Convert to JPEG".to_string(),
+            file_pattern: Some(r"(?i)\.heic$".to_string()),
+        })
+        .unwrap();
+        assert!(operation.matches("photo.HEIC"));
+        assert!(!operation.matches("photo.png"));
+
+        let any_file = BulkOperation::new(BulkOperationCapability {
+            id: "count".to_string(),
+            label: "Count".to_string(),
+            file_pattern: None,
+        })
+        .unwrap();
+        assert!(any_file.matches("anything"));
+
+        assert!(
+            BulkOperation::new(BulkOperationCapability {
+                id: "bad".to_string(),
+                label: "Bad".to_string(),
+                file_pattern: Some("(".to_string()),
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_plugin_digest_pins() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugin = temp_dir.path().join("kiorg_plugin_test");
+        std::fs::write(&plugin, "v1").unwrap();
+        let trust_file = temp_dir.path().join(TRUST_FILE_NAME);
+        assert!(load_pins(&trust_file).is_empty());
+
+        let mut pins = HashMap::new();
+        pins.insert(pin_key(&plugin), plugin_digest(&plugin).unwrap());
+        save_pins(&trust_file, &pins).unwrap();
+        assert_eq!(load_pins(&trust_file), pins);
+        assert_eq!(pin_key(&plugin), "kiorg_plugin_test");
+
+        std::fs::write(&plugin, "v2").unwrap();
+        assert_ne!(plugin_digest(&plugin).unwrap(), pins["kiorg_plugin_test"]);
+    }
+}
@@ -0,0 +1,235 @@
+//! XDG desktop portal file chooser backend
+//!
+//! `kiorg --portal` serves `org.freedesktop.impl.portal.FileChooser` on the
+//! session bus. `xdg-desktop-portal` forwards the file dialogs of sandboxed
+//! and portal-aware apps to it, and every dialog is a `kiorg --pick` window
+//! whose picked paths are sent back as URIs.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+use crate::utils::file_operations::path_to_uri;
+
+pub const BUS_NAME: &str = "org.freedesktop.impl.portal.desktop.kiorg";
+const OBJECT_PATH: &str = "/org/freedesktop/portal/desktop";
+
+/// Portal response codes
+const RESPONSE_SUCCESS: u32 = 0;
+const RESPONSE_CANCELLED: u32 = 1;
+const RESPONSE_OTHER: u32 = 2;
+
+/// A file dialog requested through the portal
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChooserRequest {
+    pub title: String,
+    pub directories: bool,
+    pub multiple: bool,
+    /// Suggested file name, set for save dialogs
+    pub save_name: Option<String>,
+    pub current_folder: Option<PathBuf>,
+}
+
+impl ChooserRequest {
+    fn from_options(title: &str, options: &HashMap<&str, Value<'_>>, save: bool) -> Self {
+        let flag = |key| {
+            options
+                .get(key)
+                .and_then(|value| bool::try_from(value).ok())
+                .unwrap_or(false)
+        };
+        let save_name = save.then(|| {
+            options
+                .get("current_name")
+                .and_then(|value| <&str>::try_from(value).ok())
+                .unwrap_or_default()
+                .to_string()
+        });
+        let current_folder = options
+            .get("current_folder")
+            .and_then(|value| value.try_clone().ok())
+            .and_then(|value| Vec::<u8>::try_from(value).ok())
+            .and_then(|bytes| folder_from_bytes(&bytes));
+        Self {
+            title: title.to_string(),
+            directories: !save && flag("directory"),
+            multiple: !save && flag("multiple"),
+            save_name,
+            current_folder,
+        }
+    }
+
+    /// Arguments of the `kiorg --pick` process showing the dialog
+    #[must_use]
+    pub fn picker_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec!["--pick".into(), "--print0".into()];
+        if !self.title.is_empty() {
+            args.push("--title".into());
+            args.push(self.title.clone().into());
+        }
+        if self.directories {
+            args.push("--directories".into());
+        }
+        if !self.multiple {
+            args.push("--single".into());
+        }
+        if let Some(name) = &self.save_name {
+            args.push("--save".into());
+            args.push(name.into());
+        }
+        if let Some(folder) = &self.current_folder {
+            args.push(folder.into());
+        }
+        args
+    }
+}
+
+/// Portal byte strings are NUL terminated
+fn folder_from_bytes(bytes: &[u8]) -> Option<PathBuf> {
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    let path = PathBuf::from(std::ffi::OsStr::from_bytes(bytes));
+    path.is_dir().then_some(path)
+}
+
+/// Paths printed by `kiorg --pick --print0`
+fn parse_picked(stdout: &[u8]) -> Vec<PathBuf> {
+    stdout
+        .split(|&byte| byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| PathBuf::from(std::ffi::OsStr::from_bytes(path)))
+        .collect()
+}
+
+/// Show the dialog in a picker process and wait for the picked paths
+fn run_picker(request: &ChooserRequest) -> std::io::Result<Vec<PathBuf>> {
+    let output = Command::new(std::env::current_exe()?)
+        .args(request.picker_args())
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()?;
+    Ok(parse_picked(&output.stdout))
+}
+
+async fn choose(request: ChooserRequest) -> (u32, HashMap<String, OwnedValue>) {
+    let mut results = HashMap::new();
+    let picked = blocking::unblock(move || run_picker(&request)).await;
+    let paths = match picked {
+        Ok(paths) if paths.is_empty() => return (RESPONSE_CANCELLED, results),
+        Ok(paths) => paths,
+        Err(e) => {
+            tracing::error!("Failed to run the file picker: {}", e);
+            return (RESPONSE_OTHER, results);
+        }
+    };
+
+    let uris: Vec<String> = paths.iter().map(|path| path_to_uri(path)).collect();
+    match OwnedValue::try_from(Value::from(uris)) {
+        Ok(uris) => {
+            results.insert("uris".to_string(), uris);
+            (RESPONSE_SUCCESS, results)
+        }
+        Err(e) => {
+            tracing::error!("Failed to encode picked files: {}", e);
+            (RESPONSE_OTHER, results)
+        }
+    }
+}
+
+struct FileChooser;
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.FileChooser")]
+impl FileChooser {
+    #[zbus(out_args("response", "results"))]
+    async fn open_file(
+        &self,
+        _handle: ObjectPath<'_>,
+        _app_id: &str,
+        _parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> (u32, HashMap<String, OwnedValue>) {
+        choose(ChooserRequest::from_options(title, &options, false)).await
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn save_file(
+        &self,
+        _handle: ObjectPath<'_>,
+        _app_id: &str,
+        _parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> (u32, HashMap<String, OwnedValue>) {
+        choose(ChooserRequest::from_options(title, &options, true)).await
+    }
+}
+
+/// Serve the file chooser until the process is killed
+pub fn run() -> zbus::Result<()> {
+    let _connection = zbus::blocking::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, FileChooser)?
+        .build()?;
+    tracing::info!("Serving the file chooser portal as {}", BUS_NAME);
+    loop {
+        std::thread::park();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picker_args() {
+        let request = ChooserRequest {
+            title: "Open Image".to_string(),
+            directories: false,
+            multiple: true,
+            save_name: None,
+            current_folder: Some(PathBuf::from("/tmp")),
+        };
+        assert_eq!(
+            request.picker_args(),
+            ["--pick", "--print0", "--title", "Open Image", "/tmp"]
+                .map(OsString::from)
+                .to_vec()
+        );
+
+        let request = ChooserRequest {
+            save_name: Some("report.pdf".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            request.picker_args(),
+            ["--pick", "--print0", "--single", "--save", "report.pdf"]
+                .map(OsString::from)
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_picked() {
+        assert_eq!(
+            parse_picked(b"/a b\0/c\nd\0"),
+            vec![PathBuf::from("/a b"), PathBuf::from("/c\nd")]
+        );
+        assert!(parse_picked(b"").is_empty());
+    }
+
+    #[test]
+    fn test_folder_from_bytes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut bytes = temp_dir.path().as_os_str().as_bytes().to_vec();
+        bytes.push(0);
+        assert_eq!(
+            folder_from_bytes(&bytes),
+            Some(temp_dir.path().to_path_buf())
+        );
+        assert_eq!(folder_from_bytes(b"/does/not/exist\0"), None);
+    }
+}
@@ -1,131 +1,146 @@
 use egui::Ui;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use crate::app::Clipboard;
 use crate::app::Kiorg;
 use crate::config;
 use crate::config::SortPreference;
-use crate::ui::file_list::{self, ROW_HEIGHT, TableHeaderParams};
+use crate::models::group_by::{GroupBy, GroupRow};
+use crate::plugins::manager::LoadedPlugin;
+use crate::ui::file_list::{self, TableHeaderParams};
+use crate::ui::operations;
 use crate::ui::popup::PopupType;
-use crate::utils::file_operations;
+use crate::ui::popup::{
+    archive_test, checksum as popup_checksum, duplicates as popup_duplicates, split as popup_split,
+};
+use crate::ui::popup::{encryption as popup_encryption, lan_send, properties, share};
+use crate::utils::archive::{ArchiveFormat, CompressionFormat};
+use crate::utils::format::format_count;
+use crate::utils::hashing::HashAlgorithm;
+use crate::utils::link::LinkKind;
+use crate::utils::{checksum, cloud, encryption, split, wallpaper};
 
 // TODO: make this configurable
 const PADDING_ROWS: usize = 3;
 
-fn new_unique_path_name_for_paste(
-    path: &std::path::Path,
-    current_path: &std::path::Path,
-) -> PathBuf {
-    let name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or_default();
-    let mut new_path = current_path.join(name);
-
-    // Handle duplicate names
-    let mut counter = 1;
-    while new_path.exists() {
-        let stem = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or_default();
-        let ext = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| format!(".{e}"))
-            .unwrap_or_default();
-        new_path = current_path.join(format!("{stem}_{counter}{ext}"));
-        counter += 1;
-    }
-
-    new_path
+/// Seconds a press has to be held on an entry to open the context menu
+const LONG_PRESS_SECS: f64 = 0.6;
+/// Pointer movement in points that cancels a long press
+const LONG_PRESS_MAX_MOVE: f32 = 6.0;
+
+/// Context menu opened by a long press, kept in egui temp memory
+#[derive(Clone)]
+struct LongPressMenu {
+    pos: egui::Pos2,
+    entry_path: PathBuf,
 }
 
-/// Handles clipboard paste operations (copy/cut)
-/// Returns true if any operation was performed
-pub fn handle_clipboard_operations(
-    clipboard: &mut Option<Clipboard>,
-    current_path: &std::path::Path,
-    action_history: &mut crate::models::action_history::TabActionHistory,
-    toasts: &mut crate::ui::egui_notify::Toasts,
-) -> bool {
-    match clipboard.take() {
-        Some(Clipboard::Copy(paths)) => {
-            let mut copy_operations = Vec::new();
-
-            paths.iter().for_each(|path| {
-                let new_path = new_unique_path_name_for_paste(path, current_path);
-                // Handle copying differently based on whether it's a file or directory
-                if path.is_dir() {
-                    if let Err(e) = file_operations::copy_dir_recursively(path, &new_path) {
-                        toasts.error(format!(
-                            "Failed to copy directory {} to {}: {e}",
-                            path.to_string_lossy(),
-                            new_path.to_string_lossy()
-                        ));
-                    } else {
-                        // Record successful copy operation
-                        copy_operations.push(crate::models::action_history::CopyOperation {
-                            source_path: path.clone(),
-                            target_path: new_path,
-                        });
-                    }
-                    return;
-                }
+fn long_press_menu_id() -> egui::Id {
+    egui::Id::new("center_panel_long_press_menu")
+}
 
-                if let Err(e) = std::fs::copy(path, &new_path) {
-                    toasts.error(format!(
-                        "Failed to copy file {} to {}: {e}",
-                        path.to_string_lossy(),
-                        new_path.to_string_lossy()
-                    ));
-                } else {
-                    // Record successful copy operation
-                    copy_operations.push(crate::models::action_history::CopyOperation {
-                        source_path: path.clone(),
-                        target_path: new_path,
-                    });
-                }
-            });
+fn long_press_fired_id() -> egui::Id {
+    egui::Id::new("center_panel_long_press_fired")
+}
 
-            // Record operations if any operations succeeded
-            if !copy_operations.is_empty() {
-                action_history.add_action(crate::models::action_history::ActionType::Copy {
-                    operations: copy_operations,
-                });
-            }
-        }
-        Some(Clipboard::Cut(paths)) => {
-            let mut move_operations = Vec::new();
-
-            paths.iter().for_each(|path| {
-                let new_path = new_unique_path_name_for_paste(path, current_path);
-                if let Err(e) = file_operations::omni_rename(path, &new_path) {
-                    toasts.error(format!(
-                        "Failed to move {} to {}: {e}",
-                        path.to_string_lossy(),
-                        new_path.to_string_lossy()
-                    ));
-                } else {
-                    // Record successful move operation
-                    move_operations.push(crate::models::action_history::MoveOperation {
-                        source_path: path.clone(),
-                        target_path: new_path,
-                    });
-                }
-            });
+/// Whether a long press already fired for the press that is currently held
+fn long_press_fired(ctx: &egui::Context) -> bool {
+    let press_start = ctx.input(|i| i.pointer.press_start_time());
+    press_start.is_some() && ctx.data(|d| d.get_temp::<f64>(long_press_fired_id())) == press_start
+}
 
-            // Record operations if any operations succeeded
-            if !move_operations.is_empty() {
-                action_history.add_action(crate::models::action_history::ActionType::Move {
-                    operations: move_operations,
-                });
-            }
+/// Detect a press held still on `response`, reporting it once per press
+fn detect_long_press(response: &egui::Response) -> Option<egui::Pos2> {
+    if !response.is_pointer_button_down_on() || long_press_fired(&response.ctx) {
+        return None;
+    }
+    let (press_start, now, origin, pos) = response.ctx.input(|i| {
+        (
+            i.pointer.press_start_time(),
+            i.time,
+            i.pointer.press_origin(),
+            i.pointer.interact_pos(),
+        )
+    });
+    let (press_start, origin, pos) = (press_start?, origin?, pos?);
+    if origin.distance(pos) > LONG_PRESS_MAX_MOVE {
+        return None;
+    }
+
+    let held = now - press_start;
+    if held < LONG_PRESS_SECS {
+        response
+            .ctx
+            .request_repaint_after(std::time::Duration::from_secs_f64(LONG_PRESS_SECS - held));
+        return None;
+    }
+    response
+        .ctx
+        .data_mut(|d| d.insert_temp(long_press_fired_id(), press_start));
+    Some(pos)
+}
+
+/// Horizontal distance in points a two-finger swipe needs to navigate the history
+const SWIPE_HISTORY_DISTANCE: f32 = 150.0;
+/// Pause in seconds that ends a swipe gesture
+const SWIPE_GESTURE_GAP_SECS: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwipeDirection {
+    Back,
+    Forward,
+}
+
+/// Progress of the current swipe gesture, kept in egui temp memory
+#[derive(Clone, Copy, Default)]
+struct SwipeGesture {
+    distance: f32,
+    last_time: f64,
+    fired: bool,
+}
+
+fn swipe_gesture_id() -> egui::Id {
+    egui::Id::new("center_panel_history_swipe")
+}
+
+/// Detect a two-finger horizontal swipe over `rect`, from a trackpad or a touchscreen
+///
+/// Like in web browsers, swiping right goes back and swiping left goes forward.
+/// Each gesture navigates at most once.
+fn detect_history_swipe(ctx: &egui::Context, rect: egui::Rect) -> Option<SwipeDirection> {
+    let (delta, pos, now) = ctx.input(|i| match i.multi_touch() {
+        Some(touch) if touch.num_touches == 2 => {
+            (touch.translation_delta, Some(touch.center_pos), i.time)
         }
-        _ => return false, // No clipboard operation to perform
+        _ => (i.raw_scroll_delta, i.pointer.hover_pos(), i.time),
+    });
+    if delta.x == 0.0 || delta.x.abs() < delta.y.abs() * 2.0 {
+        return None;
+    }
+    if !pos.is_some_and(|p| rect.contains(p)) {
+        return None;
     }
 
-    true
+    let mut gesture = ctx
+        .data(|d| d.get_temp::<SwipeGesture>(swipe_gesture_id()))
+        .unwrap_or_default();
+    if now - gesture.last_time > SWIPE_GESTURE_GAP_SECS {
+        gesture = SwipeGesture::default();
+    }
+    gesture.last_time = now;
+    gesture.distance += delta.x;
+
+    let direction = if gesture.fired || gesture.distance.abs() < SWIPE_HISTORY_DISTANCE {
+        None
+    } else if gesture.distance > 0.0 {
+        Some(SwipeDirection::Back)
+    } else {
+        Some(SwipeDirection::Forward)
+    };
+    gesture.fired |= direction.is_some();
+    ctx.data_mut(|d| d.insert_temp(swipe_gesture_id(), gesture));
+    direction
 }
 
 fn scroll_by_filtered_index(
@@ -192,21 +207,52 @@ enum ContextMenuAction {
     None,
     Add,
     Paste,
+    /// Link the clipboard entries instead of copying them
+    PasteLink(LinkKind),
+    CreateSymlink,
     Rename,
     Delete,
     Copy,
     Cut,
     BulkDelete, // New action for bulk deletion
-    OpenWith,   // New action for opening with custom command
+    DeletePermanently,
+    OpenWith, // New action for opening with custom command
+    TestArchive,
+    Decompress,
+    /// Set the image as wallpaper of one display, or of all displays
+    SetWallpaper(Option<usize>),
+    Share,
+    SendToDevice,
+    Encrypt,
+    Decrypt,
+    /// Write a checksum sidecar file next to each target file
+    GenerateChecksums(HashAlgorithm),
+    VerifyChecksums,
+    SplitFile,
+    JoinParts,
+    /// Scan the current directory tree for duplicate files
+    FindDuplicates,
+    /// Open a terminal window, in the entry the menu was opened on if it's a directory
+    OpenSystemTerminal {
+        on_entry: bool,
+    },
+    /// Download online-only cloud files and keep them on this device
+    KeepOnDevice,
+    Properties,
+    /// Run a plugin bulk operation, the index into the operations shown in the menu
+    PluginOperation(usize),
 }
 
 /// Helper function to build the context menu items and return the chosen action.
-/// Takes a boolean indicating if pasting is possible, if a file is selected, and if there are marked entries.
+/// Takes a boolean indicating if pasting is possible, if a file is selected, if there are marked entries,
+/// the path of the entry the menu was opened on, if any, and the plugin operations applicable to it.
 fn show_context_menu(
     ui: &mut Ui,
     can_paste: bool,
     has_selection: bool,
     has_marked_entries: bool,
+    entry_path: Option<&Path>,
+    plugin_operations: &[(Arc<LoadedPlugin>, usize)],
 ) -> ContextMenuAction {
     let mut action = ContextMenuAction::None;
 
@@ -229,17 +275,27 @@ fn show_context_menu(
     // Show bulk delete option when there are marked entries
     if has_marked_entries {
         // TODO: do we need to add enabled
-        if ui.button("Delete all marked items").clicked() {
+        if ui.button("Move all marked items to trash").clicked() {
             action = ContextMenuAction::BulkDelete;
             ui.close();
         }
     } else if ui
-        .add_enabled(has_selection, egui::Button::new("Delete"))
+        .add_enabled(has_selection, egui::Button::new("Move to trash"))
         .clicked()
     {
         action = ContextMenuAction::Delete;
         ui.close();
     }
+    if ui
+        .add_enabled(
+            has_selection || has_marked_entries,
+            egui::Button::new("Delete permanently"),
+        )
+        .clicked()
+    {
+        action = ContextMenuAction::DeletePermanently;
+        ui.close();
+    }
 
     // Add "Open with" option - enabled for both files and directories
     if ui
@@ -250,6 +306,121 @@ fn show_context_menu(
         ui.close();
     }
 
+    if ui
+        .add_enabled(has_selection, egui::Button::new("Share..."))
+        .clicked()
+    {
+        action = ContextMenuAction::Share;
+        ui.close();
+    }
+
+    if ui
+        .add_enabled(has_selection, egui::Button::new("Send to device..."))
+        .clicked()
+    {
+        action = ContextMenuAction::SendToDevice;
+        ui.close();
+    }
+
+    if ui
+        .add_enabled(has_selection, egui::Button::new("Encrypt..."))
+        .clicked()
+    {
+        action = ContextMenuAction::Encrypt;
+        ui.close();
+    }
+
+    ui.menu_button("Checksums", |ui| {
+        for algorithm in checksum::SIDECAR_ALGORITHMS {
+            if ui
+                .add_enabled(
+                    has_selection || has_marked_entries,
+                    egui::Button::new(format!(
+                        "Generate .{} files",
+                        checksum::extension(algorithm)
+                    )),
+                )
+                .clicked()
+            {
+                action = ContextMenuAction::GenerateChecksums(algorithm);
+                ui.close();
+            }
+        }
+        if ui.button("Verify checksums").clicked() {
+            action = ContextMenuAction::VerifyChecksums;
+            ui.close();
+        }
+    });
+
+    // Archive actions - only shown for matching files
+    if let Some(path) = entry_path.filter(|p| p.is_file()) {
+        // Reading the header of an online-only file would download it
+        let is_placeholder = cloud::is_placeholder_path(path);
+        if is_placeholder && ui.button("Download and keep on device").clicked() {
+            action = ContextMenuAction::KeepOnDevice;
+            ui.close();
+        }
+        if !is_placeholder
+            && encryption::encrypted_with(path).is_some()
+            && ui.button("Decrypt...").clicked()
+        {
+            action = ContextMenuAction::Decrypt;
+            ui.close();
+        }
+        if ArchiveFormat::from_path(path).is_some() && ui.button("Test archive").clicked() {
+            action = ContextMenuAction::TestArchive;
+            ui.close();
+        }
+        if CompressionFormat::from_path(path).is_some() && ui.button("Decompress here").clicked() {
+            action = ContextMenuAction::Decompress;
+            ui.close();
+        }
+        if !is_placeholder && ui.button("Split file...").clicked() {
+            action = ContextMenuAction::SplitFile;
+            ui.close();
+        }
+        if split::is_first_part(path) && ui.button("Join parts").clicked() {
+            action = ContextMenuAction::JoinParts;
+            ui.close();
+        }
+        if wallpaper::is_supported(path) {
+            let monitors = wallpaper::monitors();
+            if monitors.len() > 1 {
+                ui.menu_button("Set as wallpaper", |ui| {
+                    if ui.button("All displays").clicked() {
+                        action = ContextMenuAction::SetWallpaper(None);
+                        ui.close();
+                    }
+                    for (index, name) in monitors.iter().enumerate() {
+                        if ui.button(name).clicked() {
+                            action = ContextMenuAction::SetWallpaper(Some(index));
+                            ui.close();
+                        }
+                    }
+                });
+            } else if ui.button("Set as wallpaper").clicked() {
+                action = ContextMenuAction::SetWallpaper(None);
+                ui.close();
+            }
+        }
+    }
+
+    if !plugin_operations.is_empty() {
+        ui.menu_button("Plugins", |ui| {
+            for (index, (plugin, operation)) in plugin_operations.iter().enumerate() {
+                let operation = &plugin.bulk_operations[*operation];
+                if ui
+                    .button(&operation.capability.label)
+                    .on_hover_text(&plugin.metadata.name)
+                    .clicked()
+                {
+                    action = ContextMenuAction::PluginOperation(index);
+                    ui.close();
+                }
+            }
+        });
+    }
+
     ui.separator();
 
     if ui
@@ -276,10 +447,98 @@ fn show_context_menu(
         action = ContextMenuAction::Paste;
         ui.close();
     }
+    if ui
+        .add_enabled(can_paste, egui::Button::new("Paste as symlink"))
+        .clicked()
+    {
+        action = ContextMenuAction::PasteLink(LinkKind::Symlink);
+        ui.close();
+    }
+    if ui
+        .add_enabled(can_paste, egui::Button::new("Paste as hard link"))
+        .clicked()
+    {
+        action = ContextMenuAction::PasteLink(LinkKind::Hardlink);
+        ui.close();
+    }
+    if ui
+        .add_enabled(has_selection, egui::Button::new("Create symlink"))
+        .clicked()
+    {
+        action = ContextMenuAction::CreateSymlink;
+        ui.close();
+    }
+
+    ui.separator();
+
+    if ui.button("Find duplicates here").clicked() {
+        action = ContextMenuAction::FindDuplicates;
+        ui.close();
+    }
+
+    if ui.button("Open terminal here").clicked() {
+        action = ContextMenuAction::OpenSystemTerminal {
+            on_entry: entry_path.is_some(),
+        };
+        ui.close();
+    }
+
+    if ui
+        .add_enabled(has_selection, egui::Button::new("Properties"))
+        .clicked()
+    {
+        action = ContextMenuAction::Properties;
+        ui.close();
+    }
 
     action
 }
 
+/// Draw the context menu opened by a long press, if any
+///
+/// Returns the chosen action once the menu closes.
+fn show_long_press_menu(ctx: &egui::Context, app: &Kiorg) -> Option<ContextMenuAction> {
+    let menu = ctx.data(|d| d.get_temp::<LongPressMenu>(long_press_menu_id()))?;
+
+    let mut action = ContextMenuAction::None;
+    let area = egui::Area::new(long_press_menu_id().with("area"))
+        .order(egui::Order::Foreground)
+        .fixed_pos(menu.pos)
+        .show(ctx, |ui| {
+            egui::Frame::menu(ui.style()).show(ui, |ui| {
+                let has_marked_entries =
+                    !app.tab_manager.current_tab_ref().marked_entries.is_empty();
+                let plugin_operations = app
+                    .plugin_manager
+                    .bulk_operations_for(&operations::target_paths(app, Some(&menu.entry_path)));
+                action = show_context_menu(
+                    ui,
+                    app.clipboard.is_some(),
+                    true,
+                    has_marked_entries,
+                    Some(&menu.entry_path),
+                    &plugin_operations,
+                );
+            });
+        });
+
+    // Close on selection, Escape or a press outside of the menu
+    let pressed_outside = ctx.input(|i| {
+        i.pointer.any_pressed()
+            && i.pointer
+                .interact_pos()
+                .is_some_and(|pos| !area.response.rect.contains(pos))
+    });
+    if action != ContextMenuAction::None
+        || pressed_outside
+        || ctx.input(|i| i.key_pressed(egui::Key::Escape))
+    {
+        ctx.data_mut(|d| d.remove::<LongPressMenu>(long_press_menu_id()));
+        return Some(action);
+    }
+    None
+}
+
 /// Draws the center panel content.
 pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
     handle_file_drop(ui.ctx(), app);
@@ -289,7 +548,7 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
     let mut sort_requested = None; // For sort changes captured from the header click
     let mut file_list_response = None; // To store the response for the background context menu
     let mut context_menu_action = ContextMenuAction::None; // To store the action from any context menu
-    let mut double_clicked_path: Option<PathBuf> = None; // To store the path of a double-clicked entry
+    let mut activated_path: Option<PathBuf> = None; // To store the path of a double-clicked (or single-clicked) entry
     let mut drag_started_source: Option<PathBuf> = None; // To store an item (file or directory) that started being dragged
     let mut drop_target_folder: Option<PathBuf> = None; // To store the folder where a file was dropped
 
@@ -304,6 +563,19 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
     let is_drag_active = app.is_dragging();
     let primary_pointer_released = ui.ctx().input(|i| i.pointer.primary_released());
 
+    // Mouse and touch behavior
+    let mouse = app.config.mouse.clone().unwrap_or_default();
+    let pointer_moved = ui.ctx().input(|i| i.pointer.delta() != egui::Vec2::ZERO);
+    // The release ending a long press isn't a click
+    let long_press_active = mouse.long_press_menu && long_press_fired(ui.ctx());
+    let mut long_press_menu: Option<LongPressMenu> = None;
+    let size_on_disk = app
+        .config
+        .layout
+        .as_ref()
+        .and_then(|layout| layout.size_on_disk_column)
+        .unwrap_or(false);
+
     ui.vertical(|ui| {
         ui.set_min_width(width);
         ui.set_max_width(width);
@@ -317,11 +589,23 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
             on_sort: &mut |column| {
                 sort_requested = Some(column);
             },
+            size_on_disk,
         };
         let header_resp = file_list::draw_table_header(ui, &mut header_params);
 
+        // Keep a row for the footer while a huge directory is still being read
+        // or entries of other directories are marked
+        let loading = app.dir_stream.is_some();
+        let marked_elsewhere = app.tab_manager.current_tab_ref().marked_elsewhere_count();
+        let footer_height = if loading || app.type_ahead.active() || marked_elsewhere > 0 {
+            file_list::row_height(ui) + ui.spacing().item_spacing.y
+        } else {
+            0.0
+        };
+
         // --- Draw Scrollable File List within its own container for context menu ---
-        let available_height = crate::ui::clamp_height(height - header_resp.rect.height());
+        let available_height =
+            crate::ui::clamp_height(height - header_resp.rect.height() - footer_height);
         let scroll_area_id = ui.id().with("center_panel_list_scroll");
 
         // Use a containing layout for the scroll area to capture interactions
@@ -340,20 +624,31 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                     .auto_shrink([false; 2])
                     .max_height(available_height); // Use available_height
 
-                let total_rows = filtered_indices.len();
+                // Grouped listings show a header row above each group
+                let group_rows = (app.tab_manager.group_by != GroupBy::None).then(|| {
+                    app.tab_manager
+                        .group_by
+                        .rows(&tab_ref.entries, filtered_indices)
+                });
+                let total_rows = group_rows.as_ref().map_or(filtered_indices.len(), Vec::len);
 
+                let row_height = file_list::row_height(ui);
                 let ui_spacing = ui.spacing().item_spacing.y;
-                let spaced_row_height = ROW_HEIGHT + ui_spacing;
+                let spaced_row_height = row_height + ui_spacing;
 
                 if app.ensure_selected_visible {
                     if let Some(selected_entry) = tab_ref.selected_entry() {
                         // Find the position of the selected entry in the filtered list
-                        if let Some(filtered_index) =
-                            filtered_indices.iter().position(|&original_index| {
+                        let position = match &group_rows {
+                            Some(rows) => rows
+                                .iter()
+                                .position(|row| *row == GroupRow::Entry(tab_ref.selected_index)),
+                            None => filtered_indices.iter().position(|&original_index| {
                                 tab_ref.entries[original_index].meta.path
                                     == selected_entry.meta.path
-                            })
-                        {
+                            }),
+                        };
+                        if let Some(filtered_index) = position {
                             scroll_area = scroll_by_filtered_index(
                                 scroll_area,
                                 filtered_index,
@@ -368,7 +663,7 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
 
                 let current_dragged_file = app.get_dragged_file().map(|p| p.to_path_buf());
                 // Draw the rows within the scroll area
-                scroll_area.show_rows(ui, ROW_HEIGHT, total_rows, |scroll_ui, row_range| {
+                scroll_area.show_rows(ui, row_height, total_rows, |scroll_ui, row_range| {
                     // Calculate width considering potential scrollbar
                     // Use available_width which accounts for parent layouts and scrollbars automatically
                     let available_width = scroll_ui.available_width();
@@ -380,10 +675,19 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                     }
                     app.scroll_range = Some(row_range.clone());
                     let selection_range = tab_ref.get_range_selection_range();
+                    let first_row = row_range.start;
 
                     for row_index in row_range {
                         // Get the entry and original index for the current visible row from the filtered list
-                        let original_index = filtered_indices[row_index];
+                        let original_index = match group_rows.as_ref().map(|rows| &rows[row_index])
+                        {
+                            Some(GroupRow::Header(label)) => {
+                                file_list::draw_group_header(scroll_ui, label, &app.colors);
+                                continue;
+                            }
+                            Some(GroupRow::Entry(index)) => *index,
+                            None => filtered_indices[row_index],
+                        };
                         let entry = &tab_ref.entries[original_index];
 
                         let is_selected = original_index == tab_ref.selected_index;
@@ -436,12 +740,20 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                                 is_selected,
                                 colors: &app.colors,
                                 is_marked,
-                                is_bookmarked: app.bookmarks.contains(&entry.meta.path),
+                                is_bookmarked: crate::models::bookmark::is_bookmarked(
+                                    &app.bookmarks,
+                                    &entry.meta.path,
+                                ),
                                 is_being_opened: being_opened,
                                 is_in_cut_clipboard,
                                 is_in_copy_clipboard,
                                 is_drag_source,
                                 is_drag_active,
+                                touch_mode: app.touch_mode,
+                                size_on_disk,
+                                dir_size: (app.show_dir_sizes && entry.is_dir && !entry.is_symlink)
+                                    .then(|| app.dir_sizes.get(&entry.meta.path)),
+                                highlight: app.type_ahead.query.as_deref(),
                             },
                         );
 
@@ -449,7 +761,7 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                         if inline_rename_index == Some(original_index) {
                             if let Some(ref mut rename_name) = inline_rename_name {
                                 let (name_rect, name_width) =
-                                    file_list::name_column_rect(row_response.rect);
+                                    file_list::name_column_rect(row_response.rect, size_on_disk);
                                 scroll_ui.painter().rect_filled(
                                     name_rect,
                                     0.0,
@@ -526,10 +838,36 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                         if row_response.clicked() {
                             new_selected_index = Some(original_index);
                         }
+                        // Select entries under the pointer in single click mode
+                        if mouse.single_click
+                            && !is_selected
+                            && !is_drag_active
+                            && pointer_moved
+                            && row_response.hovered()
+                        {
+                            new_selected_index = Some(original_index);
+                        }
+                        if mouse.long_press_menu
+                            && let Some(pos) = detect_long_press(&row_response)
+                        {
+                            new_selected_index = Some(original_index);
+                            long_press_menu = Some(LongPressMenu {
+                                pos,
+                                entry_path: entry.meta.path.clone(),
+                            });
+                        }
+
                         // double_clicked() and clicked() return true at the same time
-                        if row_response.double_clicked() {
+                        let activated = if mouse.single_click {
+                            row_response.clicked()
+                        } else {
+                            row_response.double_clicked()
+                        };
+                        if long_press_active {
+                            // Neither activate nor drag entries while a long press is held
+                        } else if activated {
                             // Check for double-clicks to navigate or open files
-                            double_clicked_path = Some(entry.meta.path.clone());
+                            activated_path = Some(entry.meta.path.clone());
                         } else if row_response.drag_started() {
                             // Start dragging files or directories
                             drag_started_source = Some(entry.meta.path.clone());
@@ -549,20 +887,70 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                             // Capture the action, don't perform it yet
                             // Pass only the necessary booleans, not the whole app
                             let has_marked_entries = !tab_ref.marked_entries.is_empty();
+                            let plugin_operations =
+                                app.plugin_manager
+                                    .bulk_operations_for(&operations::target_paths(
+                                        app,
+                                        Some(&entry.meta.path),
+                                    ));
                             context_menu_action = show_context_menu(
                                 menu_ui,
                                 app.clipboard.is_some(),
                                 true,
                                 has_marked_entries,
+                                Some(&entry.meta.path),
+                                &plugin_operations,
                             );
                         });
                     } // End row loop
+
+                    // Keep the header of the group at the top in view while scrolled
+                    if let Some(rows) = &group_rows
+                        && first_row > 0
+                        && let Some(label) =
+                            rows[..=first_row].iter().rev().find_map(|row| match row {
+                                GroupRow::Header(label) => Some(label),
+                                GroupRow::Entry(_) => None,
+                            })
+                    {
+                        file_list::draw_sticky_group_header(scroll_ui, label, &app.colors);
+                    }
                 }); // End show_rows
             })
             .response; // End inner ui.vertical and get its response
 
         // Store the response of the inner container for context menu handling outside
         file_list_response = Some(inner_response);
+
+        if loading {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Loading… {} entries",
+                        format_count(app.tab_manager.current_tab_ref().entries.len() as u64)
+                    ))
+                    .color(app.colors.fg_light),
+                );
+            });
+        } else if let Some(query) = &app.type_ahead.query {
+            // The caret shows that typed keys go to the filter
+            let caret = if app.type_ahead.typing { "▏" } else { "" };
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Filter:").color(app.colors.fg_light));
+                ui.label(
+                    egui::RichText::new(format!("{query}{caret}")).color(app.colors.highlight),
+                );
+            });
+        } else if marked_elsewhere > 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} marked in other folders",
+                    format_count(marked_elsewhere as u64)
+                ))
+                .color(app.colors.fg_light),
+            );
+        }
     }); // End of main ui.vertical closure. All borrows of `app` inside are released here.
 
     // Write back inline rename state
@@ -577,6 +965,15 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
         app.cancel_rename();
     }
 
+    // Two-finger horizontal swipe over the list navigates the history
+    if let Some(response) = &file_list_response {
+        match detect_history_swipe(ui.ctx(), response.rect) {
+            Some(SwipeDirection::Back) => app.navigate_history_back(),
+            Some(SwipeDirection::Forward) => app.navigate_history_forward(),
+            None => {}
+        }
+    }
+
     // --- Context Menu Logic for Background Area (using the stored response) ---
     if let Some(response) = file_list_response {
         response.context_menu(|menu_ui| {
@@ -584,11 +981,16 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
             // Pass only the necessary booleans, not the whole app
             // For background context menu, no file is selected
             let has_marked_entries = !app.tab_manager.current_tab_ref().marked_entries.is_empty();
+            let plugin_operations = app
+                .plugin_manager
+                .bulk_operations_for(&operations::target_paths(app, None));
             context_menu_action = show_context_menu(
                 menu_ui,
                 app.clipboard.is_some(),
                 false, // No file is selected in background context menu
                 has_marked_entries,
+                None,
+                &plugin_operations,
             );
         });
     }
@@ -602,12 +1004,26 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
         app.set_selection(index);
     }
 
+    // Open the context menu of a long pressed entry
+    if let Some(menu) = long_press_menu {
+        ui.ctx()
+            .data_mut(|d| d.insert_temp(long_press_menu_id(), menu));
+    } else if let Some(action) = show_long_press_menu(ui.ctx(), app) {
+        context_menu_action = action;
+    }
+
     // Handle double-click navigation or file opening
-    if let Some(path) = double_clicked_path {
+    if let Some(path) = activated_path {
         if path.is_dir() {
+            // Directories are always entered, previewing only applies to files
             app.navigate_to_dir(path);
         } else if path.is_file() {
-            app.open_file(path);
+            match mouse.double_click {
+                config::ClickAction::Open => app.open_file(path),
+                config::ClickAction::Preview => {
+                    crate::ui::popup::preview::handle_show_file_popup(app, ui.ctx());
+                }
+            }
         }
     }
 
@@ -630,20 +1046,17 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
     // Handle context menu action captured from closures
     match context_menu_action {
         ContextMenuAction::Add => {
-            app.show_popup = Some(PopupType::AddEntry(String::new()));
+            crate::ui::popup::add_entry::open(app);
         }
         ContextMenuAction::Paste => {
-            let current_tab = app.tab_manager.current_tab_mut();
-            if handle_clipboard_operations(
-                &mut app.clipboard,
-                &current_tab.current_path,
-                &mut current_tab.action_history,
-                &mut app.toasts,
-            ) {
-                // Clear marked entries after successful paste operation
-                app.tab_manager.current_tab_mut().marked_entries.clear();
-                app.refresh_entries();
-            }
+            crate::ui::popup::fan_out_paste::paste(app);
+        }
+        ContextMenuAction::PasteLink(kind) => {
+            let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+            app.paste_clipboard_as_link(current_path, kind);
+        }
+        ContextMenuAction::CreateSymlink => {
+            app.create_symlink_to_selected();
         }
         ContextMenuAction::Rename => {
             app.rename_selected_entry();
@@ -655,6 +1068,9 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
             // Same as Delete, but explicitly for marked entries
             app.delete_selected_entry();
         }
+        ContextMenuAction::DeletePermanently => {
+            app.delete_selected_entry_permanently();
+        }
         ContextMenuAction::Copy => {
             app.copy_selected_entries();
         }
@@ -669,6 +1085,70 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
                 app.show_popup = Some(PopupType::OpenWith);
             }
         }
+        ContextMenuAction::TestArchive => {
+            archive_test::start_test_selected(app);
+        }
+        ContextMenuAction::GenerateChecksums(algorithm) => {
+            popup_checksum::start_generate_selected(app, ui.ctx(), algorithm);
+        }
+        ContextMenuAction::VerifyChecksums => {
+            popup_checksum::start_verify_selected(app, ui.ctx());
+        }
+        ContextMenuAction::SplitFile => {
+            popup_split::open(app);
+        }
+        ContextMenuAction::JoinParts => {
+            popup_split::start_join_selected(app, ui.ctx());
+        }
+        ContextMenuAction::FindDuplicates => {
+            popup_duplicates::open(app, ui.ctx());
+        }
+        ContextMenuAction::OpenSystemTerminal { on_entry } => {
+            // The selection was updated above, so it's the entry the menu was opened on
+            if on_entry {
+                app.open_system_terminal();
+            } else {
+                let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+                app.open_system_terminal_in(&current_path);
+            }
+        }
+        ContextMenuAction::Decompress => {
+            app.decompress_selected_entry();
+        }
+        ContextMenuAction::SetWallpaper(monitor) => {
+            app.set_selected_as_wallpaper(monitor);
+        }
+        ContextMenuAction::Share => {
+            share::open(app);
+        }
+        ContextMenuAction::SendToDevice => {
+            lan_send::open(app, ui.ctx());
+        }
+        ContextMenuAction::Encrypt => {
+            popup_encryption::open_encrypt(app);
+        }
+        ContextMenuAction::Decrypt => {
+            popup_encryption::open_decrypt(app);
+        }
+        ContextMenuAction::KeepOnDevice => {
+            app.download_selected_placeholders();
+        }
+        ContextMenuAction::Properties => {
+            properties::open(app, ui.ctx());
+        }
+        ContextMenuAction::PluginOperation(index) => {
+            // The selection was updated above, so the targets match the menu
+            let entry_path = app
+                .tab_manager
+                .current_tab_ref()
+                .selected_entry()
+                .map(|entry| entry.meta.path.clone());
+            let paths = operations::target_paths(app, entry_path.as_deref());
+            let plugin_operations = app.plugin_manager.bulk_operations_for(&paths);
+            if let Some((plugin, operation)) = plugin_operations.into_iter().nth(index) {
+                operations::start_plugin_operation(app, ui.ctx(), plugin, operation, paths);
+            }
+        }
         ContextMenuAction::None => {} // Do nothing
     }
 
@@ -0,0 +1,214 @@
+//! Dual pane layout, a second file list replacing the preview panel
+//!
+//! Each pane shows a tab. The focused pane is always the current tab, so every
+//! shortcut and popup works on it as usual. The other pane is only drawn, a
+//! click moves the focus to it.
+
+use egui::Ui;
+
+use crate::app::{Clipboard, Kiorg};
+use crate::ui::file_list::{self, EntryRowParams, TableHeaderParams};
+use crate::utils::transfer::TransferKind;
+
+/// Tabs shown side by side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DualPane {
+    /// Tabs of the left and the right pane
+    pub tabs: [usize; 2],
+    /// Index into `tabs` of the focused pane
+    pub focus: usize,
+}
+
+impl DualPane {
+    /// Tab of the pane without focus
+    #[must_use]
+    pub const fn other_tab(&self) -> usize {
+        self.tabs[1 - self.focus]
+    }
+}
+
+/// Show a second pane, opening a tab for it if there is only one
+pub fn toggle(app: &mut Kiorg) {
+    if app.dual_pane.take().is_some() {
+        // The preview panel is back, load the preview of the selection
+        app.cached_preview_path = None;
+        return;
+    }
+    let current = app.tab_manager.get_current_tab_index();
+    let other = match (0..app.tab_manager.get_tab_count()).find(|&i| i != current) {
+        Some(other) => other,
+        None => {
+            let path = app.tab_manager.current_tab_ref().current_path.clone();
+            app.tab_manager.add_tab(path);
+            let other = app.tab_manager.get_current_tab_index();
+            app.tab_manager.switch_to_tab(current);
+            other
+        }
+    };
+    app.tab_manager.refresh_tab_entries(other);
+    app.dual_pane = Some(DualPane {
+        tabs: [current, other],
+        focus: 0,
+    });
+}
+
+/// Keep the panes in line with the tabs after tabs were switched or closed
+///
+/// Switching to a tab shows it in the focused pane. Dual pane mode ends once
+/// there is no other tab to show.
+pub fn sync(app: &mut Kiorg) {
+    let Some(mut pane) = app.dual_pane else {
+        return;
+    };
+    let current = app.tab_manager.get_current_tab_index();
+    if pane.tabs[pane.focus] != current {
+        if pane.other_tab() == current {
+            pane.focus = 1 - pane.focus;
+        } else {
+            pane.tabs[pane.focus] = current;
+        }
+    }
+    let count = app.tab_manager.get_tab_count();
+    if pane.other_tab() >= count || pane.other_tab() == current {
+        let Some(other) = (0..count).find(|&i| i != current) else {
+            app.dual_pane = None;
+            return;
+        };
+        pane.tabs[1 - pane.focus] = other;
+        app.tab_manager.refresh_tab_entries(other);
+    }
+    app.dual_pane = Some(pane);
+}
+
+/// Tab shown in the pane without focus, if dual pane mode is on
+#[must_use]
+pub fn other_tab(app: &Kiorg) -> Option<usize> {
+    app.dual_pane.as_ref().map(DualPane::other_tab)
+}
+
+/// Move the focus to the other pane
+pub fn switch_focus(app: &mut Kiorg) {
+    sync(app);
+    let Some(pane) = &mut app.dual_pane else {
+        return;
+    };
+    pane.focus = 1 - pane.focus;
+    let tab = pane.tabs[pane.focus];
+    app.tab_manager.switch_to_tab(tab);
+    app.refresh_entries();
+}
+
+/// Copy or move the marked entries, or the selected one, to the other pane's directory
+pub fn transfer_to_other(app: &mut Kiorg, kind: TransferKind) {
+    sync(app);
+    let Some(other) = other_tab(app) else {
+        return;
+    };
+    let dest_dir = app.tab_manager.tab_ref(other).current_path.clone();
+    let tab = app.tab_manager.current_tab_ref();
+    let same_dir = tab.current_path == dest_dir;
+    let selected = tab.selected_entry().map(|entry| entry.meta.path.clone());
+    if same_dir {
+        app.notify_error("Both panes show the same directory");
+        return;
+    }
+    let paths = crate::ui::operations::target_paths(app, selected.as_deref());
+    if paths.is_empty() {
+        return;
+    }
+    app.transfers.enqueue(kind, paths, dest_dir);
+    app.tab_manager.current_tab_mut().marked_entries.clear();
+}
+
+/// Draw the file list of the pane without focus
+pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) {
+    let Some(other) = other_tab(app) else {
+        return;
+    };
+    let size_on_disk = app
+        .config
+        .layout
+        .as_ref()
+        .and_then(|layout| layout.size_on_disk_column)
+        .unwrap_or(false);
+    let mut clicked = None;
+
+    ui.vertical(|ui| {
+        ui.set_min_width(width);
+        ui.set_max_width(width);
+        ui.set_min_height(height);
+        ui.set_max_height(height);
+
+        let mut header_params = TableHeaderParams {
+            colors: &app.colors,
+            sort_column: &app.tab_manager.sort_column,
+            sort_order: &app.tab_manager.sort_order,
+            // Sorting applies to both panes, it's changed from the focused one
+            on_sort: &mut |_| {},
+            size_on_disk,
+        };
+        let header_resp = file_list::draw_table_header(ui, &mut header_params);
+        let available_height = crate::ui::clamp_height(height - header_resp.rect.height());
+
+        let tab = app.tab_manager.tab_ref(other);
+        let filtered_indices = tab.get_cached_filtered_entries();
+        egui::ScrollArea::vertical()
+            .id_salt(ui.id().with("dual_pane_list_scroll"))
+            .auto_shrink([false; 2])
+            .max_height(available_height)
+            .show_rows(
+                ui,
+                file_list::row_height(ui),
+                filtered_indices.len(),
+                |ui, row_range| {
+                    ui.set_min_width(ui.available_width());
+                    for row_index in row_range {
+                        let original_index = filtered_indices[row_index];
+                        let entry = &tab.entries[original_index];
+                        let (is_in_cut_clipboard, is_in_copy_clipboard) = match &app.clipboard {
+                            Some(Clipboard::Cut(paths)) => {
+                                (paths.contains(&entry.meta.path), false)
+                            }
+                            Some(Clipboard::Copy(paths)) => {
+                                (false, paths.contains(&entry.meta.path))
+                            }
+                            None => (false, false),
+                        };
+                        let response = file_list::draw_entry_row(
+                            ui,
+                            EntryRowParams {
+                                entry,
+                                is_selected: original_index == tab.selected_index,
+                                colors: &app.colors,
+                                is_marked: tab.marked_entries.contains(&entry.meta.path),
+                                is_bookmarked: crate::models::bookmark::is_bookmarked(
+                                    &app.bookmarks,
+                                    &entry.meta.path,
+                                ),
+                                is_being_opened: false,
+                                is_in_cut_clipboard,
+                                is_in_copy_clipboard,
+                                is_drag_active: false,
+                                is_drag_source: false,
+                                touch_mode: app.touch_mode,
+                                size_on_disk,
+                                dir_size: (app.show_dir_sizes && entry.is_dir && !entry.is_symlink)
+                                    .then(|| app.dir_sizes.get(&entry.meta.path)),
+                                highlight: None,
+                            },
+                        );
+                        if response.clicked() {
+                            clicked = Some(original_index);
+                        }
+                    }
+                },
+            );
+    });
+
+    // A click focuses the pane and selects the entry, the focused pane then
+    // sees a double click like any other
+    if let Some(index) = clicked {
+        switch_focus(app);
+        app.set_selection(index);
+    }
+}
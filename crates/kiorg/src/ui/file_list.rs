@@ -4,6 +4,7 @@ use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntry;
 use crate::models::tab::{SortColumn, SortOrder};
 use crate::ui::style::{HEADER_FONT_SIZE, HEADER_ROW_HEIGHT};
+use crate::utils::dir_size::DirSize;
 
 const ICON_SIZE: f32 = 14.0;
 const ICON_WIDTH: f32 = 22.0;
@@ -14,19 +15,35 @@ const FILE_SIZE_WIDTH: f32 = 60.0;
 const SECONDARY_COLUMN_FONT_SIZE: f32 = 12.0;
 pub const ROW_HEIGHT: f32 = 20.0;
 
-/// Returns the name column rect and its width for a given row rect.
-pub fn name_column_rect(row_rect: egui::Rect) -> (egui::Rect, f32) {
-    let name_x = row_rect.left() + ICON_WIDTH + HORIZONTAL_PADDING;
-    let fixed_width_total = ICON_WIDTH
-        + HORIZONTAL_PADDING
+/// Height of an entry row, taller when touch mode enlarges the interact size
+#[must_use]
+pub fn row_height(ui: &Ui) -> f32 {
+    ui.spacing().interact_size.y.max(ROW_HEIGHT)
+}
+
+/// Total width of the columns other than the name
+fn fixed_width_total(size_on_disk: bool) -> f32 {
+    let size_on_disk_width = if size_on_disk {
+        FILE_SIZE_WIDTH + INTER_COLUMN_PADDING
+    } else {
+        0.0
+    };
+    ICON_WIDTH
+        + HORIZONTAL_PADDING // Padding after icon
         + MODIFIED_DATE_WIDTH
-        + INTER_COLUMN_PADDING
+        + INTER_COLUMN_PADDING // Padding between Modified and Size
         + FILE_SIZE_WIDTH
-        + HORIZONTAL_PADDING;
-    let name_width = (row_rect.width() - fixed_width_total).max(0.0);
+        + size_on_disk_width
+        + HORIZONTAL_PADDING // Padding at the end
+}
+
+/// Returns the name column rect and its width for a given row rect.
+pub fn name_column_rect(row_rect: egui::Rect, size_on_disk: bool) -> (egui::Rect, f32) {
+    let name_x = row_rect.left() + ICON_WIDTH + HORIZONTAL_PADDING;
+    let name_width = (row_rect.width() - fixed_width_total(size_on_disk)).max(0.0);
     let rect = egui::Rect::from_min_size(
         egui::pos2(name_x, row_rect.top()),
-        egui::vec2(name_width, ROW_HEIGHT),
+        egui::vec2(name_width, row_rect.height()),
     );
     (rect, name_width)
 }
@@ -36,6 +53,8 @@ pub struct TableHeaderParams<'a> {
     pub sort_column: &'a SortColumn,
     pub sort_order: &'a SortOrder,
     pub on_sort: &'a mut dyn FnMut(SortColumn),
+    /// Show the size on disk column
+    pub size_on_disk: bool,
 }
 
 pub fn draw_table_header(ui: &mut Ui, params: &mut TableHeaderParams) -> egui::Response {
@@ -47,16 +66,8 @@ pub fn draw_table_header(ui: &mut Ui, params: &mut TableHeaderParams) -> egui::R
     );
     let mut cursor = rect.left_top();
 
-    // Calculate total fixed width (icon + date + size + paddings)
-    let fixed_width_total = ICON_WIDTH
-            + HORIZONTAL_PADDING // Padding after icon
-            + MODIFIED_DATE_WIDTH
-            + INTER_COLUMN_PADDING // Padding between Modified and Size
-            + FILE_SIZE_WIDTH
-            + HORIZONTAL_PADDING; // Padding at the end
-
     // Name width takes remaining space
-    let name_width = (rect.width() - fixed_width_total).max(0.0);
+    let name_width = (rect.width() - fixed_width_total(params.size_on_disk)).max(0.0);
 
     // Advance cursor past the icon area
     cursor.x += ICON_WIDTH + HORIZONTAL_PADDING;
@@ -83,7 +94,19 @@ pub fn draw_table_header(ui: &mut Ui, params: &mut TableHeaderParams) -> egui::R
     let size_col_rect =
         egui::Rect::from_min_size(cursor, egui::vec2(FILE_SIZE_WIDTH, HEADER_ROW_HEIGHT));
     draw_header_column(ui, params, size_col_rect, "Size", SortColumn::Size);
-    // No cursor advance needed after the last column
+
+    if params.size_on_disk {
+        cursor.x += FILE_SIZE_WIDTH + INTER_COLUMN_PADDING;
+        let on_disk_col_rect =
+            egui::Rect::from_min_size(cursor, egui::vec2(FILE_SIZE_WIDTH, HEADER_ROW_HEIGHT));
+        ui.painter().text(
+            on_disk_col_rect.left_center(),
+            Align2::LEFT_CENTER,
+            "On Disk",
+            egui::FontId::proportional(HEADER_FONT_SIZE),
+            params.colors.link_text,
+        );
+    }
 
     ui.separator();
 
@@ -149,21 +172,32 @@ pub struct EntryRowParams<'a> {
     pub is_in_copy_clipboard: bool,
     pub is_drag_active: bool,
     pub is_drag_source: bool,
+    /// Rows only sense clicks in touch mode so dragging scrolls the list
+    pub touch_mode: bool,
+    /// Show the size on disk column
+    pub size_on_disk: bool,
+    /// Recursive size of a directory entry, `None` if not shown
+    pub dir_size: Option<DirSize>,
+    /// Text typed into the type-ahead filter, highlighted in the name
+    pub highlight: Option<&'a str>,
 }
 
 fn draw_icon(
     ui: &mut Ui,
     cursor: egui::Pos2,
-    is_dir: bool,
+    entry: &DirEntry,
     is_selected: bool,
     colors: &AppColors,
     is_bookmarked: bool,
-    is_symlink: bool,
 ) -> f32 {
-    // Draw the base icon (folder, file, or symlink)
-    let base_icon = if is_symlink {
+    let is_dir = entry.is_dir;
+    // Draw the base icon (folder, file, symlink or cloud placeholder)
+    let base_icon = if entry.is_symlink {
         // Use a link icon for symlinks
         "🔗"
+    } else if entry.is_placeholder {
+        // Online-only files are downloaded when they are opened
+        "☁"
     } else if is_dir {
         "📁"
     } else {
@@ -175,8 +209,9 @@ fn draw_icon(
     } else {
         colors.fg_light
     };
+    let height = row_height(ui);
     ui.painter().text(
-        cursor + egui::vec2(HORIZONTAL_PADDING, ROW_HEIGHT / 2.0),
+        cursor + egui::vec2(HORIZONTAL_PADDING, height / 2.0),
         Align2::LEFT_CENTER,
         base_icon,
         egui::FontId::proportional(ICON_SIZE),
@@ -187,7 +222,7 @@ fn draw_icon(
     // Position it slightly offset within the icon area for better look
     if is_dir && is_bookmarked {
         ui.painter().text(
-            cursor + egui::vec2(2.0, ROW_HEIGHT * 0.5), // Adjusted position
+            cursor + egui::vec2(2.0, height * 0.5), // Adjusted position
             Align2::LEFT_CENTER,
             "🔖",
             egui::FontId::proportional(ICON_SIZE * 0.7), // Even smaller font for the bookmark icon
@@ -202,6 +237,43 @@ fn draw_icon(
     ICON_WIDTH + HORIZONTAL_PADDING
 }
 
+/// Draw the header of a group of entries, as tall as an entry row
+pub fn draw_group_header(ui: &mut Ui, label: &str, colors: &AppColors) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), row_height(ui)),
+        egui::Sense::hover(),
+    );
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, true, label));
+    paint_group_header(ui, rect, label, colors);
+    response
+}
+
+/// Pin the header of the group scrolled past to the top of the list
+pub fn draw_sticky_group_header(ui: &Ui, label: &str, colors: &AppColors) {
+    let clip_rect = ui.clip_rect();
+    let rect = egui::Rect::from_min_size(
+        clip_rect.left_top(),
+        egui::vec2(clip_rect.width(), row_height(ui)),
+    );
+    ui.painter().rect_filled(rect, 0.0, colors.bg);
+    paint_group_header(ui, rect, label, colors);
+}
+
+fn paint_group_header(ui: &Ui, rect: egui::Rect, label: &str, colors: &AppColors) {
+    ui.painter().text(
+        rect.left_center() + egui::vec2(HORIZONTAL_PADDING, 0.0),
+        Align2::LEFT_CENTER,
+        label,
+        egui::FontId::proportional(HEADER_FONT_SIZE),
+        colors.highlight,
+    );
+    ui.painter().hline(
+        rect.x_range(),
+        rect.bottom() - 1.0,
+        egui::Stroke::new(1.0, colors.bg_light),
+    );
+}
+
 pub fn draw_entry_row(ui: &mut Ui, params: EntryRowParams<'_>) -> egui::Response {
     let EntryRowParams {
         entry,
@@ -214,12 +286,19 @@ pub fn draw_entry_row(ui: &mut Ui, params: EntryRowParams<'_>) -> egui::Response
         is_in_copy_clipboard,
         is_drag_active,
         is_drag_source,
+        touch_mode,
+        size_on_disk,
+        dir_size,
+        highlight,
     } = params;
 
-    let (rect, response) = ui.allocate_exact_size(
-        egui::vec2(ui.available_width(), ROW_HEIGHT),
-        egui::Sense::click_and_drag(), // Use click_and_drag to detect double clicks and drag
-    );
+    let height = row_height(ui);
+    let sense = if touch_mode {
+        egui::Sense::click()
+    } else {
+        egui::Sense::click_and_drag() // Use click_and_drag to detect double clicks and drag
+    };
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), sense);
 
     // Provide detailed accessibility information
     response.widget_info(|| {
@@ -274,29 +353,13 @@ pub fn draw_entry_row(ui: &mut Ui, params: EntryRowParams<'_>) -> egui::Response
 
     let mut cursor = rect.left_top();
 
-    // Calculate total fixed width (icon + date + size + paddings) - same as header
-    let fixed_width_total = ICON_WIDTH
-            + HORIZONTAL_PADDING // Padding after icon
-            + MODIFIED_DATE_WIDTH
-            + INTER_COLUMN_PADDING // Padding between Modified and Size
-            + FILE_SIZE_WIDTH
-            + HORIZONTAL_PADDING; // Padding at the end
+    // Name width takes remaining space, same as the header
+    let name_width = (rect.width() - fixed_width_total(size_on_disk)).max(0.0);
 
-    // Name width takes remaining space
-    let name_width = (rect.width() - fixed_width_total).max(0.0);
-
-    cursor.x += draw_icon(
-        ui,
-        cursor,
-        entry.is_dir,
-        is_selected,
-        colors,
-        is_bookmarked,
-        entry.is_symlink,
-    );
+    cursor.x += draw_icon(ui, cursor, entry, is_selected, colors, is_bookmarked);
 
     // --- Draw Name Column ---
-    let name_clip_rect = egui::Rect::from_min_size(cursor, egui::vec2(name_width, ROW_HEIGHT));
+    let name_clip_rect = egui::Rect::from_min_size(cursor, egui::vec2(name_width, height));
     let name_color = if is_in_cut_clipboard {
         // Use error color (red) for cut files
         colors.error
@@ -317,17 +380,44 @@ pub fn draw_entry_row(ui: &mut Ui, params: EntryRowParams<'_>) -> egui::Response
         ..Default::default()
     };
 
-    job.append(
-        &name_text,
-        0.0,
-        egui::TextFormat {
-            color: name_color,
-            ..Default::default()
-        },
-    );
+    let name_format = egui::TextFormat {
+        color: name_color,
+        ..Default::default()
+    };
+    match highlight.and_then(|text| crate::ui::type_ahead::find_ignore_case(&name_text, text)) {
+        Some(range) => {
+            job.append(&name_text[..range.start], 0.0, name_format.clone());
+            job.append(
+                &name_text[range.clone()],
+                0.0,
+                egui::TextFormat {
+                    color: colors.bg,
+                    background: colors.highlight,
+                    ..Default::default()
+                },
+            );
+            job.append(&name_text[range.end..], 0.0, name_format);
+        }
+        None => job.append(&name_text, 0.0, name_format),
+    }
+    // Show where a symlink points to after its name
+    if let Some(target) = entry.link_target() {
+        job.append(
+            &format!(" → {}", target.display()),
+            0.0,
+            egui::TextFormat {
+                color: if is_selected {
+                    colors.fg_selected
+                } else {
+                    colors.fg_light
+                },
+                ..Default::default()
+            },
+        );
+    }
 
     let galley = ui.fonts_mut(|f| f.layout_job(job));
-    let galley_pos = cursor + egui::vec2(0.0, ROW_HEIGHT / 2.0 - galley.size().y / 2.0);
+    let galley_pos = cursor + egui::vec2(0.0, height / 2.0 - galley.size().y / 2.0);
 
     ui.painter()
         .with_clip_rect(name_clip_rect)
@@ -342,7 +432,7 @@ pub fn draw_entry_row(ui: &mut Ui, params: EntryRowParams<'_>) -> egui::Response
 
     // --- Draw Modified Column ---
     ui.painter().text(
-        cursor + egui::vec2(0.0, ROW_HEIGHT / 2.0),
+        cursor + egui::vec2(0.0, height / 2.0),
         Align2::LEFT_CENTER,
         entry.formatted_modified(),
         egui::FontId::proportional(SECONDARY_COLUMN_FONT_SIZE),
@@ -351,14 +441,50 @@ pub fn draw_entry_row(ui: &mut Ui, params: EntryRowParams<'_>) -> egui::Response
     cursor.x += MODIFIED_DATE_WIDTH + INTER_COLUMN_PADDING; // Advance cursor including padding
 
     // --- Draw Size Column ---
-    ui.painter().text(
-        cursor + egui::vec2(FILE_SIZE_WIDTH - HORIZONTAL_PADDING, ROW_HEIGHT / 2.0),
-        Align2::RIGHT_CENTER,
-        entry.formatted_size(),
-        egui::FontId::proportional(SECONDARY_COLUMN_FONT_SIZE),
-        secondary_font_color,
-    );
-    // No cursor advance needed after the last column
+    let size_pos = cursor + egui::vec2(FILE_SIZE_WIDTH - HORIZONTAL_PADDING, height / 2.0);
+    match dir_size {
+        Some(DirSize::Scanning) => {
+            let spinner_size = SECONDARY_COLUMN_FONT_SIZE;
+            let spinner_rect = egui::Rect::from_min_size(
+                size_pos - egui::vec2(spinner_size, spinner_size / 2.0),
+                egui::vec2(spinner_size, spinner_size),
+            );
+            egui::Spinner::new()
+                .size(spinner_size)
+                .color(secondary_font_color)
+                .paint_at(ui, spinner_rect);
+        }
+        Some(DirSize::Done(size)) => {
+            ui.painter().text(
+                size_pos,
+                Align2::RIGHT_CENTER,
+                crate::utils::format::format_size(size, false),
+                egui::FontId::proportional(SECONDARY_COLUMN_FONT_SIZE),
+                secondary_font_color,
+            );
+        }
+        None => {
+            ui.painter().text(
+                size_pos,
+                Align2::RIGHT_CENTER,
+                entry.formatted_size(),
+                egui::FontId::proportional(SECONDARY_COLUMN_FONT_SIZE),
+                secondary_font_color,
+            );
+        }
+    }
+
+    // --- Draw Size On Disk Column ---
+    if size_on_disk {
+        cursor.x += FILE_SIZE_WIDTH + INTER_COLUMN_PADDING;
+        ui.painter().text(
+            cursor + egui::vec2(FILE_SIZE_WIDTH - HORIZONTAL_PADDING, height / 2.0),
+            Align2::RIGHT_CENTER,
+            entry.formatted_size_on_disk(),
+            egui::FontId::proportional(SECONDARY_COLUMN_FONT_SIZE),
+            secondary_font_color,
+        );
+    }
 
     response
 }
@@ -372,10 +498,10 @@ pub fn draw_parent_entry_row(
     is_in_cut_clipboard: bool,
     is_in_copy_clipboard: bool,
 ) -> egui::Response {
+    let height = row_height(ui);
     let (rect, response) = ui.allocate_exact_size(
-        egui::vec2(ui.available_width(), ROW_HEIGHT),
-        // Use click_and_drag to detect double clicks
-        egui::Sense::click_and_drag(),
+        egui::vec2(ui.available_width(), height),
+        egui::Sense::click(),
     );
 
     // Provide detailed accessibility information
@@ -396,15 +522,7 @@ pub fn draw_parent_entry_row(
 
     let name_width = rect.width() - ICON_WIDTH;
 
-    cursor.x += draw_icon(
-        ui,
-        cursor,
-        entry.is_dir,
-        is_selected,
-        colors,
-        is_bookmarked,
-        entry.is_symlink,
-    );
+    cursor.x += draw_icon(ui, cursor, entry, is_selected, colors, is_bookmarked);
 
     // Name with truncation
     let name_text = truncate_text(&entry.name, name_width);
@@ -420,7 +538,7 @@ pub fn draw_parent_entry_row(
         colors.fg
     };
     ui.painter().text(
-        cursor + egui::vec2(0.0, ROW_HEIGHT / 2.0),
+        cursor + egui::vec2(0.0, height / 2.0),
         Align2::LEFT_CENTER,
         &name_text,
         egui::FontId::proportional(14.0),
@@ -51,7 +51,19 @@ pub fn show_help_window(
                         (ShortcutAction::GoToPath, "Go to path"),
                         (ShortcutAction::GoBackInHistory, "Go back in history"),
                         (ShortcutAction::GoForwardInHistory, "Go forward in history"),
-                        (ShortcutAction::ToggleHiddenFiles, "Toggle hidden files"),
+                        (
+                            ShortcutAction::JumpBack,
+                            "Jump back to an earlier selection",
+                        ),
+                        (ShortcutAction::JumpForward, "Jump forward again"),
+                        (
+                            ShortcutAction::ToggleHiddenFiles,
+                            "Toggle hidden files in this tab",
+                        ),
+                        (
+                            ShortcutAction::ToggleHiddenFilesDefault,
+                            "Toggle hidden files in this directory and by default",
+                        ),
                     ];
 
                     for (action, description) in navigation_actions {
@@ -59,6 +71,11 @@ pub fn show_help_window(
                         ui.label(description);
                         ui.end_row();
                     }
+
+                    // Counts typed before movement, mark and delete shortcuts
+                    ui.label(RichText::new("5j, 3 Space, 5gg").color(colors.highlight));
+                    ui.label("Repeat a shortcut or jump to an entry");
+                    ui.end_row();
                 });
 
                 ui.add_space(10.0); // Space between sections
@@ -71,8 +88,23 @@ pub fn show_help_window(
                             ShortcutAction::ShowTeleport,
                             "Teleport with history fuzzy search",
                         ),
+                        (
+                            ShortcutAction::ShowFileHistory,
+                            "Show recently opened and previewed files",
+                        ),
+                        (ShortcutAction::ShowProfiles, "Switch or create profiles"),
+                        (
+                            ShortcutAction::ShowSessions,
+                            "Save or restore named sessions",
+                        ),
                         (ShortcutAction::OpenDirectory, "Open directory"),
                         (ShortcutAction::ShowBookmarks, "Show bookmark popup"),
+                        (ShortcutAction::SetMark, "Set mark, followed by a letter"),
+                        (
+                            ShortcutAction::JumpToMark,
+                            "Jump to mark, followed by a letter",
+                        ),
+                        (ShortcutAction::ShowMarks, "Show marks popup"),
                         #[cfg(target_os = "windows")]
                         (ShortcutAction::ShowWindowsDrives, "Show drives popup"),
                         #[cfg(target_os = "macos")]
@@ -81,11 +113,20 @@ pub fn show_help_window(
                             ShortcutAction::ShowFilePreview,
                             "Preview file in a popup window",
                         ),
+                        (ShortcutAction::ShowDiff, "Diff the two marked files"),
+                        (
+                            ShortcutAction::RotatePreview,
+                            "Rotate pages clockwise in the PDF viewer",
+                        ),
                         (ShortcutAction::ShowSortToggle, "Show sort toggle popup"),
                         (
                             ShortcutAction::ShowActionHistory,
                             "Show action history popup",
                         ),
+                        (
+                            ShortcutAction::ShowTrash,
+                            "Browse and restore trashed files",
+                        ),
                     ];
 
                     for (action, description) in popup_actions {
@@ -102,12 +143,26 @@ pub fn show_help_window(
                 table.show(ui, |ui| {
                     let tab_actions = [
                         (ShortcutAction::CreateTab, "Create new tab"),
+                        (ShortcutAction::NewWindow, "Open new window"),
                         (ShortcutAction::CloseCurrentTab, "Close current tab"),
                         (
                             ShortcutAction::SwitchToPreviousTab,
                             "Switch to previous tab",
                         ),
                         (ShortcutAction::SwitchToNextTab, "Switch to next tab"),
+                        (
+                            ShortcutAction::ToggleDualPane,
+                            "Toggle dual pane layout in place of the preview",
+                        ),
+                        (ShortcutAction::SwitchPaneFocus, "Focus the other pane"),
+                        (
+                            ShortcutAction::CopyToOtherPane,
+                            "Copy selected entries to the other pane",
+                        ),
+                        (
+                            ShortcutAction::MoveToOtherPane,
+                            "Move selected entries to the other pane",
+                        ),
                     ];
 
                     for (action, description) in tab_actions {
@@ -140,15 +195,31 @@ pub fn show_help_window(
                             ShortcutAction::OpenWithCommand,
                             "Open file with custom command",
                         ),
+                        (
+                            ShortcutAction::ShowProperties,
+                            "Show properties of selected file/directory",
+                        ),
                         (
                             ShortcutAction::DeleteEntry,
-                            "Delete selected file/directory",
+                            "Move selected file/directory to the trash",
+                        ),
+                        (
+                            ShortcutAction::DeleteEntryPermanently,
+                            "Delete selected file/directory permanently",
                         ),
                         (
                             ShortcutAction::RenameEntry,
                             "Rename selected file/directory",
                         ),
                         (ShortcutAction::AddEntry, "Add file/directory"),
+                        (
+                            ShortcutAction::PasteClipboardAsFile,
+                            "Save the clipboard text or image as a new file",
+                        ),
+                        (
+                            ShortcutAction::TouchEntry,
+                            "Update timestamps or create an empty file",
+                        ),
                         (ShortcutAction::SelectEntry, "Mark/unmark entry"),
                         (
                             ShortcutAction::ToggleRangeSelection,
@@ -158,6 +229,24 @@ pub fn show_help_window(
                         (ShortcutAction::CopyEntry, "Copy selected entry"),
                         (ShortcutAction::CutEntry, "Cut selected entry"),
                         (ShortcutAction::PasteEntry, "Paste copied/cut entries"),
+                        (ShortcutAction::PasteSymlink, "Paste entries as symlinks"),
+                        (ShortcutAction::PasteHardlink, "Paste files as hard links"),
+                        (
+                            ShortcutAction::CreateSymlink,
+                            "Create symlink to selected entry",
+                        ),
+                        (
+                            ShortcutAction::FollowSymlink,
+                            "Go to the target of selected symlink",
+                        ),
+                        (
+                            ShortcutAction::ShowLinkTarget,
+                            "Show and copy the target of selected symlink",
+                        ),
+                        (
+                            ShortcutAction::ShareEntry,
+                            "Share selected entries to mail, AirDrop and other targets",
+                        ),
                         (
                             ShortcutAction::ToggleBookmark,
                             "Add/remove bookmark for current directory",
@@ -179,8 +268,17 @@ pub fn show_help_window(
                 ui.heading(RichText::new("Search").color(colors.fg_light));
                 let table = egui::Grid::new("search_help_grid");
                 table.show(ui, |ui| {
-                    let search_actions =
-                        [(ShortcutAction::ActivateSearch, "Activate search filter")];
+                    let search_actions = [
+                        (ShortcutAction::ActivateSearch, "Activate search filter"),
+                        (
+                            ShortcutAction::TypeAheadFilter,
+                            "Filter the list as you type, Esc clears",
+                        ),
+                        (
+                            ShortcutAction::SearchContents,
+                            "Search file contents under the current directory",
+                        ),
+                    ];
                     for (action, description) in search_actions {
                         render_shortcut_display(ui, action, shortcuts, colors);
                         ui.label(description);
@@ -208,8 +306,33 @@ pub fn show_help_window(
                             ShortcutAction::OpenTerminal,
                             "Open terminal panel at current directory",
                         ),
+                        (
+                            ShortcutAction::OpenSystemTerminal,
+                            "Open system terminal at selected or current directory",
+                        ),
                         (ShortcutAction::Exit, "Exit Kiorg or close popups"),
                         (ShortcutAction::ShowHelp, "Toggle this help window"),
+                        (ShortcutAction::ToggleTouchMode, "Toggle touch mode"),
+                        (
+                            ShortcutAction::ToggleDirSizes,
+                            "Toggle recursive directory sizes",
+                        ),
+                        (
+                            ShortcutAction::ToggleFlatten,
+                            "Toggle listing all files under the current directory",
+                        ),
+                        (
+                            ShortcutAction::ToggleSafePreview,
+                            "Toggle safe preview of all files",
+                        ),
+                        (
+                            ShortcutAction::CycleImageZoom,
+                            "Cycle image preview zoom: fit, 100%, fill",
+                        ),
+                        (
+                            ShortcutAction::TogglePickerMode,
+                            "Toggle picker mode, Enter copies the picked paths",
+                        ),
                     ];
                     for (action, description) in util_actions {
                         render_shortcut_display(ui, action, shortcuts, colors);
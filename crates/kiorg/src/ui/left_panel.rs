@@ -2,7 +2,7 @@ use egui::Ui;
 use std::path::PathBuf;
 
 use crate::app::Kiorg;
-use crate::ui::file_list::{self, ROW_HEIGHT};
+use crate::ui::file_list;
 use crate::ui::style::HEADER_ROW_HEIGHT;
 
 use super::style::section_title_text;
@@ -41,7 +41,8 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) -> Option<Pat
 
                 // Draw all rows
                 for (i, entry) in parent_entries.iter().enumerate() {
-                    let is_bookmarked = bookmarks.contains(&entry.meta.path);
+                    let is_bookmarked =
+                        crate::models::bookmark::is_bookmarked(bookmarks, &entry.meta.path);
                     // Check if this entry is in the clipboard as a cut or copy operation
                     let (is_in_cut_clipboard, is_in_copy_clipboard) = match &app.clipboard {
                         Some(crate::app::Clipboard::Cut(paths))
@@ -77,13 +78,14 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui, width: f32, height: f32) -> Option<Pat
 
                 // Ensure current directory is visible in parent list
                 if app.scroll_left_panel && !parent_entries.is_empty() {
+                    let row_height = file_list::row_height(ui);
                     let ui_spacing = ui.spacing().item_spacing.y;
-                    let spaced_row_height = ROW_HEIGHT + ui_spacing;
+                    let spaced_row_height = row_height + ui_spacing;
                     let selected_pos = parent_selected_index as f32 * spaced_row_height;
                     ui.scroll_to_rect(
                         egui::Rect::from_min_size(
                             egui::pos2(0.0, selected_pos),
-                            egui::vec2(width, ROW_HEIGHT),
+                            egui::vec2(width, row_height),
                         ),
                         Some(egui::Align::Center),
                     );
@@ -1,10 +1,13 @@
 pub mod center_panel;
+pub mod dual_pane;
 pub mod egui_notify;
 pub mod file_list;
 pub mod help_window;
 pub mod left_panel;
 pub mod notification;
+pub mod operations;
 pub mod path_nav;
+pub mod picker;
 pub mod popup;
 pub mod preview;
 pub mod rename;
@@ -14,6 +17,7 @@ pub mod separator;
 pub mod style;
 pub mod terminal;
 pub mod top_banner;
+pub mod type_ahead;
 pub mod update;
 
 #[inline]
@@ -10,6 +10,7 @@ use crate::ui::update::Release;
 pub enum NotificationMessage {
     Error(String),
     Info(String),
+    Success(String),
     UpdateAvailable(Release), // Version string
     UpdateSuccess,            // Version string
     UpdateFailed(String),     // Error message
@@ -79,6 +80,9 @@ pub fn check_notifications(app: &mut Kiorg) {
             NotificationMessage::Info(info) => {
                 notify_info(&mut app.toasts, &info);
             }
+            NotificationMessage::Success(message) => {
+                notify_success(&mut app.toasts, &message);
+            }
         }
     }
 }
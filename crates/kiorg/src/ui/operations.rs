@@ -0,0 +1,336 @@
+//! Long-running background operations, shown with their progress in a panel
+//! at the bottom of the window until they finish
+
+use crate::app::Kiorg;
+use crate::models::action_history::{ActionType, CopyOperation, MoveOperation};
+use crate::plugins::manager::LoadedPlugin;
+use crate::ui::popup::archive_test;
+use crate::ui::style::section_title_text;
+use crate::utils::format::{format_count, format_size};
+use crate::utils::transfer::{Transfer, TransferKind, TransferSummary};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Snapshot of a running or finished operation
+#[derive(Debug, Clone, Default)]
+pub struct OperationProgress {
+    pub done: u64,
+    pub total: u64,
+    /// What the operation is working on, e.g. the current file
+    pub message: Option<String>,
+    /// Summary or error, set once the operation finished
+    pub result: Option<Result<String, String>>,
+}
+
+/// Handle to a background operation
+///
+/// The operation is cancelled when the handle is dropped.
+#[derive(Debug)]
+pub struct Operation {
+    pub title: String,
+    state: Arc<Mutex<OperationProgress>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Operation {
+    pub fn snapshot(&self) -> OperationProgress {
+        self.state.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Files a plugin operation runs on: the marked entries, or the entry the
+/// context menu was opened on
+pub fn target_paths(app: &Kiorg, entry_path: Option<&Path>) -> Vec<PathBuf> {
+    let tab = app.tab_manager.current_tab_ref();
+    let mut paths: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+    if paths.is_empty() {
+        paths.extend(entry_path.map(Path::to_path_buf));
+    }
+    paths.sort();
+    paths
+}
+
+/// Run the bulk operation `index` of `plugin` on `paths` in the background
+pub fn start_plugin_operation(
+    app: &mut Kiorg,
+    ctx: &egui::Context,
+    plugin: Arc<LoadedPlugin>,
+    index: usize,
+    paths: Vec<PathBuf>,
+) {
+    let Some(operation) = plugin.bulk_operations.get(index) else {
+        return;
+    };
+    let operation_id = operation.capability.id.clone();
+    let title = operation.capability.label.clone();
+
+    let state = Arc::new(Mutex::new(OperationProgress {
+        total: paths.len() as u64,
+        ..Default::default()
+    }));
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    let thread_state = state.clone();
+    let thread_cancel = cancel.clone();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let result = plugin.run_bulk_operation(
+            &operation_id,
+            &paths,
+            &thread_cancel,
+            |done, total, message| {
+                if let Ok(mut state) = thread_state.lock() {
+                    state.done = done;
+                    state.total = total;
+                    state.message = message;
+                }
+                ctx.request_repaint();
+            },
+        );
+        if let Ok(mut state) = thread_state.lock() {
+            state.result = Some(result.map_err(|e| e.to_string()));
+        }
+        ctx.request_repaint();
+    });
+
+    app.operations.push(Operation {
+        title,
+        state,
+        cancel,
+    });
+}
+
+/// Draw the operations panel and report the finished operations
+pub fn draw(ui: &mut egui::Ui, app: &mut Kiorg) {
+    let mut finished = Vec::new();
+    app.operations.retain(|operation| {
+        let result = operation.snapshot().result;
+        if let Some(result) = result {
+            finished.push((operation.title.clone(), result));
+            return false;
+        }
+        true
+    });
+    for (title, result) in finished {
+        match result {
+            Ok(summary) => app.notify_info(format!("{title}: {summary}")),
+            Err(e) => app.notify_error(format!("{title} failed: {e}")),
+        }
+    }
+
+    finish_transfers(app);
+
+    if app.operations.is_empty() && app.transfers.is_idle() {
+        return;
+    }
+
+    egui::Panel::bottom("operations_panel")
+        .resizable(false)
+        .show_inside(ui, |ui| {
+            ui.label(section_title_text("Operations", &app.colors));
+            for operation in &app.operations {
+                let progress = operation.snapshot();
+                ui.horizontal(|ui| {
+                    ui.label(&operation.title);
+                    let fraction = if progress.total > 0 {
+                        progress.done as f32 / progress.total as f32
+                    } else {
+                        0.0
+                    };
+                    let counts = format!(
+                        "{}/{}",
+                        format_count(progress.done),
+                        format_count(progress.total)
+                    );
+                    let text = match &progress.message {
+                        Some(message) => format!("{counts} {message}"),
+                        None => counts,
+                    };
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if operation.is_cancelled() {
+                            ui.label("Cancelling...");
+                        } else if ui.button("Cancel").clicked() {
+                            operation.cancel();
+                        }
+                        ui.add(egui::ProgressBar::new(fraction).text(text));
+                    });
+                });
+            }
+            for transfer in app.transfers.transfers() {
+                draw_transfer(ui, transfer);
+            }
+        });
+}
+
+fn draw_transfer(ui: &mut egui::Ui, transfer: &Transfer) {
+    let progress = transfer.progress();
+    let dest = transfer
+        .dest_dir
+        .file_name()
+        .unwrap_or(transfer.dest_dir.as_os_str())
+        .to_string_lossy();
+    let entries = match transfer.sources.as_slice() {
+        [source] => source.file_name().unwrap_or_default().to_string_lossy(),
+        sources => format!("{} entries", format_count(sources.len() as u64)).into(),
+    };
+    let testing = transfer.kind == TransferKind::Test;
+    ui.horizontal(|ui| {
+        if testing {
+            ui.label(format!("{} {entries}", transfer.kind.verb()));
+        } else {
+            ui.label(format!("{} {entries} to {dest}", transfer.kind.verb()));
+        }
+        let fraction = if testing && progress.total_files > 0 {
+            progress.files_done as f32 / progress.total_files as f32
+        } else if !testing && progress.total_bytes > 0 {
+            progress.bytes_done as f32 / progress.total_bytes as f32
+        } else {
+            0.0
+        };
+        let text = if !progress.started {
+            "Queued".to_string()
+        } else if testing {
+            // Tar streams don't tell their member count upfront
+            let mut text = if progress.total_files > 0 {
+                format!(
+                    "{}/{} members",
+                    format_count(progress.files_done as u64),
+                    format_count(progress.total_files as u64)
+                )
+            } else {
+                format!("{} members", format_count(progress.files_done as u64))
+            };
+            if transfer.is_paused() {
+                text.push_str(" Paused");
+            } else if let Some(name) = &progress.current {
+                text.push_str(&format!(" {}", name.to_string_lossy()));
+            }
+            text
+        } else if transfer.is_paused() {
+            format!(
+                "{}/{} Paused",
+                format_size(progress.bytes_done, false),
+                format_size(progress.total_bytes, false)
+            )
+        } else {
+            let mut text = format!(
+                "{}/{} {}/s, {}/{} files",
+                format_size(progress.bytes_done, false),
+                format_size(progress.total_bytes, false),
+                format_size(progress.speed as u64, false),
+                format_count(progress.files_done as u64),
+                format_count(progress.total_files as u64)
+            );
+            if let Some(name) = progress.current.as_deref().and_then(|p| p.file_name()) {
+                text.push_str(&format!(" {}", name.to_string_lossy()));
+            }
+            text
+        };
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if transfer.is_cancelled() {
+                ui.label("Cancelling...");
+            } else {
+                if ui.button("Cancel").clicked() {
+                    transfer.cancel();
+                }
+                let paused = transfer.is_paused();
+                if ui.button(if paused { "Resume" } else { "Pause" }).clicked() {
+                    transfer.set_paused(!paused);
+                }
+            }
+            ui.add(egui::ProgressBar::new(fraction).text(text));
+        });
+    });
+}
+
+/// Record the finished pastes in the action history of the tab showing their
+/// destination and refresh the entries
+fn finish_transfers(app: &mut Kiorg) {
+    let finished = app.transfers.take_finished();
+    if finished.is_empty() {
+        return;
+    }
+    for summary in finished {
+        if summary.kind == TransferKind::Test {
+            archive_test::report(app, summary);
+        } else {
+            report_transfer(app, summary);
+        }
+    }
+    app.refresh_entries();
+}
+
+fn report_transfer(app: &mut Kiorg, summary: TransferSummary) {
+    app.dir_sizes.invalidate(&summary.dest_dir);
+    if summary.kind == TransferKind::Move {
+        for (source, _) in &summary.completed {
+            app.dir_sizes.invalidate(source);
+        }
+    }
+    let count = summary.completed.len();
+    let (done, action) = match summary.kind {
+        // Reported by `archive_test::report`
+        TransferKind::Test => return,
+        TransferKind::Copy => (
+            "Copied",
+            ActionType::Copy {
+                operations: summary
+                    .completed
+                    .into_iter()
+                    .map(|(source_path, target_path)| CopyOperation {
+                        source_path,
+                        target_path,
+                    })
+                    .collect(),
+            },
+        ),
+        TransferKind::Move => (
+            "Moved",
+            ActionType::Move {
+                operations: summary
+                    .completed
+                    .into_iter()
+                    .map(|(source_path, target_path)| MoveOperation {
+                        source_path,
+                        target_path,
+                    })
+                    .collect(),
+            },
+        ),
+    };
+    if count > 0 {
+        app.tab_manager
+            .tab_for_path_mut(&summary.dest_dir)
+            .action_history
+            .add_action(action);
+    }
+
+    let has_errors = !summary.errors.is_empty();
+    for error in summary.errors {
+        app.notify_error(error);
+    }
+    let entries = if count == 1 { "entry" } else { "entries" };
+    if summary.cancelled {
+        app.notify_info(format!(
+            "Paste cancelled, {done} {} {entries}",
+            format_count(count as u64)
+        ));
+    } else if !has_errors {
+        app.notify_success(format!("{done} {} {entries}", format_count(count as u64)));
+    }
+}
@@ -0,0 +1,312 @@
+//! Compact picker mode, a file chooser for scripts
+
+use std::path::PathBuf;
+
+use egui::{RichText, Ui};
+
+use crate::app::Kiorg;
+use crate::models::tab::Tab;
+use crate::ui::{center_panel, path_nav};
+
+/// Window size of `kiorg --pick`
+pub const PICKER_INNER_SIZE: [f32; 2] = [480.0, 640.0];
+pub const PICKER_MIN_INNER_SIZE: [f32; 2] = [320.0, 320.0];
+
+/// Where the picked paths go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickOutput {
+    /// Print to stdout, one path per line
+    Print,
+    /// Print to stdout, each path terminated by a NUL byte
+    PrintNull,
+    /// Copy to the clipboard, one path per line
+    Copy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerMode {
+    pub output: PickOutput,
+    /// Launched with `--pick`, exit once a path is picked or picking is cancelled
+    pub standalone: bool,
+    /// Pick directories instead of files
+    pub directories: bool,
+    /// Allow picking all marked entries instead of a single one
+    pub multiple: bool,
+    /// File name to save as, picks a new path in the current directory
+    pub save_name: Option<String>,
+}
+
+impl PickerMode {
+    #[must_use]
+    pub const fn new(output: PickOutput) -> Self {
+        Self {
+            output,
+            standalone: false,
+            directories: false,
+            multiple: true,
+            save_name: None,
+        }
+    }
+}
+
+fn save_name_id() -> egui::Id {
+    egui::Id::new("picker_save_name")
+}
+
+pub fn focus_save_name(ctx: &egui::Context) {
+    ctx.memory_mut(|m| m.request_focus(save_name_id()));
+}
+
+/// Whether the file name field of the save picker has keyboard focus
+#[must_use]
+pub fn save_name_focused(ctx: &egui::Context) -> bool {
+    ctx.memory(|m| m.has_focus(save_name_id()))
+}
+
+/// Paths to pick: the marked entries, otherwise the selected entry
+///
+/// Falls back to the current directory when it's empty or, when picking
+/// directories, when a file is selected.
+#[must_use]
+pub fn picked_paths(tab: &Tab, picker: &PickerMode) -> Vec<PathBuf> {
+    if let Some(name) = &picker.save_name {
+        let name = name.trim();
+        if name.is_empty() {
+            return Vec::new();
+        }
+        return vec![tab.current_path.join(name)];
+    }
+
+    if !tab.marked_entries.is_empty() {
+        let mut paths: Vec<_> = tab
+            .marked_entries
+            .iter()
+            .filter(|path| !picker.directories || path.is_dir())
+            .cloned()
+            .collect();
+        paths.sort();
+        if !picker.multiple {
+            paths.truncate(1);
+        }
+        if !paths.is_empty() {
+            return paths;
+        }
+    }
+    match tab.selected_entry() {
+        Some(entry) if !picker.directories || entry.is_dir => vec![entry.meta.path.clone()],
+        _ => vec![tab.current_path.clone()],
+    }
+}
+
+#[must_use]
+pub fn format_paths(paths: &[PathBuf], output: PickOutput) -> String {
+    let paths = paths.iter().map(|path| path.display().to_string());
+    if output == PickOutput::PrintNull {
+        paths.map(|path| path + "\0").collect()
+    } else {
+        paths.collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Switch a running window to picker mode, picked paths are copied to the clipboard
+pub fn enter(app: &mut Kiorg, ctx: &egui::Context) {
+    app.picker = Some(PickerMode::new(PickOutput::Copy));
+    app.search_bar.activate();
+    ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+        egui::WindowLevel::AlwaysOnTop,
+    ));
+}
+
+/// Go back to the normal layout
+pub fn leave(app: &mut Kiorg, ctx: &egui::Context) {
+    app.picker = None;
+    ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+        egui::WindowLevel::Normal,
+    ));
+}
+
+/// Handle Enter: directories are entered unless they are marked, files are picked
+///
+/// When saving, Enter on a file uses its name for the new file.
+pub fn open_or_pick(app: &mut Kiorg, ctx: &egui::Context) {
+    let Some(picker) = &mut app.picker else {
+        return;
+    };
+    let tab = app.tab_manager.current_tab_ref();
+    if let Some(entry) = tab.selected_entry() {
+        if entry.meta.path.is_dir() && tab.marked_entries.is_empty() {
+            let path = entry.meta.path.clone();
+            app.navigate_to_dir(path);
+            return;
+        }
+        if picker.save_name.is_some() {
+            picker.save_name = Some(entry.name.clone());
+            focus_save_name(ctx);
+            return;
+        }
+    }
+    pick(app, ctx);
+}
+
+/// Output the picked paths and leave picker mode
+pub fn pick(app: &mut Kiorg, ctx: &egui::Context) {
+    let Some(picker) = app.picker.clone() else {
+        return;
+    };
+    let paths = picked_paths(app.tab_manager.current_tab_ref(), &picker);
+    if paths.is_empty() {
+        app.toasts.info("Enter a file name first");
+        return;
+    }
+    let text = format_paths(&paths, picker.output);
+    match picker.output {
+        PickOutput::Print | PickOutput::PrintNull => {
+            use std::io::Write;
+            let mut stdout = std::io::stdout();
+            let _ = write!(stdout, "{text}");
+            if picker.output == PickOutput::Print {
+                let _ = writeln!(stdout);
+            }
+            let _ = stdout.flush();
+        }
+        PickOutput::Copy => ctx.copy_text(text),
+    }
+
+    if picker.standalone {
+        app.shutdown_requested = true;
+    } else {
+        leave(app, ctx);
+        if picker.output == PickOutput::Copy {
+            app.toasts
+                .info(format!("Copied {} path(s) to clipboard", paths.len()));
+        }
+    }
+}
+
+/// Leave picker mode without picking anything
+pub fn cancel(app: &mut Kiorg, ctx: &egui::Context) {
+    match &app.picker {
+        Some(picker) if picker.standalone => app.shutdown_requested = true,
+        Some(_) => leave(app, ctx),
+        None => {}
+    }
+}
+
+/// Draw the slim picker layout: path, actions and the file list
+pub fn draw(app: &mut Kiorg, ui: &mut Ui, height: f32) {
+    let ctx = ui.ctx().clone();
+    let saving = app
+        .picker
+        .as_ref()
+        .is_some_and(|picker| picker.save_name.is_some());
+    let header = ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(RichText::new("Cancel").color(app.colors.fg_light))
+                    .clicked()
+                {
+                    cancel(app, &ctx);
+                }
+                let (label, hint) = if saving {
+                    ("Save", "Save in the current directory")
+                } else {
+                    ("Pick", "Pick the marked entries or the selected entry")
+                };
+                if ui
+                    .button(RichText::new(label).color(app.colors.highlight))
+                    .on_hover_text(hint)
+                    .clicked()
+                {
+                    pick(app, &ctx);
+                }
+                ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                    match path_nav::draw_path_navigation(
+                        ui,
+                        &app.tab_manager.current_tab_ref().current_path,
+                        &app.colors,
+                        1,
+                    ) {
+                        Some(path_nav::PathNavMessage::Navigate(path)) => {
+                            app.navigate_to_dir(path);
+                        }
+                        Some(path_nav::PathNavMessage::GoToPath) => app.show_goto_path_popup(),
+                        None => {}
+                    }
+                });
+            });
+        });
+
+        if let Some(name) = app
+            .picker
+            .as_mut()
+            .and_then(|picker| picker.save_name.as_mut())
+        {
+            let mut confirmed = false;
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Name:").color(app.colors.fg_light));
+                let response = ui.add(
+                    egui::TextEdit::singleline(name)
+                        .id(save_name_id())
+                        .desired_width(f32::INFINITY),
+                );
+                confirmed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            });
+            if confirmed {
+                pick(app, &ctx);
+            }
+        }
+    });
+
+    let content_height = crate::ui::clamp_height(
+        height - header.response.rect.height() - ui.spacing().item_spacing.y,
+    );
+    let width = ui.available_width();
+    center_panel::draw(app, ui, width, content_height);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picked_paths() {
+        let mut picker = PickerMode::new(PickOutput::Print);
+        let mut tab = Tab::new(PathBuf::from("/tmp/empty"));
+        tab.entries.clear();
+        assert_eq!(
+            picked_paths(&tab, &picker),
+            vec![PathBuf::from("/tmp/empty")]
+        );
+
+        tab.marked_entries.insert(PathBuf::from("/tmp/empty/b"));
+        tab.marked_entries.insert(PathBuf::from("/tmp/empty/a"));
+        let paths = picked_paths(&tab, &picker);
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("/tmp/empty/a"), PathBuf::from("/tmp/empty/b")]
+        );
+        assert_eq!(
+            format_paths(&paths, PickOutput::Print),
+            "/tmp/empty/a\n/tmp/empty/b"
+        );
+        assert_eq!(
+            format_paths(&paths, PickOutput::PrintNull),
+            "/tmp/empty/a\0/tmp/empty/b\0"
+        );
+
+        picker.multiple = false;
+        assert_eq!(
+            picked_paths(&tab, &picker),
+            vec![PathBuf::from("/tmp/empty/a")]
+        );
+
+        picker.save_name = Some(" new.txt ".to_string());
+        assert_eq!(
+            picked_paths(&tab, &picker),
+            vec![PathBuf::from("/tmp/empty/new.txt")]
+        );
+        picker.save_name = Some(String::new());
+        assert!(picked_paths(&tab, &picker).is_empty());
+    }
+}
@@ -1,18 +1,106 @@
 use crate::app::Kiorg;
 use crate::models::action_history::{ActionType, CreateOperation};
 use crate::ui::popup::PopupType;
+use crate::utils::clipboard::{self, ClipboardContent};
+use crate::utils::templates;
 use egui::{Context, Frame, Key, TextEdit};
 use std::fs;
+use std::path::PathBuf;
 
 use super::window_utils::new_center_popup_window;
 
+/// State of the add entry popup
+#[derive(Debug, Clone, Default)]
+pub struct AddEntryState {
+    pub name: String,
+    /// Files in the templates directory
+    pub templates: Vec<PathBuf>,
+    /// Index into `templates` of the file to copy, an empty file is created if `None`
+    pub template: Option<usize>,
+    /// Clipboard content written to the new file instead of a template
+    pub clipboard: Option<ClipboardContent>,
+}
+
+impl AddEntryState {
+    /// Pick the next template with `step` 1 or the previous one with -1, wrapping through no template
+    fn cycle_template(&mut self, step: isize) {
+        let count = self.templates.len() as isize + 1;
+        let current = self.template.map_or(0, |i| i as isize + 1);
+        let next = (current + step).rem_euclid(count);
+        self.template = (next > 0).then(|| next as usize - 1);
+    }
+
+    fn selected_template(&self) -> Option<&PathBuf> {
+        self.templates.get(self.template?)
+    }
+}
+
+fn template_label(template: Option<&PathBuf>) -> String {
+    template.map_or_else(
+        || "Empty file".to_string(),
+        |path| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        },
+    )
+}
+
+/// Open the add entry popup, listing the templates from the configured directory
+pub fn open(app: &mut Kiorg) {
+    let config_dir = crate::config::get_kiorg_config_dir(app.config_dir_override.as_deref());
+    let templates = templates::list(&app.config.templates_dir(&config_dir));
+    app.show_popup = Some(PopupType::AddEntry(AddEntryState {
+        templates,
+        ..Default::default()
+    }));
+}
+
+/// Open the add entry popup to save the text or image in the clipboard as a
+/// new file
+pub fn open_paste(app: &mut Kiorg) {
+    let content = match clipboard::read() {
+        Ok(content) => content,
+        Err(e) => {
+            app.notify_error(e);
+            return;
+        }
+    };
+    let dir = &app.tab_manager.current_tab_ref().current_path;
+    let name = crate::utils::file_operations::unique_path(dir, content.default_name())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    app.show_popup = Some(PopupType::AddEntry(AddEntryState {
+        name,
+        clipboard: Some(content),
+        ..Default::default()
+    }));
+}
+
+fn clipboard_label(content: &ClipboardContent) -> String {
+    match content {
+        ClipboardContent::Text(text) => format!("Text, {} characters", text.chars().count()),
+        ClipboardContent::Image(png) => format!(
+            "PNG image, {}",
+            crate::utils::format::format_size(png.len() as u64, false)
+        ),
+    }
+}
+
 pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
     // Early return if not in add mode
-    if let Some(PopupType::AddEntry(entry_name)) = &mut app.show_popup {
+    if let Some(PopupType::AddEntry(state)) = &mut app.show_popup {
         let mut keep_open: bool = true;
 
         // Use Area instead of Window for a more lightweight appearance like search_bar
-        new_center_popup_window("Add file/directory")
+        let title = if state.clipboard.is_some() {
+            "Paste as new file"
+        } else {
+            "Add file/directory"
+        };
+        new_center_popup_window(title)
             .open(&mut keep_open)
             .show(ctx, |ui| {
                 // Create a frame with styling similar to search bar
@@ -25,7 +113,7 @@ pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
                         // Horizontal layout for input and close button
                         ui.horizontal(|ui| {
                             // Text input field
-                            let text_edit = TextEdit::singleline(entry_name)
+                            let text_edit = TextEdit::singleline(&mut state.name)
                                 .hint_text("Enter name (append '/' at the end for directory)...")
                                 .desired_width(f32::INFINITY) // Take available width
                                 .frame(egui::Frame::NONE); // No frame, like search bar
@@ -36,6 +124,38 @@ pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
                             response.request_focus();
                         });
                     });
+
+                if let Some(content) = &state.clipboard {
+                    ui.label(
+                        egui::RichText::new(clipboard_label(content)).color(app.colors.fg_light),
+                    );
+                }
+
+                if !state.templates.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.label("Template:");
+                        let selected = template_label(state.selected_template());
+                        egui::ComboBox::from_id_salt("add_entry_template")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut state.template,
+                                    None,
+                                    template_label(None),
+                                );
+                                for (i, path) in state.templates.iter().enumerate() {
+                                    ui.selectable_value(
+                                        &mut state.template,
+                                        Some(i),
+                                        template_label(Some(path)),
+                                    );
+                                }
+                            });
+                        ui.label(
+                            egui::RichText::new("Up/Down to switch").color(app.colors.fg_light),
+                        );
+                    });
+                }
             });
 
         if !keep_open {
@@ -48,8 +168,27 @@ pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
 /// Returns `true` if the input was handled (consumed), `false` otherwise.
 pub(crate) fn handle_key_press(ctx: &Context, app: &mut Kiorg) -> bool {
     // Early return if not in add mode
-    let entry_name = match &app.show_popup {
-        Some(PopupType::AddEntry(name)) => name.clone(),
+    let (entry_name, template) = match &mut app.show_popup {
+        Some(PopupType::AddEntry(state)) => {
+            // Up and Down are free in the single line input, use them to pick a template
+            if !state.templates.is_empty() {
+                if ctx.input(|i| i.key_pressed(Key::ArrowDown)) {
+                    state.cycle_template(1);
+                    return true;
+                }
+                if ctx.input(|i| i.key_pressed(Key::ArrowUp)) {
+                    state.cycle_template(-1);
+                    return true;
+                }
+            }
+            match state.selected_template() {
+                Some(template) if !state.name.ends_with('/') => (
+                    templates::target_name(&state.name, template),
+                    Some(template.clone()),
+                ),
+                _ => (state.name.clone(), None),
+            }
+        }
         _ => return false, // Not in add mode, let other handlers run
     };
 
@@ -77,6 +216,15 @@ pub(crate) fn handle_key_press(ctx: &Context, app: &mut Kiorg) -> bool {
                 return true; // Input handled
             }
 
+            let from_clipboard = matches!(
+                &app.show_popup,
+                Some(PopupType::AddEntry(state)) if state.clipboard.is_some()
+            );
+            if from_clipboard && entry_name.ends_with('/') {
+                app.notify_error("The clipboard content can only be saved as a file");
+                return true;
+            }
+
             let (result, is_dir) = if entry_name.ends_with('/') {
                 // Create directory
                 // Ensure parent directories exist before creating the final one
@@ -99,7 +247,18 @@ pub(crate) fn handle_key_press(ctx: &Context, app: &mut Kiorg) -> bool {
                     // Decide how to handle this error, maybe return early?
                     // For now, we'll proceed and let File::create handle the final error.
                 }
-                (fs::File::create(&new_path).map(|_| ()), false) // Discard the File handle
+                let result = match (&app.show_popup, &template) {
+                    (
+                        Some(PopupType::AddEntry(AddEntryState {
+                            clipboard: Some(content),
+                            ..
+                        })),
+                        _,
+                    ) => fs::write(&new_path, content.bytes()),
+                    (_, Some(template)) => templates::create_from(template, &new_path),
+                    (_, None) => fs::File::create(&new_path).map(|_| ()), // Discard the File handle
+                };
+                (result, false)
             };
 
             match result {
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use super::PopupType;
+use super::text_input_popup::{
+    TextInputConfig, TextSelection, clear_init_flag, draw as draw_text_input,
+};
+use crate::app::Kiorg;
+
+const ARCHIVE_PASSWORD_POPUP_ID: &str = "archive_password_popup";
+
+/// Action to resume once the archive password is entered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordPurpose {
+    /// Go back to the preview popup and decrypt the selected entry
+    Preview,
+    /// Start the archive integrity test
+    Test,
+}
+
+/// State of the archive password prompt
+#[derive(Debug, Clone)]
+pub struct ArchivePasswordState {
+    pub archive_path: PathBuf,
+    pub password: String,
+    pub purpose: PasswordPurpose,
+}
+
+impl ArchivePasswordState {
+    pub fn new(archive_path: PathBuf, purpose: PasswordPurpose) -> Self {
+        Self {
+            archive_path,
+            password: String::new(),
+            purpose,
+        }
+    }
+}
+
+/// Draw the archive password popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::ArchivePassword(state)) = &mut app.show_popup else {
+        return;
+    };
+
+    let hint = format!(
+        "Enter password for {}...",
+        state
+            .archive_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+    );
+    let config = TextInputConfig {
+        title: "Archive Password",
+        hint: &hint,
+        initial_selection: TextSelection::None,
+        password: true,
+    };
+
+    let keep_open = draw_text_input(
+        ctx,
+        &app.colors,
+        &config,
+        &mut state.password,
+        ARCHIVE_PASSWORD_POPUP_ID,
+    );
+    if !keep_open {
+        handle_cancel(app, ctx);
+    }
+}
+
+/// Cache the entered password for the session and resume the pending action
+pub fn handle_confirm(app: &mut Kiorg, ctx: &egui::Context) {
+    let Some(PopupType::ArchivePassword(state)) = &app.show_popup else {
+        return;
+    };
+    if state.password.is_empty() {
+        return;
+    }
+    let state = state.clone();
+    clear_init_flag(ctx, ARCHIVE_PASSWORD_POPUP_ID);
+
+    crate::utils::archive::cache_password(&state.archive_path, state.password);
+    match state.purpose {
+        PasswordPurpose::Preview => app.show_popup = Some(PopupType::Preview),
+        PasswordPurpose::Test => {
+            app.show_popup = None;
+            super::archive_test::start_test(app, state.archive_path);
+        }
+    }
+}
+
+pub fn handle_cancel(app: &mut Kiorg, ctx: &egui::Context) {
+    clear_init_flag(ctx, ARCHIVE_PASSWORD_POPUP_ID);
+    app.show_popup = match &app.show_popup {
+        Some(PopupType::ArchivePassword(state)) if state.purpose == PasswordPurpose::Preview => {
+            Some(PopupType::Preview)
+        }
+        _ => None,
+    };
+}
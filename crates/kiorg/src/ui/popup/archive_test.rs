@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use super::PopupType;
+use super::archive_password::{ArchivePasswordState, PasswordPurpose};
+use crate::app::Kiorg;
+use crate::utils::archive::{ArchiveFormat, ArchiveTestReport, MemberReadError};
+use crate::utils::format::format_count;
+use crate::utils::transfer::TransferSummary;
+
+/// Maximum number of corrupt members listed in the result popup
+const MAX_CORRUPT_MEMBERS_SHOWN: usize = 20;
+
+/// Whether the "Test archive" action applies to the given path
+#[must_use]
+pub fn can_test(path: &std::path::Path) -> bool {
+    path.is_file() && ArchiveFormat::from_path(path).is_some()
+}
+
+/// Start testing the currently selected archive in the background
+pub fn start_test_selected(app: &mut Kiorg) {
+    let path = {
+        let tab = app.tab_manager.current_tab_ref();
+        match tab.selected_entry() {
+            Some(entry) => entry.meta.path.clone(),
+            None => return,
+        }
+    };
+
+    if !can_test(&path) {
+        app.notify_error(format!(
+            "Not a supported archive: {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        return;
+    }
+
+    // Ask for the password first instead of reporting every member as unreadable
+    if ArchiveFormat::from_path(&path) == Some(ArchiveFormat::Zip)
+        && crate::utils::archive::cached_password(&path).is_none()
+        && std::fs::File::open(&path)
+            .map_err(|e| e.to_string())
+            .and_then(crate::utils::archive::zip_is_encrypted)
+            .unwrap_or(false)
+    {
+        app.show_popup = Some(PopupType::ArchivePassword(ArchivePasswordState::new(
+            path,
+            PasswordPurpose::Test,
+        )));
+        return;
+    }
+
+    start_test(app, path);
+}
+
+/// Queue testing an archive in the background, using the session password if
+/// any
+pub fn start_test(app: &mut Kiorg, path: PathBuf) {
+    app.transfers.enqueue_test(path);
+}
+
+fn format_report(archive_name: &str, report: &ArchiveTestReport) -> String {
+    let mut message = format!(
+        "{archive_name}: {} of {} members failed verification\n",
+        report.corrupt.len(),
+        report.tested
+    );
+    for member in report.corrupt.iter().take(MAX_CORRUPT_MEMBERS_SHOWN) {
+        message.push_str(&format!("\n{}: {}", member.name, member.error));
+    }
+    if report.corrupt.len() > MAX_CORRUPT_MEMBERS_SHOWN {
+        message.push_str(&format!(
+            "\n...and {} more",
+            report.corrupt.len() - MAX_CORRUPT_MEMBERS_SHOWN
+        ));
+    }
+    message
+}
+
+/// Report the outcome of a finished test job
+pub fn report(app: &mut Kiorg, summary: TransferSummary) {
+    let Some(path) = summary.sources.first() else {
+        return;
+    };
+    let archive_name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    for error in summary.errors {
+        app.notify_error(format!("{archive_name}: {error}"));
+    }
+    let Some(report) = summary.test_report else {
+        return;
+    };
+
+    // Don't keep a wrong password around for the next attempt
+    let invalid_password = MemberReadError::InvalidPassword.to_string();
+    if report.corrupt.iter().any(|m| m.error == invalid_password) {
+        crate::utils::archive::forget_password(path);
+    }
+
+    if !report.is_ok() {
+        let message = format_report(&archive_name, &report);
+        // The test ran in the background, don't replace another popup
+        if app.show_popup.is_none() {
+            app.show_popup = Some(PopupType::GenericMessage(
+                "Archive Test Failed".to_string(),
+                message,
+            ));
+        } else {
+            app.notify_error(message);
+        }
+    } else if summary.cancelled {
+        app.notify_info(format!(
+            "{archive_name}: test cancelled after {} members",
+            format_count(report.tested as u64)
+        ));
+    } else {
+        app.notify_success(format!(
+            "{archive_name}: all {} members OK",
+            format_count(report.tested as u64)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::archive::CorruptMember;
+
+    #[test]
+    fn test_format_report_truncates_list() {
+        let report = ArchiveTestReport {
+            tested: 30,
+            corrupt: (0..25)
+                .map(|i| CorruptMember {
+                    name: format!("file{i}"),
+                    error: "Invalid checksum".to_string(),
+                })
+                .collect(),
+        };
+        let message = format_report("a.zip", &report);
+        assert!(message.starts_with("a.zip: 25 of 30 members failed verification"));
+        assert!(message.contains("file19: Invalid checksum"));
+        assert!(!message.contains("file20:"));
+        assert!(message.ends_with("...and 5 more"));
+    }
+}
@@ -1,14 +1,17 @@
-use egui::Context;
+use egui::{Context, Frame, RichText, TextEdit};
 use std::error::Error;
+use std::fmt::Write;
 use std::fs;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf; // Removed unused Path
 
 use super::PopupType;
-use super::window_utils::show_center_popup_window;
+use super::window_utils::{new_center_popup_window, show_center_popup_window};
 use crate::app::Kiorg;
 use crate::config::get_kiorg_config_dir;
 use crate::config::shortcuts::ShortcutAction;
+use crate::models::bookmark::{self, Bookmark};
+use crate::utils::cloud::CloudFolder;
 
 // Get the full path to the bookmarks file
 fn get_bookmarks_file_path(config_dir_override: Option<&std::path::Path>) -> PathBuf {
@@ -24,7 +27,7 @@ fn get_bookmarks_file_path(config_dir_override: Option<&std::path::Path>) -> Pat
 
 // Save bookmarks to the config file
 pub fn save_bookmarks(
-    bookmarks: &[PathBuf],
+    bookmarks: &[Bookmark],
     config_dir_override: Option<&std::path::Path>,
 ) -> Result<(), Box<dyn Error>> {
     let bookmarks_file = get_bookmarks_file_path(config_dir_override);
@@ -34,17 +37,17 @@ pub fn save_bookmarks(
     {
         fs::create_dir_all(parent_dir)?;
     }
-    let mut file = fs::File::create(bookmarks_file)?;
-
+    let mut content = String::new();
     for bookmark in bookmarks {
-        writeln!(file, "{}", bookmark.to_string_lossy())?;
+        writeln!(content, "{}", bookmark.to_line())?;
     }
+    crate::utils::atomic_write::write(&bookmarks_file, content)?;
 
     Ok(())
 }
 
 // Load bookmarks from the config file
-pub fn load_bookmarks(config_dir_override: Option<&std::path::Path>) -> Vec<PathBuf> {
+pub fn load_bookmarks(config_dir_override: Option<&std::path::Path>) -> Vec<Bookmark> {
     let bookmarks_file = get_bookmarks_file_path(config_dir_override);
     if !bookmarks_file.exists() {
         return Vec::new();
@@ -56,8 +59,7 @@ pub fn load_bookmarks(config_dir_override: Option<&std::path::Path>) -> Vec<Path
             reader
                 .lines()
                 .map_while(Result::ok)
-                .filter(|line| !line.trim().is_empty())
-                .map(|line| PathBuf::from(line.trim()))
+                .filter_map(|line| Bookmark::from_line(&line))
                 .collect()
         }
         // Return empty vec on any error during file opening or reading
@@ -73,10 +75,76 @@ pub enum BookmarkAction {
     None,
 }
 
+/// Open the bookmarks popup with the cloud folders mounted right now
+pub fn open(app: &mut Kiorg) {
+    app.cloud_folders = crate::utils::cloud::detect();
+    app.show_popup = Some(PopupType::Bookmarks(0));
+}
+
+/// Path of the row at `index`, bookmarks are listed before cloud folders
+fn path_at(app: &Kiorg, index: usize) -> Option<PathBuf> {
+    app.bookmarks
+        .get(index)
+        .map(|b| b.path.clone())
+        .or_else(|| {
+            let index = index.checked_sub(app.bookmarks.len())?;
+            app.cloud_folders
+                .get(index)
+                .map(|folder| folder.path.clone())
+        })
+}
+
+/// Display the detected cloud folders below the bookmarks
+fn display_cloud_grid(
+    ui: &mut egui::Ui,
+    folders: &[CloudFolder],
+    selected_index: Option<usize>,
+    colors: &crate::config::colors::AppColors,
+) -> Option<PathBuf> {
+    let mut navigate_to_path = None;
+    let bg_selected = colors.bg_selected;
+
+    egui::Grid::new("cloud_folders_grid")
+        .num_columns(2)
+        .spacing([20.0, 2.0])
+        .with_row_color(move |i, _| (Some(i) == selected_index).then_some(bg_selected))
+        .show(ui, |ui| {
+            for (i, folder) in folders.iter().enumerate() {
+                let name_response = ui.colored_label(
+                    colors.fg_folder,
+                    format!("{} {}", folder.provider.icon(), folder.label),
+                );
+                let path_color = if Some(i) == selected_index {
+                    colors.fg_selected
+                } else {
+                    colors.fg_light
+                };
+                let path_response = ui.horizontal(|ui| {
+                    let response = ui.colored_label(path_color, folder.path.to_string_lossy());
+                    if folder.online_only {
+                        ui.colored_label(colors.fg_light, "☁")
+                            .on_hover_text("Files may be online-only until they are opened");
+                    }
+                    response
+                });
+                ui.end_row();
+
+                let combined_response = name_response
+                    .union(path_response.inner)
+                    .on_hover_cursor(egui::CursorIcon::PointingHand);
+                if combined_response.clicked() {
+                    navigate_to_path = Some(folder.path.clone());
+                }
+            }
+        });
+
+    navigate_to_path
+}
+
 /// Helper function to display bookmarks in a grid layout
 fn display_bookmarks_grid(
     ui: &mut egui::Ui,
-    bookmarks: &[PathBuf],
+    bookmarks: &[Bookmark],
     selected_index: usize,
     colors: &crate::config::colors::AppColors,
 ) -> (Option<PathBuf>, Option<PathBuf>) {
@@ -85,7 +153,7 @@ fn display_bookmarks_grid(
     let bg_selected = colors.bg_selected;
 
     egui::Grid::new("bookmarks_grid")
-        .num_columns(2)
+        .num_columns(3)
         .spacing([20.0, 2.0]) // 20px horizontal spacing, 2px vertical spacing
         .with_row_color(move |i, _| {
             if i == selected_index {
@@ -96,35 +164,42 @@ fn display_bookmarks_grid(
         })
         .show(ui, |ui| {
             for (i, bookmark) in bookmarks.iter().enumerate() {
-                // Extract folder name and parent path
-                let folder_name = bookmark
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default();
-
-                let parent_path = bookmark
-                    .parent()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_default();
+                // Labeled bookmarks show their full path, others the parent path
+                let location = if bookmark.label.is_some() {
+                    bookmark.path.to_string_lossy().to_string()
+                } else {
+                    bookmark
+                        .path
+                        .parent()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                };
 
                 let is_selected = i == selected_index;
 
-                // Column 1: Folder name
-                let folder_response = ui.colored_label(colors.fg_folder, &folder_name);
+                // Column 1: Quick-jump key
+                let key = bookmark
+                    .key
+                    .map(|key| format!("'{key}"))
+                    .unwrap_or_default();
+                let key_response = ui.label(RichText::new(key).monospace().color(colors.highlight));
+
+                // Column 2: Label or folder name
+                let folder_response = ui.colored_label(colors.fg_folder, bookmark.name());
 
-                // Column 2: Parent path
+                // Column 3: Location
                 let path_color = if is_selected {
                     colors.fg_selected
                 } else {
                     colors.fg_light
                 };
 
-                let path_response = ui.colored_label(path_color, &parent_path);
+                let path_response = ui.colored_label(path_color, &location);
 
                 ui.end_row();
 
                 // Combine responses for unified row clicking
-                let combined_response = folder_response.union(path_response);
+                let combined_response = key_response.union(folder_response).union(path_response);
 
                 // Show clickable hand cursor on hover and handle clicks
                 let combined_response = if combined_response.hovered() {
@@ -135,13 +210,13 @@ fn display_bookmarks_grid(
 
                 // Handle row click for navigation
                 if combined_response.clicked() {
-                    navigate_to_path = Some(bookmark.clone());
+                    navigate_to_path = Some(bookmark.path.clone());
                 }
 
                 // Right-click context menu for the entire row
                 combined_response.context_menu(|ui| {
                     if ui.button("Remove bookmark").clicked() {
-                        remove_bookmark_path = Some(bookmark.clone());
+                        remove_bookmark_path = Some(bookmark.path.clone());
                         ui.close();
                     }
                 });
@@ -158,11 +233,8 @@ pub fn show_bookmark_popup(ctx: &Context, app: &mut Kiorg) -> BookmarkAction {
         _ => return BookmarkAction::None,
     };
 
-    let mut current_index = if app.bookmarks.is_empty() {
-        0
-    } else {
-        current_index.min(app.bookmarks.len() - 1)
-    };
+    let row_count = app.bookmarks.len() + app.cloud_folders.len();
+    let mut current_index = current_index.min(row_count.saturating_sub(1));
 
     // Handle keyboard navigation using shortcuts
 
@@ -177,8 +249,12 @@ pub fn show_bookmark_popup(ctx: &Context, app: &mut Kiorg) -> BookmarkAction {
                 app.show_popup = None;
                 return BookmarkAction::None;
             }
-            ShortcutAction::DeleteEntry if !app.bookmarks.is_empty() => {
-                remove_bookmark_path = Some(app.bookmarks[current_index].clone());
+            ShortcutAction::DeleteEntry if current_index < app.bookmarks.len() => {
+                remove_bookmark_path = Some(app.bookmarks[current_index].path.clone());
+            }
+            ShortcutAction::RenameEntry if current_index < app.bookmarks.len() => {
+                open_edit(app, current_index);
+                return BookmarkAction::None;
             }
             _ => {} // Other actions will be handled below in the window
         }
@@ -190,7 +266,7 @@ pub fn show_bookmark_popup(ctx: &Context, app: &mut Kiorg) -> BookmarkAction {
     let mut window_open = true;
 
     if let Some(response) = show_center_popup_window("Bookmarks", ctx, &mut window_open, |ui| {
-        if app.bookmarks.is_empty() {
+        if row_count == 0 {
             ui.label("No bookmarks yet. Use 'b' to bookmark folders.");
             return;
         }
@@ -199,16 +275,14 @@ pub fn show_bookmark_popup(ctx: &Context, app: &mut Kiorg) -> BookmarkAction {
         let action = app.get_shortcut_action_from_input(ctx);
         if let Some(action) = action {
             match action {
-                ShortcutAction::MoveDown if !app.bookmarks.is_empty() => {
-                    current_index = (current_index + 1).min(app.bookmarks.len() - 1);
+                ShortcutAction::MoveDown => {
+                    current_index = (current_index + 1).min(row_count - 1);
                 }
                 ShortcutAction::MoveUp => {
                     current_index = current_index.saturating_sub(1);
                 }
-                ShortcutAction::OpenDirectoryOrFile | ShortcutAction::OpenDirectory
-                    if !app.bookmarks.is_empty() =>
-                {
-                    navigate_to_path = Some(app.bookmarks[current_index].clone());
+                ShortcutAction::OpenDirectoryOrFile | ShortcutAction::OpenDirectory => {
+                    navigate_to_path = path_at(app, current_index);
                 }
                 _ => {} // Other actions already handled above
             }
@@ -224,6 +298,28 @@ pub fn show_bookmark_popup(ctx: &Context, app: &mut Kiorg) -> BookmarkAction {
             if let Some(path) = context_menu_remove {
                 remove_bookmark_path = Some(path);
             }
+            if !app.bookmarks.is_empty() {
+                ui.add_space(4.0);
+                ui.label(
+                    RichText::new("r to set a label and a key to jump with '")
+                        .small()
+                        .color(app.colors.fg_light),
+                );
+            }
+
+            // Cloud folders are pinned automatically and can't be removed
+            if !app.cloud_folders.is_empty() {
+                if !app.bookmarks.is_empty() {
+                    ui.add_space(8.0);
+                }
+                ui.label(crate::ui::style::section_title_text("Cloud", &app.colors));
+                let selected = current_index.checked_sub(app.bookmarks.len());
+                if let Some(path) =
+                    display_cloud_grid(ui, &app.cloud_folders, selected, &app.colors)
+                {
+                    navigate_to_path = Some(path);
+                }
+            }
         });
     }) {
         // Return appropriate action based on what happened
@@ -236,7 +332,7 @@ pub fn show_bookmark_popup(ctx: &Context, app: &mut Kiorg) -> BookmarkAction {
         } else {
             // If we need to remove a bookmark, do it now
             if let Some(path) = remove_bookmark_path {
-                app.bookmarks.retain(|p| p != &path);
+                app.bookmarks.retain(|b| b.path != path);
                 action = BookmarkAction::SaveBookmarks;
             }
 
@@ -271,10 +367,10 @@ pub fn toggle_bookmark(app: &mut Kiorg) {
         let path = selected_entry.meta.path.clone();
 
         // Toggle bookmark status
-        if bookmarks.contains(&path) {
-            bookmarks.retain(|p| p != &path);
+        if bookmark::is_bookmarked(bookmarks, &path) {
+            bookmarks.retain(|b| b.path != path);
         } else {
-            bookmarks.push(path);
+            bookmarks.push(Bookmark::new(path));
         }
 
         // Save bookmarks to config file
@@ -285,3 +381,136 @@ pub fn toggle_bookmark(app: &mut Kiorg) {
         app.notify_error("Bookmarks can only be applied to directories, not files".to_string());
     }
 }
+
+/// State of the form editing a bookmark's label and key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookmarkEditState {
+    /// Index of the bookmark in the bookmarks list
+    pub index: usize,
+    pub label: String,
+    pub key: String,
+}
+
+/// Edit the label and key of the bookmark at `index`
+pub fn open_edit(app: &mut Kiorg, index: usize) {
+    let Some(bookmark) = app.bookmarks.get(index) else {
+        return;
+    };
+    app.show_popup = Some(PopupType::EditBookmark(BookmarkEditState {
+        index,
+        label: bookmark.label.clone().unwrap_or_default(),
+        key: bookmark.key.map(String::from).unwrap_or_default(),
+    }));
+}
+
+/// Go back to the bookmarks list without saving the edit
+pub fn cancel_edit(app: &mut Kiorg) {
+    if let Some(PopupType::EditBookmark(state)) = &app.show_popup {
+        app.show_popup = Some(PopupType::Bookmarks(state.index));
+    }
+}
+
+/// Save the edited label and key, keeping the form open if the key is invalid
+pub fn confirm_edit(app: &mut Kiorg) {
+    let Some(PopupType::EditBookmark(state)) = &app.show_popup else {
+        return;
+    };
+    let index = state.index;
+    let label = Some(state.label.trim().to_string()).filter(|label| !label.is_empty());
+    let key_text = state.key.trim().to_lowercase();
+    let mut chars = key_text.chars();
+    let key = match (chars.next(), chars.next()) {
+        (None, _) => None,
+        (Some(key), None) if Bookmark::is_valid_key(key) => Some(key),
+        _ => {
+            app.notify_error("The key must be a single letter");
+            return;
+        }
+    };
+    if let Some(key) = key
+        && let Some(other) = bookmark::find_by_key(&app.bookmarks, key)
+        && app.bookmarks.get(index) != Some(other)
+    {
+        let message = format!("Key '{key}' is already used by {}", other.name());
+        app.notify_error(message);
+        return;
+    }
+    let Some(bookmark) = app.bookmarks.get_mut(index) else {
+        app.show_popup = None;
+        return;
+    };
+    bookmark.label = label;
+    bookmark.key = key;
+    app.show_popup = Some(PopupType::Bookmarks(index));
+    if let Err(e) = save_bookmarks(&app.bookmarks, app.config_dir_override.as_deref()) {
+        app.notify_error(format!("Failed to save bookmarks: {e}"));
+    }
+}
+
+/// Draw the form editing a bookmark's label and key
+pub fn draw_edit(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::EditBookmark(state)) = &mut app.show_popup else {
+        return;
+    };
+    let Some(bookmark) = app.bookmarks.get(state.index) else {
+        app.show_popup = None;
+        return;
+    };
+    let mut keep_open = true;
+    new_center_popup_window("Edit Bookmark")
+        .open(&mut keep_open)
+        .show(ctx, |ui| {
+            ui.set_max_width(400.0);
+            ui.label(RichText::new(bookmark.path.to_string_lossy()).color(app.colors.fg_light));
+            ui.add_space(4.0);
+            egui::Grid::new("edit_bookmark_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Label");
+                    let label_response = Frame::default()
+                        .fill(app.colors.bg_extreme)
+                        .inner_margin(4.0)
+                        .show(ui, |ui| {
+                            ui.add(
+                                TextEdit::singleline(&mut state.label)
+                                    .hint_text(bookmark.name().into_owned())
+                                    .desired_width(300.0)
+                                    .frame(Frame::NONE),
+                            )
+                        })
+                        .inner;
+                    ui.end_row();
+
+                    ui.label("Key");
+                    Frame::default()
+                        .fill(app.colors.bg_extreme)
+                        .inner_margin(4.0)
+                        .show(ui, |ui| {
+                            ui.add(
+                                TextEdit::singleline(&mut state.key)
+                                    .hint_text("a-z")
+                                    .char_limit(1)
+                                    .desired_width(300.0)
+                                    .frame(Frame::NONE),
+                            );
+                        });
+                    ui.end_row();
+
+                    // Start in the label unless the key is being edited
+                    if ui.memory(|m| m.focused().is_none()) {
+                        label_response.request_focus();
+                    }
+                });
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Enter to save, Escape to cancel, ' and the key jumps to it")
+                    .small()
+                    .color(app.colors.fg_light),
+            );
+        });
+
+    if !keep_open {
+        cancel_edit(app);
+    }
+}
@@ -0,0 +1,309 @@
+use egui::{Context, ProgressBar};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use super::PopupType;
+use super::window_utils::new_center_popup_window;
+use crate::app::Kiorg;
+use crate::ui::style::section_title_text;
+use crate::utils::checksum::{self, VerifyReport};
+use crate::utils::format::{format_count, format_size};
+use crate::utils::hashing::{HashAlgorithm, HashProgress};
+
+/// Maximum number of failed files listed in the result popup
+const MAX_FAILURES_SHOWN: usize = 20;
+
+/// Progress data of a checksum generation or verification
+pub struct ChecksumProgressData {
+    pub title: String,
+    pub progress: HashProgress,
+    pub receiver: mpsc::Receiver<ChecksumUpdate>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for ChecksumProgressData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChecksumProgressData")
+            .field("title", &self.title)
+            .field("progress", &self.progress)
+            .field("receiver", &"<receiver>")
+            .finish()
+    }
+}
+
+/// Progress update message sent from background thread
+#[derive(Debug, Clone)]
+pub enum ChecksumUpdate {
+    Progress(HashProgress),
+    Generated {
+        written: Vec<PathBuf>,
+        errors: Vec<String>,
+    },
+    Verified {
+        checksum_file: PathBuf,
+        report: VerifyReport,
+    },
+    Error(String),
+    Cancelled,
+}
+
+/// Start a background job, the popup shows its progress
+fn start(
+    app: &mut Kiorg,
+    ctx: &Context,
+    title: String,
+    job: impl FnOnce(&AtomicBool, &(dyn Fn(HashProgress) + Sync)) -> Option<ChecksumUpdate>
+    + Send
+    + 'static,
+) {
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    app.show_popup = Some(PopupType::Checksum(ChecksumProgressData {
+        title,
+        progress: HashProgress::default(),
+        receiver: rx,
+        cancel: cancel.clone(),
+    }));
+
+    let ctx = ctx.clone();
+    thread::spawn(move || {
+        let progress_tx = tx.clone();
+        let progress_ctx = ctx.clone();
+        let on_progress = move |progress| {
+            let _ = progress_tx.send(ChecksumUpdate::Progress(progress));
+            progress_ctx.request_repaint();
+        };
+        let update = job(&cancel, &on_progress).unwrap_or(ChecksumUpdate::Cancelled);
+        let _ = tx.send(update);
+        ctx.request_repaint();
+    });
+}
+
+/// Write a sidecar checksum file for the marked files, or the selected file
+pub fn start_generate_selected(app: &mut Kiorg, ctx: &Context, algorithm: HashAlgorithm) {
+    let tab = app.tab_manager.current_tab_ref();
+    let mut paths: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+    if paths.is_empty() {
+        paths.extend(tab.selected_entry().map(|entry| entry.meta.path.clone()));
+    }
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    if paths.is_empty() {
+        app.notify_error("Checksum files can only be generated for files");
+        return;
+    }
+
+    let title = format!("Generating {} Checksums", algorithm.name());
+    start(app, ctx, title, move |cancel, on_progress| {
+        let (written, errors) =
+            checksum::generate_sidecars(&paths, algorithm, cancel, on_progress)?;
+        Some(ChecksumUpdate::Generated { written, errors })
+    });
+}
+
+/// The checksum file to verify: the selected one, or the one in the selected
+/// or current directory
+fn checksum_file_to_verify(app: &Kiorg) -> Option<PathBuf> {
+    let tab = app.tab_manager.current_tab_ref();
+    if let Some(entry) = tab.selected_entry() {
+        let path = &entry.meta.path;
+        if path.is_file() && checksum::is_checksum_file(path) {
+            return Some(path.clone());
+        }
+        if path.is_dir()
+            && let Some(file) = checksum::find_checksum_file(path)
+        {
+            return Some(file);
+        }
+    }
+    checksum::find_checksum_file(&tab.current_path)
+}
+
+/// Verify files against the selected checksum file
+pub fn start_verify_selected(app: &mut Kiorg, ctx: &Context) {
+    let Some(checksum_file) = checksum_file_to_verify(app) else {
+        app.notify_error("No checksum file found to verify against");
+        return;
+    };
+
+    start(
+        app,
+        ctx,
+        "Verifying Checksums".to_string(),
+        move |cancel, on_progress| {
+            Some(
+                match checksum::verify(&checksum_file, cancel, on_progress)? {
+                    Ok(report) => ChecksumUpdate::Verified {
+                        checksum_file,
+                        report,
+                    },
+                    Err(e) => ChecksumUpdate::Error(e),
+                },
+            )
+        },
+    );
+}
+
+/// Stop the running job, the popup closes once the workers noticed
+pub fn cancel(app: &mut Kiorg) {
+    if let Some(PopupType::Checksum(data)) = &app.show_popup {
+        data.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+fn format_report(checksum_name: &str, report: &VerifyReport) -> String {
+    let mut message = format!(
+        "{checksum_name}: {} of {} files failed verification\n",
+        report.failures.len(),
+        report.checked
+    );
+    for (name, failure) in report.failures.iter().take(MAX_FAILURES_SHOWN) {
+        message.push_str(&format!("\n{name}: {failure}"));
+    }
+    if report.failures.len() > MAX_FAILURES_SHOWN {
+        message.push_str(&format!(
+            "\n...and {} more",
+            report.failures.len() - MAX_FAILURES_SHOWN
+        ));
+    }
+    message
+}
+
+fn finish(app: &mut Kiorg, update: ChecksumUpdate) {
+    app.show_popup = None;
+    match update {
+        ChecksumUpdate::Generated { written, errors } => {
+            if !written.is_empty() {
+                app.refresh_entries();
+            }
+            if errors.is_empty() {
+                app.notify_success(format!("Created {} checksum files", written.len()));
+            } else {
+                let mut message = format!(
+                    "Created {} checksum files, {} failed\n",
+                    written.len(),
+                    errors.len()
+                );
+                for error in errors.iter().take(MAX_FAILURES_SHOWN) {
+                    message.push_str(&format!("\n{error}"));
+                }
+                app.show_popup = Some(PopupType::GenericMessage(
+                    "Checksum Generation Failed".to_string(),
+                    message,
+                ));
+            }
+        }
+        ChecksumUpdate::Verified {
+            checksum_file,
+            report,
+        } => {
+            let checksum_name = checksum_file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if report.is_ok() {
+                app.notify_success(format!("{checksum_name}: all {} files OK", report.checked));
+            } else {
+                app.show_popup = Some(PopupType::GenericMessage(
+                    "Checksum Verification Failed".to_string(),
+                    format_report(&checksum_name, &report),
+                ));
+            }
+        }
+        ChecksumUpdate::Error(error) => app.notify_error(error),
+        ChecksumUpdate::Cancelled => app.notify_info("Checksum computation cancelled"),
+        ChecksumUpdate::Progress(_) => {}
+    }
+}
+
+/// Handle progress popup UI
+pub fn handle_checksum_progress(ctx: &Context, app: &mut Kiorg) {
+    let mut finished = None;
+
+    if let Some(PopupType::Checksum(ref mut data)) = app.show_popup {
+        while let Ok(update) = data.receiver.try_recv() {
+            match update {
+                ChecksumUpdate::Progress(progress) => {
+                    // Reports of different workers may arrive out of order
+                    if progress.bytes_done >= data.progress.bytes_done {
+                        data.progress = progress;
+                    }
+                }
+                update => finished = Some(update),
+            }
+        }
+    }
+
+    if let Some(update) = finished {
+        finish(app, update);
+        return;
+    }
+
+    let Some(PopupType::Checksum(ref data)) = app.show_popup else {
+        return;
+    };
+    let progress = data.progress;
+    let cancelling = data.cancel.load(Ordering::Relaxed);
+    let mut cancel_clicked = false;
+    new_center_popup_window(&data.title).show(ctx, |ui| {
+        ui.set_min_width(400.0);
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(10.0);
+
+            let fraction = if progress.total_bytes > 0 {
+                progress.bytes_done as f32 / progress.total_bytes as f32
+            } else {
+                0.0
+            };
+            ui.add(ProgressBar::new(fraction).desired_width(350.0));
+            ui.add_space(10.0);
+            ui.label(format!(
+                "{} / {} files, {} / {}",
+                format_count(progress.files_done as u64),
+                format_count(progress.total_files as u64),
+                format_size(progress.bytes_done, false),
+                format_size(progress.total_bytes, false),
+            ));
+
+            ui.add_space(10.0);
+            if cancelling {
+                ui.label("Cancelling...");
+            } else if ui
+                .link(section_title_text("Press Esc to cancel", &app.colors))
+                .clicked()
+            {
+                cancel_clicked = true;
+            }
+            ui.add_space(5.0);
+        });
+    });
+
+    if cancel_clicked {
+        cancel(app);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::checksum::VerifyFailure;
+
+    #[test]
+    fn test_format_report_truncates_list() {
+        let report = VerifyReport {
+            checked: 30,
+            failures: (0..25)
+                .map(|i| (format!("file{i}"), VerifyFailure::Mismatch))
+                .collect(),
+        };
+        let message = format_report("SHA256SUMS", &report);
+        assert!(message.starts_with("SHA256SUMS: 25 of 30 files failed verification"));
+        assert!(message.contains("file19: checksum mismatch"));
+        assert!(!message.contains("file20:"));
+        assert!(message.ends_with("...and 5 more"));
+    }
+}
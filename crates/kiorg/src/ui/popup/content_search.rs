@@ -0,0 +1,319 @@
+use egui::text::LayoutJob;
+use egui::{Align, Context, FontId, Frame, RichText, TextEdit, TextFormat};
+use regex::Regex;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use super::PopupType;
+use super::window_utils::new_center_popup_window;
+use crate::app::Kiorg;
+use crate::config::colors::AppColors;
+use crate::ui::preview::links;
+use crate::utils::format::format_count;
+use crate::utils::grep::{self, GrepMatch};
+
+/// Update message sent from the searching thread
+#[derive(Debug)]
+enum GrepUpdate {
+    Matches(Vec<GrepMatch>),
+    Finished { truncated: bool },
+}
+
+/// Search running or done, dropped to cancel it
+struct Search {
+    matcher: Regex,
+    receiver: mpsc::Receiver<GrepUpdate>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Drop for Search {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// State of the content search popup
+pub struct ContentSearchState {
+    /// Directory searched recursively
+    pub root: PathBuf,
+    pub pattern: String,
+    /// Take the pattern as a regular expression instead of literal text
+    pub regex: bool,
+    pub case_insensitive: bool,
+    /// Pattern and options of the last search, Enter starts a new search
+    /// once they changed
+    searched: Option<(String, bool, bool)>,
+    pub results: Vec<GrepMatch>,
+    pub selected: usize,
+    pub searching: bool,
+    pub truncated: bool,
+    scroll_to_selected: bool,
+    search: Option<Search>,
+}
+
+impl std::fmt::Debug for ContentSearchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContentSearchState")
+            .field("root", &self.root)
+            .field("pattern", &self.pattern)
+            .field("results", &self.results.len())
+            .field("selected", &self.selected)
+            .field("searching", &self.searching)
+            .finish()
+    }
+}
+
+/// Open the content search popup for the current directory
+pub fn open(app: &mut Kiorg, pattern: String) {
+    let root = app.tab_manager.current_tab_ref().current_path.clone();
+    app.show_popup = Some(PopupType::ContentSearch(Box::new(ContentSearchState {
+        root,
+        pattern,
+        regex: false,
+        case_insensitive: app.search_bar.case_insensitive,
+        searched: None,
+        results: Vec::new(),
+        selected: 0,
+        searching: false,
+        truncated: false,
+        scroll_to_selected: false,
+        search: None,
+    })));
+}
+
+/// Start searching for the pattern, or jump to the selected match if it was
+/// already searched for
+pub fn confirm(app: &mut Kiorg, ctx: &Context) {
+    let Some(PopupType::ContentSearch(state)) = &mut app.show_popup else {
+        return;
+    };
+    let options = (state.pattern.clone(), state.regex, state.case_insensitive);
+    if state.searched.as_ref() == Some(&options) {
+        jump_to_selected(app);
+        return;
+    }
+    if state.pattern.is_empty() {
+        return;
+    }
+    let matcher = match grep::build_matcher(&state.pattern, state.regex, state.case_insensitive) {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            app.notify_error(format!("Invalid pattern: {e}"));
+            return;
+        }
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let root = state.root.clone();
+        let matcher = matcher.clone();
+        let cancel = cancel.clone();
+        let show_hidden = app.tab_manager.show_hidden;
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let matches_tx = tx.clone();
+            let matches_ctx = ctx.clone();
+            let truncated = grep::search(&root, &matcher, show_hidden, &cancel, |matches| {
+                let _ = matches_tx.send(GrepUpdate::Matches(matches));
+                matches_ctx.request_repaint();
+            });
+            let _ = tx.send(GrepUpdate::Finished { truncated });
+            ctx.request_repaint();
+        });
+    }
+    state.searched = Some(options);
+    state.results.clear();
+    state.selected = 0;
+    state.searching = true;
+    state.truncated = false;
+    state.search = Some(Search {
+        matcher,
+        receiver: rx,
+        cancel,
+    });
+}
+
+/// Move the selection through the matches
+pub fn move_selection(app: &mut Kiorg, delta: isize) {
+    let Some(PopupType::ContentSearch(state)) = &mut app.show_popup else {
+        return;
+    };
+    if state.results.is_empty() {
+        return;
+    }
+    state.selected = state
+        .selected
+        .saturating_add_signed(delta)
+        .min(state.results.len() - 1);
+    state.scroll_to_selected = true;
+}
+
+/// Close the popup and select the file of the selected match
+pub fn jump_to_selected(app: &mut Kiorg) {
+    let Some(PopupType::ContentSearch(state)) = &app.show_popup else {
+        return;
+    };
+    let Some(path) = state
+        .results
+        .get(state.selected)
+        .map(|result| result.path.clone())
+    else {
+        return;
+    };
+    app.show_popup = None;
+    app.reveal_file(path);
+}
+
+/// Add the matches found since the last frame
+fn poll(state: &mut ContentSearchState) {
+    let Some(search) = &state.search else {
+        return;
+    };
+    while let Ok(update) = search.receiver.try_recv() {
+        match update {
+            GrepUpdate::Matches(matches) => state.results.extend(matches),
+            GrepUpdate::Finished { truncated } => {
+                state.searching = false;
+                state.truncated = truncated;
+            }
+        }
+    }
+}
+
+/// Line of a match with the matched parts highlighted
+fn highlighted_line(line: &str, matcher: &Regex, colors: &AppColors) -> LayoutJob {
+    let mut job = LayoutJob::single_section(
+        line.to_string(),
+        TextFormat {
+            font_id: FontId::monospace(12.0),
+            color: colors.fg,
+            ..Default::default()
+        },
+    );
+    let ranges: Vec<_> = matcher
+        .find_iter(line)
+        .filter(|m| !m.is_empty())
+        .map(|m| m.range())
+        .collect();
+    links::format_ranges(&mut job, &ranges, |_, format| {
+        format.background = colors.highlight;
+        format.color = colors.bg;
+    });
+    job
+}
+
+fn context_line(line: &str, colors: &AppColors) -> RichText {
+    RichText::new(line)
+        .monospace()
+        .size(12.0)
+        .color(colors.fg_light)
+}
+
+/// Draw the content search popup
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::ContentSearch(state)) = &mut app.show_popup else {
+        return;
+    };
+    poll(state);
+    let colors = &app.colors;
+    let mut keep_open = true;
+    let mut jump = false;
+    new_center_popup_window("Search File Contents")
+        .open(&mut keep_open)
+        .show(ctx, |ui| {
+            ui.set_width(600.0);
+            ui.horizontal(|ui| {
+                Frame::default()
+                    .fill(colors.bg_extreme)
+                    .inner_margin(5.0)
+                    .show(ui, |ui| {
+                        let response = ui.add(
+                            TextEdit::singleline(&mut state.pattern)
+                                .hint_text("Text to search for, Enter to search...")
+                                .desired_width(450.0)
+                                .frame(Frame::NONE),
+                        );
+                        response.request_focus();
+                    });
+                ui.toggle_value(&mut state.regex, ".*")
+                    .on_hover_text("Regular expression");
+                let mut case_sensitive = !state.case_insensitive;
+                if ui
+                    .toggle_value(&mut case_sensitive, "Aa")
+                    .on_hover_text("Case sensitive")
+                    .changed()
+                {
+                    state.case_insensitive = !case_sensitive;
+                }
+            });
+            ui.add_space(6.0);
+
+            let status = if state.searching {
+                format!(
+                    "Searching: {} matches",
+                    format_count(state.results.len() as u64)
+                )
+            } else if state.truncated {
+                format!(
+                    "Showing the first {} matches",
+                    format_count(state.results.len() as u64)
+                )
+            } else if state.searched.is_some() {
+                format!("{} matches", format_count(state.results.len() as u64))
+            } else {
+                format!("Search the files under {}", state.root.display())
+            };
+            ui.horizontal(|ui| {
+                if state.searching {
+                    ui.spinner();
+                }
+                ui.label(RichText::new(status).color(colors.fg_light));
+            });
+            ui.add_space(4.0);
+
+            let Some(search) = &state.search else {
+                return;
+            };
+            egui::ScrollArea::vertical()
+                .max_height(400.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for (index, result) in state.results.iter().enumerate() {
+                        let relative = result
+                            .path
+                            .strip_prefix(&state.root)
+                            .unwrap_or(&result.path);
+                        let header =
+                            RichText::new(format!("{}:{}", relative.display(), result.line_number))
+                                .color(colors.highlight);
+                        let response = ui.selectable_label(index == state.selected, header);
+                        for line in &result.before {
+                            ui.label(context_line(line, colors));
+                        }
+                        ui.label(highlighted_line(&result.line, &search.matcher, colors));
+                        for line in &result.after {
+                            ui.label(context_line(line, colors));
+                        }
+                        ui.add_space(4.0);
+
+                        if index == state.selected && state.scroll_to_selected {
+                            response.scroll_to_me(Some(Align::Center));
+                            state.scroll_to_selected = false;
+                        }
+                        if response.clicked() || response.double_clicked() {
+                            state.selected = index;
+                            jump = response.double_clicked();
+                        }
+                    }
+                });
+        });
+
+    if !keep_open {
+        app.show_popup = None;
+    } else if jump {
+        jump_to_selected(app);
+    }
+}
@@ -11,7 +11,9 @@ use crate::config::colors::AppColors;
 /// Confirmation state for the delete popup
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DeleteConfirmState {
-    /// Initial confirmation for any file or directory
+    /// Single confirmation for moving entries to the trash, which can be undone
+    Trash,
+    /// Initial confirmation for permanently deleting any file or directory
     Initial,
     /// Second confirmation specifically for directories with recursive deletion
     RecursiveConfirm,
@@ -20,6 +22,8 @@ pub enum DeleteConfirmState {
 /// Progress state for delete operation
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DeleteProgressState {
+    /// Whether the entries are moved to the trash instead of deleted
+    pub trash: bool,
     pub total_files: usize,
     pub current_file: usize,
     pub current_path: String,
@@ -78,6 +82,29 @@ pub fn handle_delete_confirmation(
         return DeleteConfirmResult::None;
     }
 
+    if *state == DeleteConfirmState::Trash {
+        return show_confirm_popup(
+            ctx,
+            "Move to Trash",
+            show_delete_confirm,
+            |ui| {
+                ui.vertical_centered(|ui| match entries_to_delete {
+                    [path] => {
+                        ui.label(path.display().to_string());
+                    }
+                    _ => {
+                        ui.label(format!(
+                            "Move {} selected items to the trash?",
+                            entries_to_delete.len()
+                        ));
+                    }
+                });
+            },
+            "Move to Trash (Enter)",
+            "Cancel (Esc)",
+        );
+    }
+
     // Check if we're deleting a single entry or multiple entries
     let is_bulk_delete = entries_to_delete.len() > 1;
 
@@ -87,7 +114,8 @@ pub fn handle_delete_confirmation(
         let has_directories = entries_to_delete.iter().any(|path| path.is_dir());
 
         match *state {
-            DeleteConfirmState::Initial => {
+            // Trashing is confirmed above
+            DeleteConfirmState::Trash | DeleteConfirmState::Initial => {
                 // Initial confirmation for bulk deletion
                 show_confirm_popup(
                     ctx,
@@ -152,7 +180,8 @@ pub fn handle_delete_confirmation(
         let path = &entries_to_delete[0];
 
         match *state {
-            DeleteConfirmState::Initial => {
+            // Trashing is confirmed above
+            DeleteConfirmState::Trash | DeleteConfirmState::Initial => {
                 // Initial confirmation for any file or directory
                 show_confirm_popup(
                     ctx,
@@ -337,7 +366,12 @@ pub fn handle_delete_progress(ctx: &Context, app: &mut crate::app::Kiorg) {
     // Show progress popup
     if let Some(PopupType::DeleteProgress(ref progress_data)) = app.show_popup {
         let state = &progress_data.state;
-        new_center_popup_window("Deletion Progress").show(ctx, |ui| {
+        let title = if state.trash {
+            "Moving to Trash"
+        } else {
+            "Deletion Progress"
+        };
+        new_center_popup_window(title).show(ctx, |ui| {
             ui.set_min_width(400.0);
 
             ui.vertical_centered(|ui| {
@@ -355,8 +389,9 @@ pub fn handle_delete_progress(ctx: &Context, app: &mut crate::app::Kiorg) {
                 ui.add_space(10.0);
 
                 // Status text
+                let unit = if state.trash { "items" } else { "files" };
                 ui.label(format!(
-                    "{} / {} files",
+                    "{} / {} {unit}",
                     state.current_file, state.total_files
                 ));
 
@@ -364,7 +399,8 @@ pub fn handle_delete_progress(ctx: &Context, app: &mut crate::app::Kiorg) {
 
                 // Current file being deleted
                 if !state.current_path.is_empty() {
-                    ui.label(format!("Deleting: {}", state.current_path));
+                    let verb = if state.trash { "Trashing" } else { "Deleting" };
+                    ui.label(format!("{verb}: {}", state.current_path));
                 }
 
                 ui.add_space(10.0);
@@ -386,6 +422,11 @@ pub fn confirm_delete(app: &mut crate::app::Kiorg) {
         return;
     }
 
+    if state == DeleteConfirmState::Trash {
+        trash_async(app, entries_to_delete);
+        return;
+    }
+
     // For bulk deletion (multiple entries)
     if entries_to_delete.len() > 1 {
         // Check if we're in the initial state and any of the entries is a directory
@@ -419,6 +460,7 @@ fn delete_async(app: &mut crate::app::Kiorg, entries_to_delete: Vec<PathBuf>) {
     // Set up progress state
     let total_files = count_files_to_delete(&entries_to_delete);
     let progress_state = DeleteProgressState {
+        trash: false,
         total_files,
         current_file: 0,
         current_path: String::new(),
@@ -464,6 +506,45 @@ fn delete_async(app: &mut crate::app::Kiorg, entries_to_delete: Vec<PathBuf>) {
     });
 }
 
+/// Move the entries to the trash in the background
+///
+/// Trashing renames each entry, so progress is counted in entries rather
+/// than files.
+fn trash_async(app: &mut crate::app::Kiorg, entries_to_trash: Vec<PathBuf>) {
+    let (tx, rx) = mpsc::channel();
+    let total = entries_to_trash.len();
+    app.show_popup = Some(PopupType::DeleteProgress(DeleteProgressData {
+        state: DeleteProgressState {
+            trash: true,
+            total_files: total,
+            current_file: 0,
+            current_path: String::new(),
+            completed: false,
+            error: None,
+        },
+        receiver: rx,
+    }));
+
+    thread::spawn(move || {
+        for (index, path) in entries_to_trash.into_iter().enumerate() {
+            let _ = tx.send(DeleteProgressUpdate::Progress {
+                current: index + 1,
+                total,
+                current_path: path.display().to_string(),
+            });
+            crate::utils::preview_cache::delete_previews_for_path(&path);
+            if let Err(e) = crate::utils::trash::move_to_trash(&path) {
+                let _ = tx.send(DeleteProgressUpdate::Error(format!(
+                    "Failed to move {} to the trash: {e}",
+                    path.display()
+                )));
+                return;
+            }
+        }
+        let _ = tx.send(DeleteProgressUpdate::Completed);
+    });
+}
+
 pub fn cancel_delete(app: &mut crate::app::Kiorg) {
     app.show_popup = None;
 }
@@ -0,0 +1,375 @@
+use egui::{Context, ProgressBar, RichText};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use super::PopupType;
+use super::window_utils::new_center_popup_window;
+use crate::app::Kiorg;
+use crate::utils::duplicates::{self, DuplicateGroup, ScanProgress};
+use crate::utils::format::{format_count, format_size};
+
+/// State of the duplicate finder, scanning until `groups` is set
+pub struct DuplicatesData {
+    pub root: PathBuf,
+    pub progress: Option<ScanProgress>,
+    pub groups: Option<Vec<DuplicateGroup>>,
+    /// Copies to remove
+    pub marked: HashSet<PathBuf>,
+    /// Permanent deletion was clicked once and needs a second click
+    pub confirm_delete: bool,
+    receiver: mpsc::Receiver<DuplicatesUpdate>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for DuplicatesData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DuplicatesData")
+            .field("root", &self.root)
+            .field("progress", &self.progress)
+            .field("groups", &self.groups)
+            .field("marked", &self.marked)
+            .field("receiver", &"<receiver>")
+            .finish()
+    }
+}
+
+/// Update message sent from the scanning thread
+#[derive(Debug, Clone)]
+pub enum DuplicatesUpdate {
+    Progress(ScanProgress),
+    Finished(Vec<DuplicateGroup>),
+}
+
+/// How marked copies are removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Removal {
+    Trash,
+    Delete,
+}
+
+/// Scan the current directory tree for duplicate files
+pub fn open(app: &mut Kiorg, ctx: &Context) {
+    let root = app.tab_manager.current_tab_ref().current_path.clone();
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    app.show_popup = Some(PopupType::Duplicates(DuplicatesData {
+        root: root.clone(),
+        progress: None,
+        groups: None,
+        marked: HashSet::new(),
+        confirm_delete: false,
+        receiver: rx,
+        cancel: cancel.clone(),
+    }));
+
+    let ctx = ctx.clone();
+    thread::spawn(move || {
+        let progress_tx = tx.clone();
+        let progress_ctx = ctx.clone();
+        let groups = duplicates::find_duplicates(&root, &cancel, move |progress| {
+            let _ = progress_tx.send(DuplicatesUpdate::Progress(progress));
+            progress_ctx.request_repaint();
+        });
+        // Nobody is waiting for the result of a cancelled scan
+        if let Some(groups) = groups {
+            let _ = tx.send(DuplicatesUpdate::Finished(groups));
+            ctx.request_repaint();
+        }
+    });
+}
+
+/// Close the popup, stopping the scan if it's still running
+pub fn close(app: &mut Kiorg) {
+    if let Some(PopupType::Duplicates(data)) = &app.show_popup {
+        data.cancel.store(true, Ordering::Relaxed);
+    }
+    app.show_popup = None;
+}
+
+/// Mark every copy but the first of each group
+fn mark_redundant(data: &mut DuplicatesData) {
+    data.marked = data
+        .groups
+        .iter()
+        .flatten()
+        .flat_map(|group| group.paths.iter().skip(1).cloned())
+        .collect();
+}
+
+/// Remove the marked copies, skipping files changed since the scan
+fn remove_marked(app: &mut Kiorg, removal: Removal) {
+    let Some(PopupType::Duplicates(data)) = &mut app.show_popup else {
+        return;
+    };
+    let Some(groups) = &mut data.groups else {
+        return;
+    };
+
+    let mut removed = 0;
+    let mut errors = Vec::new();
+    for group in groups.iter_mut() {
+        let marked: Vec<PathBuf> = group
+            .paths
+            .iter()
+            .filter(|path| data.marked.contains(*path))
+            .cloned()
+            .collect();
+        // The popup never lets every copy be marked, keep one regardless
+        if marked.len() >= group.paths.len() {
+            continue;
+        }
+        for path in marked {
+            let unchanged = path
+                .symlink_metadata()
+                .is_ok_and(|m| m.is_file() && m.len() == group.size);
+            let result = if !unchanged {
+                Err(format!("{} changed since the scan", path.display()))
+            } else if removal == Removal::Trash {
+                crate::utils::trash::move_to_trash(&path).map_err(|e| e.to_string())
+            } else {
+                std::fs::remove_file(&path).map_err(|e| e.to_string())
+            };
+            match result {
+                Ok(()) => {
+                    removed += 1;
+                    group.paths.retain(|p| *p != path);
+                }
+                Err(e) => errors.push(format!("{}: {e}", path.display())),
+            }
+        }
+    }
+    groups.retain(|group| group.paths.len() > 1);
+    data.marked.clear();
+    data.confirm_delete = false;
+
+    if !errors.is_empty() {
+        app.notify_error(format!(
+            "Failed to remove duplicates: {}",
+            errors.join("; ")
+        ));
+    }
+    if removed > 0 {
+        app.notify_success(match removal {
+            Removal::Trash => format!("Moved {removed} duplicates to the trash"),
+            Removal::Delete => format!("Deleted {removed} duplicates"),
+        });
+        app.refresh_entries();
+    }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Draw the duplicate finder popup
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let mut finished = None;
+    if let Some(PopupType::Duplicates(data)) = &mut app.show_popup {
+        while let Ok(update) = data.receiver.try_recv() {
+            match update {
+                DuplicatesUpdate::Progress(progress) => data.progress = Some(progress),
+                DuplicatesUpdate::Finished(groups) => finished = Some(groups),
+            }
+        }
+        if let Some(groups) = finished {
+            data.groups = Some(groups);
+        }
+    }
+
+    let colors = app.colors.clone();
+    let Some(PopupType::Duplicates(data)) = &mut app.show_popup else {
+        return;
+    };
+    let mut keep_open = true;
+    let mut removal = None;
+    new_center_popup_window("Duplicate Files")
+        .open(&mut keep_open)
+        .show(ctx, |ui| {
+            ui.set_min_width(500.0);
+            let Some(groups) = &data.groups else {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    match data.progress {
+                        Some(ScanProgress::Hashing(progress)) => {
+                            let fraction = if progress.total_bytes > 0 {
+                                progress.bytes_done as f32 / progress.total_bytes as f32
+                            } else {
+                                0.0
+                            };
+                            ui.add(ProgressBar::new(fraction).desired_width(400.0));
+                            ui.add_space(10.0);
+                            ui.label(format!(
+                                "Comparing {} / {} files of the same size",
+                                format_count(progress.files_done as u64),
+                                format_count(progress.total_files as u64),
+                            ));
+                        }
+                        Some(ScanProgress::Scanning { files }) => {
+                            ui.spinner();
+                            ui.add_space(10.0);
+                            ui.label(format!("Scanning: {} files", format_count(files as u64)));
+                        }
+                        None => {
+                            ui.spinner();
+                        }
+                    }
+                    ui.add_space(10.0);
+                });
+                return;
+            };
+
+            if groups.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    ui.label("No duplicate files found");
+                    ui.add_space(10.0);
+                });
+                return;
+            }
+
+            let wasted: u64 = groups.iter().map(DuplicateGroup::wasted).sum();
+            ui.label(
+                RichText::new(format!(
+                    "{} groups of duplicates, {} can be freed",
+                    format_count(groups.len() as u64),
+                    format_size(wasted, false)
+                ))
+                .color(colors.fg_light),
+            );
+            ui.add_space(6.0);
+
+            egui::ScrollArea::vertical()
+                .max_height(400.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for group in groups {
+                        ui.label(
+                            RichText::new(format!(
+                                "{} × {}",
+                                group.paths.len(),
+                                format_size(group.size, false)
+                            ))
+                            .color(colors.highlight),
+                        );
+                        let unmarked = group
+                            .paths
+                            .iter()
+                            .filter(|path| !data.marked.contains(*path))
+                            .count();
+                        for path in &group.paths {
+                            let mut is_marked = data.marked.contains(path);
+                            // Never mark every copy of a file
+                            let enabled = is_marked || unmarked > 1;
+                            let response = ui
+                                .add_enabled(
+                                    enabled,
+                                    egui::Checkbox::new(
+                                        &mut is_marked,
+                                        relative_path(&data.root, path),
+                                    ),
+                                )
+                                .on_disabled_hover_text("Keep at least one copy");
+                            if response.changed() {
+                                if is_marked {
+                                    data.marked.insert(path.clone());
+                                } else {
+                                    data.marked.remove(path);
+                                }
+                                data.confirm_delete = false;
+                            }
+                        }
+                        ui.add_space(4.0);
+                    }
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("Mark all but first").clicked() {
+                    mark_redundant(data);
+                    data.confirm_delete = false;
+                }
+                if ui.button("Clear marks").clicked() {
+                    data.marked.clear();
+                    data.confirm_delete = false;
+                }
+            });
+            ui.horizontal(|ui| {
+                let has_marked = !data.marked.is_empty();
+                if ui
+                    .add_enabled(has_marked, egui::Button::new("Move marked to trash"))
+                    .clicked()
+                {
+                    removal = Some(Removal::Trash);
+                }
+                let delete_label = if data.confirm_delete {
+                    RichText::new(format!(
+                        "Click again to delete {} files permanently",
+                        data.marked.len()
+                    ))
+                    .color(colors.error)
+                } else {
+                    RichText::new("Delete marked permanently")
+                };
+                if ui
+                    .add_enabled(has_marked, egui::Button::new(delete_label))
+                    .clicked()
+                {
+                    if data.confirm_delete {
+                        removal = Some(Removal::Delete);
+                    } else {
+                        data.confirm_delete = true;
+                    }
+                }
+            });
+        });
+
+    if !keep_open {
+        close(app);
+    } else if let Some(removal) = removal {
+        remove_marked(app, removal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_redundant_keeps_first_copy() {
+        let (_tx, rx) = mpsc::channel();
+        let mut data = DuplicatesData {
+            root: PathBuf::from("/root"),
+            progress: None,
+            groups: Some(vec![
+                DuplicateGroup {
+                    size: 10,
+                    paths: vec![PathBuf::from("/root/a"), PathBuf::from("/root/b")],
+                },
+                DuplicateGroup {
+                    size: 5,
+                    paths: vec![
+                        PathBuf::from("/root/c"),
+                        PathBuf::from("/root/d"),
+                        PathBuf::from("/root/e"),
+                    ],
+                },
+            ]),
+            marked: HashSet::new(),
+            confirm_delete: false,
+            receiver: rx,
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+        mark_redundant(&mut data);
+        let expected: HashSet<PathBuf> = ["/root/b", "/root/d", "/root/e"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(data.marked, expected);
+    }
+}
@@ -2,22 +2,141 @@ use crate::config::colors::AppColors;
 use crate::models::preview_content::EbookMeta;
 use crate::ui::file_list::truncate_text;
 use crate::ui::popup::window_utils::new_center_popup_window;
-use std::path::PathBuf;
+use crate::ui::preview::ebook::Chapter;
+use crate::utils::format::format_count;
+use crate::utils::html::{Block, BlockKind, Span};
+use egui::{Key, Modifiers, RichText};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, mpsc};
 
-/// Type alias for Ebook meta receiver
-pub type EbookMetaReceiver = Arc<Mutex<mpsc::Receiver<Result<EbookMeta, String>>>>;
+/// Type alias for Ebook content receiver
+pub type EbookContentReceiver = Arc<Mutex<mpsc::Receiver<Result<EbookContent, String>>>>;
+
+const POSITIONS_FILE_NAME: &str = "reading_positions.json";
+const CHAPTER_LIST_WIDTH: f32 = 220.0;
+const NAV_BAR_HEIGHT: f32 = 32.0;
+/// Scroll distance of the line up and down shortcuts
+const LINE_SCROLL: f32 = 40.0;
+
+/// Where reading of a book stopped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReadingPosition {
+    /// Chapter index, `None` for the cover
+    pub chapter: Option<usize>,
+    /// Scroll offset within the chapter
+    pub offset: f32,
+}
+
+/// Path of the file with the reading position of every opened book
+#[must_use]
+pub fn positions_file(config_dir_override: Option<&Path>) -> PathBuf {
+    crate::config::get_kiorg_config_dir(config_dir_override).join(POSITIONS_FILE_NAME)
+}
+
+fn load_positions(file: &Path) -> HashMap<PathBuf, ReadingPosition> {
+    std::fs::read_to_string(file)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_position(file: &Path, book: &Path, position: ReadingPosition) -> std::io::Result<()> {
+    let mut positions = load_positions(file);
+    positions.insert(book.to_path_buf(), position);
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::utils::atomic_write::write(file, serde_json::to_string_pretty(&positions)?)
+}
+
+/// An ebook opened for reading
+#[derive(Debug)]
+pub struct EbookContent {
+    pub meta: EbookMeta,
+    pub path: PathBuf,
+    pub chapters: Vec<Chapter>,
+    pub position: ReadingPosition,
+    /// Scroll offset to apply on the next frame
+    pending_scroll: Option<f32>,
+    viewport_height: f32,
+    positions_file: PathBuf,
+}
+
+impl EbookContent {
+    /// Open the book at the position where reading stopped last time
+    #[must_use]
+    pub fn new(
+        meta: EbookMeta,
+        path: PathBuf,
+        chapters: Vec<Chapter>,
+        positions_file: PathBuf,
+    ) -> Self {
+        let position = load_positions(&positions_file)
+            .get(&path)
+            .copied()
+            .filter(|position| position.chapter.is_none_or(|index| index < chapters.len()))
+            .unwrap_or_default();
+        Self {
+            meta,
+            path,
+            chapters,
+            position,
+            pending_scroll: Some(position.offset),
+            viewport_height: 0.0,
+            positions_file,
+        }
+    }
+
+    /// Show a chapter from its start, `None` shows the cover
+    pub fn go_to(&mut self, chapter: Option<usize>) {
+        self.position = ReadingPosition {
+            chapter,
+            offset: 0.0,
+        };
+        self.pending_scroll = Some(0.0);
+    }
+
+    pub fn next_chapter(&mut self) {
+        let next = self.position.chapter.map_or(0, |index| index + 1);
+        if next < self.chapters.len() {
+            self.go_to(Some(next));
+        }
+    }
+
+    pub fn previous_chapter(&mut self) {
+        match self.position.chapter {
+            Some(0) => self.go_to(None),
+            Some(index) => self.go_to(Some(index - 1)),
+            None => {}
+        }
+    }
+
+    fn scroll_by(&mut self, delta: f32) {
+        self.pending_scroll = Some((self.position.offset + delta).max(0.0));
+    }
+}
+
+impl Drop for EbookContent {
+    // Remember the position however the viewer gets closed
+    fn drop(&mut self) {
+        if let Err(e) = save_position(&self.positions_file, &self.path, self.position) {
+            tracing::warn!("Failed to save reading position: {}", e);
+        }
+    }
+}
 
 /// Dedicated state for the Ebook viewer app (EPUB, etc)
 #[derive(Debug)]
 pub enum EbookViewer {
-    Loading(PathBuf, EbookMetaReceiver, std::sync::mpsc::Sender<()>),
-    Loaded(EbookMeta),
+    Loading(PathBuf, EbookContentReceiver, std::sync::mpsc::Sender<()>),
+    Loaded(Box<EbookContent>),
     Error(String),
 }
 
 impl crate::ui::popup::PopupApp for EbookViewer {
-    type Content = EbookMeta;
+    type Content = EbookContent;
 
     fn loading(
         path: PathBuf,
@@ -28,7 +147,7 @@ impl crate::ui::popup::PopupApp for EbookViewer {
     }
 
     fn loaded(content: Self::Content) -> Self {
-        Self::Loaded(content)
+        Self::Loaded(Box::new(content))
     }
 
     fn error(message: String) -> Self {
@@ -63,8 +182,8 @@ impl EbookViewer {
                 let available_height = ui.available_height();
 
                 match self {
-                    Self::Loaded(epub_meta) => {
-                        render_popup(ui, epub_meta, colors, available_width, available_height);
+                    Self::Loaded(content) => {
+                        render_reader(ui, content, colors, available_width, available_height);
                     }
                     Self::Loading(path, _, _cancel_sender) => {
                         crate::ui::popup::preview::render_loading(ui, path, colors);
@@ -79,8 +198,101 @@ impl EbookViewer {
     }
 }
 
-/// Render Ebook in popup without page navigation
-pub fn render_popup(
+/// Render the chapter list, the current chapter or cover and the navigation bar
+pub fn render_reader(
+    ui: &mut egui::Ui,
+    content: &mut EbookContent,
+    colors: &AppColors,
+    available_width: f32,
+    available_height: f32,
+) {
+    let mut go_to = None;
+    ui.horizontal_top(|ui| {
+        ui.vertical(|ui| {
+            ui.set_width(CHAPTER_LIST_WIDTH);
+            egui::ScrollArea::vertical()
+                .id_salt("ebook_chapter_list")
+                .max_height(available_height)
+                .show(ui, |ui| {
+                    let current = content.position.chapter;
+                    if ui
+                        .selectable_label(
+                            current.is_none(),
+                            RichText::new("Cover").color(colors.fg),
+                        )
+                        .clicked()
+                    {
+                        go_to = Some(None);
+                    }
+                    for (index, chapter) in content.chapters.iter().enumerate() {
+                        let label = RichText::new(&chapter.title).color(colors.fg);
+                        if ui.selectable_label(current == Some(index), label).clicked() {
+                            go_to = Some(Some(index));
+                        }
+                    }
+                });
+        });
+        ui.separator();
+
+        ui.vertical(|ui| {
+            let width = available_width - CHAPTER_LIST_WIDTH - ui.spacing().item_spacing.x * 2.0;
+            let height = available_height - NAV_BAR_HEIGHT;
+            ui.set_width(width);
+            match content.position.chapter {
+                Some(index) if index < content.chapters.len() => {
+                    let mut scroll_area = egui::ScrollArea::vertical()
+                        .id_salt(("ebook_chapter", index))
+                        .max_height(height)
+                        .auto_shrink([false, false]);
+                    if let Some(offset) = content.pending_scroll.take() {
+                        scroll_area = scroll_area.vertical_scroll_offset(offset);
+                    }
+                    let output = scroll_area.show(ui, |ui| {
+                        render_blocks(ui, &content.chapters[index].blocks, colors);
+                    });
+                    content.position.offset = output.state.offset.y;
+                    content.viewport_height = output.inner_rect.height();
+                }
+                _ => {
+                    ui.allocate_ui(egui::vec2(width, height), |ui| {
+                        render_cover(ui, &content.meta, colors, width, height);
+                    });
+                }
+            }
+
+            ui.horizontal(|ui| {
+                ui.set_height(NAV_BAR_HEIGHT);
+                let has_previous = content.position.chapter.is_some();
+                if ui
+                    .add_enabled(has_previous, egui::Button::new("◀ Previous"))
+                    .clicked()
+                {
+                    content.previous_chapter();
+                }
+                let label = match content.position.chapter {
+                    Some(index) => format!("Chapter {} of {}", index + 1, content.chapters.len()),
+                    None => format!("{} chapters", format_count(content.chapters.len() as u64)),
+                };
+                ui.label(RichText::new(label).color(colors.fg_light));
+                let has_next =
+                    content.position.chapter.map_or(0, |index| index + 1) < content.chapters.len();
+                if ui
+                    .add_enabled(has_next, egui::Button::new("Next ▶"))
+                    .clicked()
+                {
+                    content.next_chapter();
+                }
+            });
+        });
+    });
+
+    if let Some(chapter) = go_to {
+        content.go_to(chapter);
+    }
+}
+
+/// Render Ebook cover in popup without page navigation
+pub fn render_cover(
     ui: &mut egui::Ui,
     ebook_meta: &EbookMeta,
     _colors: &AppColors,
@@ -91,7 +303,6 @@ pub fn render_popup(
         // Add a small space at the top
         ui.add_space(10.0);
 
-        // For Ebook, just display the cover without any page navigation controls or page count
         // Use maximum available space since there's no navigation bar or page count display
         let max_height = available_height * 0.95;
         let max_width = available_width * 0.95;
@@ -103,3 +314,137 @@ pub fn render_popup(
         );
     });
 }
+
+/// Font size of the text in a block
+#[must_use]
+pub fn block_font_size(kind: BlockKind) -> f32 {
+    match kind {
+        BlockKind::Heading(1) => 24.0,
+        BlockKind::Heading(2) => 20.0,
+        BlockKind::Heading(_) => 17.0,
+        _ => 15.0,
+    }
+}
+
+/// Left indentation of a block
+#[must_use]
+pub fn block_indent(kind: BlockKind) -> f32 {
+    match kind {
+        BlockKind::ListItem | BlockKind::Quote | BlockKind::Preformatted => 16.0,
+        _ => 0.0,
+    }
+}
+
+/// Styled text of a span inside a block of `kind`
+pub fn span_text(span: &Span, kind: BlockKind, colors: &AppColors) -> RichText {
+    let mut text = RichText::new(&span.text).size(block_font_size(kind));
+    text = if span.bold || matches!(kind, BlockKind::Heading(_)) {
+        text.strong().color(colors.highlight)
+    } else if kind == BlockKind::Quote {
+        text.color(colors.fg_light)
+    } else {
+        text.color(colors.fg)
+    };
+    if span.italic || kind == BlockKind::Quote {
+        text = text.italics();
+    }
+    if span.code || kind == BlockKind::Preformatted {
+        text = text.monospace();
+    }
+    text
+}
+
+/// Render formatted text blocks
+pub fn render_blocks(ui: &mut egui::Ui, blocks: &[Block], colors: &AppColors) {
+    for block in blocks {
+        let size = block_font_size(block.kind);
+        let mut job = egui::text::LayoutJob::default();
+        if block.kind == BlockKind::ListItem {
+            RichText::new("•  ")
+                .color(colors.fg_light)
+                .size(size)
+                .append_to(
+                    &mut job,
+                    ui.style(),
+                    egui::FontSelection::Default,
+                    egui::Align::Center,
+                );
+        }
+        for span in &block.spans {
+            span_text(span, block.kind, colors).append_to(
+                &mut job,
+                ui.style(),
+                egui::FontSelection::Default,
+                egui::Align::Center,
+            );
+        }
+
+        ui.horizontal_top(|ui| {
+            ui.add_space(block_indent(block.kind));
+            ui.add(egui::Label::new(job).wrap());
+        });
+        ui.add_space(if matches!(block.kind, BlockKind::Heading(_)) {
+            10.0
+        } else {
+            6.0
+        });
+    }
+}
+
+/// Handle chapter navigation and scrolling keys
+pub fn handle_preview_popup_input_ebook(
+    content: &mut EbookContent,
+    key: Key,
+    modifiers: Modifiers,
+) {
+    use crate::config::shortcuts::{self, ShortcutAction, ShortcutKey, TraverseResult};
+
+    let shortcuts = shortcuts::get_default_shortcuts();
+    let shortcut_key = ShortcutKey { key, modifiers };
+    if let TraverseResult::Action(action) = shortcuts.traverse_tree(&[shortcut_key]) {
+        let page = (content.viewport_height - LINE_SCROLL).max(LINE_SCROLL);
+        match action {
+            ShortcutAction::OpenDirectory | ShortcutAction::SwitchToNextTab => {
+                content.next_chapter();
+            }
+            ShortcutAction::GoToParentDirectory | ShortcutAction::SwitchToPreviousTab => {
+                content.previous_chapter();
+            }
+            ShortcutAction::MoveDown => content.scroll_by(LINE_SCROLL),
+            ShortcutAction::MoveUp => content.scroll_by(-LINE_SCROLL),
+            ShortcutAction::PageDown => content.scroll_by(page),
+            ShortcutAction::PageUp => content.scroll_by(-page),
+            ShortcutAction::GoToFirstEntry => content.pending_scroll = Some(0.0),
+            ShortcutAction::GoToLastEntry => content.pending_scroll = Some(f32::MAX),
+            _ => {
+                // Other actions are not handled in the ebook viewer
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_position() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("config").join(POSITIONS_FILE_NAME);
+        let position = ReadingPosition {
+            chapter: Some(3),
+            offset: 120.0,
+        };
+        save_position(&file, Path::new("/books/a.epub"), position).unwrap();
+        save_position(
+            &file,
+            Path::new("/books/b.epub"),
+            ReadingPosition::default(),
+        )
+        .unwrap();
+
+        let positions = load_positions(&file);
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[Path::new("/books/a.epub")], position);
+    }
+}
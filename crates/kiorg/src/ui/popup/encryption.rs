@@ -0,0 +1,294 @@
+use std::path::{Path, PathBuf};
+
+use egui::{Key as EguiKey, RichText, TextEdit};
+
+use super::PopupType;
+use super::text_input_popup::{
+    TextInputConfig, TextSelection, clear_init_flag, draw as draw_text_input,
+};
+use super::window_utils::show_center_popup_window;
+use crate::app::Kiorg;
+use crate::ui::notification::NotificationMessage;
+use crate::utils::encryption::{self, Backend, Key};
+
+const DECRYPT_POPUP_ID: &str = "decrypt_popup";
+
+/// State of the encrypt popup
+#[derive(Debug, Clone)]
+pub struct EncryptState {
+    pub paths: Vec<PathBuf>,
+    pub backend: Backend,
+    /// Encrypt to public keys instead of a passphrase
+    pub use_recipients: bool,
+    pub passphrase: String,
+    pub confirm_passphrase: String,
+    pub recipients: String,
+    /// Overwrite and delete the plaintext files once they are encrypted
+    pub shred: bool,
+    pub error: Option<String>,
+}
+
+impl EncryptState {
+    /// Key to encrypt with, or why the form is incomplete
+    fn key(&self) -> Result<Key, String> {
+        if self.use_recipients {
+            let recipients = encryption::parse_recipients(&self.recipients);
+            if recipients.is_empty() {
+                return Err("Enter at least one recipient".to_string());
+            }
+            return Ok(Key::Recipients(recipients));
+        }
+        if self.passphrase.is_empty() {
+            return Err("Enter a passphrase".to_string());
+        }
+        if self.passphrase != self.confirm_passphrase {
+            return Err("The passphrases don't match".to_string());
+        }
+        Ok(Key::Passphrase(self.passphrase.clone()))
+    }
+}
+
+/// State of the decrypt prompt
+#[derive(Debug, Clone)]
+pub struct DecryptState {
+    pub paths: Vec<PathBuf>,
+    pub secret: String,
+}
+
+/// Marked files, or the selected file
+fn target_files(app: &Kiorg) -> Vec<PathBuf> {
+    let tab = app.tab_manager.current_tab_ref();
+    let mut paths: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+    if paths.is_empty() {
+        paths.extend(tab.selected_entry().map(|entry| entry.meta.path.clone()));
+    }
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    paths
+}
+
+/// Open the encrypt popup for the marked files, or the selected file
+pub fn open_encrypt(app: &mut Kiorg) {
+    let paths = target_files(app);
+    if paths.is_empty() {
+        app.notify_error("Only files can be encrypted, compress directories first");
+        return;
+    }
+    app.show_popup = Some(PopupType::Encrypt(EncryptState {
+        paths,
+        backend: Backend::Age,
+        use_recipients: false,
+        passphrase: String::new(),
+        confirm_passphrase: String::new(),
+        recipients: String::new(),
+        shred: false,
+        error: None,
+    }));
+}
+
+/// Open the decrypt prompt for the marked files, or the selected file
+pub fn open_decrypt(app: &mut Kiorg) {
+    let mut paths = target_files(app);
+    paths.retain(|path| encryption::encrypted_with(path).is_some());
+    if paths.is_empty() {
+        app.notify_error("No encrypted files selected");
+        return;
+    }
+    app.show_popup = Some(PopupType::Decrypt(DecryptState {
+        paths,
+        secret: String::new(),
+    }));
+}
+
+/// Run `operation` on each file in the background and report the outcome
+fn run(
+    app: &mut Kiorg,
+    paths: Vec<PathBuf>,
+    (verb, past): (&'static str, &'static str),
+    operation: impl Fn(&Path) -> Result<PathBuf, String> + Send + 'static,
+) {
+    app.toasts
+        .info(format!("{verb} {} file(s)...", paths.len()));
+    let notification_sender = app.notification_system.get_sender();
+    std::thread::spawn(move || {
+        let mut done = 0;
+        for path in &paths {
+            match operation(path) {
+                Ok(_) => done += 1,
+                Err(e) => {
+                    let _ = notification_sender.send(NotificationMessage::Error(e));
+                }
+            }
+        }
+        if done > 0 {
+            let _ = notification_sender.send(NotificationMessage::Success(format!(
+                "{past} {done} file(s)"
+            )));
+        }
+    });
+}
+
+fn submit_encrypt(app: &mut Kiorg, mut state: EncryptState) {
+    let key = match state.key() {
+        Ok(key) => key,
+        Err(e) => {
+            state.error = Some(e);
+            app.show_popup = Some(PopupType::Encrypt(state));
+            return;
+        }
+    };
+    app.show_popup = None;
+    let EncryptState {
+        paths,
+        backend,
+        shred,
+        ..
+    } = state;
+    run(app, paths, ("Encrypting", "Encrypted"), move |path| {
+        let output = encryption::encrypt(path, backend, &key)?;
+        if shred {
+            encryption::shred(path)?;
+        }
+        Ok(output)
+    });
+}
+
+/// Draw the encrypt popup
+pub fn draw_encrypt(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::Encrypt(state)) = &app.show_popup else {
+        return;
+    };
+    let mut state = state.clone();
+    let colors = &app.colors;
+    let gpg_available = encryption::gpg_available();
+
+    let mut keep_open = !ctx.input(|i| i.key_pressed(EguiKey::Escape));
+    let mut submit = false;
+    let title = format!("Encrypt {} file(s)", state.paths.len());
+    show_center_popup_window(&title, ctx, &mut keep_open, |ui| {
+        ui.set_max_width(420.0);
+        egui::Grid::new("encrypt_options_grid")
+            .num_columns(2)
+            .spacing([10.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(RichText::new("Tool").color(colors.fg_light));
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut state.backend, Backend::Age, Backend::Age.name());
+                    ui.add_enabled_ui(gpg_available, |ui| {
+                        ui.radio_value(&mut state.backend, Backend::Gpg, Backend::Gpg.name())
+                            .on_disabled_hover_text("gpg is not installed");
+                    });
+                });
+                ui.end_row();
+
+                ui.label(RichText::new("Encrypt with").color(colors.fg_light));
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut state.use_recipients, false, "Passphrase");
+                    ui.radio_value(&mut state.use_recipients, true, "Recipients");
+                });
+                ui.end_row();
+            });
+        ui.add_space(8.0);
+
+        if state.use_recipients {
+            let hint = match state.backend {
+                Backend::Age => "age1... public keys, separated by commas or new lines",
+                Backend::Gpg => "Key IDs or emails, separated by commas or new lines",
+            };
+            ui.add(
+                TextEdit::multiline(&mut state.recipients)
+                    .hint_text(hint)
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            );
+        } else {
+            let response = ui.add(
+                TextEdit::singleline(&mut state.passphrase)
+                    .hint_text("Passphrase")
+                    .password(true)
+                    .desired_width(f32::INFINITY),
+            );
+            if state.passphrase.is_empty() && ui.memory(|m| m.focused().is_none()) {
+                response.request_focus();
+            }
+            let response = ui.add(
+                TextEdit::singleline(&mut state.confirm_passphrase)
+                    .hint_text("Confirm passphrase")
+                    .password(true)
+                    .desired_width(f32::INFINITY),
+            );
+            submit |= response.lost_focus() && ui.input(|i| i.key_pressed(EguiKey::Enter));
+        }
+        ui.add_space(4.0);
+        ui.checkbox(
+            &mut state.shred,
+            "Shred the original files after encrypting",
+        )
+        .on_hover_text("Overwrite the plaintext with zeros before deleting it");
+
+        if let Some(error) = &state.error {
+            ui.add_space(4.0);
+            ui.label(RichText::new(error).color(colors.error));
+        }
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            submit |= ui.button("Encrypt").clicked();
+            if ui.button("Cancel").clicked() {
+                keep_open = false;
+            }
+        });
+    });
+
+    if !keep_open {
+        app.show_popup = None;
+    } else if submit {
+        submit_encrypt(app, state);
+    } else {
+        app.show_popup = Some(PopupType::Encrypt(state));
+    }
+}
+
+/// Draw the decrypt prompt
+pub fn draw_decrypt(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::Decrypt(state)) = &mut app.show_popup else {
+        return;
+    };
+
+    let hint = match state
+        .paths
+        .first()
+        .and_then(|p| encryption::encrypted_with(p))
+    {
+        Some(Backend::Gpg) => "Passphrase, or empty to use the GPG agent...",
+        _ => "Passphrase, secret key or identity file...",
+    };
+    let config = TextInputConfig {
+        title: "Decrypt",
+        hint,
+        initial_selection: TextSelection::None,
+        password: true,
+    };
+    let keep_open = draw_text_input(
+        ctx,
+        &app.colors,
+        &config,
+        &mut state.secret,
+        DECRYPT_POPUP_ID,
+    );
+    if !keep_open {
+        clear_init_flag(ctx, DECRYPT_POPUP_ID);
+        app.show_popup = None;
+    }
+}
+
+/// Decrypt the files with the entered secret
+pub fn handle_decrypt_confirm(app: &mut Kiorg, ctx: &egui::Context) {
+    let Some(PopupType::Decrypt(state)) = app.show_popup.take() else {
+        return;
+    };
+    clear_init_flag(ctx, DECRYPT_POPUP_ID);
+    let secret = state.secret;
+    run(app, state.paths, ("Decrypting", "Decrypted"), move |path| {
+        encryption::decrypt(path, &secret)
+    });
+}
@@ -0,0 +1,88 @@
+use egui::Context;
+use std::path::Path;
+
+use super::utils::{ConfirmResult, show_confirm_popup};
+use crate::app::{Clipboard, Kiorg};
+use crate::ui::popup::PopupType;
+use crate::utils::format::format_count;
+
+/// Paste into the current directory, or offer copying into each marked directory
+pub fn paste(app: &mut Kiorg) {
+    if let Some(dirs) = app.fan_out_paste_dirs() {
+        app.show_popup = Some(PopupType::FanOutPaste(dirs));
+        return;
+    }
+    let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+    if app.paste_clipboard(current_path) {
+        // Clear marked entries after queueing the paste operation
+        app.tab_manager.current_tab_mut().marked_entries.clear();
+    }
+}
+
+/// Copy the clipboard into every marked directory
+pub fn confirm(app: &mut Kiorg) {
+    let Some(PopupType::FanOutPaste(dirs)) = app.show_popup.take() else {
+        return;
+    };
+    if app.paste_clipboard_into_each(dirs) {
+        app.tab_manager.current_tab_mut().marked_entries.clear();
+    }
+}
+
+/// Close the popup, the clipboard and marks are kept
+pub fn cancel(app: &mut Kiorg) {
+    app.show_popup = None;
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Draw the fan-out paste confirmation popup
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::FanOutPaste(dirs)) = &app.show_popup else {
+        return;
+    };
+    let entries = match &app.clipboard {
+        Some(Clipboard::Copy(paths) | Clipboard::Cut(paths)) => paths.len(),
+        None => 0,
+    };
+
+    let mut keep_open = true;
+    let result = show_confirm_popup(
+        ctx,
+        "Paste into marked directories",
+        &mut keep_open,
+        |ui| {
+            ui.label(format!(
+                "Copy {} {} into each of these {} directories?",
+                format_count(entries as u64),
+                if entries == 1 { "entry" } else { "entries" },
+                format_count(dirs.len() as u64)
+            ));
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for dir in dirs {
+                        ui.label(egui::RichText::new(dir_name(dir)).color(app.colors.fg_folder));
+                    }
+                });
+        },
+        "Copy into each (Enter)",
+        "Cancel (Esc)",
+    );
+
+    match result {
+        ConfirmResult::Confirm => confirm(app),
+        ConfirmResult::Cancel => cancel(app),
+        ConfirmResult::None => {
+            if !keep_open {
+                cancel(app);
+            }
+        }
+    }
+}
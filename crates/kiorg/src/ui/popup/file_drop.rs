@@ -5,8 +5,8 @@ use super::window_utils::new_center_popup_window;
 use crate::app::Clipboard;
 use crate::app::Kiorg;
 use crate::config::shortcuts::ShortcutAction;
-use crate::ui::center_panel::handle_clipboard_operations;
 use crate::ui::popup::PopupType;
+use crate::utils::format::format_count;
 
 /// File drop operation types
 #[derive(Clone, Copy, PartialEq)]
@@ -26,7 +26,10 @@ pub fn draw(ctx: &Context, app: &mut Kiorg) {
         let mut action = FileDropAction::None;
 
         // Create a centered popup window with file count
-        let title = format!("Files Dropped ({})", dropped_files.len());
+        let title = format!(
+            "Files Dropped ({})",
+            format_count(dropped_files.len() as u64)
+        );
         new_center_popup_window(&title)
             .open(&mut keep_open)
             .show(ctx, |ui| {
@@ -90,32 +93,16 @@ pub fn draw(ctx: &Context, app: &mut Kiorg) {
             FileDropAction::Copy => {
                 app.clipboard = Some(Clipboard::Copy(dropped_files));
 
-                let current_tab = app.tab_manager.current_tab_mut();
-                if handle_clipboard_operations(
-                    &mut app.clipboard,
-                    &current_tab.current_path,
-                    &mut current_tab.action_history,
-                    &mut app.toasts,
-                ) {
-                    app.refresh_entries();
-                    app.toasts.success("Files copied successfully!");
-                }
+                let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+                app.paste_clipboard(current_path);
 
                 app.show_popup = None;
             }
             FileDropAction::Move => {
                 app.clipboard = Some(Clipboard::Cut(dropped_files));
 
-                let current_tab = app.tab_manager.current_tab_mut();
-                if handle_clipboard_operations(
-                    &mut app.clipboard,
-                    &current_tab.current_path,
-                    &mut current_tab.action_history,
-                    &mut app.toasts,
-                ) {
-                    app.refresh_entries();
-                    app.toasts.success("Files moved successfully!");
-                }
+                let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+                app.paste_clipboard(current_path);
 
                 app.show_popup = None;
             }
@@ -147,16 +134,8 @@ pub(crate) fn handle_key_press(
             ShortcutAction::CopyEntry => {
                 app.clipboard = Some(Clipboard::Copy(dropped_files));
 
-                let current_tab = app.tab_manager.current_tab_mut();
-                if handle_clipboard_operations(
-                    &mut app.clipboard,
-                    &current_tab.current_path,
-                    &mut current_tab.action_history,
-                    &mut app.toasts,
-                ) {
-                    app.refresh_entries();
-                    app.toasts.success("Files copied successfully!");
-                }
+                let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+                app.paste_clipboard(current_path);
 
                 app.show_popup = None;
                 return true; // Input handled
@@ -164,16 +143,8 @@ pub(crate) fn handle_key_press(
             ShortcutAction::CutEntry => {
                 app.clipboard = Some(Clipboard::Cut(dropped_files));
 
-                let current_tab = app.tab_manager.current_tab_mut();
-                if handle_clipboard_operations(
-                    &mut app.clipboard,
-                    &current_tab.current_path,
-                    &mut current_tab.action_history,
-                    &mut app.toasts,
-                ) {
-                    app.refresh_entries();
-                    app.toasts.success("Files moved successfully!");
-                }
+                let current_path = app.tab_manager.current_tab_ref().current_path.clone();
+                app.paste_clipboard(current_path);
 
                 app.show_popup = None;
                 return true; // Input handled
@@ -0,0 +1,104 @@
+use crate::app::Kiorg;
+use crate::ui::popup::PopupType;
+use crate::ui::popup::fuzzy_search_popup::{
+    FuzzyMatchResult, FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState,
+    fuzzy_filter,
+};
+use crate::utils::format::format_modified;
+use crate::visit_history::VisitHistoryEntry;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
+    title: "Recent Files",
+    search_hint: "Enter to jump to file, Shift+Enter to open it...",
+    empty_message: "No opened or previewed files yet",
+    no_match_message: "No matching files found",
+    max_visible_results: Some(15),
+};
+
+/// State for the recent files popup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileHistoryState {
+    pub query: String,
+    pub selected_index: usize,
+}
+
+/// A file from the history with its last access time
+#[derive(Debug, Clone)]
+pub struct FileHistoryItem {
+    pub path: PathBuf,
+    pub accessed: String,
+}
+
+impl FuzzySearchItem for FileHistoryItem {
+    fn display_text(&self) -> Cow<'_, str> {
+        self.path.to_string_lossy()
+    }
+
+    fn secondary_text(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(&self.accessed))
+    }
+}
+
+/// Files that still exist, most recently opened or previewed first
+pub fn get_search_results(
+    query: &str,
+    file_history: &HashMap<PathBuf, VisitHistoryEntry>,
+) -> Vec<FuzzyMatchResult<FileHistoryItem>> {
+    let mut entries: Vec<&VisitHistoryEntry> = file_history
+        .values()
+        .filter(|entry| entry.path.is_file())
+        .collect();
+    entries.sort_by(|a, b| b.accessed_ts.cmp(&a.accessed_ts));
+    let items: Vec<FileHistoryItem> = entries
+        .into_iter()
+        .map(|entry| FileHistoryItem {
+            path: entry.path.clone(),
+            accessed: format_modified(UNIX_EPOCH + Duration::from_secs(entry.accessed_ts)),
+        })
+        .collect();
+    // The sort by score is stable, so equally good matches stay in recency order
+    fuzzy_filter(query, &items)
+}
+
+/// Draw the recent files popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::FileHistory(state)) = &app.show_popup else {
+        return;
+    };
+
+    let mut fuzzy_state = FuzzySearchState::new(state.query.clone());
+    fuzzy_state.selected_index = state.selected_index;
+    let results = get_search_results(&fuzzy_state.query, &app.file_history);
+
+    let action = crate::ui::popup::fuzzy_search_popup::draw(
+        ctx,
+        &POPUP_CONFIG,
+        &app.colors,
+        &mut fuzzy_state,
+        &results,
+    );
+
+    match action {
+        FuzzySearchAction::KeepOpen => {
+            app.show_popup = Some(PopupType::FileHistory(FileHistoryState {
+                query: fuzzy_state.query,
+                selected_index: fuzzy_state.selected_index,
+            }));
+        }
+        FuzzySearchAction::Close => {
+            app.show_popup = None;
+        }
+        FuzzySearchAction::Selected(item) => {
+            app.show_popup = None;
+            if ctx.input(|i| i.modifiers.shift) {
+                app.open_file(item.path);
+            } else {
+                app.reveal_file(item.path);
+            }
+        }
+    }
+}
@@ -22,6 +22,11 @@ pub trait FuzzySearchItem: Clone {
     fn search_text(&self) -> Cow<'_, str> {
         self.display_text()
     }
+
+    /// Optional icon shown before the label.
+    fn icon(&self, _ctx: &egui::Context) -> Option<egui::ImageSource<'_>> {
+        None
+    }
 }
 
 /// Configuration for the fuzzy search popup.
@@ -293,6 +298,9 @@ fn render_item_list<T: FuzzySearchItem>(
                 );
                 content_ui.horizontal(|ui| {
                     ui.add_space(8.0);
+                    if let Some(icon) = result.item.icon(ui.ctx()) {
+                        ui.add(egui::Image::new(icon).fit_to_exact_size(Vec2::splat(20.0)));
+                    }
                     ui.label(
                         egui::RichText::new(result.item.display_text().as_ref())
                             .color(text_color)
@@ -1,3 +1,4 @@
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::preview_content::ImageMeta;
 use crate::ui::file_list::truncate_text;
@@ -48,7 +49,12 @@ impl crate::ui::popup::PopupApp for ImageViewer {
 }
 
 impl ImageViewer {
-    pub fn draw(&mut self, ctx: &egui::Context, colors: &AppColors) -> bool {
+    pub fn draw(
+        &mut self,
+        ctx: &egui::Context,
+        colors: &AppColors,
+        zoom_mode: &mut ZoomMode,
+    ) -> bool {
         let mut keep_open = true;
         let screen_size = ctx.content_rect().size();
         let popup_size = egui::vec2(screen_size.x * 0.9, screen_size.y * 0.9);
@@ -64,7 +70,14 @@ impl ImageViewer {
 
                 match self {
                     Self::Loaded(image_meta) => {
-                        render_popup(ui, image_meta, available_width, available_height);
+                        render_popup(
+                            ui,
+                            image_meta,
+                            colors,
+                            available_width,
+                            available_height,
+                            Some(zoom_mode),
+                        );
                     }
                     Self::Loading(path, _, _cancel_sender) => {
                         crate::ui::popup::preview::render_loading(ui, path, colors);
@@ -81,19 +94,38 @@ impl ImageViewer {
 
 /// Render image content optimized for popup view
 ///
-/// This version focuses on displaying the image at a large size without metadata tables
+/// This version focuses on displaying the image at a large size without metadata tables.
+/// With a `zoom_mode`, a toolbar above the image lets the user pick it.
 pub fn render_popup(
     ui: &mut egui::Ui,
     image_meta: &ImageMeta,
+    colors: &AppColors,
     available_width: f32,
     available_height: f32,
+    mut zoom_mode: Option<&mut ZoomMode>,
 ) {
     let source_id = egui::Id::new(&image_meta.title);
+    let mut available_height = available_height;
+    if image_meta.checkerboard || zoom_mode.is_some() {
+        let toolbar = ui.horizontal(|ui| {
+            if let Some(zoom_mode) = zoom_mode.as_deref_mut() {
+                crate::ui::preview::image::zoom_mode_selector(ui, zoom_mode);
+            }
+            if image_meta.checkerboard {
+                crate::ui::preview::image::render_checkerboard_toggle(ui, colors);
+            }
+        });
+        available_height -= toolbar.response.rect.height() + ui.spacing().item_spacing.y;
+    }
+    let checkerboard =
+        image_meta.checkerboard && crate::ui::preview::image::checkerboard_enabled(ui.ctx());
     crate::ui::preview::image::render_interactive(
         ui,
         &image_meta.image,
         source_id,
         available_width,
         available_height,
+        checkerboard,
+        zoom_mode.map_or_else(ZoomMode::default, |mode| *mode),
     );
 }
@@ -0,0 +1,62 @@
+use egui::{Context, Grid, RichText, ScrollArea};
+
+use super::PopupType;
+use super::window_utils::new_center_popup_window;
+use crate::app::Kiorg;
+
+/// Draw the list of marks, typing a mark's letter or clicking it jumps there
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    if !matches!(app.show_popup, Some(PopupType::JumpMarks)) {
+        return;
+    }
+
+    let colors = &app.colors;
+    let mut keep_open = true;
+    let mut jump_to = None;
+    let mut remove = None;
+    new_center_popup_window("Marks")
+        .open(&mut keep_open)
+        .show(ctx, |ui| {
+            ui.set_min_width(400.0);
+            if app.jump_marks.is_empty() {
+                ui.label(
+                    RichText::new("No marks set, press m and a letter to set one")
+                        .color(colors.fg_light),
+                );
+                return;
+            }
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                Grid::new("jump_marks_grid")
+                    .num_columns(3)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for (name, jump) in app.jump_marks.iter() {
+                            ui.label(
+                                RichText::new(name.to_string())
+                                    .monospace()
+                                    .color(colors.highlight),
+                            );
+                            let path = jump.selected.as_ref().unwrap_or(&jump.dir);
+                            let text = RichText::new(path.display().to_string()).color(colors.fg);
+                            if ui.selectable_label(false, text).clicked() {
+                                jump_to = Some(name);
+                            }
+                            if ui.small_button("×").on_hover_text("Remove mark").clicked() {
+                                remove = Some(name);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+
+    if let Some(name) = remove {
+        app.jump_marks.remove(name);
+    }
+    if !keep_open {
+        app.show_popup = None;
+    } else if let Some(name) = jump_to {
+        app.show_popup = None;
+        app.jump_to_mark(name);
+    }
+}
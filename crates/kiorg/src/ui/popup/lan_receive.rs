@@ -0,0 +1,90 @@
+use egui::{Context, RichText};
+
+use super::utils::{ConfirmResult, show_confirm_popup};
+use crate::app::Kiorg;
+use crate::ui::popup::PopupType;
+use crate::utils::format::{format_count, format_size};
+
+/// Files listed before the rest is summarized
+const MAX_LISTED_FILES: usize = 10;
+
+/// Accept the offered files, they are received in the background
+pub fn accept(app: &mut Kiorg) {
+    if let Some(PopupType::LanReceive(incoming)) = app.show_popup.take() {
+        incoming.respond(true);
+        app.toasts
+            .info(format!("Receiving files from {}...", incoming.offer.sender));
+    }
+}
+
+/// Decline the offered files
+pub fn decline(app: &mut Kiorg) {
+    if let Some(PopupType::LanReceive(incoming)) = app.show_popup.take() {
+        incoming.respond(false);
+    }
+}
+
+/// Show the next offer from another device, if no other popup is open
+pub fn poll(app: &mut Kiorg) {
+    if app.show_popup.is_some() {
+        return;
+    }
+    if let Some(incoming) = app.lan.as_ref().and_then(|lan| lan.try_recv_offer()) {
+        app.show_popup = Some(PopupType::LanReceive(incoming));
+    }
+}
+
+/// Draw the incoming files confirmation popup
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::LanReceive(incoming)) = &app.show_popup else {
+        return;
+    };
+    let offer = &incoming.offer;
+    let colors = &app.colors;
+
+    let mut keep_open = true;
+    let result = show_confirm_popup(
+        ctx,
+        "Incoming Files",
+        &mut keep_open,
+        |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(format!(
+                    "{} ({}) wants to send {} file(s), {}:",
+                    offer.sender,
+                    incoming.from.ip(),
+                    format_count(offer.files.len() as u64),
+                    format_size(offer.total_size(), false)
+                ));
+            });
+            ui.add_space(5.0);
+            for file in offer.files.iter().take(MAX_LISTED_FILES) {
+                ui.label(
+                    RichText::new(format!("{} ({})", file.name, format_size(file.size, false)))
+                        .color(colors.fg_light),
+                );
+            }
+            if offer.files.len() > MAX_LISTED_FILES {
+                ui.label(
+                    RichText::new(format!(
+                        "and {} more",
+                        format_count((offer.files.len() - MAX_LISTED_FILES) as u64)
+                    ))
+                    .color(colors.fg_light),
+                );
+            }
+        },
+        "Accept (Enter)",
+        "Decline (Esc)",
+    );
+
+    match result {
+        ConfirmResult::Confirm => accept(app),
+        ConfirmResult::Cancel => decline(app),
+        ConfirmResult::None => {
+            if !keep_open {
+                decline(app);
+            }
+        }
+    }
+}
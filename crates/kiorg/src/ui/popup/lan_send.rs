@@ -0,0 +1,109 @@
+use crate::app::Kiorg;
+use crate::lan::Peer;
+use crate::ui::popup::PopupType;
+use crate::ui::popup::fuzzy_search_popup::{
+    FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState, fuzzy_filter,
+};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
+
+static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
+    title: "Send to Device",
+    search_hint: "Type to filter devices...",
+    empty_message: "Looking for devices running kiorg...",
+    no_match_message: "No matching device found",
+    max_visible_results: None,
+};
+
+/// How often the list of devices is refreshed while the popup is open
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+impl FuzzySearchItem for Peer {
+    fn display_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.name)
+    }
+
+    fn secondary_text(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Owned(self.addr.to_string()))
+    }
+}
+
+/// State for the send to device popup
+#[derive(Debug, Clone)]
+pub struct LanSendState {
+    pub paths: Vec<PathBuf>,
+    pub query: String,
+    pub selected_index: usize,
+}
+
+/// Open the send to device popup for the marked files, or the selected file
+pub fn open(app: &mut Kiorg, ctx: &egui::Context) {
+    let tab = app.tab_manager.current_tab_ref();
+    let mut paths: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+    if paths.is_empty() {
+        paths.extend(tab.selected_entry().map(|entry| entry.meta.path.clone()));
+    }
+    paths.retain(|path| path.is_file());
+    if paths.is_empty() {
+        app.notify_error("Only files can be sent to other devices");
+        return;
+    }
+    paths.sort();
+
+    let Some(lan) = app.lan_service(ctx) else {
+        return;
+    };
+    lan.browse();
+    app.show_popup = Some(PopupType::LanSend(LanSendState {
+        paths,
+        query: String::new(),
+        selected_index: 0,
+    }));
+}
+
+/// Draw the send to device popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let (Some(PopupType::LanSend(state)), Some(lan)) = (&app.show_popup, &app.lan) else {
+        return;
+    };
+    let mut state = state.clone();
+
+    let peers = lan.peers();
+    if peers.is_empty() {
+        lan.browse();
+    }
+    ctx.request_repaint_after(REFRESH_INTERVAL);
+
+    let mut fuzzy_state = FuzzySearchState::new(state.query.clone());
+    fuzzy_state.selected_index = state.selected_index;
+    let filtered = fuzzy_filter(&fuzzy_state.query, &peers);
+
+    let action = crate::ui::popup::fuzzy_search_popup::draw(
+        ctx,
+        &POPUP_CONFIG,
+        &app.colors,
+        &mut fuzzy_state,
+        &filtered,
+    );
+
+    match action {
+        FuzzySearchAction::KeepOpen => {
+            state.query = fuzzy_state.query;
+            state.selected_index = fuzzy_state.selected_index;
+            app.show_popup = Some(PopupType::LanSend(state));
+        }
+        FuzzySearchAction::Close => {
+            app.show_popup = None;
+        }
+        FuzzySearchAction::Selected(peer) => {
+            app.show_popup = None;
+            app.toasts.info(format!(
+                "Sending {} to {}...",
+                crate::utils::share::describe(&state.paths),
+                peer.name
+            ));
+            lan.send(&peer, state.paths);
+        }
+    }
+}
@@ -34,25 +34,44 @@ pub trait PopupApp: Sized {
 pub mod about;
 pub mod action_history;
 pub mod add_entry;
+pub mod archive_password;
+pub mod archive_test;
 pub mod bookmark;
+pub mod checksum;
+pub mod content_search;
 pub mod delete;
+pub mod duplicates;
 pub mod ebook_viewer;
+pub mod encryption;
 pub mod exit;
+pub mod fan_out_paste;
 pub mod file_drop;
+pub mod file_history;
 pub mod frameless_popup;
 pub mod fuzzy_search_popup;
 pub mod generic_message;
 pub mod goto_path;
 pub mod image_viewer;
+pub mod jump_marks;
+pub mod lan_receive;
+pub mod lan_send;
 pub mod open_with;
+pub mod pdf_password;
 pub mod pdf_viewer;
 pub mod plugin;
 pub mod plugin_viewer;
 pub mod preview;
+pub mod profile;
+pub mod properties;
+pub mod session;
+pub mod share;
 pub mod sort_toggle;
+pub mod split;
 pub mod teleport;
 pub mod text_input_popup;
 pub mod theme;
+pub mod touch;
+pub mod trash;
 pub mod utils;
 pub mod video_viewer;
 #[cfg(target_os = "macos")]
@@ -67,30 +86,51 @@ pub enum PopupType {
     About,
     Help,
     Exit,
+    FanOutPaste(Vec<PathBuf>), // Marked directories to copy the clipboard into
     GenericMessage(String, String), // Title and message for generic popup
     Delete(crate::ui::popup::delete::DeleteConfirmState, Vec<PathBuf>),
     DeleteProgress(crate::ui::popup::delete::DeleteProgressData),
-    OpenWith,         // Open file with custom command popup
-    AddEntry(String), // Name for the new file/directory being added
-    Bookmarks(usize), // Selected index in the bookmarks list
+    Checksum(crate::ui::popup::checksum::ChecksumProgressData), // Checksum generation or verification progress
+    Duplicates(crate::ui::popup::duplicates::DuplicatesData),   // Duplicate file scan and results
+    ContentSearch(Box<crate::ui::popup::content_search::ContentSearchState>), // Search file contents recursively
+    Split(crate::ui::popup::split::SplitState), // Enter the part size to split the selected file
+    SplitProgress(crate::ui::popup::split::SplitProgressData), // Split or join progress
+    ArchivePassword(crate::ui::popup::archive_password::ArchivePasswordState), // Prompt for an encrypted archive's password
+    OpenWith, // Open file with custom command popup
+    AddEntry(crate::ui::popup::add_entry::AddEntryState), // Name and template of the new file/directory
+    Touch(crate::ui::popup::touch::TouchState),           // Entries and time to touch
+    Bookmarks(usize),                                     // Selected index in the bookmarks list
+    EditBookmark(crate::ui::popup::bookmark::BookmarkEditState), // Label and key of a bookmark
+    JumpMarks,                                            // Show the marks set with `m{a-z}`
     #[cfg(target_os = "windows")]
     WindowsDrives(usize), // Selected index in the drives list (Windows only)
     #[cfg(target_os = "macos")]
-    Volumes(usize), // Selected index in the volumes list (macOS only)
-    Preview,          // Show file preview in a popup window
-    Pdf(Box<crate::ui::popup::pdf_viewer::PdfViewer>), // PDF app
-    Ebook(Box<crate::ui::popup::ebook_viewer::EbookViewer>), // Ebook app
-    Image(Box<crate::ui::popup::image_viewer::ImageViewer>), // Image app
-    Video(Box<crate::ui::popup::video_viewer::VideoViewer>), // Video app
-    Plugin(Box<crate::ui::popup::plugin_viewer::PluginViewer>), // Plugin app
-    Themes(String),   // Selected theme key in the themes list
-    Plugins,          // Show plugins list
+    Volumes(usize),        // Selected index in the volumes list (macOS only)
+    Preview,                                              // Show file preview in a popup window
+    Pdf(Box<crate::ui::popup::pdf_viewer::PdfViewer>),    // PDF app
+    PdfPassword(crate::ui::popup::pdf_password::PdfPasswordState), // Prompt for an encrypted PDF's password
+    Ebook(Box<crate::ui::popup::ebook_viewer::EbookViewer>),       // Ebook app
+    Image(Box<crate::ui::popup::image_viewer::ImageViewer>),       // Image app
+    Video(Box<crate::ui::popup::video_viewer::VideoViewer>),       // Video app
+    Plugin(Box<crate::ui::popup::plugin_viewer::PluginViewer>),    // Plugin app
+    Themes(String),         // Selected theme key in the themes list
+    Plugins,                // Show plugins list
     FileDrop(Vec<PathBuf>), // List of dropped files
     Teleport(crate::ui::popup::teleport::TeleportState), // Teleport through visit history
-    UpdateConfirm(Release), // Show update confirmation with version info
+    FileHistory(crate::ui::popup::file_history::FileHistoryState), // Recently opened or previewed files
+    Profiles(crate::ui::popup::profile::ProfileState), // Switch between isolated configurations
+    Sessions(crate::ui::popup::session::SessionState), // Save or restore named sets of tabs
+    UpdateConfirm(Release),                            // Show update confirmation with version info
     UpdateProgress(crate::ui::update::UpdateProgressData), // Show update progress during download
-    UpdateRestart,    // Show restart confirmation with version info
-    SortToggle,       // Show sort toggle popup for column sorting
-    ActionHistory,    // Show action history with rollback options
+    UpdateRestart, // Show restart confirmation with version info
+    SortToggle,    // Show sort toggle popup for column sorting
+    ActionHistory, // Show action history with rollback options
     GoToPath(crate::ui::popup::goto_path::GoToPathState), // Manually enter a path
+    Share(crate::ui::popup::share::ShareState), // Pick where to share the selected files
+    LanSend(crate::ui::popup::lan_send::LanSendState), // Pick a device to send the selected files to
+    LanReceive(crate::lan::IncomingOffer), // Accept or decline files sent from another device
+    Properties(crate::ui::popup::properties::Properties), // Details of the selected entry
+    Encrypt(crate::ui::popup::encryption::EncryptState), // Pick how to encrypt the selected files
+    Decrypt(crate::ui::popup::encryption::DecryptState), // Enter the secret to decrypt the selected files
+    Trash(crate::ui::popup::trash::TrashState),          // Browse and restore trashed files
 }
@@ -4,14 +4,14 @@ use crate::ui::popup::fuzzy_search_popup::{
     FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState, fuzzy_filter,
 };
 use crate::ui::popup::text_input_popup::{TextInputConfig, TextSelection, draw as draw_text_input};
-use mimeapps::AppInfo;
+use mimeapps::{AppIcon, AppInfo};
 use std::borrow::Cow;
 
 const OPEN_WITH_POPUP_ID: &str = "open_with_popup";
 
 static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
     title: "Open with",
-    search_hint: "Type to filter application to open with...",
+    search_hint: "Type to filter application to open with, Shift+Enter to always open with it...",
     empty_message: "No applications available",
     no_match_message: "No matching application found",
     max_visible_results: None,
@@ -29,6 +29,45 @@ impl FuzzySearchItem for AppInfo {
     fn search_text(&self) -> Cow<'_, str> {
         Cow::Borrowed(&self.name)
     }
+
+    fn icon(&self, ctx: &egui::Context) -> Option<egui::ImageSource<'_>> {
+        match self.icon.as_ref()? {
+            AppIcon::Path(path) => Some(egui::ImageSource::Uri(Cow::Owned(format!(
+                "file://{}",
+                path.display()
+            )))),
+            AppIcon::Rgba {
+                width,
+                height,
+                pixels,
+            } => {
+                let texture = icon_texture(ctx, &self.id, *width, *height, pixels);
+                Some(egui::ImageSource::Texture((&texture).into()))
+            }
+        }
+    }
+}
+
+/// Upload an app icon once, keeping the texture alive in the context memory
+fn icon_texture(
+    ctx: &egui::Context,
+    app_id: &str,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> egui::TextureHandle {
+    let id = egui::Id::new(("open_with_app_icon", app_id));
+    if let Some(texture) = ctx.data(|d| d.get_temp::<egui::TextureHandle>(id)) {
+        return texture;
+    }
+    let image = egui::ColorImage::from_rgba_unmultiplied([width as usize, height as usize], pixels);
+    let texture = ctx.load_texture(
+        format!("app-icon-{app_id}"),
+        image,
+        egui::TextureOptions::LINEAR,
+    );
+    ctx.data_mut(|d| d.insert_temp(id, texture.clone()));
+    texture
 }
 
 #[cfg(not(any(test, feature = "testing")))]
@@ -53,12 +92,35 @@ impl OpenWithUiState {
         if !self.apps_loaded
             && let Some(entry) = app.tab_manager.current_tab_ref().selected_entry()
         {
-            self.apps = get_apps_for_file(&entry.meta.path);
+            self.apps = with_actions(get_apps_for_file(&entry.meta.path));
             self.apps_loaded = true;
         }
     }
 }
 
+/// List the desktop actions of each app as entries right after it, e.g.
+/// "Firefox › New Private Window"
+fn with_actions(apps: Vec<AppInfo>) -> Vec<AppInfo> {
+    let mut entries = Vec::with_capacity(apps.len());
+    for mut app in apps {
+        let actions = std::mem::take(&mut app.actions);
+        let sub_entries: Vec<AppInfo> = actions
+            .into_iter()
+            .map(|action| AppInfo {
+                path: action.path,
+                name: format!("{} › {}", app.name, action.name),
+                id: app.id.clone(),
+                icon: app.icon.clone(),
+                terminal: app.terminal,
+                actions: Vec::new(),
+            })
+            .collect();
+        entries.push(app);
+        entries.extend(sub_entries);
+    }
+    entries
+}
+
 /// Draw the open with popup dialog
 pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
     // Early return if not in open with mode
@@ -89,6 +151,7 @@ fn draw_custom_command_popup(ctx: &egui::Context, app: &mut Kiorg, ui_state: &mu
         title: "Open with",
         hint: "No associated application found for file type, enter custom command to open...",
         initial_selection: TextSelection::None,
+        password: false,
     };
 
     let keep_open = draw_text_input(
@@ -123,10 +186,11 @@ pub fn handle_confirm(app: &mut Kiorg, ctx: &egui::Context) {
         clear_state(ctx);
         confirm_open_with(app, command);
     } else if ui_state.fuzzy_state.selected_index < filtered_apps.len() {
-        let app_info = &filtered_apps[ui_state.fuzzy_state.selected_index].item;
-        let path = app_info.path.clone();
+        let app_info = filtered_apps[ui_state.fuzzy_state.selected_index]
+            .item
+            .clone();
         clear_state(ctx);
-        confirm_open_with(app, path);
+        confirm_app(app, ctx, app_info);
     }
 }
 
@@ -158,11 +222,59 @@ fn draw_app_selection_popup(ctx: &egui::Context, app: &mut Kiorg, ui_state: &mut
         }
         FuzzySearchAction::Selected(app_info) => {
             clear_state(ctx);
-            confirm_open_with(app, app_info.path);
+            confirm_app(app, ctx, app_info);
         }
     }
 }
 
+/// Open the selected file with an app, holding Shift also makes the app the
+/// default for files of the same type
+fn confirm_app(app: &mut Kiorg, ctx: &egui::Context, app_info: AppInfo) {
+    if ctx.input(|i| i.modifiers.shift) {
+        set_default_app(app, &app_info);
+    }
+    if app_info.terminal {
+        confirm_open_in_terminal(app, app_info.path);
+    } else {
+        confirm_open_with(app, app_info.path);
+    }
+}
+
+/// Open the selected file with a console application
+fn confirm_open_in_terminal(app: &mut Kiorg, exec: String) {
+    let path_to_open = {
+        let tab = app.tab_manager.current_tab_ref();
+        tab.selected_entry().map(|entry| entry.meta.path.clone())
+    };
+
+    if let Some(path) = path_to_open {
+        app.open_file_in_terminal(path, exec);
+    }
+
+    close_popup(app);
+}
+
+fn set_default_app(app: &mut Kiorg, app_info: &AppInfo) {
+    let Some(path) = app
+        .tab_manager
+        .current_tab_ref()
+        .selected_entry()
+        .map(|entry| entry.meta.path.clone())
+    else {
+        return;
+    };
+    match crate::open_wrap::set_default_app(&path, app_info) {
+        Ok(()) => app.notify_info(format!(
+            "Files of this type now open with {} by default",
+            app_info.name
+        )),
+        Err(e) => app.notify_error(format!(
+            "Failed to set {} as the default application: {e}",
+            app_info.name
+        )),
+    }
+}
+
 fn clear_state(ctx: &egui::Context) {
     ctx.data_mut(|d| {
         d.remove::<OpenWithUiState>(egui::Id::new("open_with_ui_state"));
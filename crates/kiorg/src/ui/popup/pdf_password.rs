@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use super::PopupType;
+use super::text_input_popup::{
+    TextInputConfig, TextSelection, clear_init_flag, draw as draw_text_input,
+};
+use crate::app::Kiorg;
+
+const PDF_PASSWORD_POPUP_ID: &str = "pdf_password_popup";
+
+/// Where the PDF is shown once the password is entered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordPurpose {
+    /// Reload the preview in the right panel
+    Preview,
+    /// Reopen the PDF viewer popup
+    Viewer,
+}
+
+/// State of the PDF password prompt
+#[derive(Debug, Clone)]
+pub struct PdfPasswordState {
+    pub path: PathBuf,
+    pub password: String,
+    pub purpose: PasswordPurpose,
+}
+
+fn dismissed_id() -> egui::Id {
+    egui::Id::new("pdf_password_dismissed")
+}
+
+/// Prompt for the password of a PDF that failed to open
+///
+/// The viewer popup always prompts. The right panel prompts once per file
+/// and only while nothing else is shown, so browsing past an encrypted PDF
+/// doesn't keep interrupting.
+pub fn prompt(app: &mut Kiorg, ctx: &egui::Context, path: PathBuf) {
+    let purpose = match &app.show_popup {
+        Some(PopupType::Pdf(_)) => PasswordPurpose::Viewer,
+        None => {
+            let selected = app
+                .tab_manager
+                .current_tab_ref()
+                .selected_entry()
+                .is_some_and(|entry| entry.meta.path == path);
+            let dismissed = ctx.data_mut(|d| {
+                d.get_temp_mut_or_default::<HashSet<PathBuf>>(dismissed_id())
+                    .contains(&path)
+            });
+            if !selected || dismissed {
+                return;
+            }
+            PasswordPurpose::Preview
+        }
+        _ => return,
+    };
+    app.show_popup = Some(PopupType::PdfPassword(PdfPasswordState {
+        path,
+        password: String::new(),
+        purpose,
+    }));
+}
+
+/// Draw the PDF password popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::PdfPassword(state)) = &mut app.show_popup else {
+        return;
+    };
+
+    let hint = format!(
+        "Enter password for {}...",
+        state.path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    let config = TextInputConfig {
+        title: "PDF Password",
+        hint: &hint,
+        initial_selection: TextSelection::None,
+        password: true,
+    };
+
+    let keep_open = draw_text_input(
+        ctx,
+        &app.colors,
+        &config,
+        &mut state.password,
+        PDF_PASSWORD_POPUP_ID,
+    );
+    if !keep_open {
+        handle_cancel(app, ctx);
+    }
+}
+
+/// Cache the entered password for the session and open the PDF again
+pub fn handle_confirm(app: &mut Kiorg, ctx: &egui::Context) {
+    let Some(PopupType::PdfPassword(state)) = &app.show_popup else {
+        return;
+    };
+    if state.password.is_empty() {
+        return;
+    }
+    let state = state.clone();
+    clear_init_flag(ctx, PDF_PASSWORD_POPUP_ID);
+
+    crate::ui::preview::pdf::cache_password(&state.path, state.password);
+    app.show_popup = None;
+    match state.purpose {
+        PasswordPurpose::Preview => {
+            // Forget the failed preview so it's loaded again
+            app.cached_preview_path = None;
+            app.selection_changed = true;
+        }
+        PasswordPurpose::Viewer => super::preview::handle_show_file_popup(app, ctx),
+    }
+}
+
+pub fn handle_cancel(app: &mut Kiorg, ctx: &egui::Context) {
+    clear_init_flag(ctx, PDF_PASSWORD_POPUP_ID);
+    if let Some(PopupType::PdfPassword(state)) = &app.show_popup
+        && state.purpose == PasswordPurpose::Preview
+    {
+        ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<HashSet<PathBuf>>(dismissed_id())
+                .insert(state.path.clone());
+        });
+    }
+    app.show_popup = None;
+}
@@ -1,45 +1,443 @@
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::preview_content::PdfMeta;
 use crate::ui::file_list::truncate_text;
 use crate::ui::popup::window_utils::new_center_popup_window;
+use crate::ui::preview::image::ViewOptions;
+use crate::utils::file_operations::unique_path;
+use crate::utils::format::format_count;
 use egui::{Button, Key, Modifiers, RichText};
-use std::path::PathBuf;
+use pdfium_bind::{
+    PdfBackground, PdfRotation, RenderKind, RenderRequest, RenderResponse, RenderWorker,
+};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, mpsc};
 use tracing::error;
 
 /// Type alias for PDF meta receiver
 pub type PdfMetaReceiver = Arc<Mutex<mpsc::Receiver<Result<PdfViewerContent, String>>>>;
 
+/// Result of saving the images of a document, `None` while in progress
+pub type ImageExport = Arc<Mutex<Option<Result<usize, String>>>>;
+
 /// Content for the PDF viewer, owning the document handle
 pub struct PdfViewerContent {
     pub meta: PdfMeta,
+    pub path: PathBuf,
     pub doc: Arc<Mutex<pdfium_bind::PdfDocument>>,
+    /// Text layer of the current page, `None` if the page has no text
+    pub page_text: Option<pdfium_bind::PdfPageText>,
+    /// Selected characters of the page text as (anchor, cursor) indexes
+    pub selection: Option<(usize, usize)>,
+    pub search: PdfSearch,
+    /// Table of contents of the document
+    pub outline: Vec<pdfium_bind::PdfOutlineItem>,
+    /// Label of the current page if the document defines page labels, e.g. "iv"
+    pub page_label: Option<String>,
+    /// Page width and height in points
+    pub page_size: (f32, f32),
+    /// Rotation applied on top of the pages' own, e.g. for documents
+    /// scanned sideways
+    pub rotation: PdfRotation,
+    /// What pages are rendered on
+    pub background: PdfBackground,
+    /// Sharper render of the visible part of the page when zoomed in
+    pub tile: Option<PdfTile>,
+    /// Links of the current page
+    pub links: Vec<pdfium_bind::PdfLink>,
+    /// Embedded images being saved next to the document
+    pub image_export: Option<ImageExport>,
+    /// Renders pages and tiles off the UI thread
+    renderer: RenderWorker,
+    /// Render of the current page in progress, the previous page is shown
+    /// until it's done
+    pending_page: Option<u64>,
+    /// Render of a tile in progress
+    pending_tile: Option<(u64, RenderRequest)>,
+}
+
+/// Render of a region of the page at the zoomed in scale
+///
+/// The page texture has a fixed resolution, so it gets blurry at high zoom
+/// levels. Rendering the whole page at the zoomed scale could need gigabytes
+/// for large pages, so only the visible region is rendered on top of it.
+pub struct PdfTile {
+    page: isize,
+    /// Region as `[left, top, right, bottom]` in points
+    rect: [f32; 4],
+    /// Pixels per point
+    scale: f32,
+    texture: egui::TextureHandle,
+}
+
+/// Text search in the document
+#[derive(Debug, Default)]
+pub struct PdfSearch {
+    pub query: String,
+    /// Character ranges of the matches on the current page
+    pub matches: Vec<Range<usize>>,
+    /// Index of the current match in `matches`
+    pub current: usize,
+}
+
+impl PdfSearch {
+    /// Case sensitive only if the query has upper case characters
+    fn match_case(&self) -> bool {
+        self.query.chars().any(char::is_uppercase)
+    }
+
+    fn find(&self, doc: &pdfium_bind::PdfDocument, page: isize) -> Vec<Range<usize>> {
+        doc.find_text(page, &self.query, self.match_case())
+            .unwrap_or_else(|e| {
+                error!("Error searching PDF page: {}", e);
+                Vec::new()
+            })
+    }
 }
 
 impl std::fmt::Debug for PdfViewerContent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PdfViewerContent")
             .field("meta", &self.meta)
+            .field("path", &self.path)
             .field("doc", &"<PdfDocument>")
-            .finish()
+            .field("selection", &self.selection)
+            .field("search", &self.search)
+            .field("outline", &self.outline)
+            .field("page_label", &self.page_label)
+            .field("page_size", &self.page_size)
+            .field("rotation", &self.rotation)
+            .field("links", &self.links)
+            .finish_non_exhaustive()
+    }
+}
+
+fn page_background(background: crate::config::PdfBackground, colors: &AppColors) -> PdfBackground {
+    match background {
+        crate::config::PdfBackground::White => PdfBackground::WHITE,
+        crate::config::PdfBackground::Theme => {
+            let [r, g, b, _] = colors.bg.to_array();
+            PdfBackground::Color([r, g, b])
+        }
+        crate::config::PdfBackground::Transparent => PdfBackground::Transparent,
     }
 }
 
+fn extract_page_text(
+    doc: &pdfium_bind::PdfDocument,
+    page: isize,
+) -> Option<pdfium_bind::PdfPageText> {
+    match doc.page_text(page) {
+        Ok(text) if !text.chars.is_empty() => Some(text),
+        Ok(_) => None,
+        Err(e) => {
+            error!("Error extracting PDF page text: {}", e);
+            None
+        }
+    }
+}
+
+fn extract_page_links(doc: &pdfium_bind::PdfDocument, page: isize) -> Vec<pdfium_bind::PdfLink> {
+    doc.page_links(page).unwrap_or_else(|e| {
+        error!("Error extracting PDF page links: {}", e);
+        Vec::new()
+    })
+}
+
+/// Only web and mail links are opened, other schemes could run local files
+fn is_openable_uri(uri: &str) -> bool {
+    let scheme = uri
+        .split_once(':')
+        .map(|(scheme, _)| scheme.to_ascii_lowercase());
+    matches!(scheme.as_deref(), Some("http" | "https" | "mailto"))
+}
+
 impl PdfViewerContent {
-    pub fn render_page(&mut self, ctx: &egui::Context) -> Result<(), String> {
+    pub fn new(
+        meta: PdfMeta,
+        path: PathBuf,
+        doc: Arc<Mutex<pdfium_bind::PdfDocument>>,
+        ctx: &egui::Context,
+    ) -> Self {
+        let (page_text, page_size, outline, page_label, links) =
+            doc.lock()
+                .map_or((None, (0.0, 0.0), Vec::new(), None, Vec::new()), |doc| {
+                    (
+                        extract_page_text(&doc, meta.current_page),
+                        doc.page_size(meta.current_page).unwrap_or_default(),
+                        doc.outline(),
+                        doc.page_label(meta.current_page),
+                        extract_page_links(&doc, meta.current_page),
+                    )
+                });
+        let repaint_ctx = ctx.clone();
+        let renderer = RenderWorker::new(doc.clone(), move || repaint_ctx.request_repaint());
+        Self {
+            meta,
+            path,
+            doc,
+            page_text,
+            selection: None,
+            search: PdfSearch::default(),
+            outline,
+            page_label,
+            page_size,
+            tile: None,
+            links,
+            image_export: None,
+            renderer,
+            pending_page: None,
+            pending_tile: None,
+            rotation: PdfRotation::None,
+            background: PdfBackground::WHITE,
+        }
+    }
+
+    /// Start rendering the current page in the background and load its text
+    /// and links
+    pub fn render_page(&mut self) -> Result<(), String> {
+        self.pending_page = Some(self.renderer.submit(RenderRequest {
+            page: self.meta.current_page,
+            kind: RenderKind::Page {
+                dpi: crate::ui::preview::pdf::HIGH_DPI,
+                rotation: self.rotation,
+            },
+            background: self.background,
+        }));
+        if let Some((id, _)) = self.pending_tile.take() {
+            self.renderer.cancel(id);
+        }
+
         let doc_lock = self.doc.lock().map_err(|_| "Failed to lock PDF doc")?;
-        let rendered = crate::ui::preview::pdf::render_pdf_page_high_dpi(
-            &doc_lock,
-            self.meta.current_page,
-            Some(&self.meta.file_id),
-            ctx,
-        )?;
-
-        self.meta.cover = rendered.img_source;
-        self.meta._texture_handle = Some(rendered.texture_handle);
+        self.page_text = extract_page_text(&doc_lock, self.meta.current_page);
+        self.page_size = doc_lock
+            .page_size(self.meta.current_page)
+            .unwrap_or_default();
+        self.page_label = doc_lock.page_label(self.meta.current_page);
+        self.links = extract_page_links(&doc_lock, self.meta.current_page);
+        self.tile = None;
+        self.selection = None;
+        self.search.matches = self.search.find(&doc_lock, self.meta.current_page);
+        self.search.current = 0;
         Ok(())
     }
 
+    /// Show the pages and tiles rendered since the last frame
+    pub fn poll_renders(&mut self, ctx: &egui::Context) {
+        while let Some(response) = self.renderer.try_recv() {
+            let RenderResponse {
+                id,
+                request,
+                result,
+            } = response;
+            let is_page = self.pending_page == Some(id);
+            let is_tile = self.pending_tile.is_some_and(|(tile_id, _)| tile_id == id);
+            if is_page {
+                self.pending_page = None;
+            } else if is_tile {
+                self.pending_tile = None;
+            } else {
+                continue;
+            }
+            let bitmap = match result {
+                Ok(bitmap) => bitmap,
+                Err(e) => {
+                    error!("Error rendering PDF page: {}", e);
+                    continue;
+                }
+            };
+            let image = egui::ColorImage::from_rgba_unmultiplied(
+                [bitmap.width as usize, bitmap.height as usize],
+                &bitmap.pixels,
+            );
+            match request.kind {
+                RenderKind::Page { dpi, rotation } => {
+                    let texture = ctx.load_texture(
+                        format!(
+                            "pdf_doc_{}_page_{}_dpi_{dpi}_rotation_{}",
+                            self.meta.file_id,
+                            request.page,
+                            rotation.degrees()
+                        ),
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    );
+                    self.meta.cover = egui::ImageSource::from(&texture);
+                    self.meta._texture_handle = Some(texture);
+                }
+                RenderKind::Region { rect, scale } => {
+                    let texture = ctx.load_texture(
+                        format!("pdf_doc_{}_tile", self.meta.file_id),
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    );
+                    self.tile = Some(PdfTile {
+                        page: request.page,
+                        rect,
+                        scale,
+                        texture,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Render the pages on another background
+    pub fn set_background(&mut self, background: PdfBackground) {
+        if self.background == background {
+            return;
+        }
+        self.background = background;
+        if let Err(e) = self.render_page() {
+            error!("Error rendering PDF page: {}", e);
+        }
+    }
+
+    /// Turn the pages a quarter clockwise
+    pub fn rotate(&mut self, ctx: &egui::Context) {
+        self.rotation = self.rotation.clockwise();
+        if let Err(e) = self.render_page() {
+            error!("Error rendering PDF page: {}", e);
+        }
+        ctx.request_repaint();
+    }
+
+    /// Save the images embedded in the document next to it in the background
+    pub fn export_images(&mut self, ctx: &egui::Context) {
+        let running = self
+            .image_export
+            .as_ref()
+            .is_some_and(|export| export.lock().is_ok_and(|result| result.is_none()));
+        if running {
+            return;
+        }
+        let export = ImageExport::default();
+        self.image_export = Some(export.clone());
+        let doc = self.doc.clone();
+        let path = self.path.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let result = export_images(&doc, &path);
+            if let Ok(mut export) = export.lock() {
+                *export = Some(result);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Show another page of the document
+    pub fn go_to_page(&mut self, ctx: &egui::Context, page: isize) {
+        if page == self.meta.current_page || !(0..self.meta.page_count).contains(&page) {
+            return;
+        }
+        self.meta.current_page = page;
+        self.update_page_num_text(ctx);
+        if let Err(e) = self.render_page() {
+            error!("Error rendering PDF page: {}", e);
+        }
+        ctx.request_repaint();
+    }
+
+    pub fn search_input_id(&self) -> egui::Id {
+        egui::Id::new(&self.meta.file_id).with("pdf_search")
+    }
+
+    /// Update the matches after the query changed
+    pub fn update_search(&mut self, ctx: &egui::Context) {
+        if let Ok(doc) = self.doc.lock() {
+            self.search.matches = self.search.find(&doc, self.meta.current_page);
+        }
+        self.search.current = 0;
+        if self.search.matches.is_empty() {
+            self.find_next(ctx, true);
+        }
+    }
+
+    /// Go to the next or previous match, continuing on the other pages
+    pub fn find_next(&mut self, ctx: &egui::Context, forward: bool) {
+        if self.search.query.is_empty() {
+            self.search.matches.clear();
+            return;
+        }
+
+        let search = &mut self.search;
+        if forward && search.current + 1 < search.matches.len() {
+            search.current += 1;
+            ctx.request_repaint();
+            return;
+        }
+        if !forward && search.current > 0 && !search.matches.is_empty() {
+            search.current -= 1;
+            ctx.request_repaint();
+            return;
+        }
+
+        // Find the closest page with matches, wrapping around the document
+        let page_count = self.meta.page_count;
+        let current_page = self.meta.current_page;
+        let found = {
+            let Ok(doc) = self.doc.lock() else {
+                return;
+            };
+            (1..=page_count)
+                .map(|offset| {
+                    let page = if forward {
+                        current_page + offset
+                    } else {
+                        current_page - offset
+                    };
+                    page.rem_euclid(page_count)
+                })
+                .find(|&page| !self.search.find(&doc, page).is_empty())
+        };
+        let Some(page) = found else {
+            return;
+        };
+
+        if page != current_page {
+            self.meta.current_page = page;
+            self.update_page_num_text(ctx);
+            if let Err(e) = self.render_page() {
+                error!("Error rendering PDF page: {}", e);
+                return;
+            }
+        }
+        self.search.current = if forward {
+            0
+        } else {
+            self.search.matches.len().saturating_sub(1)
+        };
+        ctx.request_repaint();
+    }
+
+    /// Text of the selection
+    pub fn selected_text(&self) -> Option<String> {
+        let (anchor, cursor) = self.selection?;
+        let text = self.page_text.as_ref()?;
+        Some(text.text(anchor.min(cursor)..anchor.max(cursor) + 1))
+    }
+
+    pub fn select_all(&mut self) {
+        self.selection = self
+            .page_text
+            .as_ref()
+            .filter(|text| !text.chars.is_empty())
+            .map(|text| (0, text.chars.len() - 1));
+    }
+
+    /// Copy the selected text to the clipboard, or the whole page without a selection
+    pub fn copy_text(&self, ctx: &egui::Context) {
+        let text = self.selected_text().or_else(|| {
+            self.page_text
+                .as_ref()
+                .map(|text| text.text(0..text.chars.len()))
+        });
+        if let Some(text) = text {
+            ctx.copy_text(text);
+        }
+    }
+
     pub fn update_page_num_text(&self, ctx: &egui::Context) {
         let input_id = self.meta.page_input_id();
         // in the UI, we display the first page as 1 instead of 0
@@ -88,7 +486,13 @@ impl crate::ui::popup::PopupApp for PdfViewer {
 }
 
 impl PdfViewer {
-    pub fn draw(&mut self, ctx: &egui::Context, colors: &AppColors) -> bool {
+    pub fn draw(
+        &mut self,
+        ctx: &egui::Context,
+        colors: &AppColors,
+        zoom_mode: &mut ZoomMode,
+        background: crate::config::PdfBackground,
+    ) -> bool {
         let mut keep_open = true;
         let screen_size = ctx.content_rect().size();
         let popup_size = egui::vec2(screen_size.x * 0.9, screen_size.y * 0.9);
@@ -100,7 +504,8 @@ impl PdfViewer {
             .open(&mut keep_open)
             .show(ctx, |ui| match self {
                 Self::Loaded(pdf_meta) => {
-                    render_popup(ui, pdf_meta, colors);
+                    pdf_meta.set_background(page_background(background, colors));
+                    render_popup(ui, pdf_meta, colors, zoom_mode);
                 }
                 Self::Loading(path, _, _) => {
                     crate::ui::popup::preview::render_loading(ui, path, colors);
@@ -115,7 +520,14 @@ impl PdfViewer {
 }
 
 /// Render PDF in popup with page navigation
-pub fn render_popup(ui: &mut egui::Ui, viewer_content: &mut PdfViewerContent, colors: &AppColors) {
+pub fn render_popup(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    colors: &AppColors,
+    zoom_mode: &mut ZoomMode,
+) {
+    viewer_content.poll_renders(ui.ctx());
+
     // Get current page and total pages
     let current_page = viewer_content.meta.current_page;
     let total_pages = viewer_content.meta.page_count;
@@ -123,9 +535,13 @@ pub fn render_popup(ui: &mut egui::Ui, viewer_content: &mut PdfViewerContent, co
     ui.vertical_centered(|ui| {
         // Create a constrained horizontal container that only takes the space it needs
         ui.allocate_ui_with_layout(
-            egui::vec2(200.0, 30.0), // Fixed width container for the navigation controls
+            egui::vec2(820.0, 30.0), // Fixed width container for the navigation, zoom and search controls
             egui::Layout::left_to_right(egui::Align::Center),
             |ui| {
+                if !viewer_content.outline.is_empty() {
+                    render_outline_menu(ui, viewer_content, colors);
+                }
+
                 // Previous page button (left arrow)
                 if ui
                     .add_enabled(
@@ -192,10 +608,20 @@ pub fn render_popup(ui: &mut egui::Ui, viewer_content: &mut PdfViewerContent, co
 
                     // Label showing "of X"
                     ui.label(
-                        RichText::new(format!("of {total_pages}"))
+                        RichText::new(format!("of {}", format_count(total_pages.max(0) as u64)))
                             .color(colors.fg)
                             .size(14.0),
                     );
+                    // Page label when it differs from the page number, e.g. "iv"
+                    if let Some(label) = &viewer_content.page_label
+                        && *label != (current_page + 1).to_string()
+                    {
+                        ui.label(
+                            RichText::new(format!("({label})"))
+                                .color(colors.fg_light)
+                                .size(14.0),
+                        );
+                    }
                 });
 
                 // Next page button (right arrow)
@@ -210,6 +636,23 @@ pub fn render_popup(ui: &mut egui::Ui, viewer_content: &mut PdfViewerContent, co
                 {
                     navigate_to_next_page(viewer_content, ui.ctx());
                 }
+
+                ui.add_space(16.0);
+                crate::ui::preview::image::zoom_mode_selector(ui, zoom_mode);
+                if ui
+                    .add(
+                        Button::new(RichText::new("⟳").size(16.0).color(colors.fg))
+                            .min_size(egui::vec2(24.0, 24.0)),
+                    )
+                    .on_hover_text("Rotate clockwise")
+                    .clicked()
+                {
+                    viewer_content.rotate(ui.ctx());
+                }
+                render_export_images(ui, viewer_content, colors);
+
+                ui.add_space(16.0);
+                render_search_controls(ui, viewer_content, colors);
             },
         );
     });
@@ -222,14 +665,453 @@ pub fn render_popup(ui: &mut egui::Ui, viewer_content: &mut PdfViewerContent, co
     let remaining_width = ui.available_width();
     let remaining_height = ui.available_height();
     let pdf_image = egui::Image::new(viewer_content.meta.cover.clone());
-    let page_id = egui::Id::new(&viewer_content.meta.file_id).with(current_page);
-    crate::ui::preview::image::render_interactive(
+    let page_id = egui::Id::new(&viewer_content.meta.file_id)
+        .with(current_page)
+        .with(viewer_content.rotation);
+    // Dragging selects text, pages with no text can still be panned by dragging
+    let has_text = viewer_content.page_text.is_some();
+    let view = crate::ui::preview::image::render_interactive_view(
         ui,
         &pdf_image,
         page_id,
         remaining_width,
         remaining_height,
+        ViewOptions {
+            drag_to_pan: !has_text,
+            zoom_mode: *zoom_mode,
+            // Pages are rendered at HIGH_DPI, 100% shows them at 72 points per inch
+            actual_size_zoom: Some(72.0 / crate::ui::preview::pdf::HIGH_DPI),
+            ..Default::default()
+        },
     );
+    // The text and links already belong to the page being rendered
+    if let Some(view) = view
+        && viewer_content.pending_page.is_none()
+    {
+        draw_zoom_tile(ui, viewer_content, &view);
+        draw_text_layer(ui, viewer_content, &view, colors);
+        draw_links(ui, viewer_content, &view);
+    }
+}
+
+/// Save the images of every page as PNG files next to the document, named
+/// after it and the page, e.g. `report-page3-1.png`
+///
+/// Returns the number of images saved.
+fn export_images(doc: &Mutex<pdfium_bind::PdfDocument>, path: &Path) -> Result<usize, String> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let lock = || {
+        doc.lock()
+            .map_err(|_| "Failed to lock PDF document".to_string())
+    };
+    let page_count = lock()?.page_count();
+
+    let mut saved = 0;
+    for page in 0..page_count {
+        // Only lock the document for a page at a time, pages keep rendering
+        let images = lock()?.page_images(page)?;
+        for (index, image) in images.into_iter().enumerate() {
+            let Some(buffer) =
+                image::RgbaImage::from_raw(image.width as u32, image.height as u32, image.pixels)
+            else {
+                continue;
+            };
+            let name = format!("{stem}-page{}-{}.png", page + 1, index + 1);
+            let target = unique_path(dir, &name);
+            buffer
+                .save(&target)
+                .map_err(|e| format!("Failed to save {}: {e}", target.display()))?;
+            saved += 1;
+        }
+    }
+    Ok(saved)
+}
+
+/// Button saving the embedded images, with the result of the last export
+fn render_export_images(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    colors: &AppColors,
+) {
+    let result = viewer_content
+        .image_export
+        .as_ref()
+        .map(|export| export.lock().ok().and_then(|result| result.clone()));
+    match result {
+        Some(None) => {
+            ui.spinner();
+        }
+        _ => {
+            if ui
+                .add(
+                    Button::new(RichText::new("🖼").size(16.0).color(colors.fg))
+                        .min_size(egui::vec2(24.0, 24.0)),
+                )
+                .on_hover_text("Export images next to the document")
+                .clicked()
+            {
+                viewer_content.export_images(ui.ctx());
+            }
+        }
+    }
+    match result.flatten() {
+        Some(Ok(saved)) => {
+            ui.label(
+                RichText::new(format!("{} image(s) saved", format_count(saved as u64)))
+                    .color(colors.success),
+            );
+        }
+        Some(Err(e)) => {
+            ui.label(RichText::new("Export failed").color(colors.error))
+                .on_hover_text(e);
+        }
+        None => {}
+    }
+}
+
+/// Draw a sharp render of the visible region when zoomed in past the
+/// resolution of the page texture
+fn draw_zoom_tile(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    view: &crate::ui::preview::image::ImageView,
+) {
+    // Regions are rendered upright, only the page texture is rotated
+    if viewer_content.rotation != PdfRotation::None {
+        viewer_content.tile = None;
+        return;
+    }
+    let (page_width, page_height) = viewer_content.page_size;
+    let Some(texture) = &viewer_content.meta._texture_handle else {
+        return;
+    };
+    if page_width <= 0.0 || page_height <= 0.0 {
+        return;
+    }
+    // Screen points per page point
+    let view_scale = view.paint_rect.width() / page_width;
+    let scale = view_scale * ui.ctx().pixels_per_point();
+    if scale <= texture.size()[0] as f32 / page_width {
+        viewer_content.tile = None;
+        return;
+    }
+
+    let origin = view.paint_rect.min;
+    let visible = view.paint_rect.intersect(view.response.rect);
+    if !visible.is_positive() {
+        return;
+    }
+    let rect = [
+        ((visible.min.x - origin.x) / view_scale).max(0.0),
+        ((visible.min.y - origin.y) / view_scale).max(0.0),
+        ((visible.max.x - origin.x) / view_scale).min(page_width),
+        ((visible.max.y - origin.y) / view_scale).min(page_height),
+    ];
+
+    let page = viewer_content.meta.current_page;
+    let covers = |tile_page: isize, [left, top, right, bottom]: [f32; 4], tile_scale: f32| {
+        tile_page == page
+            && (tile_scale - scale).abs() <= scale * 0.01
+            && left <= rect[0] + 0.5
+            && top <= rect[1] + 0.5
+            && right >= rect[2] - 0.5
+            && bottom >= rect[3] - 0.5
+    };
+    let up_to_date = viewer_content
+        .tile
+        .as_ref()
+        .is_some_and(|tile| covers(tile.page, tile.rect, tile.scale))
+        || viewer_content
+            .pending_tile
+            .is_some_and(|(_, request)| match request.kind {
+                RenderKind::Region { rect, scale } => covers(request.page, rect, scale),
+                RenderKind::Page { .. } => false,
+            });
+    // Keep showing the previous tile while panning or zooming, rendering on
+    // every frame would make the view stutter
+    let moving = view.response.dragged()
+        || ui.input(|i| i.smooth_scroll_delta != egui::Vec2::ZERO || i.zoom_delta() != 1.0);
+    if !up_to_date {
+        if moving {
+            ui.ctx().request_repaint();
+        } else {
+            let request = RenderRequest {
+                page,
+                kind: RenderKind::Region { rect, scale },
+                background: viewer_content.background,
+            };
+            let id = viewer_content.renderer.submit(request);
+            viewer_content.pending_tile = Some((id, request));
+        }
+    }
+
+    let Some(tile) = &viewer_content.tile else {
+        return;
+    };
+    if tile.page != page {
+        return;
+    }
+    let [left, top, right, bottom] = tile.rect;
+    let tile_rect = egui::Rect::from_min_max(
+        origin + egui::vec2(left, top) * view_scale,
+        origin + egui::vec2(right, bottom) * view_scale,
+    );
+    ui.painter_at(view.response.rect).image(
+        tile.texture.id(),
+        tile_rect,
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+        egui::Color32::WHITE,
+    );
+}
+
+/// Menu listing the outline of the document, clicking an entry goes to its page
+fn render_outline_menu(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    colors: &AppColors,
+) {
+    let mut target = None;
+    ui.menu_button(RichText::new("☰").size(16.0).color(colors.fg), |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(ui.ctx().content_rect().height() * 0.6)
+            .show(ui, |ui| {
+                let current_page = viewer_content.meta.current_page;
+                let mut stack: Vec<_> = viewer_content
+                    .outline
+                    .iter()
+                    .rev()
+                    .map(|i| (i, 0))
+                    .collect();
+                while let Some((item, depth)) = stack.pop() {
+                    ui.horizontal(|ui| {
+                        ui.add_space(depth as f32 * 12.0);
+                        let text = RichText::new(&item.title).color(if item.page.is_some() {
+                            colors.fg
+                        } else {
+                            colors.fg_light
+                        });
+                        let selected = item.page == Some(current_page);
+                        if ui.selectable_label(selected, text).clicked()
+                            && let Some(page) = item.page
+                        {
+                            target = Some(page);
+                            ui.close();
+                        }
+                    });
+                    stack.extend(item.children.iter().rev().map(|child| (child, depth + 1)));
+                }
+            });
+    })
+    .response
+    .on_hover_text("Table of contents");
+
+    if let Some(page) = target {
+        viewer_content.go_to_page(ui.ctx(), page);
+    }
+}
+
+/// Search box with the match count and buttons to go through the matches
+fn render_search_controls(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    colors: &AppColors,
+) {
+    let input_id = viewer_content.search_input_id();
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut viewer_content.search.query)
+            .id(input_id)
+            .hint_text("Search")
+            .desired_width(140.0),
+    );
+    if response.changed() {
+        viewer_content.update_search(ui.ctx());
+    }
+    if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+        let forward = !ui.input(|i| i.modifiers.shift);
+        viewer_content.find_next(ui.ctx(), forward);
+        // Keep typing or pressing Enter for the next match
+        ui.memory_mut(|m| m.request_focus(input_id));
+    }
+
+    if viewer_content.search.query.is_empty() {
+        return;
+    }
+    let search = &viewer_content.search;
+    let count = if search.matches.is_empty() {
+        "0/0".to_string()
+    } else {
+        format!(
+            "{}/{}",
+            format_count(search.current as u64 + 1),
+            format_count(search.matches.len() as u64)
+        )
+    };
+    ui.label(RichText::new(count).color(colors.fg_light));
+    if ui
+        .add(Button::new(RichText::new("◀").color(colors.fg)))
+        .on_hover_text("Previous match (Shift+Enter)")
+        .clicked()
+    {
+        viewer_content.find_next(ui.ctx(), false);
+    }
+    if ui
+        .add(Button::new(RichText::new("▶").color(colors.fg)))
+        .on_hover_text("Next match (Enter)")
+        .clicked()
+    {
+        viewer_content.find_next(ui.ctx(), true);
+    }
+}
+
+/// Handle text selection with the mouse and highlight the selected characters
+fn draw_text_layer(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    view: &crate::ui::preview::image::ImageView,
+    colors: &AppColors,
+) {
+    let Some(page_text) = &viewer_content.page_text else {
+        return;
+    };
+    if page_text.width <= 0.0 {
+        return;
+    }
+    let rotation = viewer_content.rotation;
+    let (width, height) = (page_text.width, page_text.height);
+    let scale = view.paint_rect.width() / rotation.rotated_size(width, height).0;
+    let origin = view.paint_rect.min;
+    let char_at = |pos: egui::Pos2| {
+        let point = (pos - origin) / scale;
+        let (x, y) = rotation.unrotate_point(point.x, point.y, width, height);
+        page_text.char_at(x, y)
+    };
+    let to_screen = |rect: [f32; 4]| {
+        let [left, top, right, bottom] = rotation.rotate_rect(rect, width, height);
+        egui::Rect::from_min_max(
+            origin + egui::vec2(left, top) * scale,
+            origin + egui::vec2(right, bottom) * scale,
+        )
+    };
+
+    let response = &view.response;
+    if let Some(pos) = response.hover_pos()
+        && view.paint_rect.contains(pos)
+    {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Text);
+    }
+    if response.drag_started_by(egui::PointerButton::Primary)
+        && let Some(index) = ui.input(|i| i.pointer.press_origin()).and_then(char_at)
+    {
+        viewer_content.selection = Some((index, index));
+    } else if response.dragged_by(egui::PointerButton::Primary)
+        && let Some((anchor, _)) = viewer_content.selection
+        && let Some(index) = response.interact_pointer_pos().and_then(char_at)
+    {
+        viewer_content.selection = Some((anchor, index));
+    } else if response.clicked() {
+        viewer_content.selection = None;
+    }
+
+    let painter = ui.painter_at(response.rect);
+    let search = &viewer_content.search;
+    for (index, range) in search.matches.iter().enumerate() {
+        let alpha = if index == search.current { 0.6 } else { 0.3 };
+        let color = colors.warn.gamma_multiply(alpha);
+        for c in page_text.chars.get(range.clone()).unwrap_or_default() {
+            if let Some(rect) = c.rect {
+                painter.rect_filled(to_screen(rect), 0.0, color);
+            }
+        }
+    }
+
+    if let Some((anchor, cursor)) = viewer_content.selection {
+        let highlight = colors.highlight.gamma_multiply(0.35);
+        for c in &page_text.chars[anchor.min(cursor)..=anchor.max(cursor)] {
+            if let Some(rect) = c.rect {
+                painter.rect_filled(to_screen(rect), 0.0, highlight);
+            }
+        }
+    }
+
+    // Ctrl+C while text is selected
+    if viewer_content.selection.is_some()
+        && ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)))
+    {
+        viewer_content.copy_text(ui.ctx());
+    }
+
+    response.context_menu(|ui| {
+        if viewer_content.selection.is_some() && ui.button("Copy").clicked() {
+            viewer_content.copy_text(ui.ctx());
+            ui.close();
+        }
+        if ui.button("Copy page text").clicked() {
+            viewer_content.selection = None;
+            viewer_content.copy_text(ui.ctx());
+            ui.close();
+        }
+        if ui.button("Select all").clicked() {
+            viewer_content.select_all();
+            ui.close();
+        }
+    });
+}
+
+/// Make the links of the page clickable, going to their page or opening their URI
+fn draw_links(
+    ui: &mut egui::Ui,
+    viewer_content: &mut PdfViewerContent,
+    view: &crate::ui::preview::image::ImageView,
+) {
+    let (page_width, page_height) = viewer_content.page_size;
+    if viewer_content.links.is_empty() || page_width <= 0.0 {
+        return;
+    }
+    let response = &view.response;
+    let Some(pos) = response.hover_pos() else {
+        return;
+    };
+    let rotation = viewer_content.rotation;
+    let scale = view.paint_rect.width() / rotation.rotated_size(page_width, page_height).0;
+    let point = (pos - view.paint_rect.min) / scale;
+    let (x, y) = rotation.unrotate_point(point.x, point.y, page_width, page_height);
+    let Some(link) = viewer_content.links.iter().find(|link| {
+        let [left, top, right, bottom] = link.rect;
+        (left..=right).contains(&x) && (top..=bottom).contains(&y)
+    }) else {
+        return;
+    };
+
+    let hover_text = match &link.target {
+        pdfium_bind::PdfLinkTarget::Page(page) => format!("Go to page {}", page + 1),
+        pdfium_bind::PdfLinkTarget::Uri(uri) => uri.clone(),
+    };
+    let openable = match &link.target {
+        pdfium_bind::PdfLinkTarget::Page(_) => true,
+        pdfium_bind::PdfLinkTarget::Uri(uri) => is_openable_uri(uri),
+    };
+    if openable {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+    let response = response.clone().on_hover_text_at_pointer(hover_text);
+
+    if !openable || !response.clicked() {
+        return;
+    }
+    match link.target.clone() {
+        pdfium_bind::PdfLinkTarget::Page(page) => {
+            viewer_content.go_to_page(ui.ctx(), page);
+        }
+        pdfium_bind::PdfLinkTarget::Uri(uri) => {
+            if let Err(e) = open::that(&uri) {
+                error!("Failed to open link {}: {}", uri, e);
+            }
+        }
+    }
 }
 
 /// Helper function to navigate to the next page in PDF
@@ -241,7 +1123,7 @@ pub fn navigate_to_next_page(viewer_content: &mut PdfViewerContent, ctx: &egui::
     }
     viewer_content.meta.current_page += 1;
     viewer_content.update_page_num_text(ctx);
-    if let Err(e) = viewer_content.render_page(ctx) {
+    if let Err(e) = viewer_content.render_page() {
         error!("Error rendering PDF page: {}", e);
         return;
     }
@@ -256,7 +1138,7 @@ pub fn navigate_to_previous_page(viewer_content: &mut PdfViewerContent, ctx: &eg
     }
     viewer_content.meta.current_page = (current_page - 1).max(0);
     viewer_content.update_page_num_text(ctx);
-    if let Err(e) = viewer_content.render_page(ctx) {
+    if let Err(e) = viewer_content.render_page() {
         error!("Error rendering PDF page: {}", e);
         return;
     }
@@ -269,7 +1151,7 @@ fn render_pdf_page_for_popup(
     viewer_content: &mut PdfViewerContent,
 ) -> Result<(), String> {
     let ctx = ui.ctx();
-    viewer_content.render_page(ctx)?;
+    viewer_content.render_page()?;
     ctx.request_repaint();
     Ok(())
 }
@@ -284,6 +1166,11 @@ pub fn handle_preview_popup_input_pdf(
 ) {
     use crate::config::shortcuts::{self, ShortcutAction, ShortcutKey, TraverseResult};
 
+    // Typing in the page number or search box
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+
     let shortcuts = shortcuts::get_default_shortcuts();
     let shortcut_key = ShortcutKey { key, modifiers };
     if let TraverseResult::Action(action) = shortcuts.traverse_tree(&[shortcut_key]) {
@@ -294,6 +1181,19 @@ pub fn handle_preview_popup_input_pdf(
             ShortcutAction::PageDown => {
                 navigate_to_next_page(viewer_content, ctx);
             }
+            ShortcutAction::CopyEntry => {
+                viewer_content.copy_text(ctx);
+            }
+            ShortcutAction::SelectAllEntries => {
+                viewer_content.select_all();
+            }
+            ShortcutAction::ActivateSearch => {
+                let input_id = viewer_content.search_input_id();
+                ctx.memory_mut(|m| m.request_focus(input_id));
+            }
+            ShortcutAction::RotatePreview => {
+                viewer_content.rotate(ctx);
+            }
             _ => {
                 // Other actions are not handled in preview popup
             }
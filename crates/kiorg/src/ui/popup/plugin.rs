@@ -2,6 +2,7 @@ use crate::app::Kiorg;
 use crate::config::shortcuts::ShortcutAction;
 use crate::plugins::manager::{FailedPlugin, LoadedPlugin};
 use egui_extras::{Column, TableBuilder};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use super::window_utils::show_center_popup_window;
@@ -86,23 +87,30 @@ fn display_plugins_table<'a>(
 }
 
 /// Helper function to display failed plugins in a grid layout
+///
+/// Returns the path of a changed plugin the user chose to trust again.
 fn display_failed_plugins_grid<'a>(
     ui: &mut egui::Ui,
     grid_id: &str,
     plugins: impl Iterator<Item = &'a FailedPlugin>,
     colors: &crate::config::colors::AppColors,
-) {
+) -> Option<PathBuf> {
+    let mut trusted = None;
     egui::Grid::new(grid_id)
-        .num_columns(2)
+        .num_columns(3)
         .max_col_width(400.0)
         .spacing([20.0, 2.0])
         .show(ui, |ui| {
             for failed_plugin in plugins {
                 ui.label(failed_plugin.path.to_string_lossy());
                 ui.colored_label(colors.error, &failed_plugin.error);
+                if failed_plugin.changed && ui.button("Trust").clicked() {
+                    trusted = Some(failed_plugin.path.clone());
+                }
                 ui.end_row();
             }
         });
+    trusted
 }
 
 fn close_popup(app: &mut Kiorg) {
@@ -128,6 +136,7 @@ pub fn draw(app: &mut Kiorg, ctx: &egui::Context) {
 
     let loaded_plugins_map = app.plugin_manager.list_loaded();
     let failed_plugins_map = app.plugin_manager.list_failed();
+    let mut trusted = None;
     let _ = show_center_popup_window("Plugins", ctx, &mut keep_open, |ui| {
         if loaded_plugins_map.is_empty() && failed_plugins_map.is_empty() {
             ui.label("No plugins found");
@@ -144,7 +153,7 @@ pub fn draw(app: &mut Kiorg, ctx: &egui::Context) {
                         ui.add_space(10.0);
                     }
                     ui.colored_label(app.colors.fg_light, "Failed to load plugins");
-                    display_failed_plugins_grid(
+                    trusted = display_failed_plugins_grid(
                         ui,
                         "failed_plugins_list_grid",
                         failed_plugins_map.iter(),
@@ -155,6 +164,12 @@ pub fn draw(app: &mut Kiorg, ctx: &egui::Context) {
         }
     });
 
+    if let Some(path) = trusted
+        && let Err(e) = app.plugin_manager.trust_plugin(&path)
+    {
+        app.notify_error(format!("Failed to load plugin: {e}"));
+    }
+
     if !keep_open {
         close_popup(app);
     }
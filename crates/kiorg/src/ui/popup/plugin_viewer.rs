@@ -1,3 +1,4 @@
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::preview_content::RenderedComponent;
 use crate::ui::file_list::truncate_text;
@@ -15,6 +16,15 @@ pub struct PluginContent {
     pub components: Vec<RenderedComponent>,
 }
 
+impl PluginContent {
+    /// Whether an image can be panned and zoomed, which the zoom mode applies to
+    pub fn has_interactive_image(&self) -> bool {
+        self.components.iter().any(
+            |component| matches!(component, RenderedComponent::Image(image) if image.interactive),
+        )
+    }
+}
+
 /// Dedicated state for the Plugin viewer app
 #[derive(Debug)]
 pub enum PluginViewer {
@@ -67,7 +77,12 @@ impl crate::ui::popup::PopupApp for PluginViewer {
 }
 
 impl PluginViewer {
-    pub fn draw(&mut self, ctx: &egui::Context, colors: &AppColors) -> bool {
+    pub fn draw(
+        &mut self,
+        ctx: &egui::Context,
+        colors: &AppColors,
+        zoom_mode: &mut ZoomMode,
+    ) -> bool {
         let mut keep_open = true;
         let screen_size = ctx.content_rect().size();
         let popup_size = egui::vec2(screen_size.x * 0.9, screen_size.y * 0.9);
@@ -80,17 +95,29 @@ impl PluginViewer {
             .min_size(popup_size)
             .open(&mut keep_open)
             .show(ctx, |ui| {
+                if let Self::Loaded(content) = self {
+                    crate::ui::popup::preview::find::draw_bar(ui, colors);
+                    if content.has_interactive_image() {
+                        ui.horizontal(|ui| {
+                            crate::ui::preview::image::zoom_mode_selector(ui, zoom_mode);
+                        });
+                    }
+                }
                 let available_width = ui.available_width();
                 let available_height = ui.available_height();
 
                 match self {
                     Self::Loaded(content) => {
+                        let filter = crate::ui::popup::preview::find::query(ctx)
+                            .map(|query| query.to_lowercase());
                         crate::ui::preview::plugin::render(
                             ui,
                             &content.components,
                             colors,
                             available_width,
                             available_height,
+                            filter.as_deref(),
+                            *zoom_mode,
                         );
                     }
                     Self::Loading(_, path, _, _cancel_sender) => {
@@ -102,6 +129,9 @@ impl PluginViewer {
                 }
             });
 
+        if !keep_open {
+            crate::ui::popup::preview::find::close(ctx);
+        }
         keep_open
     }
 }
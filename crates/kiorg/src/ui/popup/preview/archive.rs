@@ -0,0 +1,471 @@
+//! Split view for zip/tar archives in the preview popup
+//!
+//! The entry list is shown on the left. Selecting a file extracts just that
+//! entry into memory and previews it on the right. Archives nested inside the
+//! archive are listed one level deep. Encrypted zip entries are decrypted with
+//! the password cached for the session, prompting for it when missing.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use egui::RichText;
+
+use crate::config::ZoomMode;
+use crate::config::colors::AppColors;
+use crate::models::preview_content::{TarEntry, ZipEntry};
+use crate::utils::archive::{ArchiveFormat, MemberReadError};
+
+/// Maximum number of bytes extracted from an archive entry for preview
+const MAX_ENTRY_PREVIEW_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Fraction of the popup width used by the entry list
+const LIST_WIDTH_RATIO: f32 = 0.4;
+
+#[derive(Clone, Copy, Debug)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+}
+
+/// Preview content of a single entry extracted from an archive
+#[derive(Clone)]
+enum EntryContent {
+    Text(String),
+    HighlightedCode {
+        content: String,
+        language: &'static str,
+    },
+    Image(egui::TextureHandle),
+    /// Entries of an archive nested inside the selected archive
+    Zip(Vec<ZipEntry>),
+    Tar(Vec<TarEntry>),
+    /// Encrypted entry that needs a (different) password
+    Encrypted {
+        invalid_password: bool,
+    },
+    /// Entries that can't be previewed inline, with a short description
+    Unsupported(String),
+}
+
+type EntryResult = Arc<Mutex<Option<Result<EntryContent, String>>>>;
+
+/// Currently selected archive entry, kept in egui temp memory
+#[derive(Clone)]
+struct SelectedEntry {
+    archive_path: PathBuf,
+    name: String,
+    size: u64,
+    /// Password the entry was read with
+    password: Option<String>,
+    result: EntryResult,
+}
+
+fn selection_id() -> egui::Id {
+    egui::Id::new("archive_popup_selected_entry")
+}
+
+fn password_request_id() -> egui::Id {
+    egui::Id::new("archive_popup_password_request")
+}
+
+/// Take the archive whose password the user asked to enter, if any
+pub fn take_password_request(ctx: &egui::Context) -> Option<PathBuf> {
+    ctx.data_mut(|d| d.remove_temp::<PathBuf>(password_request_id()))
+}
+
+/// Render a zip archive with an inline preview of the selected entry
+pub fn render_zip(
+    ui: &mut egui::Ui,
+    archive_path: &Path,
+    entries: &[ZipEntry],
+    filter: Option<&str>,
+    colors: &AppColors,
+    available_width: f32,
+    available_height: f32,
+) {
+    render_split(
+        ui,
+        archive_path,
+        ArchiveKind::Zip,
+        colors,
+        available_width,
+        available_height,
+        |ui, selected| {
+            crate::ui::preview::zip::render_selectable(ui, entries, colors, selected, filter)
+                .map(|entry| (entry.name.clone(), entry.size))
+        },
+    );
+}
+
+/// Render a tar archive with an inline preview of the selected entry
+pub fn render_tar(
+    ui: &mut egui::Ui,
+    archive_path: &Path,
+    entries: &[TarEntry],
+    filter: Option<&str>,
+    colors: &AppColors,
+    available_width: f32,
+    available_height: f32,
+) {
+    render_split(
+        ui,
+        archive_path,
+        ArchiveKind::Tar,
+        colors,
+        available_width,
+        available_height,
+        |ui, selected| {
+            crate::ui::preview::tar::render_selectable(ui, entries, colors, selected, filter)
+                .map(|entry| (entry.name.clone(), entry.size))
+        },
+    );
+}
+
+fn render_split(
+    ui: &mut egui::Ui,
+    archive_path: &Path,
+    kind: ArchiveKind,
+    colors: &AppColors,
+    available_width: f32,
+    available_height: f32,
+    draw_list: impl FnOnce(&mut egui::Ui, Option<&str>) -> Option<(String, u64)>,
+) {
+    // Only keep the selection if it belongs to the archive being shown
+    let mut selected = ui
+        .ctx()
+        .data(|d| d.get_temp::<SelectedEntry>(selection_id()))
+        .filter(|s| s.archive_path == archive_path);
+
+    // Retry the selected entry once a new password has been entered
+    let password = crate::utils::archive::cached_password(archive_path);
+    if let Some(entry) = &selected
+        && password.is_some()
+        && password != entry.password
+    {
+        let entry = load_entry(
+            ui.ctx(),
+            archive_path.to_path_buf(),
+            kind,
+            entry.name.clone(),
+            entry.size,
+            password.clone(),
+        );
+        ui.ctx()
+            .data_mut(|d| d.insert_temp(selection_id(), entry.clone()));
+        selected = Some(entry);
+    }
+
+    let list_width = available_width * LIST_WIDTH_RATIO;
+    let mut clicked = None;
+
+    ui.horizontal_top(|ui| {
+        ui.allocate_ui_with_layout(
+            egui::vec2(list_width, available_height),
+            egui::Layout::top_down(egui::Align::Min),
+            |ui| {
+                ui.set_min_size(egui::vec2(list_width, available_height));
+                clicked = draw_list(ui, selected.as_ref().map(|s| s.name.as_str()));
+            },
+        );
+        ui.separator();
+
+        let preview_width = ui.available_width();
+        ui.allocate_ui_with_layout(
+            egui::vec2(preview_width, available_height),
+            egui::Layout::top_down(egui::Align::Min),
+            |ui| {
+                ui.set_min_size(egui::vec2(preview_width, available_height));
+                render_entry(
+                    ui,
+                    selected.as_ref(),
+                    colors,
+                    preview_width,
+                    available_height,
+                );
+            },
+        );
+    });
+
+    if let Some((name, size)) = clicked
+        && selected.as_ref().is_none_or(|s| s.name != name)
+    {
+        let entry = load_entry(
+            ui.ctx(),
+            archive_path.to_path_buf(),
+            kind,
+            name,
+            size,
+            password,
+        );
+        ui.ctx().data_mut(|d| d.insert_temp(selection_id(), entry));
+    }
+}
+
+fn render_entry(
+    ui: &mut egui::Ui,
+    selected: Option<&SelectedEntry>,
+    colors: &AppColors,
+    available_width: f32,
+    available_height: f32,
+) {
+    let Some(selected) = selected else {
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.label(RichText::new("Select a file to preview its content").color(colors.fg_light));
+        });
+        return;
+    };
+
+    let result = selected.result.lock().ok().and_then(|r| r.clone());
+    match result {
+        None => super::render_loading(ui, Path::new(&selected.name), colors),
+        Some(Err(e)) => super::render_error(ui, &e, colors),
+        Some(Ok(EntryContent::Text(text))) => {
+            egui::ScrollArea::both()
+                .id_salt("archive_entry_text_scroll")
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    let mut text_str = text.as_str();
+                    ui.add(
+                        egui::TextEdit::multiline(&mut text_str)
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(0)
+                            .font(egui::TextStyle::Monospace)
+                            .text_color(colors.fg)
+                            .interactive(false),
+                    );
+                });
+        }
+        Some(Ok(EntryContent::HighlightedCode { content, language })) => {
+            egui::ScrollArea::both()
+                .id_salt("archive_entry_code_scroll")
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    crate::ui::preview::text::render_highlighted(ui, &content, language, colors);
+                });
+        }
+        Some(Ok(EntryContent::Image(texture))) => {
+            let image = egui::Image::new(egui::ImageSource::Texture(
+                egui::load::SizedTexture::from_handle(&texture),
+            ));
+            crate::ui::preview::image::render_interactive(
+                ui,
+                &image,
+                egui::Id::new(&selected.archive_path).with(&selected.name),
+                available_width,
+                available_height,
+                false,
+                ZoomMode::FitPage,
+            );
+        }
+        Some(Ok(EntryContent::Zip(entries))) => {
+            crate::ui::preview::zip::render(ui, &entries, colors);
+        }
+        Some(Ok(EntryContent::Tar(entries))) => {
+            crate::ui::preview::tar::render(ui, &entries, colors);
+        }
+        Some(Ok(EntryContent::Encrypted { invalid_password })) => {
+            ui.vertical_centered(|ui| {
+                ui.add_space(20.0);
+                let message = if invalid_password {
+                    "Incorrect password"
+                } else {
+                    "This entry is encrypted"
+                };
+                ui.label(RichText::new(message).color(colors.fg));
+                ui.add_space(10.0);
+                if ui.button("Enter password").clicked() {
+                    ui.ctx().data_mut(|d| {
+                        d.insert_temp(password_request_id(), selected.archive_path.clone());
+                    });
+                }
+            });
+        }
+        Some(Ok(EntryContent::Unsupported(info))) => {
+            ui.vertical_centered(|ui| {
+                ui.add_space(20.0);
+                ui.label(RichText::new(info).color(colors.fg));
+            });
+        }
+    }
+}
+
+/// Extract and decode an archive entry on a background thread
+fn load_entry(
+    ctx: &egui::Context,
+    archive_path: PathBuf,
+    kind: ArchiveKind,
+    name: String,
+    size: u64,
+    password: Option<String>,
+) -> SelectedEntry {
+    let result: EntryResult = Arc::new(Mutex::new(None));
+    let selected = SelectedEntry {
+        archive_path: archive_path.clone(),
+        name: name.clone(),
+        size,
+        password: password.clone(),
+        result: result.clone(),
+    };
+
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let content =
+            match read_entry_content(&ctx, &archive_path, kind, &name, size, password.as_deref()) {
+                Err(MemberReadError::PasswordRequired) => Ok(EntryContent::Encrypted {
+                    invalid_password: false,
+                }),
+                Err(MemberReadError::InvalidPassword) => {
+                    crate::utils::archive::forget_password(&archive_path);
+                    Ok(EntryContent::Encrypted {
+                        invalid_password: true,
+                    })
+                }
+                Err(MemberReadError::Other(e)) => Err(e),
+                Ok(content) => Ok(content),
+            };
+        if let Ok(mut slot) = result.lock() {
+            *slot = Some(content);
+        }
+        ctx.request_repaint();
+    });
+
+    selected
+}
+
+fn read_entry_content(
+    ctx: &egui::Context,
+    archive_path: &Path,
+    kind: ArchiveKind,
+    name: &str,
+    size: u64,
+    password: Option<&str>,
+) -> Result<EntryContent, MemberReadError> {
+    let entry_path = Path::new(name);
+    let is_image = matches!(
+        crate::ui::preview::path_to_ext_info(entry_path).as_str(),
+        crate::ui::preview::image_extensions!()
+    );
+    let nested_archive = ArchiveFormat::from_path(entry_path);
+    // Images and nested archives can't be handled from a truncated prefix
+    if (is_image || nested_archive.is_some()) && size > MAX_ENTRY_PREVIEW_SIZE {
+        return Ok(EntryContent::Unsupported(format!(
+            "Preview disabled for entries larger than {}\n\nEntry size: {}",
+            crate::utils::format::format_size(MAX_ENTRY_PREVIEW_SIZE, false),
+            crate::utils::format::format_size(size, false),
+        )));
+    }
+
+    let bytes = match kind {
+        ArchiveKind::Zip => crate::ui::preview::zip::read_zip_entry_bytes(
+            archive_path,
+            name,
+            MAX_ENTRY_PREVIEW_SIZE,
+            password,
+        )?,
+        ArchiveKind::Tar => crate::ui::preview::tar::read_tar_entry_bytes(
+            archive_path,
+            name,
+            MAX_ENTRY_PREVIEW_SIZE,
+        )
+        .map_err(MemberReadError::Other)?,
+    };
+
+    match nested_archive {
+        Some(ArchiveFormat::Zip) => {
+            return crate::ui::preview::zip::zip_entries_from_reader(std::io::Cursor::new(bytes))
+                .map(EntryContent::Zip)
+                .map_err(MemberReadError::Other);
+        }
+        Some(ArchiveFormat::Tar) => {
+            let mut tar = crate::utils::archive::tar_archive_from_reader(
+                std::io::Cursor::new(bytes),
+                entry_path,
+            );
+            return crate::ui::preview::tar::tar_entries_from_archive(&mut tar)
+                .map(EntryContent::Tar)
+                .map_err(MemberReadError::Other);
+        }
+        None => {}
+    }
+
+    if is_image {
+        let img = image::load_from_memory(&bytes)
+            .map_err(|e| MemberReadError::Other(format!("Failed to decode image: {e}")))?;
+        let rgba8 = img.to_rgba8();
+        let size = [rgba8.width() as usize, rgba8.height() as usize];
+        let color_image =
+            egui::ColorImage::from_rgba_unmultiplied(size, rgba8.as_flat_samples().as_slice());
+        let texture = ctx.load_texture(
+            format!("archive_entry:{}:{name}", archive_path.display()),
+            color_image,
+            egui::TextureOptions::default(),
+        );
+        return Ok(EntryContent::Image(texture));
+    }
+
+    Ok(bytes_to_text_content(entry_path, bytes, size))
+}
+
+fn bytes_to_text_content(entry_path: &Path, bytes: Vec<u8>, size: u64) -> EntryContent {
+    let content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(e) => {
+            // Truncated entries may end in the middle of a multi-byte character
+            let valid_up_to = e.utf8_error().valid_up_to();
+            let bytes = e.into_bytes();
+            if size > MAX_ENTRY_PREVIEW_SIZE && valid_up_to + 4 > bytes.len() {
+                String::from_utf8_lossy(&bytes[..valid_up_to]).into_owned()
+            } else {
+                return EntryContent::Unsupported(format!(
+                    "Binary file\n\nSize: {}",
+                    crate::utils::format::format_size(size, false)
+                ));
+            }
+        }
+    };
+
+    match crate::ui::preview::text::find_syntax_from_path(entry_path) {
+        Some(syntax) => EntryContent::HighlightedCode {
+            content,
+            language: syntax.name.as_str(),
+        },
+        None => EntryContent::Text(content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_to_text_content() {
+        let content = bytes_to_text_content(Path::new("notes.unknownext"), b"hello".to_vec(), 5);
+        assert!(matches!(content, EntryContent::Text(ref t) if t == "hello"));
+
+        let content = bytes_to_text_content(Path::new("main.rs"), b"fn main() {}".to_vec(), 12);
+        assert!(matches!(
+            content,
+            EntryContent::HighlightedCode {
+                language: "Rust",
+                ..
+            }
+        ));
+
+        let content = bytes_to_text_content(Path::new("blob.bin"), vec![0xff, 0xfe, 0x00], 3);
+        assert!(matches!(content, EntryContent::Unsupported(_)));
+    }
+
+    #[test]
+    fn test_bytes_to_text_content_truncated_utf8() {
+        // "é" is two bytes, cut off after the first one
+        let mut bytes = b"abc".to_vec();
+        bytes.push(0xc3);
+        let content = bytes_to_text_content(
+            Path::new("notes.unknownext"),
+            bytes,
+            MAX_ENTRY_PREVIEW_SIZE + 1,
+        );
+        assert!(matches!(content, EntryContent::Text(ref t) if t == "abc"));
+    }
+}
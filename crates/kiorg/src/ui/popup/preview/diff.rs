@@ -0,0 +1,353 @@
+//! Diff mode of the preview popup, comparing the two marked files
+//!
+//! Both files are highlighted whole, each row then copies the sections of
+//! its line, so the highlighting matches the regular preview.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use egui::text::{LayoutJob, LayoutSection};
+use egui::{Align2, Rect, RichText, Sense};
+
+use crate::app::Kiorg;
+use crate::config::colors::AppColors;
+use crate::models::dir_entry::DirEntryMeta;
+use crate::models::preview_content::PreviewContent;
+use crate::ui::popup::PopupType;
+use crate::ui::preview::loading::create_load_popup_meta_task;
+use crate::ui::preview::text;
+use crate::utils::diff::{FileDiff, HunkHeader, SplitRow, UnifiedRow};
+
+/// Space between the line numbers and the text
+const GUTTER_PADDING: f32 = 12.0;
+
+/// Two files compared in the preview popup, the older one is shown first
+#[derive(Clone, Debug)]
+pub struct DiffState {
+    pub old_path: PathBuf,
+    pub new_path: PathBuf,
+    pub diff: FileDiff,
+    /// Syntax both files are highlighted as, detected from the newer one
+    language: &'static str,
+    pub side_by_side: bool,
+}
+
+impl DiffState {
+    pub fn load(old_path: PathBuf, new_path: PathBuf) -> Result<Self, String> {
+        let diff = FileDiff::from_files(&old_path, &new_path)?;
+        let language = text::find_syntax_from_path(&new_path)
+            .or_else(|| text::find_syntax_from_path(&old_path))
+            .map_or("Plain Text", |syntax| syntax.name.as_str());
+        Ok(Self {
+            old_path,
+            new_path,
+            diff,
+            language,
+            side_by_side: false,
+        })
+    }
+
+    /// Popup title naming both files
+    #[must_use]
+    pub fn title(&self) -> String {
+        format!(
+            "{} ↔ {}",
+            file_name(&self.old_path),
+            file_name(&self.new_path)
+        )
+    }
+}
+
+/// Marked files to compare, older one first, `None` unless exactly two files are marked
+fn marked_pair(app: &Kiorg) -> Option<(DirEntryMeta, DirEntryMeta)> {
+    let marked = &app.tab_manager.current_tab_ref().marked_entries;
+    if marked.len() != 2 || !marked.iter().all(|path| path.is_file()) {
+        return None;
+    }
+    let mut entries: Vec<DirEntryMeta> = marked
+        .iter()
+        .map(|path| DirEntryMeta {
+            path: path.clone(),
+            modified: std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH),
+        })
+        .collect();
+    entries.sort_by(|a, b| (a.modified, &a.path).cmp(&(b.modified, &b.path)));
+    let new = entries.pop()?;
+    let old = entries.pop()?;
+    Some((old, new))
+}
+
+/// Handle the `ShowDiff` shortcut action
+///
+/// The files are compared on a background thread while the preview popup
+/// shows the loading state, like any other preview.
+pub fn open(app: &mut Kiorg) {
+    let Some((old, new)) = marked_pair(app) else {
+        app.notify_error("Mark exactly two files to compare them");
+        return;
+    };
+
+    if let Some(PreviewContent::Loading { cancel, .. }) = &app.preview_content {
+        let _ = cancel.send(());
+    }
+    let path = new.path.clone();
+    let (receiver, cancel) = create_load_popup_meta_task(new, move |new| {
+        DiffState::load(old.path, new.path).map(|state| PreviewContent::Diff(Box::new(state)))
+    });
+    app.preview_content = Some(PreviewContent::Loading {
+        path,
+        receiver,
+        cancel,
+    });
+    app.show_popup = Some(PopupType::Preview);
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Draw the diff in the preview popup
+pub fn render(ui: &mut egui::Ui, state: &mut DiffState, colors: &AppColors) {
+    draw_toolbar(ui, state, colors);
+    ui.separator();
+    if state.diff.is_identical() {
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new("The files are identical").color(colors.fg_light));
+        });
+        return;
+    }
+    draw_rows(ui, state, colors);
+}
+
+fn draw_toolbar(ui: &mut egui::Ui, state: &mut DiffState, colors: &AppColors) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(format!("+{}", state.diff.added)).color(colors.success));
+        ui.label(RichText::new(format!("-{}", state.diff.removed)).color(colors.error));
+        ui.separator();
+        ui.checkbox(
+            &mut state.side_by_side,
+            RichText::new("Side by side").color(colors.fg),
+        );
+    });
+}
+
+/// Sections of `job` within `range`, moved to the start of a new job
+fn line_job(job: &LayoutJob, range: Range<usize>) -> LayoutJob {
+    let first = job
+        .sections
+        .partition_point(|section| section.byte_range.end <= range.start);
+    let sections = job.sections[first..]
+        .iter()
+        .take_while(|section| section.byte_range.start < range.end)
+        .map(|section| LayoutSection {
+            leading_space: 0.0,
+            byte_range: section.byte_range.start.max(range.start) - range.start
+                ..section.byte_range.end.min(range.end) - range.start,
+            format: section.format.clone(),
+        })
+        .collect();
+    LayoutJob {
+        text: job.text[range].to_string(),
+        sections,
+        ..Default::default()
+    }
+}
+
+/// How a line differs from the other file
+#[derive(Clone, Copy)]
+enum Change {
+    Equal,
+    Removed,
+    Added,
+}
+
+/// Draws the cells of the visible rows
+struct RowPainter<'a> {
+    ui: &'a egui::Ui,
+    colors: &'a AppColors,
+    font_id: egui::FontId,
+    /// Width of a line number column
+    gutter_width: f32,
+    /// Width of the `+` or `-` marker column
+    marker_width: f32,
+}
+
+impl RowPainter<'_> {
+    /// Draw one line into `rect`, preceded by a column for each line number
+    fn line(
+        &self,
+        rect: Rect,
+        numbers: &[Option<usize>],
+        code: Option<(&LayoutJob, Range<usize>)>,
+        change: Change,
+    ) {
+        let painter = self.ui.painter_at(rect);
+        let (background, marker) = match change {
+            Change::Equal => (None, " "),
+            Change::Removed => (Some(self.colors.error.gamma_multiply(0.2)), "-"),
+            Change::Added => (Some(self.colors.success.gamma_multiply(0.2)), "+"),
+        };
+        // A missing line means the other file has more changed lines here
+        let fill = if code.is_some() {
+            background
+        } else {
+            Some(self.colors.bg_light)
+        };
+        if let Some(fill) = fill {
+            painter.rect_filled(rect, 0.0, fill);
+        }
+
+        let mut x = rect.min.x;
+        for number in numbers {
+            x += self.gutter_width;
+            if let Some(number) = number {
+                painter.text(
+                    egui::pos2(x - GUTTER_PADDING / 2.0, rect.min.y),
+                    Align2::RIGHT_TOP,
+                    (number + 1).to_string(),
+                    self.font_id.clone(),
+                    self.colors.fg_light,
+                );
+            }
+        }
+        let Some((job, range)) = code else {
+            return;
+        };
+        painter.text(
+            egui::pos2(x, rect.min.y),
+            Align2::LEFT_TOP,
+            marker,
+            self.font_id.clone(),
+            self.colors.fg_light,
+        );
+        let galley = painter.layout_job(line_job(job, range));
+        painter.galley(
+            egui::pos2(x + self.marker_width, rect.min.y),
+            galley,
+            self.colors.fg,
+        );
+    }
+
+    fn hunk(&self, rect: Rect, header: HunkHeader) {
+        let painter = self.ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, self.colors.bg_extreme);
+        painter.text(
+            egui::pos2(rect.min.x + GUTTER_PADDING / 2.0, rect.min.y),
+            Align2::LEFT_TOP,
+            header.to_string(),
+            self.font_id.clone(),
+            self.colors.highlight,
+        );
+    }
+}
+
+fn draw_rows(ui: &mut egui::Ui, state: &DiffState, colors: &AppColors) {
+    let diff = &state.diff;
+    let old_job = text::highlight(ui, &diff.old, state.language, colors);
+    let new_job = text::highlight(ui, &diff.new, state.language, colors);
+
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let digits = diff.line_count().to_string().len();
+    let sample = ui
+        .painter()
+        .layout_no_wrap("0".repeat(digits), font_id.clone(), colors.fg_light);
+    let row_height = sample.size().y;
+    let marker_width = sample.size().x / digits as f32 * 2.0;
+    let gutter_width = sample.size().x + GUTTER_PADDING;
+
+    let rows = if state.side_by_side {
+        diff.split.len()
+    } else {
+        diff.unified.len()
+    };
+    ui.spacing_mut().item_spacing.y = 0.0;
+    egui::ScrollArea::vertical().auto_shrink(false).show_rows(
+        ui,
+        row_height,
+        rows,
+        |ui, visible| {
+            for index in visible {
+                let (rect, _) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), row_height),
+                    Sense::hover(),
+                );
+                let cells = RowPainter {
+                    ui,
+                    colors,
+                    font_id: font_id.clone(),
+                    gutter_width,
+                    marker_width,
+                };
+                let old = |line: usize| Some((&old_job, diff.old_line(line)));
+                let new = |line: usize| Some((&new_job, diff.new_line(line)));
+
+                if !state.side_by_side {
+                    match diff.unified[index] {
+                        UnifiedRow::Hunk(header) => cells.hunk(rect, header),
+                        UnifiedRow::Equal { old: o, new: n } => {
+                            cells.line(rect, &[Some(o), Some(n)], new(n), Change::Equal);
+                        }
+                        UnifiedRow::Removed(o) => {
+                            cells.line(rect, &[Some(o), None], old(o), Change::Removed);
+                        }
+                        UnifiedRow::Added(n) => {
+                            cells.line(rect, &[None, Some(n)], new(n), Change::Added);
+                        }
+                    }
+                    continue;
+                }
+
+                let (left, right) = rect.split_left_right_at_fraction(0.5);
+                match diff.split[index] {
+                    SplitRow::Hunk(header) => cells.hunk(rect, header),
+                    SplitRow::Equal { old: o, new: n } => {
+                        cells.line(left, &[Some(o)], old(o), Change::Equal);
+                        cells.line(right, &[Some(n)], new(n), Change::Equal);
+                    }
+                    SplitRow::Changed { old: o, new: n } => {
+                        cells.line(left, &[o], o.and_then(old), Change::Removed);
+                        cells.line(right, &[n], n.and_then(new), Change::Added);
+                    }
+                }
+                // Divider between the two files
+                ui.painter().vline(
+                    left.max.x,
+                    rect.y_range(),
+                    egui::Stroke::new(1.0, colors.bg_light),
+                );
+            }
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Color32;
+
+    #[test]
+    fn test_line_job() {
+        let format = |color| egui::TextFormat::simple(egui::FontId::monospace(12.0), color);
+        let mut job = LayoutJob::default();
+        job.append("let a = 1;\n", 0.0, format(Color32::RED));
+        job.append("let b", 0.0, format(Color32::GREEN));
+        job.append(" = 2;\n", 0.0, format(Color32::BLUE));
+
+        let line = line_job(&job, 11..21);
+        assert_eq!(line.text, "let b = 2;");
+        let sections: Vec<_> = line
+            .sections
+            .iter()
+            .map(|s| (s.byte_range.clone(), s.format.color))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![(0..5, Color32::GREEN), (5..10, Color32::BLUE)]
+        );
+    }
+}
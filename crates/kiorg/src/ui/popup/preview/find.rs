@@ -0,0 +1,257 @@
+//! Find bar of the preview popup
+//!
+//! Ctrl+F opens a search field above the popup content. Matches are
+//! highlighted in text and code with next/previous navigation, while archive
+//! entry lists and table rows are filtered down to the matching ones.
+
+use std::ops::Range;
+
+use egui::text::LayoutJob;
+use egui::{Key, Modifiers, RichText};
+
+use crate::config::colors::AppColors;
+use crate::ui::preview::links;
+
+/// Matches beyond this are not highlighted, to keep huge files responsive
+const MAX_MATCHES: usize = 10_000;
+
+#[derive(Debug, Clone, Default)]
+struct FindState {
+    query: String,
+    /// Index of the current match
+    current: usize,
+    /// Number of matches in the content shown in the last frame
+    count: usize,
+    /// The content shown in the last frame supports next/previous navigation
+    navigable: bool,
+    /// Scroll to the current match in the next frame
+    scroll: bool,
+}
+
+impl FindState {
+    fn step(&mut self, forward: bool) {
+        if self.count == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % self.count
+        } else {
+            (self.current + self.count - 1) % self.count
+        };
+        self.scroll = true;
+    }
+}
+
+fn state_id() -> egui::Id {
+    egui::Id::new("preview_find_state")
+}
+
+fn input_id() -> egui::Id {
+    egui::Id::new("preview_find_input")
+}
+
+fn state(ctx: &egui::Context) -> Option<FindState> {
+    ctx.data(|d| d.get_temp::<FindState>(state_id()))
+}
+
+fn update(ctx: &egui::Context, f: impl FnOnce(&mut FindState)) {
+    ctx.data_mut(|d| {
+        if let Some(mut state) = d.get_temp::<FindState>(state_id()) {
+            f(&mut state);
+            d.insert_temp(state_id(), state);
+        }
+    });
+}
+
+/// Show the find bar and focus its search field
+pub fn open(ctx: &egui::Context) {
+    if state(ctx).is_none() {
+        ctx.data_mut(|d| d.insert_temp(state_id(), FindState::default()));
+    }
+    ctx.memory_mut(|m| m.request_focus(input_id()));
+}
+
+/// Hide the find bar, returns whether it was shown
+pub fn close(ctx: &egui::Context) -> bool {
+    ctx.data_mut(|d| d.remove_temp::<FindState>(state_id()))
+        .is_some()
+}
+
+/// Text searched for, `None` while the find bar is hidden or empty
+#[must_use]
+pub fn query(ctx: &egui::Context) -> Option<String> {
+    state(ctx)
+        .map(|state| state.query)
+        .filter(|query| !query.is_empty())
+}
+
+/// Handle a key press in the preview popup
+///
+/// Returns true when the find bar consumed the key.
+pub fn handle_key(ctx: &egui::Context, key: Key, modifiers: Modifiers) -> bool {
+    if key == Key::F && modifiers.command {
+        open(ctx);
+        return true;
+    }
+    if state(ctx).is_none() {
+        return false;
+    }
+    let focused = ctx.memory(|m| m.has_focus(input_id()));
+    match key {
+        Key::Escape => {
+            close(ctx);
+            true
+        }
+        // Enter is handled by the search field
+        Key::Enter => true,
+        Key::N if !focused => {
+            update(ctx, |state| state.step(!modifiers.shift));
+            true
+        }
+        _ => focused,
+    }
+}
+
+/// Draw the find bar when it's shown
+pub fn draw_bar(ui: &mut egui::Ui, colors: &AppColors) {
+    let Some(mut state) = state(ui.ctx()) else {
+        return;
+    };
+
+    let mut closed = false;
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Find:").color(colors.fg_light));
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut state.query)
+                .id(input_id())
+                .hint_text("Search the preview")
+                .desired_width(240.0),
+        );
+        if response.changed() {
+            state.current = 0;
+            state.scroll = true;
+        }
+        if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+            let forward = !ui.input(|i| i.modifiers.shift);
+            state.step(forward);
+            response.request_focus();
+        }
+
+        if state.navigable && !state.query.is_empty() {
+            let status = if state.count == 0 {
+                "No matches".to_string()
+            } else {
+                format!("{}/{}", state.current + 1, state.count)
+            };
+            ui.label(RichText::new(status).color(colors.fg_light));
+            if ui
+                .button("⏶")
+                .on_hover_text("Previous match (Shift+Enter)")
+                .clicked()
+            {
+                state.step(false);
+            }
+            if ui.button("⏷").on_hover_text("Next match (Enter)").clicked() {
+                state.step(true);
+            }
+        }
+        if ui.button("✕").on_hover_text("Close (Esc)").clicked() {
+            closed = true;
+        }
+    });
+    ui.separator();
+
+    if closed {
+        close(ui.ctx());
+        return;
+    }
+    // Content that supports navigation reports its matches again this frame
+    state.navigable = false;
+    ui.data_mut(|d| d.insert_temp(state_id(), state));
+}
+
+/// Byte ranges of the non-overlapping matches of `query`, ignoring ASCII case
+#[must_use]
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    let (text, query) = (text.as_bytes(), query.as_bytes());
+    if query.is_empty() {
+        return matches;
+    }
+    // Both are valid UTF-8, so a byte match always starts and ends on a char boundary
+    let mut start = 0;
+    while start + query.len() <= text.len() && matches.len() < MAX_MATCHES {
+        if text[start..start + query.len()].eq_ignore_ascii_case(query) {
+            matches.push(start..start + query.len());
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+/// Record the number of matches shown, returns the current match and
+/// whether to scroll to it
+fn current_match(ctx: &egui::Context, count: usize) -> (Option<usize>, bool) {
+    let mut result = (None, false);
+    update(ctx, |state| {
+        state.count = count;
+        state.navigable = true;
+        state.current = state.current.min(count.saturating_sub(1));
+        result = ((count > 0).then_some(state.current), state.scroll);
+        state.scroll = false;
+    });
+    result
+}
+
+/// Highlight the matches of `query` in `job`
+///
+/// Returns the char index of the current match when it changed and should be
+/// scrolled to.
+pub fn highlight_matches(
+    ctx: &egui::Context,
+    job: &mut LayoutJob,
+    query: &str,
+    colors: &AppColors,
+) -> Option<usize> {
+    let matches = find_matches(&job.text, query);
+    let (current, scroll) = current_match(ctx, matches.len());
+    let current_char = current.map(|index| job.text[..matches[index].start].chars().count());
+    links::format_ranges(job, &matches, |index, format| {
+        if Some(index) == current {
+            format.background = colors.highlight;
+            format.color = colors.bg;
+        } else {
+            format.background = colors.bg_selected;
+        }
+    });
+    current_char.filter(|_| scroll)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matches() {
+        let text = "Foo foo FOOfoo été";
+        assert_eq!(find_matches(text, "foo"), vec![0..3, 4..7, 8..11, 11..14]);
+        assert_eq!(find_matches(text, "été"), vec![15..20]);
+        assert!(find_matches(text, "").is_empty());
+        assert!(find_matches("aa", "aaa").is_empty());
+    }
+
+    #[test]
+    fn test_step_wraps_around() {
+        let mut state = FindState {
+            count: 3,
+            ..Default::default()
+        };
+        state.step(false);
+        assert_eq!(state.current, 2);
+        state.step(true);
+        assert_eq!(state.current, 0);
+        assert!(state.scroll);
+    }
+}
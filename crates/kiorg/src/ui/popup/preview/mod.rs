@@ -1,17 +1,26 @@
 //! Preview popup module for displaying file previews in a popup window
 
+pub mod archive;
+pub mod diff;
+pub mod find;
+pub mod text_view;
+
 use egui::Context;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use tracing::error;
 
 use crate::app::Kiorg;
+use crate::config;
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::preview_content::PreviewContent;
 use crate::ui::file_list::truncate_text;
 use crate::ui::popup::PopupApp;
 use crate::ui::popup::PopupType;
 use crate::ui::popup::window_utils::new_center_popup_window;
+use crate::ui::preview::links::LinkTarget;
 use crate::ui::preview::loading::create_load_popup_meta_task;
 
 fn available_screen_width(ctx: &Context) -> f32 {
@@ -30,7 +39,10 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
             (
                 selected_entry.is_dir,
                 selected_entry.clone(),
-                crate::ui::preview::path_to_ext_info(&selected_entry.meta.path),
+                crate::ui::preview::preview_ext_info(
+                    &selected_entry.meta.path,
+                    selected_entry.is_placeholder,
+                ),
             )
         } else {
             // No entry selected
@@ -39,11 +51,12 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
     };
     let path = &entry.meta.path;
 
-    if is_dir {
-        // Show preview popup for directories
+    // Directories, and the static metadata of files in safe mode
+    if is_dir || crate::ui::preview::is_safe_preview(app, ctx, path) {
         app.show_popup = Some(PopupType::Preview);
         return;
     }
+    app.record_file_history(path);
 
     // First check if any plugins can handle this file
     let plugin_result = if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
@@ -97,6 +110,7 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
             let path_buf = path.to_path_buf();
             let (rx, cancel_sender) =
                 create_load_popup_meta_task(entry.meta.clone(), move |entry| {
+                    let path = entry.path.clone();
                     let (mut meta, doc) =
                         crate::ui::preview::pdf::extract_pdf_metadata(entry, &ctx_clone)?;
                     let doc_arc = Arc::new(Mutex::new(doc));
@@ -112,7 +126,9 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
                         meta.cover = rendered.img_source;
                         meta._texture_handle = Some(rendered.texture_handle);
                     }
-                    Ok(crate::ui::popup::pdf_viewer::PdfViewerContent { meta, doc: doc_arc })
+                    Ok(crate::ui::popup::pdf_viewer::PdfViewerContent::new(
+                        meta, path, doc_arc, &ctx_clone,
+                    ))
                 });
             app.show_popup = Some(PopupType::Pdf(Box::new(PopupApp::loading(
                 path_buf,
@@ -122,9 +138,25 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
         }
         crate::ui::preview::epub_extensions!() => {
             let path_buf = path.to_path_buf();
-            let (rx, cancel_sender) = create_load_popup_meta_task(entry.meta.clone(), |entry| {
-                crate::ui::preview::ebook::extract_ebook_metadata(entry)
-            });
+            let positions_file =
+                crate::ui::popup::ebook_viewer::positions_file(app.config_dir_override.as_deref());
+            let (rx, cancel_sender) =
+                create_load_popup_meta_task(entry.meta.clone(), move |entry| {
+                    let path = entry.path.clone();
+                    let meta = crate::ui::preview::ebook::extract_ebook_metadata(entry)?;
+                    // Still show the cover if the chapters can't be read
+                    let chapters =
+                        crate::ui::preview::ebook::read_chapters(&path).unwrap_or_else(|e| {
+                            tracing::warn!("Failed to read ebook chapters: {}", e);
+                            Vec::new()
+                        });
+                    Ok(crate::ui::popup::ebook_viewer::EbookContent::new(
+                        meta,
+                        path,
+                        chapters,
+                        positions_file,
+                    ))
+                });
             app.show_popup = Some(PopupType::Ebook(Box::new(PopupApp::loading(
                 path_buf,
                 rx,
@@ -135,12 +167,18 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
             let path_buf = path.to_path_buf();
             let ctx_clone = ctx.clone();
             let available_width = available_screen_width(ctx);
+            let apply_orientation = app
+                .config
+                .preview
+                .as_ref()
+                .is_none_or(config::Preview::exif_orientation);
             let (rx, cancel_sender) =
                 create_load_popup_meta_task(entry.meta.clone(), move |entry| {
                     crate::ui::preview::image::read_image_with_metadata(
                         entry,
                         &ctx_clone,
                         Some(available_width),
+                        apply_orientation,
                     )
                 });
             app.show_popup = Some(PopupType::Image(Box::new(PopupApp::loading(
@@ -149,7 +187,9 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
                 cancel_sender,
             ))));
         }
-        crate::ui::preview::zip_extensions!() | crate::ui::preview::tar_extensions!() => {
+        crate::ui::preview::zip_extensions!()
+        | crate::ui::preview::tar_extensions!()
+        | crate::ui::preview::compressed_extensions!() => {
             app.show_popup = Some(PopupType::Preview);
         }
         crate::ui::preview::video_extensions!() => {
@@ -190,6 +230,16 @@ pub fn handle_show_file_popup(app: &mut Kiorg, ctx: &egui::Context) {
 }
 
 pub fn close_popup(app: &mut Kiorg) {
+    // Leaving the diff brings back the preview of the selected entry
+    if matches!(app.show_popup, Some(PopupType::Preview))
+        && matches!(
+            app.preview_content,
+            Some(PreviewContent::Diff(_) | PreviewContent::Loading { .. })
+        )
+    {
+        app.cached_preview_path = None;
+        app.selection_changed = true;
+    }
     app.show_popup = None;
 }
 
@@ -204,23 +254,50 @@ pub fn draw(ctx: &Context, app: &mut Kiorg) {
     let popup_size = egui::vec2(screen_size.x * 0.9, screen_size.y * 0.9);
     let popup_content_width = popup_size.x * 0.9; // Calculate once
 
-    let window_title = {
-        let tab = app.tab_manager.current_tab_ref();
-        let selected_entry = tab.selected_entry();
-        selected_entry.map_or_else(|| "File Preview".to_string(), |entry| entry.name.clone())
+    let (window_title, selected_path): (String, Option<PathBuf>) = match &app.preview_content {
+        Some(PreviewContent::Diff(state)) => (state.title(), None),
+        _ => {
+            let tab = app.tab_manager.current_tab_ref();
+            match tab.selected_entry() {
+                Some(entry) => (entry.name.clone(), Some(entry.meta.path.clone())),
+                None => ("File Preview".to_string(), None),
+            }
+        }
     };
 
+    let mut clicked_link = None;
+    let mut print = false;
+    let saved_options = text_view::TextViewOptions::from_config(app.config.preview.as_ref());
+    let mut text_options = saved_options;
     new_center_popup_window(&truncate_text(&window_title, popup_content_width))
         .max_size(popup_size)
         .min_size(popup_size)
         .open(&mut keep_open)
         .show(ctx, |ui| {
+            find::draw_bar(ui, &app.colors);
+            if app
+                .preview_content
+                .as_ref()
+                .is_some_and(PreviewContent::is_printable)
+            {
+                print = draw_print_button(ui);
+            }
+
             // Calculate available space in the popup
             let available_width = ui.available_width();
             let available_height = ui.available_height();
 
             if let Some(content) = &mut app.preview_content {
-                render_content(ui, content, &app.colors, available_width, available_height);
+                clicked_link = render_content(
+                    ui,
+                    content,
+                    selected_path.as_deref(),
+                    find::query(ctx).as_deref(),
+                    &mut text_options,
+                    &app.colors,
+                    available_width,
+                    available_height,
+                );
             } else {
                 ui.vertical_centered(|ui| {
                     ui.label("No preview content available");
@@ -228,57 +305,109 @@ pub fn draw(ctx: &Context, app: &mut Kiorg) {
             }
         });
 
+    if let Some(link) = clicked_link {
+        app.open_link(link);
+    }
+    if print {
+        app.print_selected_entry();
+    }
+    if text_options != saved_options {
+        text_options.save_to(app.config.preview.get_or_insert_default());
+        if let Err(e) =
+            config::save_config_with_override(&app.config, app.config_dir_override.as_deref())
+        {
+            app.toasts
+                .error(format!("Failed to save preview settings: {e}"));
+        }
+    }
+
     if !keep_open {
+        find::close(ctx);
+        text_view::reset(ctx);
         close_popup(app);
+    } else if let Some(archive_path) = archive::take_password_request(ctx) {
+        app.show_popup = Some(PopupType::ArchivePassword(
+            crate::ui::popup::archive_password::ArchivePasswordState::new(
+                archive_path,
+                crate::ui::popup::archive_password::PasswordPurpose::Preview,
+            ),
+        ));
     }
 }
 
+/// Right aligned Print button above the popup content, returns whether it was clicked
+pub fn draw_print_button(ui: &mut egui::Ui) -> bool {
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.button("🖨 Print")
+            .on_hover_text("Send the file to the default printer (Ctrl+P)")
+            .clicked()
+    })
+    .inner
+}
+
 fn render_content(
     ui: &mut egui::Ui,
     content: &mut PreviewContent,
+    path: Option<&Path>,
+    query: Option<&str>,
+    text_options: &mut text_view::TextViewOptions,
     colors: &AppColors,
     available_width: f32,
     available_height: f32,
-) {
+) -> Option<LinkTarget> {
+    let base_dir = path.and_then(Path::parent);
+    let mut clicked_link = None;
+    // Lists and tables are filtered case insensitively
+    let filter = query.map(str::to_lowercase);
+
     // Display the preview content based on its type
     match content {
         PreviewContent::Text(text) => {
-            // Display text with syntax highlighting if it's source code
+            text_view::draw_toolbar(ui, text_options, text, colors);
             egui::ScrollArea::both()
                 .auto_shrink([false; 2])
                 .show(ui, |ui| {
-                    let mut text_str = text.as_str();
-                    ui.add(
-                        egui::TextEdit::multiline(&mut text_str)
-                            .desired_width(f32::INFINITY)
-                            .desired_rows(0)
-                            .font(egui::TextStyle::Monospace)
-                            .text_color(colors.fg)
-                            .interactive(false),
+                    let job = egui::text::LayoutJob::single_section(
+                        text.clone(),
+                        egui::TextFormat::simple(
+                            egui::TextStyle::Monospace.resolve(ui.style()),
+                            colors.fg,
+                        ),
                     );
+                    let links = crate::ui::preview::links::cached_links(ui.ctx(), text, base_dir);
+                    clicked_link = text_view::show(ui, job, &links, query, *text_options, colors);
                 });
         }
         PreviewContent::HighlightedCode { content, language } => {
+            text_view::draw_toolbar(ui, text_options, content, colors);
             // Display syntax highlighted code with both horizontal and vertical scrolling
             egui::ScrollArea::both()
                 .auto_shrink([false; 2])
                 .show(ui, |ui| {
-                    crate::ui::preview::text::render_highlighted(ui, content, language);
+                    let job = crate::ui::preview::text::highlight(ui, content, language, colors);
+                    let links =
+                        crate::ui::preview::links::cached_links(ui.ctx(), content, base_dir);
+                    crate::ui::preview::text::render_code_frame(ui, colors, |ui| {
+                        clicked_link =
+                            text_view::show(ui, job, &links, query, *text_options, colors);
+                    });
                 });
         }
         PreviewContent::Image(image_meta) => {
             crate::ui::popup::image_viewer::render_popup(
                 ui,
                 image_meta,
+                colors,
                 available_width,
                 available_height,
+                None,
             );
         }
         PreviewContent::Video(_) | PreviewContent::Pdf(_) => {
             error!("Video and PDF should be rendered through their respective viewers");
         }
         PreviewContent::Ebook(ebook_meta) => {
-            crate::ui::popup::ebook_viewer::render_popup(
+            crate::ui::popup::ebook_viewer::render_cover(
                 ui,
                 ebook_meta,
                 colors,
@@ -286,20 +415,44 @@ fn render_content(
                 available_height,
             );
         }
-        PreviewContent::Zip(zip_entries) => {
+        PreviewContent::Html(html_meta) => {
             egui::ScrollArea::vertical()
-                .id_salt("zip_popup_scroll")
+                .auto_shrink([false; 2])
                 .show(ui, |ui| {
-                    crate::ui::preview::zip::render(ui, zip_entries, colors);
+                    clicked_link = crate::ui::preview::html::render(ui, html_meta, colors);
                 });
         }
-        PreviewContent::Tar(tar_entries) => {
+        PreviewContent::Binary(binary_meta) => {
             egui::ScrollArea::vertical()
-                .id_salt("tar_popup_scroll")
+                .auto_shrink([false; 2])
                 .show(ui, |ui| {
-                    crate::ui::preview::tar::render(ui, tar_entries, colors);
+                    crate::ui::preview::binary::render(ui, binary_meta, colors);
                 });
         }
+        PreviewContent::Zip(zip_entries) => match path {
+            Some(path) => archive::render_zip(
+                ui,
+                path,
+                zip_entries,
+                filter.as_deref(),
+                colors,
+                available_width,
+                available_height,
+            ),
+            None => crate::ui::preview::zip::render(ui, zip_entries, colors),
+        },
+        PreviewContent::Tar(tar_entries) => match path {
+            Some(path) => archive::render_tar(
+                ui,
+                path,
+                tar_entries,
+                filter.as_deref(),
+                colors,
+                available_width,
+                available_height,
+            ),
+            None => crate::ui::preview::tar::render(ui, tar_entries, colors),
+        },
         PreviewContent::PluginPreview { components } => {
             crate::ui::preview::plugin::render(
                 ui,
@@ -307,11 +460,21 @@ fn render_content(
                 colors,
                 available_width,
                 available_height,
+                filter.as_deref(),
+                ZoomMode::default(),
             );
         }
+        PreviewContent::Diff(state) => {
+            diff::render(ui, state, colors);
+        }
         PreviewContent::Loading { path, .. } => {
             render_loading(ui, path, colors);
         }
+        PreviewContent::Deferred { reason, .. } => {
+            ui.vertical_centered(|ui| {
+                ui.label(egui::RichText::new(reason.as_str()).color(colors.fg_light));
+            });
+        }
         // For other file types
         _ => {
             ui.vertical_centered(|ui| {
@@ -319,6 +482,8 @@ fn render_content(
             });
         }
     }
+
+    clicked_link
 }
 
 pub fn render_loading(ui: &mut egui::Ui, path: &std::path::Path, colors: &AppColors) {
@@ -0,0 +1,232 @@
+//! Text and code view of the preview popup
+//!
+//! A toolbar above the text toggles line numbers and soft wrapping, both
+//! saved in the `[preview]` config section, and jumps to a line with Ctrl+G.
+
+use egui::text::{CCursor, LayoutJob};
+use egui::{Align, Align2, Key, Modifiers, Rect, RichText, Sense};
+
+use crate::config::Preview;
+use crate::config::colors::AppColors;
+use crate::ui::popup::preview::find;
+use crate::ui::preview::links::{self, LinkTarget, TextLink};
+
+/// Space between the line numbers and the text
+const GUTTER_PADDING: f32 = 12.0;
+/// Narrowest width text is wrapped at
+const MIN_WRAP_WIDTH: f32 = 100.0;
+
+/// How text is shown, saved in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextViewOptions {
+    pub line_numbers: bool,
+    pub wrap: bool,
+}
+
+impl TextViewOptions {
+    #[must_use]
+    pub fn from_config(preview: Option<&Preview>) -> Self {
+        Self {
+            line_numbers: preview.and_then(|p| p.line_numbers).unwrap_or(false),
+            wrap: preview.and_then(|p| p.wrap_lines).unwrap_or(false),
+        }
+    }
+
+    pub fn save_to(self, preview: &mut Preview) {
+        preview.line_numbers = Some(self.line_numbers);
+        preview.wrap_lines = Some(self.wrap);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct GotoState {
+    input: String,
+    /// Line jumped to, highlighted until the popup closes
+    line: Option<usize>,
+    /// Scroll to the line in the next frame
+    scroll: bool,
+}
+
+fn goto_state_id() -> egui::Id {
+    egui::Id::new("preview_goto_line_state")
+}
+
+fn goto_input_id() -> egui::Id {
+    egui::Id::new("preview_goto_line_input")
+}
+
+fn goto_state(ctx: &egui::Context) -> GotoState {
+    ctx.data(|d| d.get_temp::<GotoState>(goto_state_id()))
+        .unwrap_or_default()
+}
+
+/// Forget the line jumped to, called when the popup closes
+pub fn reset(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.remove_temp::<GotoState>(goto_state_id()));
+}
+
+/// Handle a key press in the preview popup
+///
+/// Returns true when the go to line field consumed the key.
+pub fn handle_key(ctx: &egui::Context, key: Key, modifiers: Modifiers) -> bool {
+    if key == Key::G && modifiers.command {
+        ctx.memory_mut(|m| m.request_focus(goto_input_id()));
+        return true;
+    }
+    let focused = ctx.memory(|m| m.has_focus(goto_input_id()));
+    if focused && key == Key::Escape {
+        ctx.memory_mut(|m| m.surrender_focus(goto_input_id()));
+    }
+    focused
+}
+
+/// Number of lines as laid out, a trailing newline starts an empty line
+fn line_count(text: &str) -> usize {
+    text.bytes().filter(|&b| b == b'\n').count() + 1
+}
+
+/// Line typed in the go to line field, clamped to the last line
+fn parse_line(input: &str, line_count: usize) -> Option<usize> {
+    let line = input.trim().parse::<usize>().ok()?;
+    (line > 0).then(|| line.min(line_count))
+}
+
+/// Draw the line numbers and wrap toggles and the go to line field
+pub fn draw_toolbar(
+    ui: &mut egui::Ui,
+    options: &mut TextViewOptions,
+    text: &str,
+    colors: &AppColors,
+) {
+    let mut state = goto_state(ui.ctx());
+    let line_count = line_count(text);
+    ui.horizontal(|ui| {
+        ui.checkbox(
+            &mut options.line_numbers,
+            RichText::new("Line numbers").color(colors.fg),
+        );
+        ui.checkbox(&mut options.wrap, RichText::new("Wrap").color(colors.fg));
+        ui.separator();
+        ui.label(RichText::new("Go to line:").color(colors.fg_light));
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut state.input)
+                .id(goto_input_id())
+                .hint_text(format!("1-{line_count}"))
+                .desired_width(80.0),
+        );
+        if response.lost_focus()
+            && ui.input(|i| i.key_pressed(Key::Enter))
+            && let Some(line) = parse_line(&state.input, line_count)
+        {
+            state.line = Some(line);
+            state.scroll = true;
+        }
+    });
+    ui.data_mut(|d| d.insert_temp(goto_state_id(), state));
+}
+
+/// Show laid out text with the toolbar options applied
+///
+/// Matches of `query` are highlighted, otherwise `links` are clickable.
+/// Returns the link clicked in this frame, if any.
+pub fn show(
+    ui: &mut egui::Ui,
+    mut job: LayoutJob,
+    links: &[TextLink],
+    query: Option<&str>,
+    options: TextViewOptions,
+    colors: &AppColors,
+) -> Option<LinkTarget> {
+    let scroll_to_match = match query {
+        Some(query) => find::highlight_matches(ui.ctx(), &mut job, query, colors),
+        None => {
+            links::apply_link_format(&mut job, links, colors);
+            None
+        }
+    };
+
+    let font_id = job.sections.first().map_or_else(
+        || egui::TextStyle::Monospace.resolve(ui.style()),
+        |section| section.format.font_id.clone(),
+    );
+    let gutter_width = if options.line_numbers {
+        let digits = line_count(&job.text).to_string().len();
+        let sample =
+            ui.painter()
+                .layout_no_wrap("0".repeat(digits), font_id.clone(), colors.fg_light);
+        sample.size().x + GUTTER_PADDING
+    } else {
+        0.0
+    };
+    job.wrap.max_width = if options.wrap {
+        (ui.available_width() - gutter_width).max(MIN_WRAP_WIDTH)
+    } else {
+        f32::INFINITY
+    };
+
+    let galley = ui.painter().layout_job(job);
+    let size = egui::vec2(gutter_width + galley.size().x, galley.size().y);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    let origin = rect.min + egui::vec2(gutter_width, 0.0);
+
+    let mut goto = goto_state(ui.ctx());
+    let visible = ui.clip_rect();
+    let mut line = 1;
+    let mut line_start = true;
+    for row in &galley.rows {
+        let row_rect = row.rect().translate(origin.to_vec2());
+        if goto.line == Some(line) {
+            let line_rect = Rect::from_x_y_ranges(rect.x_range(), row_rect.y_range());
+            ui.painter().rect_filled(line_rect, 0.0, colors.bg_selected);
+            if goto.scroll && line_start {
+                ui.scroll_to_rect(line_rect, Some(Align::Center));
+            }
+        }
+        let row_visible = visible.y_range().intersects(row_rect.y_range());
+        if options.line_numbers && line_start && row_visible {
+            ui.painter().text(
+                egui::pos2(origin.x - GUTTER_PADDING / 2.0, row_rect.min.y),
+                Align2::RIGHT_TOP,
+                line.to_string(),
+                font_id.clone(),
+                colors.fg_light,
+            );
+        }
+        line_start = row.ends_with_newline;
+        if line_start {
+            line += 1;
+        }
+    }
+    if goto.scroll {
+        goto.scroll = false;
+        ui.data_mut(|d| d.insert_temp(goto_state_id(), goto));
+    }
+
+    ui.painter().galley(origin, galley.clone(), colors.fg);
+    if let Some(index) = scroll_to_match {
+        let match_rect = galley
+            .pos_from_cursor(CCursor::new(index))
+            .translate(origin.to_vec2());
+        ui.scroll_to_rect(match_rect, Some(Align::Center));
+    }
+
+    if query.is_some() {
+        return None;
+    }
+    links::link_at_pointer(ui, response, &galley, origin, links)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        let count = line_count("a\nb\nc\n");
+        assert_eq!(count, 4);
+        assert_eq!(parse_line(" 2 ", count), Some(2));
+        assert_eq!(parse_line("99", count), Some(4));
+        assert_eq!(parse_line("0", count), None);
+        assert_eq!(parse_line("two", count), None);
+    }
+}
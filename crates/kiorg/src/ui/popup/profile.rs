@@ -0,0 +1,153 @@
+use crate::app::Kiorg;
+use crate::config;
+use crate::ui::popup::PopupType;
+use crate::ui::popup::fuzzy_search_popup::{
+    FuzzyMatchResult, FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState,
+    fuzzy_filter,
+};
+use std::borrow::Cow;
+use std::path::Path;
+
+static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
+    title: "Profiles",
+    search_hint: "Switch profile or type a name to create one...",
+    empty_message: "No profiles found",
+    no_match_message: "No matching profiles found",
+    max_visible_results: Some(15),
+};
+
+/// State for the profile switch popup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileState {
+    pub query: String,
+    pub selected_index: usize,
+}
+
+/// A profile to switch to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileItem {
+    /// Profile name, `None` for the default profile
+    pub name: Option<String>,
+    pub note: Option<&'static str>,
+}
+
+impl FuzzySearchItem for ProfileItem {
+    fn display_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(
+            self.name
+                .as_deref()
+                .unwrap_or(config::profile::DEFAULT_PROFILE_NAME),
+        )
+    }
+
+    fn secondary_text(&self) -> Option<Cow<'_, str>> {
+        self.note.map(Cow::Borrowed)
+    }
+}
+
+/// Default profile followed by the named ones, plus an entry to create a
+/// profile named after the query if there is none with that name
+pub fn get_search_results(
+    query: &str,
+    base_dir: &Path,
+    active: Option<&str>,
+) -> Vec<FuzzyMatchResult<ProfileItem>> {
+    let names = config::profile::list(base_dir);
+    let note = |name: Option<&str>| (name == active).then_some("active");
+    let items: Vec<ProfileItem> = std::iter::once(None)
+        .chain(names.iter().map(|name| Some(name.as_str())))
+        .map(|name| ProfileItem {
+            name: name.map(str::to_string),
+            note: note(name),
+        })
+        .collect();
+    let mut results = fuzzy_filter(query, &items);
+
+    let query = query.trim();
+    if config::profile::validate_name(query).is_ok() && !names.iter().any(|name| name == query) {
+        results.push(FuzzyMatchResult {
+            item: ProfileItem {
+                name: Some(query.to_string()),
+                note: Some("create"),
+            },
+            score: 0,
+        });
+    }
+    results
+}
+
+/// Draw the profile switch popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::Profiles(state)) = &app.show_popup else {
+        return;
+    };
+
+    let mut fuzzy_state = FuzzySearchState::new(state.query.clone());
+    fuzzy_state.selected_index = state.selected_index;
+    let config_dir = config::get_kiorg_config_dir(app.config_dir_override.as_deref());
+    let results = get_search_results(
+        &fuzzy_state.query,
+        &config::profile::base_dir(&config_dir),
+        app.profile.as_deref(),
+    );
+
+    let action = crate::ui::popup::fuzzy_search_popup::draw(
+        ctx,
+        &POPUP_CONFIG,
+        &app.colors,
+        &mut fuzzy_state,
+        &results,
+    );
+
+    match action {
+        FuzzySearchAction::KeepOpen => {
+            app.show_popup = Some(PopupType::Profiles(ProfileState {
+                query: fuzzy_state.query,
+                selected_index: fuzzy_state.selected_index,
+            }));
+        }
+        FuzzySearchAction::Close => {
+            app.show_popup = None;
+        }
+        FuzzySearchAction::Selected(item) => {
+            app.show_popup = None;
+            if item.name != app.profile {
+                app.switch_profile(ctx, item.name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_search_results() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(config::profile::config_dir(temp_dir.path(), "work")).unwrap();
+
+        let names = |results: Vec<FuzzyMatchResult<ProfileItem>>| {
+            results
+                .into_iter()
+                .map(|r| (r.item.display_text().into_owned(), r.item.note))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(get_search_results("", temp_dir.path(), Some("work"))),
+            vec![
+                ("default".to_string(), None),
+                ("work".to_string(), Some("active")),
+            ]
+        );
+        assert_eq!(
+            names(get_search_results("work", temp_dir.path(), None)),
+            vec![("work".to_string(), None)]
+        );
+        assert_eq!(
+            names(get_search_results("home", temp_dir.path(), None)),
+            vec![("home".to_string(), Some("create"))]
+        );
+        assert!(get_search_results("a/b", temp_dir.path(), None).is_empty());
+    }
+}
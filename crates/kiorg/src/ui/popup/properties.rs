@@ -0,0 +1,261 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use egui::{Context, RichText};
+
+use super::PopupType;
+use super::window_utils::show_center_popup_window;
+use crate::app::Kiorg;
+use crate::config::colors::AppColors;
+use crate::utils::signature::{self, Signature, Status};
+use crate::utils::{cloud, disk_size, format};
+
+/// Signature checks running in the background, `None` until they finish
+pub type SignatureResult = Arc<Mutex<Option<Vec<Signature>>>>;
+
+/// Details of a file or directory, read when the popup is opened
+#[derive(Debug, Clone)]
+pub struct Properties {
+    pub path: PathBuf,
+    pub rows: Vec<(&'static str, String)>,
+    /// Code signatures of executables and installers
+    pub signatures: Option<SignatureResult>,
+}
+
+fn size_text(size: u64) -> String {
+    format!(
+        "{} ({} bytes)",
+        format::format_size(size, false),
+        format::format_count(size)
+    )
+}
+
+impl Properties {
+    /// Read the properties of `path` without following a symlink at `path`
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let metadata = std::fs::symlink_metadata(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut rows = Vec::new();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        rows.push(("Name", name));
+        if let Some(parent) = path.parent() {
+            rows.push(("Location", parent.display().to_string()));
+        }
+
+        let kind = if metadata.is_symlink() {
+            "Symbolic link"
+        } else if metadata.is_dir() {
+            "Directory"
+        } else {
+            "File"
+        };
+        rows.push(("Type", kind.to_string()));
+        if metadata.is_symlink() {
+            let target = std::fs::read_link(path)
+                .map(|target| target.display().to_string())
+                .unwrap_or_else(|e| format!("Unreadable: {e}"));
+            rows.push(("Target", target));
+        }
+
+        if metadata.is_dir() {
+            let items = std::fs::read_dir(path).map(Iterator::count);
+            if let Ok(items) = items {
+                rows.push((
+                    "Contains",
+                    format!("{} item(s)", format::format_count(items as u64)),
+                ));
+            }
+        } else {
+            let size = metadata.len();
+            rows.push(("Size", size_text(size)));
+            if let Some(on_disk) = disk_size::size_on_disk(path, &metadata) {
+                let is_placeholder = cloud::is_placeholder(path, &metadata);
+                let text = match disk_size::savings_reason(size, on_disk, is_placeholder) {
+                    Some(reason) => format!("{}, {reason}", size_text(on_disk)),
+                    None => size_text(on_disk),
+                };
+                rows.push(("Size on disk", text));
+            }
+        }
+
+        let times = [
+            ("Modified", metadata.modified()),
+            ("Created", metadata.created()),
+            ("Accessed", metadata.accessed()),
+        ];
+        for (label, time) in times {
+            if let Ok(time) = time {
+                rows.push((label, format::format_modified(time)));
+            }
+        }
+        rows.push(("Permissions", permissions(&metadata)));
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            rows,
+            signatures: None,
+        })
+    }
+
+    /// Verify code signatures in the background if `path` can carry one
+    fn check_signatures(&mut self, ctx: &Context) {
+        if cloud::is_placeholder_path(&self.path) || !signature::is_candidate(&self.path) {
+            return;
+        }
+        let result = SignatureResult::default();
+        self.signatures = Some(result.clone());
+        let path = self.path.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let signatures = signature::check(&path);
+            *result.lock().expect("signature result lock poisoned") = Some(signatures);
+            ctx.request_repaint();
+        });
+    }
+}
+
+#[cfg(unix)]
+fn permissions(metadata: &std::fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = metadata.permissions().mode();
+    let bits: String = (0..9)
+        .map(|i| {
+            let bit = 8 - i;
+            if mode & (1 << bit) == 0 {
+                '-'
+            } else {
+                ['x', 'w', 'r'][bit % 3]
+            }
+        })
+        .collect();
+    format!("{bits} ({:o})", mode & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn permissions(metadata: &std::fs::Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "Read-only".to_string()
+    } else {
+        "Read and write".to_string()
+    }
+}
+
+/// Open the properties popup for the selected entry
+pub fn open(app: &mut Kiorg, ctx: &Context) {
+    let Some(path) = app
+        .tab_manager
+        .current_tab_ref()
+        .selected_entry()
+        .map(|entry| entry.meta.path.clone())
+    else {
+        return;
+    };
+    match Properties::read(&path) {
+        Ok(mut properties) => {
+            properties.check_signatures(ctx);
+            app.show_popup = Some(PopupType::Properties(properties));
+        }
+        Err(e) => app.notify_error(e),
+    }
+}
+
+/// Draw the properties popup
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::Properties(properties)) = &app.show_popup else {
+        return;
+    };
+    let colors = &app.colors;
+
+    let mut keep_open = true;
+    let response = show_center_popup_window("Properties", ctx, &mut keep_open, |ui| {
+        egui::Grid::new("properties_grid")
+            .num_columns(2)
+            .spacing([12.0, 6.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for (label, value) in &properties.rows {
+                    ui.label(RichText::new(*label).color(colors.fg_light));
+                    ui.add(egui::Label::new(RichText::new(value).color(colors.fg)).wrap());
+                    ui.end_row();
+                }
+                if let Some(result) = &properties.signatures {
+                    draw_signatures(ui, result, colors);
+                }
+            });
+    });
+
+    if response.is_none() || !keep_open {
+        app.show_popup = None;
+    }
+}
+
+fn draw_signatures(ui: &mut egui::Ui, result: &SignatureResult, colors: &AppColors) {
+    let result = result.lock().expect("signature result lock poisoned");
+    let Some(signatures) = result.as_ref() else {
+        ui.label(RichText::new("Signature").color(colors.fg_light));
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(RichText::new("Verifying...").color(colors.fg_light));
+        });
+        ui.end_row();
+        return;
+    };
+    for signature in signatures {
+        let color = match signature.status {
+            Status::Valid => colors.success,
+            Status::Invalid => colors.error,
+            Status::Unsigned | Status::Unverified => colors.warn,
+        };
+        ui.label(RichText::new(signature.kind).color(colors.fg_light));
+        ui.add(egui::Label::new(RichText::new(&signature.detail).color(color)).wrap());
+        ui.end_row();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(properties: &'a Properties, label: &str) -> Option<&'a str> {
+        properties
+            .rows
+            .iter()
+            .find(|(name, _)| *name == label)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn test_read_properties() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("a.txt");
+        std::fs::write(&file, "hello").unwrap();
+
+        let properties = Properties::read(&file).unwrap();
+        assert_eq!(value(&properties, "Name"), Some("a.txt"));
+        assert_eq!(value(&properties, "Type"), Some("File"));
+        assert_eq!(value(&properties, "Size"), Some("5 B (5 bytes)"));
+        assert!(value(&properties, "Modified").is_some());
+
+        let properties = Properties::read(temp_dir.path()).unwrap();
+        assert_eq!(value(&properties, "Type"), Some("Directory"));
+        assert_eq!(value(&properties, "Contains"), Some("1 item(s)"));
+        assert_eq!(value(&properties, "Size"), None);
+
+        assert!(Properties::read(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("run.sh");
+        std::fs::write(&file, "").unwrap();
+        std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o754)).unwrap();
+        let metadata = std::fs::metadata(&file).unwrap();
+        assert_eq!(permissions(&metadata), "rwxr-xr-- (754)");
+    }
+}
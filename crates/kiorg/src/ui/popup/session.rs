@@ -0,0 +1,144 @@
+use crate::app::Kiorg;
+use crate::config;
+use crate::ui::popup::PopupType;
+use crate::ui::popup::fuzzy_search_popup::{
+    FuzzyMatchResult, FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState,
+    fuzzy_filter,
+};
+use std::borrow::Cow;
+use std::path::Path;
+
+static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
+    title: "Sessions",
+    search_hint: "Enter to restore, Shift+Enter to save over it, or type a name to save one...",
+    empty_message: "No saved sessions, type a name to save the current tabs",
+    no_match_message: "No matching sessions found",
+    max_visible_results: Some(15),
+};
+
+/// State for the sessions popup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionState {
+    pub query: String,
+    pub selected_index: usize,
+}
+
+/// A saved session, or a new one to save
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionItem {
+    pub name: String,
+    /// Not saved yet, selecting it saves the current tabs
+    pub new: bool,
+    pub note: Option<&'static str>,
+}
+
+impl FuzzySearchItem for SessionItem {
+    fn display_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.name)
+    }
+
+    fn secondary_text(&self) -> Option<Cow<'_, str>> {
+        self.note.map(Cow::Borrowed)
+    }
+}
+
+/// Saved sessions, plus an entry to save a session named after the query if
+/// there is none with that name
+pub fn get_search_results(
+    query: &str,
+    config_dir: &Path,
+    active: Option<&str>,
+) -> Vec<FuzzyMatchResult<SessionItem>> {
+    let names = config::session::list(config_dir);
+    let items: Vec<SessionItem> = names
+        .iter()
+        .map(|name| SessionItem {
+            name: name.clone(),
+            new: false,
+            note: (Some(name.as_str()) == active).then_some("active"),
+        })
+        .collect();
+    let mut results = fuzzy_filter(query, &items);
+
+    let query = query.trim();
+    if config::session::validate_name(query).is_ok() && !names.iter().any(|name| name == query) {
+        results.push(FuzzyMatchResult {
+            item: SessionItem {
+                name: query.to_string(),
+                new: true,
+                note: Some("save"),
+            },
+            score: 0,
+        });
+    }
+    results
+}
+
+/// Draw the sessions popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::Sessions(state)) = &app.show_popup else {
+        return;
+    };
+
+    let mut fuzzy_state = FuzzySearchState::new(state.query.clone());
+    fuzzy_state.selected_index = state.selected_index;
+    let config_dir = config::get_kiorg_config_dir(app.config_dir_override.as_deref());
+    let results = get_search_results(&fuzzy_state.query, &config_dir, app.session.as_deref());
+
+    let action = crate::ui::popup::fuzzy_search_popup::draw(
+        ctx,
+        &POPUP_CONFIG,
+        &app.colors,
+        &mut fuzzy_state,
+        &results,
+    );
+
+    match action {
+        FuzzySearchAction::KeepOpen => {
+            app.show_popup = Some(PopupType::Sessions(SessionState {
+                query: fuzzy_state.query,
+                selected_index: fuzzy_state.selected_index,
+            }));
+        }
+        FuzzySearchAction::Close => {
+            app.show_popup = None;
+        }
+        FuzzySearchAction::Selected(item) => {
+            app.show_popup = None;
+            if item.new || ctx.input(|i| i.modifiers.shift) {
+                app.save_session(&item.name);
+            } else {
+                app.restore_session(&item.name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_search_results() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sessions_dir = temp_dir.path().join(config::session::SESSIONS_DIR_NAME);
+        std::fs::create_dir_all(&sessions_dir).unwrap();
+        std::fs::write(sessions_dir.join("kiorg.json"), "{}").unwrap();
+
+        let names = |results: Vec<FuzzyMatchResult<SessionItem>>| {
+            results
+                .into_iter()
+                .map(|r| (r.item.name, r.item.note))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(get_search_results("", temp_dir.path(), Some("kiorg"))),
+            vec![("kiorg".to_string(), Some("active"))]
+        );
+        assert_eq!(
+            names(get_search_results("docs", temp_dir.path(), None)),
+            vec![("docs".to_string(), Some("save"))]
+        );
+        assert!(get_search_results("a/b", temp_dir.path(), None).is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::app::Kiorg;
+use crate::ui::popup::PopupType;
+use crate::ui::popup::fuzzy_search_popup::{
+    FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState, fuzzy_filter,
+};
+use crate::utils::share::{self, ShareTarget};
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
+    title: "Share",
+    search_hint: "Type to filter share targets...",
+    empty_message: "No share targets available for the selection",
+    no_match_message: "No matching share target found",
+    max_visible_results: None,
+};
+
+impl FuzzySearchItem for ShareTarget {
+    fn display_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.name)
+    }
+}
+
+/// State for the share popup
+#[derive(Debug, Clone)]
+pub struct ShareState {
+    pub paths: Vec<PathBuf>,
+    pub targets: Vec<ShareTarget>,
+    pub query: String,
+    pub selected_index: usize,
+}
+
+/// Open the share popup for the marked entries, or the selected entry
+pub fn open(app: &mut Kiorg) {
+    let tab = app.tab_manager.current_tab_ref();
+    let mut paths: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+    if paths.is_empty() {
+        paths.extend(tab.selected_entry().map(|entry| entry.meta.path.clone()));
+    }
+    if paths.is_empty() {
+        return;
+    }
+    paths.sort();
+
+    let targets = share::targets(&paths);
+    app.show_popup = Some(PopupType::Share(ShareState {
+        paths,
+        targets,
+        query: String::new(),
+        selected_index: 0,
+    }));
+}
+
+/// Draw the share popup
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::Share(state)) = &app.show_popup else {
+        return;
+    };
+    let mut state = state.clone();
+
+    let mut fuzzy_state = FuzzySearchState::new(state.query.clone());
+    fuzzy_state.selected_index = state.selected_index;
+    let filtered = fuzzy_filter(&fuzzy_state.query, &state.targets);
+
+    let action = crate::ui::popup::fuzzy_search_popup::draw(
+        ctx,
+        &POPUP_CONFIG,
+        &app.colors,
+        &mut fuzzy_state,
+        &filtered,
+    );
+
+    match action {
+        FuzzySearchAction::KeepOpen => {
+            state.query = fuzzy_state.query;
+            state.selected_index = fuzzy_state.selected_index;
+            app.show_popup = Some(PopupType::Share(state));
+        }
+        FuzzySearchAction::Close => {
+            app.show_popup = None;
+        }
+        FuzzySearchAction::Selected(target) => {
+            app.show_popup = None;
+            match share::share(&state.paths, &target) {
+                Ok(()) => {
+                    app.toasts.info(format!(
+                        "Shared {} via {}",
+                        share::describe(&state.paths),
+                        target.name
+                    ));
+                }
+                Err(e) => app.notify_error(e),
+            }
+        }
+    }
+}
@@ -36,7 +36,18 @@ pub fn show_sort_toggle_popup(app: &mut Kiorg, ctx: &egui::Context) {
 
                     ui.label(RichText::new("[m]").color(Color32::LIGHT_BLUE).strong());
                     ui.label("Modified");
+
+                    ui.add_space(20.0);
+
+                    ui.label(RichText::new("[e]").color(Color32::LIGHT_BLUE).strong());
+                    ui.label("Extension");
+                    ui.add_space(10.0);
+                });
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
                     ui.add_space(10.0);
+                    ui.label(RichText::new("[g]").color(Color32::LIGHT_BLUE).strong());
+                    ui.label(format!("Group by: {}", app.tab_manager.group_by.label()));
                 });
             });
             ui.add_space(10.0);
@@ -64,6 +75,13 @@ pub fn handle_sort_toggle_key(app: &mut Kiorg, key: Key) {
         Key::M => {
             app.tab_manager.toggle_sort(SortColumn::Modified);
         }
+        Key::E => {
+            app.tab_manager.toggle_sort(SortColumn::Extension);
+        }
+        Key::G => {
+            let group_by = app.tab_manager.group_by.next();
+            app.tab_manager.set_group_by(group_by);
+        }
         _ => {}
     }
 }
@@ -0,0 +1,275 @@
+use egui::{Context, ProgressBar};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use super::PopupType;
+use super::text_input_popup::{
+    TextInputConfig, TextSelection, clear_init_flag, draw as draw_text_input,
+};
+use super::window_utils::new_center_popup_window;
+use crate::app::Kiorg;
+use crate::ui::style::section_title_text;
+use crate::utils::format::format_size;
+use crate::utils::split::{self, JoinedFile, SplitMode};
+
+const SPLIT_POPUP_ID: &str = "split_popup";
+
+/// State of the split prompt
+#[derive(Debug, Clone)]
+pub struct SplitState {
+    pub path: PathBuf,
+    /// Part size or number of parts, see [`SplitMode::parse`]
+    pub input: String,
+}
+
+/// Progress data of a split or join running in the background
+pub struct SplitProgressData {
+    pub title: String,
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    pub receiver: mpsc::Receiver<SplitUpdate>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for SplitProgressData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SplitProgressData")
+            .field("title", &self.title)
+            .field("bytes_done", &self.bytes_done)
+            .field("total_bytes", &self.total_bytes)
+            .field("receiver", &"<receiver>")
+            .finish()
+    }
+}
+
+/// Progress update message sent from background thread
+#[derive(Debug, Clone)]
+pub enum SplitUpdate {
+    Progress { done: u64, total: u64 },
+    Split { path: PathBuf, parts: usize },
+    Joined(JoinedFile),
+    Error(String),
+    Cancelled,
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Open the split prompt for the selected file
+pub fn open(app: &mut Kiorg) {
+    let Some(entry) = app.tab_manager.current_tab_ref().selected_entry() else {
+        return;
+    };
+    let path = entry.meta.path.clone();
+    if !path.is_file() {
+        app.notify_error("Only files can be split");
+        return;
+    }
+    app.show_popup = Some(PopupType::Split(SplitState {
+        path,
+        input: String::new(),
+    }));
+}
+
+/// Draw the split prompt
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::Split(state)) = &mut app.show_popup else {
+        return;
+    };
+
+    let title = format!("Split {}", file_name(&state.path));
+    let config = TextInputConfig {
+        title: &title,
+        hint: "Part size like 25M, 4G or fat32, or number of parts...",
+        initial_selection: TextSelection::None,
+        password: false,
+    };
+    let keep_open = draw_text_input(ctx, &app.colors, &config, &mut state.input, SPLIT_POPUP_ID);
+    if !keep_open {
+        clear_init_flag(ctx, SPLIT_POPUP_ID);
+        app.show_popup = None;
+    }
+}
+
+/// Start splitting with the entered part size or count
+pub fn handle_confirm(app: &mut Kiorg, ctx: &Context) {
+    let Some(PopupType::Split(state)) = &app.show_popup else {
+        return;
+    };
+    let mode = match SplitMode::parse(&state.input) {
+        Ok(mode) => mode,
+        Err(e) => {
+            // Keep the prompt open to fix the input
+            app.notify_error(e);
+            return;
+        }
+    };
+    let path = state.path.clone();
+    clear_init_flag(ctx, SPLIT_POPUP_ID);
+
+    let title = format!("Splitting {}", file_name(&path));
+    start(app, ctx, title, move |cancel, on_progress| {
+        Some(match split::split_file(&path, mode, cancel, on_progress)? {
+            Ok(parts) => SplitUpdate::Split {
+                path,
+                parts: parts.len(),
+            },
+            Err(e) => SplitUpdate::Error(e),
+        })
+    });
+}
+
+/// Join the parts of the selected `.001` file back together
+pub fn start_join_selected(app: &mut Kiorg, ctx: &Context) {
+    let Some(entry) = app.tab_manager.current_tab_ref().selected_entry() else {
+        return;
+    };
+    let first_part = entry.meta.path.clone();
+    if !split::is_first_part(&first_part) {
+        app.notify_error("Select the first part of a split file, e.g. file.001");
+        return;
+    }
+
+    let title = format!("Joining {}", file_name(&first_part.with_extension("")));
+    start(app, ctx, title, move |cancel, on_progress| {
+        Some(match split::join_parts(&first_part, cancel, on_progress)? {
+            Ok(joined) => SplitUpdate::Joined(joined),
+            Err(e) => SplitUpdate::Error(e),
+        })
+    });
+}
+
+/// Start a background job, the progress popup replaces the current one
+fn start(
+    app: &mut Kiorg,
+    ctx: &Context,
+    title: String,
+    job: impl FnOnce(&AtomicBool, &mut dyn FnMut(u64, u64)) -> Option<SplitUpdate> + Send + 'static,
+) {
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    app.show_popup = Some(PopupType::SplitProgress(SplitProgressData {
+        title,
+        bytes_done: 0,
+        total_bytes: 0,
+        receiver: rx,
+        cancel: cancel.clone(),
+    }));
+
+    let ctx = ctx.clone();
+    thread::spawn(move || {
+        let progress_tx = tx.clone();
+        let progress_ctx = ctx.clone();
+        let mut on_progress = move |done, total| {
+            let _ = progress_tx.send(SplitUpdate::Progress { done, total });
+            progress_ctx.request_repaint();
+        };
+        let update = job(&cancel, &mut on_progress).unwrap_or(SplitUpdate::Cancelled);
+        let _ = tx.send(update);
+        ctx.request_repaint();
+    });
+}
+
+/// Stop the running split or join, its output is removed
+pub fn cancel(app: &mut Kiorg) {
+    if let Some(PopupType::SplitProgress(data)) = &app.show_popup {
+        data.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+fn finish(app: &mut Kiorg, update: SplitUpdate) {
+    app.show_popup = None;
+    match update {
+        SplitUpdate::Split { path, parts } => {
+            app.notify_success(format!("Split {} into {parts} parts", file_name(&path)));
+            app.refresh_entries();
+        }
+        SplitUpdate::Joined(joined) => {
+            let name = file_name(&joined.path);
+            match joined.verified {
+                Some(true) => app.notify_success(format!(
+                    "Joined {} parts into {name}, checksum verified",
+                    joined.parts
+                )),
+                Some(false) => app.notify_error(format!(
+                    "Joined {name} doesn't match its checksum, a part may be corrupt or missing"
+                )),
+                None => app.notify_success(format!("Joined {} parts into {name}", joined.parts)),
+            }
+            app.prev_path = Some(joined.path);
+            app.refresh_entries();
+        }
+        SplitUpdate::Error(error) => app.notify_error(error),
+        SplitUpdate::Cancelled => app.notify_info("Cancelled, partial files were removed"),
+        SplitUpdate::Progress { .. } => {}
+    }
+}
+
+/// Handle progress popup UI
+pub fn handle_split_progress(ctx: &Context, app: &mut Kiorg) {
+    let mut finished = None;
+
+    if let Some(PopupType::SplitProgress(ref mut data)) = app.show_popup {
+        while let Ok(update) = data.receiver.try_recv() {
+            match update {
+                SplitUpdate::Progress { done, total } => {
+                    data.bytes_done = done;
+                    data.total_bytes = total;
+                }
+                update => finished = Some(update),
+            }
+        }
+    }
+
+    if let Some(update) = finished {
+        finish(app, update);
+        return;
+    }
+
+    let Some(PopupType::SplitProgress(ref data)) = app.show_popup else {
+        return;
+    };
+    let cancelling = data.cancel.load(Ordering::Relaxed);
+    let mut cancel_clicked = false;
+    new_center_popup_window(&data.title).show(ctx, |ui| {
+        ui.set_min_width(400.0);
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(10.0);
+
+            let fraction = if data.total_bytes > 0 {
+                data.bytes_done as f32 / data.total_bytes as f32
+            } else {
+                0.0
+            };
+            ui.add(ProgressBar::new(fraction).desired_width(350.0));
+            ui.add_space(10.0);
+            ui.label(format!(
+                "{} / {}",
+                format_size(data.bytes_done, false),
+                format_size(data.total_bytes, false),
+            ));
+
+            ui.add_space(10.0);
+            if cancelling {
+                ui.label("Cancelling...");
+            } else if ui
+                .link(section_title_text("Press Esc to cancel", &app.colors))
+                .clicked()
+            {
+                cancel_clicked = true;
+            }
+            ui.add_space(5.0);
+        });
+    });
+
+    if cancel_clicked {
+        cancel(app);
+    }
+}
@@ -1,9 +1,10 @@
 use crate::app::Kiorg;
+use crate::config;
 use crate::ui::popup::PopupType;
 use crate::ui::popup::fuzzy_search_popup::{
     FuzzyMatchResult, FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState,
 };
-use crate::visit_history::VisitHistoryEntry;
+use crate::visit_history::{self, VisitHistoryEntry};
 use nucleo::{Config as NucleoConfig, Matcher, Utf32Str};
 use std::borrow::Cow;
 use std::path::PathBuf;
@@ -38,6 +39,8 @@ impl Default for TeleportState {
 #[derive(Debug, Clone)]
 pub struct TeleportSearchResult {
     pub entry: VisitHistoryEntry,
+    /// Visit count decayed by the time since the last visit
+    pub frecency: f64,
 }
 
 impl FuzzySearchItem for TeleportSearchResult {
@@ -54,13 +57,31 @@ impl FuzzySearchItem for TeleportSearchResult {
     }
 }
 
+/// Order of a match, its fuzzy score boosted by how often and how recently the
+/// directory was visited
+fn rank(result: &FuzzyMatchResult<TeleportSearchResult>) -> f64 {
+    f64::from(result.score) * (1.0 + result.item.frecency.ln_1p())
+}
+
 /// Filter and sort visit history based on fuzzy search query.
-/// This uses custom sorting logic based on access count and timestamp.
+/// Directories are ranked by frecency, their visit count decayed by the time
+/// since the last visit, halving every configured half-life.
 pub fn get_search_results(
     query: &str,
     visit_history: &std::collections::HashMap<PathBuf, VisitHistoryEntry>,
+    history_config: &config::History,
 ) -> Vec<FuzzyMatchResult<TeleportSearchResult>> {
-    // If query is empty, just return all directories sorted by access count
+    let now = visit_history::now_ts();
+    let half_life_secs = history_config.frecency_half_life_secs();
+    let result = |entry: &VisitHistoryEntry, score| FuzzyMatchResult {
+        item: TeleportSearchResult {
+            entry: entry.clone(),
+            frecency: entry.frecency(now, half_life_secs),
+        },
+        score,
+    };
+
+    // If query is empty, just return all directories sorted by frecency
     if query.is_empty() {
         let mut results: Vec<FuzzyMatchResult<TeleportSearchResult>> = visit_history
             .iter()
@@ -70,21 +91,16 @@ pub fn get_search_results(
                     return None;
                 }
 
-                Some(FuzzyMatchResult {
-                    item: TeleportSearchResult {
-                        entry: entry.clone(),
-                    },
-                    score: 0, // Score not relevant for empty query
-                })
+                // Score not relevant for empty query
+                Some(result(entry, 0))
             })
             .collect();
 
-        // Sort by access count (descending), then by recent access (descending)
+        // Sort by frecency (descending), then by recent access (descending)
         results.sort_by(|a, b| {
             b.item
-                .entry
-                .count
-                .cmp(&a.item.entry.count)
+                .frecency
+                .total_cmp(&a.item.frecency)
                 .then_with(|| b.item.entry.accessed_ts.cmp(&a.item.entry.accessed_ts))
         });
 
@@ -115,21 +131,15 @@ pub fn get_search_results(
 
             matcher
                 .fuzzy_match(haystack_utf32, needle_utf32)
-                .map(|score| FuzzyMatchResult {
-                    item: TeleportSearchResult {
-                        entry: entry.clone(),
-                    },
-                    score,
-                })
+                .map(|score| result(entry, score))
         })
         .collect();
 
-    // Sort by score (descending), then by access count (descending), then by recent access (descending)
+    // Sort by score boosted by frecency (descending), then by frecency (descending)
     results.sort_by(|a, b| {
-        b.score
-            .cmp(&a.score)
-            .then_with(|| b.item.entry.count.cmp(&a.item.entry.count))
-            .then_with(|| b.item.entry.accessed_ts.cmp(&a.item.entry.accessed_ts))
+        rank(b)
+            .total_cmp(&rank(a))
+            .then_with(|| b.item.frecency.total_cmp(&a.item.frecency))
     });
 
     results
@@ -147,7 +157,8 @@ pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
     fuzzy_state.selected_index = state.selected_index;
 
     // Get search results with custom sorting
-    let results = get_search_results(&fuzzy_state.query, &app.visit_history);
+    let history_config = app.config.history.clone().unwrap_or_default();
+    let results = get_search_results(&fuzzy_state.query, &app.visit_history, &history_config);
 
     let action = crate::ui::popup::fuzzy_search_popup::draw(
         ctx,
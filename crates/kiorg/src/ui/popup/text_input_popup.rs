@@ -21,6 +21,8 @@ pub struct TextInputConfig<'a> {
     pub hint: &'a str,
     /// Initial text selection behavior (only applies on first frame)
     pub initial_selection: TextSelection,
+    /// Mask the entered text, e.g. for passwords
+    pub password: bool,
 }
 
 /// Draw a text input popup and return whether it should stay open
@@ -62,6 +64,7 @@ pub fn draw(
 
                         let text_edit = TextEdit::singleline(text)
                             .hint_text(config.hint)
+                            .password(config.password)
                             .desired_width(f32::INFINITY)
                             .frame(Frame::NONE);
                         let response = ui.add(text_edit);
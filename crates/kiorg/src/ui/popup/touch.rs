@@ -0,0 +1,239 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone, Timelike};
+use egui::{Context, DragValue, Frame, RichText, TextEdit};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::PopupType;
+use super::window_utils::new_center_popup_window;
+use crate::app::Kiorg;
+use crate::models::action_history::{ActionType, CreateOperation};
+use crate::utils::format::format_count;
+use crate::utils::touch;
+
+/// Local date and time edited field by field in the touch popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl Timestamp {
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let time: DateTime<Local> = time.into();
+        Self {
+            year: time.year(),
+            month: time.month(),
+            day: time.day(),
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+        }
+    }
+
+    /// `None` for dates like February 30th or times skipped by a DST change
+    #[must_use]
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        let time = NaiveDate::from_ymd_opt(self.year, self.month, self.day)?.and_hms_opt(
+            self.hour,
+            self.minute,
+            self.second,
+        )?;
+        Local
+            .from_local_datetime(&time)
+            .earliest()
+            .map(SystemTime::from)
+    }
+}
+
+/// State of the touch popup
+#[derive(Debug, Clone)]
+pub struct TouchState {
+    /// Entry in the current directory to touch or create, used without marked entries
+    pub name: String,
+    /// Marked entries to touch
+    pub targets: Vec<PathBuf>,
+    /// Use `time` instead of the current time
+    pub set_time: bool,
+    pub time: Timestamp,
+}
+
+/// Open the touch popup for the marked entries or the selected one
+pub fn open(app: &mut Kiorg) {
+    let tab = app.tab_manager.current_tab_ref();
+    let mut targets: Vec<PathBuf> = tab.marked_entries.iter().cloned().collect();
+    targets.sort();
+    let selected = tab.selected_entry().filter(|_| targets.is_empty());
+    let name = selected.map(|entry| entry.name.clone()).unwrap_or_default();
+    // Start from the selected entry's time so small corrections are easy
+    let time = selected.map_or_else(SystemTime::now, |entry| entry.meta.modified);
+    app.show_popup = Some(PopupType::Touch(TouchState {
+        name,
+        targets,
+        set_time: false,
+        time: Timestamp::from_system_time(time),
+    }));
+}
+
+/// Touch the entries, creating the named file if it doesn't exist
+pub fn confirm(app: &mut Kiorg) {
+    let Some(PopupType::Touch(state)) = &app.show_popup else {
+        return;
+    };
+    let time = if state.set_time {
+        match state.time.to_system_time() {
+            Some(time) => time,
+            None => {
+                // Keep the popup open to fix the date
+                app.notify_error("Invalid date or time");
+                return;
+            }
+        }
+    } else {
+        SystemTime::now()
+    };
+    let paths = if state.targets.is_empty() {
+        let name = state.name.trim();
+        if name.is_empty() {
+            app.notify_error("Enter a name to touch");
+            return;
+        }
+        vec![app.tab_manager.current_tab_ref().current_path.join(name)]
+    } else {
+        state.targets.clone()
+    };
+    app.show_popup = None;
+
+    let mut created = Vec::new();
+    let mut errors = Vec::new();
+    for path in &paths {
+        match touch::touch(path, time) {
+            Ok(true) => created.push(CreateOperation {
+                path: path.clone(),
+                is_dir: false,
+            }),
+            Ok(false) => {}
+            Err(e) => errors.push(format!("{}: {e}", path.display())),
+        }
+    }
+
+    if !created.is_empty() {
+        app.tab_manager
+            .current_tab_mut()
+            .action_history
+            .add_action(ActionType::Create {
+                operations: created.clone(),
+            });
+    }
+    if let [path] = paths.as_slice() {
+        app.prev_path = Some(path.clone());
+    }
+    app.refresh_entries();
+
+    if !errors.is_empty() {
+        app.notify_error(format!("Failed to touch: {}", errors.join("; ")));
+    } else if let [operation] = created.as_slice() {
+        app.notify_success(format!(
+            "Created {}",
+            operation
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+        ));
+    } else {
+        app.notify_success(format!(
+            "Updated the timestamps of {} entries",
+            format_count(paths.len() as u64)
+        ));
+    }
+}
+
+/// Draw the touch popup
+pub fn draw(ctx: &Context, app: &mut Kiorg) {
+    let Some(PopupType::Touch(state)) = &mut app.show_popup else {
+        return;
+    };
+    let mut keep_open = true;
+    new_center_popup_window("Touch")
+        .open(&mut keep_open)
+        .show(ctx, |ui| {
+            ui.set_max_width(400.0);
+            if state.targets.is_empty() {
+                Frame::default()
+                    .fill(app.colors.bg_extreme)
+                    .inner_margin(5.0)
+                    .show(ui, |ui| {
+                        let response = ui.add(
+                            TextEdit::singleline(&mut state.name)
+                                .hint_text("Name of the file to touch or create...")
+                                .desired_width(f32::INFINITY)
+                                .frame(Frame::NONE),
+                        );
+                        // Focus the name unless the date is being edited
+                        if ui.memory(|m| m.focused().is_none()) {
+                            response.request_focus();
+                        }
+                    });
+            } else {
+                ui.label(format!(
+                    "Update {} marked entries",
+                    format_count(state.targets.len() as u64)
+                ));
+            }
+
+            ui.add_space(4.0);
+            ui.checkbox(&mut state.set_time, "Set time");
+            ui.add_enabled_ui(state.set_time, |ui| {
+                let time = &mut state.time;
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut time.year).range(1970..=9999));
+                    ui.label("-");
+                    ui.add(DragValue::new(&mut time.month).range(1..=12));
+                    ui.label("-");
+                    ui.add(DragValue::new(&mut time.day).range(1..=31));
+                    ui.add_space(8.0);
+                    ui.add(DragValue::new(&mut time.hour).range(0..=23));
+                    ui.label(":");
+                    ui.add(DragValue::new(&mut time.minute).range(0..=59));
+                    ui.label(":");
+                    ui.add(DragValue::new(&mut time.second).range(0..=59));
+                });
+            });
+            if !state.set_time {
+                ui.label(RichText::new("Uses the current time").color(app.colors.fg_light));
+            } else if state.time.to_system_time().is_none() {
+                ui.label(RichText::new("Invalid date").color(app.colors.error));
+            }
+        });
+
+    if !keep_open {
+        app.show_popup = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let time = Timestamp {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 13,
+            minute: 45,
+            second: 30,
+        };
+        let system_time = time.to_system_time().unwrap();
+        assert_eq!(Timestamp::from_system_time(system_time), time);
+
+        let invalid = Timestamp { day: 30, ..time };
+        assert!(invalid.to_system_time().is_none());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::app::Kiorg;
+use crate::ui::popup::PopupType;
+use crate::ui::popup::fuzzy_search_popup::{
+    FuzzySearchAction, FuzzySearchItem, FuzzySearchPopupConfig, FuzzySearchState, fuzzy_filter,
+};
+use crate::utils::trash::{self, TrashItem};
+use std::borrow::Cow;
+
+static POPUP_CONFIG: FuzzySearchPopupConfig = FuzzySearchPopupConfig {
+    title: "Trash",
+    search_hint: "Type to filter trashed files, Enter to restore...",
+    empty_message: "Trash is empty",
+    no_match_message: "No matching trashed file found",
+    max_visible_results: None,
+};
+
+impl FuzzySearchItem for TrashItem {
+    fn display_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.name)
+    }
+
+    fn secondary_text(&self) -> Option<Cow<'_, str>> {
+        let location = self
+            .original_path
+            .as_deref()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.display().to_string());
+        let deleted_at = self
+            .deleted_at
+            .map(|date| date.format("%Y-%m-%d %H:%M").to_string());
+        match (location, deleted_at) {
+            (Some(location), Some(deleted_at)) => Some(format!("{location}  {deleted_at}").into()),
+            (location, deleted_at) => location.or(deleted_at).map(Cow::Owned),
+        }
+    }
+}
+
+/// State for the trash popup
+#[derive(Debug, Clone)]
+pub struct TrashState {
+    pub items: Vec<TrashItem>,
+    pub query: String,
+    pub selected_index: usize,
+}
+
+/// Open the trash popup with the current content of the trash
+pub fn open(app: &mut Kiorg) {
+    match trash::list_trash() {
+        Ok(items) => {
+            app.show_popup = Some(PopupType::Trash(TrashState {
+                items,
+                query: String::new(),
+                selected_index: 0,
+            }));
+        }
+        Err(e) => app.notify_error(format!("Failed to read the trash: {e}")),
+    }
+}
+
+/// Draw the trash popup, selecting an item restores it
+pub fn draw(ctx: &egui::Context, app: &mut Kiorg) {
+    let Some(PopupType::Trash(state)) = &app.show_popup else {
+        return;
+    };
+    let mut state = state.clone();
+
+    let mut fuzzy_state = FuzzySearchState::new(state.query.clone());
+    fuzzy_state.selected_index = state.selected_index;
+    let filtered = fuzzy_filter(&fuzzy_state.query, &state.items);
+
+    let action = crate::ui::popup::fuzzy_search_popup::draw(
+        ctx,
+        &POPUP_CONFIG,
+        &app.colors,
+        &mut fuzzy_state,
+        &filtered,
+    );
+
+    match action {
+        FuzzySearchAction::KeepOpen => {
+            state.query = fuzzy_state.query;
+            state.selected_index = fuzzy_state.selected_index;
+            app.show_popup = Some(PopupType::Trash(state));
+        }
+        FuzzySearchAction::Close => {
+            app.show_popup = None;
+        }
+        FuzzySearchAction::Selected(item) => {
+            match trash::restore(&item) {
+                Ok(path) => {
+                    app.notify_success(format!("Restored {}", path.display()));
+                    state.items.retain(|other| *other != item);
+                    app.refresh_entries();
+                }
+                Err(e) => app.notify_error(format!("Failed to restore {}: {e}", item.name)),
+            }
+            // Stay open to restore more items
+            state.query = fuzzy_state.query;
+            state.selected_index = fuzzy_state
+                .selected_index
+                .min(state.items.len().saturating_sub(1));
+            app.show_popup = Some(PopupType::Trash(state));
+        }
+    }
+}
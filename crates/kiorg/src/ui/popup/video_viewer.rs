@@ -1,3 +1,4 @@
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::preview_content::VideoMeta;
 use crate::ui::file_list::truncate_text;
@@ -83,5 +84,7 @@ pub fn render_popup(ui: &mut egui::Ui, video_meta: &VideoMeta) {
         source_id,
         ui.available_width(),
         ui.available_height(),
+        false,
+        ZoomMode::FitPage,
     );
 }
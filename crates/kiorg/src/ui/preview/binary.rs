@@ -0,0 +1,410 @@
+//! Executable and library preview module
+//!
+//! Shows the headers of ELF, PE and Mach-O files: the architecture, linked
+//! libraries, symbol counts and embedded version information.
+
+use egui::RichText;
+
+use crate::config::colors::AppColors;
+use crate::models::preview_content::BinaryMeta;
+use crate::utils::format::format_count;
+
+/// Binaries are read in full to parse them, larger ones are not previewed
+#[cfg(feature = "binary-preview")]
+const MAX_BINARY_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Render the headers of a binary
+pub fn render(ui: &mut egui::Ui, meta: &BinaryMeta, colors: &AppColors) {
+    ui.label(
+        RichText::new(&meta.format)
+            .color(colors.fg)
+            .strong()
+            .size(16.0),
+    );
+    ui.add_space(10.0);
+
+    egui::Grid::new("binary_metadata_grid")
+        .num_columns(2)
+        .spacing([10.0, 6.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for (key, value) in &meta.details {
+                ui.with_layout(egui::Layout::left_to_right(egui::Align::LEFT), |ui| {
+                    ui.set_min_width(super::METADATA_TBL_KEY_COL_W);
+                    ui.set_max_width(super::METADATA_TBL_KEY_COL_W);
+                    ui.add(egui::Label::new(RichText::new(key).color(colors.fg)).wrap());
+                });
+                ui.add(egui::Label::new(RichText::new(value).color(colors.fg)).wrap());
+                ui.end_row();
+            }
+        });
+
+    if meta.libraries.is_empty() {
+        return;
+    }
+    ui.add_space(15.0);
+    ui.label(
+        RichText::new(format!("Linked libraries ({})", meta.libraries.len()))
+            .color(colors.fg)
+            .strong(),
+    );
+    ui.add_space(5.0);
+    for library in &meta.libraries {
+        ui.label(
+            RichText::new(library)
+                .color(colors.fg_light)
+                .family(egui::FontFamily::Monospace),
+        );
+    }
+}
+
+/// Parse the headers of a binary, `None` if the file is not one
+#[cfg(feature = "binary-preview")]
+pub fn read_binary_metadata(path: &std::path::Path) -> Option<Result<BinaryMeta, String>> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut magic = [0u8; 16];
+    file.read_exact(&mut magic).ok()?;
+    match goblin::peek_bytes(&magic).ok()? {
+        goblin::Hint::Elf(_)
+        | goblin::Hint::Mach(_)
+        | goblin::Hint::MachFat(_)
+        | goblin::Hint::PE => {}
+        _ => return None,
+    }
+    if file.metadata().is_ok_and(|m| m.len() > MAX_BINARY_BYTES) {
+        return Some(Err("The binary is too large to parse".to_string()));
+    }
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => return Some(Err(format!("Failed to read file: {e}"))),
+    };
+    Some(parse(&bytes))
+}
+
+#[cfg(feature = "binary-preview")]
+fn parse(bytes: &[u8]) -> Result<BinaryMeta, String> {
+    match goblin::Object::parse(bytes).map_err(|e| format!("Failed to parse binary: {e}"))? {
+        goblin::Object::Elf(elf) => Ok(parse_elf(&elf, bytes)),
+        goblin::Object::PE(pe) => Ok(parse_pe(&pe)),
+        goblin::Object::Mach(goblin::mach::Mach::Binary(macho)) => Ok(parse_macho(&macho)),
+        goblin::Object::Mach(goblin::mach::Mach::Fat(fat)) => parse_fat(&fat),
+        _ => Err("Unsupported binary format".to_string()),
+    }
+}
+
+/// Version packed as `xxxx.yy.zz` nibbles, used by Mach-O load commands
+#[cfg(feature = "binary-preview")]
+fn packed_version(version: u32) -> String {
+    format!(
+        "{}.{}.{}",
+        version >> 16,
+        (version >> 8) & 0xff,
+        version & 0xff
+    )
+}
+
+#[cfg(feature = "binary-preview")]
+fn parse_elf(elf: &goblin::elf::Elf, bytes: &[u8]) -> BinaryMeta {
+    use goblin::elf::header;
+
+    let kind = match elf.header.e_type {
+        header::ET_EXEC => "executable",
+        header::ET_DYN if elf.interpreter.is_some() => "executable (PIE)",
+        header::ET_DYN => "shared library",
+        header::ET_REL => "relocatable object",
+        header::ET_CORE => "core dump",
+        _ => "file",
+    };
+    let bits = if elf.is_64 { 64 } else { 32 };
+    let mut details = vec![
+        (
+            "Architecture".to_string(),
+            header::machine_to_str(elf.header.e_machine).to_string(),
+        ),
+        (
+            "Endianness".to_string(),
+            if elf.little_endian { "Little" } else { "Big" }.to_string(),
+        ),
+    ];
+    if elf.entry != 0 {
+        details.push(("Entry point".to_string(), format!("{:#x}", elf.entry)));
+    }
+    if let Some(interpreter) = elf.interpreter {
+        details.push(("Interpreter".to_string(), interpreter.to_string()));
+    }
+    if let Some(soname) = elf.soname {
+        details.push(("Soname".to_string(), soname.to_string()));
+    }
+    let symbols = if elf.syms.is_empty() {
+        format!(
+            "{} dynamic, stripped",
+            format_count(elf.dynsyms.len() as u64)
+        )
+    } else {
+        format!(
+            "{}, {} dynamic",
+            format_count(elf.syms.len() as u64),
+            format_count(elf.dynsyms.len() as u64)
+        )
+    };
+    details.push(("Symbols".to_string(), symbols));
+
+    let build_id = elf
+        .iter_note_sections(bytes, Some(".note.gnu.build-id"))
+        .and_then(|mut notes| notes.find_map(Result::ok))
+        .map(|note| {
+            note.desc
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        });
+    if let Some(build_id) = build_id {
+        details.push(("Build ID".to_string(), build_id));
+    }
+    // Compilers record their version in the .comment section
+    let comment = elf
+        .section_headers
+        .iter()
+        .find(|section| elf.shdr_strtab.get_at(section.sh_name) == Some(".comment"))
+        .and_then(|section| {
+            let start = usize::try_from(section.sh_offset).ok()?;
+            let end = start.checked_add(usize::try_from(section.sh_size).ok()?)?;
+            bytes.get(start..end)
+        });
+    if let Some(comment) = comment {
+        let mut compilers: Vec<String> = Vec::new();
+        for text in comment.split(|&b| b == 0) {
+            let text = String::from_utf8_lossy(text).trim().to_string();
+            if !text.is_empty() && !compilers.contains(&text) {
+                compilers.push(text);
+            }
+        }
+        if !compilers.is_empty() {
+            details.push(("Compiler".to_string(), compilers.join("\n")));
+        }
+    }
+
+    BinaryMeta {
+        format: format!("ELF {bits}-bit {kind}"),
+        details,
+        libraries: elf.libraries.iter().map(|lib| lib.to_string()).collect(),
+    }
+}
+
+#[cfg(feature = "binary-preview")]
+fn subsystem_name(subsystem: u16) -> Option<&'static str> {
+    use goblin::pe::subsystem::*;
+
+    Some(match subsystem {
+        IMAGE_SUBSYSTEM_NATIVE => "Native",
+        IMAGE_SUBSYSTEM_WINDOWS_GUI => "Windows GUI",
+        IMAGE_SUBSYSTEM_WINDOWS_CUI => "Windows console",
+        IMAGE_SUBSYSTEM_EFI_APPLICATION => "EFI application",
+        IMAGE_SUBSYSTEM_EFI_BOOT_SERVICE_DRIVER => "EFI boot service driver",
+        IMAGE_SUBSYSTEM_EFI_RUNTIME_DRIVER => "EFI runtime driver",
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "binary-preview")]
+fn parse_pe(pe: &goblin::pe::PE) -> BinaryMeta {
+    let mut details = vec![(
+        "Architecture".to_string(),
+        goblin::pe::header::machine_to_str(pe.header.coff_header.machine).to_string(),
+    )];
+    if let Some(optional_header) = &pe.header.optional_header {
+        let subsystem = optional_header.windows_fields.subsystem;
+        if let Some(name) = subsystem_name(subsystem) {
+            details.push(("Subsystem".to_string(), name.to_string()));
+        }
+    }
+    if pe.entry != 0 {
+        details.push(("Entry point".to_string(), format!("{:#x}", pe.entry)));
+    }
+    details.push(("Image base".to_string(), format!("{:#x}", pe.image_base)));
+    if pe.clr_data.is_some() {
+        details.push(("Runtime".to_string(), ".NET".to_string()));
+    }
+    details.push((
+        "Symbols".to_string(),
+        format!(
+            "{} imported, {} exported",
+            pe.imports.len(),
+            pe.exports.len()
+        ),
+    ));
+
+    if let Some(version_info) = pe
+        .resource_data
+        .as_ref()
+        .and_then(|resources| resources.version_info.as_ref())
+    {
+        if let Some(fixed) = version_info.fixed_info.as_ref().filter(|f| f.is_valid()) {
+            details.push(("File version".to_string(), fixed.file_version().to_string()));
+        }
+        let strings = &version_info.string_info;
+        let fields = [
+            ("Description", strings.file_description()),
+            ("Product", strings.product_name()),
+            ("Product version", strings.product_version()),
+            ("Company", strings.company_name()),
+            ("Copyright", strings.legal_copyright()),
+            ("Original name", strings.original_filename()),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+                details.push((key.to_string(), value));
+            }
+        }
+    }
+
+    let bits = if pe.is_64 { "PE32+" } else { "PE32" };
+    let kind = if pe.is_lib { "DLL" } else { "executable" };
+    BinaryMeta {
+        format: format!("{bits} {kind}"),
+        details,
+        libraries: pe.libraries.iter().map(|lib| lib.to_string()).collect(),
+    }
+}
+
+#[cfg(feature = "binary-preview")]
+fn arch_name(cputype: u32, cpusubtype: u32) -> String {
+    use goblin::mach::constants::cputype::{CPU_SUBTYPE_MASK, get_arch_name_from_types};
+
+    get_arch_name_from_types(cputype, cpusubtype & !CPU_SUBTYPE_MASK)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("CPU type {cputype}"))
+}
+
+#[cfg(feature = "binary-preview")]
+fn parse_macho(macho: &goblin::mach::MachO) -> BinaryMeta {
+    use goblin::mach::header;
+    use goblin::mach::load_command::CommandVariant;
+
+    let kind = match macho.header.filetype {
+        header::MH_EXECUTE => "executable",
+        header::MH_DYLIB => "dynamic library",
+        header::MH_BUNDLE => "bundle",
+        header::MH_OBJECT => "object",
+        header::MH_CORE => "core dump",
+        filetype => header::filetype_to_str(filetype),
+    };
+    let bits = if macho.is_64 { 64 } else { 32 };
+    let mut details = vec![(
+        "Architecture".to_string(),
+        arch_name(macho.header.cputype(), macho.header.cpusubtype()),
+    )];
+    if macho.entry != 0 {
+        details.push(("Entry point".to_string(), format!("{:#x}", macho.entry)));
+    }
+    for load_command in &macho.load_commands {
+        match &load_command.command {
+            CommandVariant::IdDylib(command) => {
+                details.push((
+                    "Version".to_string(),
+                    packed_version(command.dylib.current_version),
+                ));
+            }
+            CommandVariant::BuildVersion(command) => {
+                let platform = match command.platform {
+                    1 => "macOS",
+                    2 => "iOS",
+                    3 => "tvOS",
+                    4 => "watchOS",
+                    6 => "Mac Catalyst",
+                    11 => "visionOS",
+                    _ => "OS",
+                };
+                details.push((
+                    "Minimum OS".to_string(),
+                    format!("{platform} {}", packed_version(command.minos)),
+                ));
+                details.push(("SDK".to_string(), packed_version(command.sdk)));
+            }
+            CommandVariant::VersionMinMacosx(command) => {
+                details.push((
+                    "Minimum OS".to_string(),
+                    format!("macOS {}", packed_version(command.version)),
+                ));
+            }
+            CommandVariant::VersionMinIphoneos(command) => {
+                details.push((
+                    "Minimum OS".to_string(),
+                    format!("iOS {}", packed_version(command.version)),
+                ));
+            }
+            _ => {}
+        }
+    }
+    details.push(("Symbols".to_string(), macho.symbols().count().to_string()));
+
+    BinaryMeta {
+        format: format!("Mach-O {bits}-bit {kind}"),
+        details,
+        // The first library is the binary itself
+        libraries: macho
+            .libs
+            .iter()
+            .filter(|lib| **lib != "self")
+            .map(|lib| lib.to_string())
+            .collect(),
+    }
+}
+
+#[cfg(feature = "binary-preview")]
+fn parse_fat(fat: &goblin::mach::MultiArch) -> Result<BinaryMeta, String> {
+    let arches = fat
+        .iter_arches()
+        .filter_map(Result::ok)
+        .map(|arch| arch_name(arch.cputype, arch.cpusubtype))
+        .collect::<Vec<_>>();
+    // Slices usually only differ in their architecture, so show the first one
+    let first = fat.into_iter().find_map(|arch| match arch {
+        Ok(goblin::mach::SingleArch::MachO(macho)) => Some(macho),
+        _ => None,
+    });
+    let mut meta = first
+        .map(|macho| parse_macho(&macho))
+        .ok_or_else(|| "No Mach-O binary in the universal binary".to_string())?;
+    meta.format = "Mach-O universal binary".to_string();
+    meta.details.retain(|(key, _)| key != "Architecture");
+    meta.details
+        .insert(0, ("Architectures".to_string(), arches.join(", ")));
+    Ok(meta)
+}
+
+#[cfg(all(test, feature = "binary-preview"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packed_version() {
+        assert_eq!(packed_version(0x000d_0100), "13.1.0");
+        assert_eq!(packed_version(0x04c0_0102), "1216.1.2");
+    }
+
+    #[test]
+    fn test_read_own_executable() {
+        let path = std::env::current_exe().unwrap();
+        let meta = read_binary_metadata(&path).unwrap().unwrap();
+        assert!(
+            ["ELF", "PE32", "Mach-O"]
+                .iter()
+                .any(|format| meta.format.starts_with(format)),
+            "unexpected format {}",
+            meta.format
+        );
+        assert!(meta.details.iter().any(|(key, _)| key == "Architecture"));
+    }
+
+    #[test]
+    fn test_read_non_binary() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        std::fs::write(&path, "just some plain text here").unwrap();
+        assert!(read_binary_metadata(&path).is_none());
+        assert!(read_binary_metadata(&temp_dir.path().join("missing")).is_none());
+    }
+}
@@ -0,0 +1,206 @@
+//! On-demand file checksums shown below the preview
+
+use crate::config::colors::AppColors;
+use crate::utils::format::format_size;
+use crate::utils::hashing::{self, HashAlgorithm};
+use egui::RichText;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Snapshot of a running or finished checksum computation
+#[derive(Debug, Clone, Default)]
+pub struct ChecksumProgress {
+    pub bytes_done: u64,
+    pub total: u64,
+    /// Algorithm name and hex digest, set once the whole file has been read
+    pub result: Option<Result<Vec<(&'static str, String)>, String>>,
+}
+
+/// Handle to a background checksum computation of a file
+///
+/// The computation is cancelled when the handle is dropped, i.e. when the
+/// selection moves to another entry.
+#[derive(Debug)]
+pub struct ChecksumJob {
+    pub path: PathBuf,
+    state: Arc<Mutex<ChecksumProgress>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl ChecksumJob {
+    pub fn start(path: PathBuf, ctx: &egui::Context) -> Self {
+        let state = Arc::new(Mutex::new(ChecksumProgress::default()));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let thread_path = path.clone();
+        let thread_state = state.clone();
+        let thread_cancel = cancel.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let result = compute_checksums(&thread_path, &thread_cancel, |done, total| {
+                if let Ok(mut state) = thread_state.lock() {
+                    state.bytes_done = done;
+                    state.total = total;
+                }
+                ctx.request_repaint();
+            });
+            let Some(result) = result else {
+                return;
+            };
+            if let Ok(mut state) = thread_state.lock() {
+                state.result = Some(result);
+            }
+            ctx.request_repaint();
+        });
+
+        Self {
+            path,
+            state,
+            cancel,
+        }
+    }
+
+    pub fn snapshot(&self) -> ChecksumProgress {
+        self.state.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+impl Drop for ChecksumJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Compute the BLAKE3, MD5, SHA-1 and SHA-256 digests of `path` in a single pass
+///
+/// `on_progress` is called with the bytes read so far and the file size.
+/// Returns `None` if the computation was cancelled.
+pub fn compute_checksums(
+    path: &Path,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(u64, u64),
+) -> Option<Result<Vec<(&'static str, String)>, String>> {
+    let result = hashing::hash_file(path, &HashAlgorithm::ALL, cancel, on_progress)?;
+    Some(result.map(|digests| {
+        digests
+            .into_iter()
+            .map(|(algorithm, digest)| (algorithm.name(), digest))
+            .collect()
+    }))
+}
+
+/// Render the checksum section, returns true if the user asked to compute
+/// the checksums of the previewed file
+pub fn render(ui: &mut egui::Ui, job: Option<&ChecksumJob>, colors: &AppColors) -> bool {
+    ui.add_space(15.0);
+    ui.label(
+        RichText::new("Checksums")
+            .color(colors.fg_folder)
+            .strong()
+            .size(14.0),
+    );
+    ui.add_space(5.0);
+
+    let Some(job) = job else {
+        return ui.button("Compute checksums").clicked();
+    };
+    let progress = job.snapshot();
+    match &progress.result {
+        None => {
+            let fraction = if progress.total == 0 {
+                0.0
+            } else {
+                progress.bytes_done as f32 / progress.total as f32
+            };
+            ui.add(
+                egui::ProgressBar::new(fraction)
+                    .desired_width(ui.available_width())
+                    .fill(colors.bg_selected)
+                    .text(
+                        RichText::new(format!(
+                            "{} / {}",
+                            format_size(progress.bytes_done, false),
+                            format_size(progress.total, false)
+                        ))
+                        .color(colors.fg),
+                    ),
+            );
+        }
+        Some(Err(e)) => {
+            ui.label(RichText::new(e).color(colors.error));
+        }
+        Some(Ok(digests)) => {
+            egui::Grid::new("checksum_grid")
+                .num_columns(3)
+                .spacing([10.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, digest) in digests {
+                        ui.with_layout(egui::Layout::left_to_right(egui::Align::LEFT), |ui| {
+                            ui.set_min_width(super::METADATA_TBL_KEY_COL_W);
+                            ui.set_max_width(super::METADATA_TBL_KEY_COL_W);
+                            ui.label(RichText::new(*name).color(colors.fg));
+                        });
+                        ui.add(
+                            egui::Label::new(RichText::new(digest).color(colors.fg).monospace())
+                                .wrap(),
+                        );
+                        if ui
+                            .small_button("Copy")
+                            .on_hover_text(format!("Copy the {name} digest"))
+                            .clicked()
+                        {
+                            ui.ctx().copy_text(digest.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_checksums() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("abc.txt");
+        std::fs::write(&file, "abc").unwrap();
+
+        let cancel = AtomicBool::new(false);
+        let digests = compute_checksums(&file, &cancel, |_, _| {})
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            digests,
+            vec![
+                (
+                    "BLAKE3",
+                    "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85".to_string()
+                ),
+                ("MD5", "900150983cd24fb0d6963f7d28e17f72".to_string()),
+                (
+                    "SHA-1",
+                    "a9993e364706816aba3e25717850c26c9cd0d89d".to_string()
+                ),
+                (
+                    "SHA-256",
+                    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()
+                ),
+            ]
+        );
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(compute_checksums(&file, &cancel, |_, _| {}).is_none());
+        let missing = compute_checksums(
+            &temp_dir.path().join("missing"),
+            &AtomicBool::new(false),
+            |_, _| {},
+        );
+        assert!(matches!(missing, Some(Err(_))));
+    }
+}
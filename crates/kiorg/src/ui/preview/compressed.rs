@@ -0,0 +1,99 @@
+//! Single compressed file (gz, bz2, xz, zst) preview module
+
+use std::path::Path;
+
+use crate::models::dir_entry::DirEntryMeta;
+use crate::models::preview_content::PreviewContent;
+use crate::utils::archive::{CompressionFormat, read_decompressed_head};
+
+/// Number of decompressed bytes shown in the preview
+const PREVIEW_HEAD_SIZE: usize = 64 * 1024;
+
+/// Decompress the head of a compressed file and preview it as text
+pub fn read_compressed_preview(entry: DirEntryMeta) -> Result<PreviewContent, String> {
+    let path = &entry.path;
+    let format = CompressionFormat::from_path(path)
+        .ok_or_else(|| format!("Unsupported compression format: {}", path.display()))?;
+    let (bytes, truncated) = read_decompressed_head(path, format, PREVIEW_HEAD_SIZE)?;
+
+    // Inner file name, e.g. `notes.md` for `notes.md.gz`
+    let inner_path = path.with_extension("");
+    Ok(head_to_preview(&inner_path, format, bytes, truncated))
+}
+
+fn head_to_preview(
+    inner_path: &Path,
+    format: CompressionFormat,
+    bytes: Vec<u8>,
+    truncated: bool,
+) -> PreviewContent {
+    let content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(e) => {
+            // The head may end in the middle of a multi-byte character
+            let valid_up_to = e.utf8_error().valid_up_to();
+            let bytes = e.into_bytes();
+            if truncated && valid_up_to + 4 > bytes.len() {
+                String::from_utf8_lossy(&bytes[..valid_up_to]).into_owned()
+            } else {
+                return PreviewContent::text(format!(
+                    "{} compressed file\n\nDecompressed content of {} is binary",
+                    format.name(),
+                    inner_path.file_name().unwrap_or_default().to_string_lossy()
+                ));
+            }
+        }
+    };
+
+    match super::text::find_syntax_from_path(inner_path) {
+        Some(syntax) => PreviewContent::HighlightedCode {
+            content,
+            language: syntax.name.as_str(),
+        },
+        None => PreviewContent::text(content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_to_preview_text() {
+        let content = head_to_preview(
+            Path::new("/tmp/notes.unknownext"),
+            CompressionFormat::Gzip,
+            b"hello".to_vec(),
+            false,
+        );
+        assert!(matches!(content, PreviewContent::Text(ref t) if t == "hello"));
+    }
+
+    #[test]
+    fn test_head_to_preview_highlighted() {
+        let content = head_to_preview(
+            Path::new("/tmp/main.rs"),
+            CompressionFormat::Zstd,
+            b"fn main() {}".to_vec(),
+            false,
+        );
+        assert!(matches!(
+            content,
+            PreviewContent::HighlightedCode {
+                language: "Rust",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_head_to_preview_binary() {
+        let content = head_to_preview(
+            Path::new("/tmp/blob"),
+            CompressionFormat::Xz,
+            vec![0xff, 0xfe, 0x00, 0x01],
+            false,
+        );
+        assert!(matches!(content, PreviewContent::Text(ref t) if t.contains("binary")));
+    }
+}
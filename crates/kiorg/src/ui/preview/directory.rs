@@ -3,12 +3,204 @@
 use crate::config::colors::AppColors;
 use crate::models::preview_content::DirectoryEntry;
 use crate::ui::preview::{prefix_dir_name, prefix_file_name};
+use crate::utils::format::format_size;
 use egui::RichText;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Number of largest children shown in the size breakdown
+const SIZE_BREAKDOWN_TOP_N: usize = 10;
+/// Wait before scanning so quickly moving the selection doesn't start a walk per directory
+const SIZE_SCAN_DELAY: Duration = Duration::from_millis(200);
+/// Publish partial results every this many files
+const SIZE_SCAN_UPDATE_INTERVAL: u64 = 2000;
+
+/// Recursive size of a direct child of the scanned directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeItem {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+/// Snapshot of a running or finished size scan
+#[derive(Debug, Clone, Default)]
+pub struct SizeBreakdown {
+    /// Children sorted by size, largest first
+    pub items: Vec<SizeItem>,
+    pub total: u64,
+    pub done: bool,
+}
+
+impl SizeBreakdown {
+    fn add(&mut self, item: SizeItem) {
+        self.total += item.size;
+        let pos = self.items.partition_point(|i| i.size >= item.size);
+        self.items.insert(pos, item);
+    }
+}
+
+/// Handle to a background recursive size scan of a directory
+///
+/// The scan is cancelled when the handle is dropped, i.e. when the selection
+/// moves to another entry.
+#[derive(Debug)]
+pub struct DirSizeScan {
+    pub path: PathBuf,
+    state: Arc<Mutex<SizeBreakdown>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl DirSizeScan {
+    pub fn start(path: PathBuf, ctx: &egui::Context) -> Self {
+        let state = Arc::new(Mutex::new(SizeBreakdown::default()));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let thread_path = path.clone();
+        let thread_state = state.clone();
+        let thread_cancel = cancel.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(SIZE_SCAN_DELAY);
+            if thread_cancel.load(Ordering::Relaxed) {
+                return;
+            }
+            scan_dir_sizes(&thread_path, &thread_cancel, |breakdown| {
+                if let Ok(mut state) = thread_state.lock() {
+                    *state = breakdown.clone();
+                }
+                ctx.request_repaint();
+            });
+        });
+
+        Self {
+            path,
+            state,
+            cancel,
+        }
+    }
+
+    pub fn snapshot(&self) -> SizeBreakdown {
+        self.state.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+impl Drop for DirSizeScan {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Compute the recursive size of every direct child of `path`
+///
+/// `on_update` is called with partial results while scanning and once more
+/// with `done` set when the scan completes. Symlinks are counted by their own
+/// size and never followed. Returns `None` if the scan was cancelled.
+pub fn scan_dir_sizes(
+    path: &Path,
+    cancel: &AtomicBool,
+    mut on_update: impl FnMut(&SizeBreakdown),
+) -> Option<SizeBreakdown> {
+    let mut breakdown = SizeBreakdown::default();
+    let mut files_seen = 0u64;
+
+    for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_dir = metadata.is_dir();
+        let mut size = metadata.len();
+
+        if is_dir {
+            size = 0;
+            let mut stack = vec![entry.path()];
+            while let Some(dir) = stack.pop() {
+                for child in fs::read_dir(&dir).into_iter().flatten().flatten() {
+                    if cancel.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    let Ok(child_meta) = child.metadata() else {
+                        continue;
+                    };
+                    if child_meta.is_dir() {
+                        stack.push(child.path());
+                    } else {
+                        size += child_meta.len();
+                        files_seen += 1;
+                        if files_seen.is_multiple_of(SIZE_SCAN_UPDATE_INTERVAL) {
+                            on_update(&breakdown);
+                        }
+                    }
+                }
+            }
+        }
+
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        breakdown.add(SizeItem { name, is_dir, size });
+    }
+
+    breakdown.done = true;
+    on_update(&breakdown);
+    Some(breakdown)
+}
+
+/// Render the largest children of the directory as percentage bars
+fn render_size_breakdown(ui: &mut egui::Ui, breakdown: &SizeBreakdown, colors: &AppColors) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Size Breakdown:").color(colors.fg).strong());
+        ui.label(RichText::new(format_size(breakdown.total, false)).color(colors.fg_light));
+        if !breakdown.done {
+            ui.spinner();
+        }
+    });
+    ui.add_space(5.0);
+
+    let bar_width = ui.available_width();
+    for item in breakdown.items.iter().take(SIZE_BREAKDOWN_TOP_N) {
+        let fraction = if breakdown.total == 0 {
+            0.0
+        } else {
+            item.size as f32 / breakdown.total as f32
+        };
+        let name = if item.is_dir {
+            prefix_dir_name(&item.name)
+        } else {
+            prefix_file_name(&item.name)
+        };
+        ui.add(
+            egui::ProgressBar::new(fraction)
+                .desired_width(bar_width)
+                .fill(colors.bg_selected)
+                .text(
+                    RichText::new(format!(
+                        "{name}  {} ({:.1}%)",
+                        format_size(item.size, false),
+                        fraction * 100.0
+                    ))
+                    .color(colors.fg),
+                ),
+        );
+    }
+    ui.add_space(10.0);
+}
 
 /// Render directory content
-pub fn render(ui: &mut egui::Ui, entries: &[DirectoryEntry], colors: &AppColors) {
+pub fn render(
+    ui: &mut egui::Ui,
+    entries: &[DirectoryEntry],
+    size_scan: Option<&DirSizeScan>,
+    colors: &AppColors,
+) {
+    if let Some(scan) = size_scan {
+        render_size_breakdown(ui, &scan.snapshot(), colors);
+    }
+
     // Display directory contents
     ui.label(
         RichText::new("Directory Contents:")
@@ -78,3 +270,40 @@ pub fn read_dir_entries(path: &Path) -> Result<Vec<DirectoryEntry>, String> {
 
     Ok(entries)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_dir_sizes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("big/nested")).unwrap();
+        fs::write(root.join("big/a.bin"), vec![0u8; 300]).unwrap();
+        fs::write(root.join("big/nested/b.bin"), vec![0u8; 700]).unwrap();
+        fs::write(root.join("small.txt"), vec![0u8; 10]).unwrap();
+        fs::create_dir(root.join("empty")).unwrap();
+
+        let cancel = AtomicBool::new(false);
+        let breakdown = scan_dir_sizes(root, &cancel, |_| {}).unwrap();
+        assert!(breakdown.done);
+        assert_eq!(breakdown.total, 1010);
+        let sizes: Vec<_> = breakdown
+            .items
+            .iter()
+            .map(|i| (i.name.as_str(), i.size))
+            .collect();
+        assert_eq!(sizes, vec![("big", 1000), ("small.txt", 10), ("empty", 0)]);
+    }
+
+    #[test]
+    fn test_scan_dir_sizes_cancelled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        fs::write(temp_dir.path().join("sub/file"), b"data").unwrap();
+
+        let cancel = AtomicBool::new(true);
+        assert!(scan_dir_sizes(temp_dir.path(), &cancel, |_| {}).is_none());
+    }
+}
@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntryMeta;
 use crate::models::preview_content::{CachedEbookMeta, CachedPreviewContent, EbookMeta, metadata};
-use crate::utils::preview_cache;
+use crate::utils::{html, preview_cache};
 
 /// Render Ebook content
 pub fn render(
@@ -165,6 +165,41 @@ pub fn extract_ebook_metadata(entry: DirEntryMeta) -> Result<EbookMeta, String>
     Ok(meta)
 }
 
+/// A readable document of the ebook's spine
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub title: String,
+    pub blocks: Vec<html::Block>,
+}
+
+/// Read the text of all chapters in reading order
+///
+/// Documents without any text, like a cover page, are skipped.
+pub fn read_chapters(path: &std::path::Path) -> Result<Vec<Chapter>, String> {
+    let epub = rbook::Epub::options()
+        .skip_toc(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open EPUB file: {e}"))?;
+
+    let mut chapters = Vec::new();
+    let mut reader = epub.reader();
+    while let Some(content) = reader.read_next() {
+        let content = content.map_err(|e| format!("Failed to read EPUB chapter: {e}"))?;
+        let document = html::parse(content.content());
+        if document.blocks.is_empty() {
+            continue;
+        }
+        let title = document
+            .heading()
+            .unwrap_or_else(|| format!("Chapter {}", chapters.len() + 1));
+        chapters.push(Chapter {
+            title,
+            blocks: document.blocks,
+        });
+    }
+    Ok(chapters)
+}
+
 fn create_metadata_map(epub: &rbook::Epub) -> HashMap<String, String> {
     /// Utility to group multiple metadata entries with a single join.
     #[inline]
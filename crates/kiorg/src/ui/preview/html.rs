@@ -0,0 +1,364 @@
+//! HTML and MHTML preview module
+//!
+//! Pages are rendered as formatted text with their links and images, scripts
+//! and styles are dropped and nothing is fetched from the network.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use egui::RichText;
+use egui::load::Bytes;
+use egui::text::LayoutJob;
+
+use super::links::{self, LinkTarget, TextLink};
+use crate::config::colors::AppColors;
+use crate::models::dir_entry::DirEntryMeta;
+use crate::models::preview_content::{HtmlMeta, PreviewContent};
+use crate::ui::popup::ebook_viewer::{block_font_size, block_indent, span_text};
+use crate::utils::html::{self, BlockKind, Span};
+use crate::utils::{mhtml, preview_cache};
+
+/// Local images larger than this are shown as their alt text
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+fn show_source_id() -> egui::Id {
+    egui::Id::new("html_preview_show_source")
+}
+
+fn is_mhtml(path: &Path) -> bool {
+    matches!(super::path_to_ext_info(path).as_str(), "mht" | "mhtml")
+}
+
+/// Read and parse a page along with the images it shows
+pub fn load(entry: DirEntryMeta) -> Result<PreviewContent, String> {
+    let data = std::fs::read(&entry.path).map_err(|e| format!("Failed to read file: {e}"))?;
+    let archive = if is_mhtml(&entry.path) {
+        Some(mhtml::parse(&data)?)
+    } else {
+        None
+    };
+    let source = match &archive {
+        Some(archive) => archive.html.clone(),
+        None => String::from_utf8_lossy(&data).into_owned(),
+    };
+    let document = html::parse(&source);
+    let dir = entry
+        .path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    let cache_key = preview_cache::calculate_cache_key(&entry);
+    let mut images = HashMap::new();
+    let sources = document
+        .blocks
+        .iter()
+        .flat_map(|block| &block.spans)
+        .filter_map(|span| span.image.as_deref());
+    for src in sources {
+        if images.contains_key(src) {
+            continue;
+        }
+        if let Some(bytes) = image_bytes(src, &dir, archive.as_ref()) {
+            let svg = src.to_ascii_lowercase().contains(".svg")
+                || src.starts_with("data:image/svg")
+                || bytes.starts_with(b"<svg")
+                || bytes.starts_with(b"<?xml");
+            let uri = format!(
+                "bytes://html/{cache_key}/{}{}",
+                images.len(),
+                if svg { ".svg" } else { "" }
+            );
+            images.insert(src.to_string(), (uri, Bytes::from(bytes)));
+        }
+    }
+
+    Ok(PreviewContent::Html(HtmlMeta {
+        document,
+        source,
+        dir,
+        location: archive.and_then(|archive| archive.location),
+        images,
+    }))
+}
+
+/// Bytes of an embedded, archived or local image, remote images are not fetched
+fn image_bytes(src: &str, dir: &Path, archive: Option<&mhtml::Archive>) -> Option<Vec<u8>> {
+    if let Some(data) = src.strip_prefix("data:") {
+        let (media_type, payload) = data.split_once(',')?;
+        return if media_type.ends_with(";base64") {
+            mhtml::decode_base64(payload.as_bytes())
+        } else {
+            Some(html::percent_decode(payload))
+        };
+    }
+    if let Some(archive) = archive {
+        return archive.resource(src).map(<[u8]>::to_vec);
+    }
+    let path = local_path(src, dir)?;
+    let metadata = std::fs::metadata(&path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_IMAGE_BYTES {
+        return None;
+    }
+    std::fs::read(path).ok()
+}
+
+/// Local file a relative or `file://` URL points to
+fn local_path(url: &str, dir: &Path) -> Option<PathBuf> {
+    let url = url.split(['#', '?']).next().unwrap_or_default();
+    if url.is_empty() {
+        return None;
+    }
+    let decoded = String::from_utf8(html::percent_decode(url)).ok()?;
+    if let Some(path) = decoded.strip_prefix("file://") {
+        return Some(PathBuf::from(path));
+    }
+    if decoded.contains("://") || decoded.starts_with("//") || decoded.contains(':') {
+        return None;
+    }
+    Some(dir.join(decoded))
+}
+
+/// Resolve `url` against the URL of an archived page
+fn join_url(base: &str, url: &str) -> String {
+    let Some((scheme, rest)) = base.split_once("://") else {
+        return url.to_string();
+    };
+    if let Some(url) = url.strip_prefix("//") {
+        return format!("{scheme}://{url}");
+    }
+    let host = &rest[..rest.find('/').unwrap_or(rest.len())];
+    if url.starts_with('/') {
+        return format!("{scheme}://{host}{url}");
+    }
+    let dir = &base[..base
+        .rfind('/')
+        .filter(|&i| i > scheme.len() + 2)
+        .unwrap_or(base.len())];
+    format!("{dir}/{url}")
+}
+
+/// Where a clicked link goes, `None` for links within the page
+fn link_target(href: &str, meta: &HtmlMeta) -> Option<LinkTarget> {
+    let href = href.trim();
+    if href.starts_with('#') || href.starts_with("javascript:") {
+        return None;
+    }
+    let lower = href.to_ascii_lowercase();
+    if ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+    {
+        return Some(LinkTarget::Url(href.to_string()));
+    }
+    if let Some(location) = &meta.location
+        && !lower.starts_with("file://")
+    {
+        return Some(LinkTarget::Url(join_url(location, href)));
+    }
+    local_path(href, &meta.dir)
+        .filter(|path| path.exists())
+        .map(LinkTarget::Path)
+}
+
+/// Render a page, or its source when toggled, returns the clicked link if any
+pub fn render(ui: &mut egui::Ui, meta: &HtmlMeta, colors: &AppColors) -> Option<LinkTarget> {
+    let mut show_source = ui
+        .ctx()
+        .data(|d| d.get_temp::<bool>(show_source_id()))
+        .unwrap_or(false);
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+        if ui
+            .toggle_value(&mut show_source, "</> Source")
+            .on_hover_text("Show the HTML source instead of the rendered page")
+            .changed()
+        {
+            ui.ctx()
+                .data_mut(|d| d.insert_temp(show_source_id(), show_source));
+        }
+    });
+    ui.add_space(4.0);
+
+    if show_source {
+        return super::text::render_highlighted_with_links(
+            ui,
+            &meta.source,
+            "html",
+            Some(&meta.dir),
+            colors,
+        );
+    }
+    if meta.document.blocks.is_empty() {
+        ui.label(RichText::new("The page has no text content").color(colors.fg_light));
+        return None;
+    }
+
+    let mut clicked = None;
+    for block in &meta.document.blocks {
+        ui.horizontal_top(|ui| {
+            ui.add_space(block_indent(block.kind));
+            ui.vertical(|ui| {
+                // Images break the text of a block into separate runs
+                let mut run: Vec<&Span> = Vec::new();
+                for span in &block.spans {
+                    let image = span
+                        .image
+                        .as_ref()
+                        .and_then(|src| meta.images.get(src.as_str()));
+                    match image {
+                        Some((uri, bytes)) => {
+                            if let Some(link) = render_run(ui, &run, block.kind, meta, colors) {
+                                clicked = Some(link);
+                            }
+                            run.clear();
+                            let image = egui::Image::from_bytes(uri.clone(), bytes.clone())
+                                .max_width(ui.available_width())
+                                .fit_to_original_size(1.0);
+                            let response = ui.add(image);
+                            if !span.text.is_empty() {
+                                response.on_hover_text(&span.text);
+                            }
+                        }
+                        None => run.push(span),
+                    }
+                }
+                if let Some(link) = render_run(ui, &run, block.kind, meta, colors) {
+                    clicked = Some(link);
+                }
+            });
+        });
+        ui.add_space(if matches!(block.kind, BlockKind::Heading(_)) {
+            10.0
+        } else {
+            6.0
+        });
+    }
+    clicked
+}
+
+/// Render a run of text spans with clickable links
+fn render_run(
+    ui: &mut egui::Ui,
+    spans: &[&Span],
+    kind: BlockKind,
+    meta: &HtmlMeta,
+    colors: &AppColors,
+) -> Option<LinkTarget> {
+    if spans.iter().all(|span| span.text.trim().is_empty()) {
+        return None;
+    }
+    let mut job = LayoutJob::default();
+    if kind == BlockKind::ListItem {
+        RichText::new("•  ")
+            .color(colors.fg_light)
+            .size(block_font_size(kind))
+            .append_to(
+                &mut job,
+                ui.style(),
+                egui::FontSelection::Default,
+                egui::Align::Center,
+            );
+    }
+
+    let mut text_links = Vec::new();
+    for span in spans {
+        let start = job.text.len();
+        let char_start = job.text.chars().count();
+        let mut text = span_text(span, kind, colors);
+        let target = span
+            .link
+            .as_deref()
+            .and_then(|href| link_target(href, meta));
+        if target.is_some() {
+            text = text.color(colors.link_text).underline();
+        }
+        text.append_to(
+            &mut job,
+            ui.style(),
+            egui::FontSelection::Default,
+            egui::Align::Center,
+        );
+        if let Some(target) = target {
+            text_links.push(TextLink {
+                byte_range: start..job.text.len(),
+                char_range: char_start..job.text.chars().count(),
+                target,
+            });
+        }
+    }
+    links::show_job(ui, job, &text_links)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(dir: &Path, location: Option<&str>) -> HtmlMeta {
+        HtmlMeta {
+            document: html::Document::default(),
+            source: String::new(),
+            dir: dir.to_path_buf(),
+            location: location.map(str::to_string),
+            images: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_link_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("other page.html"), "").unwrap();
+        let local = meta(temp_dir.path(), None);
+
+        assert_eq!(
+            link_target("https://example.com/", &local),
+            Some(LinkTarget::Url("https://example.com/".to_string()))
+        );
+        assert_eq!(
+            link_target("other%20page.html#top", &local),
+            Some(LinkTarget::Path(temp_dir.path().join("other page.html")))
+        );
+        assert_eq!(link_target("missing.html", &local), None);
+        assert_eq!(link_target("#section", &local), None);
+        assert_eq!(link_target("javascript:alert(1)", &local), None);
+
+        let archived = meta(temp_dir.path(), Some("https://example.com/docs/page.html"));
+        assert_eq!(
+            link_target("next.html", &archived),
+            Some(LinkTarget::Url(
+                "https://example.com/docs/next.html".to_string()
+            ))
+        );
+        assert_eq!(
+            link_target("/index.html", &archived),
+            Some(LinkTarget::Url(
+                "https://example.com/index.html".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_load_resolves_images() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.png"), b"png").unwrap();
+        let path = temp_dir.path().join("page.html");
+        std::fs::write(
+            &path,
+            r#"<h1>Title</h1><p><img src="a.png"><img src="data:image/gif;base64,R0lG">
+            <img src="https://example.com/remote.png" alt="Remote"></p>"#,
+        )
+        .unwrap();
+
+        let entry = DirEntryMeta {
+            path,
+            modified: std::time::SystemTime::UNIX_EPOCH,
+        };
+        let Ok(PreviewContent::Html(meta)) = load(entry) else {
+            panic!("expected an HTML preview");
+        };
+        assert_eq!(meta.document.heading().as_deref(), Some("Title"));
+        assert_eq!(meta.images.len(), 2);
+        assert_eq!(meta.images["a.png"].1.as_ref(), b"png");
+        assert_eq!(meta.images["data:image/gif;base64,R0lG"].1.as_ref(), b"GIF");
+        assert!(!meta.images.contains_key("https://example.com/remote.png"));
+    }
+}
@@ -1,14 +1,67 @@
 //! Image preview module
 
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntryMeta;
-use crate::models::preview_content::{CachedImageMeta, CachedPreviewContent, ImageMeta, metadata};
+use crate::models::preview_content::{
+    CachedImageMeta, CachedPreviewContent, ChannelStats, ImageMeta, ImageStats, metadata,
+};
+use crate::utils::format::format_count;
 use crate::utils::preview_cache;
 use egui::{Rect, RichText};
 use image::{GenericImageView, ImageDecoder, ImageFormat};
 use std::collections::HashMap;
 use std::path::Path;
 
+/// EXIF tags shown in their own table, by group title
+const EXIF_GROUPS: &[(&str, &[&str])] = &[
+    (
+        "Camera",
+        &[
+            "Make",
+            "Model",
+            "BodySerialNumber",
+            "Software",
+            "DateTimeOriginal",
+            "DateTimeDigitized",
+            "DateTime",
+            "Artist",
+            "Copyright",
+        ],
+    ),
+    (
+        "Exposure",
+        &[
+            "ExposureTime",
+            "FNumber",
+            "PhotographicSensitivity",
+            "ExposureProgram",
+            "ExposureMode",
+            "ExposureBiasValue",
+            "ShutterSpeedValue",
+            "ApertureValue",
+            "BrightnessValue",
+            "MeteringMode",
+            "Flash",
+            "WhiteBalance",
+            "SceneCaptureType",
+        ],
+    ),
+    (
+        "Lens",
+        &[
+            "LensMake",
+            "LensModel",
+            "LensSerialNumber",
+            "LensSpecification",
+            "FocalLength",
+            "FocalLengthIn35mmFilm",
+            "MaxApertureValue",
+            "DigitalZoomRatio",
+        ],
+    ),
+];
+
 /// Render image content
 pub fn render(
     ui: &mut egui::Ui,
@@ -26,16 +79,19 @@ pub fn render(
     );
     ui.add_space(10.0);
 
-    // Display image (centered)
-    ui.vertical_centered(|ui| {
-        ui.add(
-            image_meta
-                .image
-                .clone()
-                .max_size(egui::vec2(available_width, available_height * 0.6))
-                .maintain_aspect_ratio(true),
-        );
-    });
+    // Display image, scroll to zoom and drag to pan
+    let show_checkerboard = image_meta.checkerboard && checkerboard_enabled(ui.ctx());
+    render_zoomable(
+        ui,
+        &image_meta.image,
+        egui::Id::new(&image_meta.title),
+        egui::vec2(available_width, available_height * 0.6),
+        show_checkerboard,
+    );
+    if image_meta.checkerboard {
+        ui.add_space(5.0);
+        render_checkerboard_toggle(ui, colors);
+    }
     ui.add_space(15.0);
 
     // Create a table for regular metadata
@@ -63,45 +119,159 @@ pub fn render(
                         ui.set_max_width(super::METADATA_TBL_KEY_COL_W);
                         ui.add(egui::Label::new(RichText::new(key).color(colors.fg)).wrap());
                     });
-                    ui.add(egui::Label::new(RichText::new(value).color(colors.fg)).wrap());
+                    metadata_value(ui, key, value, colors);
                     ui.end_row();
                 }
             }
         });
 
-    // Display EXIF data in a separate table if available
-    if let Some(exif_data) = &image_meta.exif_data {
+    if let Some(stats) = &image_meta.stats {
         ui.add_space(15.0);
         ui.label(
-            RichText::new("EXIF Data")
+            RichText::new("Histogram")
                 .color(colors.fg_folder)
                 .strong()
                 .size(14.0),
         );
         ui.add_space(5.0);
-        egui::Grid::new("exif_data_grid")
-            .num_columns(2)
-            .spacing([10.0, 6.0])
-            .striped(true)
-            .show(ui, |ui| {
-                // Sort keys for consistent display
-                let mut sorted_keys: Vec<&String> = exif_data.keys().collect::<Vec<&String>>();
-                sorted_keys.sort();
+        render_histogram(ui, stats, colors);
+    }
 
-                // Display each EXIF field in a table row
-                for key in sorted_keys {
-                    if let Some(value) = exif_data.get(key) {
+    // Display EXIF data in separate tables per group if available
+    if let Some(exif_data) = &image_meta.exif_data {
+        for (title, rows) in group_exif(exif_data) {
+            ui.add_space(15.0);
+            ui.label(
+                RichText::new(format!("EXIF: {title}"))
+                    .color(colors.fg_folder)
+                    .strong()
+                    .size(14.0),
+            );
+            ui.add_space(5.0);
+            egui::Grid::new(("exif_data_grid", title))
+                .num_columns(2)
+                .spacing([10.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (key, value) in rows {
                         ui.with_layout(egui::Layout::left_to_right(egui::Align::LEFT), |ui| {
                             ui.set_min_width(super::METADATA_TBL_KEY_COL_W);
                             ui.set_max_width(super::METADATA_TBL_KEY_COL_W);
                             ui.add(egui::Label::new(RichText::new(key).color(colors.fg)).wrap());
                         });
-                        ui.add(egui::Label::new(RichText::new(value).color(colors.fg)).wrap());
+                        metadata_value(ui, key, value, colors);
                         ui.end_row();
                     }
-                }
-            });
+                });
+
+            if title == "GPS"
+                && let Some(coordinates) = exif_data.get(metadata::EXIF_GPS_COORDINATES)
+            {
+                ui.add_space(5.0);
+                render_gps_actions(ui, coordinates);
+            }
+        }
+    }
+}
+
+/// Value cell of a metadata table, right click to copy it
+fn metadata_value(ui: &mut egui::Ui, key: &str, value: &str, colors: &AppColors) {
+    let response = ui.add(
+        egui::Label::new(RichText::new(value).color(colors.fg))
+            .wrap()
+            .sense(egui::Sense::click()),
+    );
+    super::table::copy_menu(&response, value, &[key, value], None);
+}
+
+/// Split EXIF fields into the display groups, GPS and everything else
+///
+/// Fields within a group keep the order of `EXIF_GROUPS`, the remaining
+/// groups are sorted by tag name. Empty groups are omitted.
+fn group_exif(exif_data: &HashMap<String, String>) -> Vec<(&'static str, Vec<(&str, &str)>)> {
+    let mut groups = Vec::new();
+    for (title, tags) in EXIF_GROUPS {
+        let rows: Vec<(&str, &str)> = tags
+            .iter()
+            .filter_map(|tag| exif_data.get_key_value(*tag))
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        if !rows.is_empty() {
+            groups.push((*title, rows));
+        }
     }
+
+    let mut gps = Vec::new();
+    let mut other = Vec::new();
+    for (key, value) in exif_data {
+        if EXIF_GROUPS
+            .iter()
+            .any(|(_, tags)| tags.contains(&key.as_str()))
+        {
+            continue;
+        }
+        if key.starts_with("GPS") {
+            gps.push((key.as_str(), value.as_str()));
+        } else {
+            other.push((key.as_str(), value.as_str()));
+        }
+    }
+    for (title, mut rows) in [("GPS", gps), ("Other", other)] {
+        if !rows.is_empty() {
+            rows.sort_unstable();
+            groups.push((title, rows));
+        }
+    }
+    groups
+}
+
+/// Copy and open-in-maps actions for the decimal GPS coordinates
+fn render_gps_actions(ui: &mut egui::Ui, coordinates: &str) {
+    ui.horizontal(|ui| {
+        if ui.button("Copy coordinates").clicked() {
+            ui.ctx().copy_text(coordinates.to_string());
+        }
+        if ui.button("Open in maps").clicked()
+            && let Some((lat, lon)) = coordinates.split_once(", ")
+        {
+            let url =
+                format!("https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map=15/{lat}/{lon}");
+            if let Err(e) = crate::open_wrap::open_that(&url) {
+                tracing::warn!("Failed to open map url {}: {}", url, e);
+            }
+        }
+    });
+}
+
+/// Decimal latitude and longitude from the EXIF GPS fields
+fn gps_coordinates(fields: &[exif::Field]) -> Option<(f64, f64)> {
+    let find = |tag| fields.iter().find(|f| f.tag == tag).map(|f| &f.value);
+    let to_degrees = |value: &exif::Value| match value {
+        exif::Value::Rational(dms) if dms.len() == 3 => {
+            Some(dms[0].to_f64() + dms[1].to_f64() / 60.0 + dms[2].to_f64() / 3600.0)
+        }
+        _ => None,
+    };
+    let is_negative = |value: Option<&exif::Value>, negative_ref: u8| match value {
+        Some(exif::Value::Ascii(refs)) => refs
+            .first()
+            .and_then(|r| r.first())
+            .is_some_and(|r| r.eq_ignore_ascii_case(&negative_ref)),
+        _ => false,
+    };
+
+    let mut lat = to_degrees(find(exif::Tag::GPSLatitude)?)?;
+    let mut lon = to_degrees(find(exif::Tag::GPSLongitude)?)?;
+    if is_negative(find(exif::Tag::GPSLatitudeRef), b'S') {
+        lat = -lat;
+    }
+    if is_negative(find(exif::Tag::GPSLongitudeRef), b'W') {
+        lon = -lon;
+    }
+    if !lat.is_finite() || !lon.is_finite() {
+        return None;
+    }
+    Some((lat, lon))
 }
 
 /// Generate a URI for an image file path
@@ -111,11 +281,51 @@ fn image_path_to_uri(path: &Path) -> String {
 }
 
 /// Read image file, extract metadata, and create `PreviewContent`
+/// Reason to defer the preview of an image with more than `max_pixels` pixels
+///
+/// Only the image header is read, unknown formats and SVGs are never deferred.
+#[must_use]
+pub fn exceeds_pixel_limit(path: &Path, max_pixels: u64) -> Option<String> {
+    let (width, height) = image::ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    let pixels = u64::from(width) * u64::from(height);
+    (pixels > max_pixels).then(|| {
+        format!("Image is {width}x{height}, larger than the {max_pixels} pixel preview limit")
+    })
+}
+
+/// Key of the cached preview of an image, which differs for images shown
+/// without their EXIF orientation applied
+pub fn cache_key(entry: &DirEntryMeta, apply_orientation: bool) -> String {
+    let key = preview_cache::calculate_cache_key(entry);
+    if apply_orientation {
+        key
+    } else {
+        format!("{key}.unoriented")
+    }
+}
+
+/// Decode an image for the preview, rotated and flipped upright as its EXIF
+/// orientation says when `apply_orientation` is set
 pub fn read_image_with_metadata(
     entry: DirEntryMeta,
     ctx: &egui::Context,
     available_width: Option<f32>,
+    apply_orientation: bool,
 ) -> Result<ImageMeta, String> {
+    // SVGs are rasterized by resvg instead of decoded
+    if entry
+        .path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+    {
+        return super::svg::read_svg_with_metadata(entry, ctx, available_width);
+    }
+
     // Get the filename for the title
     let title = entry
         .path
@@ -151,6 +361,12 @@ pub fn read_image_with_metadata(
         // Only create the HashMap if we have EXIF data
         if !fields.is_empty() {
             let mut exif_map = HashMap::new();
+            if let Some((lat, lon)) = gps_coordinates(&fields) {
+                exif_map.insert(
+                    metadata::EXIF_GPS_COORDINATES.to_string(),
+                    format!("{lat:.6}, {lon:.6}"),
+                );
+            }
             for field in fields {
                 exif_map.insert(
                     format!("{}", field.tag),
@@ -238,21 +454,29 @@ pub fn read_image_with_metadata(
         Err(e) => return Err(format!("Failed to decode image: {e}")),
     };
 
-    img.apply_orientation(orientation);
+    if apply_orientation {
+        img.apply_orientation(orientation);
+    }
 
     // Extract basic image information
     let dimensions = img.dimensions();
     metadata.insert(
         metadata::IMG_DIMENSIONS.to_string(),
-        format!("{}x{} pixels", dimensions.0, dimensions.1),
+        format!(
+            "{}x{} pixels",
+            format_count(dimensions.0.into()),
+            format_count(dimensions.1.into())
+        ),
     );
 
     let texture_id = format!("image_{}", entry.path.display());
+    let stats = compute_stats(&img);
 
     if !skip_cache {
         let title_clone = title.clone();
         let metadata_clone = metadata.clone();
         let exif_data_clone = exif_data.clone();
+        let stats_clone = stats.clone();
         let img_clone = img.clone();
 
         std::thread::spawn(move || {
@@ -270,8 +494,9 @@ pub fn read_image_with_metadata(
                     exif_data: exif_data_clone,
                     cache_bytes: Some(png_bytes),
                     uri: None,
+                    stats: stats_clone,
                 });
-                let cache_key = preview_cache::calculate_cache_key(&entry);
+                let cache_key = cache_key(&entry, apply_orientation);
                 if let Err(e) = preview_cache::save_preview(&cache_key, &cached_content) {
                     tracing::warn!("Failed to save image preview cache: {}", e);
                 }
@@ -335,11 +560,191 @@ pub fn read_image_with_metadata(
     let color_image = egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
     let texture = ctx.load_texture(texture_id, color_image, egui::TextureOptions::default());
 
-    let meta = crate::models::preview_content::ImageMeta::new(title, metadata, texture, exif_data);
+    let mut meta =
+        crate::models::preview_content::ImageMeta::new(title, metadata, texture, exif_data);
+    meta.stats = stats;
 
     Ok(meta)
 }
 
+/// Images with more pixels are downscaled before computing their histogram
+const HISTOGRAM_MAX_PIXELS: u64 = 1024 * 1024;
+
+/// Names and plot colors of the channels in `ImageStats`
+const HISTOGRAM_CHANNELS: [(&str, egui::Color32); 4] = [
+    ("Red", egui::Color32::from_rgb(230, 70, 70)),
+    ("Green", egui::Color32::from_rgb(70, 200, 90)),
+    ("Blue", egui::Color32::from_rgb(80, 130, 240)),
+    ("Luminance", egui::Color32::from_gray(200)),
+];
+
+/// Histogram and min, max and mean of the RGB channels and the luminance
+///
+/// Fully transparent pixels are ignored, returns `None` if all of them are.
+pub fn compute_stats(img: &image::DynamicImage) -> Option<ImageStats> {
+    let (width, height) = img.dimensions();
+    let sample;
+    let img = if u64::from(width) * u64::from(height) > HISTOGRAM_MAX_PIXELS {
+        sample = img.thumbnail(1024, 1024);
+        &sample
+    } else {
+        img
+    };
+
+    let mut histograms = [[0u32; 256]; 4];
+    let mut count = 0u64;
+    for pixel in img.to_rgba8().pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a == 0 {
+            continue;
+        }
+        // Rec. 709 luma of the encoded values
+        let luma = (0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b)).round();
+        for (histogram, value) in histograms.iter_mut().zip([r, g, b, luma as u8]) {
+            histogram[usize::from(value)] += 1;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return None;
+    }
+
+    let channels = histograms
+        .iter()
+        .map(|histogram| {
+            let min = histogram.iter().position(|&n| n > 0).unwrap_or(0);
+            let max = histogram.iter().rposition(|&n| n > 0).unwrap_or(0);
+            let sum: u64 = histogram
+                .iter()
+                .enumerate()
+                .map(|(value, &n)| value as u64 * u64::from(n))
+                .sum();
+            ChannelStats {
+                histogram: histogram.to_vec(),
+                min: min as u8,
+                max: max as u8,
+                mean: (sum as f64 / count as f64) as f32,
+            }
+        })
+        .collect();
+    Some(ImageStats { channels })
+}
+
+/// Draw the histogram as overlapping channel curves with a table of statistics
+fn render_histogram(ui: &mut egui::Ui, stats: &ImageStats, colors: &AppColors) {
+    let width = ui.available_width().min(400.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 80.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, colors.bg_extreme);
+
+    // Scale to the highest bin, ignoring clipped blacks and whites that would flatten the rest
+    let peak = stats
+        .channels
+        .iter()
+        .flat_map(|channel| &channel.histogram[1..255])
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+    for (channel, (_, color)) in stats.channels.iter().zip(HISTOGRAM_CHANNELS) {
+        let points: Vec<egui::Pos2> = channel
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(value, &n)| {
+                let x = rect.left() + rect.width() * value as f32 / 255.0;
+                let y = rect.bottom() - rect.height() * (n as f32 / peak).min(1.0);
+                egui::pos2(x, y)
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.0, color.gamma_multiply(0.8)),
+        ));
+    }
+    ui.add_space(5.0);
+
+    egui::Grid::new("image_histogram_grid")
+        .num_columns(4)
+        .spacing([10.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Channel", "Min", "Max", "Mean"] {
+                ui.label(RichText::new(header).color(colors.fg_light));
+            }
+            ui.end_row();
+            for (channel, (name, color)) in stats.channels.iter().zip(HISTOGRAM_CHANNELS) {
+                ui.label(RichText::new(name).color(color));
+                ui.label(RichText::new(channel.min.to_string()).color(colors.fg));
+                ui.label(RichText::new(channel.max.to_string()).color(colors.fg));
+                ui.label(RichText::new(format!("{:.1}", channel.mean)).color(colors.fg));
+                ui.end_row();
+            }
+        });
+}
+
+/// Side of a checkerboard square in points
+const CHECKERBOARD_SQUARE: f32 = 8.0;
+const CHECKERBOARD_LIGHT: egui::Color32 = egui::Color32::from_gray(230);
+const CHECKERBOARD_DARK: egui::Color32 = egui::Color32::from_gray(190);
+
+fn checkerboard_hidden_id() -> egui::Id {
+    egui::Id::new("image_checkerboard_hidden")
+}
+
+/// Whether the checkerboard behind transparent images is shown
+pub fn checkerboard_enabled(ctx: &egui::Context) -> bool {
+    !ctx.data(|d| {
+        d.get_temp::<bool>(checkerboard_hidden_id())
+            .unwrap_or(false)
+    })
+}
+
+/// Checkbox toggling the checkerboard behind transparent images
+pub fn render_checkerboard_toggle(ui: &mut egui::Ui, colors: &AppColors) {
+    let mut enabled = checkerboard_enabled(ui.ctx());
+    if ui
+        .checkbox(
+            &mut enabled,
+            RichText::new("Checkerboard background").color(colors.fg),
+        )
+        .changed()
+    {
+        ui.ctx()
+            .data_mut(|d| d.insert_temp(checkerboard_hidden_id(), !enabled));
+    }
+}
+
+/// Checkerboard filling `rect`, only the squares inside `clip` are generated
+pub fn checkerboard_shape(rect: Rect, clip: Rect) -> egui::Shape {
+    let visible = rect.intersect(clip);
+    if !visible.is_positive() {
+        return egui::Shape::Noop;
+    }
+
+    let mut shapes = vec![egui::Shape::rect_filled(visible, 0.0, CHECKERBOARD_LIGHT)];
+    let first_col = ((visible.min.x - rect.min.x) / CHECKERBOARD_SQUARE).floor() as i64;
+    let last_col = ((visible.max.x - rect.min.x) / CHECKERBOARD_SQUARE).ceil() as i64;
+    let first_row = ((visible.min.y - rect.min.y) / CHECKERBOARD_SQUARE).floor() as i64;
+    let last_row = ((visible.max.y - rect.min.y) / CHECKERBOARD_SQUARE).ceil() as i64;
+    for row in first_row..last_row {
+        for col in first_col..last_col {
+            if (row + col) % 2 == 0 {
+                continue;
+            }
+            let min = rect.min + egui::vec2(col as f32, row as f32) * CHECKERBOARD_SQUARE;
+            let square =
+                Rect::from_min_size(min, egui::Vec2::splat(CHECKERBOARD_SQUARE)).intersect(visible);
+            shapes.push(egui::Shape::rect_filled(square, 0.0, CHECKERBOARD_DARK));
+        }
+    }
+    egui::Shape::Vec(shapes)
+}
+
+/// Smallest zoom relative to the zoom that fits the image in the view
+const MIN_ZOOM_FACTOR: f32 = 0.5;
+const MAX_ZOOM: f32 = 32.0;
+
 /// Render an interactive image with pan and zoom support
 pub fn render_interactive(
     ui: &mut egui::Ui,
@@ -347,7 +752,82 @@ pub fn render_interactive(
     source_id: egui::Id,
     available_width: f32,
     available_height: f32,
+    checkerboard: bool,
+    zoom_mode: ZoomMode,
 ) {
+    render_interactive_view(
+        ui,
+        image,
+        source_id,
+        available_width,
+        available_height,
+        ViewOptions {
+            checkerboard,
+            zoom_mode,
+            ..Default::default()
+        },
+    );
+}
+
+/// How [`render_interactive_view`] shows and handles an image
+#[derive(Debug, Clone, Copy)]
+pub struct ViewOptions {
+    /// Paint a checkerboard behind the image
+    pub checkerboard: bool,
+    /// Pan with the primary button in addition to the middle button
+    pub drag_to_pan: bool,
+    /// How the image fits the view when it's first shown or reset
+    pub zoom_mode: ZoomMode,
+    /// Zoom showing the image at its actual size, for images not rendered at
+    /// one pixel per screen pixel
+    pub actual_size_zoom: Option<f32>,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        Self {
+            checkerboard: false,
+            drag_to_pan: true,
+            zoom_mode: ZoomMode::default(),
+            actual_size_zoom: None,
+        }
+    }
+}
+
+/// Pick the zoom mode of the preview popup, returns whether it changed
+pub fn zoom_mode_selector(ui: &mut egui::Ui, zoom_mode: &mut ZoomMode) -> bool {
+    let mut changed = false;
+    for (mode, label) in [
+        (ZoomMode::FitPage, "Fit page"),
+        (ZoomMode::FitWidth, "Fit width"),
+        (ZoomMode::ActualSize, "100%"),
+    ] {
+        changed |= ui.selectable_value(zoom_mode, mode, label).changed();
+    }
+    changed
+}
+
+/// Where an interactive image was painted in this frame
+pub struct ImageView {
+    /// Response of the whole view area
+    pub response: egui::Response,
+    /// Screen rect of the image after pan and zoom
+    pub paint_rect: Rect,
+}
+
+/// Like [`render_interactive`], for callers that draw on top of the image
+///
+/// With [`ViewOptions::drag_to_pan`] disabled, dragging with the primary button is left to
+/// the caller and the image is panned with the middle button or by scrolling.
+pub fn render_interactive_view(
+    ui: &mut egui::Ui,
+    image: &egui::Image<'static>,
+    source_id: egui::Id,
+    available_width: f32,
+    available_height: f32,
+    options: ViewOptions,
+) -> Option<ImageView> {
+    let mut view = None;
     ui.vertical_centered(|ui| {
         let default_init_height = available_height * 0.97;
         let default_init_width = available_width * 0.97;
@@ -366,21 +846,39 @@ pub fn render_interactive(
             return;
         };
 
-        // Unique id for storing pan/zoom state per image
-        let id = ui.id().with("image_pan_zoom").with(source_id);
-        let mut pan = ui.ctx().data(|d| {
-            d.get_temp::<egui::Vec2>(id.with("pan"))
-                .unwrap_or(egui::Vec2::ZERO)
-        });
+        // Unique id for storing pan/zoom state per image, a new zoom mode
+        // starts over from its initial view
+        let id = ui
+            .id()
+            .with("image_pan_zoom")
+            .with(source_id)
+            .with(options.zoom_mode);
 
-        let init_zoom = || -> f32 {
+        let fit_zoom = {
             let scale_x = default_init_width / raw_img_w;
             let scale_y = default_init_height / raw_img_h;
             scale_x.min(scale_y).min(1.0)
         };
+        let init_zoom = match options.zoom_mode {
+            ZoomMode::FitPage => fit_zoom,
+            ZoomMode::FitWidth => default_init_width / raw_img_w,
+            ZoomMode::ActualSize => options
+                .actual_size_zoom
+                .unwrap_or_else(|| 1.0 / ui.ctx().pixels_per_point()),
+        };
+        // Start from the top of images taller than the view, the pan is
+        // clamped to the image below
+        let init_pan = egui::vec2(
+            0.0,
+            (raw_img_h * init_zoom - available_height).max(0.0) / 2.0,
+        );
+
+        let mut pan = ui
+            .ctx()
+            .data(|d| d.get_temp::<egui::Vec2>(id.with("pan")).unwrap_or(init_pan));
         let mut zoom = ui
             .ctx()
-            .data(|d| d.get_temp::<f32>(id.with("zoom")).unwrap_or_else(init_zoom));
+            .data(|d| d.get_temp::<f32>(id.with("zoom")).unwrap_or(init_zoom));
         let mut reset_view = false;
 
         egui::ScrollArea::both()
@@ -397,7 +895,9 @@ pub fn render_interactive(
                     return;
                 }
                 // detect pan through click and drag
-                if response.dragged() {
+                if response.dragged_by(egui::PointerButton::Middle)
+                    || (options.drag_to_pan && response.dragged())
+                {
                     // drag_delta is absolute value relative to view port without zoom applied
                     pan += response.drag_delta() * zoom;
                 }
@@ -406,7 +906,20 @@ pub fn render_interactive(
                 ui.input(|i| {
                     // Pinch zoom: zoom_delta is a relative multiplier, not an offset
                     let zoom_delta = i.zoom_delta();
-                    zoom *= zoom_delta;
+                    if zoom_delta != 1.0 {
+                        let min_zoom = fit_zoom.min(init_zoom) * MIN_ZOOM_FACTOR;
+                        let new_zoom = (zoom * zoom_delta).clamp(min_zoom, MAX_ZOOM);
+                        // Keep the point between the fingers (or under the pointer) in place
+                        let anchor = i
+                            .multi_touch()
+                            .map(|touch| touch.center_pos)
+                            .or_else(|| i.pointer.hover_pos());
+                        if let Some(anchor) = anchor {
+                            let offset = anchor - response.rect.center();
+                            pan = offset - (offset - pan) * (new_zoom / zoom);
+                        }
+                        zoom = new_zoom;
+                    }
                     // scroll value is absolute vlaue relative to view port without zoom applied
                     let scroll = i.smooth_scroll_delta;
                     if scroll.x.abs() > 0.0 {
@@ -441,14 +954,312 @@ pub fn render_interactive(
                 // use from_center_size to always center image when pan is 0
                 let paint_rect =
                     Rect::from_center_size(response.rect.center() + pan, scaled_img_size);
+                if options.checkerboard {
+                    ui.painter()
+                        .add(checkerboard_shape(paint_rect, ui.clip_rect()));
+                }
                 image.paint_at(ui, paint_rect);
+                view = Some(ImageView {
+                    response,
+                    paint_rect,
+                });
             });
 
         if reset_view {
-            zoom = init_zoom();
-            pan = egui::Vec2::ZERO;
+            zoom = init_zoom;
+            pan = init_pan;
             ui.ctx().data_mut(|d| d.insert_temp(id.with("pan"), pan));
             ui.ctx().data_mut(|d| d.insert_temp(id.with("zoom"), zoom));
         }
     });
+    view
+}
+
+/// Zoom preset of the right panel image preview, cycled with a shortcut
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZoomPreset {
+    /// The whole image is visible
+    #[default]
+    Fit,
+    /// One image pixel per screen pixel
+    Actual,
+    /// The image covers the whole view, the rest can be panned into view
+    Fill,
+}
+
+impl ZoomPreset {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Fit => Self::Actual,
+            Self::Actual => Self::Fill,
+            Self::Fill => Self::Fit,
+        }
+    }
+
+    /// Zoom of an image of `image_size` in a view of `view_size`
+    #[must_use]
+    pub fn zoom(self, image_size: egui::Vec2, view_size: egui::Vec2, pixels_per_point: f32) -> f32 {
+        let scale = view_size / image_size;
+        match self {
+            Self::Fit => scale.min_elem(),
+            Self::Actual => 1.0 / pixels_per_point,
+            Self::Fill => scale.max_elem(),
+        }
+    }
+}
+
+/// Pan and zoom of an image in the right panel
+#[derive(Debug, Clone, Copy, Default)]
+struct PanelZoom {
+    preset: ZoomPreset,
+    /// Zoom set by scrolling, replaces the zoom of the preset
+    zoom: Option<f32>,
+    pan: egui::Vec2,
+}
+
+fn panel_zoom_cycle_id() -> egui::Id {
+    egui::Id::new("image_panel_zoom_cycle")
+}
+
+/// Switch the image in the right panel to the next zoom preset
+pub fn cycle_panel_zoom(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(panel_zoom_cycle_id(), true));
+}
+
+/// Keep an image of `scaled_size` covering as much of `view_size` as it can
+fn clamp_pan(pan: egui::Vec2, scaled_size: egui::Vec2, view_size: egui::Vec2) -> egui::Vec2 {
+    let max_pan = ((scaled_size - view_size) / 2.0).max(egui::Vec2::ZERO);
+    pan.clamp(-max_pan, max_pan)
+}
+
+/// Render an image that zooms with the scroll wheel and pans by dragging
+///
+/// The view is as wide as `max_size` and as tall as the fitted image, double
+/// click goes back to the fitted view.
+pub fn render_zoomable(
+    ui: &mut egui::Ui,
+    image: &egui::Image<'static>,
+    source_id: egui::Id,
+    max_size: egui::Vec2,
+    checkerboard: bool,
+) {
+    let image_size = image
+        .load_for_size(ui.ctx(), max_size)
+        .ok()
+        .and_then(|poll| poll.size())
+        .filter(|size| size.x > 0.0 && size.y > 0.0);
+    let Some(image_size) = image_size else {
+        ui.allocate_ui_with_layout(
+            egui::vec2(max_size.x, max_size.y.min(100.0)),
+            egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+            |ui| {
+                ui.spinner();
+            },
+        );
+        return;
+    };
+
+    let view_size = egui::vec2(
+        max_size.x,
+        max_size.y.min(image_size.y * max_size.x / image_size.x),
+    );
+    let (rect, response) = ui.allocate_exact_size(view_size, egui::Sense::click_and_drag());
+    let id = egui::Id::new("image_panel_zoom").with(source_id);
+    let mut state = ui.data(|d| d.get_temp::<PanelZoom>(id)).unwrap_or_default();
+    if ui
+        .data_mut(|d| d.remove_temp::<bool>(panel_zoom_cycle_id()))
+        .is_some()
+    {
+        state = PanelZoom {
+            preset: state.preset.next(),
+            ..Default::default()
+        };
+    }
+    if response.double_clicked() {
+        state = PanelZoom::default();
+    }
+
+    let pixels_per_point = ui.ctx().pixels_per_point();
+    let mut zoom = state
+        .zoom
+        .unwrap_or_else(|| state.preset.zoom(image_size, view_size, pixels_per_point));
+    if response.dragged() {
+        state.pan += response.drag_delta();
+    }
+    if response.hovered() {
+        let (scroll, pinch, pointer) = ui.input(|i| {
+            (
+                i.smooth_scroll_delta.y,
+                i.zoom_delta(),
+                i.pointer.hover_pos(),
+            )
+        });
+        let factor = pinch * (scroll / 200.0).exp();
+        if factor != 1.0 {
+            let min_zoom =
+                ZoomPreset::Fit.zoom(image_size, view_size, pixels_per_point) * MIN_ZOOM_FACTOR;
+            let new_zoom = (zoom * factor).clamp(min_zoom, MAX_ZOOM);
+            // Keep the point under the pointer in place
+            if let Some(pointer) = pointer {
+                let offset = pointer - rect.center();
+                state.pan = offset - (offset - state.pan) * (new_zoom / zoom);
+            }
+            zoom = new_zoom;
+            state.zoom = Some(zoom);
+        }
+        // The preview panel doesn't scroll while the pointer is over the image
+        ui.input_mut(|i| i.smooth_scroll_delta = egui::Vec2::ZERO);
+    }
+
+    let scaled_size = image_size * zoom;
+    state.pan = clamp_pan(state.pan, scaled_size, view_size);
+    ui.data_mut(|d| d.insert_temp(id, state));
+
+    let clip = rect.intersect(ui.clip_rect());
+    let mut view_ui = ui.new_child(egui::UiBuilder::new().max_rect(rect));
+    view_ui.set_clip_rect(clip);
+    let paint_rect = Rect::from_center_size(rect.center() + state.pan, scaled_size);
+    if checkerboard {
+        view_ui.painter().add(checkerboard_shape(paint_rect, clip));
+    }
+    image.paint_at(&view_ui, paint_rect);
+
+    if state.zoom.is_some() || state.preset != ZoomPreset::Fit {
+        let percent = zoom * pixels_per_point * 100.0;
+        view_ui.painter().text(
+            rect.right_bottom() - egui::vec2(4.0, 4.0),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("{percent:.0}%"),
+            egui::FontId::proportional(12.0),
+            egui::Color32::WHITE,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ascii_field(tag: exif::Tag, value: &str) -> exif::Field {
+        exif::Field {
+            tag,
+            ifd_num: exif::In::PRIMARY,
+            value: exif::Value::Ascii(vec![value.as_bytes().to_vec()]),
+        }
+    }
+
+    fn dms_field(tag: exif::Tag, d: u32, m: u32, s: u32) -> exif::Field {
+        exif::Field {
+            tag,
+            ifd_num: exif::In::PRIMARY,
+            value: exif::Value::Rational(vec![
+                exif::Rational::from((d, 1)),
+                exif::Rational::from((m, 1)),
+                exif::Rational::from((s, 100)),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_zoom_presets() {
+        let image = egui::vec2(400.0, 200.0);
+        let view = egui::vec2(100.0, 100.0);
+        assert_eq!(ZoomPreset::Fit.zoom(image, view, 2.0), 0.25);
+        assert_eq!(ZoomPreset::Actual.zoom(image, view, 2.0), 0.5);
+        assert_eq!(ZoomPreset::Fill.zoom(image, view, 2.0), 0.5);
+        assert_eq!(ZoomPreset::Fill.next(), ZoomPreset::Fit);
+    }
+
+    #[test]
+    fn test_clamp_pan() {
+        let view = egui::vec2(100.0, 100.0);
+        let pan = clamp_pan(egui::vec2(80.0, -80.0), egui::vec2(200.0, 50.0), view);
+        assert_eq!(pan, egui::vec2(50.0, 0.0));
+    }
+
+    #[test]
+    fn test_checkerboard_shape() {
+        let rect = Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(32.0, 16.0));
+        let egui::Shape::Vec(shapes) = checkerboard_shape(rect, rect) else {
+            panic!("expected checkerboard shapes");
+        };
+        // Light background plus every other square of the 4x2 grid
+        assert_eq!(shapes.len(), 1 + 4);
+
+        // Only the visible part is generated
+        let clip = Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(8.0, 8.0));
+        let egui::Shape::Vec(shapes) = checkerboard_shape(rect, clip) else {
+            panic!("expected checkerboard shapes");
+        };
+        assert_eq!(shapes.len(), 1);
+
+        let outside = Rect::from_min_size(egui::pos2(100.0, 100.0), egui::vec2(8.0, 8.0));
+        assert!(matches!(
+            checkerboard_shape(rect, outside),
+            egui::Shape::Noop
+        ));
+    }
+
+    #[test]
+    fn test_gps_coordinates() {
+        let fields = vec![
+            dms_field(exif::Tag::GPSLatitude, 37, 46, 3000),
+            ascii_field(exif::Tag::GPSLatitudeRef, "N"),
+            dms_field(exif::Tag::GPSLongitude, 122, 25, 900),
+            ascii_field(exif::Tag::GPSLongitudeRef, "W"),
+        ];
+        let (lat, lon) = gps_coordinates(&fields).unwrap();
+        assert!((lat - 37.775).abs() < 1e-9);
+        assert!((lon + 122.419_166_666).abs() < 1e-6);
+
+        assert_eq!(gps_coordinates(&fields[..2]), None);
+    }
+
+    #[test]
+    fn test_group_exif() {
+        let exif_data: HashMap<String, String> = [
+            ("Model", "X100V"),
+            ("Make", "FUJIFILM"),
+            ("FNumber", "f/2"),
+            ("LensModel", "23mm"),
+            ("GPSLatitude", "37 deg"),
+            ("GPSCoordinates", "37.775000, -122.419167"),
+            ("Orientation", "row 0 at top"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let groups = group_exif(&exif_data);
+        let titles: Vec<&str> = groups.iter().map(|(title, _)| *title).collect();
+        assert_eq!(titles, vec!["Camera", "Exposure", "Lens", "GPS", "Other"]);
+        assert_eq!(groups[0].1, vec![("Make", "FUJIFILM"), ("Model", "X100V")]);
+        assert_eq!(groups[3].1[0].0, "GPSCoordinates");
+        assert_eq!(groups[4].1, vec![("Orientation", "row 0 at top")]);
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let mut img = image::RgbaImage::new(2, 2);
+        img.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        img.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+        img.put_pixel(0, 1, image::Rgba([255, 255, 255, 255]));
+        // Transparent pixels are ignored
+        img.put_pixel(1, 1, image::Rgba([0, 255, 0, 0]));
+
+        let stats = compute_stats(&image::DynamicImage::ImageRgba8(img)).unwrap();
+        let red = &stats.channels[0];
+        assert_eq!((red.min, red.max), (0, 255));
+        assert_eq!(red.histogram[255], 2);
+        assert!((red.mean - 170.0).abs() < 0.01);
+        let green = &stats.channels[1];
+        assert_eq!((green.min, green.max, green.histogram[0]), (0, 255, 2));
+        let luminance = &stats.channels[3];
+        assert_eq!(luminance.histogram.iter().sum::<u32>(), 3);
+        assert_eq!(luminance.max, 255);
+
+        let transparent = image::RgbaImage::new(1, 1);
+        assert!(compute_stats(&image::DynamicImage::ImageRgba8(transparent)).is_none());
+    }
 }
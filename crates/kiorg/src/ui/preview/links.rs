@@ -0,0 +1,261 @@
+//! Clickable URLs and file paths in text previews
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use egui::text::{LayoutJob, TextFormat};
+use egui::{CursorIcon, Galley, Pos2, Sense, Stroke};
+
+use crate::config::colors::AppColors;
+
+/// Tokens longer than this are never treated as links
+const MAX_LINK_LEN: usize = 4096;
+
+/// What a clicked link points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    Url(String),
+    /// Existing file or directory
+    Path(PathBuf),
+}
+
+/// A link found in the previewed text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextLink {
+    pub byte_range: Range<usize>,
+    pub char_range: Range<usize>,
+    pub target: LinkTarget,
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '"' | '\'' | '`' | '<' | '>' | '(' | ')' | '[' | ']' | '{' | '}'
+        )
+}
+
+/// Find URLs and existing paths in `text`
+///
+/// Relative paths (`./`, `../`) are resolved against `base_dir`, usually the
+/// directory of the previewed file.
+pub fn find_links(text: &str, base_dir: Option<&Path>) -> Vec<TextLink> {
+    let mut links = Vec::new();
+    let mut chars = text.char_indices().enumerate().peekable();
+
+    while let Some((char_start, (byte_start, c))) = chars.next() {
+        if is_delimiter(c) {
+            continue;
+        }
+        let mut byte_end = byte_start + c.len_utf8();
+        let mut char_end = char_start + 1;
+        while let Some(&(char_idx, (byte_idx, c))) = chars.peek() {
+            if is_delimiter(c) {
+                break;
+            }
+            byte_end = byte_idx + c.len_utf8();
+            char_end = char_idx + 1;
+            chars.next();
+        }
+
+        // Trailing punctuation usually belongs to the sentence, not the link
+        let token = &text[byte_start..byte_end];
+        let trimmed = token.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if trimmed.is_empty() || trimmed.len() > MAX_LINK_LEN {
+            continue;
+        }
+        let trimmed_chars = token[trimmed.len()..].chars().count();
+
+        if let Some(target) = parse_target(trimmed, base_dir) {
+            links.push(TextLink {
+                byte_range: byte_start..byte_start + trimmed.len(),
+                char_range: char_start..char_end - trimmed_chars,
+                target,
+            });
+        }
+    }
+
+    links
+}
+
+fn parse_target(token: &str, base_dir: Option<&Path>) -> Option<LinkTarget> {
+    if let Some(rest) = token
+        .strip_prefix("https://")
+        .or_else(|| token.strip_prefix("http://"))
+    {
+        return (!rest.is_empty()).then(|| LinkTarget::Url(token.to_string()));
+    }
+
+    let path = if let Some(rest) = token.strip_prefix("~/") {
+        dirs::home_dir()?.join(rest)
+    } else if token.starts_with("./") || token.starts_with("../") {
+        base_dir?.join(token)
+    } else if Path::new(token).is_absolute() && token.len() > 1 {
+        PathBuf::from(token)
+    } else {
+        return None;
+    };
+    path.exists().then_some(LinkTarget::Path(path))
+}
+
+#[derive(Default)]
+struct LinkFinder;
+
+impl egui::cache::ComputerMut<(&str, Option<&Path>), Arc<Vec<TextLink>>> for LinkFinder {
+    fn compute(&mut self, (text, base_dir): (&str, Option<&Path>)) -> Arc<Vec<TextLink>> {
+        Arc::new(find_links(text, base_dir))
+    }
+}
+
+type LinkCache = egui::cache::FrameCache<Arc<Vec<TextLink>>, LinkFinder>;
+
+/// Links of `text`, cached across frames so paths aren't checked on every repaint
+pub fn cached_links(
+    ctx: &egui::Context,
+    text: &str,
+    base_dir: Option<&Path>,
+) -> Arc<Vec<TextLink>> {
+    ctx.memory_mut(|mem| mem.caches.cache::<LinkCache>().get((text, base_dir)))
+}
+
+/// Underline the link ranges of a layout job in the link color
+pub fn apply_link_format(job: &mut LayoutJob, links: &[TextLink], colors: &AppColors) {
+    let ranges: Vec<_> = links.iter().map(|l| l.byte_range.clone()).collect();
+    format_ranges(job, &ranges, |_, format| {
+        format.color = colors.link_text;
+        format.underline = Stroke::new(1.0, colors.link_underscore);
+    });
+}
+
+/// Split the sections of a layout job at sorted, non-overlapping byte ranges
+/// and restyle the parts inside them, `format` gets the index of the range
+pub fn format_ranges(
+    job: &mut LayoutJob,
+    ranges: &[Range<usize>],
+    mut format: impl FnMut(usize, &mut TextFormat),
+) {
+    if ranges.is_empty() {
+        return;
+    }
+
+    let mut sections = Vec::with_capacity(job.sections.len() + ranges.len() * 2);
+    for section in job.sections.drain(..) {
+        let mut start = section.byte_range.start;
+        let end = section.byte_range.end;
+        for (index, range) in ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.start < end && r.end > start)
+        {
+            let range_start = range.start.max(start);
+            let range_end = range.end.min(end);
+            if range_start > start {
+                let mut plain = section.clone();
+                plain.byte_range = start..range_start;
+                sections.push(plain);
+            }
+            let mut formatted = section.clone();
+            formatted.byte_range = range_start..range_end;
+            format(index, &mut formatted.format);
+            sections.push(formatted);
+            start = range_end;
+        }
+        if start < end {
+            let mut plain = section;
+            plain.byte_range = start..end;
+            sections.push(plain);
+        }
+    }
+    job.sections = sections;
+}
+
+/// Show a laid out text and return the link clicked in this frame, if any
+pub fn show_job(ui: &mut egui::Ui, mut job: LayoutJob, links: &[TextLink]) -> Option<LinkTarget> {
+    // Wrap like a label would
+    if !job.wrap.max_width.is_finite() && ui.wrap_mode() != egui::TextWrapMode::Extend {
+        job.wrap.max_width = ui.available_width();
+    }
+    let galley = ui.painter().layout_job(job);
+    let (rect, response) = ui.allocate_exact_size(galley.size(), Sense::click());
+    ui.painter()
+        .galley(rect.min, galley.clone(), ui.visuals().text_color());
+    link_at_pointer(ui, response, &galley, rect.min, links)
+}
+
+/// Link under the pointer in a galley painted at `origin`, returned when clicked
+pub fn link_at_pointer(
+    ui: &egui::Ui,
+    response: egui::Response,
+    galley: &Galley,
+    origin: Pos2,
+    links: &[TextLink],
+) -> Option<LinkTarget> {
+    let hovered = response.hover_pos().and_then(|pos| {
+        let index = galley.cursor_from_pos(pos - origin).index;
+        links.iter().find(|l| l.char_range.contains(&index))
+    });
+    let link = hovered?;
+    ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+    let response = response.on_hover_text(match &link.target {
+        LinkTarget::Url(url) => url.clone(),
+        LinkTarget::Path(path) => path.display().to_string(),
+    });
+    response.clicked().then(|| link.target.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_urls() {
+        let text = "see https://example.com/a?b=1, or (http://x.org).";
+        let links = find_links(text, None);
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            links[0].target,
+            LinkTarget::Url("https://example.com/a?b=1".to_string())
+        );
+        assert_eq!(
+            &text[links[0].byte_range.clone()],
+            "https://example.com/a?b=1"
+        );
+        assert_eq!(links[1].target, LinkTarget::Url("http://x.org".to_string()));
+        assert!(find_links("https:// alone", None).is_empty());
+    }
+
+    #[test]
+    fn test_find_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+        let absolute = temp_dir.path().join("notes.txt");
+
+        let text = format!("é {} ./notes.txt ./missing.txt /", absolute.display());
+        let links = find_links(&text, Some(temp_dir.path()));
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, LinkTarget::Path(absolute));
+        assert_eq!(links[0].char_range.start, 2);
+        assert_eq!(
+            links[1].target,
+            LinkTarget::Path(temp_dir.path().join("./notes.txt"))
+        );
+        assert_eq!(&text[links[1].byte_range.clone()], "./notes.txt");
+
+        // Relative paths need a base directory
+        assert!(find_links("./notes.txt", None).is_empty());
+    }
+
+    #[test]
+    fn test_apply_link_format() {
+        let colors = crate::theme::get_default_theme().get_colors().clone();
+        let text = "go to https://a.io now";
+        let links = find_links(text, None);
+        let mut job = LayoutJob::single_section(text.to_string(), egui::TextFormat::default());
+        apply_link_format(&mut job, &links, &colors);
+
+        let ranges: Vec<_> = job.sections.iter().map(|s| s.byte_range.clone()).collect();
+        assert_eq!(ranges, vec![0..6, 6..16, 16..20]);
+        assert_eq!(job.sections[1].format.color, colors.link_text);
+    }
+}
@@ -2,18 +2,29 @@
 
 pub const METADATA_TBL_KEY_COL_W: f32 = 100.0;
 
+pub mod binary;
+pub mod checksum;
+pub mod compressed;
 pub mod directory;
 pub mod ebook;
+pub mod html;
 pub mod image;
+pub mod links;
 pub mod loading;
 pub mod pdf;
 pub mod plugin;
+pub mod svg;
+pub mod syntax_theme;
+pub mod table;
 pub mod tar;
 pub mod text;
 pub mod video;
 pub mod zip;
 
+use egui::RichText;
+
 use crate::app::Kiorg;
+use crate::config::colors::AppColors;
 use crate::models::preview_content::PreviewContent;
 use crate::utils::preview_cache;
 
@@ -44,7 +55,7 @@ pub fn path_to_ext_info(path: &std::path::Path) -> String {
         _ => {
             let last = parts[parts.len() - 1].to_lowercase();
             match last.as_str() {
-                "zstd" | "gz" | "bz2" | "xz" => {
+                "zstd" | "zst" | "gz" | "bz2" | "xz" => {
                     // Handle cases like tar.gz, tar.bz2, etc.
                     let second_last = parts[parts.len() - 2].to_lowercase();
                     if second_last != "tar" {
@@ -59,6 +70,21 @@ pub fn path_to_ext_info(path: &std::path::Path) -> String {
     }
 }
 
+/// Extension the preview of a file is chosen by
+///
+/// Files without an extension, like `README` or downloaded blobs, get the
+/// extension of the type sniffed from their first bytes. Online-only files
+/// aren't sniffed since reading them downloads them.
+pub fn preview_ext_info(path: &std::path::Path, is_placeholder: bool) -> String {
+    if !is_placeholder
+        && path.extension().is_none()
+        && let Some(sniffed) = mimeapps::sniff_file_type(path)
+    {
+        return sniffed.extension.to_string();
+    }
+    path_to_ext_info(path)
+}
+
 // Macros for file extension patterns to avoid duplication
 #[macro_export]
 macro_rules! video_extensions {
@@ -97,7 +123,25 @@ macro_rules! zip_extensions {
 #[macro_export]
 macro_rules! tar_extensions {
     () => {
-        "tar" | "tgz" | "tar.gz" | "tbz" | "tbz2" | "tar.bz2"
+        "tar"
+            | "tgz"
+            | "tar.gz"
+            | "tbz"
+            | "tbz2"
+            | "tar.bz2"
+            | "txz"
+            | "tar.xz"
+            | "tzst"
+            | "tar.zst"
+            | "tar.zstd"
+    };
+}
+
+/// Single compressed files, compressed tarballs are matched by `tar_extensions`
+#[macro_export]
+macro_rules! compressed_extensions {
+    () => {
+        "gz" | "bz2" | "xz" | "zst" | "zstd"
     };
 }
 
@@ -115,8 +159,25 @@ macro_rules! epub_extensions {
     };
 }
 
+#[macro_export]
+macro_rules! html_extensions {
+    () => {
+        "html" | "htm" | "xhtml" | "mht" | "mhtml"
+    };
+}
+
+/// Executables and libraries, those without an extension are detected by their magic bytes
+#[macro_export]
+macro_rules! binary_extensions {
+    () => {
+        "exe" | "dll" | "sys" | "efi" | "so" | "dylib" | "bundle" | "o" | "obj" | "elf"
+    };
+}
+
 // Public macros for use in other modules
+pub use compressed_extensions;
 pub use epub_extensions;
+pub use html_extensions;
 pub use image_extensions;
 pub use pdf_extensions;
 pub use tar_extensions;
@@ -133,6 +194,73 @@ pub fn prefix_dir_name(name: &str) -> String {
     format!("📁 {name}")
 }
 
+fn force_load_id() -> egui::Id {
+    egui::Id::new("preview_force_load")
+}
+
+/// Load a deferred preview, ignoring the preview limits for this file
+pub fn load_deferred(app: &mut Kiorg, ctx: &egui::Context, path: std::path::PathBuf) {
+    ctx.data_mut(|d| d.insert_temp(force_load_id(), path));
+    app.cached_preview_path = None;
+    app.selection_changed = true;
+}
+
+/// Render the placeholder of a deferred preview
+///
+/// Returns true when loading the preview was requested.
+pub fn render_deferred(ui: &mut egui::Ui, reason: &str, colors: &AppColors) -> bool {
+    ui.vertical_centered(|ui| {
+        ui.add_space(20.0);
+        ui.label(RichText::new(reason).color(colors.fg_light));
+        ui.add_space(10.0);
+        ui.button(RichText::new("Load preview").color(colors.highlight))
+            .clicked()
+    })
+    .inner
+}
+
+/// Whether `path` is previewed in safe mode, showing only static metadata
+///
+/// A preview loaded with the "Load preview" button is not.
+pub fn is_safe_preview(app: &Kiorg, ctx: &egui::Context, path: &std::path::Path) -> bool {
+    let forced = ctx
+        .data(|d| d.get_temp::<std::path::PathBuf>(force_load_id()))
+        .is_some_and(|forced| forced == path);
+    !forced
+        && (app.safe_preview
+            || app
+                .config
+                .preview
+                .as_ref()
+                .is_some_and(|preview| preview.is_untrusted(path)))
+}
+
+/// Static metadata shown instead of the preview in safe mode
+fn safe_preview_info(
+    entry: &crate::models::dir_entry::DirEntryMeta,
+    size: u64,
+    is_placeholder: bool,
+    untrusted: bool,
+) -> String {
+    // Reading an online-only file makes the cloud provider download it
+    let file_type = if is_placeholder {
+        "Online-only file".to_string()
+    } else {
+        text::file_type_info(&entry.path)
+    };
+    let scope = if untrusted {
+        "Safe preview: this directory is untrusted"
+    } else {
+        "Safe preview is enabled"
+    };
+    format!(
+        "{}\n\n{file_type}\nSize: {}\nModified: {}\n\n{scope}",
+        entry.path.file_name().unwrap_or_default().to_string_lossy(),
+        crate::utils::format::format_size(size, false),
+        crate::utils::format::format_modified(entry.modified),
+    )
+}
+
 /// Update the preview cache based on the selected file
 pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
     let tab = app.tab_manager.current_tab_ref();
@@ -154,6 +282,8 @@ pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
             .cloned() // Clone the entry data if found
     });
     app.cached_preview_path = selected_path; // Update the cached path in app regardless
+    app.dir_size_scan = None; // Cancel the size scan of the previously selected directory
+    app.checksum_job = None; // Cancel the checksums of the previously selected file
 
     let entry = match maybe_entry {
         Some(entry) => entry,
@@ -169,10 +299,42 @@ pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
         loading::load_preview_async(app, entry.meta.clone(), |entry| {
             directory::read_dir_entries(&entry.path).map(PreviewContent::Directory)
         });
+        app.dir_size_scan = Some(directory::DirSizeScan::start(entry.meta.path.clone(), ctx));
+        return;
+    }
+
+    let forced = ctx
+        .data(|d| d.get_temp::<std::path::PathBuf>(force_load_id()))
+        .is_some_and(|path| path == entry.meta.path);
+    let limits = app.config.preview.clone().unwrap_or_default();
+    let ext = preview_ext_info(&entry.meta.path, entry.is_placeholder);
+    if !forced && limits.is_disabled(&ext) {
+        app.preview_content = Some(PreviewContent::Deferred {
+            path: entry.meta.path.clone(),
+            reason: format!("Automatic previews are disabled for {ext} files"),
+        });
+        return;
+    }
+
+    // Files in untrusted locations are never handed to decoders or plugins
+    if is_safe_preview(app, ctx, &entry.meta.path) {
+        let untrusted = limits.is_untrusted(&entry.meta.path);
+        let size = entry.size;
+        let is_placeholder = entry.is_placeholder;
+        loading::load_preview_async(app, entry.meta.clone(), move |entry| {
+            let reason = safe_preview_info(&entry, size, is_placeholder, untrusted);
+            Ok(PreviewContent::Deferred {
+                path: entry.path,
+                reason,
+            })
+        });
         return;
     }
 
-    let cache_key = preview_cache::calculate_cache_key(&entry.meta);
+    let cache_key = match ext.as_str() {
+        image_extensions!() => image::cache_key(&entry.meta, limits.exif_orientation()),
+        _ => preview_cache::calculate_cache_key(&entry.meta),
+    };
     if let Some(cached) = preview_cache::load_preview(&cache_key) {
         match cached.try_into_preview_content(ctx) {
             Ok(content) => {
@@ -190,6 +352,15 @@ pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
         }
     }
 
+    // Reading an online-only file makes the cloud provider download it
+    if !forced && entry.is_placeholder && !limits.download_online_only.unwrap_or(false) {
+        app.preview_content = Some(PreviewContent::Deferred {
+            path: entry.meta.path.clone(),
+            reason: "This file is online-only, previewing it downloads it".to_string(),
+        });
+        return;
+    }
+
     // First check if any plugins can handle this file
     let plugin_result =
         if let Some(file_name) = entry.meta.path.file_name().and_then(|n| n.to_str()) {
@@ -203,24 +374,43 @@ pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
         loading::load_preview_async(app, entry.meta.clone(), move |entry| {
             let result = plugin.preview(&entry.path.to_string_lossy(), available_width);
             match result {
-                Ok(plugin_content) => Ok(PreviewContent::plugin_preview_from_components(
-                    plugin_content,
-                    &ctx_clone,
-                )),
+                Ok(plugin_content) => {
+                    let (content, cache) =
+                        PreviewContent::plugin_preview_with_cache(plugin_content, &ctx_clone);
+                    // Encode the images and save the cache in the background
+                    std::thread::spawn(move || {
+                        cache.save(&preview_cache::calculate_cache_key(&entry));
+                    });
+                    Ok(content)
+                }
                 Err(e) => Ok(PreviewContent::text(format!("Plugin error: {}", e))),
             }
         });
         return;
     }
 
-    let ext = path_to_ext_info(&entry.meta.path);
     match ext.as_str() {
         image_extensions!() => {
             let ctx_clone = ctx.clone();
             let available_width = app.calculate_right_panel_width(ctx);
+            let max_pixels = limits.max_image_pixels.filter(|_| !forced);
+            let apply_orientation = limits.exif_orientation();
             loading::load_preview_async(app, entry.meta.clone(), move |entry| {
-                image::read_image_with_metadata(entry, &ctx_clone, Some(available_width))
-                    .map(PreviewContent::Image)
+                if let Some(reason) =
+                    max_pixels.and_then(|max| image::exceeds_pixel_limit(&entry.path, max))
+                {
+                    return Ok(PreviewContent::Deferred {
+                        path: entry.path,
+                        reason,
+                    });
+                }
+                image::read_image_with_metadata(
+                    entry,
+                    &ctx_clone,
+                    Some(available_width),
+                    apply_orientation,
+                )
+                .map(PreviewContent::Image)
             });
         }
         video_extensions!() => {
@@ -241,11 +431,32 @@ pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
                 tar::read_tar_entries(entry).map(PreviewContent::Tar)
             });
         }
+        compressed_extensions!() => {
+            loading::load_preview_async(app, entry.meta.clone(), move |entry| {
+                compressed::read_compressed_preview(entry)
+            });
+        }
         epub_extensions!() => {
             loading::load_preview_async(app, entry.meta.clone(), move |entry| {
                 ebook::extract_ebook_metadata(entry).map(PreviewContent::Ebook)
             });
         }
+        // Archives embed their images, so they are not held to the text limit
+        html_extensions!()
+            if forced || ext.starts_with("mht") || entry.size <= limits.max_text_bytes() =>
+        {
+            loading::load_preview_async(app, entry.meta.clone(), html::load);
+        }
+        #[cfg(feature = "binary-preview")]
+        binary_extensions!() => {
+            let size = entry.size;
+            loading::load_preview_async(app, entry.meta.clone(), move |entry| {
+                match binary::read_binary_metadata(&entry.path) {
+                    Some(meta) => meta.map(PreviewContent::Binary),
+                    None => text::render_generic_file(entry.path, size),
+                }
+            });
+        }
         pdf_extensions!() => {
             let ctx_clone = ctx.clone();
             loading::load_preview_async(app, entry.meta.clone(), move |entry| {
@@ -260,7 +471,8 @@ pub fn update_selected_cache(app: &mut Kiorg, ctx: &egui::Context) {
                 app.preview_content = Some(PreviewContent::text("Empty file".to_string()));
                 return;
             }
-            text::load_async(app, entry.meta.clone(), size);
+            let max_bytes = (!forced).then(|| limits.max_text_bytes());
+            text::load_async(app, entry.meta.clone(), size, max_bytes);
         }
     }
 }
@@ -288,6 +500,21 @@ mod tests {
         assert_eq!(prefixed_name.chars().nth(1), Some('\u{00A0}'));
     }
 
+    #[test]
+    fn test_preview_ext_info_sniffs_files_without_extension() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let blob = temp_dir.path().join("download");
+        std::fs::write(&blob, b"%PDF-1.4\n").unwrap();
+        assert_eq!(preview_ext_info(&blob, false), "pdf");
+        // Reading online-only files would download them
+        assert_eq!(preview_ext_info(&blob, true), "download");
+
+        // The extension wins over the content
+        let named = temp_dir.path().join("notes.txt");
+        std::fs::write(&named, b"%PDF-1.4\n").unwrap();
+        assert_eq!(preview_ext_info(&named, false), "txt");
+    }
+
     #[test]
     fn test_path_to_ext_info_no_extension() {
         assert_eq!(path_to_ext_info(Path::new("filename")), "filename");
@@ -310,6 +537,7 @@ mod tests {
         assert_eq!(path_to_ext_info(Path::new("data.tar.bz2")), "tar.bz2");
         assert_eq!(path_to_ext_info(Path::new("backup.tar.xz")), "tar.xz");
         assert_eq!(path_to_ext_info(Path::new("file.tar.zstd")), "tar.zstd");
+        assert_eq!(path_to_ext_info(Path::new("file.tar.zst")), "tar.zst");
     }
 
     #[test]
@@ -3,9 +3,75 @@
 use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntryMeta;
 use crate::models::preview_content::{CachedPdfMeta, CachedPreviewContent, PdfMeta, metadata};
+use crate::utils::format::format_count;
 use crate::utils::preview_cache;
 use egui::{ColorImage, RichText, TextureOptions, widgets::ImageSource};
-use pdfium_bind::PdfDocument;
+use pdfium_bind::{PdfDocument, PdfFormType};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Error shown in place of an encrypted PDF's preview until its password is entered
+pub const PASSWORD_REQUIRED: &str = "This PDF is password protected";
+
+/// PDF passwords entered during this session, keyed by file path
+static SESSION_PASSWORDS: OnceLock<Mutex<HashMap<PathBuf, String>>> = OnceLock::new();
+
+fn session_passwords() -> &'static Mutex<HashMap<PathBuf, String>> {
+    SESSION_PASSWORDS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Password previously entered for the PDF, if any
+#[must_use]
+pub fn cached_password(path: &Path) -> Option<String> {
+    session_passwords()
+        .lock()
+        .ok()
+        .and_then(|passwords| passwords.get(path).cloned())
+}
+
+/// Remember the password of a PDF until the app exits
+pub fn cache_password(path: &Path, password: String) {
+    if let Ok(mut passwords) = session_passwords().lock() {
+        passwords.insert(path.to_path_buf(), password);
+    }
+}
+
+fn forget_password(path: &Path) {
+    if let Ok(mut passwords) = session_passwords().lock() {
+        passwords.remove(path);
+    }
+}
+
+fn password_request_id() -> egui::Id {
+    egui::Id::new("pdf_password_request")
+}
+
+/// Take the encrypted PDF that failed to open for lack of a password, if any
+pub fn take_password_request(ctx: &egui::Context) -> Option<PathBuf> {
+    ctx.data_mut(|d| d.remove_temp::<PathBuf>(password_request_id()))
+}
+
+/// Open a PDF with the password cached for it
+///
+/// When the password is missing or wrong, asks the UI thread to prompt for it.
+fn open_document(path: &Path, ctx: &egui::Context) -> Result<PdfDocument, String> {
+    let password = cached_password(path);
+    match PdfDocument::open_with_password(path, password.as_deref()) {
+        Ok(doc) => Ok(doc),
+        Err(pdfium_bind::OpenError::Password) => {
+            forget_password(path);
+            ctx.data_mut(|d| d.insert_temp(password_request_id(), path.to_path_buf()));
+            ctx.request_repaint();
+            Err(if password.is_some() {
+                "Incorrect password".to_string()
+            } else {
+                PASSWORD_REQUIRED.to_string()
+            })
+        }
+        Err(e) => Err(e.into()),
+    }
+}
 
 fn format_pdf_date(pdf_date: &str) -> String {
     // PDF date format: D:YYYYMMDDHHmmSSOHH'mm'
@@ -131,6 +197,42 @@ fn format_metadata_key(key: &str) -> String {
     capitalized.join(" ")
 }
 
+/// Form fields listed in the metadata table before the rest is summarized
+const MAX_LISTED_FORM_FIELDS: usize = 20;
+
+/// Describe the form of a document, telling fillable forms apart from flat
+/// documents like scans
+fn insert_form_summary(doc: &PdfDocument, metadata: &mut HashMap<String, String>) {
+    let form_type = doc.form_type();
+    let kind = match form_type {
+        PdfFormType::None => return,
+        PdfFormType::AcroForm => "AcroForm",
+        PdfFormType::XfaFull => "XFA",
+        PdfFormType::XfaForeground => "XFA foreground",
+    };
+    let fields = doc.form_fields();
+    metadata.insert(
+        metadata::PDF_FORM.to_string(),
+        format!("{kind}, {} field(s)", format_count(fields.len() as u64)),
+    );
+    if fields.is_empty() {
+        return;
+    }
+
+    let mut listed: Vec<String> = fields
+        .iter()
+        .take(MAX_LISTED_FORM_FIELDS)
+        .map(|field| format!("{} ({})", field.name, field.field_type.name()))
+        .collect();
+    if fields.len() > MAX_LISTED_FORM_FIELDS {
+        listed.push(format!(
+            "and {} more",
+            format_count((fields.len() - MAX_LISTED_FORM_FIELDS) as u64)
+        ));
+    }
+    metadata.insert(metadata::PDF_FORM_FIELDS.to_string(), listed.join("\n"));
+}
+
 pub struct RenderedPdfPage {
     pub img_source: egui::widgets::ImageSource<'static>,
     pub texture_handle: egui::TextureHandle,
@@ -150,6 +252,9 @@ pub fn render_pdf_page_low_dpi(
     render_pdf_page_with_dpi(doc, page_number, file_id, 150.0, ctx) // Use 150 DPI for regular preview
 }
 
+/// Resolution of the pages rendered for the popup view
+pub const HIGH_DPI: f32 = 300.0;
+
 /// Render a specific PDF page as an egui `ImageSource` with high DPI for popup view
 #[inline]
 pub fn render_pdf_page_high_dpi(
@@ -158,7 +263,7 @@ pub fn render_pdf_page_high_dpi(
     file_id: Option<&str>,
     ctx: &egui::Context,
 ) -> Result<RenderedPdfPage, String> {
-    render_pdf_page_with_dpi(doc, page_number, file_id, 300.0, ctx) // Use 300 DPI for popup
+    render_pdf_page_with_dpi(doc, page_number, file_id, HIGH_DPI, ctx)
 }
 
 /// Render a specific PDF page as an egui `ImageSource` with configurable DPI
@@ -202,12 +307,13 @@ pub fn extract_pdf_metadata(
     ctx: &egui::Context,
 ) -> Result<(PdfMeta, PdfDocument), String> {
     let path = &entry.path;
-    let doc = PdfDocument::open(path)?;
+    let doc = open_document(path, ctx)?;
+    let encrypted = cached_password(path).is_some();
     let file_id = path.to_string_lossy();
     let rendered = render_pdf_page_low_dpi(&doc, 0, Some(&file_id), ctx)?;
 
     // Extract metadata
-    let mut metadata = std::collections::HashMap::new();
+    let mut metadata = HashMap::new();
     for &field in &[
         metadata::PDF_TITLE,
         metadata::PDF_AUTHOR,
@@ -230,6 +336,7 @@ pub fn extract_pdf_metadata(
 
     let version = doc.get_pdf_version();
     metadata.insert(metadata::PDF_VERSION.to_string(), format!("{}", version));
+    insert_form_summary(&doc, &mut metadata);
 
     let title = metadata.get(metadata::PDF_TITLE).cloned();
     let page_count = doc.page_count();
@@ -262,6 +369,11 @@ pub fn extract_pdf_metadata(
     let current_page_clone = meta.current_page;
     let page_count_clone = meta.page_count;
 
+    // Keep the cover of encrypted documents out of the on-disk cache
+    if encrypted {
+        return Ok((meta, doc));
+    }
+
     // Spawn background task to save cache
     std::thread::spawn(move || {
         let cached = CachedPreviewContent::Pdf(CachedPdfMeta {
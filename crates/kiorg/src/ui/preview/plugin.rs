@@ -1,7 +1,10 @@
+use crate::config::ZoomMode;
 use crate::config::colors::AppColors;
 use crate::models::preview_content::RenderedComponent;
 use crate::ui::preview;
+use ahash::RandomState;
 use egui::{RichText, Ui};
+use std::collections::VecDeque;
 
 pub fn render(
     ui: &mut Ui,
@@ -9,6 +12,8 @@ pub fn render(
     colors: &AppColors,
     available_width: f32,
     available_height: f32,
+    filter: Option<&str>,
+    zoom_mode: ZoomMode,
 ) {
     for (i, component) in components.iter().enumerate() {
         ui.push_id(i, |ui| {
@@ -32,6 +37,8 @@ pub fn render(
                             source_id,
                             available_width,
                             available_height,
+                            false,
+                            zoom_mode,
                         );
                     } else {
                         ui.vertical_centered(|ui| {
@@ -44,49 +51,125 @@ pub fn render(
                     }
                 }
                 RenderedComponent::Table(table) => {
-                    use egui_extras::{Column, TableBuilder};
-                    let num_columns: usize = if let Some(headers) = &table.headers {
-                        headers.len()
-                    } else if let Some(first_row) = table.rows.first() {
-                        first_row.len()
-                    } else {
-                        return;
-                    };
-
-                    let mut builder = TableBuilder::new(ui).striped(true).vscroll(false);
-                    for _ in 0..(num_columns - 1) {
-                        builder = builder
-                            .column(Column::auto_with_initial_suggestion(150.0).resizable(true));
-                    }
-                    builder = builder.column(Column::remainder());
-
-                    let body_cb = |mut body: egui_extras::TableBody| {
-                        for row in &table.rows {
-                            body.row(18.0, |mut row_ui| {
-                                for cell in row {
-                                    row_ui.col(|ui| {
-                                        ui.label(RichText::new(cell).color(colors.fg));
-                                    });
-                                }
-                            });
-                        }
-                    };
-
-                    if let Some(headers) = &table.headers {
-                        builder
-                            .header(20.0, |mut header| {
-                                for h in headers {
-                                    header.col(|ui| {
-                                        ui.strong(RichText::new(h).color(colors.fg));
-                                    });
-                                }
-                            })
-                            .body(body_cb);
-                    } else {
-                        builder.body(body_cb);
-                    }
+                    preview::table::render(ui, table, colors, filter);
                 }
             }
         });
     }
 }
+
+/// Most plugin image textures kept for reuse
+const MAX_CACHED_TEXTURES: usize = 32;
+
+/// Textures of plugin images keyed by their uid and a fingerprint of their data
+///
+/// Plugins send the whole image again every time a file is previewed, the
+/// cache avoids decoding and uploading it again when nothing changed.
+#[derive(Clone, Default)]
+struct TextureCache {
+    /// Least recently used first
+    entries: VecDeque<(String, u64, egui::TextureHandle)>,
+}
+
+fn texture_cache_id() -> egui::Id {
+    egui::Id::new("plugin_texture_cache")
+}
+
+/// Fingerprint of image data, identical payloads get identical fingerprints
+#[must_use]
+pub fn fingerprint(data: &[u8]) -> u64 {
+    RandomState::with_seeds(0, 0, 0, 0).hash_one(data)
+}
+
+/// Texture loaded earlier for an image with this uid and fingerprint
+#[must_use]
+pub fn cached_texture(
+    ctx: &egui::Context,
+    uid: &str,
+    fingerprint: u64,
+) -> Option<egui::TextureHandle> {
+    ctx.data_mut(|data| {
+        let cache = data.get_temp_mut_or_default::<TextureCache>(texture_cache_id());
+        let index = cache
+            .entries
+            .iter()
+            .position(|(id, fp, _)| id == uid && *fp == fingerprint)?;
+        let entry = cache.entries.remove(index)?;
+        let texture = entry.2.clone();
+        cache.entries.push_back(entry);
+        Some(texture)
+    })
+}
+
+/// Remember the texture of an image, replacing older data sent with the same uid
+pub fn cache_texture(
+    ctx: &egui::Context,
+    uid: &str,
+    fingerprint: u64,
+    texture: egui::TextureHandle,
+) {
+    ctx.data_mut(|data| {
+        let cache = data.get_temp_mut_or_default::<TextureCache>(texture_cache_id());
+        cache.entries.retain(|(id, _, _)| id != uid);
+        if cache.entries.len() >= MAX_CACHED_TEXTURES {
+            cache.entries.pop_front();
+        }
+        cache
+            .entries
+            .push_back((uid.to_string(), fingerprint, texture));
+    });
+}
+
+/// Drop all cached textures, e.g. when files changed on disk
+pub fn invalidate_textures(ctx: &egui::Context) {
+    ctx.data_mut(|data| data.remove::<TextureCache>(texture_cache_id()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture(ctx: &egui::Context, name: &str) -> egui::TextureHandle {
+        ctx.load_texture(
+            name,
+            egui::ColorImage::filled([1, 1], egui::Color32::WHITE),
+            egui::TextureOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_texture_cache() {
+        let ctx = egui::Context::default();
+        let fp = fingerprint(b"image");
+        assert_eq!(fp, fingerprint(b"image"));
+        assert_ne!(fp, fingerprint(b"other image"));
+
+        let first = texture(&ctx, "first");
+        cache_texture(&ctx, "cover", fp, first.clone());
+        assert_eq!(
+            cached_texture(&ctx, "cover", fp).map(|t| t.id()),
+            Some(first.id())
+        );
+        // Same uid with different data is a miss
+        assert!(cached_texture(&ctx, "cover", fingerprint(b"other image")).is_none());
+
+        // New data replaces the old texture of the uid
+        let second = texture(&ctx, "second");
+        cache_texture(&ctx, "cover", fp + 1, second.clone());
+        assert!(cached_texture(&ctx, "cover", fp).is_none());
+        assert_eq!(
+            cached_texture(&ctx, "cover", fp + 1).map(|t| t.id()),
+            Some(second.id())
+        );
+
+        // Oldest entries are evicted
+        for i in 0..MAX_CACHED_TEXTURES {
+            cache_texture(&ctx, &format!("page_{i}"), fp, texture(&ctx, "page"));
+        }
+        assert!(cached_texture(&ctx, "cover", fp + 1).is_none());
+        assert!(cached_texture(&ctx, "page_0", fp).is_some());
+
+        invalidate_textures(&ctx);
+        assert!(cached_texture(&ctx, "page_0", fp).is_none());
+    }
+}
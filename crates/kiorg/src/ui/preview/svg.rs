@@ -0,0 +1,99 @@
+//! SVG preview module
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::models::dir_entry::DirEntryMeta;
+use crate::models::preview_content::{ImageMeta, metadata};
+
+/// Longest side of the rasterized texture in pixels
+const MAX_RASTER_SIZE: f32 = 4096.0;
+/// Extra resolution so the image stays sharp when zooming in the popup
+const ZOOM_HEADROOM: f32 = 2.0;
+
+/// Scale from SVG user units to texture pixels
+///
+/// The SVG is rasterized at the size it's shown at on the current display,
+/// small icons are scaled up to fill the available width.
+fn raster_scale(size: egui::Vec2, available_width: Option<f32>, pixels_per_point: f32) -> f32 {
+    let fit = available_width.map_or(1.0, |width| width / size.x).max(1.0);
+    (fit * pixels_per_point * ZOOM_HEADROOM).min(MAX_RASTER_SIZE / size.max_elem())
+}
+
+/// Rasterize an SVG file and create its `ImageMeta`
+pub fn read_svg_with_metadata(
+    entry: DirEntryMeta,
+    ctx: &egui::Context,
+    available_width: Option<f32>,
+) -> Result<ImageMeta, String> {
+    let path = &entry.path;
+    let title = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    let data = std::fs::read(path).map_err(|e| format!("failed to read SVG: {e}"))?;
+    let options = resvg::usvg::Options {
+        resources_dir: path.parent().map(Path::to_path_buf),
+        ..Default::default()
+    };
+    let tree = resvg::usvg::Tree::from_data(&data, &options)
+        .map_err(|e| format!("failed to parse SVG: {e}"))?;
+
+    let size = egui::vec2(tree.size().width(), tree.size().height());
+    let scale = raster_scale(size, available_width, ctx.pixels_per_point());
+    let width = (size.x * scale).ceil().max(1.0) as u32;
+    let height = (size.y * scale).ceil().max(1.0) as u32;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| format!("invalid SVG size: {width}x{height}"))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    let color_image =
+        egui::ColorImage::from_rgba_premultiplied([width as usize, height as usize], pixmap.data());
+    let texture = ctx.load_texture(
+        format!("svg_{}", path.display()),
+        color_image,
+        egui::TextureOptions::LINEAR,
+    );
+
+    let mut metadata = HashMap::new();
+    metadata.insert(metadata::IMG_FORMAT.to_string(), "SVG".to_string());
+    metadata.insert(
+        metadata::IMG_DIMENSIONS.to_string(),
+        format!("{}x{} (vector)", size.x.round(), size.y.round()),
+    );
+    metadata.insert(
+        metadata::IMG_FILE_SIZE.to_string(),
+        crate::utils::format::format_size(data.len() as u64, false),
+    );
+
+    // Show the texture at the SVG's own size, its extra pixels are for high DPI and zoom
+    let texture_id = texture.id();
+    let mut meta = ImageMeta::new(title, metadata, texture, None);
+    meta.image = egui::Image::new(egui::load::SizedTexture::new(texture_id, size));
+    meta.checkerboard = true;
+    Ok(meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raster_scale() {
+        // Small icons are scaled up to the available width
+        assert_eq!(raster_scale(egui::vec2(16.0, 16.0), Some(320.0), 1.0), 40.0);
+        // Large drawings are rendered at their own size on high DPI displays
+        assert_eq!(
+            raster_scale(egui::vec2(800.0, 600.0), Some(400.0), 2.0),
+            4.0
+        );
+        // Texture size is capped
+        assert_eq!(raster_scale(egui::vec2(4096.0, 100.0), None, 2.0), 1.0);
+    }
+}
@@ -0,0 +1,194 @@
+//! Syntax highlighting theme generated from the app colors
+//!
+//! Code previews are highlighted with the colors of the current app theme so
+//! custom themes don't clash with a fixed highlighting theme.
+
+use std::str::FromStr;
+
+use egui::Color32;
+use syntect::highlighting::{
+    Color, FontStyle, ScopeSelectors, StyleModifier, Theme, ThemeItem, ThemeSettings,
+};
+
+use crate::config::colors::AppColors;
+
+fn color(color: Color32) -> Color {
+    Color {
+        r: color.r(),
+        g: color.g(),
+        b: color.b(),
+        a: 0xFF,
+    }
+}
+
+/// App colors used by the generated theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyntaxColors {
+    pub fg: Color32,
+    pub fg_light: Color32,
+    pub fg_folder: Color32,
+    pub bg_extreme: Color32,
+    pub bg_selected: Color32,
+    pub bg_light: Color32,
+    pub success: Color32,
+    pub warn: Color32,
+    pub error: Color32,
+    pub highlight: Color32,
+    pub link_text: Color32,
+    pub link_underscore: Color32,
+}
+
+impl From<&AppColors> for SyntaxColors {
+    fn from(colors: &AppColors) -> Self {
+        Self {
+            fg: colors.fg,
+            fg_light: colors.fg_light,
+            fg_folder: colors.fg_folder,
+            bg_extreme: colors.bg_extreme,
+            bg_selected: colors.bg_selected,
+            bg_light: colors.bg_light,
+            success: colors.success,
+            warn: colors.warn,
+            error: colors.error,
+            highlight: colors.highlight,
+            link_text: colors.link_text,
+            link_underscore: colors.link_underscore,
+        }
+    }
+}
+
+/// Scope selectors and how they are shown, later items win on equal specificity
+fn rules(colors: &SyntaxColors) -> Vec<(&'static str, Color32, FontStyle)> {
+    let plain = FontStyle::empty();
+    vec![
+        (
+            "comment, punctuation.definition.comment",
+            colors.fg_light,
+            FontStyle::ITALIC,
+        ),
+        (
+            "string, punctuation.definition.string",
+            colors.success,
+            plain,
+        ),
+        (
+            "constant.character.escape, string.regexp",
+            colors.link_underscore,
+            plain,
+        ),
+        (
+            "constant.numeric, constant.language, constant.character, constant.other",
+            colors.warn,
+            plain,
+        ),
+        (
+            "keyword, storage, keyword.operator.word",
+            colors.highlight,
+            plain,
+        ),
+        ("keyword.operator, punctuation", colors.fg, plain),
+        (
+            "storage.type, entity.name.type, entity.name.class, support.type, support.class",
+            colors.fg_folder,
+            plain,
+        ),
+        (
+            "entity.name.function, support.function, meta.function-call entity.name",
+            colors.link_text,
+            plain,
+        ),
+        (
+            "variable.language, variable.parameter",
+            colors.link_underscore,
+            plain,
+        ),
+        ("entity.name.tag", colors.error, plain),
+        ("entity.other.attribute-name", colors.warn, plain),
+        (
+            "markup.heading, entity.name.section",
+            colors.fg_folder,
+            FontStyle::BOLD,
+        ),
+        ("markup.bold", colors.fg, FontStyle::BOLD),
+        ("markup.italic", colors.fg, FontStyle::ITALIC),
+        (
+            "markup.underline.link, markup.underline",
+            colors.link_underscore,
+            FontStyle::UNDERLINE,
+        ),
+        ("markup.inserted", colors.success, plain),
+        ("markup.deleted", colors.error, plain),
+        ("markup.changed", colors.warn, plain),
+        ("invalid", colors.error, FontStyle::UNDERLINE),
+    ]
+}
+
+/// Highlighting theme that uses `colors`
+#[must_use]
+pub fn generate(colors: &SyntaxColors) -> Theme {
+    let scopes = rules(colors)
+        .into_iter()
+        .filter_map(|(selector, foreground, font_style)| {
+            Some(ThemeItem {
+                scope: ScopeSelectors::from_str(selector).ok()?,
+                style: StyleModifier {
+                    foreground: Some(color(foreground)),
+                    background: None,
+                    font_style: Some(font_style),
+                },
+            })
+        })
+        .collect();
+
+    Theme {
+        name: Some("kiorg".to_string()),
+        author: None,
+        settings: ThemeSettings {
+            foreground: Some(color(colors.fg)),
+            background: Some(color(colors.bg_extreme)),
+            caret: Some(color(colors.fg)),
+            selection: Some(color(colors.bg_selected)),
+            line_highlight: Some(color(colors.bg_light)),
+            ..Default::default()
+        },
+        scopes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syntect::highlighting::Highlighter;
+    use syntect::parsing::Scope;
+
+    fn foreground(theme: &Theme, scopes: &[&str]) -> Color {
+        let stack: Vec<Scope> = scopes.iter().map(|s| Scope::new(s).unwrap()).collect();
+        Highlighter::new(theme).style_for_stack(&stack).foreground
+    }
+
+    #[test]
+    fn test_generated_theme_uses_app_colors() {
+        let colors = SyntaxColors::from(&crate::theme::get_default_theme().colors);
+        let theme = generate(&colors);
+        assert_eq!(rules(&colors).len(), theme.scopes.len());
+
+        let source = "source.rust";
+        assert_eq!(
+            foreground(&theme, &[source, "string.quoted.double.rust"]),
+            color(colors.success)
+        );
+        assert_eq!(
+            foreground(&theme, &[source, "comment.line.double-slash.rust"]),
+            color(colors.fg_light)
+        );
+        assert_eq!(
+            foreground(&theme, &[source, "keyword.control.rust"]),
+            color(colors.highlight)
+        );
+        assert_eq!(
+            foreground(&theme, &[source, "keyword.operator.arithmetic.rust"]),
+            color(colors.fg)
+        );
+        assert_eq!(foreground(&theme, &[source]), color(colors.fg));
+    }
+}
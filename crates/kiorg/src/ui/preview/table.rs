@@ -0,0 +1,243 @@
+//! Table rendering shared by plugin previews and metadata tables
+
+use std::cmp::Ordering;
+
+use egui::{Label, Layout, Response, RichText, Sense, Ui};
+use egui_extras::{Column, TableBuilder};
+use kiorg_plugin::{ColumnAlign, TableComponent};
+
+use crate::config::colors::AppColors;
+
+/// Column the rows are sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SortOrder {
+    column: usize,
+    ascending: bool,
+}
+
+/// Clicking a header sorts ascending, then descending, then restores the original order
+fn next_order(order: Option<SortOrder>, column: usize) -> Option<SortOrder> {
+    match order {
+        Some(order) if order.column == column && order.ascending => Some(SortOrder {
+            column,
+            ascending: false,
+        }),
+        Some(order) if order.column == column => None,
+        _ => Some(SortOrder {
+            column,
+            ascending: true,
+        }),
+    }
+}
+
+/// Compare cells as numbers when both are numbers, otherwise as case insensitive text
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// Indices of the rows in display order
+///
+/// `filter` is a lowercase text that shown rows must contain in one of their cells.
+fn sorted_rows(rows: &[Vec<String>], order: Option<SortOrder>, filter: Option<&str>) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..rows.len())
+        .filter(|&row| {
+            filter.is_none_or(|filter| {
+                rows[row]
+                    .iter()
+                    .any(|cell| cell.to_lowercase().contains(filter))
+            })
+        })
+        .collect();
+    if let Some(order) = order {
+        let cell = |row: usize| rows[row].get(order.column).map_or("", String::as_str);
+        indices.sort_by(|&a, &b| {
+            let ordering = compare_cells(cell(a), cell(b));
+            if order.ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+    indices
+}
+
+/// Tab separated text of a table, e.g. for pasting into a spreadsheet
+fn to_tsv(table: &TableComponent) -> String {
+    table
+        .headers
+        .iter()
+        .chain(&table.rows)
+        .map(|row| row.join("\t"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Context menu of a table cell to copy the cell, its row or the whole table
+pub fn copy_menu(
+    response: &Response,
+    cell: &str,
+    row: &[&str],
+    table: Option<&dyn Fn() -> String>,
+) {
+    response.context_menu(|ui| {
+        if ui.button("Copy").clicked() {
+            ui.ctx().copy_text(cell.to_string());
+            ui.close();
+        }
+        if ui.button("Copy row").clicked() {
+            ui.ctx().copy_text(row.join("\t"));
+            ui.close();
+        }
+        if let Some(table) = table
+            && ui.button("Copy table").clicked()
+        {
+            ui.ctx().copy_text(table());
+            ui.close();
+        }
+    });
+}
+
+fn cell_layout(align: ColumnAlign) -> Layout {
+    match align {
+        ColumnAlign::Left => Layout::left_to_right(egui::Align::Center),
+        ColumnAlign::Center => Layout::top_down(egui::Align::Center),
+        ColumnAlign::Right => Layout::right_to_left(egui::Align::Center),
+    }
+}
+
+/// Render a table, rows can be sorted by clicking the column headers
+///
+/// Only rows containing the lowercase `filter` text are shown.
+pub fn render(ui: &mut Ui, table: &TableComponent, colors: &AppColors, filter: Option<&str>) {
+    let num_columns: usize = if let Some(headers) = &table.headers {
+        headers.len()
+    } else if let Some(first_row) = table.rows.first() {
+        first_row.len()
+    } else {
+        return;
+    };
+    if num_columns == 0 {
+        return;
+    }
+
+    let sort_id = ui.id().with("table_sort");
+    let order = ui
+        .data(|d| d.get_temp::<Option<SortOrder>>(sort_id))
+        .flatten();
+    let rows = sorted_rows(&table.rows, order, filter);
+    let copy_table = || to_tsv(table);
+    let cell_text = |text: &str| {
+        let text = RichText::new(text).color(colors.fg);
+        if table.monospace {
+            text.monospace()
+        } else {
+            text
+        }
+    };
+
+    let mut builder = TableBuilder::new(ui).striped(true).vscroll(false);
+    for _ in 0..(num_columns - 1) {
+        builder = builder.column(Column::auto_with_initial_suggestion(150.0).resizable(true));
+    }
+    builder = builder.column(Column::remainder());
+
+    let body_cb = |mut body: egui_extras::TableBody| {
+        for &index in &rows {
+            let row = &table.rows[index];
+            let row_cells: Vec<&str> = row.iter().map(String::as_str).collect();
+            body.row(18.0, |mut row_ui| {
+                for (column, cell) in row.iter().enumerate() {
+                    row_ui.col(|ui| {
+                        ui.with_layout(cell_layout(table.alignment(column)), |ui| {
+                            let response =
+                                ui.add(Label::new(cell_text(cell)).sense(Sense::click()));
+                            copy_menu(&response, cell, &row_cells, Some(&copy_table));
+                        });
+                    });
+                }
+            });
+        }
+    };
+
+    let Some(headers) = &table.headers else {
+        builder.body(body_cb);
+        return;
+    };
+
+    let mut clicked = None;
+    builder
+        .header(20.0, |mut header| {
+            for (column, h) in headers.iter().enumerate() {
+                header.col(|ui| {
+                    let arrow = match order {
+                        Some(order) if order.column == column && order.ascending => " ⏶",
+                        Some(order) if order.column == column => " ⏷",
+                        _ => "",
+                    };
+                    ui.with_layout(cell_layout(table.alignment(column)), |ui| {
+                        let label = RichText::new(format!("{h}{arrow}"))
+                            .color(colors.fg)
+                            .strong();
+                        if ui
+                            .add(Label::new(label).sense(Sense::click()))
+                            .on_hover_text("Click to sort")
+                            .clicked()
+                        {
+                            clicked = Some(column);
+                        }
+                    });
+                });
+            }
+        })
+        .body(body_cb);
+
+    if let Some(column) = clicked {
+        ui.data_mut(|d| d.insert_temp(sort_id, next_order(order, column)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(cells: &[&str]) -> Vec<Vec<String>> {
+        cells
+            .iter()
+            .map(|cell| vec![cell.to_string(), String::new()])
+            .collect()
+    }
+
+    #[test]
+    fn test_sorted_rows() {
+        let table = rows(&["b", "10", "A", "9"]);
+        assert_eq!(sorted_rows(&table, None, None), vec![0, 1, 2, 3]);
+
+        let ascending = next_order(None, 0);
+        assert_eq!(sorted_rows(&table, ascending, None), vec![3, 1, 2, 0]);
+        let descending = next_order(ascending, 0);
+        assert_eq!(sorted_rows(&table, descending, None), vec![0, 2, 1, 3]);
+        assert_eq!(next_order(descending, 0), None);
+        assert_eq!(next_order(descending, 1).map(|order| order.column), Some(1));
+    }
+
+    #[test]
+    fn test_sorted_rows_filter() {
+        let table = rows(&["Apple", "banana", "Pineapple"]);
+        let ascending = next_order(None, 0);
+        assert_eq!(sorted_rows(&table, ascending, Some("apple")), vec![0, 2]);
+        assert!(sorted_rows(&table, None, Some("cherry")).is_empty());
+    }
+
+    #[test]
+    fn test_to_tsv() {
+        let table = TableComponent::new(
+            Some(vec!["Key".to_string(), "Value".to_string()]),
+            vec![vec!["a".to_string(), "1".to_string()]],
+        );
+        assert_eq!(to_tsv(&table), "Key\tValue\na\t1");
+    }
+}
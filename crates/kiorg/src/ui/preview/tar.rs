@@ -1,9 +1,8 @@
 //! Tar archive preview module
 
 use egui::RichText;
-use std::fs::File;
-use std::io::BufReader;
-use tar::Archive;
+use std::io::Read;
+use std::path::Path;
 
 use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntryMeta;
@@ -13,6 +12,32 @@ use crate::utils::preview_cache;
 
 /// Render tar archive content
 pub fn render(ui: &mut egui::Ui, entries: &[TarEntry], colors: &AppColors) {
+    render_entries(ui, entries, colors, false, None, None);
+}
+
+/// Render tar archive content with clickable file entries
+///
+/// Only entries whose name contains the lowercase `filter` text are listed.
+/// Returns the file entry clicked in this frame, if any.
+pub fn render_selectable<'a>(
+    ui: &mut egui::Ui,
+    entries: &'a [TarEntry],
+    colors: &AppColors,
+    selected: Option<&str>,
+    filter: Option<&str>,
+) -> Option<&'a TarEntry> {
+    render_entries(ui, entries, colors, true, selected, filter)
+}
+
+fn render_entries<'a>(
+    ui: &mut egui::Ui,
+    entries: &'a [TarEntry],
+    colors: &AppColors,
+    selectable: bool,
+    selected: Option<&str>,
+    filter: Option<&str>,
+) -> Option<&'a TarEntry> {
+    let mut clicked = None;
     // Display tar file contents
     ui.label(
         RichText::new("Tar Archive Contents:")
@@ -25,6 +50,10 @@ pub fn render(ui: &mut egui::Ui, entries: &[TarEntry], colors: &AppColors) {
     // TODO: calculate the correct row height
     const ROW_HEIGHT: f32 = 10.0;
 
+    let entries: Vec<&'a TarEntry> = entries
+        .iter()
+        .filter(|entry| filter.is_none_or(|filter| entry.name.to_lowercase().contains(filter)))
+        .collect();
     // Get the total number of entries
     let total_rows = entries.len();
 
@@ -39,7 +68,7 @@ pub fn render(ui: &mut egui::Ui, entries: &[TarEntry], colors: &AppColors) {
 
             // Display entries in the visible range
             for row_index in row_range {
-                let entry = &entries[row_index];
+                let entry = entries[row_index];
                 ui.horizontal(|ui| {
                     // Display permissions
                     ui.label(
@@ -55,7 +84,20 @@ pub fn render(ui: &mut egui::Ui, entries: &[TarEntry], colors: &AppColors) {
                     } else {
                         prefix_file_name(&entry.name)
                     };
-                    ui.label(RichText::new(&name_text).color(colors.fg));
+                    if selectable && !entry.is_dir {
+                        let is_selected = selected == Some(entry.name.as_str());
+                        if ui
+                            .selectable_label(
+                                is_selected,
+                                RichText::new(&name_text).color(colors.fg),
+                            )
+                            .clicked()
+                        {
+                            clicked = Some(entry);
+                        }
+                    } else {
+                        ui.label(RichText::new(&name_text).color(colors.fg));
+                    }
 
                     // Push size to the right
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -72,40 +114,31 @@ pub fn render(ui: &mut egui::Ui, entries: &[TarEntry], colors: &AppColors) {
                 });
             }
         });
+
+    clicked
 }
 
 /// Read entries from a tar file and return them as a vector of `TarEntry`
 pub fn read_tar_entries(entry: DirEntryMeta) -> Result<Vec<TarEntry>, String> {
-    let path = &entry.path;
-    let file = File::open(path).map_err(|e| format!("Failed to open tar file: {e}"))?;
-
-    // Try to determine if it's compressed by the file extension
-    let mut archive: Box<dyn std::io::Read> = Box::new(BufReader::new(file));
-
-    // Handle compressed tar files
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        match ext.to_lowercase().as_str() {
-            "gz" | "tgz" => {
-                let file =
-                    File::open(path).map_err(|e| format!("Failed to reopen tar.gz file: {e}"))?;
-                let gz = flate2::read::GzDecoder::new(BufReader::new(file));
-                archive = Box::new(gz);
-            }
-            "bz2" | "tbz" | "tbz2" => {
-                let file =
-                    File::open(path).map_err(|e| format!("Failed to reopen tar.bz2 file: {e}"))?;
-                let bz2 = bzip2::read::BzDecoder::new(BufReader::new(file));
-                archive = Box::new(bz2);
-            }
-            _ => {
-                let file =
-                    File::open(path).map_err(|e| format!("Failed to reopen tar file: {e}"))?;
-                archive = Box::new(BufReader::new(file));
-            }
+    let mut tar = crate::utils::archive::open_tar_archive(&entry.path)?;
+    let entries = tar_entries_from_archive(&mut tar)?;
+
+    // Spawn background task to save cache
+    let cached = CachedPreviewContent::Tar(entries.clone());
+    std::thread::spawn(move || {
+        let cache_key = preview_cache::calculate_cache_key(&entry);
+        if let Err(e) = preview_cache::save_preview(&cache_key, &cached) {
+            tracing::warn!("Failed to save tar preview cache: {}", e);
         }
-    }
+    });
 
-    let mut tar = Archive::new(archive);
+    Ok(entries)
+}
+
+/// List the entries of an already opened tar archive
+pub fn tar_entries_from_archive(
+    tar: &mut tar::Archive<impl Read>,
+) -> Result<Vec<TarEntry>, String> {
     let mut entries = Vec::new();
 
     let tar_entries = tar
@@ -134,14 +167,86 @@ pub fn read_tar_entries(entry: DirEntryMeta) -> Result<Vec<TarEntry>, String> {
         });
     }
 
-    // Spawn background task to save cache
-    let cached = CachedPreviewContent::Tar(entries.clone());
-    std::thread::spawn(move || {
-        let cache_key = preview_cache::calculate_cache_key(&entry);
-        if let Err(e) = preview_cache::save_preview(&cache_key, &cached) {
-            tracing::warn!("Failed to save tar preview cache: {}", e);
+    Ok(entries)
+}
+
+/// Extract a single file entry from a tar archive into memory
+///
+/// At most `max_size` bytes of the entry are read.
+pub fn read_tar_entry_bytes(path: &Path, name: &str, max_size: u64) -> Result<Vec<u8>, String> {
+    let mut tar = crate::utils::archive::open_tar_archive(path)?;
+    let tar_entries = tar
+        .entries()
+        .map_err(|e| format!("Failed to read tar entries: {e}"))?;
+
+    for entry_result in tar_entries {
+        let entry = entry_result.map_err(|e| format!("Failed to read tar entry: {e}"))?;
+        let matches = entry
+            .path()
+            .map(|p| p.to_string_lossy() == name)
+            .unwrap_or(false);
+        if !matches {
+            continue;
         }
-    });
 
-    Ok(entries)
+        let mut bytes = Vec::new();
+        entry
+            .take(max_size)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to extract tar entry {name}: {e}"))?;
+        return Ok(bytes);
+    }
+
+    Err(format!("Entry {name} not found in tar archive"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn append_file(builder: &mut tar::Builder<impl std::io::Write>, name: &str, content: &[u8]) {
+        let mut header = tar::Header::new_gnu();
+        header.set_path(name).unwrap();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, content).unwrap();
+    }
+
+    #[test]
+    fn test_read_tar_entry_bytes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.tar");
+        let mut builder = tar::Builder::new(File::create(&path).unwrap());
+        append_file(&mut builder, "a.txt", b"first");
+        append_file(&mut builder, "dir/b.txt", b"second file");
+        builder.finish().unwrap();
+        drop(builder);
+
+        assert_eq!(
+            read_tar_entry_bytes(&path, "dir/b.txt", 1024).unwrap(),
+            b"second file"
+        );
+        assert_eq!(read_tar_entry_bytes(&path, "a.txt", 3).unwrap(), b"fir");
+        assert!(read_tar_entry_bytes(&path, "missing.txt", 1024).is_err());
+    }
+
+    #[test]
+    fn test_read_tar_gz_entry_bytes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        append_file(&mut builder, "hello.txt", b"hello gz");
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert_eq!(
+            read_tar_entry_bytes(&path, "hello.txt", 1024).unwrap(),
+            b"hello gz"
+        );
+    }
 }
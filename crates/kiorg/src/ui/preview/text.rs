@@ -2,36 +2,36 @@
 
 use std::io::BufRead;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use egui::RichText;
-use egui_extras::syntax_highlighting::{CodeTheme, SyntectSettings};
+use egui::text::{LayoutJob, LayoutSection, TextFormat};
 use file_type::FileType;
-use humansize::{BINARY, format_size};
 use syntect::{
     dumps,
+    easy::HighlightLines,
+    highlighting::FontStyle,
     parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
 };
 
 use crate::app::Kiorg;
 use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntryMeta;
 use crate::models::preview_content::PreviewContent;
+use crate::ui::preview::links::{self, LinkTarget};
 use crate::ui::preview::loading::load_preview_async;
+use crate::ui::preview::syntax_theme::{self, SyntaxColors};
+use crate::utils::format::format_size;
 
-static SYNTECT_SETTINGS: OnceLock<SyntectSettings> = OnceLock::new();
-
-fn get_syntect_settings() -> &'static SyntectSettings {
-    SYNTECT_SETTINGS.get_or_init(|| egui_extras::syntax_highlighting::SyntectSettings {
-        ps: dumps::from_uncompressed_data(yazi_prebuilt::syntaxes())
-            .expect("Failed to load syntect syntax"),
-        ..Default::default()
-    })
-}
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
 
 fn get_syntax_set() -> &'static SyntaxSet {
-    &get_syntect_settings().ps
+    SYNTAX_SET.get_or_init(|| {
+        dumps::from_uncompressed_data(yazi_prebuilt::syntaxes())
+            .expect("Failed to load syntect syntax")
+    })
 }
 
 /// Render text content
@@ -39,6 +39,30 @@ pub fn render(ui: &mut egui::Ui, text: &str, colors: &AppColors) {
     ui.label(RichText::new(text).color(colors.fg));
 }
 
+/// Render text content with clickable URLs and paths
+///
+/// Returns the link clicked in this frame, if any.
+pub fn render_with_links(
+    ui: &mut egui::Ui,
+    text: &str,
+    base_dir: Option<&Path>,
+    text_style: egui::TextStyle,
+    colors: &AppColors,
+) -> Option<LinkTarget> {
+    let links = links::cached_links(ui.ctx(), text, base_dir);
+    if links.is_empty() {
+        ui.label(RichText::new(text).color(colors.fg).text_style(text_style));
+        return None;
+    }
+
+    let mut job = egui::text::LayoutJob::single_section(
+        text.to_string(),
+        egui::TextFormat::simple(text_style.resolve(ui.style()), colors.fg),
+    );
+    links::apply_link_format(&mut job, &links, colors);
+    links::show_job(ui, job, &links)
+}
+
 pub fn find_syntax_from_path(path: &std::path::Path) -> Option<&'static SyntaxReference> {
     let syntaxes = get_syntax_set();
     let name = path
@@ -70,28 +94,138 @@ pub fn find_syntax_from_path(path: &std::path::Path) -> Option<&'static SyntaxRe
 }
 
 /// Render syntax highlighted code content
-pub fn render_highlighted(ui: &mut egui::Ui, text: &str, language: &'static str) {
-    let theme = CodeTheme::from_memory(ui.ctx(), ui.style());
-    let syntect_settings = get_syntect_settings();
-    let layout_job = egui_extras::syntax_highlighting::highlight_with(
-        ui.ctx(),
-        ui.style(),
-        &theme,
-        text,
-        language,
-        syntect_settings,
-    );
+pub fn render_highlighted(
+    ui: &mut egui::Ui,
+    text: &str,
+    language: &'static str,
+    colors: &AppColors,
+) {
+    let layout_job = highlight(ui, text, language, colors);
+    render_code_frame(ui, colors, |ui| {
+        ui.add(egui::Label::new(layout_job).selectable(true));
+    });
+}
+
+/// Render syntax highlighted code content with clickable URLs and paths
+///
+/// Returns the link clicked in this frame, if any.
+pub fn render_highlighted_with_links(
+    ui: &mut egui::Ui,
+    text: &str,
+    language: &'static str,
+    base_dir: Option<&Path>,
+    colors: &AppColors,
+) -> Option<LinkTarget> {
+    let links = links::cached_links(ui.ctx(), text, base_dir);
+    if links.is_empty() {
+        render_highlighted(ui, text, language, colors);
+        return None;
+    }
+
+    let mut layout_job = highlight(ui, text, language, colors);
+    links::apply_link_format(&mut layout_job, &links, colors);
+    let mut clicked = None;
+    render_code_frame(ui, colors, |ui| {
+        clicked = links::show_job(ui, layout_job, &links);
+    });
+    clicked
+}
+
+#[derive(Default)]
+struct Highlighter;
 
+type HighlightKey<'a> = (&'a egui::FontId, SyntaxColors, &'a str, &'a str);
+
+impl egui::cache::ComputerMut<HighlightKey<'_>, LayoutJob> for Highlighter {
+    fn compute(&mut self, (font_id, colors, text, language): HighlightKey<'_>) -> LayoutJob {
+        highlight_uncached(font_id, colors, text, language)
+    }
+}
+
+type HighlightCache = egui::cache::FrameCache<LayoutJob, Highlighter>;
+
+/// Highlight `text` as `language` with the colors of the app theme
+pub fn highlight(
+    ui: &egui::Ui,
+    text: &str,
+    language: &'static str,
+    colors: &AppColors,
+) -> LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let key = (&font_id, SyntaxColors::from(colors), text, language);
+    ui.ctx()
+        .memory_mut(|mem| mem.caches.cache::<HighlightCache>().get(key))
+}
+
+fn highlight_uncached(
+    font_id: &egui::FontId,
+    colors: SyntaxColors,
+    text: &str,
+    language: &str,
+) -> LayoutJob {
+    let syntaxes = get_syntax_set();
+    let syntax = syntaxes
+        .find_syntax_by_name(language)
+        .or_else(|| syntaxes.find_syntax_by_extension(language))
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let theme = syntax_theme::generate(&colors);
+    let mut highlighter = HighlightLines::new(syntax, &theme);
+
+    let plain = TextFormat::simple(font_id.clone(), colors.fg);
+    let mut job = LayoutJob {
+        text: text.to_string(),
+        ..Default::default()
+    };
+    for line in LinesWithEndings::from(text) {
+        let start = line.as_ptr() as usize - text.as_ptr() as usize;
+        let Ok(ranges) = highlighter.highlight_line(line, syntaxes) else {
+            job.sections.push(LayoutSection {
+                leading_space: 0.0,
+                byte_range: start..start + line.len(),
+                format: plain.clone(),
+            });
+            continue;
+        };
+        for (style, range) in ranges {
+            let offset = range.as_ptr() as usize - text.as_ptr() as usize;
+            let fg = style.foreground;
+            let color = egui::Color32::from_rgb(fg.r, fg.g, fg.b);
+            let underline = if style.font_style.contains(FontStyle::UNDERLINE) {
+                egui::Stroke::new(1.0, color)
+            } else {
+                egui::Stroke::NONE
+            };
+            job.sections.push(LayoutSection {
+                leading_space: 0.0,
+                byte_range: offset..offset + range.len(),
+                format: TextFormat {
+                    font_id: font_id.clone(),
+                    color,
+                    italics: style.font_style.contains(FontStyle::ITALIC),
+                    underline,
+                    ..Default::default()
+                },
+            });
+        }
+    }
+    job
+}
+
+pub fn render_code_frame(
+    ui: &mut egui::Ui,
+    colors: &AppColors,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
     let available_size = ui.available_size();
     let spacing = ui.spacing().item_spacing;
-    // Wrap the label in a container with dark background for consistency across all themes
+    // Same background as the generated highlighting theme
     egui::Frame::new()
-        .fill(egui::Color32::from_rgb(40, 44, 52))
+        .fill(colors.bg_extreme)
         .inner_margin(egui::Margin::same(8))
         .show(ui, |ui| {
             // Make the frame take up all available width
             ui.set_min_width(available_size.x - 2.0 * spacing.x);
-            ui.add(egui::Label::new(layout_job).selectable(true));
+            add_contents(ui);
         });
 }
 
@@ -101,20 +235,16 @@ pub fn render_empty(ui: &mut egui::Ui, colors: &AppColors) {
 }
 
 /// Load text content asynchronously
-pub fn load_async(app: &mut Kiorg, entry: DirEntryMeta, file_size: u64) {
+///
+/// Files larger than `max_bytes` are previewed truncated, `None` loads them in full.
+pub fn load_async(app: &mut Kiorg, entry: DirEntryMeta, file_size: u64, max_bytes: Option<u64>) {
     load_preview_async(app, entry, move |entry| {
-        // Check if file size is larger than 1MB (1,048,576 bytes)
-        const MAX_PREVIEW_SIZE: u64 = 1_048_576;
-        if file_size > MAX_PREVIEW_SIZE {
-            let info = format!(
-                "Preview disabled for files larger than {}\n\nFile size: {}",
-                format_size(MAX_PREVIEW_SIZE, BINARY),
-                format_size(file_size, BINARY),
-            );
-            return Ok(PreviewContent::text(info));
-        }
-
         let path = &entry.path;
+        if let Some(max_bytes) = max_bytes
+            && file_size > max_bytes
+        {
+            return load_truncated_text(path, max_bytes, file_size);
+        }
 
         if let Some(syntax) = find_syntax_from_path(path) {
             // For supported languages, load the full file for syntax highlighting
@@ -126,6 +256,46 @@ pub fn load_async(app: &mut Kiorg, entry: DirEntryMeta, file_size: u64) {
     });
 }
 
+/// Decode bytes cut off at an arbitrary offset, `None` if they are not UTF-8 text
+fn decode_truncated(bytes: &[u8]) -> Option<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(content) => Some(content.to_string()),
+        // Only the last character is cut off
+        Err(e) if e.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..e.valid_up_to()]).to_string())
+        }
+        Err(_) => None,
+    }
+}
+
+/// Load the beginning of a file that is too large to preview in full
+fn load_truncated_text(
+    path: &Path,
+    max_bytes: u64,
+    file_size: u64,
+) -> Result<PreviewContent, String> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(max_bytes).read_to_end(&mut bytes))
+        .map_err(|e| format!("Failed to read file: {e}"))?;
+    let Some(mut content) = decode_truncated(&bytes) else {
+        return render_generic_file(path.to_path_buf(), file_size);
+    };
+    content.push_str(&format!(
+        "\n\n[Truncated, showing the first {} of {}]",
+        format_size(max_bytes, false),
+        format_size(file_size, false),
+    ));
+
+    match find_syntax_from_path(path) {
+        Some(syntax) => Ok(PreviewContent::HighlightedCode {
+            content,
+            language: syntax.name.as_str(),
+        }),
+        None => Ok(PreviewContent::text(content)),
+    }
+}
+
 /// Try to load a file as UTF-8 text
 pub fn try_load_utf8_str(path: PathBuf, file_size: u64) -> Result<PreviewContent, String> {
     // TODO: reuse the buffer between file reads
@@ -163,8 +333,27 @@ pub fn try_load_utf8_str(path: PathBuf, file_size: u64) -> Result<PreviewContent
 
 /// Detect file type and return `PreviewContent` with generic file information
 pub fn render_generic_file(path: PathBuf, size: u64) -> Result<PreviewContent, String> {
-    // Try to detect the file type using file_type crate
-    let file_type_info = match FileType::try_from_file(&path) {
+    // Executables without an extension are recognized by their headers
+    #[cfg(feature = "binary-preview")]
+    if let Some(meta) = super::binary::read_binary_metadata(&path) {
+        return meta.map(PreviewContent::Binary);
+    }
+
+    // Return the PreviewContent directly
+    Ok(PreviewContent::text(format!(
+        "{}\n\n{}\n\nSize: {} bytes",
+        path.as_path()
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy(),
+        file_type_info(&path),
+        size
+    )))
+}
+
+/// Describe the type of a file from its first bytes, without decoding it
+pub fn file_type_info(path: &Path) -> String {
+    match FileType::try_from_file(path) {
         Ok(file_type) => {
             let media_types = file_type.media_types().join(", ");
             let extensions = file_type.extensions().join(", ");
@@ -174,22 +363,18 @@ pub fn render_generic_file(path: PathBuf, size: u64) -> Result<PreviewContent, S
             } else if !extensions.is_empty() {
                 format!("File type: {extensions}")
             } else {
-                "Unknown file type".to_string()
+                sniffed_type_info(path)
             }
         }
-        Err(_) => "Unknown file type".to_string(),
-    };
+        Err(_) => sniffed_type_info(path),
+    }
+}
 
-    // Return the PreviewContent directly
-    Ok(PreviewContent::text(format!(
-        "{}\n\n{}\n\nSize: {} bytes",
-        path.as_path()
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy(),
-        file_type_info,
-        size
-    )))
+fn sniffed_type_info(path: &Path) -> String {
+    match mimeapps::sniff_file_type(path) {
+        Some(sniffed) => format!("File type: {} ({})", sniffed.mime_type, sniffed.extension),
+        None => "Unknown file type".to_string(),
+    }
 }
 
 /// Load full text content
@@ -226,6 +411,14 @@ mod tests {
     use super::*;
     use std::path::Path;
 
+    #[test]
+    fn test_decode_truncated() {
+        assert_eq!(decode_truncated(b"plain").as_deref(), Some("plain"));
+        // "é" cut in half at the end
+        assert_eq!(decode_truncated(b"caf\xc3").as_deref(), Some("caf"));
+        assert_eq!(decode_truncated(b"\xff\xfebinary"), None);
+    }
+
     #[test]
     fn test_find_syntax_from_path_rust() {
         assert_eq!(
@@ -2,16 +2,45 @@
 
 use egui::RichText;
 use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
 use zip::ZipArchive;
 
 use crate::config::colors::AppColors;
 use crate::models::dir_entry::DirEntryMeta;
 use crate::models::preview_content::{CachedPreviewContent, ZipEntry};
 use crate::ui::preview::{prefix_dir_name, prefix_file_name};
+use crate::utils::archive::MemberReadError;
 use crate::utils::preview_cache;
 
 /// Render zip archive content
 pub fn render(ui: &mut egui::Ui, entries: &[ZipEntry], colors: &AppColors) {
+    render_entries(ui, entries, colors, false, None, None);
+}
+
+/// Render zip archive content with clickable file entries
+///
+/// Only entries whose name contains the lowercase `filter` text are listed.
+/// Returns the file entry clicked in this frame, if any.
+pub fn render_selectable<'a>(
+    ui: &mut egui::Ui,
+    entries: &'a [ZipEntry],
+    colors: &AppColors,
+    selected: Option<&str>,
+    filter: Option<&str>,
+) -> Option<&'a ZipEntry> {
+    render_entries(ui, entries, colors, true, selected, filter)
+}
+
+fn render_entries<'a>(
+    ui: &mut egui::Ui,
+    entries: &'a [ZipEntry],
+    colors: &AppColors,
+    selectable: bool,
+    selected: Option<&str>,
+    filter: Option<&str>,
+) -> Option<&'a ZipEntry> {
+    let mut clicked = None;
     // Display zip file contents
     ui.label(
         RichText::new("Zip Archive Contents:")
@@ -23,6 +52,10 @@ pub fn render(ui: &mut egui::Ui, entries: &[ZipEntry], colors: &AppColors) {
     // TODO: calculate the correct row height
     const ROW_HEIGHT: f32 = 10.0;
 
+    let entries: Vec<&'a ZipEntry> = entries
+        .iter()
+        .filter(|entry| filter.is_none_or(|filter| entry.name.to_lowercase().contains(filter)))
+        .collect();
     // Get the total number of entries
     let total_rows = entries.len();
 
@@ -37,7 +70,7 @@ pub fn render(ui: &mut egui::Ui, entries: &[ZipEntry], colors: &AppColors) {
 
             // Display entries in the visible range
             for row_index in row_range {
-                let entry = &entries[row_index];
+                let entry = entries[row_index];
                 let entry_text = if entry.is_dir {
                     RichText::new(prefix_dir_name(&entry.name)).strong()
                 } else {
@@ -45,7 +78,17 @@ pub fn render(ui: &mut egui::Ui, entries: &[ZipEntry], colors: &AppColors) {
                 };
 
                 ui.horizontal(|ui| {
-                    ui.label(entry_text.color(colors.fg));
+                    if selectable && !entry.is_dir {
+                        let is_selected = selected == Some(entry.name.as_str());
+                        if ui
+                            .selectable_label(is_selected, entry_text.color(colors.fg))
+                            .clicked()
+                        {
+                            clicked = Some(entry);
+                        }
+                    } else {
+                        ui.label(entry_text.color(colors.fg));
+                    }
                     if !entry.is_dir {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.label(
@@ -60,6 +103,8 @@ pub fn render(ui: &mut egui::Ui, entries: &[ZipEntry], colors: &AppColors) {
                 });
             }
         });
+
+    clicked
 }
 
 /// Read entries from a zip file and return them as a vector of `ZipEntry`
@@ -67,10 +112,25 @@ pub fn read_zip_entries(entry: DirEntryMeta) -> Result<Vec<ZipEntry>, String> {
     let path = &entry.path;
     // Open the zip file
     let file = File::open(path).map_err(|e| format!("Failed to open zip file: {e}"))?;
+    let entries = zip_entries_from_reader(file)?;
+
+    // Spawn background task to save cache
+    let cached = CachedPreviewContent::Zip(entries.clone());
+    std::thread::spawn(move || {
+        let cache_key = preview_cache::calculate_cache_key(&entry);
+        if let Err(e) = preview_cache::save_preview(&cache_key, &cached) {
+            tracing::warn!("Failed to save zip preview cache: {}", e);
+        }
+    });
+
+    Ok(entries)
+}
 
-    // Create a zip archive from the file
+/// List the entries of a zip archive from any seekable reader
+pub fn zip_entries_from_reader(reader: impl Read + Seek) -> Result<Vec<ZipEntry>, String> {
+    // Create a zip archive from the reader
     let mut archive =
-        ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {e}"))?;
+        ZipArchive::new(reader).map_err(|e| format!("Failed to read zip archive: {e}"))?;
 
     // Create a vector to store the entries
     let mut entries = Vec::new();
@@ -100,14 +160,92 @@ pub fn read_zip_entries(entry: DirEntryMeta) -> Result<Vec<ZipEntry>, String> {
         _ => a.name.cmp(&b.name),
     });
 
-    // Spawn background task to save cache
-    let cached = CachedPreviewContent::Zip(entries.clone());
-    std::thread::spawn(move || {
-        let cache_key = preview_cache::calculate_cache_key(&entry);
-        if let Err(e) = preview_cache::save_preview(&cache_key, &cached) {
-            tracing::warn!("Failed to save zip preview cache: {}", e);
+    Ok(entries)
+}
+
+/// Extract a single file entry from a zip archive into memory
+///
+/// At most `max_size` bytes of the uncompressed entry are read. Encrypted
+/// entries are decrypted with `password` when given.
+pub fn read_zip_entry_bytes(
+    path: &Path,
+    name: &str,
+    max_size: u64,
+    password: Option<&str>,
+) -> Result<Vec<u8>, MemberReadError> {
+    let file = File::open(path)
+        .map_err(|e| MemberReadError::Other(format!("Failed to open zip file: {e}")))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| MemberReadError::Other(format!("Failed to read zip archive: {e}")))?;
+    let file = match password {
+        Some(password) => archive.by_name_decrypt(name, password.as_bytes()),
+        None => archive.by_name(name),
+    }
+    .map_err(|e| match MemberReadError::from(e) {
+        MemberReadError::Other(e) => {
+            MemberReadError::Other(format!("Failed to read zip entry {name}: {e}"))
         }
-    });
+        e => e,
+    })?;
+
+    let mut bytes = Vec::new();
+    file.take(max_size)
+        .read_to_end(&mut bytes)
+        .map_err(|e| MemberReadError::Other(format!("Failed to extract zip entry {name}: {e}")))?;
+    Ok(bytes)
+}
 
-    Ok(entries)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_read_zip_entry_bytes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("dir/hello.txt", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"hello world").unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(
+            read_zip_entry_bytes(&path, "dir/hello.txt", 1024, None).unwrap(),
+            b"hello world"
+        );
+        assert_eq!(
+            read_zip_entry_bytes(&path, "dir/hello.txt", 5, None).unwrap(),
+            b"hello"
+        );
+        assert!(read_zip_entry_bytes(&path, "missing.txt", 1024, None).is_err());
+    }
+
+    #[test]
+    fn test_read_encrypted_zip_entry_bytes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("secret.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file(
+            "secret.txt",
+            SimpleFileOptions::default().with_aes_encryption(zip::AesMode::Aes256, "hunter2"),
+        )
+        .unwrap();
+        zip.write_all(b"top secret").unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(
+            read_zip_entry_bytes(&path, "secret.txt", 1024, None),
+            Err(MemberReadError::PasswordRequired)
+        );
+        assert_eq!(
+            read_zip_entry_bytes(&path, "secret.txt", 1024, Some("wrong")),
+            Err(MemberReadError::InvalidPassword)
+        );
+        assert_eq!(
+            read_zip_entry_bytes(&path, "secret.txt", 1024, Some("hunter2")).unwrap(),
+            b"top secret"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use egui::{RichText, Ui};
 
 use crate::app::Kiorg;
+use crate::config::ZoomMode;
 use crate::models::preview_content::PreviewContent;
 use crate::ui::preview;
 use crate::ui::style::{HEADER_ROW_HEIGHT, section_title_text};
@@ -8,13 +9,17 @@ use crate::ui::style::{HEADER_ROW_HEIGHT, section_title_text};
 const PANEL_SPACING: f32 = 10.0;
 
 /// Draws the right panel (preview).
-pub fn draw(app: &mut Kiorg, _ctx: &egui::Context, ui: &mut Ui, width: f32, height: f32) {
+pub fn draw(app: &mut Kiorg, ctx: &egui::Context, ui: &mut Ui, width: f32, height: f32) {
     if matches!(app.show_popup, Some(crate::ui::popup::PopupType::Preview)) {
         // If preview is alwready shown in a popup, avoid unnecessary rendering in this panel
         return;
     }
 
     let colors = &app.colors;
+    let base_dir = app.tab_manager.current_tab_ref().current_path.clone();
+    let mut clicked_link = None;
+    let mut load_deferred = None;
+    let mut compute_checksums = false;
 
     ui.vertical(|ui| {
         ui.set_min_width(width);
@@ -57,11 +62,28 @@ pub fn draw(app: &mut Kiorg, _ctx: &egui::Context, ui: &mut Ui, width: f32, heig
                             );
                         });
                     }
+                    Some(PreviewContent::Deferred { path, reason }) => {
+                        if preview::render_deferred(ui, reason, colors) {
+                            load_deferred = Some(path.clone());
+                        }
+                    }
                     Some(PreviewContent::Text(text)) => {
-                        preview::text::render(ui, text, colors);
+                        clicked_link = preview::text::render_with_links(
+                            ui,
+                            text,
+                            Some(&base_dir),
+                            egui::TextStyle::Body,
+                            colors,
+                        );
                     }
                     Some(PreviewContent::HighlightedCode { content, language }) => {
-                        preview::text::render_highlighted(ui, content, language);
+                        clicked_link = preview::text::render_highlighted_with_links(
+                            ui,
+                            content,
+                            language,
+                            Some(&base_dir),
+                            colors,
+                        );
                     }
                     Some(PreviewContent::PluginPreview { components }) => {
                         preview::plugin::render(
@@ -70,6 +92,8 @@ pub fn draw(app: &mut Kiorg, _ctx: &egui::Context, ui: &mut Ui, width: f32, heig
                             colors,
                             available_width,
                             available_height,
+                            None,
+                            ZoomMode::default(),
                         );
                     }
                     Some(PreviewContent::Image(image_meta)) => {
@@ -108,6 +132,12 @@ pub fn draw(app: &mut Kiorg, _ctx: &egui::Context, ui: &mut Ui, width: f32, heig
                             available_height,
                         );
                     }
+                    Some(PreviewContent::Html(html_meta)) => {
+                        clicked_link = preview::html::render(ui, html_meta, colors);
+                    }
+                    Some(PreviewContent::Binary(binary_meta)) => {
+                        preview::binary::render(ui, binary_meta, colors);
+                    }
                     Some(PreviewContent::Zip(entries)) => {
                         preview::zip::render(ui, entries, colors);
                     }
@@ -115,13 +145,26 @@ pub fn draw(app: &mut Kiorg, _ctx: &egui::Context, ui: &mut Ui, width: f32, heig
                         preview::tar::render(ui, entries, colors);
                     }
                     Some(PreviewContent::Directory(entries)) => {
-                        preview::directory::render(ui, entries, colors);
+                        preview::directory::render(ui, entries, app.dir_size_scan.as_ref(), colors);
                     }
-                    None => {
-                        // No file selected or preview not loaded yet
+                    None | Some(PreviewContent::Diff(_)) => {
+                        // No file selected, preview not loaded yet or a diff only the popup shows
                         preview::text::render_empty(ui, colors);
                     }
                 }
+
+                let is_file_preview = !matches!(
+                    app.preview_content,
+                    None | Some(
+                        PreviewContent::Loading { .. }
+                            | PreviewContent::Directory(_)
+                            | PreviewContent::Diff(_)
+                    )
+                );
+                if is_file_preview {
+                    compute_checksums =
+                        preview::checksum::render(ui, app.checksum_job.as_ref(), colors);
+                }
             });
 
         // Draw help text in its own row at the bottom
@@ -130,4 +173,14 @@ pub fn draw(app: &mut Kiorg, _ctx: &egui::Context, ui: &mut Ui, width: f32, heig
             ui.label(egui::RichText::new("? for help").color(colors.fg_light));
         });
     });
+
+    if let Some(link) = clicked_link {
+        app.open_link(link);
+    }
+    if compute_checksums && let Some(path) = app.cached_preview_path.clone() {
+        app.checksum_job = Some(preview::checksum::ChecksumJob::start(path, ctx));
+    }
+    if let Some(path) = load_deferred {
+        preview::load_deferred(app, ctx, path);
+    }
 }
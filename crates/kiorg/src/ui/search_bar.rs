@@ -124,7 +124,7 @@ pub fn draw(ctx: &Context, app: &mut Kiorg) {
                         // Search input
                         let text_edit =
                             egui::TextEdit::singleline(app.search_bar.query.as_mut().unwrap())
-                                .hint_text("Search...")
+                                .hint_text("Search... (ext:pdf size>10mb modified<7d)")
                                 .desired_width(f32::INFINITY) // Take available width
                                 .frame(egui::Frame::NONE);
                         let response = ui.add(text_edit);
@@ -193,6 +193,26 @@ pub fn draw(ctx: &Context, app: &mut Kiorg) {
                             apply_new_query(app);
                         }
 
+                        // Switch to searching file contents with the same text
+                        let grep_button_clicked = ui
+                            .add(
+                                egui::Button::new(
+                                    egui::RichText::new("Gr").color(app.colors.fg_light),
+                                )
+                                .small()
+                                .frame(false),
+                            )
+                            .on_hover_text("Search file contents instead")
+                            .clicked();
+                        if grep_button_clicked {
+                            let pattern = app.search_bar.query.clone().unwrap_or_default();
+                            app.search_bar.close();
+                            let tab = app.tab_manager.current_tab_mut();
+                            tab.update_filtered_cache(&None, false, false);
+                            crate::ui::popup::content_search::open(app, pattern);
+                            return;
+                        }
+
                         // Close button
                         if ui.button("×").clicked() {
                             app.search_bar.close();
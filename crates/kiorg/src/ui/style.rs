@@ -2,6 +2,27 @@ use crate::config::colors::AppColors;
 
 pub const HEADER_FONT_SIZE: f32 = 12.0;
 pub const HEADER_ROW_HEIGHT: f32 = HEADER_FONT_SIZE + 4.0;
+/// Height of rows and buttons in touch mode, large enough to hit with a finger
+pub const TOUCH_INTERACT_HEIGHT: f32 = 36.0;
+
+/// Enlarge or restore widget spacing for touch mode
+pub fn apply_touch_mode(ctx: &egui::Context, enabled: bool) {
+    let default_spacing = egui::style::Spacing::default();
+    ctx.all_styles_mut(|style| {
+        let spacing = &mut style.spacing;
+        if enabled {
+            spacing.interact_size.y = TOUCH_INTERACT_HEIGHT;
+            spacing.button_padding = egui::vec2(12.0, 8.0);
+            spacing.item_spacing = egui::vec2(10.0, 6.0);
+            spacing.scroll.bar_width = 16.0;
+        } else {
+            spacing.interact_size = default_spacing.interact_size;
+            spacing.button_padding = default_spacing.button_padding;
+            spacing.item_spacing = default_spacing.item_spacing;
+            spacing.scroll = default_spacing.scroll;
+        }
+    });
+}
 
 #[must_use]
 pub fn section_title_text(text: &str, colors: &AppColors) -> egui::RichText {
@@ -33,8 +33,13 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui) {
                 ui.menu_button(RichText::new("☰").color(app.colors.fg_light), |ui| {
                     ui.set_min_width(150.0);
 
+                    if ui.button("New window").clicked() {
+                        app.new_window_requested = true;
+                        ui.close();
+                    }
+
                     if ui.button("Bookmarks").clicked() {
-                        app.show_popup = Some(PopupType::Bookmarks(0));
+                        crate::ui::popup::bookmark::open(app);
                         ui.close();
                     }
 
@@ -50,6 +55,16 @@ pub fn draw(app: &mut Kiorg, ui: &mut Ui) {
                         ui.close();
                     }
 
+                    if ui.button("Trash").clicked() {
+                        crate::ui::popup::trash::open(app);
+                        ui.close();
+                    }
+
+                    if ui.button("Find duplicates").clicked() {
+                        crate::ui::popup::duplicates::open(app, ui.ctx());
+                        ui.close();
+                    }
+
                     if ui.button("Themes").clicked() {
                         // Use current theme key or default to dark_kiorg
                         let current_theme_key = app
@@ -0,0 +1,158 @@
+//! Filter-as-you-type narrowing the file list, lighter than the search bar
+
+use std::ops::Range;
+
+use egui::{Context, Key};
+
+use crate::app::Kiorg;
+
+#[derive(Default)]
+pub struct TypeAhead {
+    pub query: Option<String>,
+    /// Typed text goes to the filter, Enter stops typing and keeps the filter
+    pub typing: bool,
+}
+
+impl TypeAhead {
+    #[must_use]
+    pub const fn active(&self) -> bool {
+        self.query.is_some()
+    }
+
+    pub fn activate(&mut self) {
+        if self.query.is_none() {
+            self.query = Some(String::new());
+        }
+        self.typing = true;
+    }
+
+    pub fn close(&mut self) {
+        self.typing = false;
+        self.query = None;
+    }
+}
+
+/// Start filtering the file list as you type, replacing the search filter
+pub fn activate(app: &mut Kiorg) {
+    if app.search_bar.active() {
+        app.search_bar.close();
+        app.tab_manager
+            .current_tab_mut()
+            .update_filtered_cache(&None, false, false);
+    }
+    app.type_ahead.activate();
+}
+
+/// Stop filtering and show all entries again
+pub fn close(app: &mut Kiorg) {
+    app.type_ahead.close();
+    app.tab_manager
+        .current_tab_mut()
+        .update_filtered_cache(&None, false, false);
+}
+
+/// Narrow the entries down to the names containing the typed text, ignoring case
+pub fn apply(app: &mut Kiorg) {
+    let tab = app.tab_manager.current_tab_mut();
+    tab.update_filtered_cache(&app.type_ahead.query, true, false);
+    let filtered = tab.get_cached_filtered_entries();
+    // Keep the selection if it still matches
+    if !filtered.contains(&tab.selected_index)
+        && let Some(&index) = filtered.first()
+    {
+        tab.update_selection(index);
+        app.selection_changed = true;
+    }
+    app.ensure_selected_visible = true;
+}
+
+/// Handle the keys typed into the filter, returns whether they were consumed
+pub fn handle_key_press(ctx: &Context, app: &mut Kiorg) -> bool {
+    if !app.type_ahead.typing || app.show_popup.is_some() {
+        return false;
+    }
+    let Some(query) = &mut app.type_ahead.query else {
+        return false;
+    };
+
+    let mut changed = false;
+    let mut stop_typing = false;
+    let mut cancel = false;
+    let mut movement = 0;
+    ctx.input(|i| {
+        for event in &i.events {
+            match event {
+                egui::Event::Text(text) => {
+                    query.push_str(text);
+                    changed = true;
+                }
+                egui::Event::Key {
+                    key, pressed: true, ..
+                } => match key {
+                    Key::Backspace => changed |= query.pop().is_some(),
+                    Key::Enter => stop_typing = true,
+                    Key::Escape => cancel = true,
+                    Key::ArrowDown => movement += 1,
+                    Key::ArrowUp => movement -= 1,
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    });
+
+    if cancel {
+        close(app);
+        return true;
+    }
+    if changed {
+        apply(app);
+    }
+    if movement != 0 {
+        app.move_selection(movement);
+    }
+    if stop_typing {
+        app.type_ahead.typing = false;
+        // An empty filter has nothing to keep
+        if app.type_ahead.query.as_deref() == Some("") {
+            close(app);
+        }
+    }
+    // Typed keys never reach the shortcuts
+    true
+}
+
+/// Byte range of the first occurrence of `needle` in `haystack`, ignoring case
+#[must_use]
+pub fn find_ignore_case(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    if needle.is_empty() {
+        return None;
+    }
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut rest = haystack[start..].chars();
+        let mut end = start;
+        for n in needle.chars() {
+            let h = rest.next()?;
+            if !h.to_lowercase().eq(n.to_lowercase()) {
+                return None;
+            }
+            end += h.len_utf8();
+        }
+        Some(start..end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_ignore_case() {
+        assert_eq!(find_ignore_case("Cargo.toml", "TOML"), Some(6..10));
+        assert_eq!(find_ignore_case("Cargo.toml", "car"), Some(0..3));
+        assert_eq!(find_ignore_case("Résumé.pdf", "sumé"), Some(3..8));
+        assert_eq!(find_ignore_case("README.md", "txt"), None);
+        assert_eq!(find_ignore_case("README.md", ""), None);
+        assert_eq!(find_ignore_case("ab", "abc"), None);
+    }
+}
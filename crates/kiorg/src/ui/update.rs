@@ -1,6 +1,6 @@
 use crate::app::Kiorg;
+use crate::utils::format::format_size;
 use egui::Context;
-use humansize::{BINARY, format_size};
 use self_update::cargo_crate_version;
 use semver::Version;
 use std::env::consts::ARCH;
@@ -295,8 +295,8 @@ pub fn show_update_progress(ctx: &Context, app: &mut Kiorg) {
             // Status text
             ui.label(format!(
                 "{} / {} ({:.1}%)",
-                format_size(state.downloaded_bytes, BINARY),
-                format_size(state.total_bytes as u64, BINARY),
+                format_size(state.downloaded_bytes, false),
+                format_size(state.total_bytes as u64, false),
                 progress * 100.0
             ));
 
@@ -0,0 +1,807 @@
+//! Archive helpers shared by previews and archive actions
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use tar::Archive;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// Archive passwords entered during this session, keyed by archive path
+static SESSION_PASSWORDS: OnceLock<Mutex<HashMap<PathBuf, String>>> = OnceLock::new();
+
+fn session_passwords() -> &'static Mutex<HashMap<PathBuf, String>> {
+    SESSION_PASSWORDS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Password previously entered for the archive, if any
+#[must_use]
+pub fn cached_password(archive_path: &Path) -> Option<String> {
+    session_passwords()
+        .lock()
+        .ok()
+        .and_then(|passwords| passwords.get(archive_path).cloned())
+}
+
+/// Remember the password of an archive until the app exits
+pub fn cache_password(archive_path: &Path, password: String) {
+    if let Ok(mut passwords) = session_passwords().lock() {
+        passwords.insert(archive_path.to_path_buf(), password);
+    }
+}
+
+/// Forget a cached archive password, e.g. after it turned out to be wrong
+pub fn forget_password(archive_path: &Path) {
+    if let Ok(mut passwords) = session_passwords().lock() {
+        passwords.remove(archive_path);
+    }
+}
+
+/// Error reading a single archive member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberReadError {
+    /// The member is encrypted and no password was given
+    PasswordRequired,
+    /// The member is encrypted and the given password doesn't match
+    InvalidPassword,
+    Other(String),
+}
+
+impl std::fmt::Display for MemberReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PasswordRequired => write!(f, "Password required"),
+            Self::InvalidPassword => write!(f, "Incorrect password"),
+            Self::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<ZipError> for MemberReadError {
+    fn from(e: ZipError) -> Self {
+        match e {
+            ZipError::UnsupportedArchive(msg) if msg == ZipError::PASSWORD_REQUIRED => {
+                Self::PasswordRequired
+            }
+            ZipError::InvalidPassword => Self::InvalidPassword,
+            e => Self::Other(e.to_string()),
+        }
+    }
+}
+
+/// Whether any member of the zip archive is encrypted
+pub fn zip_is_encrypted(reader: impl Read + Seek) -> Result<bool, String> {
+    let mut archive =
+        ZipArchive::new(reader).map_err(|e| format!("Failed to read zip archive: {e}"))?;
+    for i in 0..archive.len() {
+        let member = archive
+            .by_index_raw(i)
+            .map_err(|e| format!("Failed to read zip entry: {e}"))?;
+        if member.encrypted() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Archive formats supported by the integrity test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    SevenZ,
+}
+
+impl ArchiveFormat {
+    /// Detect the archive format from the file extension
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match crate::ui::preview::path_to_ext_info(path).as_str() {
+            crate::ui::preview::zip_extensions!() => Some(Self::Zip),
+            crate::ui::preview::tar_extensions!() => Some(Self::Tar),
+            "7z" => Some(Self::SevenZ),
+            _ => None,
+        }
+    }
+}
+
+/// A member that failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptMember {
+    pub name: String,
+    pub error: String,
+}
+
+/// Outcome of an archive integrity test
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveTestReport {
+    /// Number of members that were read
+    pub tested: usize,
+    pub corrupt: Vec<CorruptMember>,
+}
+
+impl ArchiveTestReport {
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.corrupt.is_empty()
+    }
+}
+
+/// Open a tar archive, picking the decompressor from the file extension
+pub fn open_tar_archive(path: &Path) -> Result<Archive<Box<dyn Read>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open tar file: {e}"))?;
+    Ok(tar_archive_from_reader(BufReader::new(file), path))
+}
+
+/// Wrap a tar stream in the decompressor matching the extension of `name`
+pub fn tar_archive_from_reader<'a>(
+    reader: impl Read + 'a,
+    name: &Path,
+) -> Archive<Box<dyn Read + 'a>> {
+    // Handle compressed tar files
+    let ext = name
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    let archive: Box<dyn Read + 'a> = match ext.as_str() {
+        "gz" | "tgz" => Box::new(flate2::read::GzDecoder::new(reader)),
+        "bz2" | "tbz" | "tbz2" => Box::new(bzip2::read::BzDecoder::new(reader)),
+        "xz" | "txz" => Box::new(XzTarReader {
+            source: Some(reader),
+            decoded: None,
+        }),
+        "zst" | "zstd" | "tzst" => match zstd::stream::read::Decoder::new(reader) {
+            Ok(decoder) => Box::new(decoder),
+            Err(e) => Box::new(FailedReader(Some(e))),
+        },
+        _ => Box::new(reader),
+    };
+    Archive::new(archive)
+}
+
+/// lzma-rs has no streaming xz reader, so the tarball is unpacked into an
+/// anonymous temporary file on the first read
+struct XzTarReader<R: Read> {
+    source: Option<R>,
+    decoded: Option<File>,
+}
+
+impl<R: Read> Read for XzTarReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if let Some(source) = self.source.take() {
+            let mut file = tempfile::tempfile()?;
+            lzma_rs::xz_decompress(&mut BufReader::new(source), &mut file)
+                .map_err(std::io::Error::other)?;
+            file.rewind()?;
+            self.decoded = Some(file);
+        }
+        match self.decoded.as_mut() {
+            Some(file) => file.read(buf),
+            None => Err(std::io::Error::other("Failed to decompress xz stream")),
+        }
+    }
+}
+
+/// Reader that reports a decompressor setup error on first use
+struct FailedReader(Option<std::io::Error>);
+
+impl Read for FailedReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(self
+            .0
+            .take()
+            .unwrap_or_else(|| std::io::Error::other("Failed to set up decompressor")))
+    }
+}
+
+/// Compression formats for single compressed files (not tarballs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+}
+
+impl CompressionFormat {
+    /// Detect the compression format from the file extension
+    ///
+    /// Compressed tarballs such as `.tar.gz` are not treated as single files.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match crate::ui::preview::path_to_ext_info(path).as_str() {
+            "gz" => Some(Self::Gzip),
+            "bz2" => Some(Self::Bzip2),
+            "xz" => Some(Self::Xz),
+            "zst" | "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Decompress a single compressed file into `writer`
+pub fn decompress_to(
+    path: &Path,
+    format: CompressionFormat,
+    writer: &mut impl Write,
+) -> std::io::Result<u64> {
+    let mut reader = BufReader::new(File::open(path)?);
+    match format {
+        CompressionFormat::Gzip => {
+            std::io::copy(&mut flate2::read::MultiGzDecoder::new(reader), writer)
+        }
+        CompressionFormat::Bzip2 => {
+            std::io::copy(&mut bzip2::read::MultiBzDecoder::new(reader), writer)
+        }
+        CompressionFormat::Zstd => {
+            std::io::copy(&mut zstd::stream::read::Decoder::new(reader)?, writer)
+        }
+        CompressionFormat::Xz => {
+            let mut counter = CountingWriter {
+                inner: writer,
+                count: 0,
+            };
+            lzma_rs::xz_decompress(&mut reader, &mut counter).map_err(std::io::Error::other)?;
+            Ok(counter.count)
+        }
+    }
+}
+
+struct CountingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Writer that keeps the first `limit` bytes and refuses anything past that
+struct HeadWriter {
+    buf: Vec<u8>,
+    limit: usize,
+}
+
+impl Write for HeadWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let remaining = self.limit - self.buf.len();
+        if remaining == 0 {
+            return Err(std::io::Error::other("preview limit reached"));
+        }
+        let n = remaining.min(buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Decompress at most `limit` bytes from the start of a compressed file
+///
+/// Returns the bytes and whether the content was cut off at `limit`.
+pub fn read_decompressed_head(
+    path: &Path,
+    format: CompressionFormat,
+    limit: usize,
+) -> Result<(Vec<u8>, bool), String> {
+    let mut head = HeadWriter {
+        buf: Vec::new(),
+        limit,
+    };
+    match decompress_to(path, format, &mut head) {
+        Ok(_) => Ok((head.buf, false)),
+        Err(_) if head.buf.len() == limit => Ok((head.buf, true)),
+        Err(e) => Err(format!("Failed to decompress {} file: {e}", format.name())),
+    }
+}
+
+/// Decompress a single compressed file next to itself, dropping the extension
+///
+/// Fails without touching anything if the target already exists.
+pub fn decompress_in_place(path: &Path) -> Result<PathBuf, String> {
+    let format = CompressionFormat::from_path(path).ok_or_else(|| {
+        format!(
+            "Not a compressed file: {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        )
+    })?;
+    let target = path.with_extension("");
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)
+        .map_err(|e| format!("Failed to create temporary file: {e}"))?;
+    decompress_to(path, format, tmp.as_file_mut())
+        .map_err(|e| format!("Failed to decompress {}: {e}", path.display()))?;
+    tmp.persist_noclobber(&target)
+        .map_err(|e| format!("Failed to write {}: {e}", target.display()))?;
+    Ok(target)
+}
+
+/// Decompress every member of an archive and verify its checksum
+///
+/// `progress` is called before each member with `(current, total, name)` and
+/// returns whether to go on, the members tested so far are reported when it
+/// stops. `total` is 0 when the member count isn't known upfront (tar streams).
+/// Encrypted zip members are decrypted with `password` when given.
+/// Returns an error only when the archive itself can't be opened.
+pub fn test_archive(
+    path: &Path,
+    password: Option<&str>,
+    mut progress: impl FnMut(usize, usize, &str) -> bool,
+) -> Result<ArchiveTestReport, String> {
+    match ArchiveFormat::from_path(path) {
+        Some(ArchiveFormat::Zip) => test_zip(path, password, &mut progress),
+        Some(ArchiveFormat::Tar) => test_tar(path, &mut progress),
+        Some(ArchiveFormat::SevenZ) => test_7z(path, &mut progress),
+        None => Err(format!(
+            "Unsupported archive format: {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        )),
+    }
+}
+
+fn test_zip(
+    path: &Path,
+    password: Option<&str>,
+    progress: &mut impl FnMut(usize, usize, &str) -> bool,
+) -> Result<ArchiveTestReport, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open zip file: {e}"))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {e}"))?;
+
+    let total = archive.len();
+    let mut report = ArchiveTestReport::default();
+    for i in 0..total {
+        let member = match password {
+            Some(password) => archive.by_index_decrypt(i, password.as_bytes()),
+            None => archive.by_index(i),
+        };
+        let mut member = match member {
+            Ok(member) => member,
+            Err(e) => {
+                let name = archive
+                    .name_for_index(i)
+                    .map_or_else(|| format!("#{i}"), str::to_string);
+                report.tested += 1;
+                report.corrupt.push(CorruptMember {
+                    name,
+                    error: MemberReadError::from(e).to_string(),
+                });
+                continue;
+            }
+        };
+        let name = member.name().to_string();
+        if !progress(i + 1, total, &name) {
+            break;
+        }
+        report.tested += 1;
+
+        if member.is_dir() {
+            continue;
+        }
+        // The zip reader validates the CRC32 once the member is fully read
+        if let Err(e) = std::io::copy(&mut member, &mut std::io::sink()) {
+            report.corrupt.push(CorruptMember {
+                name,
+                error: e.to_string(),
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+fn test_tar(
+    path: &Path,
+    progress: &mut impl FnMut(usize, usize, &str) -> bool,
+) -> Result<ArchiveTestReport, String> {
+    let mut archive = open_tar_archive(path)?;
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read tar entries: {e}"))?;
+
+    let mut report = ArchiveTestReport::default();
+    let mut last_name = String::new();
+    for entry_result in entries {
+        // Header checksums and decompression errors surface while iterating.
+        // The stream can't be resynced after that, so stop at the first one.
+        let mut entry = match entry_result {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.corrupt.push(CorruptMember {
+                    name: format!("after {last_name}"),
+                    error: e.to_string(),
+                });
+                break;
+            }
+        };
+        let name = entry
+            .path()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !progress(report.tested + 1, 0, &name) {
+            break;
+        }
+        report.tested += 1;
+
+        if let Err(e) = std::io::copy(&mut entry, &mut std::io::sink()) {
+            report.corrupt.push(CorruptMember {
+                name,
+                error: e.to_string(),
+            });
+            break;
+        }
+        last_name = name;
+    }
+
+    Ok(report)
+}
+
+fn test_7z(
+    path: &Path,
+    progress: &mut impl FnMut(usize, usize, &str) -> bool,
+) -> Result<ArchiveTestReport, String> {
+    let mut archive = sevenz_rust::SevenZReader::open(path, sevenz_rust::Password::empty())
+        .map_err(|e| format!("Failed to read 7z archive: {e}"))?;
+
+    let total = archive.archive().files.len();
+    let mut report = ArchiveTestReport::default();
+    let mut stopped = false;
+    let mut last_name = String::new();
+    // Returning false only skips the rest of the current block
+    let result = archive.for_each_entries(|entry, member| {
+        if stopped || !progress(report.tested + 1, total, entry.name()) {
+            stopped = true;
+            return Ok(false);
+        }
+        report.tested += 1;
+
+        // The LZMA decoder panics on some corrupt streams
+        let copied = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            std::io::copy(member, &mut std::io::sink())
+        }));
+        let error = match copied {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some("Corrupt compressed data".to_string()),
+        };
+        if let Some(error) = error {
+            report.corrupt.push(CorruptMember {
+                name: entry.name().to_string(),
+                error,
+            });
+            // Members of a solid block are decoded in sequence, the rest of
+            // the block can't be read after an error
+            return Ok(false);
+        }
+        last_name = entry.name().to_string();
+        Ok(true)
+    });
+    if let Err(e) = result {
+        if report.tested == 0 {
+            return Err(format!("Failed to read 7z archive: {e}"));
+        }
+        report.corrupt.push(CorruptMember {
+            name: format!("after {last_name}"),
+            error: e.to_string(),
+        });
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_zip(path: &Path, files: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, content) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_zip_archive_ok() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("ok.zip");
+        write_zip(&path, &[("a.txt", b"aaaa"), ("b.txt", b"bbbb")]);
+
+        let mut seen = Vec::new();
+        let report = test_archive(&path, None, |current, total, name| {
+            seen.push((current, total, name.to_string()));
+            true
+        })
+        .unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.tested, 2);
+        assert_eq!(
+            seen,
+            vec![(1, 2, "a.txt".to_string()), (2, 2, "b.txt".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_zip_archive_corrupt_member() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("bad.zip");
+        write_zip(
+            &path,
+            &[("a.txt", b"good data"), ("b.txt", b"will be flipped")],
+        );
+
+        // Flip a byte of the stored content without touching the recorded CRC
+        let mut bytes = std::fs::read(&path).unwrap();
+        let pos = bytes
+            .windows(b"will be flipped".len())
+            .position(|w| w == b"will be flipped")
+            .unwrap();
+        bytes[pos] ^= 0xff;
+        std::fs::write(&path, bytes).unwrap();
+
+        let report = test_archive(&path, None, |_, _, _| true).unwrap();
+        assert_eq!(report.tested, 2);
+        assert_eq!(report.corrupt.len(), 1);
+        assert_eq!(report.corrupt[0].name, "b.txt");
+    }
+
+    #[test]
+    fn test_encrypted_zip_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("secret.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file(
+            "secret.txt",
+            SimpleFileOptions::default().with_aes_encryption(zip::AesMode::Aes256, "hunter2"),
+        )
+        .unwrap();
+        zip.write_all(b"top secret").unwrap();
+        zip.finish().unwrap();
+
+        assert!(zip_is_encrypted(File::open(&path).unwrap()).unwrap());
+        assert!(
+            test_archive(&path, Some("hunter2"), |_, _, _| true)
+                .unwrap()
+                .is_ok()
+        );
+
+        let report = test_archive(&path, None, |_, _, _| true).unwrap();
+        assert_eq!(report.corrupt[0].name, "secret.txt");
+        assert_eq!(report.corrupt[0].error, "Password required");
+
+        let report = test_archive(&path, Some("wrong"), |_, _, _| true).unwrap();
+        assert_eq!(report.corrupt[0].error, "Incorrect password");
+    }
+
+    #[test]
+    fn test_session_password_cache() {
+        let path = Path::new("/tmp/kiorg_test_session_password.zip");
+        assert_eq!(cached_password(path), None);
+        cache_password(path, "hunter2".to_string());
+        assert_eq!(cached_password(path).as_deref(), Some("hunter2"));
+        forget_password(path);
+        assert_eq!(cached_password(path), None);
+    }
+
+    #[test]
+    fn test_tar_gz_archive_truncated() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&path).unwrap(),
+            flate2::Compression::fast(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        let content = vec![7u8; 64 * 1024];
+        let mut header = tar::Header::new_gnu();
+        header.set_path("data.bin").unwrap();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, content.as_slice()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let report = test_archive(&path, None, |_, _, _| true).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.tested, 1);
+
+        // Chop off the end of the compressed stream
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+        let report = test_archive(&path, None, |_, _, _| true).unwrap();
+        assert!(!report.is_ok());
+    }
+
+    fn tar_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_path(name).unwrap();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_tar_xz_and_zstd_archives() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let tar = tar_bytes(&[("a.txt", b"aaaa"), ("b.txt", b"bbbb")]);
+
+        let mut xz = Vec::new();
+        lzma_rs::xz_compress(&mut tar.as_slice(), &mut xz).unwrap();
+        let zst = zstd::encode_all(tar.as_slice(), 0).unwrap();
+
+        for (name, bytes) in [
+            ("test.tar.xz", &xz),
+            ("test.txz", &xz),
+            ("test.tar.zst", &zst),
+            ("test.tar.zstd", &zst),
+        ] {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, bytes).unwrap();
+            assert_eq!(ArchiveFormat::from_path(&path), Some(ArchiveFormat::Tar));
+
+            let mut archive = open_tar_archive(&path).unwrap();
+            let names: Vec<String> = archive
+                .entries()
+                .unwrap()
+                .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+                .collect();
+            assert_eq!(names, ["a.txt", "b.txt"], "{name}");
+
+            let report = test_archive(&path, None, |_, _, _| true).unwrap();
+            assert!(report.is_ok(), "{name}");
+            assert_eq!(report.tested, 2, "{name}");
+        }
+
+        // Corrupt xz data surfaces as an error instead of an empty archive
+        let path = temp_dir.path().join("broken.tar.xz");
+        std::fs::write(&path, &xz[..xz.len() / 2]).unwrap();
+        let mut archive = open_tar_archive(&path).unwrap();
+        assert!(archive.entries().unwrap().any(|e| e.is_err()));
+    }
+
+    #[test]
+    fn test_compression_format_from_path() {
+        assert_eq!(
+            CompressionFormat::from_path(Path::new("log.gz")),
+            Some(CompressionFormat::Gzip)
+        );
+        assert_eq!(
+            CompressionFormat::from_path(Path::new("dump.sql.zst")),
+            Some(CompressionFormat::Zstd)
+        );
+        assert_eq!(CompressionFormat::from_path(Path::new("a.tar.gz")), None);
+        assert_eq!(CompressionFormat::from_path(Path::new("a.tar.zst")), None);
+        assert_eq!(CompressionFormat::from_path(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn test_read_decompressed_head_gzip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("notes.txt.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(b"hello compressed world").unwrap();
+        encoder.finish().unwrap();
+
+        let (bytes, truncated) =
+            read_decompressed_head(&path, CompressionFormat::Gzip, 1024).unwrap();
+        assert_eq!(bytes, b"hello compressed world");
+        assert!(!truncated);
+
+        let (bytes, truncated) = read_decompressed_head(&path, CompressionFormat::Gzip, 5).unwrap();
+        assert_eq!(bytes, b"hello");
+        assert!(truncated);
+    }
+
+    #[test]
+    fn test_decompress_in_place_zstd() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("data.json.zst");
+        let compressed = zstd::encode_all(&b"{\"a\": 1}"[..], 0).unwrap();
+        std::fs::write(&path, compressed).unwrap();
+
+        let target = decompress_in_place(&path).unwrap();
+        assert_eq!(target, temp_dir.path().join("data.json"));
+        assert_eq!(std::fs::read(&target).unwrap(), b"{\"a\": 1}");
+
+        // Refuse to overwrite the existing output
+        assert!(decompress_in_place(&path).is_err());
+    }
+
+    #[test]
+    fn test_7z_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.7z");
+        let mut writer = sevenz_rust::SevenZWriter::create(&path).unwrap();
+        for (name, content) in [("a.txt", [b'a'; 64]), ("b.txt", [b'b'; 64])] {
+            writer
+                .push_archive_entry(
+                    sevenz_rust::SevenZArchiveEntry::from_path(&path, name.to_string()),
+                    Some(content.as_slice()),
+                )
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        let mut seen = Vec::new();
+        let report = test_archive(&path, None, |current, total, name| {
+            seen.push((current, total, name.to_string()));
+            true
+        })
+        .unwrap();
+        assert!(report.is_ok());
+        assert_eq!(
+            seen,
+            vec![(1, 2, "a.txt".to_string()), (2, 2, "b.txt".to_string())]
+        );
+
+        // Stopping early reports the members tested so far
+        let report = test_archive(&path, None, |current, _, _| current < 2).unwrap();
+        assert_eq!(report.tested, 1);
+
+        // Damage the packed data following the 32 byte signature header
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[34] ^= 0x55;
+        bytes[40] ^= 0xff;
+        std::fs::write(&path, bytes).unwrap();
+        let report = test_archive(&path, None, |_, _, _| true).unwrap();
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_archive() {
+        assert!(test_archive(Path::new("file.rar"), None, |_, _, _| true).is_err());
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("a.tar.gz")),
+            Some(ArchiveFormat::Tar)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("a.jar")),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("a.7z")),
+            Some(ArchiveFormat::SevenZ)
+        );
+    }
+}
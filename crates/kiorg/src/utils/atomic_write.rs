@@ -0,0 +1,76 @@
+//! Crash-safe writes of config and state files
+//!
+//! The content goes to a temporary file in the same directory, which is then
+//! renamed over the target. A crash or power loss while saving leaves either
+//! the old or the new file, never a truncated one.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Replace the content of `path` atomically, like [`fs::write`]
+///
+/// A symlinked target is written through, and the permissions of an existing
+/// file are kept.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    // Renaming over a symlink would replace the link, e.g. to a dotfiles repo
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let mut file = tempfile::Builder::new()
+        .prefix(".kiorg-")
+        .suffix(".tmp")
+        .tempfile_in(dir)?;
+    file.write_all(contents.as_ref())?;
+    if let Ok(metadata) = fs::metadata(&path) {
+        file.as_file().set_permissions(metadata.permissions())?;
+    }
+    file.as_file().sync_all()?;
+    file.persist(&path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_replaces_content() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("state.json");
+
+        write(&path, "first").unwrap();
+        write(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+
+        // No temporary files are left behind
+        let names: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["state.json"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_through_symlink() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("config.toml");
+        let link = temp_dir.path().join("link.toml");
+        fs::write(&target, "old").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write(&link, "new").unwrap();
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+    }
+}
@@ -0,0 +1,423 @@
+//! Checksum files: `.sha256`/`.md5` sidecars and `SHA256SUMS` style lists
+//!
+//! Files are written in the coreutils format, `<digest>  <name>`, so they can
+//! be checked with `sha256sum -c` too. Both the coreutils and the BSD tagged
+//! format, `SHA256 (<name>) = <digest>`, are read.
+
+use crate::utils::hashing::{self, HashAlgorithm, HashProgress};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+/// Algorithms sidecar files can be generated with
+pub const SIDECAR_ALGORITHMS: [HashAlgorithm; 2] = [HashAlgorithm::Sha256, HashAlgorithm::Md5];
+
+/// Well known names of checksum lists covering a whole directory
+const CHECKSUM_LIST_NAMES: [&str; 5] = ["SHA256SUMS", "MD5SUMS", "SHA1SUMS", "B3SUMS", "CHECKSUMS"];
+
+/// Extension of the checksum files of `algorithm`, without the leading dot
+pub const fn extension(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Blake3 => "b3",
+        HashAlgorithm::Md5 => "md5",
+        HashAlgorithm::Sha1 => "sha1",
+        HashAlgorithm::Sha256 => "sha256",
+    }
+}
+
+/// `file.txt` gets `file.txt.sha256`
+pub fn sidecar_path(path: &Path, algorithm: HashAlgorithm) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(extension(algorithm));
+    path.with_file_name(name)
+}
+
+/// Whether `path` looks like a checksum file by its name
+pub fn is_checksum_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    if CHECKSUM_LIST_NAMES
+        .iter()
+        .any(|list| name.eq_ignore_ascii_case(list))
+    {
+        return true;
+    }
+    algorithm_from_name(name).is_some()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                HashAlgorithm::ALL
+                    .iter()
+                    .any(|a| ext.eq_ignore_ascii_case(extension(*a)))
+                    || ext.eq_ignore_ascii_case("txt")
+                    || ext.to_ascii_lowercase().ends_with("sum")
+            })
+}
+
+/// The checksum file to verify a directory against, lists win over sidecars
+pub fn find_checksum_file(dir: &Path) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_checksum_file(path))
+        .collect();
+    candidates.sort_by_key(|path| {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let is_list = CHECKSUM_LIST_NAMES
+            .iter()
+            .any(|list| name.eq_ignore_ascii_case(list));
+        (!is_list, path.clone())
+    });
+    candidates.into_iter().next()
+}
+
+/// Algorithm hinted by a file name, e.g. `SHA256SUMS` or `file.md5`
+fn algorithm_from_name(name: &str) -> Option<HashAlgorithm> {
+    let name = name.to_ascii_lowercase();
+    if name.contains("sha256") {
+        Some(HashAlgorithm::Sha256)
+    } else if name.contains("sha1") {
+        Some(HashAlgorithm::Sha1)
+    } else if name.contains("md5") {
+        Some(HashAlgorithm::Md5)
+    } else if name.contains("b3") || name.contains("blake3") {
+        Some(HashAlgorithm::Blake3)
+    } else {
+        None
+    }
+}
+
+/// Algorithm of a BSD style tag, e.g. `SHA256`
+fn algorithm_from_tag(tag: &str) -> Option<HashAlgorithm> {
+    match tag.to_ascii_uppercase().replace('-', "").as_str() {
+        "SHA256" => Some(HashAlgorithm::Sha256),
+        "SHA1" => Some(HashAlgorithm::Sha1),
+        "MD5" => Some(HashAlgorithm::Md5),
+        "BLAKE3" => Some(HashAlgorithm::Blake3),
+        _ => None,
+    }
+}
+
+/// SHA-256 and BLAKE3 digests have the same length, the file name decides
+fn algorithm_from_digest(digest: &str, hint: Option<HashAlgorithm>) -> Option<HashAlgorithm> {
+    match (digest.len(), hint) {
+        (32, _) => Some(HashAlgorithm::Md5),
+        (40, _) => Some(HashAlgorithm::Sha1),
+        (64, Some(HashAlgorithm::Blake3)) => Some(HashAlgorithm::Blake3),
+        (64, _) => Some(HashAlgorithm::Sha256),
+        _ => None,
+    }
+}
+
+/// One line of a checksum file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumEntry {
+    pub algorithm: HashAlgorithm,
+    /// Lowercase hex digest
+    pub digest: String,
+    /// Path as written in the file, relative to the checksum file
+    pub name: String,
+}
+
+/// A checksum file line for `name`
+pub fn format_line(digest: &str, name: &str) -> String {
+    // coreutils escapes names with a backslash or newline and marks the line
+    if name.contains(['\\', '\n']) {
+        let escaped = name.replace('\\', "\\\\").replace('\n', "\\n");
+        format!("\\{digest}  {escaped}\n")
+    } else {
+        format!("{digest}  {name}\n")
+    }
+}
+
+/// Parse the content of a checksum file named `file_name`
+///
+/// Blank lines, comments and lines in an unknown format are skipped.
+pub fn parse(file_name: &str, content: &str) -> Vec<ChecksumEntry> {
+    let hint = algorithm_from_name(file_name);
+    content
+        .lines()
+        .filter_map(|line| parse_line(line.trim_end_matches('\r'), hint))
+        .collect()
+}
+
+fn parse_line(line: &str, hint: Option<HashAlgorithm>) -> Option<ChecksumEntry> {
+    if line.trim().is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+
+    // BSD style: `SHA256 (name) = digest`
+    if let Some((tag, rest)) = line.split_once(" (")
+        && let Some((name, digest)) = rest.rsplit_once(") = ")
+        && let Some(algorithm) = algorithm_from_tag(tag)
+        && is_hex(digest)
+    {
+        return Some(ChecksumEntry {
+            algorithm,
+            digest: digest.to_ascii_lowercase(),
+            name: unescape(name, escaped),
+        });
+    }
+
+    // coreutils style: `digest  name`, or `digest *name` for binary mode
+    let (digest, rest) = line.split_once(' ')?;
+    let name = rest.strip_prefix([' ', '*']).unwrap_or(rest);
+    if !is_hex(digest) || name.is_empty() {
+        return None;
+    }
+    Some(ChecksumEntry {
+        algorithm: algorithm_from_digest(digest, hint)?,
+        digest: digest.to_ascii_lowercase(),
+        name: unescape(name, escaped),
+    })
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn unescape(name: &str, escaped: bool) -> String {
+    if !escaped {
+        return name.to_string();
+    }
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Write a sidecar file next to each of `paths`
+///
+/// Returns the sidecars written and the errors, or `None` if cancelled.
+pub fn generate_sidecars(
+    paths: &[PathBuf],
+    algorithm: HashAlgorithm,
+    cancel: &AtomicBool,
+    on_progress: impl Fn(HashProgress) + Sync,
+) -> Option<(Vec<PathBuf>, Vec<String>)> {
+    let results = hashing::hash_files(paths, &[algorithm], cancel, on_progress)?;
+    let mut written = Vec::new();
+    let mut errors = Vec::new();
+    for (path, result) in paths.iter().zip(results) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let digest = match result {
+            Ok(digests) => digests.into_iter().next().map(|(_, digest)| digest),
+            Err(e) => {
+                errors.push(format!("{name}: {e}"));
+                continue;
+            }
+        };
+        let sidecar = sidecar_path(path, algorithm);
+        let content = format_line(&digest.unwrap_or_default(), &name);
+        match crate::utils::atomic_write::write(&sidecar, content) {
+            Ok(()) => written.push(sidecar),
+            Err(e) => errors.push(format!("{name}: Failed to write checksum file: {e}")),
+        }
+    }
+    Some((written, errors))
+}
+
+/// Why a file failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyFailure {
+    Mismatch,
+    Missing,
+    Error(String),
+}
+
+impl std::fmt::Display for VerifyFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mismatch => write!(f, "checksum mismatch"),
+            Self::Missing => write!(f, "missing"),
+            Self::Error(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Outcome of verifying a checksum file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Number of files listed in the checksum file
+    pub checked: usize,
+    /// Failed files, by name as written in the checksum file
+    pub failures: Vec<(String, VerifyFailure)>,
+}
+
+impl VerifyReport {
+    pub const fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Check the files listed in `checksum_file` against their digests
+///
+/// Names are resolved against the directory of the checksum file. Returns
+/// `None` if cancelled.
+pub fn verify(
+    checksum_file: &Path,
+    cancel: &AtomicBool,
+    on_progress: impl Fn(HashProgress) + Sync,
+) -> Option<Result<VerifyReport, String>> {
+    let content = match std::fs::read(checksum_file) {
+        Ok(content) => String::from_utf8_lossy(&content).into_owned(),
+        Err(e) => return Some(Err(format!("Failed to read checksum file: {e}"))),
+    };
+    let file_name = checksum_file
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let entries = parse(&file_name, &content);
+    if entries.is_empty() {
+        return Some(Err(format!("No checksums found in {file_name}")));
+    }
+
+    let dir = checksum_file.parent().unwrap_or_else(|| Path::new("."));
+    let mut report = VerifyReport {
+        checked: entries.len(),
+        failures: Vec::new(),
+    };
+    let mut present = Vec::new();
+    for entry in &entries {
+        let path = dir.join(&entry.name);
+        if path.is_file() {
+            present.push((entry, path));
+        } else {
+            report
+                .failures
+                .push((entry.name.clone(), VerifyFailure::Missing));
+        }
+    }
+
+    let mut algorithms: Vec<HashAlgorithm> = Vec::new();
+    for (entry, _) in &present {
+        if !algorithms.contains(&entry.algorithm) {
+            algorithms.push(entry.algorithm);
+        }
+    }
+    let paths: Vec<PathBuf> = present.iter().map(|(_, path)| path.clone()).collect();
+    let results = hashing::hash_files(&paths, &algorithms, cancel, on_progress)?;
+
+    for ((entry, _), result) in present.iter().zip(results) {
+        let failure = match result {
+            Ok(digests) => digests
+                .iter()
+                .find(|(algorithm, _)| *algorithm == entry.algorithm)
+                .filter(|(_, digest)| *digest == entry.digest)
+                .is_none()
+                .then_some(VerifyFailure::Mismatch),
+            Err(e) => Some(VerifyFailure::Error(e)),
+        };
+        if let Some(failure) = failure {
+            report.failures.push((entry.name.clone(), failure));
+        }
+    }
+    report.failures.sort_by(|a, b| a.0.cmp(&b.0));
+    Some(Ok(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const ABC_MD5: &str = "900150983cd24fb0d6963f7d28e17f72";
+
+    #[test]
+    fn test_parse() {
+        let content = format!(
+            "# comment\n{ABC_SHA256}  a.txt\n{ABC_SHA256} *dir/b.bin\r\n\nnot a checksum\n\\{ABC_SHA256}  new\\nline\nMD5 (c d.txt) = {}\n",
+            ABC_MD5.to_uppercase()
+        );
+        let entries = parse("SHA256SUMS", &content);
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a.txt", "dir/b.bin", "new\nline", "c d.txt"]);
+        assert_eq!(entries[0].algorithm, HashAlgorithm::Sha256);
+        assert_eq!(entries[3].algorithm, HashAlgorithm::Md5);
+        assert_eq!(entries[3].digest, ABC_MD5);
+
+        // Same length as SHA-256, told apart by the file name
+        let entries = parse("files.b3", &format!("{ABC_SHA256}  a.txt\n"));
+        assert_eq!(entries[0].algorithm, HashAlgorithm::Blake3);
+
+        assert_eq!(
+            parse("x.sha256", &format_line(ABC_SHA256, "new\nline"))[0].name,
+            "new\nline"
+        );
+    }
+
+    #[test]
+    fn test_is_checksum_file() {
+        assert!(is_checksum_file(Path::new("/a/SHA256SUMS")));
+        assert!(is_checksum_file(Path::new("/a/file.txt.sha256")));
+        assert!(is_checksum_file(Path::new("/a/release.md5")));
+        assert!(is_checksum_file(Path::new("/a/sha256sums.txt")));
+        assert!(!is_checksum_file(Path::new("/a/file.txt")));
+        assert!(!is_checksum_file(Path::new("/a/md5.rs")));
+    }
+
+    #[test]
+    fn test_generate_and_verify() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let a = temp_dir.path().join("a.txt");
+        let b = temp_dir.path().join("b.txt");
+        std::fs::write(&a, "abc").unwrap();
+        std::fs::write(&b, "abc").unwrap();
+        let cancel = AtomicBool::new(false);
+
+        let (written, errors) = generate_sidecars(
+            &[a.clone(), temp_dir.path().join("missing")],
+            HashAlgorithm::Md5,
+            &cancel,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(written, [temp_dir.path().join("a.txt.md5")]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&written[0]).unwrap(),
+            format!("{ABC_MD5}  a.txt\n")
+        );
+        assert!(
+            verify(&written[0], &cancel, |_| {})
+                .unwrap()
+                .unwrap()
+                .is_ok()
+        );
+
+        let list = temp_dir.path().join("SHA256SUMS");
+        std::fs::write(
+            &list,
+            format!("{ABC_SHA256}  a.txt\n{ABC_SHA256}  b.txt\n{ABC_SHA256}  gone.txt\n"),
+        )
+        .unwrap();
+        assert_eq!(find_checksum_file(temp_dir.path()), Some(list.clone()));
+        std::fs::write(&b, "changed").unwrap();
+        let report = verify(&list, &cancel, |_| {}).unwrap().unwrap();
+        assert_eq!(report.checked, 3);
+        assert_eq!(
+            report.failures,
+            [
+                ("b.txt".to_string(), VerifyFailure::Mismatch),
+                ("gone.txt".to_string(), VerifyFailure::Missing),
+            ]
+        );
+    }
+}
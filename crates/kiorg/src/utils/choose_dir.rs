@@ -0,0 +1,58 @@
+//! Handing the directory kiorg was left in to the shell, see `kiorg --choose-dir`
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Where the directory is written on quit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChooseDirOutput {
+    Stdout,
+    File(PathBuf),
+}
+
+impl ChooseDirOutput {
+    /// Output for the value of `--choose-dir`, stdout when it's missing or `-`
+    #[must_use]
+    pub fn from_arg(arg: Option<PathBuf>) -> Self {
+        match arg {
+            Some(path) if path.as_os_str() != "-" => Self::File(path),
+            _ => Self::Stdout,
+        }
+    }
+
+    /// Write `dir` followed by a newline
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        let line = format!("{}\n", dir.display());
+        match self {
+            Self::Stdout => {
+                let mut stdout = io::stdout();
+                stdout.write_all(line.as_bytes())?;
+                stdout.flush()
+            }
+            Self::File(path) => std::fs::write(path, line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_to_file() {
+        assert_eq!(ChooseDirOutput::from_arg(None), ChooseDirOutput::Stdout);
+        assert_eq!(
+            ChooseDirOutput::from_arg(Some(PathBuf::from("-"))),
+            ChooseDirOutput::Stdout
+        );
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("cwd");
+        let output = ChooseDirOutput::from_arg(Some(file.clone()));
+        output.write(Path::new("/home/user/projects")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "/home/user/projects\n"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Read the text or image in the system clipboard, to paste it as a new file
+//!
+//! Linux asks `wl-paste` on Wayland and `xclip` on X11. macOS reads images
+//! through `osascript` and text with `pbpaste`. Windows uses PowerShell.
+//! Images of any format are converted to PNG.
+
+use std::process::{Command, Output};
+
+/// What the clipboard holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardContent {
+    Text(String),
+    /// PNG encoded image
+    Image(Vec<u8>),
+}
+
+impl ClipboardContent {
+    /// File name suggested for the content
+    #[must_use]
+    pub const fn default_name(&self) -> &'static str {
+        match self {
+            Self::Text(_) => "clipboard.txt",
+            Self::Image(_) => "clipboard.png",
+        }
+    }
+
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Image(png) => png,
+        }
+    }
+}
+
+/// Text or image in the clipboard, images first
+pub fn read() -> Result<ClipboardContent, String> {
+    match platform::read_image()? {
+        Some(image) => Ok(ClipboardContent::Image(to_png(&image)?)),
+        None => match platform::read_text()? {
+            Some(text) if !text.is_empty() => Ok(ClipboardContent::Text(text)),
+            _ => Err("The clipboard holds no text or image".to_string()),
+        },
+    }
+}
+
+/// Encode an image of any supported format as PNG
+fn to_png(image: &[u8]) -> Result<Vec<u8>, String> {
+    if image.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Ok(image.to_vec());
+    }
+    let image = image::load_from_memory(image)
+        .map_err(|e| format!("Failed to read the clipboard image: {e}"))?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode the clipboard image: {e}"))?;
+    Ok(png.into_inner())
+}
+
+/// Run `command`, mapping a missing program to a hint on what to install
+fn run(mut command: Command) -> Result<Output, String> {
+    let program = command.get_program().to_string_lossy().to_string();
+    command.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            format!("{program} not found, it's needed to read the clipboard")
+        } else {
+            format!("Failed to run {program}: {e}")
+        }
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::run;
+    use std::process::Command;
+
+    fn wayland() -> bool {
+        std::env::var_os("WAYLAND_DISPLAY").is_some()
+    }
+
+    /// Command printing the clipboard content of `mime_type`, or its types
+    /// when `None`
+    fn paste(mime_type: Option<&str>) -> Command {
+        if wayland() {
+            let mut command = Command::new("wl-paste");
+            match mime_type {
+                Some(mime_type) => command.args(["--no-newline", "--type", mime_type]),
+                None => command.arg("--list-types"),
+            };
+            command
+        } else {
+            let mut command = Command::new("xclip");
+            command.args(["-selection", "clipboard", "-o", "-t"]);
+            command.arg(mime_type.unwrap_or("TARGETS"));
+            command
+        }
+    }
+
+    pub fn read_image() -> Result<Option<Vec<u8>>, String> {
+        let types = run(paste(None))?;
+        if !types.status.success() {
+            // An empty clipboard has no types
+            return Ok(None);
+        }
+        let types = String::from_utf8_lossy(&types.stdout);
+        let types: Vec<&str> = types.lines().map(str::trim).collect();
+        let Some(image_type) = types
+            .iter()
+            .find(|t| **t == "image/png")
+            .or_else(|| types.iter().find(|t| t.starts_with("image/")))
+        else {
+            return Ok(None);
+        };
+        let output = run(paste(Some(image_type)))?;
+        Ok((output.status.success() && !output.stdout.is_empty()).then_some(output.stdout))
+    }
+
+    pub fn read_text() -> Result<Option<String>, String> {
+        let mime_type = if wayland() {
+            "text/plain"
+        } else {
+            "UTF8_STRING"
+        };
+        let output = run(paste(Some(mime_type)))?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::run;
+    use std::process::Command;
+
+    pub fn read_image() -> Result<Option<Vec<u8>>, String> {
+        let mut command = Command::new("osascript");
+        command.args(["-e", "the clipboard as «class PNGf»"]);
+        let output = run(command)?;
+        if !output.status.success() {
+            // No image in the clipboard
+            return Ok(None);
+        }
+        // Printed as «data PNGf89504E47…»
+        let printed = String::from_utf8_lossy(&output.stdout);
+        let hex = printed
+            .trim()
+            .trim_start_matches("«data PNGf")
+            .trim_end_matches('»');
+        Ok(decode_hex(hex))
+    }
+
+    fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+        if hex.len() % 2 != 0 {
+            return None;
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect()
+    }
+
+    pub fn read_text() -> Result<Option<String>, String> {
+        let output = run(Command::new("pbpaste"))?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::run;
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    fn powershell(script: &str) -> Command {
+        let mut command = Command::new("powershell");
+        command
+            .args(["-NoProfile", "-NonInteractive", "-Command", script])
+            .creation_flags(CREATE_NO_WINDOW);
+        command
+    }
+
+    pub fn read_image() -> Result<Option<Vec<u8>>, String> {
+        let file = tempfile::Builder::new()
+            .suffix(".png")
+            .tempfile()
+            .map_err(|e| format!("Failed to create a temporary file: {e}"))?;
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $image = [System.Windows.Forms.Clipboard]::GetImage(); \
+             if ($image) {{ $image.Save('{}', [System.Drawing.Imaging.ImageFormat]::Png) }}",
+            file.path().display().to_string().replace('\'', "''")
+        );
+        let output = run(powershell(&script))?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let png = std::fs::read(file.path()).unwrap_or_default();
+        Ok((!png.is_empty()).then_some(png))
+    }
+
+    pub fn read_text() -> Result<Option<String>, String> {
+        // Output as UTF-8 rather than the console code page
+        let output = run(powershell(
+            "[Console]::OutputEncoding = [System.Text.Encoding]::UTF8; Get-Clipboard -Raw",
+        ))?;
+        Ok(output.status.success().then(|| {
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end_matches(['\r', '\n'])
+                .to_string()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_png() {
+        let image = image::RgbImage::from_pixel(2, 2, image::Rgb([255, 0, 0]));
+        let mut bmp = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut bmp, image::ImageFormat::Bmp).unwrap();
+        let png = to_png(bmp.get_ref()).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        assert_eq!(image::load_from_memory(&png).unwrap().width(), 2);
+        // PNG data is kept as it is
+        assert_eq!(to_png(&png).unwrap(), png);
+        assert!(to_png(b"not an image").is_err());
+    }
+}
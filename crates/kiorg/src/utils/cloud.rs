@@ -0,0 +1,487 @@
+//! Detect locally mounted cloud storage folders
+//!
+//! Providers are found by their well-known locations: Dropbox writes its
+//! folders to `info.json`, macOS File Provider extensions mount under
+//! `~/Library/CloudStorage`, OneDrive exports its folders in environment
+//! variables on Windows and GNOME mounts online accounts through gvfs.
+//!
+//! Files inside them can be placeholders whose content is only downloaded
+//! when they are read, see [`is_placeholder`].
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudProvider {
+    Dropbox,
+    OneDrive,
+    GoogleDrive,
+    ICloud,
+    /// Any other File Provider extension, e.g. Box
+    Other,
+}
+
+impl CloudProvider {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dropbox => "Dropbox",
+            Self::OneDrive => "OneDrive",
+            Self::GoogleDrive => "Google Drive",
+            Self::ICloud => "iCloud Drive",
+            Self::Other => "Cloud",
+        }
+    }
+
+    #[must_use]
+    pub const fn icon(self) -> &'static str {
+        match self {
+            Self::Dropbox => "📦",
+            Self::OneDrive => "☁",
+            Self::GoogleDrive => "🔺",
+            Self::ICloud => "🍏",
+            Self::Other => "🌐",
+        }
+    }
+}
+
+/// A cloud folder mounted on this machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudFolder {
+    pub provider: CloudProvider,
+    /// Display name, e.g. "OneDrive - Contoso"
+    pub label: String,
+    pub path: PathBuf,
+    /// Files may only be downloaded when they are opened
+    pub online_only: bool,
+}
+
+/// Cloud folders mounted for the current user
+#[must_use]
+pub fn detect() -> Vec<CloudFolder> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let mut folders = detect_in(&home, |name| std::env::var_os(name));
+    #[cfg(target_os = "windows")]
+    folders.extend(google_drive_letters());
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    if let Some(runtime_dir) = dirs::runtime_dir() {
+        folders.extend(gvfs_mounts(&runtime_dir.join("gvfs")));
+    }
+    dedup(folders)
+}
+
+/// Folders found under `home` and in the environment read by `env`
+fn detect_in(home: &Path, env: impl Fn(&str) -> Option<OsString>) -> Vec<CloudFolder> {
+    let mut folders = Vec::new();
+
+    // File Provider mounts, the only location of current macOS clients
+    let cloud_storage = home.join("Library/CloudStorage");
+    if let Ok(entries) = std::fs::read_dir(&cloud_storage) {
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        paths.sort();
+        folders.extend(paths.into_iter().map(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            file_provider_folder(&name, path)
+        }));
+    }
+
+    let icloud = home.join("Library/Mobile Documents/com~apple~CloudDocs");
+    if icloud.is_dir() {
+        folders.push(CloudFolder {
+            provider: CloudProvider::ICloud,
+            label: CloudProvider::ICloud.name().to_string(),
+            path: icloud,
+            online_only: true,
+        });
+    }
+    let icloud = home.join("iCloudDrive");
+    if icloud.is_dir() {
+        folders.push(CloudFolder {
+            provider: CloudProvider::ICloud,
+            label: CloudProvider::ICloud.name().to_string(),
+            path: icloud,
+            online_only: true,
+        });
+    }
+
+    let mut info_files = vec![home.join(".dropbox/info.json")];
+    for var in ["APPDATA", "LOCALAPPDATA"] {
+        if let Some(dir) = env(var) {
+            info_files.push(PathBuf::from(dir).join("Dropbox/info.json"));
+        }
+    }
+    for info in info_files {
+        if let Ok(content) = std::fs::read_to_string(&info) {
+            folders.extend(
+                parse_dropbox_info(&content)
+                    .into_iter()
+                    .filter(|folder| folder.path.is_dir()),
+            );
+        }
+    }
+
+    // Set by the OneDrive client on Windows
+    for (var, account) in [
+        ("OneDriveConsumer", "Personal"),
+        ("OneDriveCommercial", "Business"),
+        ("OneDrive", ""),
+    ] {
+        if let Some(path) = env(var).map(PathBuf::from).filter(|path| path.is_dir()) {
+            let label = match path.file_name().map(|name| name.to_string_lossy()) {
+                Some(name) if name.starts_with("OneDrive - ") => name.to_string(),
+                _ if account.is_empty() => CloudProvider::OneDrive.name().to_string(),
+                _ => format!("OneDrive - {account}"),
+            };
+            folders.push(CloudFolder {
+                provider: CloudProvider::OneDrive,
+                label,
+                path,
+                online_only: cfg!(target_os = "windows"),
+            });
+        }
+    }
+
+    // The Linux OneDrive client syncs into ~/OneDrive by default
+    let onedrive = home.join("OneDrive");
+    if home.join(".config/onedrive").is_dir() && onedrive.is_dir() {
+        folders.push(CloudFolder {
+            provider: CloudProvider::OneDrive,
+            label: CloudProvider::OneDrive.name().to_string(),
+            path: onedrive,
+            online_only: false,
+        });
+    }
+
+    folders
+}
+
+/// Folder mounted by a File Provider extension, named like `OneDrive-Contoso`
+fn file_provider_folder(name: &str, path: PathBuf) -> CloudFolder {
+    let (provider, account) = [
+        ("Dropbox", CloudProvider::Dropbox),
+        ("OneDrive", CloudProvider::OneDrive),
+        ("GoogleDrive", CloudProvider::GoogleDrive),
+        ("iCloud", CloudProvider::ICloud),
+    ]
+    .into_iter()
+    .find_map(|(prefix, provider)| {
+        name.strip_prefix(prefix)
+            .map(|rest| (provider, rest.trim_start_matches(['-', ' '])))
+    })
+    .unwrap_or((CloudProvider::Other, name));
+
+    let label = match (provider, account) {
+        (CloudProvider::Other, name) => name.to_string(),
+        (provider, "") => provider.name().to_string(),
+        (provider, account) => format!("{} - {account}", provider.name()),
+    };
+    CloudFolder {
+        provider,
+        label,
+        path,
+        online_only: true,
+    }
+}
+
+/// Folders listed in Dropbox's `info.json`, e.g. `{"personal": {"path": "..."}}`
+fn parse_dropbox_info(content: &str) -> Vec<CloudFolder> {
+    let Ok(serde_json::Value::Object(accounts)) = serde_json::from_str(content) else {
+        return Vec::new();
+    };
+    accounts
+        .iter()
+        .filter_map(|(account, info)| {
+            let path = PathBuf::from(info.get("path")?.as_str()?);
+            let label = match account.as_str() {
+                "personal" => CloudProvider::Dropbox.name().to_string(),
+                "business" => "Dropbox - Business".to_string(),
+                other => format!("Dropbox - {other}"),
+            };
+            let online_only = path.components().any(|c| c.as_os_str() == "CloudStorage");
+            Some(CloudFolder {
+                provider: CloudProvider::Dropbox,
+                label,
+                path,
+                online_only,
+            })
+        })
+        .collect()
+}
+
+/// Google Drive for desktop mounts a virtual drive with a `My Drive` folder
+#[cfg(target_os = "windows")]
+fn google_drive_letters() -> Vec<CloudFolder> {
+    // SAFETY: GetLogicalDrives is a pure FFI call with no invariants to maintain.
+    let drives_mask = unsafe { windows_sys::Win32::Storage::FileSystem::GetLogicalDrives() };
+    // Skip A: to C:, the virtual drive is never mounted there
+    (3..26)
+        .filter(|i| drives_mask & (1 << i) != 0)
+        .map(|i| PathBuf::from(format!("{}:\\My Drive", (b'A' + i) as char)))
+        .filter(|path| path.is_dir())
+        .map(|path| CloudFolder {
+            provider: CloudProvider::GoogleDrive,
+            label: CloudProvider::GoogleDrive.name().to_string(),
+            path,
+            online_only: true,
+        })
+        .collect()
+}
+
+/// Online accounts mounted by GNOME, e.g. `google-drive:host=gmail.com,user=me`
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn gvfs_mounts(gvfs: &Path) -> Vec<CloudFolder> {
+    let Ok(entries) = std::fs::read_dir(gvfs) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<CloudFolder> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (scheme, params) = name.split_once(':')?;
+            let provider = match scheme {
+                "google-drive" => CloudProvider::GoogleDrive,
+                "onedrive" => CloudProvider::OneDrive,
+                _ => return None,
+            };
+            let user = params
+                .split(',')
+                .find_map(|param| param.strip_prefix("user="));
+            let label = match user {
+                Some(user) => format!("{} - {user}", provider.name()),
+                None => provider.name().to_string(),
+            };
+            Some(CloudFolder {
+                provider,
+                label,
+                path: entry.path(),
+                online_only: true,
+            })
+        })
+        .collect();
+    folders.sort_by(|a, b| a.path.cmp(&b.path));
+    folders
+}
+
+/// Whether a file is a cloud placeholder whose content is not on this device
+///
+/// Reading the content of a placeholder makes the provider download it,
+/// the metadata can be read without that.
+#[must_use]
+pub fn is_placeholder(path: &Path, metadata: &std::fs::Metadata) -> bool {
+    if !metadata.is_file() {
+        return false;
+    }
+    if icloud_stub_target(path).is_some() {
+        return true;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        // OneDrive Files-On-Demand and other Cloud Files API providers
+        const OFFLINE: u32 = 0x1000;
+        const RECALL_ON_OPEN: u32 = 0x4_0000;
+        const RECALL_ON_DATA_ACCESS: u32 = 0x40_0000;
+        metadata.file_attributes() & (OFFLINE | RECALL_ON_OPEN | RECALL_ON_DATA_ACCESS) != 0
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use std::os::macos::fs::MetadataExt;
+        // Evicted iCloud and File Provider files
+        const SF_DATALESS: u32 = 0x4000_0000;
+        metadata.st_flags() & SF_DATALESS != 0
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    false
+}
+
+/// Like [`is_placeholder`], reading the metadata of `path`
+#[must_use]
+pub fn is_placeholder_path(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|metadata| is_placeholder(path, &metadata))
+}
+
+/// File an evicted iCloud stub like `.report.pdf.icloud` stands for
+///
+/// Older macOS versions replace evicted files with these hidden stubs.
+fn icloud_stub_target(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let target = name.strip_prefix('.')?.strip_suffix(".icloud")?;
+    (!target.is_empty()).then(|| path.with_file_name(target))
+}
+
+/// Download placeholders in `paths`, directories included, and keep them on
+/// this device, returns how many files were downloaded
+pub fn download(paths: &[PathBuf]) -> Result<usize, String> {
+    let mut downloaded = 0;
+    for path in paths {
+        if path.is_dir() && !path.is_symlink() {
+            let entries = std::fs::read_dir(path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+            let children: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+            downloaded += download(&children)?;
+        } else if is_placeholder_path(path) {
+            download_file(path)?;
+            downloaded += 1;
+        }
+    }
+    Ok(downloaded)
+}
+
+fn download_file(path: &Path) -> Result<(), String> {
+    if let Some(target) = icloud_stub_target(path) {
+        // The stub itself has no content to read
+        return run_command("brctl", &[OsString::from("download"), target.into()]);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // Pinning is what "Always keep on this device" does in Explorer
+        let _ = run_command(
+            "attrib",
+            &[OsString::from("+P"), OsString::from("-U"), path.into()],
+        );
+    }
+    // Reading the whole file makes every provider download it
+    let mut file =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    std::io::copy(&mut file, &mut std::io::sink())
+        .map_err(|e| format!("Failed to download {}: {e}", path.display()))?;
+    Ok(())
+}
+
+fn run_command(program: &str, args: &[OsString]) -> Result<(), String> {
+    let status = std::process::Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program} failed with {status}"))
+    }
+}
+
+/// Drop folders found through more than one location
+fn dedup(folders: Vec<CloudFolder>) -> Vec<CloudFolder> {
+    let mut unique: Vec<CloudFolder> = Vec::with_capacity(folders.len());
+    for folder in folders {
+        if !unique.iter().any(|existing| existing.path == folder.path) {
+            unique.push(folder);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icloud_stub_target() {
+        assert_eq!(
+            icloud_stub_target(Path::new("/docs/.report.pdf.icloud")),
+            Some(PathBuf::from("/docs/report.pdf"))
+        );
+        assert_eq!(icloud_stub_target(Path::new("/docs/report.pdf")), None);
+        assert_eq!(icloud_stub_target(Path::new("/docs/..icloud")), None);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let stub = temp_dir.path().join(".a.txt.icloud");
+        let file = temp_dir.path().join("b.txt");
+        std::fs::write(&stub, "").unwrap();
+        std::fs::write(&file, "local").unwrap();
+        assert!(is_placeholder_path(&stub));
+        assert!(!is_placeholder_path(&file));
+        assert!(!is_placeholder_path(temp_dir.path()));
+        // Local files are left alone
+        assert_eq!(download(&[file]), Ok(0));
+    }
+
+    #[test]
+    fn test_file_provider_folder() {
+        let folder = file_provider_folder("OneDrive-Contoso", PathBuf::from("/x"));
+        assert_eq!(folder.provider, CloudProvider::OneDrive);
+        assert_eq!(folder.label, "OneDrive - Contoso");
+        let folder = file_provider_folder("GoogleDrive-me@gmail.com", PathBuf::from("/x"));
+        assert_eq!(folder.provider, CloudProvider::GoogleDrive);
+        assert_eq!(folder.label, "Google Drive - me@gmail.com");
+        let folder = file_provider_folder("Dropbox", PathBuf::from("/x"));
+        assert_eq!(folder.label, "Dropbox");
+        let folder = file_provider_folder("Box-Box", PathBuf::from("/x"));
+        assert_eq!(folder.provider, CloudProvider::Other);
+        assert_eq!(folder.label, "Box-Box");
+    }
+
+    #[test]
+    fn test_parse_dropbox_info() {
+        let folders = parse_dropbox_info(
+            r#"{"personal": {"path": "/home/me/Dropbox", "host": 1}, "business": {"path": "/home/me/Dropbox (Work)"}}"#,
+        );
+        let mut found: Vec<_> = folders
+            .iter()
+            .map(|f| (f.label.as_str(), f.path.to_string_lossy().to_string()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("Dropbox", "/home/me/Dropbox".to_string()),
+                ("Dropbox - Business", "/home/me/Dropbox (Work)".to_string()),
+            ]
+        );
+        assert!(parse_dropbox_info("not json").is_empty());
+    }
+
+    #[test]
+    fn test_detect_in() {
+        let home = tempfile::tempdir().unwrap();
+        let home = home.path();
+        std::fs::create_dir_all(home.join("Library/CloudStorage/OneDrive-Personal")).unwrap();
+        std::fs::create_dir_all(home.join("Library/Mobile Documents/com~apple~CloudDocs")).unwrap();
+        let dropbox = home.join("Dropbox");
+        std::fs::create_dir_all(&dropbox).unwrap();
+        std::fs::create_dir_all(home.join(".dropbox")).unwrap();
+        std::fs::write(
+            home.join(".dropbox/info.json"),
+            format!(
+                r#"{{"personal": {{"path": {:?}}}}}"#,
+                dropbox.to_string_lossy()
+            ),
+        )
+        .unwrap();
+
+        let folders = detect_in(home, |_| None);
+        let found: Vec<_> = folders
+            .iter()
+            .map(|f| (f.provider, f.label.as_str(), f.online_only))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (CloudProvider::OneDrive, "OneDrive - Personal", true),
+                (CloudProvider::ICloud, "iCloud Drive", true),
+                (CloudProvider::Dropbox, "Dropbox", false),
+            ]
+        );
+        assert_eq!(folders[2].path, dropbox);
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn test_gvfs_mounts() {
+        let gvfs = tempfile::tempdir().unwrap();
+        std::fs::create_dir(gvfs.path().join("google-drive:host=gmail.com,user=me")).unwrap();
+        std::fs::create_dir(gvfs.path().join("smb-share:server=nas,share=media")).unwrap();
+        let folders = gvfs_mounts(gvfs.path());
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].label, "Google Drive - me");
+    }
+}
@@ -0,0 +1,287 @@
+//! Line diff of two text files
+//!
+//! The rows of the unified and the side-by-side views are computed once, so
+//! drawing only has to look up the visible lines.
+
+use std::ops::Range;
+use std::path::Path;
+use std::time::Duration;
+
+use similar::{Algorithm, DiffTag, TextDiff};
+
+/// Files larger than this are not compared
+pub const MAX_DIFF_FILE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// After this long the diff is approximated instead of minimal
+const DIFF_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Line ranges of a hunk, 1-based like the `@@ -a,b +c,d @@` header of a patch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HunkHeader {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+}
+
+impl std::fmt::Display for HunkHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_len, self.new_start, self.new_len
+        )
+    }
+}
+
+/// Row of the unified view, lines are 0-based indices into either file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnifiedRow {
+    Hunk(HunkHeader),
+    Equal { old: usize, new: usize },
+    Removed(usize),
+    Added(usize),
+}
+
+/// Row of the side-by-side view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitRow {
+    Hunk(HunkHeader),
+    Equal {
+        old: usize,
+        new: usize,
+    },
+    /// A side is `None` where the other file has more changed lines
+    Changed {
+        old: Option<usize>,
+        new: Option<usize>,
+    },
+}
+
+/// Differences between an old and a new text
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    pub old: String,
+    pub new: String,
+    old_lines: Vec<Range<usize>>,
+    new_lines: Vec<Range<usize>>,
+    pub unified: Vec<UnifiedRow>,
+    pub split: Vec<SplitRow>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Byte range of each line, without its line ending
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    text.split_inclusive('\n')
+        .map(|line| {
+            let range = start..start + line.trim_end_matches(['\n', '\r']).len();
+            start += line.len();
+            range
+        })
+        .collect()
+}
+
+impl FileDiff {
+    #[must_use]
+    pub fn new(old: String, new: String) -> Self {
+        let mut unified = Vec::new();
+        let mut split = Vec::new();
+        let mut added = 0;
+        let mut removed = 0;
+
+        let diff = TextDiff::configure()
+            .algorithm(Algorithm::Patience)
+            .timeout(DIFF_TIMEOUT)
+            .diff_lines(&old, &new);
+        for group in diff.grouped_ops(CONTEXT_LINES) {
+            let (Some(first), Some(last)) = (group.first(), group.last()) else {
+                continue;
+            };
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+            let header = HunkHeader {
+                old_start: old_range.start + 1,
+                old_len: old_range.len(),
+                new_start: new_range.start + 1,
+                new_len: new_range.len(),
+            };
+            unified.push(UnifiedRow::Hunk(header));
+            split.push(SplitRow::Hunk(header));
+
+            for op in &group {
+                let (tag, old_range, new_range) = op.as_tag_tuple();
+                if tag == DiffTag::Equal {
+                    for (old, new) in old_range.zip(new_range) {
+                        unified.push(UnifiedRow::Equal { old, new });
+                        split.push(SplitRow::Equal { old, new });
+                    }
+                    continue;
+                }
+                removed += old_range.len();
+                added += new_range.len();
+                unified.extend(old_range.clone().map(UnifiedRow::Removed));
+                unified.extend(new_range.clone().map(UnifiedRow::Added));
+                // Replaced lines are paired up, the longer side continues alone
+                let rows = old_range.len().max(new_range.len());
+                split.extend((0..rows).map(|i| SplitRow::Changed {
+                    old: (i < old_range.len()).then(|| old_range.start + i),
+                    new: (i < new_range.len()).then(|| new_range.start + i),
+                }));
+            }
+        }
+
+        Self {
+            old_lines: line_ranges(&old),
+            new_lines: line_ranges(&new),
+            old,
+            new,
+            unified,
+            split,
+            added,
+            removed,
+        }
+    }
+
+    /// Read and compare two text files
+    pub fn from_files(old: &Path, new: &Path) -> Result<Self, String> {
+        Ok(Self::new(read_text(old)?, read_text(new)?))
+    }
+
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.unified.is_empty()
+    }
+
+    /// Byte range of a line of the old text, without its line ending
+    #[must_use]
+    pub fn old_line(&self, index: usize) -> Range<usize> {
+        self.old_lines.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Byte range of a line of the new text, without its line ending
+    #[must_use]
+    pub fn new_line(&self, index: usize) -> Range<usize> {
+        self.new_lines.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Number of lines of the longer text
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.old_lines.len().max(self.new_lines.len())
+    }
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read {name}: {e}"))?
+        .len();
+    if size > MAX_DIFF_FILE_SIZE {
+        return Err(format!("{name} is too large to compare"));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {name}: {e}"))?;
+    String::from_utf8(bytes).map_err(|_| format!("{name} is not a text file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_and_split_rows() {
+        let old = "a\nb\nc\nd\n".to_string();
+        let new = "a\nB\nB2\nc\nd\ne\n".to_string();
+        let diff = FileDiff::new(old, new);
+        assert!(!diff.is_identical());
+        assert_eq!(diff.removed, 1);
+        assert_eq!(diff.added, 3);
+
+        let header = HunkHeader {
+            old_start: 1,
+            old_len: 4,
+            new_start: 1,
+            new_len: 6,
+        };
+        assert_eq!(header.to_string(), "@@ -1,4 +1,6 @@");
+        assert_eq!(
+            diff.unified,
+            vec![
+                UnifiedRow::Hunk(header),
+                UnifiedRow::Equal { old: 0, new: 0 },
+                UnifiedRow::Removed(1),
+                UnifiedRow::Added(1),
+                UnifiedRow::Added(2),
+                UnifiedRow::Equal { old: 2, new: 3 },
+                UnifiedRow::Equal { old: 3, new: 4 },
+                UnifiedRow::Added(5),
+            ]
+        );
+        assert_eq!(
+            diff.split,
+            vec![
+                SplitRow::Hunk(header),
+                SplitRow::Equal { old: 0, new: 0 },
+                SplitRow::Changed {
+                    old: Some(1),
+                    new: Some(1)
+                },
+                SplitRow::Changed {
+                    old: None,
+                    new: Some(2)
+                },
+                SplitRow::Equal { old: 2, new: 3 },
+                SplitRow::Equal { old: 3, new: 4 },
+                SplitRow::Changed {
+                    old: None,
+                    new: Some(5)
+                },
+            ]
+        );
+        assert_eq!(&diff.old[diff.old_line(1)], "b");
+        assert_eq!(&diff.new[diff.new_line(2)], "B2");
+        assert_eq!(diff.line_count(), 6);
+    }
+
+    #[test]
+    fn test_hunks_keep_context_only() {
+        let old: String = (0..20).map(|i| format!("line {i}\r\n")).collect();
+        let new = old
+            .replace("line 2\r\n", "two\r\n")
+            .replace("line 17", "seventeen");
+        let diff = FileDiff::new(old, new);
+        let headers: Vec<String> = diff
+            .unified
+            .iter()
+            .filter_map(|row| match row {
+                UnifiedRow::Hunk(header) => Some(header.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(headers, ["@@ -1,6 +1,6 @@", "@@ -15,6 +15,6 @@"]);
+        // Line endings are not part of the line
+        assert_eq!(&diff.new[diff.new_line(17)], "seventeen");
+    }
+
+    #[test]
+    fn test_identical_and_binary_files() {
+        let diff = FileDiff::new("same\n".to_string(), "same\n".to_string());
+        assert!(diff.is_identical());
+        assert!(diff.split.is_empty());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let text = temp_dir.path().join("a.txt");
+        let binary = temp_dir.path().join("b.bin");
+        std::fs::write(&text, "text\n").unwrap();
+        std::fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+        assert_eq!(
+            FileDiff::from_files(&text, &binary).unwrap_err(),
+            "b.bin is not a text file"
+        );
+        assert!(FileDiff::from_files(&text, &text).unwrap().is_identical());
+    }
+}
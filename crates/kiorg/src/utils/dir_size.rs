@@ -0,0 +1,206 @@
+//! Recursive directory sizes shown in the size column
+//!
+//! Sizes are computed one directory at a time by a background worker and
+//! cached for the session. A directory is scanned again once a change inside
+//! it invalidated its size.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+/// Size of a directory as known to the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirSize {
+    /// Queued or being scanned
+    Scanning,
+    Done(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    /// Scan requested with this generation, results of older scans are dropped
+    Scanning(u64),
+    Done(u64),
+}
+
+#[derive(Default)]
+struct Shared {
+    sizes: Mutex<HashMap<PathBuf, Entry>>,
+    shutdown: AtomicBool,
+}
+
+/// Session cache of recursive directory sizes
+pub struct DirSizeCache {
+    shared: Arc<Shared>,
+    sender: Mutex<Option<Sender<(PathBuf, u64)>>>,
+    generation: AtomicU64,
+    on_update: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl std::fmt::Debug for DirSizeCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DirSizeCache")
+            .field("sizes", &lock(&self.shared.sizes).len())
+            .finish()
+    }
+}
+
+/// Lock shared state, it stays consistent even if a thread panicked holding it
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl DirSizeCache {
+    /// `on_update` is called whenever a size was computed, e.g. to wake up the UI
+    pub fn new(on_update: impl Fn() + Send + Sync + 'static) -> Self {
+        Self {
+            shared: Arc::default(),
+            sender: Mutex::new(None),
+            generation: AtomicU64::new(0),
+            on_update: Arc::new(on_update),
+        }
+    }
+
+    /// Size of the directory at `path`, queueing a scan if it isn't known yet
+    pub fn get(&self, path: &Path) -> DirSize {
+        let generation = {
+            let mut sizes = lock(&self.shared.sizes);
+            match sizes.get(path) {
+                Some(Entry::Done(size)) => return DirSize::Done(*size),
+                Some(Entry::Scanning(_)) => return DirSize::Scanning,
+                None => {
+                    let generation = self.generation.fetch_add(1, Ordering::Relaxed);
+                    sizes.insert(path.to_path_buf(), Entry::Scanning(generation));
+                    generation
+                }
+            }
+        };
+
+        let mut sender = lock(&self.sender);
+        let sender = sender.get_or_insert_with(|| {
+            let (tx, rx) = mpsc::channel::<(PathBuf, u64)>();
+            let shared = self.shared.clone();
+            let on_update = self.on_update.clone();
+            thread::spawn(move || {
+                for (path, generation) in rx {
+                    run_scan(&shared, &path, generation, &*on_update);
+                }
+            });
+            tx
+        });
+        let _ = sender.send((path.to_path_buf(), generation));
+        DirSize::Scanning
+    }
+
+    /// Forget the size of `path` and of every directory containing it
+    pub fn invalidate(&self, path: &Path) {
+        lock(&self.shared.sizes).retain(|dir, _| !path.starts_with(dir));
+    }
+}
+
+impl Drop for DirSizeCache {
+    fn drop(&mut self) {
+        // The worker exits once the sender is dropped and the scan it runs stops
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+fn run_scan(shared: &Shared, path: &Path, generation: u64, on_update: &dyn Fn()) {
+    // Skip directories invalidated while they were queued
+    if lock(&shared.sizes).get(path) != Some(&Entry::Scanning(generation)) {
+        return;
+    }
+    let Some(size) = dir_size(path, &shared.shutdown) else {
+        return;
+    };
+    let mut sizes = lock(&shared.sizes);
+    if let Some(entry) = sizes.get_mut(path)
+        && *entry == Entry::Scanning(generation)
+    {
+        *entry = Entry::Done(size);
+        drop(sizes);
+        on_update();
+    }
+}
+
+/// Total size of the files under `path`
+///
+/// Symlinks are counted by their own size and never followed. Returns `None`
+/// if cancelled.
+pub fn dir_size(path: &Path, cancel: &AtomicBool) -> Option<u64> {
+    let mut size = 0;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                stack.push(entry.path());
+            } else {
+                size += metadata.len();
+            }
+        }
+    }
+    Some(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait_for_size(cache: &DirSizeCache, path: &Path) -> u64 {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let DirSize::Done(size) = cache.get(path) {
+                return size;
+            }
+            assert!(Instant::now() < deadline, "Scan should finish");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("one.txt"), vec![0u8; 10]).unwrap();
+        fs::write(root.join("a/two.txt"), vec![0u8; 20]).unwrap();
+        fs::write(root.join("a/b/three.txt"), vec![0u8; 30]).unwrap();
+
+        assert_eq!(dir_size(root, &AtomicBool::new(false)), Some(60));
+        assert_eq!(dir_size(&root.join("a"), &AtomicBool::new(false)), Some(50));
+        assert_eq!(dir_size(root, &AtomicBool::new(true)), None);
+    }
+
+    #[test]
+    fn test_cache_invalidation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("dir");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("nested/file.txt"), vec![0u8; 10]).unwrap();
+
+        let cache = DirSizeCache::new(|| {});
+        assert_eq!(cache.get(&dir), DirSize::Scanning);
+        assert_eq!(wait_for_size(&cache, &dir), 10);
+
+        // Cached until a change inside the directory
+        fs::write(dir.join("nested/more.txt"), vec![0u8; 5]).unwrap();
+        assert_eq!(cache.get(&dir), DirSize::Done(10));
+        cache.invalidate(&dir.join("nested/more.txt"));
+        assert_eq!(wait_for_size(&cache, &dir), 15);
+
+        // Unrelated paths keep their size
+        cache.invalidate(&temp_dir.path().join("other"));
+        assert_eq!(cache.get(&dir), DirSize::Done(15));
+    }
+}
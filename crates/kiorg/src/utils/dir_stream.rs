@@ -0,0 +1,134 @@
+//! Background listing of the rest of a huge directory
+//!
+//! The first [`INITIAL_ENTRIES`] entries of a directory are read right away so
+//! they show immediately. A [`DirStream`] reads the remaining ones on a
+//! background thread and sends them in batches, until the listing ends or the
+//! stream is dropped.
+
+use std::fs::ReadDir;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::models::dir_entry::DirEntry;
+use crate::models::tab::read_entry;
+
+/// Entries read before listing the rest of a directory in the background
+pub const INITIAL_ENTRIES: usize = 5_000;
+
+const BATCH_SIZE: usize = 5_000;
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Listing of a directory in progress or done
+#[derive(Debug)]
+pub struct DirStream {
+    /// Directory being listed
+    pub dir: PathBuf,
+    receiver: Receiver<Vec<DirEntry>>,
+    cancel: Arc<AtomicBool>,
+    /// The listing finished or was cancelled
+    pub done: bool,
+}
+
+impl DirStream {
+    /// Keep reading the entries left in `read_dir`, a listing of `dir`
+    #[must_use]
+    pub fn start(dir: PathBuf, read_dir: ReadDir, show_hidden: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        {
+            let cancel = cancel.clone();
+            thread::spawn(move || read_rest(read_dir, show_hidden, &cancel, &sender));
+        }
+        Self {
+            dir,
+            receiver,
+            cancel,
+            done: false,
+        }
+    }
+
+    /// Entries read since the last call
+    pub fn poll(&mut self) -> Vec<DirEntry> {
+        let mut entries = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(batch) => entries.extend(batch),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        entries
+    }
+}
+
+impl Drop for DirStream {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+fn read_rest(
+    read_dir: ReadDir,
+    show_hidden: bool,
+    cancel: &AtomicBool,
+    sender: &Sender<Vec<DirEntry>>,
+) {
+    let mut batch = Vec::new();
+    let mut last_sent = Instant::now();
+    for entry in read_dir.flatten() {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(entry) = read_entry(&entry, show_hidden) else {
+            continue;
+        };
+        batch.push(entry);
+        if batch.len() >= BATCH_SIZE || last_sent.elapsed() >= BATCH_INTERVAL {
+            if sender.send(std::mem::take(&mut batch)).is_err() {
+                return;
+            }
+            last_sent = Instant::now();
+        }
+    }
+    let _ = sender.send(batch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_reads_remaining_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for index in 0..20 {
+            std::fs::write(temp_dir.path().join(format!("{index}.txt")), "").unwrap();
+        }
+        std::fs::write(temp_dir.path().join(".hidden"), "").unwrap();
+
+        let mut read_dir = std::fs::read_dir(temp_dir.path()).unwrap();
+        let first: Vec<_> = read_dir
+            .by_ref()
+            .take(5)
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        let mut stream = DirStream::start(temp_dir.path().to_path_buf(), read_dir, true);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut rest = Vec::new();
+        while !stream.done {
+            assert!(Instant::now() < deadline, "Listing should finish");
+            rest.extend(stream.poll().into_iter().map(|entry| entry.meta.path));
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(first.len() + rest.len(), 21);
+        assert!(rest.iter().all(|path| !first.contains(path)));
+    }
+}
@@ -0,0 +1,98 @@
+//! Space files take up on disk
+//!
+//! The size on disk can differ from the logical size of a file: sparse and
+//! compressed files take less space, small files still take a whole block
+//! and cloud placeholders take none at all.
+
+use std::fs::Metadata;
+use std::path::Path;
+
+/// Bytes allocated on disk for `path`, `metadata` is its symlink metadata
+#[must_use]
+pub fn size_on_disk(path: &Path, metadata: &Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let _ = path;
+        // st_blocks is always in 512 byte units
+        Some(metadata.blocks() * 512)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use windows_sys::Win32::Foundation::{GetLastError, NO_ERROR};
+        use windows_sys::Win32::Storage::FileSystem::{GetCompressedFileSizeW, INVALID_FILE_SIZE};
+
+        if !metadata.is_file() {
+            return None;
+        }
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let mut high = 0u32;
+        // Reports the allocated size of compressed and sparse files, and the
+        // logical size of everything else
+        let low = unsafe { GetCompressedFileSizeW(wide.as_ptr(), &mut high) };
+        if low == INVALID_FILE_SIZE && unsafe { GetLastError() } != NO_ERROR {
+            return None;
+        }
+        Some((u64::from(high) << 32) | u64::from(low))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (path, metadata);
+        None
+    }
+}
+
+/// Like [`size_on_disk`], reading the metadata of `path`
+#[must_use]
+pub fn size_on_disk_of(path: &Path) -> Option<u64> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    size_on_disk(path, &metadata)
+}
+
+/// Why a file takes less space on disk than its size, if it does
+#[must_use]
+pub fn savings_reason(size: u64, on_disk: u64, is_placeholder: bool) -> Option<&'static str> {
+    if is_placeholder {
+        Some("online-only, not stored on this device")
+    } else if on_disk < size {
+        Some("sparse or compressed")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_savings_reason() {
+        assert_eq!(savings_reason(100, 4096, false), None);
+        assert_eq!(
+            savings_reason(8192, 4096, false),
+            Some("sparse or compressed")
+        );
+        assert_eq!(
+            savings_reason(8192, 0, true),
+            Some("online-only, not stored on this device")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_size_on_disk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let small = temp_dir.path().join("small.txt");
+        std::fs::write(&small, "hello").unwrap();
+        assert!(size_on_disk_of(&small).unwrap() >= 5);
+
+        // A file with a hole in the middle only allocates the written blocks
+        let sparse = temp_dir.path().join("sparse.img");
+        let file = std::fs::File::create(&sparse).unwrap();
+        file.set_len(64 * 1024 * 1024).unwrap();
+        drop(file);
+        assert!(size_on_disk_of(&sparse).unwrap() < 64 * 1024 * 1024);
+        assert_eq!(size_on_disk_of(&temp_dir.path().join("missing")), None);
+    }
+}
@@ -0,0 +1,184 @@
+//! Finding files with the same content under a directory
+//!
+//! Only files sharing their size with another file are hashed, which rules
+//! out most files without reading them. Hard links to the same file are
+//! counted once, as deleting one of them frees no space.
+
+use crate::utils::cloud;
+use crate::utils::hashing::{self, HashAlgorithm, HashProgress};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Files with the same content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// Size of each file
+    pub size: u64,
+    /// Sorted by path
+    pub paths: Vec<PathBuf>,
+}
+
+impl DuplicateGroup {
+    /// Space freed by keeping a single copy
+    pub const fn wasted(&self) -> u64 {
+        self.size * (self.paths.len() as u64 - 1)
+    }
+}
+
+/// Progress of [`find_duplicates`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanProgress {
+    /// Walking the tree, with the number of files found so far
+    Scanning { files: usize },
+    /// Hashing the files of the same size
+    Hashing(HashProgress),
+}
+
+/// Identity of a file on disk, shared by its hard links
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Non-empty regular files under `root` and their sizes, links aren't followed
+///
+/// Returns `None` if cancelled.
+fn collect_files(
+    root: &Path,
+    cancel: &AtomicBool,
+    on_progress: &impl Fn(ScanProgress),
+) -> Option<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Ok(metadata) = path.symlink_metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                dirs.push(path);
+            } else if metadata.is_file()
+                && metadata.len() > 0
+                // Hashing would download online-only files
+                && !cloud::is_placeholder(&path, &metadata)
+                && file_id(&metadata).is_none_or(|id| seen.insert(id))
+            {
+                files.push((path, metadata.len()));
+            }
+        }
+        on_progress(ScanProgress::Scanning { files: files.len() });
+    }
+    Some(files)
+}
+
+/// Find the files with the same content under `root`
+///
+/// Groups are sorted by the space they waste, largest first. Returns `None`
+/// if cancelled.
+pub fn find_duplicates(
+    root: &Path,
+    cancel: &AtomicBool,
+    on_progress: impl Fn(ScanProgress) + Sync,
+) -> Option<Vec<DuplicateGroup>> {
+    let files = collect_files(root, cancel, &on_progress)?;
+
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for (path, size) in files {
+        by_size.entry(size).or_default().push(path);
+    }
+    let candidates: Vec<(PathBuf, u64)> = by_size
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .flat_map(|(size, paths)| paths.into_iter().map(move |path| (path, size)))
+        .collect();
+    let paths: Vec<PathBuf> = candidates.iter().map(|(path, _)| path.clone()).collect();
+
+    let digests = hashing::hash_files(&paths, &[HashAlgorithm::Blake3], cancel, |progress| {
+        on_progress(ScanProgress::Hashing(progress));
+    })?;
+
+    let mut by_content: HashMap<(u64, String), Vec<PathBuf>> = HashMap::new();
+    for ((path, size), digests) in candidates.into_iter().zip(digests) {
+        // Unreadable files can't be compared
+        let Some((_, digest)) = digests.ok().and_then(|d| d.into_iter().next()) else {
+            continue;
+        };
+        by_content.entry((size, digest)).or_default().push(path);
+    }
+    let mut groups: Vec<DuplicateGroup> = by_content
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|((size, _), mut paths)| {
+            paths.sort();
+            DuplicateGroup { size, paths }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.wasted()
+            .cmp(&a.wasted())
+            .then_with(|| a.paths.cmp(&b.paths))
+    });
+    Some(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_duplicates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("one.txt"), "same content").unwrap();
+        fs::write(root.join("a/two.txt"), "same content").unwrap();
+        fs::write(root.join("a/b/three.txt"), "same content").unwrap();
+        // Same size, different content
+        fs::write(root.join("a/other.txt"), "diff content").unwrap();
+        fs::write(root.join("big1.bin"), vec![7u8; 100]).unwrap();
+        fs::write(root.join("a/b/big2.bin"), vec![7u8; 100]).unwrap();
+        // Empty files are all alike, but not worth reporting
+        fs::write(root.join("empty1"), "").unwrap();
+        fs::write(root.join("empty2"), "").unwrap();
+        #[cfg(unix)]
+        {
+            fs::hard_link(root.join("big1.bin"), root.join("hardlink.bin")).unwrap();
+            std::os::unix::fs::symlink(root.join("one.txt"), root.join("symlink.txt")).unwrap();
+        }
+
+        let groups = find_duplicates(root, &AtomicBool::new(false), |_| {}).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].size, 100);
+        assert_eq!(groups[0].paths.len(), 2);
+        assert_eq!(groups[0].wasted(), 100);
+        assert_eq!(
+            groups[1],
+            DuplicateGroup {
+                size: 12,
+                paths: vec![
+                    root.join("a/b/three.txt"),
+                    root.join("a/two.txt"),
+                    root.join("one.txt"),
+                ],
+            }
+        );
+
+        assert!(find_duplicates(root, &AtomicBool::new(true), |_| {}).is_none());
+    }
+}
@@ -0,0 +1,396 @@
+//! Per-file encryption with age or GPG
+//!
+//! age is built in. GPG is run through the `gpg` binary, so its keyring and
+//! agent are used for recipients and private keys.
+
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use age::secrecy::SecretString;
+
+use super::file_operations::unique_path;
+
+/// Tool used to encrypt a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Age,
+    Gpg,
+}
+
+impl Backend {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Age => "age",
+            Self::Gpg => "GPG",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Age => "age",
+            Self::Gpg => "gpg",
+        }
+    }
+}
+
+/// What a file is encrypted to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// Symmetric encryption with a passphrase
+    Passphrase(String),
+    /// age public keys, or GPG key IDs and emails
+    Recipients(Vec<String>),
+}
+
+/// Split recipients separated by commas, spaces or new lines
+pub fn parse_recipients(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|recipient| !recipient.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether the `gpg` binary can be run
+pub fn gpg_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("gpg")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
+
+const AGE_HEADER: &[u8] = b"age-encryption.org/";
+const AGE_ARMOR_HEADER: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+const PGP_ARMOR_HEADER: &[u8] = b"-----BEGIN PGP MESSAGE-----";
+
+fn read_head(path: &Path) -> Vec<u8> {
+    let mut head = Vec::new();
+    if let Ok(file) = std::fs::File::open(path) {
+        let _ = file.take(64).read_to_end(&mut head);
+    }
+    head
+}
+
+/// Backend a file was encrypted with, `None` if it doesn't look encrypted
+pub fn encrypted_with(path: &Path) -> Option<Backend> {
+    let head = read_head(path);
+    if head.starts_with(AGE_HEADER) || head.starts_with(AGE_ARMOR_HEADER) {
+        return Some(Backend::Age);
+    }
+    if head.starts_with(PGP_ARMOR_HEADER) {
+        return Some(Backend::Gpg);
+    }
+    // Binary OpenPGP messages have no magic, so go by the extension
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    matches!(ext.as_str(), "gpg" | "pgp").then_some(Backend::Gpg)
+}
+
+/// Where the encrypted copy of `path` is written, e.g. `report.pdf.age`
+pub fn encrypted_path(path: &Path, backend: Backend) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let dir = path.parent().unwrap_or(Path::new(""));
+    unique_path(dir, &format!("{name}.{}", backend.extension()))
+}
+
+/// Where the decrypted copy of `path` is written, e.g. `report.pdf` for `report.pdf.age`
+pub fn decrypted_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let plain = name
+        .rsplit_once('.')
+        .filter(|(stem, ext)| {
+            !stem.is_empty()
+                && ["age", "gpg", "pgp", "asc"]
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+        .map(|(stem, _)| stem.to_string())
+        .unwrap_or_else(|| format!("{name}.decrypted"));
+    unique_path(dir, &plain)
+}
+
+/// Write `output` with `write`, removing the partial file if it fails
+fn write_output(
+    output: &Path,
+    write: impl FnOnce(&mut std::fs::File) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut file = std::fs::File::create_new(output)
+        .map_err(|e| format!("Failed to create {}: {e}", output.display()))?;
+    let result = write(&mut file).and_then(|()| {
+        file.sync_all()
+            .map_err(|e| format!("Failed to write {}: {e}", output.display()))
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_file(output);
+    }
+    result
+}
+
+/// Encrypt `path` into a new file next to it and return its path
+pub fn encrypt(path: &Path, backend: Backend, key: &Key) -> Result<PathBuf, String> {
+    let output = encrypted_path(path, backend);
+    match backend {
+        Backend::Age => age_encrypt(path, &output, key)?,
+        Backend::Gpg => {
+            let mut args = vec!["--batch", "--yes"];
+            let passphrase = match key {
+                Key::Passphrase(passphrase) => {
+                    args.extend(["--pinentry-mode", "loopback", "--passphrase-fd", "0"]);
+                    args.push("--symmetric");
+                    Some(passphrase.as_str())
+                }
+                Key::Recipients(recipients) => {
+                    args.push("--encrypt");
+                    for recipient in recipients {
+                        args.extend(["--recipient", recipient]);
+                    }
+                    None
+                }
+            };
+            run_gpg(&args, path, &output, passphrase)?;
+        }
+    }
+    Ok(output)
+}
+
+fn age_encrypt(path: &Path, output: &Path, key: &Key) -> Result<(), String> {
+    let encryptor = match key {
+        Key::Passphrase(passphrase) => {
+            age::Encryptor::with_user_passphrase(SecretString::from(passphrase.clone()))
+        }
+        Key::Recipients(recipients) => {
+            let recipients = recipients
+                .iter()
+                .map(|recipient| {
+                    age::x25519::Recipient::from_str(recipient)
+                        .map_err(|_| format!("Not an age public key: {recipient}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            age::Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn age::Recipient))
+                .map_err(|e| format!("Failed to encrypt: {e}"))?
+        }
+    };
+
+    let mut input =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    write_output(output, |file| {
+        let mut writer = encryptor
+            .wrap_output(file)
+            .map_err(|e| format!("Failed to encrypt: {e}"))?;
+        std::io::copy(&mut input, &mut writer)
+            .and_then(|_| writer.finish())
+            .map(|_| ())
+            .map_err(|e| format!("Failed to encrypt: {e}"))
+    })
+}
+
+/// Decrypt `path` into a new file next to it and return its path
+///
+/// For age files `secret` is the passphrase, an `AGE-SECRET-KEY-` or the
+/// path of an identity file. For GPG it is the passphrase of a symmetrically
+/// encrypted file, or empty to let the GPG agent find the private key.
+pub fn decrypt(path: &Path, secret: &str) -> Result<PathBuf, String> {
+    let Some(backend) = encrypted_with(path) else {
+        return Err(format!("{} is not encrypted", path.display()));
+    };
+    let output = decrypted_path(path);
+    match backend {
+        Backend::Age => age_decrypt(path, &output, secret)?,
+        Backend::Gpg => {
+            let mut args = vec!["--batch", "--yes"];
+            if !secret.is_empty() {
+                args.extend(["--pinentry-mode", "loopback", "--passphrase-fd", "0"]);
+            }
+            args.push("--decrypt");
+            run_gpg(&args, path, &output, Some(secret).filter(|s| !s.is_empty()))?;
+        }
+    }
+    Ok(output)
+}
+
+fn age_identities(secret: &str) -> Result<Vec<Box<dyn age::Identity>>, String> {
+    let secret = secret.trim();
+    if secret.starts_with("AGE-SECRET-KEY-") {
+        let identity = age::x25519::Identity::from_str(secret)
+            .map_err(|_| "Invalid age secret key".to_string())?;
+        return Ok(vec![Box::new(identity)]);
+    }
+    if Path::new(secret).is_file() {
+        return age::IdentityFile::from_file(secret.to_string())
+            .map_err(|e| format!("Failed to read identity file: {e}"))?
+            .into_identities()
+            .map_err(|e| format!("Failed to read identity file: {e}"));
+    }
+    Err("Enter an age secret key or the path of an identity file".to_string())
+}
+
+fn age_decrypt(path: &Path, output: &Path, secret: &str) -> Result<(), String> {
+    let input =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    let decryptor = age::Decryptor::new(age::armor::ArmoredReader::new(BufReader::new(input)))
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let identities = if decryptor.is_scrypt() {
+        let identity = age::scrypt::Identity::new(SecretString::from(secret.to_string()));
+        vec![Box::new(identity) as Box<dyn age::Identity>]
+    } else {
+        age_identities(secret)?
+    };
+    let mut reader = decryptor
+        .decrypt(identities.iter().map(|identity| identity.as_ref()))
+        .map_err(|e| format!("Failed to decrypt: {e}"))?;
+
+    write_output(output, |file| {
+        std::io::copy(&mut reader, file)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to decrypt: {e}"))
+    })
+}
+
+fn run_gpg(
+    args: &[&str],
+    input: &Path,
+    output: &Path,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    if !gpg_available() {
+        return Err("gpg is not installed".to_string());
+    }
+    let mut child = Command::new("gpg")
+        .args(args)
+        .arg("--output")
+        .arg(output)
+        .arg(input)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run gpg: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take()
+        && let Some(passphrase) = passphrase
+    {
+        let _ = writeln!(stdin, "{passphrase}");
+    }
+    let result = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run gpg: {e}"))?;
+    if result.status.success() {
+        return Ok(());
+    }
+    let _ = std::fs::remove_file(output);
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    let message = stderr
+        .lines()
+        .rfind(|line| !line.trim().is_empty())
+        .unwrap_or("unknown error")
+        .trim_start_matches("gpg: ");
+    Err(format!("gpg failed: {message}"))
+}
+
+/// Overwrite a file with zeros before deleting it
+///
+/// Copy-on-write file systems, SSD wear leveling and backups may still keep
+/// the old contents, this only makes recovering them harder.
+pub fn shred(path: &Path) -> Result<(), String> {
+    let error = |e: std::io::Error| format!("Failed to shred {}: {e}", path.display());
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(error)?;
+    let mut remaining = file.metadata().map_err(error)?.len();
+    let zeros = vec![0u8; 64 * 1024];
+    while remaining > 0 {
+        let len = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..len]).map_err(error)?;
+        remaining -= len as u64;
+    }
+    file.sync_all().map_err(error)?;
+    drop(file);
+    std::fs::remove_file(path).map_err(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recipients() {
+        assert_eq!(
+            parse_recipients("alice@example.com, bob@example.com\n age1abc "),
+            vec!["alice@example.com", "bob@example.com", "age1abc"]
+        );
+        assert!(parse_recipients(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_output_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("report.pdf");
+        assert_eq!(
+            encrypted_path(&file, Backend::Age),
+            dir.path().join("report.pdf.age")
+        );
+        assert_eq!(
+            decrypted_path(&dir.path().join("report.pdf.GPG")),
+            dir.path().join("report.pdf")
+        );
+        std::fs::write(&file, "").unwrap();
+        assert_eq!(
+            decrypted_path(&dir.path().join("report.pdf.age")),
+            dir.path().join("report_1.pdf")
+        );
+        assert_eq!(
+            decrypted_path(&dir.path().join("secret")),
+            dir.path().join("secret.decrypted")
+        );
+    }
+
+    #[test]
+    fn test_age_passphrase_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "top secret").unwrap();
+
+        let key = Key::Passphrase("correct horse".to_string());
+        let encrypted = encrypt(&file, Backend::Age, &key).unwrap();
+        assert_eq!(encrypted, dir.path().join("notes.txt.age"));
+        assert_eq!(encrypted_with(&encrypted), Some(Backend::Age));
+        assert_eq!(encrypted_with(&file), None);
+
+        shred(&file).unwrap();
+        assert!(!file.exists());
+
+        assert!(decrypt(&encrypted, "wrong").is_err());
+        assert!(!file.exists(), "failed decryption must not leave a file");
+        let decrypted = decrypt(&encrypted, "correct horse").unwrap();
+        assert_eq!(decrypted, file);
+        assert_eq!(std::fs::read_to_string(&decrypted).unwrap(), "top secret");
+    }
+
+    #[test]
+    fn test_age_recipient_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "for alice").unwrap();
+
+        let identity = age::x25519::Identity::generate();
+        let key = Key::Recipients(vec![identity.to_public().to_string()]);
+        let encrypted = encrypt(&file, Backend::Age, &key).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let bad_key = Key::Recipients(vec!["not-a-key".to_string()]);
+        assert!(encrypt(&encrypted, Backend::Age, &bad_key).is_err());
+
+        assert!(decrypt(&encrypted, "").is_err());
+        use age::secrecy::ExposeSecret;
+        let secret = identity.to_string();
+        let decrypted = decrypt(&encrypted, secret.expose_secret()).unwrap();
+        assert_eq!(std::fs::read_to_string(decrypted).unwrap(), "for alice");
+    }
+}
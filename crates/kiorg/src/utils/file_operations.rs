@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Recursively copy a directory from src to dst
 pub fn copy_dir_recursively(src: &Path, dst: &Path) -> std::io::Result<()> {
@@ -45,6 +45,43 @@ pub fn omni_rename(src: &Path, dst: &Path) -> std::io::Result<()> {
     }
 }
 
+/// Path in `dir` named `name` that doesn't exist yet, e.g. `a_1.txt`
+///
+/// Dangling symlinks count as taken so they are never overwritten.
+pub fn unique_path(dir: &Path, name: impl AsRef<Path>) -> PathBuf {
+    let name = name.as_ref();
+    let path = dir.join(name);
+    if path.symlink_metadata().is_err() {
+        return path;
+    }
+    let stem = name.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = name
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| dir.join(format!("{stem}_{n}{ext}")))
+        .find(|path| path.symlink_metadata().is_err())
+        .expect("unbounded range")
+}
+
+/// `file://` URI of an absolute path, percent-encoding everything but unreserved characters
+#[cfg(unix)]
+#[must_use]
+pub fn path_to_uri(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut uri = String::from("file://");
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    uri
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +97,27 @@ mod tests {
         let result = copy_dir_recursively(src, dst);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_unique_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "").unwrap();
+        std::fs::write(dir.path().join("a_1.txt"), "").unwrap();
+        assert_eq!(unique_path(dir.path(), "a.txt"), dir.path().join("a_2.txt"));
+        std::fs::write(dir.path().join(".hidden"), "").unwrap();
+        assert_eq!(
+            unique_path(dir.path(), ".hidden"),
+            dir.path().join(".hidden_1")
+        );
+        assert_eq!(unique_path(dir.path(), "b"), dir.path().join("b"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_path_to_uri() {
+        assert_eq!(
+            path_to_uri(Path::new("/home/me/My Files/ä.txt")),
+            "file:///home/me/My%20Files/%C3%A4.txt"
+        );
+    }
 }
@@ -0,0 +1,181 @@
+//! Flattened listing of every file under a directory
+//!
+//! A background thread walks the tree and sends the files it finds in batches,
+//! each named by its path relative to the root. The walk stops after
+//! [`MAX_ENTRIES`] files or once the [`FlattenWalk`] is dropped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::models::dir_entry::DirEntry;
+use crate::models::tab::read_entry;
+
+/// Most files listed, the walk stops there
+pub const MAX_ENTRIES: usize = 100_000;
+
+const BATCH_SIZE: usize = 500;
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Recursive listing in progress or done
+#[derive(Debug)]
+pub struct FlattenWalk {
+    /// Directory being listed
+    pub root: PathBuf,
+    receiver: Receiver<Vec<DirEntry>>,
+    cancel: Arc<AtomicBool>,
+    truncated: Arc<AtomicBool>,
+    /// Files received so far
+    pub count: usize,
+    /// The walk finished, was cancelled or hit the limit
+    pub done: bool,
+}
+
+impl FlattenWalk {
+    /// Start listing the files under `root`
+    #[must_use]
+    pub fn start(root: PathBuf, show_hidden: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let truncated = Arc::new(AtomicBool::new(false));
+        {
+            let root = root.clone();
+            let cancel = cancel.clone();
+            let truncated = truncated.clone();
+            thread::spawn(move || walk(&root, show_hidden, &cancel, &truncated, &sender));
+        }
+        Self {
+            root,
+            receiver,
+            cancel,
+            truncated,
+            count: 0,
+            done: false,
+        }
+    }
+
+    /// Files found since the last call
+    pub fn poll(&mut self) -> Vec<DirEntry> {
+        let mut entries = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(batch) => entries.extend(batch),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        self.count += entries.len();
+        entries
+    }
+
+    /// The walk stopped at [`MAX_ENTRIES`] with files left
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for FlattenWalk {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+fn walk(
+    root: &Path,
+    show_hidden: bool,
+    cancel: &AtomicBool,
+    truncated: &AtomicBool,
+    sender: &Sender<Vec<DirEntry>>,
+) {
+    let mut batch = Vec::new();
+    let mut last_sent = Instant::now();
+    let mut count = 0;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            if cancel.load(Ordering::Relaxed) {
+                return;
+            }
+            let Some(mut entry) = read_entry(&entry, show_hidden) else {
+                continue;
+            };
+            if entry.is_dir {
+                // Symlinked directories may loop back into the tree
+                if !entry.is_symlink {
+                    stack.push(entry.meta.path.clone());
+                }
+                continue;
+            }
+            if count == MAX_ENTRIES {
+                truncated.store(true, Ordering::Relaxed);
+                let _ = sender.send(batch);
+                return;
+            }
+            if let Ok(relative) = entry.meta.path.strip_prefix(root) {
+                entry.name = relative.to_string_lossy().into_owned();
+            }
+            batch.push(entry);
+            count += 1;
+            if batch.len() >= BATCH_SIZE || last_sent.elapsed() >= BATCH_INTERVAL {
+                if sender.send(std::mem::take(&mut batch)).is_err() {
+                    return;
+                }
+                last_sent = Instant::now();
+            }
+        }
+    }
+    let _ = sender.send(batch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(walk: &mut FlattenWalk) -> Vec<String> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut names = Vec::new();
+        while !walk.done {
+            assert!(Instant::now() < deadline, "Walk should finish");
+            names.extend(walk.poll().into_iter().map(|entry| entry.name));
+            thread::sleep(Duration::from_millis(10));
+        }
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_walk_lists_nested_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::create_dir_all(root.join("empty")).unwrap();
+        fs::write(root.join("one.txt"), "").unwrap();
+        fs::write(root.join("a/two.txt"), "").unwrap();
+        fs::write(root.join("a/b/three.txt"), "").unwrap();
+        fs::write(root.join("a/.hidden"), "").unwrap();
+
+        let mut walk = FlattenWalk::start(root.to_path_buf(), false);
+        let expected: Vec<String> = [
+            Path::new("a").join("b").join("three.txt"),
+            Path::new("a").join("two.txt"),
+            PathBuf::from("one.txt"),
+        ]
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+        assert_eq!(collect(&mut walk), expected);
+        assert_eq!(walk.count, 3);
+        assert!(!walk.is_truncated());
+
+        let mut walk = FlattenWalk::start(root.to_path_buf(), true);
+        assert_eq!(collect(&mut walk).len(), 4);
+    }
+}
@@ -1,7 +1,128 @@
 use chrono::{DateTime, Local};
 use humansize::{BINARY, format_size as humansize_format};
+use std::borrow::Cow;
+use std::sync::RwLock;
 use std::time::SystemTime;
 
+/// Separators of numbers shown in the UI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub thousands: Cow<'static, str>,
+    pub decimal: Cow<'static, str>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+impl NumberFormat {
+    pub const ENGLISH: Self = Self::new(",", ".");
+
+    const fn new(thousands: &'static str, decimal: &'static str) -> Self {
+        Self {
+            thousands: Cow::Borrowed(thousands),
+            decimal: Cow::Borrowed(decimal),
+        }
+    }
+
+    /// Separators used by a POSIX locale name like `de_DE.UTF-8`
+    #[must_use]
+    pub fn from_locale(locale: &str) -> Self {
+        let name = locale.split(['.', '@']).next().unwrap_or_default();
+        let mut parts = name.split(['_', '-']);
+        let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+        match (language.as_str(), region.as_str()) {
+            ("de" | "it", "CH") => Self::new("'", "."),
+            (
+                "de" | "es" | "it" | "nl" | "pt" | "da" | "tr" | "id" | "el" | "ro" | "sl" | "hr",
+                _,
+            ) => Self::new(".", ","),
+            // No-break space, keeping a number on one line
+            (
+                "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "uk" | "hu"
+                | "bg",
+                _,
+            ) => Self::new("\u{a0}", ","),
+            _ => Self::ENGLISH,
+        }
+    }
+
+    /// Separators of the locale set in the environment
+    #[must_use]
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .map_or_else(Self::default, |locale| Self::from_locale(&locale))
+    }
+
+    /// Format an integer with thousands separators
+    #[must_use]
+    pub fn format_count(&self, n: u64) -> String {
+        self.group_digits(&n.to_string())
+    }
+
+    /// Format a size in bytes into a human-readable string
+    #[must_use]
+    pub fn format_size(&self, size: u64) -> String {
+        let formatted = humansize_format(size, BINARY);
+        let Some((number, unit)) = formatted.split_once(' ') else {
+            return formatted;
+        };
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (number, None),
+        };
+        let mut result = self.group_digits(integer);
+        if let Some(fraction) = fraction {
+            result.push_str(&self.decimal);
+            result.push_str(fraction);
+        }
+        result.push(' ');
+        result.push_str(unit);
+        result
+    }
+
+    fn group_digits(&self, digits: &str) -> String {
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                result.push_str(&self.thousands);
+            }
+            result.push(digit);
+        }
+        result
+    }
+}
+
+/// Format used everywhere in the UI, `None` until set or first used
+static NUMBER_FORMAT: RwLock<Option<NumberFormat>> = RwLock::new(None);
+
+/// Use `format` for the numbers shown from now on
+pub fn set_number_format(format: NumberFormat) {
+    *NUMBER_FORMAT.write().unwrap_or_else(|e| e.into_inner()) = Some(format);
+}
+
+/// Format of the numbers shown in the UI, the environment's locale unless
+/// overridden in the config
+pub fn number_format() -> NumberFormat {
+    if let Some(format) = &*NUMBER_FORMAT.read().unwrap_or_else(|e| e.into_inner()) {
+        return format.clone();
+    }
+    let format = NumberFormat::from_env();
+    set_number_format(format.clone());
+    format
+}
+
+/// Formats a count with the thousands separator of the UI
+pub fn format_count(n: u64) -> String {
+    number_format().format_count(n)
+}
+
 /// Formats a SystemTime into a string with the format "%Y-%m-%d %H:%M:%S"
 pub fn format_modified(modified: SystemTime) -> String {
     DateTime::<Local>::from(modified)
@@ -15,7 +136,7 @@ pub fn format_size(size: u64, is_dir: bool) -> String {
     if is_dir {
         String::new()
     } else {
-        humansize_format(size, BINARY)
+        number_format().format_size(size)
     }
 }
 
@@ -40,4 +161,41 @@ mod tests {
         assert_eq!(format_size(1024, false), "1 KiB");
         assert_eq!(format_size(100, true), "");
     }
+
+    #[test]
+    fn test_number_format_from_locale() {
+        assert_eq!(
+            NumberFormat::from_locale("en_US.UTF-8"),
+            NumberFormat::ENGLISH
+        );
+        assert_eq!(NumberFormat::from_locale("C"), NumberFormat::ENGLISH);
+        assert_eq!(
+            NumberFormat::from_locale("de_DE.UTF-8"),
+            NumberFormat::new(".", ",")
+        );
+        assert_eq!(
+            NumberFormat::from_locale("de_CH"),
+            NumberFormat::new("'", ".")
+        );
+        assert_eq!(
+            NumberFormat::from_locale("fr_FR@euro"),
+            NumberFormat::new("\u{a0}", ",")
+        );
+    }
+
+    #[test]
+    fn test_number_format_separators() {
+        let english = NumberFormat::ENGLISH;
+        assert_eq!(english.format_count(0), "0");
+        assert_eq!(english.format_count(999), "999");
+        assert_eq!(english.format_count(1000), "1,000");
+        assert_eq!(english.format_count(1_234_567), "1,234,567");
+        assert_eq!(english.format_size(1000), "1,000 B");
+        assert_eq!(english.format_size(1536), "1.50 KiB");
+
+        let german = NumberFormat::from_locale("de_DE");
+        assert_eq!(german.format_count(1_234_567), "1.234.567");
+        assert_eq!(german.format_size(1536), "1,50 KiB");
+        assert_eq!(german.format_size(1023 * 1024 * 1024), "1.023 MiB");
+    }
 }
@@ -0,0 +1,260 @@
+//! Searching the contents of the files under a directory
+//!
+//! Files are walked on the calling thread and searched in parallel. Like
+//! ripgrep, binary files are skipped and symlinks aren't followed. The search
+//! stops after [`MAX_MATCHES`] matching lines.
+
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use regex::{Regex, RegexBuilder};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Most matching lines reported by [`search`]
+pub const MAX_MATCHES: usize = 10_000;
+/// Lines shown before and after a match
+pub const CONTEXT_LINES: usize = 1;
+/// Larger files are skipped
+const MAX_FILE_SIZE: u64 = 32 * 1024 * 1024;
+/// Bytes checked for a NUL to tell binary files apart
+const BINARY_CHECK_LEN: usize = 8192;
+/// Longer lines are cut, minified files would flood the results
+const MAX_LINE_LEN: usize = 300;
+
+/// A matching line and the lines around it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+    pub path: PathBuf,
+    /// Starts at 1
+    pub line_number: usize,
+    pub line: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Build the matcher for `pattern`, taken literally unless `regex` is set
+pub fn build_matcher(
+    pattern: &str,
+    regex: bool,
+    case_insensitive: bool,
+) -> Result<Regex, regex::Error> {
+    let pattern = if regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+}
+
+fn truncate_line(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_LEN) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// Matching lines of the file at `path`, none for binary or large files
+pub fn search_file(path: &Path, matcher: &Regex) -> io::Result<Vec<GrepMatch>> {
+    if std::fs::metadata(path)?.len() > MAX_FILE_SIZE {
+        return Ok(Vec::new());
+    }
+    let bytes = std::fs::read(path)?;
+    if bytes[..bytes.len().min(BINARY_CHECK_LEN)].contains(&0) {
+        return Ok(Vec::new());
+    }
+    let content = String::from_utf8_lossy(&bytes);
+    let lines: Vec<&str> = content.lines().collect();
+    Ok(lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| matcher.is_match(line))
+        .map(|(index, line)| GrepMatch {
+            path: path.to_path_buf(),
+            line_number: index + 1,
+            line: truncate_line(line),
+            before: lines[index.saturating_sub(CONTEXT_LINES)..index]
+                .iter()
+                .map(|line| truncate_line(line))
+                .collect(),
+            after: lines[index + 1..(index + 1 + CONTEXT_LINES).min(lines.len())]
+                .iter()
+                .map(|line| truncate_line(line))
+                .collect(),
+        })
+        .collect())
+}
+
+fn is_hidden(entry: &std::fs::DirEntry) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const HIDDEN_ATTRIBUTE: u32 = 0x2;
+        entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.file_attributes() & HIDDEN_ATTRIBUTE != 0)
+    }
+    #[cfg(not(windows))]
+    {
+        entry.file_name().to_string_lossy().starts_with('.')
+    }
+}
+
+/// Regular files under a directory, walked as they're requested
+struct Files<'a> {
+    dirs: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+    show_hidden: bool,
+    stop: [&'a AtomicBool; 2],
+}
+
+impl Iterator for Files<'_> {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<PathBuf> {
+        loop {
+            if self.stop.iter().any(|stop| stop.load(Ordering::Relaxed)) {
+                return None;
+            }
+            if let Some(file) = self.files.pop() {
+                return Some(file);
+            }
+            let dir = self.dirs.pop()?;
+            for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+                if !self.show_hidden && is_hidden(&entry) {
+                    continue;
+                }
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    self.dirs.push(entry.path());
+                } else if file_type.is_file() {
+                    self.files.push(entry.path());
+                }
+            }
+            // Keep the walk in the order a listing shows
+            self.files.sort_unstable_by(|a, b| b.cmp(a));
+        }
+    }
+}
+
+/// Search the files under `root`, handing the matches of each file to
+/// `on_matches` as they're found
+///
+/// Returns whether the search stopped at [`MAX_MATCHES`].
+pub fn search(
+    root: &Path,
+    matcher: &Regex,
+    show_hidden: bool,
+    cancel: &AtomicBool,
+    on_matches: impl Fn(Vec<GrepMatch>) + Sync,
+) -> bool {
+    let count = AtomicUsize::new(0);
+    let truncated = AtomicBool::new(false);
+    let files = Files {
+        dirs: vec![root.to_path_buf()],
+        files: Vec::new(),
+        show_hidden,
+        stop: [cancel, &truncated],
+    };
+    files.par_bridge().for_each(|path| {
+        // Unreadable files are skipped like binary ones
+        let Ok(mut matches) = search_file(&path, matcher) else {
+            return;
+        };
+        if matches.is_empty() || cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let previous = count.fetch_add(matches.len(), Ordering::Relaxed);
+        if previous + matches.len() > MAX_MATCHES {
+            truncated.store(true, Ordering::Relaxed);
+            matches.truncate(MAX_MATCHES.saturating_sub(previous));
+        }
+        if !matches.is_empty() {
+            on_matches(matches);
+        }
+    });
+    truncated.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_search_file_with_context() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, "one\nTwo fish\nthree\nfour\nred fish\n").unwrap();
+
+        let matcher = build_matcher("fish", false, false).unwrap();
+        let matches = search_file(&path, &matcher).unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].line_number, 2);
+        assert_eq!(matches[0].line, "Two fish");
+        assert_eq!(matches[0].before, vec!["one"]);
+        assert_eq!(matches[0].after, vec!["three"]);
+        assert_eq!(matches[1].line_number, 5);
+        assert!(matches[1].after.is_empty());
+
+        let matcher = build_matcher("^t(wo|hree)", true, true).unwrap();
+        let lines: Vec<usize> = search_file(&path, &matcher)
+            .unwrap()
+            .iter()
+            .map(|m| m.line_number)
+            .collect();
+        assert_eq!(lines, vec![2, 3]);
+
+        // Patterns are literal unless regex is requested
+        assert!(build_matcher("a(b", false, false).is_ok());
+        assert!(build_matcher("a(b", true, false).is_err());
+
+        let binary = temp_dir.path().join("data.bin");
+        fs::write(&binary, b"fish\0fish").unwrap();
+        let matcher = build_matcher("fish", false, false).unwrap();
+        assert!(search_file(&binary, &matcher).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_walks_tree() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("a.txt"), "needle\n").unwrap();
+        fs::write(root.join("src/nested/b.rs"), "// needle\nneedle\n").unwrap();
+        fs::write(root.join("src/c.rs"), "haystack\n").unwrap();
+        fs::write(root.join(".git/config"), "needle\n").unwrap();
+
+        let matcher = build_matcher("needle", false, false).unwrap();
+        let found = Mutex::new(Vec::new());
+        let collect = |matches: Vec<GrepMatch>| {
+            found
+                .lock()
+                .unwrap()
+                .extend(matches.into_iter().map(|m| (m.path, m.line_number)));
+        };
+        let truncated = search(root, &matcher, false, &AtomicBool::new(false), collect);
+        assert!(!truncated);
+        let mut found = std::mem::take(&mut *found.lock().unwrap());
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                (root.join("a.txt"), 1),
+                (root.join("src/nested/b.rs"), 1),
+                (root.join("src/nested/b.rs"), 2),
+            ]
+        );
+
+        let count = AtomicUsize::new(0);
+        search(root, &matcher, true, &AtomicBool::new(false), |matches| {
+            count.fetch_add(matches.len(), Ordering::Relaxed);
+        });
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
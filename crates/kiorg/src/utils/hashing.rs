@@ -0,0 +1,364 @@
+//! File hashing shared by the features comparing or verifying file content
+//!
+//! Files are read in chunks, feeding every requested algorithm in a single
+//! pass, with throttled progress reports and cancellation between chunks.
+//! Hashing is mostly bound by disk reads, so the number of files read at once
+//! is capped across the whole app: concurrent jobs share the disk instead of
+//! thrashing it.
+
+use md5::Md5;
+use rayon::prelude::*;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+pub const READ_BUFFER_SIZE: usize = 1024 * 1024;
+/// Publish progress at most this often to avoid repainting on every read
+pub const PROGRESS_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+/// Files read at once across all hashing jobs
+const MAX_CONCURRENT_FILES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    Blake3,
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [Self; 4] = [Self::Blake3, Self::Md5, Self::Sha1, Self::Sha256];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Blake3 => "BLAKE3",
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+        }
+    }
+
+    fn hasher(self) -> Hasher {
+        match self {
+            Self::Blake3 => Hasher::Blake3(Box::default()),
+            Self::Md5 => Hasher::Md5(Md5::new()),
+            Self::Sha1 => Hasher::Sha1(Sha1::new()),
+            Self::Sha256 => Hasher::Sha256(Sha256::new()),
+        }
+    }
+}
+
+enum Hasher {
+    Blake3(Box<blake3::Hasher>),
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha256(Sha256),
+}
+
+impl Hasher {
+    fn update(&mut self, chunk: &[u8]) {
+        match self {
+            Self::Blake3(hasher) => {
+                hasher.update(chunk);
+            }
+            Self::Md5(hasher) => hasher.update(chunk),
+            Self::Sha1(hasher) => hasher.update(chunk),
+            Self::Sha256(hasher) => hasher.update(chunk),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Self::Md5(hasher) => hex(&hasher.finalize()),
+            Self::Sha1(hasher) => hex(&hasher.finalize()),
+            Self::Sha256(hasher) => hex(&hasher.finalize()),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hex digests of a file, in the order the algorithms were requested
+pub type Digests = Vec<(HashAlgorithm, String)>;
+
+/// Progress of [`hash_files`], summed over all files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashProgress {
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    pub files_done: usize,
+    pub total_files: usize,
+}
+
+/// Counting semaphore limiting the files read at once
+struct Slots {
+    used: Mutex<usize>,
+    released: Condvar,
+}
+
+static SLOTS: Slots = Slots {
+    used: Mutex::new(0),
+    released: Condvar::new(),
+};
+
+/// A reading slot, given back when dropped
+struct SlotGuard;
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        let mut used = SLOTS.used.lock().unwrap_or_else(|e| e.into_inner());
+        *used -= 1;
+        SLOTS.released.notify_one();
+    }
+}
+
+/// Wait for a reading slot, `None` if cancelled while waiting
+fn acquire_slot(cancel: &AtomicBool) -> Option<SlotGuard> {
+    let mut used = SLOTS.used.lock().unwrap_or_else(|e| e.into_inner());
+    while *used >= MAX_CONCURRENT_FILES {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        // Wake up now and then to notice cancellation
+        used = SLOTS
+            .released
+            .wait_timeout(used, PROGRESS_UPDATE_INTERVAL)
+            .unwrap_or_else(|e| e.into_inner())
+            .0;
+    }
+    *used += 1;
+    Some(SlotGuard)
+}
+
+/// Hashes data read by the caller, e.g. while copying a file
+///
+/// Holds a reading slot until dropped, like [`hash_file`] while it reads.
+pub struct StreamHasher {
+    hashers: Vec<(HashAlgorithm, Hasher)>,
+    _slot: SlotGuard,
+}
+
+impl StreamHasher {
+    /// Wait for a reading slot, `None` if cancelled while waiting
+    pub fn new(algorithms: &[HashAlgorithm], cancel: &AtomicBool) -> Option<Self> {
+        let slot = acquire_slot(cancel)?;
+        Some(Self {
+            hashers: algorithms.iter().map(|a| (*a, a.hasher())).collect(),
+            _slot: slot,
+        })
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        for (_, hasher) in &mut self.hashers {
+            hasher.update(chunk);
+        }
+    }
+
+    pub fn finalize(self) -> Digests {
+        self.hashers
+            .into_iter()
+            .map(|(algorithm, hasher)| (algorithm, hasher.finalize_hex()))
+            .collect()
+    }
+}
+
+/// Hash `path` with `algorithms` in a single pass
+///
+/// `on_progress` is called with the bytes read so far and the file size.
+/// Returns `None` if the computation was cancelled.
+pub fn hash_file(
+    path: &Path,
+    algorithms: &[HashAlgorithm],
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Option<Result<Digests, String>> {
+    let mut hasher = StreamHasher::new(algorithms, cancel)?;
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return Some(Err(format!("Failed to open file: {e}"))),
+    };
+    let total = file.metadata().map(|m| m.len()).unwrap_or_default();
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    let mut done = 0u64;
+    let mut last_update = Instant::now();
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let read = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Some(Err(format!("Failed to read file: {e}"))),
+        };
+        hasher.update(&buffer[..read]);
+        done += read as u64;
+        if last_update.elapsed() >= PROGRESS_UPDATE_INTERVAL {
+            on_progress(done, total.max(done));
+            last_update = Instant::now();
+        }
+    }
+
+    Some(Ok(hasher.finalize()))
+}
+
+/// Hash many files in parallel
+///
+/// Results are in the order of `paths`. Returns `None` if cancelled.
+pub fn hash_files(
+    paths: &[PathBuf],
+    algorithms: &[HashAlgorithm],
+    cancel: &AtomicBool,
+    on_progress: impl Fn(HashProgress) + Sync,
+) -> Option<Vec<Result<Digests, String>>> {
+    let total_bytes: u64 = paths
+        .iter()
+        .filter_map(|path| path.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    let bytes_done = AtomicU64::new(0);
+    let files_done = AtomicU64::new(0);
+    let report = || {
+        let bytes = bytes_done.load(Ordering::Relaxed);
+        on_progress(HashProgress {
+            bytes_done: bytes,
+            total_bytes: total_bytes.max(bytes),
+            files_done: files_done.load(Ordering::Relaxed) as usize,
+            total_files: paths.len(),
+        });
+    };
+
+    // Waiting for a reading slot blocks the thread, keep that off the global
+    // pool the UI uses
+    let hash_all = || -> Vec<_> {
+        paths
+            .par_iter()
+            .map(|path| {
+                let mut reported = 0;
+                let result = hash_file(path, algorithms, cancel, |done, _| {
+                    bytes_done.fetch_add(done - reported, Ordering::Relaxed);
+                    reported = done;
+                    report();
+                })?;
+                let size = path.metadata().map(|m| m.len()).unwrap_or_default();
+                bytes_done.fetch_add(size.saturating_sub(reported), Ordering::Relaxed);
+                files_done.fetch_add(1, Ordering::Relaxed);
+                report();
+                Some(result)
+            })
+            .collect()
+    };
+    let results = match rayon::ThreadPoolBuilder::new()
+        .num_threads(MAX_CONCURRENT_FILES)
+        .build()
+    {
+        Ok(pool) => pool.install(hash_all),
+        Err(_) => hash_all(),
+    };
+
+    if cancel.load(Ordering::Relaxed) {
+        return None;
+    }
+    results.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("abc.txt");
+        std::fs::write(&file, "abc").unwrap();
+
+        let cancel = AtomicBool::new(false);
+        let digests = hash_file(&file, &HashAlgorithm::ALL, &cancel, |_, _| {})
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            digests,
+            vec![
+                (
+                    HashAlgorithm::Blake3,
+                    "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85".to_string()
+                ),
+                (
+                    HashAlgorithm::Md5,
+                    "900150983cd24fb0d6963f7d28e17f72".to_string()
+                ),
+                (
+                    HashAlgorithm::Sha1,
+                    "a9993e364706816aba3e25717850c26c9cd0d89d".to_string()
+                ),
+                (
+                    HashAlgorithm::Sha256,
+                    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()
+                ),
+            ]
+        );
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(hash_file(&file, &HashAlgorithm::ALL, &cancel, |_, _| {}).is_none());
+        let missing = hash_file(
+            &temp_dir.path().join("missing"),
+            &[HashAlgorithm::Blake3],
+            &AtomicBool::new(false),
+            |_, _| {},
+        );
+        assert!(matches!(missing, Some(Err(_))));
+
+        let mut stream = StreamHasher::new(&HashAlgorithm::ALL, &AtomicBool::new(false)).unwrap();
+        stream.update(b"a");
+        stream.update(b"bc");
+        assert_eq!(stream.finalize(), digests);
+    }
+
+    #[test]
+    fn test_hash_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let paths: Vec<_> = (0..10)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("{i}.bin"));
+                std::fs::write(&path, vec![i as u8; 1000 * (i + 1)]).unwrap();
+                path
+            })
+            .collect();
+
+        // Reports of different threads may arrive out of order
+        let last = Mutex::new(HashProgress::default());
+        let results = hash_files(
+            &paths,
+            &[HashAlgorithm::Blake3],
+            &AtomicBool::new(false),
+            |progress| {
+                let mut last = last.lock().unwrap();
+                if progress.files_done >= last.files_done && progress.bytes_done >= last.bytes_done
+                {
+                    *last = progress;
+                }
+            },
+        )
+        .unwrap();
+        assert_eq!(results.len(), paths.len());
+        for (path, result) in paths.iter().zip(results) {
+            let expected = blake3::hash(&std::fs::read(path).unwrap());
+            assert_eq!(result.unwrap()[0].1, expected.to_hex().to_string());
+        }
+        let progress = last.into_inner().unwrap();
+        assert_eq!(progress.files_done, 10);
+        assert_eq!(progress.total_files, 10);
+        assert_eq!(progress.bytes_done, 55_000);
+
+        assert!(hash_files(&paths, &HashAlgorithm::ALL, &AtomicBool::new(true), |_| {}).is_none());
+    }
+}
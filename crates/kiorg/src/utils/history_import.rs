@@ -0,0 +1,298 @@
+//! Import the directories known to zoxide or autojump into the visit history,
+//! so teleport ranks them right away
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::config;
+use crate::visit_history::{self, VisitHistoryEntry};
+
+/// Tool whose database is imported
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Zoxide,
+    Autojump,
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "zoxide" => Ok(Self::Zoxide),
+            "autojump" => Ok(Self::Autojump),
+            _ => Err(format!(
+                "unknown history source '{name}', expected zoxide or autojump"
+            )),
+        }
+    }
+}
+
+impl Source {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Zoxide => "zoxide",
+            Self::Autojump => "autojump",
+        }
+    }
+
+    /// Where the tool keeps its database by default
+    #[must_use]
+    pub fn default_path(self) -> Option<PathBuf> {
+        match self {
+            Self::Zoxide => match std::env::var_os("_ZO_DATA_DIR") {
+                Some(dir) => Some(PathBuf::from(dir).join("db.zo")),
+                None => Some(dirs::data_local_dir()?.join("zoxide").join("db.zo")),
+            },
+            #[cfg(target_os = "macos")]
+            Self::Autojump => Some(
+                dirs::home_dir()?
+                    .join("Library")
+                    .join("autojump")
+                    .join("autojump.txt"),
+            ),
+            #[cfg(not(target_os = "macos"))]
+            Self::Autojump => Some(dirs::data_dir()?.join("autojump").join("autojump.txt")),
+        }
+    }
+
+    /// Directories in the database at `path`
+    pub fn read(self, path: &Path) -> Result<Vec<VisitHistoryEntry>, String> {
+        match self {
+            Self::Zoxide => {
+                let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+                parse_zoxide(&bytes)
+            }
+            Self::Autojump => {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+                // autojump doesn't record when a directory was visited
+                let accessed_ts = std::fs::metadata(path)
+                    .and_then(|meta| meta.modified())
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or_else(visit_history::now_ts, |age| age.as_secs());
+                Ok(parse_autojump(&content, accessed_ts))
+            }
+        }
+    }
+}
+
+/// Visit count standing in for a zoxide or autojump score
+fn score_to_count(score: f64) -> u64 {
+    if score.is_finite() && score > 1.0 {
+        score.round() as u64
+    } else {
+        1
+    }
+}
+
+/// Read zoxide's `db.zo`: a version number followed by the directories with
+/// their score and last access time, encoded with bincode
+pub fn parse_zoxide(bytes: &[u8]) -> Result<Vec<VisitHistoryEntry>, String> {
+    const VERSION: u32 = 3;
+
+    let mut reader = Reader { bytes };
+    let version = u32::from_le_bytes(reader.take()?);
+    if version != VERSION {
+        return Err(format!("unsupported zoxide database version {version}"));
+    }
+    let count = u64::from_le_bytes(reader.take()?);
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let len = usize::try_from(u64::from_le_bytes(reader.take()?))
+            .map_err(|_| "corrupt zoxide database".to_string())?;
+        let path = std::str::from_utf8(reader.take_slice(len)?)
+            .map_err(|_| "invalid path in zoxide database".to_string())?;
+        let rank = f64::from_le_bytes(reader.take()?);
+        let accessed_ts = u64::from_le_bytes(reader.take()?);
+        entries.push(VisitHistoryEntry {
+            path: PathBuf::from(path),
+            accessed_ts,
+            count: score_to_count(rank),
+        });
+    }
+    Ok(entries)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take_slice(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < len {
+            return Err("truncated zoxide database".to_string());
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let slice = self.take_slice(N)?;
+        Ok(slice.try_into().expect("slice has N bytes"))
+    }
+}
+
+/// Read autojump's `autojump.txt`, lines of a weight and a path separated by
+/// a tab
+#[must_use]
+pub fn parse_autojump(content: &str, accessed_ts: u64) -> Vec<VisitHistoryEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (weight, path) = line.split_once('\t')?;
+            let weight = weight.trim().parse::<f64>().ok()?;
+            Some(VisitHistoryEntry {
+                path: PathBuf::from(path),
+                accessed_ts,
+                count: score_to_count(weight),
+            })
+        })
+        .collect()
+}
+
+/// Add the imported directories that still exist to `history`, adding up the
+/// visit counts of directories already in it
+///
+/// Returns the number of directories imported.
+pub fn merge(
+    history: &mut HashMap<PathBuf, VisitHistoryEntry>,
+    imported: Vec<VisitHistoryEntry>,
+    history_config: &config::History,
+) -> usize {
+    let mut merged = 0;
+    for entry in imported {
+        if history_config.is_excluded(&entry.path) || !entry.path.is_dir() {
+            continue;
+        }
+        merged += 1;
+        match history.get_mut(&entry.path) {
+            Some(existing) => {
+                existing.count += entry.count;
+                existing.accessed_ts = existing.accessed_ts.max(entry.accessed_ts);
+            }
+            None => {
+                history.insert(entry.path.clone(), entry);
+            }
+        }
+    }
+    visit_history::prune_history(history, history_config.max_entries());
+    merged
+}
+
+/// Import the database of `source`, at `path` or its default location, into
+/// the saved visit history
+///
+/// Returns the number of directories imported.
+pub fn import(
+    source: Source,
+    path: Option<&Path>,
+    config_dir_override: Option<&Path>,
+) -> Result<usize, String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => source
+            .default_path()
+            .ok_or_else(|| format!("can't find the {} database", source.name()))?,
+    };
+    let imported = source.read(&path)?;
+
+    let history_config = config::load_config_with_override(config_dir_override)
+        .ok()
+        .and_then(|config| config.history)
+        .unwrap_or_default();
+    let mut history =
+        visit_history::load_visit_history(config_dir_override).map_err(|e| e.to_string())?;
+    let merged = merge(&mut history, imported, &history_config);
+    visit_history::save_visit_history(&history, config_dir_override).map_err(|e| e.to_string())?;
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zoxide_db(dirs: &[(&str, f64, u64)]) -> Vec<u8> {
+        let mut bytes = 3u32.to_le_bytes().to_vec();
+        bytes.extend((dirs.len() as u64).to_le_bytes());
+        for (path, rank, accessed_ts) in dirs {
+            bytes.extend((path.len() as u64).to_le_bytes());
+            bytes.extend(path.as_bytes());
+            bytes.extend(rank.to_le_bytes());
+            bytes.extend(accessed_ts.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_parse_zoxide() {
+        let bytes = zoxide_db(&[("/home/user/kiorg", 12.4, 1_700_000_000), ("/tmp", 0.5, 42)]);
+        let entries = parse_zoxide(&bytes).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, PathBuf::from("/home/user/kiorg"));
+        assert_eq!(entries[0].count, 12);
+        assert_eq!(entries[0].accessed_ts, 1_700_000_000);
+        assert_eq!(entries[1].count, 1);
+
+        assert!(parse_zoxide(&bytes[..bytes.len() - 1]).is_err());
+        let mut old_version = bytes.clone();
+        old_version[0] = 2;
+        assert!(parse_zoxide(&old_version).is_err());
+    }
+
+    #[test]
+    fn test_parse_autojump() {
+        let content = "22.4\t/home/user/kiorg\nnot a line\n10.0\t/home/user/with\ttab\n";
+        let entries = parse_autojump(content, 7);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, PathBuf::from("/home/user/kiorg"));
+        assert_eq!(entries[0].count, 22);
+        assert_eq!(entries[0].accessed_ts, 7);
+        assert_eq!(entries[1].path, PathBuf::from("/home/user/with\ttab"));
+    }
+
+    #[test]
+    fn test_merge_adds_up_existing_visits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("dir");
+        let excluded = temp_dir.path().join("secret");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::create_dir(&excluded).unwrap();
+
+        let mut history = HashMap::new();
+        history.insert(
+            dir.clone(),
+            VisitHistoryEntry {
+                path: dir.clone(),
+                accessed_ts: 100,
+                count: 2,
+            },
+        );
+        let imported = |path: &Path| VisitHistoryEntry {
+            path: path.to_path_buf(),
+            accessed_ts: 50,
+            count: 5,
+        };
+        let history_config = config::History {
+            exclude: vec![excluded.clone()],
+            ..config::History::default()
+        };
+        let merged = merge(
+            &mut history,
+            vec![
+                imported(&dir),
+                imported(&excluded),
+                imported(&temp_dir.path().join("missing")),
+            ],
+            &history_config,
+        );
+        assert_eq!(merged, 1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[&dir].count, 7);
+        assert_eq!(history[&dir].accessed_ts, 100);
+    }
+}
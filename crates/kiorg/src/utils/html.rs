@@ -0,0 +1,520 @@
+//! Minimal HTML to formatted text conversion, e.g. for ebook chapters
+//!
+//! This is not a full HTML parser. It keeps the text structure that matters
+//! for reading: paragraphs, headings, lists, quotes, preformatted text,
+//! links, images and bold, italic and code spans. Everything else is
+//! flattened to text. Scripts and styles are dropped.
+
+/// Kind of a block of text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Paragraph,
+    /// Heading level from 1 to 6
+    Heading(u8),
+    ListItem,
+    Quote,
+    Preformatted,
+}
+
+/// A run of text sharing the same style
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    /// Target of the enclosing `<a href>`, as written in the document
+    pub link: Option<String>,
+    /// Source of an `<img>`, the text is its alt text in brackets
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub spans: Vec<Span>,
+}
+
+impl Block {
+    /// Plain text of the block
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    /// Content of the `<title>` element
+    pub title: Option<String>,
+    pub blocks: Vec<Block>,
+}
+
+impl Document {
+    /// Text of the first heading, falling back to the document title
+    #[must_use]
+    pub fn heading(&self) -> Option<String> {
+        self.blocks
+            .iter()
+            .find(|block| matches!(block.kind, BlockKind::Heading(_)))
+            .map(Block::text)
+            .or_else(|| self.title.clone())
+            .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|text| !text.is_empty())
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    doc: Document,
+    spans: Vec<Span>,
+    heading: Option<u8>,
+    list_item: bool,
+    quote: usize,
+    pre: usize,
+    bold: usize,
+    italic: usize,
+    code: usize,
+    link: Option<String>,
+    /// Inside `<head>`, `<script>` or `<style>`
+    hidden: usize,
+    title: Option<String>,
+    /// The last character of the block is not whitespace
+    after_text: bool,
+}
+
+impl Parser {
+    fn kind(&self) -> BlockKind {
+        if let Some(level) = self.heading {
+            BlockKind::Heading(level)
+        } else if self.pre > 0 {
+            BlockKind::Preformatted
+        } else if self.list_item {
+            BlockKind::ListItem
+        } else if self.quote > 0 {
+            BlockKind::Quote
+        } else {
+            BlockKind::Paragraph
+        }
+    }
+
+    fn span(&self, text: &str) -> Span {
+        Span {
+            text: text.to_string(),
+            bold: self.bold > 0,
+            italic: self.italic > 0,
+            code: self.code > 0,
+            link: self.link.clone(),
+            image: None,
+        }
+    }
+
+    fn push_str(&mut self, text: &str) {
+        let span = self.span(text);
+        match self.spans.last_mut() {
+            Some(last)
+                if last.image.is_none()
+                    && last.bold == span.bold
+                    && last.italic == span.italic
+                    && last.code == span.code
+                    && last.link == span.link =>
+            {
+                last.text.push_str(text);
+            }
+            _ => self.spans.push(span),
+        }
+    }
+
+    fn image(&mut self, src: Option<String>, alt: Option<String>) {
+        if self.hidden > 0 {
+            return;
+        }
+        let alt = alt.map(|alt| alt.trim().to_string()).unwrap_or_default();
+        let text = if alt.is_empty() {
+            String::new()
+        } else {
+            format!("[{alt}]")
+        };
+        match src.filter(|src| !src.trim().is_empty()) {
+            Some(src) => {
+                self.after_text = true;
+                self.spans.push(Span {
+                    image: Some(src.trim().to_string()),
+                    ..self.span(&text)
+                });
+            }
+            None => self.text(&text),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.hidden > 0 {
+            if let Some(title) = &mut self.title {
+                title.push_str(text);
+            }
+            return;
+        }
+        if self.pre > 0 {
+            self.push_str(text);
+            return;
+        }
+
+        // Collapse whitespace like a browser does
+        let mut collapsed = String::with_capacity(text.len());
+        for ch in text.chars() {
+            if !ch.is_ascii_whitespace() {
+                self.after_text = true;
+                collapsed.push(ch);
+            } else if self.after_text {
+                self.after_text = false;
+                collapsed.push(' ');
+            }
+        }
+        if !collapsed.is_empty() {
+            self.push_str(&collapsed);
+        }
+    }
+
+    fn line_break(&mut self) {
+        if self.hidden == 0 {
+            self.push_str("\n");
+            self.after_text = false;
+        }
+    }
+
+    /// End the current block
+    fn flush(&mut self) {
+        self.after_text = false;
+        let mut spans = std::mem::take(&mut self.spans);
+        if self.pre == 0
+            && let Some(last) = spans.last_mut()
+        {
+            let trimmed = last.text.trim_end().len();
+            last.text.truncate(trimmed);
+        }
+        spans.retain(|span| !span.text.is_empty() || span.image.is_some());
+        if !spans.is_empty() {
+            self.doc.blocks.push(Block {
+                kind: self.kind(),
+                spans,
+            });
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let name_end = tag
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        let step = |count: &mut usize| {
+            if closing {
+                *count = count.saturating_sub(1);
+            } else if !self_closing {
+                *count += 1;
+            }
+        };
+
+        match name.as_str() {
+            "head" | "script" | "style" => step(&mut self.hidden),
+            "title" => {
+                if closing {
+                    self.doc.title = self.title.take();
+                } else {
+                    self.title = Some(String::new());
+                }
+            }
+            "b" | "strong" => step(&mut self.bold),
+            "i" | "em" | "cite" | "var" => step(&mut self.italic),
+            "code" | "kbd" | "samp" | "tt" => step(&mut self.code),
+            "br" => self.line_break(),
+            "a" => {
+                self.link = if closing {
+                    None
+                } else {
+                    attribute(tag, "href").filter(|href| !href.trim().is_empty())
+                };
+            }
+            "img" => self.image(attribute(tag, "src"), attribute(tag, "alt")),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush();
+                self.heading = (!closing).then(|| name.as_bytes()[1] - b'0');
+            }
+            "li" | "dt" | "dd" => {
+                self.flush();
+                self.list_item = !closing;
+            }
+            "ul" | "ol" | "dl" => {
+                self.flush();
+                self.list_item = false;
+            }
+            "blockquote" => {
+                self.flush();
+                step(&mut self.quote);
+            }
+            "pre" => {
+                self.flush();
+                step(&mut self.pre);
+            }
+            "p" | "div" | "section" | "article" | "header" | "footer" | "aside" | "nav"
+            | "figure" | "figcaption" | "table" | "tr" | "hr" | "body" | "html" => self.flush(),
+            _ => {}
+        }
+    }
+}
+
+/// Value of an attribute in the inside of a tag, e.g. `img src="a.png" alt="A"`
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(index) = rest.find(name) {
+        let before = rest[..index].chars().next_back();
+        let after = rest[index + name.len()..].trim_start();
+        rest = &rest[index + name.len()..];
+        if !before.is_some_and(|c| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next()?;
+        let value = if quote == '"' || quote == '\'' {
+            let value = &value[1..];
+            &value[..value.find(quote)?]
+        } else {
+            value
+                .split(|c: char| c.is_ascii_whitespace())
+                .next()
+                .unwrap_or_default()
+        };
+        return Some(decode_entities(value));
+    }
+    None
+}
+
+/// Decode character references like `&amp;` and `&#8212;`
+#[must_use]
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..=end]);
+        let ch = reference.and_then(|reference| match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            "mdash" => Some('—'),
+            "ndash" => Some('–'),
+            "hellip" => Some('…'),
+            "lsquo" => Some('‘'),
+            "rsquo" => Some('’'),
+            "ldquo" => Some('“'),
+            "rdquo" => Some('”'),
+            "copy" => Some('©'),
+            _ => {
+                let number = reference.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (ch, reference) {
+            (Some(ch), Some(reference)) => {
+                decoded.push(ch);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Decode `%XX` escapes of a URL, invalid escapes are kept as is
+#[must_use]
+pub fn percent_decode(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let (Some(high), Some(low)) = (
+                bytes.get(i + 1).copied().and_then(hex),
+                bytes.get(i + 2).copied().and_then(hex),
+            )
+        {
+            decoded.push((high << 4) | low);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    decoded
+}
+
+/// Convert an HTML or XHTML document into blocks of styled text
+#[must_use]
+pub fn parse(html: &str) -> Document {
+    let mut parser = Parser::default();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        parser.text(&decode_entities(&rest[..start]));
+        rest = &rest[start..];
+
+        let (tag, end) = if let Some(comment) = rest.strip_prefix("<!--") {
+            (None, comment.find("-->").map(|end| end + 7))
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            (None, rest.find('>').map(|end| end + 1))
+        } else {
+            let end = rest.find('>');
+            (end.map(|end| &rest[1..end]), end.map(|end| end + 1))
+        };
+        let Some(end) = end else {
+            // Unterminated tag, drop the rest
+            rest = "";
+            break;
+        };
+        if let Some(tag) = tag {
+            parser.tag(tag.trim());
+        }
+        rest = &rest[end..];
+    }
+    parser.text(&decode_entities(rest));
+    parser.flush();
+    parser.doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> Span {
+        Span {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let doc = parse(
+            r#"<?xml version="1.0"?>
+            <html><head><title>Chapter &amp; Verse</title><style>p { color: red; }</style></head>
+            <body>
+              <h2>One</h2>
+              <p>Some   <b>bold</b> and
+                 <em>italic</em> text.<br/>Next line</p>
+              <!-- a comment -->
+              <ul><li>First</li><li><p>Second</p></li></ul>
+              <blockquote><p>Quoted</p></blockquote>
+              <pre>let x =
+    1;</pre>
+              <p><img src="a.png" alt="A map"/></p>
+            </body></html>"#,
+        );
+
+        assert_eq!(doc.title.as_deref(), Some("Chapter & Verse"));
+        assert_eq!(doc.heading().as_deref(), Some("One"));
+        let kinds: Vec<_> = doc.blocks.iter().map(|block| block.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                BlockKind::Heading(2),
+                BlockKind::Paragraph,
+                BlockKind::ListItem,
+                BlockKind::ListItem,
+                BlockKind::Quote,
+                BlockKind::Preformatted,
+                BlockKind::Paragraph,
+            ]
+        );
+        assert_eq!(
+            doc.blocks[1].spans,
+            vec![
+                span("Some "),
+                Span {
+                    bold: true,
+                    ..span("bold")
+                },
+                span(" and "),
+                Span {
+                    italic: true,
+                    ..span("italic")
+                },
+                span(" text.\nNext line"),
+            ]
+        );
+        assert_eq!(doc.blocks[3].text(), "Second");
+        assert_eq!(doc.blocks[5].text(), "let x =\n    1;");
+        assert_eq!(doc.blocks[6].text(), "[A map]");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &#65;&#x42; &unknown; & c"),
+            "a <b> AB &unknown; & c"
+        );
+    }
+
+    #[test]
+    fn test_links_and_images() {
+        let doc = parse(
+            r#"<p>See <a href="docs/a.html">the <b>docs</b></a>.
+            <img src="logo.png"> <img alt="no source"></p>
+            <script>document.write("<p>injected</p>")</script>"#,
+        );
+        assert_eq!(doc.blocks.len(), 1);
+        let link = Some("docs/a.html".to_string());
+        assert_eq!(
+            doc.blocks[0].spans,
+            vec![
+                span("See "),
+                Span {
+                    link: link.clone(),
+                    ..span("the ")
+                },
+                Span {
+                    bold: true,
+                    link,
+                    ..span("docs")
+                },
+                span(". "),
+                Span {
+                    image: Some("logo.png".to_string()),
+                    ..span("")
+                },
+                span(" [no source]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b%2Fc%zz%"), b"a b/c%zz%");
+    }
+
+    #[test]
+    fn test_heading_falls_back_to_title() {
+        let doc = parse("<html><head><title> The\n End </title></head><p>Bye</p></html>");
+        assert_eq!(doc.heading().as_deref(), Some("The End"));
+        assert_eq!(parse("<p>No title</p>").heading(), None);
+    }
+}
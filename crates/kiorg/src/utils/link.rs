@@ -0,0 +1,226 @@
+//! Symbolic and hard links to existing entries
+//!
+//! Windows only allows symlinks with Developer Mode or administrator rights.
+//! Without them, directories are linked with a junction instead and linking
+//! files fails with an explanation.
+
+use crate::utils::transfer::paste_target;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Symlink,
+    Hardlink,
+}
+
+impl LinkKind {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Symlink => "symlink",
+            Self::Hardlink => "hard link",
+        }
+    }
+}
+
+/// Links created by [`link_entries`] and the errors of the ones that failed
+#[derive(Debug, Default)]
+pub struct LinkSummary {
+    pub created: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+/// Create a link in `dest_dir` to each of `sources`
+///
+/// Links are named like the source, numbered if the name is taken.
+pub fn link_entries(kind: LinkKind, sources: &[PathBuf], dest_dir: &Path) -> LinkSummary {
+    let mut summary = LinkSummary::default();
+    for source in sources {
+        let link = paste_target(source, dest_dir);
+        let result = match kind {
+            LinkKind::Symlink => create_symlink(source, &link),
+            LinkKind::Hardlink => create_hardlink(source, &link),
+        };
+        match result {
+            Ok(()) => summary.created.push(link),
+            Err(e) => summary.errors.push(format!(
+                "{}: {e}",
+                source.file_name().unwrap_or_default().to_string_lossy()
+            )),
+        }
+    }
+    summary
+}
+
+/// Target of the symlink at `link` as stored in the link, and its absolute
+/// path with every link resolved, `None` if the target doesn't exist
+pub fn symlink_target(link: &Path) -> io::Result<(PathBuf, Option<PathBuf>)> {
+    let target = std::fs::read_link(link)?;
+    Ok((target, std::fs::canonicalize(link).ok()))
+}
+
+/// Create a hard link at `link` to the file `target`
+pub fn create_hardlink(target: &Path, link: &Path) -> io::Result<()> {
+    if target.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Directories can't be hard linked",
+        ));
+    }
+    std::fs::hard_link(target, link)
+}
+
+/// Create a symlink at `link` pointing to `target`
+#[cfg(unix)]
+pub fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+/// Create a symlink at `link` pointing to `target`
+///
+/// Directories fall back to a junction without the symlink privilege.
+#[cfg(windows)]
+pub fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    let is_dir = target.is_dir();
+    if can_create_symlinks() {
+        return if is_dir {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        };
+    }
+    if is_dir {
+        create_junction(target, link)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Creating symlinks to files requires Developer Mode or administrator rights",
+        ))
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn create_symlink(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Symlinks are not supported on this platform",
+    ))
+}
+
+/// Whether the process holds the privilege to create symlinks
+///
+/// Probed once by creating a symlink in a temporary directory.
+#[cfg(windows)]
+fn can_create_symlinks() -> bool {
+    static CAN_CREATE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *CAN_CREATE.get_or_init(|| {
+        tempfile::tempdir().is_ok_and(|dir| {
+            std::os::windows::fs::symlink_file(dir.path().join("target"), dir.path().join("link"))
+                .is_ok()
+        })
+    })
+}
+
+/// Junctions link local directories and need no privilege
+#[cfg(windows)]
+fn create_junction(target: &Path, link: &Path) -> io::Result<()> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let target = std::fs::canonicalize(target)?;
+    // canonicalize returns a verbatim `\\?\` path mklink doesn't accept
+    let target = target
+        .to_str()
+        .and_then(|path| path.strip_prefix(r"\\?\"))
+        .map(PathBuf::from)
+        .unwrap_or(target);
+    let output = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg("mklink")
+        .arg("/J")
+        .arg(link)
+        .arg(&target)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "Failed to create junction: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir_all(source_dir.join("dir")).unwrap();
+        std::fs::create_dir(&dest_dir).unwrap();
+        let file = source_dir.join("file.txt");
+        std::fs::write(&file, "content").unwrap();
+        std::fs::write(dest_dir.join("file.txt"), "taken").unwrap();
+
+        let summary = link_entries(
+            LinkKind::Hardlink,
+            &[file.clone(), source_dir.join("dir")],
+            &dest_dir,
+        );
+        assert_eq!(summary.created, [dest_dir.join("file_1.txt")]);
+        assert_eq!(summary.errors.len(), 1);
+        std::fs::write(&file, "changed").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dest_dir.join("file_1.txt")).unwrap(),
+            "changed"
+        );
+
+        #[cfg(unix)]
+        {
+            let summary = link_entries(
+                LinkKind::Symlink,
+                &[file.clone(), source_dir.join("dir")],
+                &dest_dir,
+            );
+            assert!(summary.errors.is_empty());
+            assert_eq!(
+                summary.created,
+                [dest_dir.join("file_2.txt"), dest_dir.join("dir")]
+            );
+            assert_eq!(
+                std::fs::read_link(dest_dir.join("dir")).unwrap(),
+                source_dir.join("dir")
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("dir")).unwrap();
+        std::fs::write(root.join("dir/file.txt"), "").unwrap();
+        create_symlink(Path::new("dir/file.txt"), &root.join("relative")).unwrap();
+        create_symlink(&root.join("relative"), &root.join("chained")).unwrap();
+        create_symlink(Path::new("missing"), &root.join("broken")).unwrap();
+
+        let (target, resolved) = symlink_target(&root.join("relative")).unwrap();
+        assert_eq!(target, Path::new("dir/file.txt"));
+        assert_eq!(resolved, Some(root.join("dir/file.txt")));
+        // Every link on the way is resolved
+        let (target, resolved) = symlink_target(&root.join("chained")).unwrap();
+        assert_eq!(target, root.join("relative"));
+        assert_eq!(resolved, Some(root.join("dir/file.txt")));
+        assert_eq!(
+            symlink_target(&root.join("broken")).unwrap(),
+            (PathBuf::from("missing"), None)
+        );
+        assert!(symlink_target(&root.join("dir")).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! Web page archives saved as MHTML (`.mht`, `.mhtml`)
+//!
+//! An MHTML file is a MIME multipart message. The first `text/html` part is
+//! the page, the other parts are the resources it references by
+//! `Content-Location` or `cid:` URL.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+
+/// A page extracted from an MHTML file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Archive {
+    pub html: String,
+    /// `Content-Location` of the page, relative resources are resolved against it
+    pub location: Option<String>,
+    /// Resource bytes by `Content-Location` and `cid:` URL
+    pub resources: HashMap<String, Vec<u8>>,
+}
+
+impl Archive {
+    /// Bytes of the resource `src` refers to
+    #[must_use]
+    pub fn resource(&self, src: &str) -> Option<&[u8]> {
+        if let Some(bytes) = self.resources.get(src) {
+            return Some(bytes);
+        }
+        let base = self.location.as_deref()?;
+        let dir = &base[..base.rfind('/')? + 1];
+        self.resources
+            .get(&format!("{dir}{}", src.trim_start_matches("./")))
+            .map(Vec::as_slice)
+    }
+}
+
+struct Part<'a> {
+    headers: HashMap<String, String>,
+    body: &'a [u8],
+}
+
+impl Part<'_> {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    /// Media type without parameters, lowercased
+    fn content_type(&self) -> String {
+        self.header("content-type")
+            .and_then(|value| value.split(';').next())
+            .unwrap_or("text/plain")
+            .trim()
+            .to_ascii_lowercase()
+    }
+
+    fn decoded_body(&self) -> Vec<u8> {
+        let encoding = self
+            .header("content-transfer-encoding")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match encoding.as_str() {
+            "base64" => decode_base64(self.body).unwrap_or_default(),
+            "quoted-printable" => decode_quoted_printable(self.body),
+            _ => self.body.to_vec(),
+        }
+    }
+}
+
+/// Split a MIME entity into its headers and body
+fn split_part(data: &[u8]) -> Part<'_> {
+    let mut headers: HashMap<String, String> = HashMap::new();
+    let mut last: Option<String> = None;
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (line, next) = match rest.iter().position(|&b| b == b'\n') {
+            Some(end) => (&rest[..end], &rest[end + 1..]),
+            None => (rest, &rest[rest.len()..]),
+        };
+        rest = next;
+        let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line));
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            // Folded header line
+            if let Some(value) = last.as_ref().and_then(|name| headers.get_mut(name)) {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim().to_ascii_lowercase();
+            headers.insert(name.clone(), value.trim().to_string());
+            last = Some(name);
+        }
+    }
+    Part {
+        headers,
+        body: rest,
+    }
+}
+
+/// Parameter of a header value, e.g. `boundary` in a `Content-Type`
+fn parameter(value: &str, name: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Bodies of the parts of a multipart body
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{boundary}");
+    let delimiter = delimiter.as_bytes();
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut offset = 0;
+    while offset < body.len() {
+        let end = body[offset..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(body.len(), |end| offset + end + 1);
+        let line = &body[offset..end];
+        if line.starts_with(delimiter) {
+            if let Some(start) = start {
+                // The line break before the delimiter belongs to it
+                let mut part = &body[start..offset];
+                part = part.strip_suffix(b"\n").unwrap_or(part);
+                part = part.strip_suffix(b"\r").unwrap_or(part);
+                parts.push(part);
+            }
+            if line[delimiter.len()..].starts_with(b"--") {
+                return parts;
+            }
+            start = Some(end);
+        }
+        offset = end;
+    }
+    if let Some(start) = start {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+/// Decode base64 that may be split over several lines
+pub fn decode_base64(data: &[u8]) -> Option<Vec<u8>> {
+    let data: Vec<u8> = data
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(&data)
+        .or_else(|_| base64::engine::general_purpose::STANDARD_NO_PAD.decode(&data))
+        .ok()
+}
+
+fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] != b'=' {
+            decoded.push(data[i]);
+            i += 1;
+            continue;
+        }
+        match &data[i + 1..(i + 3).min(data.len())] {
+            // Soft line breaks
+            [b'\r', b'\n'] => i += 3,
+            [b'\n', ..] => i += 2,
+            &[high, low] if hex(high).is_some() && hex(low).is_some() => {
+                decoded.push((hex(high).unwrap_or_default() << 4) | hex(low).unwrap_or_default());
+                i += 3;
+            }
+            _ => {
+                decoded.push(b'=');
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+/// Extract the page and its resources from an MHTML file
+pub fn parse(data: &[u8]) -> Result<Archive, String> {
+    let message = split_part(data);
+    let content_type = message.header("content-type").unwrap_or_default();
+    let parts = match parameter(content_type, "boundary") {
+        Some(boundary) if message.content_type().starts_with("multipart/") => {
+            split_multipart(message.body, &boundary)
+                .into_iter()
+                .map(split_part)
+                .collect()
+        }
+        _ => vec![message],
+    };
+
+    let mut archive = Archive::default();
+    let mut found_html = false;
+    for part in parts {
+        if !found_html && part.content_type() == "text/html" {
+            found_html = true;
+            archive.html = String::from_utf8_lossy(&part.decoded_body()).into_owned();
+            archive.location = part.header("content-location").map(str::to_string);
+            continue;
+        }
+        let body = part.decoded_body();
+        if let Some(id) = part.header("content-id") {
+            let id = id.trim().trim_start_matches('<').trim_end_matches('>');
+            archive.resources.insert(format!("cid:{id}"), body.clone());
+        }
+        if let Some(location) = part.header("content-location") {
+            archive.resources.insert(location.to_string(), body);
+        }
+    }
+    if !found_html {
+        return Err("No HTML page found in the archive".to_string());
+    }
+    Ok(archive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARCHIVE: &str = "From: <Saved by Blink>\r\n\
+Subject: Example\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/related;\r\n\
+\ttype=\"text/html\";\r\n\
+\tboundary=\"----MultipartBoundary--abc\"\r\n\
+\r\n\
+------MultipartBoundary--abc\r\n\
+Content-Type: text/html\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+Content-Location: https://example.com/docs/page.html\r\n\
+\r\n\
+<html><body><h1 class=3D\"title\">Caf=C3=A9</h1><p>A long line that is =\r\n\
+wrapped</p></body></html>\r\n\
+------MultipartBoundary--abc\r\n\
+Content-Type: image/png\r\n\
+Content-Transfer-Encoding: base64\r\n\
+Content-Location: https://example.com/docs/img/a.png\r\n\
+\r\n\
+aGVs\r\n\
+bG8=\r\n\
+------MultipartBoundary--abc\r\n\
+Content-Type: image/gif\r\n\
+Content-Transfer-Encoding: binary\r\n\
+Content-ID: <frame-1@mhtml.blink>\r\n\
+\r\n\
+GIF\r\n\
+------MultipartBoundary--abc--\r\n";
+
+    #[test]
+    fn test_parse_archive() {
+        let archive = parse(ARCHIVE.as_bytes()).unwrap();
+        assert_eq!(
+            archive.html,
+            "<html><body><h1 class=\"title\">Café</h1><p>A long line that is wrapped</p></body></html>"
+        );
+        assert_eq!(
+            archive.location.as_deref(),
+            Some("https://example.com/docs/page.html")
+        );
+        assert_eq!(archive.resource("img/a.png"), Some(&b"hello"[..]));
+        assert_eq!(
+            archive.resource("https://example.com/docs/img/a.png"),
+            Some(&b"hello"[..])
+        );
+        assert_eq!(
+            archive.resource("cid:frame-1@mhtml.blink"),
+            Some(&b"GIF"[..])
+        );
+        assert_eq!(archive.resource("missing.png"), None);
+    }
+
+    #[test]
+    fn test_parse_single_part() {
+        let archive = parse(b"Content-Type: text/html\n\n<p>Hi</p>").unwrap();
+        assert_eq!(archive.html, "<p>Hi</p>");
+        assert!(parse(b"Content-Type: text/plain\n\nHi").is_err());
+    }
+}
@@ -1,5 +1,34 @@
+pub mod archive;
+pub mod atomic_write;
+pub mod checksum;
+pub mod choose_dir;
+pub mod clipboard;
+pub mod cloud;
+pub mod diff;
+pub mod dir_size;
+pub mod dir_stream;
+pub mod disk_size;
+pub mod duplicates;
+pub mod encryption;
 pub mod file_operations;
+pub mod flatten;
 pub mod format;
+pub mod grep;
+pub mod hashing;
+pub mod history_import;
+pub mod html;
 pub mod icon;
+pub mod link;
+pub mod mhtml;
 pub mod preview_cache;
+pub mod print;
 pub mod rollback;
+pub mod share;
+pub mod signature;
+pub mod split;
+pub mod templates;
+pub mod terminal;
+pub mod touch;
+pub mod transfer;
+pub mod trash;
+pub mod wallpaper;
@@ -3,7 +3,8 @@ use ahash::RandomState;
 use rkyv::rancor::Error;
 use std::fs;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use crate::models::dir_entry::DirEntryMeta;
 use crate::models::preview_content::CachedPreviewContent;
@@ -40,6 +41,9 @@ mod imp {
     use super::*;
 
     pub fn get_cache_dir() -> Option<PathBuf> {
+        if let Some(dir) = crate::config::portable::cache_dir() {
+            return Some(dir);
+        }
         dirs::cache_dir().map(|mut d| {
             d.push("kiorg");
             d
@@ -57,12 +61,17 @@ mod imp {
 
 pub use imp::{get_cache_dir, purge_cache_dir};
 
+/// Size the preview cache is pruned to on startup
+pub const MAX_CACHE_SIZE: u64 = 1024 * 1024 * 1024;
+
 pub fn calculate_path_hash(path: &std::path::Path) -> u64 {
     let path_str = path.to_string_lossy();
     let hasher = RandomState::with_seeds(0, 0, 0, 0);
     hasher.hash_one(path_str.as_bytes())
 }
 
+/// Key of the cached preview of a file, changes whenever the file's
+/// modification time or size changes
 pub fn calculate_cache_key(entry: &DirEntryMeta) -> String {
     let path_hash = calculate_path_hash(&entry.path);
 
@@ -72,7 +81,8 @@ pub fn calculate_cache_key(entry: &DirEntryMeta) -> String {
         .ok()
         .map(|d| d.as_nanos())
         .unwrap_or(0);
-    format!("{path_hash:x}.{mtime}")
+    let size = fs::metadata(&entry.path).map(|m| m.len()).unwrap_or(0);
+    format!("{path_hash:x}.{mtime}.{size}")
 }
 
 pub fn delete_previews_for_path(path: &std::path::Path) {
@@ -129,7 +139,11 @@ pub fn load_preview(key: &str) -> Option<CachedPreviewContent> {
     if !path.exists() {
         return None;
     }
-    let bytes = fs::read(path).ok()?;
+    let bytes = fs::read(&path).ok()?;
+    // Mark the entry as recently used so pruning keeps it
+    if let Ok(file) = fs::File::options().append(true).open(&path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
     rkyv::from_bytes::<CachedPreviewContent, Error>(&bytes).ok()
 }
 
@@ -139,6 +153,45 @@ pub fn delete_preview(key: &str) {
     }
 }
 
+/// Remove the least recently used cached previews until the cache takes at
+/// most `max_bytes`
+pub fn prune_cache_dir(max_bytes: u64) {
+    if let Some(dir) = get_cache_dir() {
+        prune_dir(&dir, max_bytes);
+    }
+}
+
+fn prune_dir(dir: &Path, max_bytes: u64) {
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|shard| shard.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|shard| fs::read_dir(shard.path()).ok())
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            let used = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            Some((used, meta.len(), entry.path()))
+        })
+        .collect();
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    if total <= max_bytes {
+        return;
+    }
+    files.sort_by_key(|(used, _, _)| *used);
+    for (_, size, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= size;
+        }
+    }
+}
+
 pub fn atomic_write(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
     let mut temp_path = path.to_path_buf();
     if let Some(file_name) = temp_path.file_name() {
@@ -161,7 +214,6 @@ pub fn atomic_write(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()>
 mod tests {
     use super::*;
     use crate::models::preview_content::ZipEntry;
-    use std::time::SystemTime;
 
     #[test]
     fn test_calculate_cache_key() {
@@ -170,7 +222,33 @@ mod tests {
             modified: SystemTime::UNIX_EPOCH,
         };
         let key = calculate_cache_key(&entry);
-        assert_eq!(key, "f32edd2249c84742.0");
+        assert_eq!(key, "f32edd2249c84742.0.0");
+    }
+
+    #[test]
+    fn test_prune_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let shard = dir.path().join("ab");
+        fs::create_dir(&shard).unwrap();
+        for (i, name) in ["old", "middle", "new"].iter().enumerate() {
+            let path = shard.join(name);
+            fs::write(&path, [0u8; 10]).unwrap();
+            let used = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(i as u64 + 1);
+            fs::File::options()
+                .append(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(used)
+                .unwrap();
+        }
+
+        prune_dir(dir.path(), 30);
+        assert!(shard.join("old").exists());
+
+        prune_dir(dir.path(), 20);
+        assert!(!shard.join("old").exists());
+        assert!(shard.join("middle").exists());
+        assert!(shard.join("new").exists());
     }
 
     #[test]
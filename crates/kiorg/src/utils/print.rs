@@ -0,0 +1,58 @@
+//! Send files to the system printer
+//!
+//! On Linux and macOS files are spooled with CUPS `lp`, which converts text,
+//! images and PDFs on its own. Windows hands the file to the default
+//! application registered for it with the print verb.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Command that prints `path` with the default printer
+#[must_use]
+pub fn print_command(path: &Path) -> Command {
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut command = Command::new("lp");
+        command.arg("--").arg(path);
+        command
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let path = path.to_string_lossy().replace('\'', "''");
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            &format!("Start-Process -FilePath '{path}' -Verb Print"),
+        ]);
+        command
+    }
+}
+
+/// Print `path`, returns the output of the spooler on success
+pub fn print_file(path: &Path) -> Result<String, String> {
+    let output = print_command(path)
+        .output()
+        .map_err(|e| format!("Failed to start the print command: {e}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Failed to print: {}", stderr.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_print_command() {
+        let command = print_command(Path::new("/tmp/-report.pdf"));
+        assert_eq!(command.get_program(), "lp");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["--", "/tmp/-report.pdf"]);
+    }
+}
@@ -86,8 +86,7 @@ impl RollbackManager {
                 let mut single_result = None;
 
                 for op in operations.iter().rev() {
-                    let is_dir = op.target_path.is_dir();
-                    match Self::rollback_copy(&op.target_path, is_dir) {
+                    match Self::rollback_copy(&op.target_path) {
                         RollbackResult::Success(msg) => {
                             success_count += 1;
                             if success_count == 1 {
@@ -194,9 +193,29 @@ impl RollbackManager {
         }
     }
 
-    /// Rollback a copy operation by deleting the copied file/directory
-    fn rollback_copy(target_path: &std::path::Path, is_directory: bool) -> RollbackResult {
-        Self::rollback_create(target_path, is_directory)
+    /// Rollback a copy operation by moving the copied file/directory to the trash
+    ///
+    /// The copy may have been edited since it was pasted, so it's kept
+    /// recoverable rather than deleted.
+    fn rollback_copy(target_path: &std::path::Path) -> RollbackResult {
+        if target_path.symlink_metadata().is_err() {
+            return RollbackResult::Error(format!(
+                "Cannot rollback copy: {} no longer exists",
+                target_path.display()
+            ));
+        }
+
+        match crate::utils::trash::move_to_trash(target_path) {
+            Ok(()) => RollbackResult::Success(format!(
+                "Moved copy '{}' to the trash",
+                target_path.display()
+            )),
+            Err(e) => RollbackResult::Error(format!(
+                "Failed to move {} to the trash: {}",
+                target_path.display(),
+                e
+            )),
+        }
     }
 
     /// Rollback a move operation by moving back to original location
@@ -211,7 +230,9 @@ impl RollbackManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::action_history::{ActionType, CreateOperation, RenameOperation};
+    use crate::models::action_history::{
+        ActionType, CopyOperation, CreateOperation, MoveOperation, RenameOperation,
+    };
     use tempfile::tempdir;
 
     #[test]
@@ -286,4 +307,59 @@ mod tests {
         assert!(old_file.exists(), "File should be renamed back");
         assert!(!new_file.exists(), "New file should not exist");
     }
+
+    #[test]
+    fn test_rollback_copy_moves_copies_to_trash() {
+        let temp_dir = tempdir().unwrap();
+        let source = temp_dir.path().join("source.txt");
+        let copy = temp_dir.path().join("dest").join("source.txt");
+        std::fs::create_dir(temp_dir.path().join("dest")).unwrap();
+        std::fs::write(&source, "original").unwrap();
+        std::fs::write(&copy, "edited after the paste").unwrap();
+
+        let action = ActionType::Copy {
+            operations: vec![CopyOperation {
+                source_path: source.clone(),
+                target_path: copy.clone(),
+            }],
+        };
+
+        let manager = RollbackManager::new();
+        assert!(manager.rollback_action(&action).is_ok());
+        assert!(source.exists(), "Source should be kept");
+        assert!(!copy.exists(), "Copy should be removed");
+
+        // The copy can be restored from the trash
+        let trashed = crate::utils::trash::list_trash()
+            .unwrap()
+            .into_iter()
+            .find(|item| item.original_path.as_ref() == Some(&copy))
+            .expect("Copy should be in the trash");
+        crate::utils::trash::restore(&trashed).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&copy).unwrap(),
+            "edited after the paste"
+        );
+    }
+
+    #[test]
+    fn test_rollback_move() {
+        let temp_dir = tempdir().unwrap();
+        let original = temp_dir.path().join("file.txt");
+        let moved = temp_dir.path().join("dest").join("file.txt");
+        std::fs::create_dir(temp_dir.path().join("dest")).unwrap();
+        std::fs::write(&moved, "content").unwrap();
+
+        let action = ActionType::Move {
+            operations: vec![MoveOperation {
+                source_path: original.clone(),
+                target_path: moved.clone(),
+            }],
+        };
+
+        let manager = RollbackManager::new();
+        assert!(manager.rollback_action(&action).is_ok());
+        assert!(original.exists(), "File should be moved back");
+        assert!(!moved.exists(), "Moved file should be gone");
+    }
 }
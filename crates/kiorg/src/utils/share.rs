@@ -0,0 +1,269 @@
+//! Share files through the platform share targets
+//!
+//! macOS lists the `NSSharingService`s that accept the files, e.g. AirDrop,
+//! Mail and Messages. Windows opens the system Share UI through the Explorer
+//! share verb. Linux has no share sheet, files are attached to a new email with
+//! `xdg-email`, or the desktop portal's email composer when it's missing as in
+//! sandboxes, and sent with `bluetooth-sendto` when it's installed.
+
+use std::path::PathBuf;
+
+/// A place the files can be shared to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareTarget {
+    pub name: String,
+    kind: TargetKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TargetKind {
+    /// Index into the sharing services returned for the files
+    #[cfg(target_os = "macos")]
+    Service(usize),
+    #[cfg(target_os = "windows")]
+    ShareUi,
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    Command(&'static str),
+    /// `org.freedesktop.portal.Email`, attaching the files by descriptor
+    #[cfg(target_os = "linux")]
+    EmailPortal,
+}
+
+/// Share targets that accept all of `paths`
+#[must_use]
+pub fn targets(paths: &[PathBuf]) -> Vec<ShareTarget> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    #[cfg(target_os = "macos")]
+    {
+        macos::service_names(paths)
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| ShareTarget {
+                name,
+                kind: TargetKind::Service(index),
+            })
+            .collect()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // The share verb takes a single file
+        if paths.len() == 1 && paths[0].is_file() {
+            vec![ShareTarget {
+                name: "Share…".to_string(),
+                kind: TargetKind::ShareUi,
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        if !paths.iter().all(|path| path.is_file()) {
+            return Vec::new();
+        }
+        let email = if find_in_path("xdg-email").is_none() {
+            email_portal_kind()
+        } else {
+            TargetKind::Command("xdg-email")
+        };
+        let mut targets = vec![ShareTarget {
+            name: "Email".to_string(),
+            kind: email,
+        }];
+        if find_in_path("bluetooth-sendto").is_some() {
+            targets.push(ShareTarget {
+                name: "Bluetooth".to_string(),
+                kind: TargetKind::Command("bluetooth-sendto"),
+            });
+        }
+        targets
+    }
+}
+
+/// Share `paths` to `target` as returned by [`targets`] for the same paths
+pub fn share(paths: &[PathBuf], target: &ShareTarget) -> Result<(), String> {
+    match &target.kind {
+        #[cfg(target_os = "macos")]
+        TargetKind::Service(index) => macos::perform(paths, *index),
+        #[cfg(target_os = "windows")]
+        TargetKind::ShareUi => share_ui_command(&paths[0])
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to open the share UI: {e}")),
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        TargetKind::Command(program) => {
+            // Both tools stay open until the user is done, don't wait for them
+            share_command(program, paths)
+                .spawn()
+                .map(|_| ())
+                .map_err(|e| format!("Failed to run {program}: {e}"))
+        }
+        #[cfg(target_os = "linux")]
+        TargetKind::EmailPortal => compose_email_via_portal(paths),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn email_portal_kind() -> TargetKind {
+    TargetKind::EmailPortal
+}
+
+/// Other systems have no portal, `xdg-email` reports that it's missing
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn email_portal_kind() -> TargetKind {
+    TargetKind::Command("xdg-email")
+}
+
+/// Open the email composer of the desktop portal with `paths` attached
+#[cfg(target_os = "linux")]
+fn compose_email_via_portal(paths: &[PathBuf]) -> Result<(), String> {
+    use std::collections::HashMap;
+    use std::os::fd::AsFd;
+    use zbus::zvariant::{Fd, Value};
+
+    // The descriptors have to stay open until the portal received them
+    let files = paths
+        .iter()
+        .map(|path| std::fs::File::open(path).map_err(|e| format!("{}: {e}", path.display())))
+        .collect::<Result<Vec<_>, _>>()?;
+    let fds: Vec<Fd<'_>> = files.iter().map(|file| Fd::from(file.as_fd())).collect();
+    let options = HashMap::from([("attachment_fds", Value::from(fds))]);
+
+    let connection = zbus::blocking::Connection::session()
+        .map_err(|e| format!("Failed to connect to the session bus: {e}"))?;
+    connection
+        .call_method(
+            Some("org.freedesktop.portal.Desktop"),
+            "/org/freedesktop/portal/desktop",
+            Some("org.freedesktop.portal.Email"),
+            "ComposeEmail",
+            &("", options),
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open the email composer: {e}"))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn share_command(program: &str, paths: &[PathBuf]) -> std::process::Command {
+    let mut command = std::process::Command::new(program);
+    if program == "xdg-email" {
+        for path in paths {
+            command.arg("--attach").arg(path);
+        }
+    } else {
+        command.args(paths);
+    }
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(target_os = "windows")]
+fn share_ui_command(path: &std::path::Path) -> std::process::Command {
+    let quote = |s: &std::ffi::OsStr| s.to_string_lossy().replace('\'', "''");
+    let dir = path
+        .parent()
+        .map(|p| quote(p.as_os_str()))
+        .unwrap_or_default();
+    let name = path.file_name().map(quote).unwrap_or_default();
+    let mut command = std::process::Command::new("powershell");
+    command.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-Command",
+        &format!(
+            "(New-Object -ComObject Shell.Application).Namespace('{dir}').ParseName('{name}').InvokeVerb('Windows.ModernShare')"
+        ),
+    ]);
+    command
+}
+
+/// Display name of a share, e.g. "notes.txt" or "3 files"
+#[must_use]
+pub fn describe(paths: &[PathBuf]) -> String {
+    match paths {
+        [path] => path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string()),
+        _ => format!("{} files", paths.len()),
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::path::PathBuf;
+
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send, msg_send_id};
+    use objc2_foundation::{NSArray, NSString, NSURL};
+
+    fn items(paths: &[PathBuf]) -> Retained<NSArray<NSURL>> {
+        let urls = paths
+            .iter()
+            .filter_map(|path| path.to_str())
+            .map(|path| unsafe { NSURL::fileURLWithPath(&NSString::from_str(path)) })
+            .collect();
+        NSArray::from_vec(urls)
+    }
+
+    fn services(items: &NSArray<NSURL>) -> Retained<NSArray<AnyObject>> {
+        unsafe { msg_send_id![class!(NSSharingService), sharingServicesForItems: items] }
+    }
+
+    pub fn service_names(paths: &[PathBuf]) -> Vec<String> {
+        services(&items(paths))
+            .iter()
+            .map(|service| {
+                let title: Retained<NSString> = unsafe { msg_send_id![service, title] };
+                title.to_string()
+            })
+            .collect()
+    }
+
+    pub fn perform(paths: &[PathBuf], index: usize) -> Result<(), String> {
+        let items = items(paths);
+        let services = services(&items);
+        let Some(service) = services.get(index) else {
+            return Err("Share target is no longer available".to_string());
+        };
+        let can_perform: bool = unsafe { msg_send![service, canPerformWithItems: &*items] };
+        if !can_perform {
+            return Err("Share target can't take these files".to_string());
+        }
+        let _: () = unsafe { msg_send![service, performWithItems: &*items] };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(&[PathBuf::from("/tmp/notes.txt")]), "notes.txt");
+        assert_eq!(
+            describe(&[PathBuf::from("/tmp/a"), PathBuf::from("/tmp/b")]),
+            "2 files"
+        );
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn test_share_command() {
+        let paths = [PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/b.txt")];
+        let command = share_command("xdg-email", &paths);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["--attach", "/tmp/a.txt", "--attach", "/tmp/b.txt"]);
+    }
+}
@@ -0,0 +1,506 @@
+//! Check code signatures of executables and installers
+//!
+//! Windows binaries are verified with `Get-AuthenticodeSignature`, macOS apps
+//! and installers with `codesign` and `spctl` to also report notarization.
+//! On other platforms an Authenticode signature can only be detected, not
+//! verified. A detached GPG signature next to any file (`file.sig`,
+//! `file.asc` or `file.gpg`) is verified with `gpg --verify`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Outcome of a signature check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Signed and the signature checks out
+    Valid,
+    /// Signed, but the signature is broken or not trusted
+    Invalid,
+    /// No signature found
+    Unsigned,
+    /// Signed, but the signature couldn't be verified on this system
+    Unverified,
+}
+
+/// A signature check for a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    /// Signature scheme, e.g. "Authenticode"
+    pub kind: &'static str,
+    pub status: Status,
+    /// Signer, or why the check failed
+    pub detail: String,
+}
+
+impl Signature {
+    fn new(kind: &'static str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+const AUTHENTICODE_EXTENSIONS: &[&str] = &[
+    "exe",
+    "dll",
+    "sys",
+    "msi",
+    "msix",
+    "msixbundle",
+    "appx",
+    "cab",
+    "cat",
+    "ps1",
+    "efi",
+];
+const MACOS_EXTENSIONS: &[&str] = &["app", "dmg", "pkg", "mpkg", "dylib", "framework"];
+const DETACHED_SIGNATURE_EXTENSIONS: &[&str] = &["sig", "asc", "gpg"];
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+/// Detached GPG signature next to `path`, e.g. `file.tar.gz.sig`
+#[must_use]
+pub fn detached_signature(path: &Path) -> Option<PathBuf> {
+    if has_extension(path, DETACHED_SIGNATURE_EXTENSIONS) {
+        return None;
+    }
+    DETACHED_SIGNATURE_EXTENSIONS.iter().find_map(|ext| {
+        let mut name = path.file_name()?.to_os_string();
+        name.push(".");
+        name.push(ext);
+        let candidate = path.with_file_name(name);
+        candidate.is_file().then_some(candidate)
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Whether `path` is worth checking for a signature
+#[must_use]
+pub fn is_candidate(path: &Path) -> bool {
+    has_extension(path, AUTHENTICODE_EXTENSIONS)
+        || (cfg!(target_os = "macos")
+            && (has_extension(path, MACOS_EXTENSIONS) || is_executable(path)))
+        || detached_signature(path).is_some()
+}
+
+/// Check all signatures that apply to `path`, this runs external tools and
+/// should be called off the UI thread
+#[must_use]
+pub fn check(path: &Path) -> Vec<Signature> {
+    let mut signatures = Vec::new();
+    if has_extension(path, AUTHENTICODE_EXTENSIONS) {
+        signatures.extend(authenticode(path));
+    }
+    #[cfg(target_os = "macos")]
+    if has_extension(path, MACOS_EXTENSIONS) || is_executable(path) {
+        signatures.extend(codesign(path));
+    }
+    if let Some(signature) = detached_signature(path) {
+        signatures.push(gpg_verify(path, &signature));
+    }
+    signatures
+}
+
+/// Last non-empty line of a tool's output, used as the error detail
+fn last_line(output: &[u8]) -> String {
+    String::from_utf8_lossy(output)
+        .lines()
+        .rfind(|line| !line.trim().is_empty())
+        .unwrap_or("unknown error")
+        .trim()
+        .to_string()
+}
+
+#[cfg(target_os = "windows")]
+fn authenticode(path: &Path) -> Option<Signature> {
+    const KIND: &str = "Authenticode";
+    let quoted = path.to_string_lossy().replace('\'', "''");
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            &format!(
+                "$s = Get-AuthenticodeSignature -LiteralPath '{quoted}'; \
+                 \"$($s.Status)|$($s.SignerCertificate.Subject)|$($s.StatusMessage)\""
+            ),
+        ])
+        .output();
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            return Some(Signature::new(
+                KIND,
+                Status::Unverified,
+                last_line(&output.stderr),
+            ));
+        }
+        Err(e) => {
+            return Some(Signature::new(
+                KIND,
+                Status::Unverified,
+                format!("Failed to run powershell: {e}"),
+            ));
+        }
+    };
+    Some(parse_authenticode(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse the `Status|Subject|StatusMessage` line printed for Authenticode
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_authenticode(output: &str) -> Signature {
+    const KIND: &str = "Authenticode";
+    let mut fields = output.trim().splitn(3, '|');
+    let status = fields.next().unwrap_or_default();
+    let subject = fields.next().unwrap_or_default().trim();
+    let message = fields.next().unwrap_or_default().trim();
+    let signer = certificate_name(subject);
+    match status {
+        "Valid" => Signature::new(KIND, Status::Valid, format!("Signed by {signer}")),
+        "NotSigned" => Signature::new(KIND, Status::Unsigned, "Not signed"),
+        "UnknownError" | "" => Signature::new(KIND, Status::Unverified, message),
+        _ if subject.is_empty() => Signature::new(KIND, Status::Invalid, message),
+        _ => Signature::new(KIND, Status::Invalid, format!("{signer}: {message}")),
+    }
+}
+
+/// Common name of an X.509 subject such as `CN=Foo Inc, O=Foo Inc, C=US`
+fn certificate_name(subject: &str) -> &str {
+    subject
+        .split(", ")
+        .find_map(|part| part.strip_prefix("CN="))
+        .map(|name| name.trim_matches('"'))
+        .unwrap_or(subject)
+}
+
+/// Without Windows APIs an Authenticode signature can be found but not
+/// checked against the certificate store
+#[cfg(all(not(target_os = "windows"), feature = "binary-preview"))]
+fn authenticode(path: &Path) -> Option<Signature> {
+    const KIND: &str = "Authenticode";
+    const MAX_PE_BYTES: u64 = 512 * 1024 * 1024;
+    if std::fs::metadata(path).ok()?.len() > MAX_PE_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(path).ok()?;
+    let pe = goblin::pe::PE::parse(&bytes).ok()?;
+    if pe.certificates.is_empty() {
+        Some(Signature::new(KIND, Status::Unsigned, "Not signed"))
+    } else {
+        Some(Signature::new(
+            KIND,
+            Status::Unverified,
+            "Signed, can only be verified on Windows",
+        ))
+    }
+}
+
+#[cfg(all(not(target_os = "windows"), not(feature = "binary-preview")))]
+fn authenticode(_path: &Path) -> Option<Signature> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn codesign(path: &Path) -> Vec<Signature> {
+    const KIND: &str = "Code signature";
+    let mut signatures = Vec::new();
+    // Disk images and installer packages aren't signed with codesign
+    if !has_extension(path, &["dmg", "pkg", "mpkg"]) {
+        let verify = Command::new("codesign")
+            .args(["--verify", "--strict"])
+            .arg(path)
+            .output();
+        let signature = match verify {
+            Err(e) => Signature::new(
+                KIND,
+                Status::Unverified,
+                format!("Failed to run codesign: {e}"),
+            ),
+            Ok(output) if output.status.success() => {
+                let details = Command::new("codesign")
+                    .args(["-dv", "--verbose=2"])
+                    .arg(path)
+                    .output()
+                    .map(|output| String::from_utf8_lossy(&output.stderr).into_owned())
+                    .unwrap_or_default();
+                Signature::new(KIND, Status::Valid, parse_codesign(&details))
+            }
+            Ok(output) => {
+                let message = last_line(&output.stderr);
+                let message = message.rsplit(": ").next().unwrap_or(&message).to_string();
+                if message.contains("not signed at all") {
+                    Signature::new(KIND, Status::Unsigned, "Not signed")
+                } else {
+                    Signature::new(KIND, Status::Invalid, message)
+                }
+            }
+        };
+        let unsigned = signature.status == Status::Unsigned;
+        signatures.push(signature);
+        if unsigned {
+            return signatures;
+        }
+    }
+
+    let assess_type = if has_extension(path, &["pkg", "mpkg"]) {
+        "install"
+    } else if has_extension(path, &["dmg"]) {
+        "open"
+    } else {
+        "execute"
+    };
+    let mut command = Command::new("spctl");
+    command.args(["--assess", "-vv", "--type", assess_type]);
+    if assess_type == "open" {
+        command.args(["--context", "context:primary-signature"]);
+    }
+    if let Ok(output) = command.arg(path).output() {
+        let text = String::from_utf8_lossy(&output.stderr);
+        signatures.push(parse_spctl(output.status.success(), &text));
+    }
+    signatures
+}
+
+/// Signer and team from the output of `codesign -dv --verbose=2`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_codesign(output: &str) -> String {
+    let mut authority = None;
+    let mut team = None;
+    for line in output.lines() {
+        if authority.is_none()
+            && let Some(value) = line.strip_prefix("Authority=")
+        {
+            authority = Some(value.trim());
+        } else if let Some(value) = line.strip_prefix("TeamIdentifier=") {
+            team = Some(value.trim()).filter(|team| *team != "not set");
+        } else if line.starts_with("Signature=adhoc") {
+            return "Ad-hoc signature, no signer identity".to_string();
+        }
+    }
+    match (authority, team) {
+        (Some(authority), Some(team)) if !authority.contains(team) => {
+            format!("Signed by {authority} ({team})")
+        }
+        (Some(authority), _) => format!("Signed by {authority}"),
+        _ => "Signed".to_string(),
+    }
+}
+
+/// Gatekeeper verdict from the output of `spctl --assess -vv`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_spctl(accepted: bool, output: &str) -> Signature {
+    const KIND: &str = "Gatekeeper";
+    let source = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("source="))
+        .unwrap_or_default()
+        .trim();
+    if !accepted {
+        let reason = if source.is_empty() {
+            last_line(output.as_bytes())
+        } else {
+            source.to_string()
+        };
+        return Signature::new(KIND, Status::Invalid, format!("Rejected: {reason}"));
+    }
+    match source {
+        "Notarized Developer ID" => Signature::new(KIND, Status::Valid, "Notarized by Apple"),
+        "" => Signature::new(KIND, Status::Valid, "Accepted"),
+        source => Signature::new(KIND, Status::Valid, format!("Accepted: {source}")),
+    }
+}
+
+fn gpg_verify(path: &Path, signature: &Path) -> Signature {
+    const KIND: &str = "GPG signature";
+    let name = signature
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if !crate::utils::encryption::gpg_available() {
+        return Signature::new(
+            KIND,
+            Status::Unverified,
+            format!("{name} found, but gpg is not installed"),
+        );
+    }
+    let output = Command::new("gpg")
+        .args(["--batch", "--status-fd", "1", "--verify"])
+        .arg(signature)
+        .arg(path)
+        .output();
+    match output {
+        Ok(output) => parse_gpg_status(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => Signature::new(KIND, Status::Unverified, format!("Failed to run gpg: {e}")),
+    }
+}
+
+/// Interpret the `[GNUPG:]` status lines of `gpg --verify`
+fn parse_gpg_status(output: &str) -> Signature {
+    const KIND: &str = "GPG signature";
+    let mut signer = None;
+    let mut trusted = false;
+    for line in output.lines() {
+        let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match keyword {
+            "GOODSIG" => {
+                signer = rest.split_once(' ').map(|(_, user)| user.to_string());
+            }
+            "BADSIG" => {
+                let user = rest.split_once(' ').map_or(rest, |(_, user)| user);
+                return Signature::new(KIND, Status::Invalid, format!("Bad signature from {user}"));
+            }
+            "EXPKEYSIG" | "REVKEYSIG" => {
+                let user = rest.split_once(' ').map_or(rest, |(_, user)| user);
+                let reason = if keyword == "EXPKEYSIG" {
+                    "expired"
+                } else {
+                    "revoked"
+                };
+                return Signature::new(
+                    KIND,
+                    Status::Invalid,
+                    format!("Signed by {user} with a {reason} key"),
+                );
+            }
+            "NO_PUBKEY" => {
+                return Signature::new(
+                    KIND,
+                    Status::Unverified,
+                    format!("Public key {rest} is not in your keyring"),
+                );
+            }
+            "TRUST_FULLY" | "TRUST_ULTIMATE" => trusted = true,
+            "NODATA" => {
+                return Signature::new(KIND, Status::Invalid, "The signature file is not valid");
+            }
+            _ => {}
+        }
+    }
+    match signer {
+        Some(signer) if trusted => {
+            Signature::new(KIND, Status::Valid, format!("Signed by {signer}"))
+        }
+        Some(signer) => Signature::new(
+            KIND,
+            Status::Valid,
+            format!("Signed by {signer}, key is not certified as trusted"),
+        ),
+        None => Signature::new(
+            KIND,
+            Status::Unverified,
+            "gpg could not verify the signature",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detached_signature() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("tool.tar.gz");
+        std::fs::write(&file, "data").unwrap();
+        assert_eq!(detached_signature(&file), None);
+        assert!(!is_candidate(&file));
+
+        let signature = temp_dir.path().join("tool.tar.gz.asc");
+        std::fs::write(&signature, "sig").unwrap();
+        assert_eq!(detached_signature(&file), Some(signature.clone()));
+        assert_eq!(detached_signature(&signature), None);
+        assert!(is_candidate(&file));
+        assert!(is_candidate(Path::new("setup.EXE")));
+    }
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = "[GNUPG:] NEWSIG\n\
+                    [GNUPG:] GOODSIG 0123456789ABCDEF Alice <alice@example.com>\n\
+                    [GNUPG:] VALIDSIG ABCDEF 2024-01-01\n\
+                    [GNUPG:] TRUST_UNDEFINED 0 pgp\n";
+        let signature = parse_gpg_status(good);
+        assert_eq!(signature.status, Status::Valid);
+        assert_eq!(
+            signature.detail,
+            "Signed by Alice <alice@example.com>, key is not certified as trusted"
+        );
+
+        let trusted = good.replace("TRUST_UNDEFINED", "TRUST_ULTIMATE");
+        assert_eq!(
+            parse_gpg_status(&trusted).detail,
+            "Signed by Alice <alice@example.com>"
+        );
+
+        let bad = "[GNUPG:] BADSIG 0123456789ABCDEF Alice <alice@example.com>\n";
+        assert_eq!(parse_gpg_status(bad).status, Status::Invalid);
+
+        let missing = "[GNUPG:] ERRSIG 0123456789ABCDEF 1 8 00 1700000000 9 -\n\
+                       [GNUPG:] NO_PUBKEY 0123456789ABCDEF\n";
+        let signature = parse_gpg_status(missing);
+        assert_eq!(signature.status, Status::Unverified);
+        assert!(signature.detail.contains("0123456789ABCDEF"));
+    }
+
+    #[test]
+    fn test_parse_authenticode() {
+        let valid = parse_authenticode("Valid|CN=Foo Inc, O=Foo Inc, C=US|Signature verified.\r\n");
+        assert_eq!(valid.status, Status::Valid);
+        assert_eq!(valid.detail, "Signed by Foo Inc");
+
+        assert_eq!(
+            parse_authenticode("NotSigned||The file is not digitally signed.").status,
+            Status::Unsigned
+        );
+        let invalid = parse_authenticode("HashMismatch|CN=Foo Inc|The hash doesn't match.");
+        assert_eq!(invalid.status, Status::Invalid);
+        assert_eq!(invalid.detail, "Foo Inc: The hash doesn't match.");
+    }
+
+    #[test]
+    fn test_parse_codesign() {
+        let output = "Executable=/Applications/Foo.app/Contents/MacOS/Foo\n\
+                      Authority=Developer ID Application: Foo Inc (ABCDE12345)\n\
+                      Authority=Developer ID Certification Authority\n\
+                      Authority=Apple Root CA\n\
+                      TeamIdentifier=ABCDE12345\n";
+        assert_eq!(
+            parse_codesign(output),
+            "Signed by Developer ID Application: Foo Inc (ABCDE12345)"
+        );
+        assert_eq!(
+            parse_codesign("Signature=adhoc\nTeamIdentifier=not set\n"),
+            "Ad-hoc signature, no signer identity"
+        );
+
+        let notarized = parse_spctl(
+            true,
+            "/Applications/Foo.app: accepted\nsource=Notarized Developer ID\n",
+        );
+        assert_eq!(notarized.detail, "Notarized by Apple");
+        let rejected = parse_spctl(false, "/tmp/foo: rejected\nsource=no usable signature\n");
+        assert_eq!(rejected.status, Status::Invalid);
+        assert_eq!(rejected.detail, "Rejected: no usable signature");
+    }
+}
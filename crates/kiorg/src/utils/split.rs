@@ -0,0 +1,462 @@
+//! Splitting large files into numbered parts and joining them back
+//!
+//! Parts are named `<file>.001`, `<file>.002`, ... like 7-Zip and HJSplit
+//! name them, so other tools can join them too, e.g. `cat file.* > file`. A
+//! SHA-256 sidecar of the whole file is written next to the parts and checked
+//! once they are joined.
+
+use crate::utils::checksum;
+use crate::utils::hashing::{
+    HashAlgorithm, PROGRESS_UPDATE_INTERVAL, READ_BUFFER_SIZE, StreamHasher,
+};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Largest file FAT32 can store
+const FAT32_MAX_FILE_SIZE: u64 = 4 * 1024 * 1024 * 1024 - 1;
+/// Digits of the part number, more are used for over 999 parts
+const MIN_PART_DIGITS: usize = 3;
+
+/// How to cut a file into parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitMode {
+    /// Parts of this many bytes, the last one may be smaller
+    PartSize(u64),
+    /// This many parts of about the same size
+    PartCount(u64),
+}
+
+impl SplitMode {
+    /// Parse a part size like `25M`, `1.5GiB`, `4096B` or `fat32`, or a part
+    /// count given as a bare number
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("fat32") {
+            return Ok(Self::PartSize(FAT32_MAX_FILE_SIZE));
+        }
+        let split_at = input
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(input.len());
+        let (number, unit) = input.split_at(split_at);
+        let invalid = || format!("Invalid part size or count: {input}");
+        if unit.is_empty() {
+            let count: u64 = number.parse().map_err(|_| invalid())?;
+            if count < 2 {
+                return Err("Split into at least 2 parts".to_string());
+            }
+            return Ok(Self::PartCount(count));
+        }
+
+        let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+            "B" => 1,
+            "K" | "KB" | "KIB" => 1 << 10,
+            "M" | "MB" | "MIB" => 1 << 20,
+            "G" | "GB" | "GIB" => 1 << 30,
+            "T" | "TB" | "TIB" => 1 << 40,
+            _ => return Err(invalid()),
+        };
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        let size = (number * multiplier as f64) as u64;
+        if size == 0 {
+            return Err("Part size must be positive".to_string());
+        }
+        Ok(Self::PartSize(size))
+    }
+
+    fn part_size(self, total: u64) -> u64 {
+        match self {
+            Self::PartSize(size) => size,
+            Self::PartCount(count) => total.div_ceil(count).max(1),
+        }
+    }
+}
+
+/// Path of part `index`, counted from 1, of a file split into `digits` wide
+/// part numbers
+fn part_path_with_digits(path: &Path, index: usize, digits: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{index:0digits$}"));
+    path.with_file_name(name)
+}
+
+/// Path of part `index` of `path`, counted from 1
+pub fn part_path(path: &Path, index: usize) -> PathBuf {
+    part_path_with_digits(path, index, MIN_PART_DIGITS)
+}
+
+/// Whether `path` is the first part of a split file, e.g. `movie.mkv.001`
+pub fn is_first_part(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.len() >= MIN_PART_DIGITS && ext.trim_start_matches('0') == "1")
+}
+
+/// The parts of the file `first_part` starts, in order
+pub fn find_parts(first_part: &Path) -> Vec<PathBuf> {
+    let digits = first_part
+        .extension()
+        .map_or(MIN_PART_DIGITS, |ext| ext.len());
+    let original = first_part.with_extension("");
+    (1..)
+        .map(|index| part_path_with_digits(&original, index, digits))
+        .take_while(|part| part.is_file())
+        .collect()
+}
+
+/// Reports throttled progress of reading `total` bytes
+struct Progress<F: FnMut(u64, u64)> {
+    done: u64,
+    total: u64,
+    last_update: Instant,
+    on_progress: F,
+}
+
+impl<F: FnMut(u64, u64)> Progress<F> {
+    fn new(total: u64, on_progress: F) -> Self {
+        Self {
+            done: 0,
+            total,
+            last_update: Instant::now(),
+            on_progress,
+        }
+    }
+
+    fn add(&mut self, bytes: usize) {
+        self.done += bytes as u64;
+        if self.last_update.elapsed() >= PROGRESS_UPDATE_INTERVAL {
+            (self.on_progress)(self.done, self.total);
+            self.last_update = Instant::now();
+        }
+    }
+}
+
+/// Read `reader` to its end or `limit` bytes, feeding `writer` and `hasher`
+///
+/// Returns `None` if cancelled.
+fn pipe(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    limit: u64,
+    buffer: &mut [u8],
+    hasher: &mut StreamHasher,
+    cancel: &AtomicBool,
+    progress: &mut Progress<impl FnMut(u64, u64)>,
+) -> Option<std::io::Result<()>> {
+    let mut remaining = limit;
+    while remaining > 0 {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let len = buffer
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        let read = match reader.read(&mut buffer[..len]) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Some(Err(e)),
+        };
+        if let Err(e) = writer.write_all(&buffer[..read]) {
+            return Some(Err(e));
+        }
+        hasher.update(&buffer[..read]);
+        progress.add(read);
+        remaining -= read as u64;
+    }
+    Some(Ok(()))
+}
+
+/// SHA-256 digest of everything fed to `hasher`
+fn sha256_hex(hasher: StreamHasher) -> String {
+    hasher
+        .finalize()
+        .into_iter()
+        .map(|(_, digest)| digest)
+        .next()
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn remove_parts(parts: &[PathBuf]) {
+    for part in parts {
+        let _ = fs::remove_file(part);
+    }
+}
+
+/// Split `path` into numbered parts next to it
+///
+/// `on_progress` is called with the bytes written so far and the file size.
+/// Returns the parts, or `None` if cancelled. Parts written before an error
+/// or cancellation are removed.
+pub fn split_file(
+    path: &Path,
+    mode: SplitMode,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(u64, u64),
+) -> Option<Result<Vec<PathBuf>, String>> {
+    let mut source = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return Some(Err(format!("Failed to open file: {e}"))),
+    };
+    let total = source.metadata().map(|m| m.len()).unwrap_or_default();
+    let part_size = mode.part_size(total);
+    let count = total.div_ceil(part_size);
+    if count < 2 {
+        return Some(Err(format!(
+            "{} is not larger than the part size",
+            file_name(path)
+        )));
+    }
+    let digits = MIN_PART_DIGITS.max(count.to_string().len());
+    let parts: Vec<PathBuf> = (1..=count as usize)
+        .map(|index| part_path_with_digits(path, index, digits))
+        .collect();
+    if let Some(existing) = parts.iter().find(|part| part.symlink_metadata().is_ok()) {
+        return Some(Err(format!("{} already exists", file_name(existing))));
+    }
+
+    let mut hasher = StreamHasher::new(&[HashAlgorithm::Sha256], cancel)?;
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    let mut progress = Progress::new(total, on_progress);
+    let mut written = Vec::new();
+    let mut result = Some(Ok(()));
+    for part in &parts {
+        let mut output = match OpenOptions::new().write(true).create_new(true).open(part) {
+            Ok(file) => file,
+            Err(e) => {
+                result = Some(Err(e));
+                break;
+            }
+        };
+        written.push(part.clone());
+        result = pipe(
+            &mut source,
+            &mut output,
+            part_size,
+            &mut buffer,
+            &mut hasher,
+            cancel,
+            &mut progress,
+        );
+        if !matches!(result, Some(Ok(()))) {
+            break;
+        }
+    }
+
+    let Some(result) = result else {
+        remove_parts(&written);
+        return None;
+    };
+    let outcome = match result {
+        Ok(()) => {
+            let sidecar = checksum::sidecar_path(path, HashAlgorithm::Sha256);
+            let line = checksum::format_line(&sha256_hex(hasher), &file_name(path));
+            crate::utils::atomic_write::write(&sidecar, line)
+                .map_err(|e| format!("Failed to write checksum file: {e}"))
+        }
+        Err(e) => Err(format!("Failed to split {}: {e}", file_name(path))),
+    };
+    match outcome {
+        Ok(()) => Some(Ok(parts)),
+        Err(e) => {
+            remove_parts(&written);
+            Some(Err(e))
+        }
+    }
+}
+
+/// A file joined from its parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinedFile {
+    pub path: PathBuf,
+    pub parts: usize,
+    /// Whether the content matches the checksum written when splitting,
+    /// `None` without a checksum file
+    pub verified: Option<bool>,
+}
+
+/// Join the parts starting with `first_part` into the original file
+///
+/// `on_progress` is called with the bytes written so far and the total size.
+/// Returns `None` if cancelled, the partial file is removed then.
+pub fn join_parts(
+    first_part: &Path,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(u64, u64),
+) -> Option<Result<JoinedFile, String>> {
+    if !is_first_part(first_part) {
+        return Some(Err(format!(
+            "{} is not the first part of a split file",
+            file_name(first_part)
+        )));
+    }
+    let output_path = first_part.with_extension("");
+    if output_path.symlink_metadata().is_ok() {
+        return Some(Err(format!("{} already exists", file_name(&output_path))));
+    }
+    let parts = find_parts(first_part);
+    let total = parts
+        .iter()
+        .filter_map(|part| part.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    let mut hasher = StreamHasher::new(&[HashAlgorithm::Sha256], cancel)?;
+    let mut output = match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&output_path)
+    {
+        Ok(file) => file,
+        Err(e) => return Some(Err(format!("Failed to create file: {e}"))),
+    };
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    let mut progress = Progress::new(total, on_progress);
+    let mut result = Some(Ok(()));
+    for part in &parts {
+        result = match File::open(part) {
+            Ok(mut input) => pipe(
+                &mut input,
+                &mut output,
+                u64::MAX,
+                &mut buffer,
+                &mut hasher,
+                cancel,
+                &mut progress,
+            ),
+            Err(e) => Some(Err(e)),
+        };
+        if !matches!(result, Some(Ok(()))) {
+            break;
+        }
+    }
+    drop(output);
+
+    match result {
+        Some(Ok(())) => {}
+        Some(Err(e)) => {
+            let _ = fs::remove_file(&output_path);
+            return Some(Err(format!(
+                "Failed to join {}: {e}",
+                file_name(first_part)
+            )));
+        }
+        None => {
+            let _ = fs::remove_file(&output_path);
+            return None;
+        }
+    }
+
+    let digest = sha256_hex(hasher);
+    let name = file_name(&output_path);
+    let sidecar = checksum::sidecar_path(&output_path, HashAlgorithm::Sha256);
+    let verified = fs::read_to_string(&sidecar).ok().and_then(|content| {
+        checksum::parse(&file_name(&sidecar), &content)
+            .into_iter()
+            .find(|entry| entry.name == name && entry.algorithm == HashAlgorithm::Sha256)
+            .map(|entry| entry.digest == digest)
+    });
+    Some(Ok(JoinedFile {
+        path: output_path,
+        parts: parts.len(),
+        verified,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_split_mode() {
+        assert_eq!(SplitMode::parse("5"), Ok(SplitMode::PartCount(5)));
+        assert_eq!(
+            SplitMode::parse("25M"),
+            Ok(SplitMode::PartSize(25 * 1024 * 1024))
+        );
+        assert_eq!(
+            SplitMode::parse(" 1.5 GiB "),
+            Ok(SplitMode::PartSize(1536 * 1024 * 1024))
+        );
+        assert_eq!(SplitMode::parse("100b"), Ok(SplitMode::PartSize(100)));
+        assert_eq!(
+            SplitMode::parse("FAT32"),
+            Ok(SplitMode::PartSize(FAT32_MAX_FILE_SIZE))
+        );
+        assert!(SplitMode::parse("1").is_err());
+        assert!(SplitMode::parse("0M").is_err());
+        assert!(SplitMode::parse("5X").is_err());
+        assert!(SplitMode::parse("").is_err());
+    }
+
+    #[test]
+    fn test_split_and_join() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("data.bin");
+        let content: Vec<u8> = (0..2500u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&file, &content).unwrap();
+        let cancel = AtomicBool::new(false);
+
+        let parts = split_file(&file, SplitMode::PartSize(1000), &cancel, |_, _| {})
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            parts,
+            (1..=3).map(|i| part_path(&file, i)).collect::<Vec<_>>()
+        );
+        assert_eq!(std::fs::metadata(&parts[2]).unwrap().len(), 500);
+        assert!(is_first_part(&parts[0]));
+        assert!(!is_first_part(&parts[1]));
+        assert_eq!(find_parts(&parts[0]), parts);
+
+        // Splitting again would overwrite the parts
+        assert!(
+            split_file(&file, SplitMode::PartCount(3), &cancel, |_, _| {})
+                .unwrap()
+                .is_err()
+        );
+
+        std::fs::remove_file(&file).unwrap();
+        let joined = join_parts(&parts[0], &cancel, |_, _| {}).unwrap().unwrap();
+        assert_eq!(
+            joined,
+            JoinedFile {
+                path: file.clone(),
+                parts: 3,
+                verified: Some(true),
+            }
+        );
+        assert_eq!(std::fs::read(&file).unwrap(), content);
+
+        // A corrupted part fails verification
+        std::fs::remove_file(&file).unwrap();
+        std::fs::write(&parts[1], vec![0u8; 1000]).unwrap();
+        let joined = join_parts(&parts[0], &cancel, |_, _| {}).unwrap().unwrap();
+        assert_eq!(joined.verified, Some(false));
+    }
+
+    #[test]
+    fn test_split_cancelled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("data.bin");
+        std::fs::write(&file, vec![1u8; 100]).unwrap();
+
+        let result = split_file(
+            &file,
+            SplitMode::PartCount(4),
+            &AtomicBool::new(true),
+            |_, _| {},
+        );
+        assert!(result.is_none());
+        assert!(!part_path(&file, 1).exists());
+    }
+}
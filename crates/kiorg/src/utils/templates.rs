@@ -0,0 +1,107 @@
+//! Files copied when adding a new file, like a LaTeX skeleton or a script header
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Template files in `dir` sorted by name, hidden files are skipped
+#[must_use]
+pub fn list(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    templates.sort();
+    templates
+}
+
+/// Name of the file created from `template`, taking its extension if `name` has none
+///
+/// An empty `name` keeps the template's own name.
+#[must_use]
+pub fn target_name(name: &str, template: &Path) -> String {
+    if name.is_empty() {
+        return template
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+    }
+    match template.extension() {
+        Some(ext) if Path::new(name).extension().is_none() => {
+            format!("{name}.{}", ext.to_string_lossy())
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Copy `template` to `dest`, failing if `dest` already exists
+///
+/// Permissions are copied along with the content so executable script
+/// templates stay executable.
+pub fn create_from(template: &Path, dest: &Path) -> io::Result<()> {
+    let mut source = fs::File::open(template)?;
+    let mut target = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dest)?;
+    let result = io::copy(&mut source, &mut target)
+        .and_then(|_| target.set_permissions(source.metadata()?.permissions()));
+    if result.is_err() {
+        let _ = fs::remove_file(dest);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_name() {
+        let template = Path::new("/templates/article.tex");
+        assert_eq!(target_name("report", template), "report.tex");
+        assert_eq!(target_name("report.txt", template), "report.txt");
+        assert_eq!(target_name("Makefile", Path::new("Makefile")), "Makefile");
+        assert_eq!(target_name("", template), "article.tex");
+    }
+
+    #[test]
+    fn test_list_and_create_from() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("templates");
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("script.py"), "#!/usr/bin/env python3\n").unwrap();
+        fs::write(dir.join("article.tex"), "\\documentclass{article}\n").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+
+        let templates = list(&dir);
+        assert_eq!(
+            templates,
+            vec![dir.join("article.tex"), dir.join("script.py")]
+        );
+        assert!(list(&temp_dir.path().join("missing")).is_empty());
+
+        let dest = temp_dir.path().join("main.py");
+        create_from(&templates[1], &dest).unwrap();
+        assert_eq!(
+            fs::read_to_string(&dest).unwrap(),
+            "#!/usr/bin/env python3\n"
+        );
+        // Never overwrites an existing file
+        assert!(create_from(&templates[0], &dest).is_err());
+        assert_eq!(
+            fs::read_to_string(&dest).unwrap(),
+            "#!/usr/bin/env python3\n"
+        );
+    }
+}
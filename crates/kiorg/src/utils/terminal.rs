@@ -0,0 +1,300 @@
+//! Running console applications in a terminal emulator
+//!
+//! Desktop entries with `Terminal=true` don't open a window of their own, so
+//! their command is wrapped in the configured terminal emulator, or one found
+//! on the system.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Terminal emulators tried in order when none is configured, with the
+/// arguments running the command that follows
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const KNOWN_EMULATORS: &[(&str, &[&str])] = &[
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("xfce4-terminal", &["-x"]),
+    ("alacritty", &["-e"]),
+    ("kitty", &[]),
+    ("foot", &[]),
+    ("wezterm", &["start", "--"]),
+    ("xterm", &["-e"]),
+];
+
+/// Split a command line into arguments, honoring double quotes and the
+/// backslash escapes allowed inside them like the desktop entry `Exec` key
+pub fn split_command(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut in_quotes = false;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_arg = true;
+            }
+            '\\' if in_quotes => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Arguments of a desktop entry `Exec` command opening `path`
+///
+/// File and URL field codes are replaced with the path, which is appended if
+/// the command has none. The other field codes are dropped.
+pub fn exec_args(exec: &str, path: &Path) -> Vec<String> {
+    let path = path.to_string_lossy();
+    let mut has_path = false;
+    let mut args = Vec::new();
+    for arg in split_command(exec) {
+        match arg.as_str() {
+            "%f" | "%F" | "%u" | "%U" => {
+                has_path = true;
+                args.push(path.to_string());
+            }
+            "%i" | "%c" | "%k" | "%d" | "%D" | "%n" | "%N" | "%v" | "%m" => {}
+            _ => args.push(arg.replace("%%", "%")),
+        }
+    }
+    if !has_path {
+        args.push(path.to_string());
+    }
+    args
+}
+
+/// Command running `args` in a terminal emulator
+///
+/// `emulator` is a command prefix like `alacritty -e`, without it `$TERMINAL`
+/// or a known emulator is used.
+pub fn command_in_terminal(emulator: Option<&str>, args: &[String]) -> io::Result<Command> {
+    let prefix = match emulator {
+        Some(emulator) => split_command(emulator),
+        None => default_emulator()?,
+    };
+    let Some((program, prefix_args)) = prefix.split_first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Terminal emulator command is empty",
+        ));
+    };
+    let mut command = Command::new(program);
+    command.args(prefix_args).args(args);
+    Ok(command)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn default_emulator() -> io::Result<Vec<String>> {
+    if let Ok(terminal) = std::env::var("TERMINAL")
+        && !terminal.trim().is_empty()
+    {
+        let mut prefix = split_command(&terminal);
+        prefix.push("-e".to_string());
+        return Ok(prefix);
+    }
+    KNOWN_EMULATORS
+        .iter()
+        .find(|(program, _)| is_in_path(program))
+        .map(|(program, args)| {
+            std::iter::once(program)
+                .chain(args.iter())
+                .map(|arg| arg.to_string())
+                .collect()
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "No terminal emulator found, set terminal.emulator in the config",
+            )
+        })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn is_in_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// Terminal.app runs a shell command line given through AppleScript
+#[cfg(target_os = "macos")]
+fn default_emulator() -> io::Result<Vec<String>> {
+    Ok(vec!["osascript".to_string(), "-e".to_string()])
+}
+
+#[cfg(target_os = "windows")]
+fn default_emulator() -> io::Result<Vec<String>> {
+    Ok(["cmd", "/c", "start", "", "cmd", "/k"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect())
+}
+
+/// Arguments for the default emulator of the platform, Terminal.app takes a
+/// single AppleScript instead of the command arguments
+#[cfg(target_os = "macos")]
+pub fn default_emulator_args(args: &[String]) -> Vec<String> {
+    let command_line = args
+        .iter()
+        .map(|arg| format!("'{}'", arg.replace('\'', r"'\''")))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let escaped = command_line.replace('\\', r"\\").replace('"', "\\\"");
+    vec![format!(
+        "tell application \"Terminal\"\nactivate\ndo script \"{escaped}\"\nend tell"
+    )]
+}
+
+/// Arguments for the default emulator of the platform, used as they are
+#[cfg(not(target_os = "macos"))]
+pub fn default_emulator_args(args: &[String]) -> Vec<String> {
+    args.to_vec()
+}
+
+/// Open `path` with the desktop entry command `exec` in a terminal emulator
+pub fn open_in_terminal(path: &Path, exec: &str, emulator: Option<&str>) -> io::Result<()> {
+    let args = exec_args(exec, path);
+    let args = if emulator.is_some() {
+        args
+    } else {
+        default_emulator_args(&args)
+    };
+    command_in_terminal(emulator, &args)?.spawn().map(|_| ())
+}
+
+/// Command opening a terminal window in `dir`
+///
+/// `command` is a command line like `wezterm start --cwd %d`, where `%d` is
+/// replaced by the directory. Without it the platform's terminal is used.
+/// The command always runs in `dir` as emulators usually start there.
+pub fn system_terminal_command(command: Option<&str>, dir: &Path) -> io::Result<Command> {
+    let args = match command {
+        Some(command) => split_command(command),
+        None => default_system_terminal()?,
+    };
+    let dir_arg = dir.to_string_lossy();
+    let args: Vec<String> = args
+        .into_iter()
+        .map(|arg| arg.replace("%d", &dir_arg))
+        .collect();
+    let Some((program, args)) = args.split_first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Terminal command is empty",
+        ));
+    };
+    let mut command = Command::new(program);
+    command.args(args).current_dir(dir);
+    Ok(command)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn default_system_terminal() -> io::Result<Vec<String>> {
+    if let Ok(terminal) = std::env::var("TERMINAL")
+        && !terminal.trim().is_empty()
+    {
+        return Ok(split_command(&terminal));
+    }
+    KNOWN_EMULATORS
+        .iter()
+        .find(|(program, _)| is_in_path(program))
+        .map(|(program, _)| vec![program.to_string()])
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "No terminal emulator found, set terminal.command in the config",
+            )
+        })
+}
+
+#[cfg(target_os = "macos")]
+fn default_system_terminal() -> io::Result<Vec<String>> {
+    Ok(["open", "-a", "Terminal", "%d"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect())
+}
+
+/// `start` opens a new console window running in the current directory
+#[cfg(target_os = "windows")]
+fn default_system_terminal() -> io::Result<Vec<String>> {
+    Ok(["cmd", "/c", "start", "", "cmd"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect())
+}
+
+/// Open a terminal window in `dir`, see [`system_terminal_command`]
+pub fn open_system_terminal(dir: &Path, command: Option<&str>) -> io::Result<()> {
+    system_terminal_command(command, dir)?.spawn().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(split_command("vim -p"), ["vim", "-p"]);
+        assert_eq!(
+            split_command(r#"sh -c "echo \"hi\" \$HOME"  x"#),
+            ["sh", "-c", r#"echo "hi" $HOME"#, "x"]
+        );
+        assert_eq!(split_command(r#"a "" b"#), ["a", "", "b"]);
+        assert!(split_command("   ").is_empty());
+    }
+
+    #[test]
+    fn test_exec_args() {
+        let path = Path::new("/tmp/notes 1.txt");
+        assert_eq!(exec_args("vim %F", path), ["vim", "/tmp/notes 1.txt"]);
+        assert_eq!(exec_args("htop", path), ["htop", "/tmp/notes 1.txt"]);
+        assert_eq!(
+            exec_args("mc %i %c --printf 100%% %u", path),
+            ["mc", "--printf", "100%", "/tmp/notes 1.txt"]
+        );
+    }
+
+    #[test]
+    fn test_command_in_terminal() {
+        let args = vec!["vim".to_string(), "/tmp/a.txt".to_string()];
+        let command = command_in_terminal(Some("alacritty -e"), &args).unwrap();
+        assert_eq!(command.get_program(), "alacritty");
+        let command_args: Vec<_> = command.get_args().collect();
+        assert_eq!(command_args, ["-e", "vim", "/tmp/a.txt"]);
+
+        assert!(command_in_terminal(Some(" "), &args).is_err());
+    }
+
+    #[test]
+    fn test_system_terminal_command() {
+        let dir = Path::new("/tmp/my dir");
+        let command = system_terminal_command(Some("wezterm start --cwd %d"), dir).unwrap();
+        assert_eq!(command.get_program(), "wezterm");
+        let command_args: Vec<_> = command.get_args().collect();
+        assert_eq!(command_args, ["start", "--cwd", "/tmp/my dir"]);
+        assert_eq!(command.get_current_dir(), Some(dir));
+
+        assert!(system_terminal_command(Some(" "), dir).is_err());
+    }
+}
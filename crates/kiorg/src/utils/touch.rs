@@ -0,0 +1,75 @@
+//! Updating the timestamps of files and directories, like `touch`
+
+use std::fs::{self, File, FileTimes};
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Open `path` for changing its timestamps, directories included
+fn open_for_times(path: &Path) -> io::Result<File> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        use windows_sys::Win32::Storage::FileSystem::{
+            FILE_FLAG_BACKUP_SEMANTICS, FILE_WRITE_ATTRIBUTES,
+        };
+        // Directories can only be opened with backup semantics
+        fs::OpenOptions::new()
+            .access_mode(FILE_WRITE_ATTRIBUTES)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+            .open(path)
+    }
+    #[cfg(not(windows))]
+    {
+        File::open(path)
+    }
+}
+
+/// Set the access and modification time of `path` to `time`
+///
+/// A missing `path` is created as an empty file. Returns whether it was
+/// created.
+pub fn touch(path: &Path, time: SystemTime) -> io::Result<bool> {
+    let created = match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+    {
+        Ok(_) => true,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => false,
+        Err(e) => return Err(e),
+    };
+    let times = FileTimes::new().set_accessed(time).set_modified(time);
+    open_for_times(path)?.set_times(times)?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_touch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "content").unwrap();
+        assert!(!touch(&file, time).unwrap());
+        assert_eq!(fs::metadata(&file).unwrap().modified().unwrap(), time);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "content");
+
+        let dir = temp_dir.path().join("dir");
+        fs::create_dir(&dir).unwrap();
+        assert!(!touch(&dir, time).unwrap());
+        assert_eq!(fs::metadata(&dir).unwrap().modified().unwrap(), time);
+
+        let new_file = temp_dir.path().join("new.txt");
+        assert!(touch(&new_file, time).unwrap());
+        assert_eq!(fs::metadata(&new_file).unwrap().len(), 0);
+        assert_eq!(fs::metadata(&new_file).unwrap().modified().unwrap(), time);
+
+        assert!(touch(&temp_dir.path().join("missing/new.txt"), time).is_err());
+    }
+}
@@ -0,0 +1,739 @@
+//! Copying and moving files in the background
+//!
+//! Pasted files are queued as jobs run one at a time by a worker thread, so
+//! large trees don't block the UI and concurrent pastes don't compete for the
+//! disk. Files are copied in chunks with progress reports and pause or cancel
+//! checks between chunks, and every copy is read back and compared with its
+//! source before it counts as done. Archive integrity tests run through the
+//! same queue.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::utils::archive::{self, ArchiveTestReport};
+use crate::utils::file_operations::unique_path;
+use crate::utils::hashing::{self, HashAlgorithm, StreamHasher};
+
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+/// Publish progress at most this often to avoid repainting on every chunk
+const PROGRESS_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+/// How often a paused job checks whether it was resumed or cancelled
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferKind {
+    Copy,
+    Move,
+    /// Verify the members of the archive in `sources`, nothing is written
+    Test,
+}
+
+impl TransferKind {
+    pub const fn verb(self) -> &'static str {
+        match self {
+            Self::Copy => "Copying",
+            Self::Move => "Moving",
+            Self::Test => "Testing",
+        }
+    }
+}
+
+/// Progress of a job, summed over all its files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransferProgress {
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    pub files_done: usize,
+    pub total_files: usize,
+    /// File being copied
+    pub current: Option<PathBuf>,
+    /// Bytes per second, averaged over the last updates
+    pub speed: f64,
+    /// Whether the worker started the job, queued jobs wait for the ones
+    /// before them
+    pub started: bool,
+}
+
+/// Result of a finished job
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferSummary {
+    pub kind: TransferKind,
+    pub sources: Vec<PathBuf>,
+    pub dest_dir: PathBuf,
+    /// Source and target of every entry transferred completely
+    pub completed: Vec<(PathBuf, PathBuf)>,
+    pub errors: Vec<String>,
+    pub cancelled: bool,
+    /// Members checked by a [`TransferKind::Test`] job
+    pub test_report: Option<ArchiveTestReport>,
+}
+
+impl TransferSummary {
+    fn new(kind: TransferKind, sources: &[PathBuf], dest_dir: &Path) -> Self {
+        Self {
+            kind,
+            sources: sources.to_vec(),
+            dest_dir: dest_dir.to_path_buf(),
+            completed: Vec::new(),
+            errors: Vec::new(),
+            cancelled: false,
+            test_report: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Control {
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    progress: Mutex<TransferProgress>,
+    summary: Mutex<Option<TransferSummary>>,
+}
+
+/// A queued, running or finished job
+#[derive(Debug)]
+pub struct Transfer {
+    pub kind: TransferKind,
+    pub sources: Vec<PathBuf>,
+    pub dest_dir: PathBuf,
+    control: Arc<Control>,
+}
+
+impl Transfer {
+    pub fn progress(&self) -> TransferProgress {
+        lock(&self.control.progress).clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.control.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.control.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.control.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Stop the job, the entry being transferred is rolled back
+    pub fn cancel(&self) {
+        self.control.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn take_summary(&self) -> Option<TransferSummary> {
+        lock(&self.control.summary).take()
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    pending: VecDeque<(TransferKind, Vec<PathBuf>, PathBuf, Arc<Control>)>,
+    shutdown: bool,
+}
+
+type Shared = Arc<(Mutex<Queue>, Condvar)>;
+
+/// Jobs waiting for, or being run by, the worker thread
+///
+/// The worker starts with the first job and stops once the queue is dropped,
+/// cancelling the jobs left.
+pub struct TransferQueue {
+    transfers: Vec<Transfer>,
+    shared: Shared,
+    worker_started: bool,
+    on_progress: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl TransferQueue {
+    /// `on_progress` is called whenever a job made progress or finished, e.g.
+    /// to wake up the UI
+    pub fn new(on_progress: impl Fn() + Send + Sync + 'static) -> Self {
+        Self {
+            transfers: Vec::new(),
+            shared: Shared::default(),
+            worker_started: false,
+            on_progress: Arc::new(on_progress),
+        }
+    }
+
+    /// Queue copying or moving `sources` into `dest_dir`
+    ///
+    /// Entries whose name is taken in `dest_dir` get a numbered name, e.g.
+    /// `notes_1.txt`.
+    pub fn enqueue(&mut self, kind: TransferKind, sources: Vec<PathBuf>, dest_dir: PathBuf) {
+        let control = Arc::new(Control::default());
+        let (queue, condvar) = &*self.shared;
+        lock(queue)
+            .pending
+            .push_back((kind, sources.clone(), dest_dir.clone(), control.clone()));
+        condvar.notify_one();
+        self.transfers.push(Transfer {
+            kind,
+            sources,
+            dest_dir,
+            control,
+        });
+
+        if !self.worker_started {
+            self.worker_started = true;
+            let shared = self.shared.clone();
+            let on_progress = self.on_progress.clone();
+            thread::spawn(move || run(&shared, &*on_progress));
+        }
+    }
+
+    /// Queue an integrity test of `archive`, decrypted with the password
+    /// entered for it this session if any
+    pub fn enqueue_test(&mut self, archive: PathBuf) {
+        let dest_dir = archive.parent().map(Path::to_path_buf).unwrap_or_default();
+        self.enqueue(TransferKind::Test, vec![archive], dest_dir);
+    }
+
+    /// Jobs not reported by [`Self::take_finished`] yet, in queue order
+    pub fn transfers(&self) -> &[Transfer] {
+        &self.transfers
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.transfers.is_empty()
+    }
+
+    /// Remove the finished jobs and return their results
+    pub fn take_finished(&mut self) -> Vec<TransferSummary> {
+        let mut finished = Vec::new();
+        self.transfers
+            .retain(|transfer| match transfer.take_summary() {
+                Some(summary) => {
+                    finished.push(summary);
+                    false
+                }
+                None => true,
+            });
+        finished
+    }
+}
+
+impl Drop for TransferQueue {
+    fn drop(&mut self) {
+        for transfer in &self.transfers {
+            transfer.cancel();
+        }
+        let (queue, condvar) = &*self.shared;
+        lock(queue).shutdown = true;
+        condvar.notify_one();
+    }
+}
+
+/// Lock shared state, it stays consistent even if a thread panicked holding it
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn run(shared: &Shared, on_progress: &dyn Fn()) {
+    let (queue, condvar) = &**shared;
+    loop {
+        let (kind, sources, dest_dir, control) = {
+            let mut queue = lock(queue);
+            while queue.pending.is_empty() && !queue.shutdown {
+                queue = condvar.wait(queue).unwrap_or_else(|e| e.into_inner());
+            }
+            if queue.shutdown {
+                return;
+            }
+            match queue.pending.pop_front() {
+                Some(job) => job,
+                None => continue,
+            }
+        };
+
+        let summary = Job::new(kind, &control, on_progress).run(&sources, &dest_dir);
+        *lock(&control.summary) = Some(summary);
+        on_progress();
+    }
+}
+
+/// Path in `dest_dir` to paste `path` to, numbered if the name is taken
+pub fn paste_target(path: &Path, dest_dir: &Path) -> PathBuf {
+    unique_path(dest_dir, path.file_name().unwrap_or_default())
+}
+
+/// Outcome of transferring one entry
+enum Step {
+    Done,
+    Cancelled,
+}
+
+/// State of the job the worker is running
+struct Job<'a> {
+    kind: TransferKind,
+    control: &'a Control,
+    on_progress: &'a dyn Fn(),
+    progress: TransferProgress,
+    last_update: Instant,
+    bytes_at_last_update: u64,
+    buffer: Vec<u8>,
+}
+
+impl<'a> Job<'a> {
+    fn new(kind: TransferKind, control: &'a Control, on_progress: &'a dyn Fn()) -> Self {
+        Self {
+            kind,
+            control,
+            on_progress,
+            progress: TransferProgress {
+                started: true,
+                ..Default::default()
+            },
+            last_update: Instant::now(),
+            bytes_at_last_update: 0,
+            buffer: vec![0; COPY_BUFFER_SIZE],
+        }
+    }
+
+    fn run(self, sources: &[PathBuf], dest_dir: &Path) -> TransferSummary {
+        match self.kind {
+            TransferKind::Copy | TransferKind::Move => self.transfer(sources, dest_dir),
+            TransferKind::Test => self.test(sources, dest_dir),
+        }
+    }
+
+    fn transfer(mut self, sources: &[PathBuf], dest_dir: &Path) -> TransferSummary {
+        for source in sources {
+            let (files, bytes) = tree_size(source);
+            self.progress.total_files += files;
+            self.progress.total_bytes += bytes;
+        }
+        self.publish();
+
+        let mut summary = TransferSummary::new(self.kind, sources, dest_dir);
+        for source in sources {
+            if self.is_cancelled() {
+                summary.cancelled = true;
+                break;
+            }
+            if source.is_dir() && dest_dir.starts_with(source) {
+                summary.errors.push(format!(
+                    "Cannot paste {} into itself",
+                    source.to_string_lossy()
+                ));
+                continue;
+            }
+            let target = paste_target(source, dest_dir);
+            let result = if self.kind == TransferKind::Move {
+                self.move_entry(source, &target)
+            } else {
+                self.copy_entry(source, &target)
+            };
+            match result {
+                Ok(Step::Done) => summary.completed.push((source.clone(), target)),
+                Ok(Step::Cancelled) => {
+                    // Only keep entries transferred completely
+                    remove_entry(&target);
+                    summary.cancelled = true;
+                    break;
+                }
+                Err(e) => {
+                    let action = if self.kind == TransferKind::Move {
+                        "move"
+                    } else {
+                        "copy"
+                    };
+                    summary.errors.push(format!(
+                        "Failed to {action} {} to {}: {e}",
+                        source.to_string_lossy(),
+                        target.to_string_lossy()
+                    ));
+                }
+            }
+        }
+        summary
+    }
+
+    /// Read every member of the archive, counting members as files
+    fn test(mut self, sources: &[PathBuf], dest_dir: &Path) -> TransferSummary {
+        let mut summary = TransferSummary::new(self.kind, sources, dest_dir);
+        let Some(path) = sources.first() else {
+            return summary;
+        };
+        self.publish();
+
+        let password = archive::cached_password(path);
+        let result = archive::test_archive(path, password.as_deref(), |current, total, name| {
+            self.progress.files_done = current - 1;
+            self.progress.total_files = total;
+            self.progress.current = Some(PathBuf::from(name));
+            self.report();
+            !self.wait_while_paused()
+        });
+        summary.cancelled = self.is_cancelled();
+        match result {
+            Ok(report) => summary.test_report = Some(report),
+            Err(e) => summary.errors.push(e),
+        }
+        summary
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.control.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Block while paused, returns whether the job was cancelled
+    fn wait_while_paused(&mut self) -> bool {
+        let mut paused = false;
+        while self.control.paused.load(Ordering::Relaxed) && !self.is_cancelled() {
+            if !paused {
+                paused = true;
+                self.progress.speed = 0.0;
+                self.publish();
+            }
+            thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+        if paused {
+            // Don't count the pause in the speed
+            self.last_update = Instant::now();
+            self.bytes_at_last_update = self.progress.bytes_done;
+        }
+        self.is_cancelled()
+    }
+
+    fn publish(&self) {
+        *lock(&self.control.progress) = self.progress.clone();
+        (self.on_progress)();
+    }
+
+    /// Publish progress unless it was published recently
+    fn report(&mut self) {
+        let elapsed = self.last_update.elapsed();
+        if elapsed < PROGRESS_UPDATE_INTERVAL {
+            return;
+        }
+        let bytes = self.progress.bytes_done - self.bytes_at_last_update;
+        let speed = bytes as f64 / elapsed.as_secs_f64();
+        self.progress.speed = if self.progress.speed > 0.0 {
+            (self.progress.speed + speed) / 2.0
+        } else {
+            speed
+        };
+        self.last_update = Instant::now();
+        self.bytes_at_last_update = self.progress.bytes_done;
+        self.publish();
+    }
+
+    fn move_entry(&mut self, source: &Path, target: &Path) -> io::Result<Step> {
+        match std::fs::rename(source, target) {
+            Ok(()) => {
+                let (files, bytes) = tree_size(target);
+                self.progress.files_done += files;
+                self.progress.bytes_done += bytes;
+                self.report();
+                Ok(Step::Done)
+            }
+            // Error 18 is "Invalid cross-device link"
+            Err(e) if e.raw_os_error() == Some(18) => {
+                if let Step::Cancelled = self.copy_entry(source, target)? {
+                    return Ok(Step::Cancelled);
+                }
+                if source.is_dir() && !source.is_symlink() {
+                    std::fs::remove_dir_all(source)?;
+                } else {
+                    std::fs::remove_file(source)?;
+                }
+                Ok(Step::Done)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn copy_entry(&mut self, source: &Path, target: &Path) -> io::Result<Step> {
+        let metadata = source.symlink_metadata()?;
+        if metadata.is_symlink() {
+            copy_symlink(source, target)?;
+            self.progress.files_done += 1;
+            self.report();
+            return Ok(Step::Done);
+        }
+        if metadata.is_dir() {
+            std::fs::create_dir_all(target)?;
+            for entry in std::fs::read_dir(source)? {
+                let entry = entry?;
+                if let Step::Cancelled =
+                    self.copy_entry(&entry.path(), &target.join(entry.file_name()))?
+                {
+                    return Ok(Step::Cancelled);
+                }
+            }
+            std::fs::set_permissions(target, metadata.permissions())?;
+            return Ok(Step::Done);
+        }
+        self.copy_file(source, target, &metadata)
+    }
+
+    fn copy_file(
+        &mut self,
+        source: &Path,
+        target: &Path,
+        metadata: &std::fs::Metadata,
+    ) -> io::Result<Step> {
+        self.progress.current = Some(source.to_path_buf());
+        self.publish();
+
+        let mut reader = File::open(source)?;
+        let mut writer = File::create(target)?;
+        let cancelled = || {
+            let _ = std::fs::remove_file(target);
+            Ok(Step::Cancelled)
+        };
+        let Some(mut hasher) = StreamHasher::new(&[HashAlgorithm::Blake3], &self.control.cancelled)
+        else {
+            drop(writer);
+            return cancelled();
+        };
+        loop {
+            if self.wait_while_paused() {
+                drop(writer);
+                return cancelled();
+            }
+            let read = match reader.read(&mut self.buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            writer.write_all(&self.buffer[..read])?;
+            hasher.update(&self.buffer[..read]);
+            self.progress.bytes_done += read as u64;
+            self.report();
+        }
+        writer.set_permissions(metadata.permissions())?;
+        if let Ok(modified) = metadata.modified() {
+            let _ = writer.set_modified(modified);
+        }
+        drop(writer);
+
+        // Give the slot back, reading the copy needs one as well
+        let expected = hasher.finalize();
+        match hashing::hash_file(
+            target,
+            &[HashAlgorithm::Blake3],
+            &self.control.cancelled,
+            |_, _| {},
+        ) {
+            None => return cancelled(),
+            Some(Ok(digests)) if digests == expected => {}
+            Some(Ok(_)) => {
+                let _ = std::fs::remove_file(target);
+                return Err(io::Error::other("the copy doesn't match the source"));
+            }
+            Some(Err(e)) => return Err(io::Error::other(e)),
+        }
+
+        self.progress.files_done += 1;
+        self.progress.current = None;
+        self.report();
+        Ok(Step::Done)
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(source)?, target)
+}
+
+/// Copy the file a link points to where links need privileges
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, target: &Path) -> io::Result<()> {
+    std::fs::copy(source, target).map(|_| ())
+}
+
+/// Number of files and their total size under `path`, links aren't followed
+fn tree_size(path: &Path) -> (usize, u64) {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return (0, 0);
+    };
+    if !metadata.is_dir() {
+        return (
+            1,
+            if metadata.is_symlink() {
+                0
+            } else {
+                metadata.len()
+            },
+        );
+    }
+    std::fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| tree_size(&entry.path()))
+        .fold((0, 0), |(files, bytes), (f, b)| (files + f, bytes + b))
+}
+
+/// Remove a partially transferred entry
+fn remove_entry(path: &Path) {
+    match path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => {
+            let _ = std::fs::remove_dir_all(path);
+        }
+        Ok(_) => {
+            let _ = std::fs::remove_file(path);
+        }
+        Err(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_until_idle(queue: &mut TransferQueue) -> Vec<TransferSummary> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut finished = Vec::new();
+        while !queue.is_idle() && Instant::now() < deadline {
+            finished.extend(queue.take_finished());
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(queue.is_idle(), "transfers should finish");
+        finished
+    }
+
+    #[test]
+    fn test_paste_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest = temp_dir.path();
+        let source = Path::new("/elsewhere/notes.txt");
+        assert_eq!(paste_target(source, dest), dest.join("notes.txt"));
+        std::fs::write(dest.join("notes.txt"), "").unwrap();
+        std::fs::write(dest.join("notes_1.txt"), "").unwrap();
+        assert_eq!(paste_target(source, dest), dest.join("notes_2.txt"));
+    }
+
+    #[test]
+    fn test_copy_and_move() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src = temp_dir.path().join("src");
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir_all(src.join("dir/nested")).unwrap();
+        std::fs::create_dir(&dest).unwrap();
+        std::fs::write(src.join("a.txt"), "aaa").unwrap();
+        std::fs::write(src.join("dir/b.txt"), vec![7u8; 3 * COPY_BUFFER_SIZE + 5]).unwrap();
+        std::fs::write(src.join("dir/nested/c.txt"), "c").unwrap();
+        std::fs::write(dest.join("a.txt"), "existing").unwrap();
+
+        let mut queue = TransferQueue::new(|| {});
+        queue.enqueue(
+            TransferKind::Copy,
+            vec![src.join("a.txt"), src.join("dir")],
+            dest.clone(),
+        );
+        queue.enqueue(
+            TransferKind::Move,
+            vec![src.join("dir/nested")],
+            dest.clone(),
+        );
+        let finished = wait_until_idle(&mut queue);
+        assert_eq!(finished.len(), 2);
+
+        let copy = &finished[0];
+        assert_eq!(copy.kind, TransferKind::Copy);
+        assert!(copy.errors.is_empty());
+        assert_eq!(
+            copy.completed,
+            vec![
+                (src.join("a.txt"), dest.join("a_1.txt")),
+                (src.join("dir"), dest.join("dir")),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest.join("a.txt")).unwrap(),
+            "existing"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest.join("a_1.txt")).unwrap(),
+            "aaa"
+        );
+        assert_eq!(
+            std::fs::read(dest.join("dir/b.txt")).unwrap().len(),
+            3 * COPY_BUFFER_SIZE + 5
+        );
+        assert!(dest.join("dir/nested/c.txt").exists());
+
+        let moved = &finished[1];
+        assert_eq!(moved.kind, TransferKind::Move);
+        assert_eq!(
+            moved.completed,
+            vec![(src.join("dir/nested"), dest.join("nested"))]
+        );
+        assert!(!src.join("dir/nested").exists());
+        assert!(dest.join("nested/c.txt").exists());
+    }
+
+    #[test]
+    fn test_pause_and_cancel() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        let big = temp_dir.path().join("big.bin");
+        std::fs::write(&big, vec![1u8; 8 * COPY_BUFFER_SIZE]).unwrap();
+
+        let mut queue = TransferQueue::new(|| {});
+        queue.enqueue(TransferKind::Copy, vec![big.clone()], dest.clone());
+        queue.transfers()[0].set_paused(true);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !queue.transfers()[0].progress().started && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(200));
+        // At most the chunk read before noticing the pause is copied
+        assert!(queue.transfers()[0].progress().bytes_done <= COPY_BUFFER_SIZE as u64);
+        assert!(queue.take_finished().is_empty());
+
+        queue.transfers()[0].cancel();
+        let finished = wait_until_idle(&mut queue);
+        assert!(finished[0].cancelled);
+        assert!(finished[0].completed.is_empty());
+        assert!(!dest.join("big.bin").exists());
+        assert!(big.exists());
+
+        // Pasting a directory into itself fails
+        let dir = temp_dir.path().join("dir");
+        std::fs::create_dir(&dir).unwrap();
+        queue.enqueue(TransferKind::Copy, vec![dir.clone()], dir.clone());
+        let finished = wait_until_idle(&mut queue);
+        assert_eq!(finished[0].errors.len(), 1);
+        assert!(finished[0].completed.is_empty());
+    }
+
+    #[test]
+    fn test_archive_test_job() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("ok.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        for name in ["a.txt", "b.txt"] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(name.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut queue = TransferQueue::new(|| {});
+        queue.enqueue_test(path.clone());
+        queue.enqueue_test(temp_dir.path().join("missing.zip"));
+        assert_eq!(queue.transfers()[0].kind, TransferKind::Test);
+        assert_eq!(queue.transfers()[0].sources, vec![path]);
+        let finished = wait_until_idle(&mut queue);
+
+        let report = finished[0].test_report.as_ref().unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.tested, 2);
+        assert!(finished[0].errors.is_empty());
+        assert!(finished[1].test_report.is_none());
+        assert_eq!(finished[1].errors.len(), 1);
+    }
+}
@@ -0,0 +1,648 @@
+//! Moving files to the trash of the platform and restoring them
+//!
+//! Linux and other Unix systems follow the freedesktop.org Trash
+//! specification, so items trashed by kiorg show up in the trash of the
+//! desktop and the other way around. Windows uses the Recycle Bin and macOS
+//! the Finder trash.
+
+use chrono::NaiveDateTime;
+use std::io;
+use std::path::PathBuf;
+
+/// A file or directory in the trash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashItem {
+    /// Name of the item before it was trashed
+    pub name: String,
+    /// Where the item is restored to, unknown for items trashed by Finder
+    pub original_path: Option<PathBuf>,
+    pub deleted_at: Option<NaiveDateTime>,
+    /// The trashed file or directory
+    pub path: PathBuf,
+    /// File describing the item, removed once it's restored
+    info_path: Option<PathBuf>,
+}
+
+#[cfg(all(
+    not(any(test, feature = "testing")),
+    not(any(target_os = "macos", target_os = "windows"))
+))]
+pub use freedesktop::{list_trash, move_to_trash};
+#[cfg(all(not(any(test, feature = "testing")), target_os = "macos"))]
+pub use macos::{list_trash, move_to_trash};
+#[cfg(any(test, feature = "testing"))]
+pub use testing::{list_trash, move_to_trash};
+#[cfg(all(not(any(test, feature = "testing")), target_os = "windows"))]
+pub use windows::{list_trash, move_to_trash};
+
+/// Move a trashed item back to where it was
+///
+/// Returns the restored path. Fails instead of overwriting an entry created
+/// at that path since.
+pub fn restore(item: &TrashItem) -> io::Result<PathBuf> {
+    let Some(original_path) = &item.original_path else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("The original location of {} is unknown", item.name),
+        ));
+    };
+    if original_path.symlink_metadata().is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", original_path.display()),
+        ));
+    }
+    if let Some(parent) = original_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::utils::file_operations::omni_rename(&item.path, original_path)?;
+    if let Some(info_path) = &item.info_path {
+        std::fs::remove_file(info_path)?;
+    }
+    Ok(original_path.clone())
+}
+
+/// Sort items with the most recently trashed first
+fn sort_items(items: &mut [TrashItem]) {
+    items.sort_by(|a, b| {
+        b.deleted_at
+            .cmp(&a.deleted_at)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Tests trash files into a directory of their own instead of the trash of
+/// the user
+#[cfg(any(test, feature = "testing"))]
+mod testing {
+    use super::{TrashItem, freedesktop};
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    fn trash_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("kiorg-test-trash-{}", std::process::id()))
+    }
+
+    pub fn move_to_trash(path: &Path) -> io::Result<()> {
+        freedesktop::trash_into(&trash_dir(), path)
+    }
+
+    pub fn list_trash() -> io::Result<Vec<TrashItem>> {
+        let mut items = freedesktop::list_trash_dir(&trash_dir());
+        super::sort_items(&mut items);
+        Ok(items)
+    }
+}
+
+/// <https://specifications.freedesktop.org/trash-spec/latest/>
+#[cfg(any(
+    test,
+    feature = "testing",
+    not(any(target_os = "macos", target_os = "windows"))
+))]
+mod freedesktop {
+    use super::TrashItem;
+    use chrono::NaiveDateTime;
+    use std::fs::OpenOptions;
+    use std::io::{self, Write};
+    use std::path::{Path, PathBuf};
+
+    const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+    /// Characters kept as they are in the `Path` key, like URIs
+    fn is_unreserved(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte)
+    }
+
+    pub fn encode_path(path: &Path) -> String {
+        path.to_string_lossy()
+            .bytes()
+            .map(|byte| {
+                if is_unreserved(byte) {
+                    (byte as char).to_string()
+                } else {
+                    format!("%{byte:02X}")
+                }
+            })
+            .collect()
+    }
+
+    pub fn decode_path(value: &str) -> PathBuf {
+        let bytes = value.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%'
+                && let Some(byte) = value
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+        PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+    }
+
+    /// Content of the `.trashinfo` file of `original_path`
+    pub fn trash_info(original_path: &Path, deleted_at: NaiveDateTime) -> String {
+        format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            encode_path(original_path),
+            deleted_at.format(DATE_FORMAT)
+        )
+    }
+
+    /// Original path and deletion date of a `.trashinfo` file
+    ///
+    /// Relative paths are relative to the directory containing the trash.
+    pub fn parse_trash_info(
+        content: &str,
+        trash_dir: &Path,
+    ) -> Option<(PathBuf, Option<NaiveDateTime>)> {
+        let mut in_section = false;
+        let mut path = None;
+        let mut deleted_at = None;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                in_section = line == "[Trash Info]";
+                continue;
+            }
+            if !in_section {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("Path=") {
+                path = Some(decode_path(value));
+            } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+                deleted_at = NaiveDateTime::parse_from_str(value, DATE_FORMAT).ok();
+            }
+        }
+        let path = path?;
+        let path = if path.is_absolute() {
+            path
+        } else {
+            trash_dir.parent()?.join(path)
+        };
+        Some((path, deleted_at))
+    }
+
+    /// Move `path` into the trash directory `trash_dir`, which must be on the
+    /// same file system
+    pub fn trash_into(trash_dir: &Path, path: &Path) -> io::Result<()> {
+        let path = std::path::absolute(path)?;
+        let Some(name) = path.file_name() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Cannot trash {}", path.display()),
+            ));
+        };
+        let files_dir = trash_dir.join("files");
+        let info_dir = trash_dir.join("info");
+        std::fs::create_dir_all(&files_dir)?;
+        std::fs::create_dir_all(&info_dir)?;
+
+        // Claim a name by creating its info file, which fails if another
+        // process took it first
+        let name = name.to_string_lossy();
+        let info = trash_info(&path, chrono::Local::now().naive_local());
+        for n in 0.. {
+            let trashed_name = if n == 0 {
+                name.to_string()
+            } else {
+                match name.rsplit_once('.') {
+                    Some((stem, ext)) if !stem.is_empty() => format!("{stem}.{n}.{ext}"),
+                    _ => format!("{name}.{n}"),
+                }
+            };
+            let info_path = info_dir.join(format!("{trashed_name}.trashinfo"));
+            let mut info_file = match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&info_path)
+            {
+                Ok(file) => file,
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            };
+            let trashed_path = files_dir.join(&trashed_name);
+            if trashed_path.symlink_metadata().is_ok() {
+                // Left over without an info file
+                drop(info_file);
+                std::fs::remove_file(&info_path)?;
+                continue;
+            }
+            let result = info_file
+                .write_all(info.as_bytes())
+                .and_then(|()| std::fs::rename(&path, &trashed_path));
+            if let Err(e) = result {
+                drop(info_file);
+                let _ = std::fs::remove_file(&info_path);
+                return Err(e);
+            }
+            return Ok(());
+        }
+        unreachable!("the range of names is unbounded")
+    }
+
+    /// Items of the trash directory `trash_dir`
+    pub fn list_trash_dir(trash_dir: &Path) -> Vec<TrashItem> {
+        let Ok(entries) = std::fs::read_dir(trash_dir.join("info")) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let info_path = entry.path();
+                let file_name = info_path.file_name()?.to_str()?;
+                let trashed_name = file_name.strip_suffix(".trashinfo")?;
+                let path = trash_dir.join("files").join(trashed_name);
+                path.symlink_metadata().ok()?;
+                let content = std::fs::read_to_string(&info_path).ok()?;
+                let (original_path, deleted_at) = parse_trash_info(&content, trash_dir)?;
+                Some(TrashItem {
+                    name: original_path.file_name()?.to_string_lossy().into_owned(),
+                    original_path: Some(original_path),
+                    deleted_at,
+                    path,
+                    info_path: Some(info_path),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(all(
+        not(any(test, feature = "testing")),
+        not(any(target_os = "macos", target_os = "windows"))
+    ))]
+    pub use platform::{list_trash, move_to_trash};
+
+    #[cfg(all(
+        not(any(test, feature = "testing")),
+        not(any(target_os = "macos", target_os = "windows"))
+    ))]
+    mod platform {
+        use super::{list_trash_dir, trash_into};
+        use crate::utils::trash::TrashItem;
+        use std::io;
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        use std::path::{Path, PathBuf};
+
+        const STICKY_BIT: u32 = 0o1000;
+
+        /// `$XDG_DATA_HOME/Trash`
+        fn home_trash() -> Option<PathBuf> {
+            dirs::data_dir().map(|data_home| data_home.join("Trash"))
+        }
+
+        fn uid() -> Option<u32> {
+            std::fs::metadata("/proc/self")
+                .or_else(|_| std::fs::metadata(dirs::home_dir().unwrap_or_default()))
+                .ok()
+                .map(|metadata| metadata.uid())
+        }
+
+        /// Device of `path`, or of its closest existing ancestor
+        fn device(path: &Path) -> Option<u64> {
+            path.ancestors()
+                .find_map(|ancestor| ancestor.metadata().ok())
+                .map(|metadata| metadata.dev())
+        }
+
+        /// Top directory of the mount `path` is on
+        fn mount_point(path: &Path, dev: u64) -> PathBuf {
+            let mut top = path.to_path_buf();
+            for ancestor in path.ancestors().skip(1) {
+                match ancestor.metadata() {
+                    Ok(metadata) if metadata.dev() == dev => top = ancestor.to_path_buf(),
+                    _ => break,
+                }
+            }
+            top
+        }
+
+        /// Trash directories of the mount `topdir`, the shared `.Trash` is only
+        /// used if the administrator set it up safely
+        fn topdir_trashes(topdir: &Path, uid: u32) -> [Option<PathBuf>; 2] {
+            let shared = topdir.join(".Trash");
+            let shared_is_safe = shared.symlink_metadata().is_ok_and(|metadata| {
+                metadata.is_dir() && metadata.permissions().mode() & STICKY_BIT != 0
+            });
+            [
+                shared_is_safe.then(|| shared.join(uid.to_string())),
+                Some(topdir.join(format!(".Trash-{uid}"))),
+            ]
+        }
+
+        pub fn move_to_trash(path: &Path) -> io::Result<()> {
+            let path = std::path::absolute(path)?;
+            let not_found = || io::Error::new(io::ErrorKind::NotFound, "No trash directory");
+            let home_trash = home_trash().ok_or_else(not_found)?;
+            let dev = path.symlink_metadata()?.dev();
+            if device(&home_trash) == Some(dev) {
+                return trash_into(&home_trash, &path);
+            }
+
+            let uid = uid().ok_or_else(not_found)?;
+            let topdir = mount_point(path.parent().unwrap_or(&path), dev);
+            let mut last_error = not_found();
+            for trash_dir in topdir_trashes(&topdir, uid).into_iter().flatten() {
+                if !trash_dir.exists()
+                    && let Err(e) = std::fs::create_dir_all(&trash_dir).and_then(|()| {
+                        std::fs::set_permissions(&trash_dir, PermissionsExt::from_mode(0o700))
+                    })
+                {
+                    last_error = e;
+                    continue;
+                }
+                match trash_into(&trash_dir, &path) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => last_error = e,
+                }
+            }
+            Err(last_error)
+        }
+
+        /// Mount points from the kernel mount table
+        fn mount_points() -> Vec<PathBuf> {
+            std::fs::read_to_string("/proc/self/mounts")
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| line.split_whitespace().nth(1))
+                // Spaces and other special characters are escaped as octal
+                .map(|point| point.replace("\\040", " "))
+                .map(PathBuf::from)
+                .collect()
+        }
+
+        pub fn list_trash() -> io::Result<Vec<TrashItem>> {
+            let mut items = home_trash()
+                .map(|trash| list_trash_dir(&trash))
+                .unwrap_or_default();
+            if let Some(uid) = uid() {
+                for topdir in mount_points() {
+                    for trash_dir in topdir_trashes(&topdir, uid).into_iter().flatten() {
+                        items.extend(list_trash_dir(&trash_dir));
+                    }
+                }
+            }
+            super::super::sort_items(&mut items);
+            Ok(items)
+        }
+    }
+}
+
+#[cfg(all(not(any(test, feature = "testing")), target_os = "macos"))]
+mod macos {
+    use super::TrashItem;
+    use objc2_foundation::{NSFileManager, NSString, NSURL};
+    use std::io;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    pub fn move_to_trash(path: &Path) -> io::Result<()> {
+        let Some(path_str) = path.to_str() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Path is not valid UTF-8",
+            ));
+        };
+        let url = unsafe { NSURL::fileURLWithPath(&NSString::from_str(path_str)) };
+        let manager = unsafe { NSFileManager::defaultManager() };
+        unsafe { manager.trashItemAtURL_resultingItemURL_error(&url, None) }
+            .map_err(|e| io::Error::other(e.localizedDescription().to_string()))
+    }
+
+    /// Items of the Finder trash of the home volume
+    ///
+    /// Finder keeps the original locations to itself, so items can only be
+    /// put back from Finder.
+    pub fn list_trash() -> io::Result<Vec<TrashItem>> {
+        let Some(trash_dir) = dirs::home_dir().map(|home| home.join(".Trash")) else {
+            return Ok(Vec::new());
+        };
+        let mut items: Vec<_> = std::fs::read_dir(trash_dir)?
+            .flatten()
+            .filter(|entry| entry.file_name() != ".DS_Store")
+            .map(|entry| {
+                // Moving the item into the trash changed its status
+                let deleted_at = entry
+                    .path()
+                    .symlink_metadata()
+                    .ok()
+                    .and_then(|metadata| chrono::DateTime::from_timestamp(metadata.ctime(), 0))
+                    .map(|time| time.with_timezone(&chrono::Local).naive_local());
+                TrashItem {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    original_path: None,
+                    deleted_at,
+                    path: entry.path(),
+                    info_path: None,
+                }
+            })
+            .collect();
+        super::sort_items(&mut items);
+        Ok(items)
+    }
+}
+
+#[cfg(all(not(any(test, feature = "testing")), target_os = "windows"))]
+mod windows {
+    use super::TrashItem;
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+    use windows_sys::Win32::UI::Shell::{
+        FO_DELETE, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, SHFILEOPSTRUCTW,
+        SHFileOperationW,
+    };
+
+    /// Seconds between the FILETIME epoch, 1601, and the Unix epoch
+    const FILETIME_UNIX_OFFSET: i64 = 11_644_473_600;
+
+    pub fn move_to_trash(path: &Path) -> io::Result<()> {
+        let path = std::path::absolute(path)?;
+        // The list of paths ends with an empty path
+        let from: Vec<u16> = path.as_os_str().encode_wide().chain([0, 0]).collect();
+        let mut operation = SHFILEOPSTRUCTW {
+            hwnd: std::ptr::null_mut(),
+            wFunc: FO_DELETE as _,
+            pFrom: from.as_ptr(),
+            pTo: std::ptr::null(),
+            fFlags: (FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT) as _,
+            fAnyOperationsAborted: 0,
+            hNameMappings: std::ptr::null_mut(),
+            lpszProgressTitle: std::ptr::null(),
+        };
+        let result = unsafe { SHFileOperationW(&mut operation) };
+        if result != 0 || operation.fAnyOperationsAborted != 0 {
+            return Err(io::Error::other(format!(
+                "Failed to move {} to the Recycle Bin (error {result:#x})",
+                path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Original path and deletion time from a `$I` file of the Recycle Bin
+    fn parse_index_file(bytes: &[u8]) -> Option<(PathBuf, i64)> {
+        let read_i64 =
+            |at: usize| Some(i64::from_le_bytes(bytes.get(at..at + 8)?.try_into().ok()?));
+        let version = read_i64(0)?;
+        let filetime = read_i64(16)?;
+        let name: &[u8] = match version {
+            1 => bytes.get(24..24 + 520)?,
+            2 => {
+                let len = u32::from_le_bytes(bytes.get(24..28)?.try_into().ok()?) as usize;
+                bytes.get(28..28 + len * 2)?
+            }
+            _ => return None,
+        };
+        let wide: Vec<u16> = name
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .take_while(|c| *c != 0)
+            .collect();
+        let unix_time = filetime / 10_000_000 - FILETIME_UNIX_OFFSET;
+        Some((PathBuf::from(String::from_utf16_lossy(&wide)), unix_time))
+    }
+
+    /// Items of the Recycle Bins of the current user on all drives
+    pub fn list_trash() -> io::Result<Vec<TrashItem>> {
+        let mut items = Vec::new();
+        for letter in b'A'..=b'Z' {
+            let bin = PathBuf::from(format!("{}:\\$Recycle.Bin", letter as char));
+            // Only the folder of the current user is readable
+            let Ok(user_dirs) = std::fs::read_dir(&bin) else {
+                continue;
+            };
+            for entries in user_dirs
+                .flatten()
+                .filter_map(|dir| std::fs::read_dir(dir.path()).ok())
+            {
+                for entry in entries.flatten() {
+                    let index_path = entry.path();
+                    let Some(suffix) = entry
+                        .file_name()
+                        .to_str()
+                        .and_then(|n| n.strip_prefix("$I"))
+                        .map(str::to_string)
+                    else {
+                        continue;
+                    };
+                    let path = index_path.with_file_name(format!("$R{suffix}"));
+                    if path.symlink_metadata().is_err() {
+                        continue;
+                    }
+                    let Some((original_path, unix_time)) = std::fs::read(&index_path)
+                        .ok()
+                        .and_then(|bytes| parse_index_file(&bytes))
+                    else {
+                        continue;
+                    };
+                    let deleted_at = chrono::DateTime::from_timestamp(unix_time, 0)
+                        .map(|time| time.with_timezone(&chrono::Local).naive_local());
+                    items.push(TrashItem {
+                        name: original_path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                        original_path: Some(original_path),
+                        deleted_at,
+                        path,
+                        info_path: Some(index_path),
+                    });
+                }
+            }
+        }
+        super::sort_items(&mut items);
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::Path;
+
+    #[test]
+    fn test_trash_info() {
+        let deleted_at = NaiveDate::from_ymd_opt(2024, 3, 9)
+            .unwrap()
+            .and_hms_opt(14, 5, 0)
+            .unwrap();
+        let path = Path::new("/home/me/My Notes/café 100%.txt");
+        let info = freedesktop::trash_info(path, deleted_at);
+        assert_eq!(
+            info,
+            "[Trash Info]\nPath=/home/me/My%20Notes/caf%C3%A9%20100%25.txt\n\
+             DeletionDate=2024-03-09T14:05:00\n"
+        );
+        assert_eq!(
+            freedesktop::parse_trash_info(&info, Path::new("/home/me/.local/share/Trash")),
+            Some((path.to_path_buf(), Some(deleted_at)))
+        );
+
+        // Paths in the trash of a mount are relative to its top directory
+        let relative = "[Trash Info]\nPath=photos/a.jpg\nDeletionDate=bad\n";
+        assert_eq!(
+            freedesktop::parse_trash_info(relative, Path::new("/mnt/usb/.Trash-1000")),
+            Some((PathBuf::from("/mnt/usb/photos/a.jpg"), None))
+        );
+        assert_eq!(
+            freedesktop::parse_trash_info("[Other]\nPath=/a\n", Path::new("/t")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_trash_and_restore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let trash_dir = temp_dir.path().join("Trash");
+        let file = temp_dir.path().join("notes.txt");
+        let dir = temp_dir.path().join("dir");
+        std::fs::write(&file, "first").unwrap();
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("inner.txt"), "inner").unwrap();
+
+        freedesktop::trash_into(&trash_dir, &file).unwrap();
+        std::fs::write(&file, "second").unwrap();
+        freedesktop::trash_into(&trash_dir, &file).unwrap();
+        freedesktop::trash_into(&trash_dir, &dir).unwrap();
+        assert!(!file.exists());
+        assert!(!dir.exists());
+        assert!(trash_dir.join("files/notes.txt").exists());
+        assert!(trash_dir.join("files/notes.1.txt").exists());
+        assert!(trash_dir.join("info/notes.1.txt.trashinfo").exists());
+
+        let mut items = freedesktop::list_trash_dir(&trash_dir);
+        sort_items(&mut items);
+        assert_eq!(items.len(), 3);
+        let notes: Vec<_> = items
+            .iter()
+            .filter(|item| item.name == "notes.txt")
+            .collect();
+        assert_eq!(notes.len(), 2);
+        assert!(
+            notes
+                .iter()
+                .all(|item| item.original_path.as_deref() == Some(file.as_path()))
+        );
+
+        let dir_item = items.iter().find(|item| item.name == "dir").unwrap();
+        assert_eq!(restore(dir_item).unwrap(), dir);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("inner.txt")).unwrap(),
+            "inner"
+        );
+
+        // Restoring doesn't overwrite
+        restore(notes[0]).unwrap();
+        assert!(restore(notes[1]).is_err());
+        assert_eq!(freedesktop::list_trash_dir(&trash_dir).len(), 1);
+    }
+}
@@ -0,0 +1,213 @@
+//! Set an image as the desktop wallpaper
+//!
+//! Linux goes through the desktop environment's own tool: `gsettings` on
+//! GNOME based desktops, `plasma-apply-wallpaperimage` on KDE Plasma and
+//! `feh` everywhere else. macOS uses `NSWorkspace`, which is also the only
+//! platform that can set the wallpaper of a single display. Windows uses
+//! `SystemParametersInfoW`.
+
+use std::path::Path;
+
+/// Whether desktops can show the image as wallpaper
+#[must_use]
+pub fn is_supported(path: &Path) -> bool {
+    matches!(
+        crate::ui::preview::path_to_ext_info(path).as_str(),
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp"
+    )
+}
+
+/// Names of the displays the wallpaper can be set for individually
+///
+/// Empty when the platform only supports one wallpaper for all displays.
+#[must_use]
+pub fn monitors() -> Vec<String> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::monitors()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Set `path` as wallpaper of the display at `monitor` in [`monitors`], or
+/// of all displays
+pub fn set_wallpaper(path: &Path, monitor: Option<usize>) -> Result<(), String> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {e}", path.display()))?;
+    #[cfg(target_os = "macos")]
+    {
+        macos::set_wallpaper(&path, monitor)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = monitor;
+        windows::set_wallpaper(&path)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = monitor;
+        let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+        for mut command in linux_commands(&desktop, &path) {
+            let program = command.get_program().to_string_lossy().to_string();
+            let output = command.output().map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    format!("{program} not found, install feh to set wallpapers on this desktop")
+                } else {
+                    format!("Failed to run {program}: {e}")
+                }
+            })?;
+            if !output.status.success() {
+                return Err(format!(
+                    "{program} failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Commands that set the wallpaper on the desktop named by `XDG_CURRENT_DESKTOP`
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn linux_commands(desktop: &str, path: &Path) -> Vec<std::process::Command> {
+    use std::process::Command;
+
+    let desktop = desktop.to_lowercase();
+    if ["gnome", "unity", "budgie", "pantheon"]
+        .iter()
+        .any(|name| desktop.contains(name))
+    {
+        let uri = crate::utils::file_operations::path_to_uri(path);
+        ["picture-uri", "picture-uri-dark"]
+            .into_iter()
+            .map(|key| {
+                let mut command = Command::new("gsettings");
+                command.args(["set", "org.gnome.desktop.background", key, &uri]);
+                command
+            })
+            .collect()
+    } else if desktop.contains("kde") {
+        let mut command = Command::new("plasma-apply-wallpaperimage");
+        command.arg(path);
+        vec![command]
+    } else {
+        let mut command = Command::new("feh");
+        command.arg("--bg-fill").arg(path);
+        vec![command]
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::path::Path;
+
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send, msg_send_id};
+    use objc2_app_kit::NSWorkspace;
+    use objc2_foundation::{NSArray, NSDictionary, NSError, NSString, NSURL};
+
+    fn screens() -> Retained<NSArray<AnyObject>> {
+        unsafe { msg_send_id![class!(NSScreen), screens] }
+    }
+
+    pub fn monitors() -> Vec<String> {
+        screens()
+            .iter()
+            .map(|screen| {
+                let name: Retained<NSString> = unsafe { msg_send_id![screen, localizedName] };
+                name.to_string()
+            })
+            .collect()
+    }
+
+    pub fn set_wallpaper(path: &Path, monitor: Option<usize>) -> Result<(), String> {
+        let Some(path_str) = path.to_str() else {
+            return Err("Path is not valid UTF-8".to_string());
+        };
+        let url = unsafe { NSURL::fileURLWithPath(&NSString::from_str(path_str)) };
+        let options = NSDictionary::<NSString, AnyObject>::new();
+        let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+        for (index, screen) in screens().iter().enumerate() {
+            if monitor.is_some_and(|monitor| monitor != index) {
+                continue;
+            }
+            let result: Result<(), Retained<NSError>> = unsafe {
+                msg_send![
+                    &workspace,
+                    setDesktopImageURL: &*url,
+                    forScreen: screen,
+                    options: &*options,
+                    error: _
+                ]
+            };
+            result.map_err(|e| e.localizedDescription().to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SPI_SETDESKWALLPAPER, SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SystemParametersInfoW,
+    };
+
+    pub fn set_wallpaper(path: &Path) -> Result<(), String> {
+        let mut wide: Vec<u16> = path
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_SETDESKWALLPAPER,
+                0,
+                wide.as_mut_ptr().cast(),
+                SPIF_UPDATEINIFILE | SPIF_SENDCHANGE,
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_supported() {
+        assert!(is_supported(Path::new("/tmp/photo.JPG")));
+        assert!(!is_supported(Path::new("/tmp/logo.svg")));
+        assert!(!is_supported(Path::new("/tmp/notes.txt")));
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn test_linux_commands() {
+        let path = Path::new("/tmp/photo.jpg");
+        let programs = |desktop| {
+            linux_commands(desktop, path)
+                .iter()
+                .map(|command| command.get_program().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(programs("ubuntu:GNOME"), ["gsettings", "gsettings"]);
+        assert_eq!(programs("KDE"), ["plasma-apply-wallpaperimage"]);
+        assert_eq!(programs("i3"), ["feh"]);
+
+        let commands = linux_commands("GNOME", Path::new("/tmp/50% #1?.jpg"));
+        let args: Vec<_> = commands[0].get_args().collect();
+        assert_eq!(args[3], "file:///tmp/50%25%20%231%3F.jpg");
+    }
+}
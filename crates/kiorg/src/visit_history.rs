@@ -8,8 +8,9 @@ use crate::config;
 
 // Constants
 const HISTORY_FILE_NAME: &str = "history.csv";
+const FILE_HISTORY_FILE_NAME: &str = "file_history.csv";
 
-/// Represents a folder visit entry in the history
+/// Represents a folder visit, or a file open or preview, in the history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VisitHistoryEntry {
     pub path: PathBuf,
@@ -17,10 +18,30 @@ pub struct VisitHistoryEntry {
     pub count: u64,
 }
 
+impl VisitHistoryEntry {
+    /// Visit count decayed by the time since the last visit, counting half as
+    /// much every `half_life_secs`
+    #[must_use]
+    pub fn frecency(&self, now: u64, half_life_secs: f64) -> f64 {
+        let age = now.saturating_sub(self.accessed_ts) as f64;
+        self.count as f64 * 0.5_f64.powf(age / half_life_secs)
+    }
+}
+
+/// Seconds since the Unix epoch, as stored in the history
+#[must_use]
+pub fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// Message types for the async history saver thread
 #[derive(Debug, Clone)]
 pub enum HistorySaveMessage {
     Save(HashMap<PathBuf, VisitHistoryEntry>, Option<PathBuf>), // history data + config_dir_override
+    SaveFiles(HashMap<PathBuf, VisitHistoryEntry>, Option<PathBuf>), // file history + config_dir_override
     Shutdown,
 }
 
@@ -44,6 +65,12 @@ impl HistorySaver {
                             tracing::error!(err = ?e, "Failed to save visit history in background thread");
                         }
                     }
+                    HistorySaveMessage::SaveFiles(history, config_dir_override) => {
+                        if let Err(e) = save_file_history(&history, config_dir_override.as_deref())
+                        {
+                            tracing::error!(err = ?e, "Failed to save file history in background thread");
+                        }
+                    }
                     HistorySaveMessage::Shutdown => {
                         tracing::debug!("History saver thread shutting down");
                         break;
@@ -74,6 +101,22 @@ impl HistorySaver {
         }
     }
 
+    /// Queue a save of the opened and previewed files (non-blocking)
+    pub fn save_files_async(
+        &self,
+        history: &HashMap<PathBuf, VisitHistoryEntry>,
+        config_dir_override: Option<&std::path::Path>,
+    ) {
+        let message = HistorySaveMessage::SaveFiles(
+            history.clone(),
+            config_dir_override.map(|p| p.to_path_buf()),
+        );
+
+        if let Err(e) = self.sender.send(message) {
+            tracing::error!(err = ?e, "Failed to send save message to history saver thread");
+        }
+    }
+
     /// Shutdown the background thread gracefully
     pub fn shutdown(&self) {
         let _ = self.sender.send(HistorySaveMessage::Shutdown);
@@ -89,9 +132,23 @@ impl Default for HistorySaver {
 /// Load visit history from CSV file
 pub fn load_visit_history(
     config_dir_override: Option<&std::path::Path>,
+) -> Result<HashMap<PathBuf, VisitHistoryEntry>, Box<dyn std::error::Error>> {
+    load_history(HISTORY_FILE_NAME, config_dir_override)
+}
+
+/// Load the history of opened and previewed files from CSV file
+pub fn load_file_history(
+    config_dir_override: Option<&std::path::Path>,
+) -> Result<HashMap<PathBuf, VisitHistoryEntry>, Box<dyn std::error::Error>> {
+    load_history(FILE_HISTORY_FILE_NAME, config_dir_override)
+}
+
+fn load_history(
+    file_name: &str,
+    config_dir_override: Option<&std::path::Path>,
 ) -> Result<HashMap<PathBuf, VisitHistoryEntry>, Box<dyn std::error::Error>> {
     let config_dir = config::get_kiorg_config_dir(config_dir_override);
-    let history_path = config_dir.join(HISTORY_FILE_NAME);
+    let history_path = config_dir.join(file_name);
 
     let mut history = HashMap::new();
 
@@ -156,6 +213,22 @@ pub fn load_visit_history(
 pub fn save_visit_history(
     history: &HashMap<PathBuf, VisitHistoryEntry>,
     config_dir_override: Option<&std::path::Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    save_history(HISTORY_FILE_NAME, history, config_dir_override)
+}
+
+/// Save the history of opened and previewed files to CSV file
+pub fn save_file_history(
+    history: &HashMap<PathBuf, VisitHistoryEntry>,
+    config_dir_override: Option<&std::path::Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    save_history(FILE_HISTORY_FILE_NAME, history, config_dir_override)
+}
+
+fn save_history(
+    file_name: &str,
+    history: &HashMap<PathBuf, VisitHistoryEntry>,
+    config_dir_override: Option<&std::path::Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let config_dir = config::get_kiorg_config_dir(config_dir_override);
 
@@ -163,7 +236,7 @@ pub fn save_visit_history(
         std::fs::create_dir_all(&config_dir)?;
     }
 
-    let history_path = config_dir.join(HISTORY_FILE_NAME);
+    let history_path = config_dir.join(file_name);
     let mut content = String::from("path,accessed_ts,count\n");
 
     for entry in history.values() {
@@ -175,16 +248,13 @@ pub fn save_visit_history(
         ));
     }
 
-    std::fs::write(&history_path, content)?;
+    crate::utils::atomic_write::write(&history_path, content)?;
     Ok(())
 }
 
 /// Update visit history for a given path
 pub fn update_visit_history(history: &mut HashMap<PathBuf, VisitHistoryEntry>, path: &Path) {
-    let current_time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
+    let current_time = now_ts();
 
     match history.get_mut(path) {
         Some(entry) => {
@@ -202,3 +272,36 @@ pub fn update_visit_history(history: &mut HashMap<PathBuf, VisitHistoryEntry>, p
         }
     }
 }
+
+/// Record a visit to `path` unless it is excluded in the history config, and
+/// forget the least recently used entries beyond the configured cap
+///
+/// Returns whether the history changed.
+pub fn record_visit(
+    history: &mut HashMap<PathBuf, VisitHistoryEntry>,
+    path: &Path,
+    history_config: &config::History,
+) -> bool {
+    if history_config.is_excluded(path) {
+        return false;
+    }
+    update_visit_history(history, path);
+    prune_history(history, history_config.max_entries());
+    true
+}
+
+/// Drop the least recently accessed entries until at most `max_entries` remain
+pub fn prune_history(history: &mut HashMap<PathBuf, VisitHistoryEntry>, max_entries: usize) {
+    if history.len() <= max_entries {
+        return;
+    }
+    let mut entries: Vec<(u64, PathBuf)> = history
+        .values()
+        .map(|entry| (entry.accessed_ts, entry.path.clone()))
+        .collect();
+    entries.sort_unstable();
+    let excess = history.len() - max_entries;
+    for (_, path) in entries.into_iter().take(excess) {
+        history.remove(&path);
+    }
+}
@@ -511,6 +511,19 @@ impl TestHarness<'_> {
         // sort_all_tabs is called implicitly by toggle_sort now, no need for explicit call
         self.harness.step(); // Allow sort to apply and UI to update
     }
+
+    /// Steps until the pasted files are copied or moved in the background and
+    /// the entries are refreshed
+    pub fn wait_for_transfers(&mut self) {
+        let finished = wait_for_condition_with_timeout(
+            || {
+                self.harness.step();
+                self.harness.state().transfers.is_idle()
+            },
+            std::time::Duration::from_secs(5),
+        );
+        assert!(finished, "Transfers should finish");
+    }
 }
 
 // Add methods to TestHarness to delegate to the inner harness
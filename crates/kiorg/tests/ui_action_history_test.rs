@@ -358,6 +358,7 @@ fn test_undo_redo_copy_file() {
     // Paste using 'p' shortcut
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Wait for paste operation to complete
     wait_for_condition(|| {
@@ -486,6 +487,7 @@ fn test_undo_redo_move_file() {
     // Paste using 'p' shortcut
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Wait for move operation to complete
     wait_for_condition(|| {
@@ -4,7 +4,7 @@ mod ui_test_helpers;
 use egui::Key;
 use kiorg::ui::popup::PopupType;
 use tempfile::tempdir;
-use ui_test_helpers::{create_harness, create_test_files};
+use ui_test_helpers::{create_harness, create_harness_with_config_dir, create_test_files};
 
 #[test]
 fn test_add_file_and_directory() {
@@ -35,8 +35,11 @@ fn test_add_file_and_directory() {
     harness.step();
 
     // Check if the input field contains the file name
-    if let Some(PopupType::AddEntry(name)) = &harness.state().show_popup {
-        assert_eq!(name, file_name, "Input field should contain the file name");
+    if let Some(PopupType::AddEntry(state)) = &harness.state().show_popup {
+        assert_eq!(
+            &state.name, file_name,
+            "Input field should contain the file name"
+        );
     } else {
         panic!("Add mode should be active with the file name");
     }
@@ -105,9 +108,9 @@ fn test_add_file_and_directory() {
     harness.step();
 
     // Check if the input field contains the directory name
-    if let Some(PopupType::AddEntry(name)) = &harness.state().show_popup {
+    if let Some(PopupType::AddEntry(state)) = &harness.state().show_popup {
         assert_eq!(
-            name, dir_name_input,
+            &state.name, dir_name_input,
             "Input field should contain the directory name"
         );
     } else {
@@ -264,9 +267,9 @@ fn test_add_entry_cancel() {
     harness.step();
 
     // Check if the input field contains the partial name
-    if let Some(PopupType::AddEntry(name)) = &harness.state().show_popup {
+    if let Some(PopupType::AddEntry(state)) = &harness.state().show_popup {
         assert_eq!(
-            name, partial_name,
+            &state.name, partial_name,
             "Input field should contain partial name"
         );
     } else {
@@ -338,9 +341,9 @@ fn test_add_entry_name_conflict() {
 
     // Verify the error message was shown (we can't directly check toast content in tests)
     // But we can verify the popup is still open with the same content
-    if let Some(PopupType::AddEntry(name)) = &harness.state().show_popup {
+    if let Some(PopupType::AddEntry(state)) = &harness.state().show_popup {
         assert_eq!(
-            name, existing_file,
+            &state.name, existing_file,
             "Input field should still contain the conflicting name"
         );
     } else {
@@ -377,9 +380,9 @@ fn test_add_entry_name_conflict() {
     }
 
     // Verify the input field still contains the conflicting name
-    if let Some(PopupType::AddEntry(name)) = &harness.state().show_popup {
+    if let Some(PopupType::AddEntry(state)) = &harness.state().show_popup {
         assert_eq!(
-            name, &dir_input,
+            &state.name, &dir_input,
             "Input field should still contain the conflicting directory name"
         );
     } else {
@@ -396,3 +399,82 @@ fn test_add_entry_name_conflict() {
         "Add mode should be inactive after cancellation"
     );
 }
+
+#[test]
+fn test_add_file_from_template() {
+    let temp_dir = tempdir().unwrap();
+    let config_temp_dir = tempdir().unwrap();
+    let templates_dir = config_temp_dir.path().join("templates");
+    std::fs::create_dir_all(&templates_dir).unwrap();
+    std::fs::write(
+        templates_dir.join("article.tex"),
+        "\\documentclass{article}\n",
+    )
+    .unwrap();
+    std::fs::write(templates_dir.join("script.py"), "#!/usr/bin/env python3\n").unwrap();
+    let mut harness = create_harness_with_config_dir(&temp_dir, config_temp_dir);
+
+    harness.key_press(Key::A);
+    harness.step();
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text("report".to_string()));
+    harness.step();
+
+    // Down picks the first template, Up goes back past the empty file to the last one
+    harness.key_press(Key::ArrowDown);
+    harness.step();
+    harness.key_press(Key::ArrowUp);
+    harness.step();
+    harness.key_press(Key::ArrowUp);
+    harness.step();
+    match &harness.state().show_popup {
+        Some(PopupType::AddEntry(state)) => {
+            assert_eq!(state.templates.len(), 2);
+            assert_eq!(state.template, Some(1));
+        }
+        other => panic!("Add mode should be active, got {other:?}"),
+    }
+
+    // The name takes the template's extension
+    harness.key_press(Key::Enter);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("report.py")).unwrap(),
+        "#!/usr/bin/env python3\n"
+    );
+    assert!(!temp_dir.path().join("report").exists());
+}
+
+#[test]
+fn test_paste_clipboard_as_file() {
+    use kiorg::ui::popup::add_entry::AddEntryState;
+    use kiorg::utils::clipboard::ClipboardContent;
+
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[temp_dir.path().join("clipboard.txt")]);
+    let mut harness = create_harness(&temp_dir);
+
+    // The clipboard itself isn't available in tests, open the popup with its content
+    harness.state_mut().show_popup = Some(PopupType::AddEntry(AddEntryState {
+        name: "clipboard_1.txt".to_string(),
+        clipboard: Some(ClipboardContent::Text("pasted text\n".to_string())),
+        ..Default::default()
+    }));
+    harness.step();
+
+    harness.key_press(Key::Enter);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("clipboard_1.txt")).unwrap(),
+        "pasted text\n"
+    );
+    // The existing file is left alone
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("clipboard.txt")).unwrap(),
+        ""
+    );
+}
@@ -35,7 +35,7 @@ fn test_bookmark_feature() {
     {
         let app = harness.state();
         assert_eq!(app.bookmarks.len(), 1);
-        assert!(app.bookmarks[0].ends_with("dir1"));
+        assert!(app.bookmarks[0].path.ends_with("dir1"));
     }
 
     // Open bookmark popup with 'B' (shift+b)
@@ -75,7 +75,7 @@ fn test_bookmark_feature() {
     {
         let app = harness.state();
         assert_eq!(app.bookmarks.len(), 2);
-        assert!(app.bookmarks[1].ends_with("dir2"));
+        assert!(app.bookmarks[1].path.ends_with("dir2"));
     }
 
     // Try to bookmark a file (should not work)
@@ -107,7 +107,7 @@ fn test_bookmark_feature() {
     {
         let app = harness.state();
         assert_eq!(app.bookmarks.len(), 1);
-        assert!(app.bookmarks[0].ends_with("dir2")); // Only dir2 remains
+        assert!(app.bookmarks[0].path.ends_with("dir2")); // Only dir2 remains
     }
 }
 
@@ -181,3 +181,65 @@ fn test_bookmark_popup_close_with_q_and_esc() {
         "Bookmark popup should be closed after pressing 'Esc'"
     );
 }
+
+#[test]
+fn test_bookmark_label_and_quick_jump_key() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[temp_dir.path().join("dir1"), temp_dir.path().join("dir2")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.step();
+
+    // Bookmark dir1 and edit it from the popup with 'r'
+    harness.key_press(Key::B);
+    harness.step();
+    harness.key_press_modifiers(shift_modifiers(), Key::B);
+    harness.step();
+    harness.key_press(Key::R);
+    harness.step();
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::EditBookmark(_))
+    ));
+    if let Some(PopupType::EditBookmark(state)) = &mut harness.state_mut().show_popup {
+        state.label = "Home projects".to_string();
+        state.key = "x".to_string();
+    }
+    harness.step();
+    harness.key_press(Key::Enter);
+    harness.step();
+
+    // Saved in the bookmarks file and shown in the list
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::Bookmarks(0))
+    ));
+    {
+        let bookmark = &harness.state().bookmarks[0];
+        assert_eq!(bookmark.label.as_deref(), Some("Home projects"));
+        assert_eq!(bookmark.key, Some('x'));
+    }
+    let config_dir = harness.state().config_dir_override.clone().unwrap();
+    let content = std::fs::read_to_string(config_dir.join("bookmarks.txt")).unwrap();
+    assert!(content.contains("\tHome projects\tx"));
+    harness.step();
+    assert!(harness.query_by_label("Home projects").is_some());
+    assert!(harness.query_by_label("'x").is_some());
+
+    harness.key_press(Key::Escape);
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().show_popup.is_none()
+    });
+
+    // ' and the key jumps to the bookmark
+    harness.state_mut().navigate_to_dir(paths[1].clone());
+    harness.step();
+    harness.key_press(Key::Quote);
+    harness.key_press(Key::X);
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().current_path,
+        paths[0]
+    );
+}
@@ -4,7 +4,7 @@ mod ui_test_helpers;
 use egui::Key;
 use kiorg::ui::popup::PopupType;
 use tempfile::tempdir;
-use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+use ui_test_helpers::{create_harness, create_test_files, shift_modifiers, wait_for_condition};
 
 #[test]
 fn test_bulk_delete_with_space_key() {
@@ -54,7 +54,7 @@ fn test_bulk_delete_with_space_key() {
     }
 
     // Press delete key to trigger bulk deletion
-    harness.key_press(Key::D);
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Verify the delete popup is shown with multiple entries
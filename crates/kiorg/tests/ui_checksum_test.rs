@@ -0,0 +1,83 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::checksum;
+use kiorg::utils::hashing::HashAlgorithm;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+
+#[test]
+fn test_generate_and_verify_checksums() {
+    let temp_dir = tempdir().unwrap();
+    let test_files =
+        create_test_files(&[temp_dir.path().join("a.txt"), temp_dir.path().join("b.txt")]);
+    std::fs::write(&test_files[0], "abc").unwrap();
+    std::fs::write(&test_files[1], "def").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+
+    // Mark both files and generate SHA-256 sidecars
+    {
+        let tab = harness.state_mut().tab_manager.current_tab_mut();
+        tab.marked_entries.extend(test_files.iter().cloned());
+    }
+    let ctx = harness.ctx.clone();
+    checksum::start_generate_selected(harness.state_mut(), &ctx, HashAlgorithm::Sha256);
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::Checksum(_))
+    ));
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().show_popup.is_none()
+    });
+
+    let sidecar = temp_dir.path().join("a.txt.sha256");
+    assert_eq!(
+        std::fs::read_to_string(&sidecar).unwrap(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  a.txt\n"
+    );
+    assert!(temp_dir.path().join("b.txt.sha256").exists());
+
+    // Corrupt a file and verify its sidecar, the mismatch is reported
+    std::fs::write(&test_files[0], "changed").unwrap();
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .marked_entries
+        .clear();
+    let index = harness
+        .state()
+        .tab_manager
+        .current_tab_ref()
+        .entries
+        .iter()
+        .position(|e| e.meta.path == sidecar)
+        .expect("sidecar should be listed after the refresh");
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .selected_index = index;
+    harness.step();
+
+    checksum::start_verify_selected(harness.state_mut(), &ctx);
+    wait_for_condition(|| {
+        harness.step();
+        !matches!(harness.state().show_popup, Some(PopupType::Checksum(_)))
+    });
+    match &harness.state().show_popup {
+        Some(PopupType::GenericMessage(title, message)) => {
+            assert_eq!(title, "Checksum Verification Failed");
+            assert!(message.contains("a.txt: checksum mismatch"), "{message}");
+        }
+        other => panic!("Expected verification results, got {other:?}"),
+    }
+
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+}
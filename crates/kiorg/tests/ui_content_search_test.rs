@@ -0,0 +1,72 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+
+#[test]
+fn test_content_search_jumps_to_match() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("src"),
+        temp_dir.path().join("src/nested"),
+        temp_dir.path().join("readme.txt"),
+        temp_dir.path().join("src/nested/lib.rs"),
+    ]);
+    std::fs::write(&paths[2], "nothing to see\n").unwrap();
+    std::fs::write(&paths[3], "fn main() {}\nlet needle = 1;\n").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press_modifiers(
+        egui::Modifiers {
+            ctrl: true,
+            shift: true,
+            ..Default::default()
+        },
+        Key::F,
+    );
+    harness.step();
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::ContentSearch(_))
+    ));
+
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text("needle".to_string()));
+    harness.step();
+    harness.key_press(Key::Enter);
+    harness.step();
+
+    assert!(
+        wait_for_condition(|| {
+            harness.step();
+            matches!(
+                &harness.state().show_popup,
+                Some(PopupType::ContentSearch(state)) if !state.searching
+            )
+        }),
+        "Content search should finish"
+    );
+    match &harness.state().show_popup {
+        Some(PopupType::ContentSearch(state)) => {
+            assert_eq!(state.results.len(), 1);
+            assert_eq!(state.results[0].path, paths[3]);
+            assert_eq!(state.results[0].line_number, 2);
+            assert_eq!(state.results[0].before, vec!["fn main() {}"]);
+        }
+        other => panic!("Content search popup should be open, got {other:?}"),
+    }
+
+    // Enter on an unchanged pattern jumps to the file of the selected match
+    harness.key_press(Key::Enter);
+    harness.step();
+    let app = harness.state();
+    assert!(app.show_popup.is_none());
+    let tab = app.tab_manager.current_tab_ref();
+    assert_eq!(tab.current_path, paths[1]);
+    assert_eq!(tab.selected_entry().unwrap().meta.path, paths[3]);
+}
@@ -4,7 +4,7 @@ mod ui_test_helpers;
 use egui::Key;
 use kiorg::ui::popup::PopupType;
 use tempfile::tempdir;
-use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+use ui_test_helpers::{create_harness, create_test_files, shift_modifiers, wait_for_condition};
 
 #[test]
 fn test_folder_delete_double_confirmation() {
@@ -37,8 +37,8 @@ fn test_folder_delete_double_confirmation() {
     tab.selected_index = 0;
     harness.step();
 
-    // Press 'd' to initiate deletion
-    harness.key_press(Key::D);
+    // Press Shift+D to initiate permanent deletion
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Verify delete popup is shown
@@ -115,8 +115,8 @@ fn test_folder_delete_cancel_first_confirmation() {
     tab.selected_index = 0;
     harness.step();
 
-    // Press 'd' to initiate deletion
-    harness.key_press(Key::D);
+    // Press Shift+D to initiate permanent deletion
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Verify delete popup is shown
@@ -157,8 +157,8 @@ fn test_folder_delete_cancel_second_confirmation() {
     tab.selected_index = 0;
     harness.step();
 
-    // Press 'd' to initiate deletion
-    harness.key_press(Key::D);
+    // Press Shift+D to initiate permanent deletion
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Press Enter for first confirmation
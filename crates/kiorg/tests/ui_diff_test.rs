@@ -0,0 +1,89 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use std::time::{Duration, SystemTime};
+
+use egui::Key;
+use kiorg::models::preview_content::PreviewContent;
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, wait_for_condition};
+
+#[test]
+fn test_diff_two_marked_files() {
+    let temp_dir = tempdir().unwrap();
+    let new_path = temp_dir.path().join("a.rs");
+    let old_path = temp_dir.path().join("b.rs");
+    std::fs::write(&new_path, "fn main() {\n    println!(\"new\");\n}\n").unwrap();
+    std::fs::write(&old_path, "fn main() {\n    println!(\"old\");\n}\n").unwrap();
+    // The older file is compared against the newer one, whatever their names
+    let hour_ago = SystemTime::now() - Duration::from_secs(3600);
+    std::fs::File::options()
+        .write(true)
+        .open(&old_path)
+        .unwrap()
+        .set_modified(hour_ago)
+        .unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+
+    // A single marked file isn't enough
+    harness.key_press(Key::Space);
+    harness.step();
+    harness.key_press(Key::G);
+    harness.key_press(Key::D);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+    harness.key_press(Key::G);
+    harness.key_press(Key::D);
+    harness.step();
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::Preview)
+    ));
+
+    // The files are compared in the background
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content,
+            Some(PreviewContent::Diff(_))
+        )
+    }));
+    match &harness.state().preview_content {
+        Some(PreviewContent::Diff(state)) => {
+            assert_eq!(state.old_path, old_path);
+            assert_eq!(state.new_path, new_path);
+            assert_eq!(state.diff.added, 1);
+            assert_eq!(state.diff.removed, 1);
+        }
+        other => panic!("Expected diff preview, got {other:?}"),
+    }
+
+    // Both layouts draw without the popup closing
+    if let Some(PreviewContent::Diff(state)) = &mut harness.state_mut().preview_content {
+        state.side_by_side = true;
+    }
+    harness.step();
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::Preview)
+    ));
+
+    // Closing the popup brings back the preview of the selected file
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content,
+            Some(PreviewContent::Text(_) | PreviewContent::HighlightedCode { .. })
+        )
+    }));
+}
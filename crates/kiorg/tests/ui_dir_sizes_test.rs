@@ -0,0 +1,46 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::utils::dir_size::DirSize;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+
+#[test]
+fn test_toggle_dir_sizes() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[temp_dir.path().join("dir")]);
+    std::fs::write(test_files[0].join("file.bin"), vec![0u8; 1500]).unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+    assert!(!harness.state().show_dir_sizes);
+
+    let alt = egui::Modifiers {
+        alt: true,
+        ..Default::default()
+    };
+    harness.key_press_modifiers(alt, Key::S);
+    harness.step();
+    assert!(harness.state().show_dir_sizes);
+
+    // Drawing the list queues the scan of the visible directory
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().dir_sizes.get(&test_files[0]) == DirSize::Done(1500)
+    });
+
+    // Changes reported by the watcher invalidate the cached size
+    std::fs::write(test_files[0].join("more.bin"), vec![0u8; 500]).unwrap();
+    harness
+        .state()
+        .dir_sizes
+        .invalidate(&test_files[0].join("more.bin"));
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().dir_sizes.get(&test_files[0]) == DirSize::Done(2000)
+    });
+
+    harness.key_press_modifiers(alt, Key::S);
+    harness.step();
+    assert!(!harness.state().show_dir_sizes);
+}
@@ -95,6 +95,7 @@ fn test_copy_directory() {
     // Paste the directory
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify the directory was copied with all its contents
     let copied_dir = target_dir.join("source_dir");
@@ -182,6 +183,7 @@ fn test_copy_directory_to_same_parent() {
     // Paste in the same directory
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify the directory was copied with a new suffix
     let copied_dir = temp_dir.path().join("source_dir_1");
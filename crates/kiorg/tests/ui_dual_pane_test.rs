@@ -0,0 +1,89 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_dual_pane_copy_and_move_to_other_pane() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("a.txt"),
+        temp_dir.path().join("b.txt"),
+        temp_dir.path().join("dest"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+
+    // A second tab is opened for the other pane
+    harness.key_press_modifiers(
+        egui::Modifiers {
+            alt: true,
+            ..Default::default()
+        },
+        Key::D,
+    );
+    harness.step();
+    {
+        let app = harness.state();
+        let pane = app.dual_pane.expect("Dual pane should be on");
+        assert_eq!(app.tab_manager.get_tab_count(), 2);
+        assert_eq!(pane.tabs, [0, 1]);
+        assert_eq!(pane.focus, 0);
+    }
+
+    // Tab focuses the other pane, which then goes to the destination
+    harness.key_press(Key::Tab);
+    harness.step();
+    assert_eq!(harness.state().tab_manager.get_current_tab_index(), 1);
+    assert_eq!(harness.state().dual_pane.unwrap().focus, 1);
+    harness.state_mut().navigate_to_dir(paths[2].clone());
+    harness.step();
+
+    harness.key_press(Key::Tab);
+    harness.step();
+    assert_eq!(harness.state().tab_manager.get_current_tab_index(), 0);
+
+    // F5 copies the selected entry
+    let index = harness
+        .state()
+        .tab_manager
+        .get_entry_index_by_path(&paths[0])
+        .unwrap();
+    harness.state_mut().set_selection(index);
+    harness.key_press(Key::F5);
+    harness.step();
+    harness.wait_for_transfers();
+    assert!(paths[0].exists());
+    assert!(paths[2].join("a.txt").exists());
+
+    // F6 moves the marked entries
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .marked_entries
+        .insert(paths[1].clone());
+    harness.key_press(Key::F6);
+    harness.step();
+    harness.wait_for_transfers();
+    assert!(!paths[1].exists());
+    assert!(paths[2].join("b.txt").exists());
+
+    // The other pane shows the new entries
+    let other = harness.state().tab_manager.tab_ref(1);
+    let names: Vec<&str> = other.entries.iter().map(|e| e.name.as_str()).collect();
+    assert!(names.contains(&"a.txt") && names.contains(&"b.txt"));
+
+    // Turning it off brings the preview back and keeps the tabs
+    harness.key_press_modifiers(
+        egui::Modifiers {
+            alt: true,
+            ..Default::default()
+        },
+        Key::D,
+    );
+    harness.step();
+    assert!(harness.state().dual_pane.is_none());
+    assert_eq!(harness.state().tab_manager.get_tab_count(), 2);
+}
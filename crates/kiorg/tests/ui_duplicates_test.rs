@@ -0,0 +1,69 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::duplicates;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+
+#[test]
+fn test_find_and_delete_duplicates() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("a.txt"),
+        temp_dir.path().join("b.txt"),
+        temp_dir.path().join("c.txt"),
+    ]);
+    std::fs::write(&test_files[0], "same").unwrap();
+    std::fs::write(&test_files[1], "same").unwrap();
+    std::fs::write(&test_files[2], "diff").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+    let ctx = harness.ctx.clone();
+    duplicates::open(harness.state_mut(), &ctx);
+    wait_for_condition(|| {
+        harness.step();
+        matches!(
+            &harness.state().show_popup,
+            Some(PopupType::Duplicates(data)) if data.groups.is_some()
+        )
+    });
+    match &harness.state().show_popup {
+        Some(PopupType::Duplicates(data)) => {
+            let groups = data.groups.as_ref().unwrap();
+            assert_eq!(groups.len(), 1);
+            assert_eq!(
+                groups[0].paths,
+                vec![test_files[0].clone(), test_files[1].clone()]
+            );
+        }
+        other => panic!("Expected duplicates popup, got {other:?}"),
+    }
+
+    // Permanent deletion needs a second click
+    harness.get_by_label("Mark all but first").click();
+    harness.step();
+    harness.get_by_label("Delete marked permanently").click();
+    harness.step();
+    assert!(test_files[1].exists());
+    harness
+        .get_by_label("Click again to delete 1 files permanently")
+        .click();
+    harness.step();
+
+    assert!(test_files[0].exists());
+    assert!(!test_files[1].exists());
+    assert!(test_files[2].exists());
+    match &harness.state().show_popup {
+        Some(PopupType::Duplicates(data)) => {
+            assert!(data.groups.as_ref().unwrap().is_empty());
+            assert!(data.marked.is_empty());
+        }
+        other => panic!("Expected duplicates popup, got {other:?}"),
+    }
+
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+}
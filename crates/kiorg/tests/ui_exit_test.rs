@@ -35,7 +35,7 @@ fn test_exit_saves_state() {
         let app = harness.state();
         assert_eq!(app.bookmarks.len(), 1, "Should have one bookmark");
         assert!(
-            app.bookmarks[0].ends_with("dir1"),
+            app.bookmarks[0].path.ends_with("dir1"),
             "Bookmark should be dir1"
         );
     }
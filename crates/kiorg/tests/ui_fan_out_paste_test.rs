@@ -0,0 +1,113 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_paste_into_marked_directories() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("a_dir"),
+        temp_dir.path().join("b_dir"),
+        temp_dir.path().join("file.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+
+    // Copy file.txt
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Y);
+    harness.step();
+
+    // Mark both directories
+    harness.key_press(Key::G);
+    harness.key_press(Key::G);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+
+    // Pasting offers copying into each marked directory
+    harness.key_press(Key::P);
+    harness.step();
+    match &harness.state().show_popup {
+        Some(PopupType::FanOutPaste(dirs)) => {
+            assert_eq!(dirs, &vec![test_files[0].clone(), test_files[1].clone()]);
+        }
+        other => panic!("Expected fan-out paste popup, got {other:?}"),
+    }
+
+    harness.key_press(Key::Enter);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+    harness.wait_for_transfers();
+
+    assert!(test_files[0].join("file.txt").exists());
+    assert!(test_files[1].join("file.txt").exists());
+    assert!(test_files[2].exists());
+    assert!(!temp_dir.path().join("file_1.txt").exists());
+    assert!(
+        harness
+            .state()
+            .tab_manager
+            .current_tab_ref()
+            .marked_entries
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_cancel_paste_into_marked_directories() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("a_dir"),
+        temp_dir.path().join("b_dir"),
+        temp_dir.path().join("file.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Y);
+    harness.step();
+    harness.key_press(Key::G);
+    harness.key_press(Key::G);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+
+    harness.key_press(Key::P);
+    harness.step();
+    harness.key_press(Key::Escape);
+    harness.step();
+
+    // Nothing was pasted, the clipboard and marks are kept
+    assert!(harness.state().show_popup.is_none());
+    assert!(harness.state().transfers.is_idle());
+    assert!(!test_files[0].join("file.txt").exists());
+    assert!(harness.state().clipboard.is_some());
+    assert_eq!(
+        harness
+            .state()
+            .tab_manager
+            .current_tab_ref()
+            .marked_entries
+            .len(),
+        2
+    );
+}
@@ -73,6 +73,7 @@ fn test_file_drop_popup_copy() {
         // Check if popup is closed (operation completed)
         harness.state().show_popup.is_none()
     });
+    harness.wait_for_transfers();
 
     // Verify the popup is closed
     assert!(
@@ -177,6 +178,7 @@ fn test_file_drop_popup_move() {
         // Check if popup is closed (operation completed)
         harness.state().show_popup.is_none()
     });
+    harness.wait_for_transfers();
 
     // Verify the popup is closed
     assert!(
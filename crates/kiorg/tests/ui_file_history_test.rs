@@ -0,0 +1,52 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::{Key, Modifiers};
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_file_history_popup_jumps_to_file() {
+    let temp_dir = tempdir().unwrap();
+    let sub_dir = temp_dir.path().join("docs");
+    std::fs::create_dir(&sub_dir).unwrap();
+    let paths = create_test_files(&[sub_dir.join("notes.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.state_mut().record_file_history(&paths[0]);
+    assert!(harness.state().file_history.contains_key(&paths[0]));
+
+    harness.key_press_modifiers(Modifiers::CTRL, Key::E);
+    harness.step();
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::FileHistory(_))
+    ));
+
+    harness.key_press(Key::Enter);
+    harness.step();
+    harness.step();
+
+    assert!(harness.state().show_popup.is_none());
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.current_path, sub_dir);
+    assert_eq!(
+        tab.selected_entry().map(|entry| entry.meta.path.clone()),
+        Some(paths[0].clone())
+    );
+}
+
+#[test]
+fn test_preview_popup_records_file_history() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[temp_dir.path().join("a.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press_modifiers(Modifiers::SHIFT, Key::K);
+    harness.step();
+
+    let history = &harness.state().file_history;
+    assert_eq!(history.get(&paths[0]).map(|entry| entry.count), Some(1));
+    assert!(harness.state().visit_history.get(&paths[0]).is_none());
+}
@@ -3,7 +3,7 @@ mod ui_test_helpers;
 
 use egui::Key;
 use tempfile::tempdir;
-use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+use ui_test_helpers::{create_harness, create_test_files, shift_modifiers, wait_for_condition};
 
 #[test]
 fn test_delete_shortcut() {
@@ -40,8 +40,8 @@ fn test_delete_shortcut() {
     harness.key_press(Key::J);
     harness.step();
 
-    // Simulate pressing 'd' key to delete test1.txt
-    harness.key_press(Key::D);
+    // Simulate pressing Shift+D to delete test1.txt
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Simulate pressing Enter to confirm deletion
@@ -73,7 +73,7 @@ fn test_delete_shortcut() {
     harness.key_press(Key::K);
     harness.step();
     // Delete dir1 (directory with nested files and subdirectory)
-    harness.key_press(Key::D);
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
     harness.key_press(Key::Enter);
     harness.step();
@@ -200,6 +200,7 @@ fn test_copy_paste_shortcuts() {
     // Paste the file
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify the file was copied to dir2 while original remains
     assert!(
@@ -245,6 +246,7 @@ fn test_copy_paste_same_directory() {
     // Paste in the same directory
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify the file was copied with a new suffix
     assert!(test_files[0].exists(), "test1.txt should still exist");
@@ -300,6 +302,7 @@ fn test_cut_paste_shortcuts() {
     // Paste the file
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify the file was moved to dir2
     assert!(
@@ -4,7 +4,9 @@ mod ui_test_helpers;
 use egui::Key;
 use kiorg::ui::popup::PopupType;
 use tempfile::tempdir;
-use ui_test_helpers::{create_harness, create_test_files, ctrl_modifiers, wait_for_condition};
+use ui_test_helpers::{
+    create_harness, create_test_files, ctrl_modifiers, shift_modifiers, wait_for_condition,
+};
 
 /// Integration test that uses Ctrl+A to select all current files and then deletes them
 #[test]
@@ -52,8 +54,8 @@ fn test_ctrl_a_select_all_and_delete() {
         }
     }
 
-    // Press 'd' to trigger delete operation
-    harness.key_press(Key::D);
+    // Press Shift+D to trigger delete operation
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Verify the delete popup is shown with all entries
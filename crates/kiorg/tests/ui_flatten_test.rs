@@ -0,0 +1,99 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use tempfile::tempdir;
+use ui_test_helpers::{TestHarness, create_harness, create_test_files, wait_for_condition};
+
+fn press_alt_f(harness: &mut TestHarness<'_>) {
+    harness.key_press_modifiers(
+        egui::Modifiers {
+            alt: true,
+            ..Default::default()
+        },
+        Key::F,
+    );
+    harness.step();
+}
+
+#[test]
+fn test_flatten_lists_nested_files() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("dir/nested"),
+        temp_dir.path().join("top.txt"),
+        temp_dir.path().join("dir/inner.txt"),
+        temp_dir.path().join("dir/nested/deep.txt"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+
+    press_alt_f(&mut harness);
+    assert!(harness.state().flatten.is_some());
+    assert!(
+        wait_for_condition(|| {
+            harness.step();
+            harness
+                .state()
+                .flatten
+                .as_ref()
+                .is_some_and(|walk| walk.done)
+        }),
+        "Flattened listing should finish"
+    );
+
+    // Only files are listed, named by their path relative to the directory
+    let tab = harness.state().tab_manager.current_tab_ref();
+    let mut listed: Vec<_> = tab
+        .entries
+        .iter()
+        .map(|entry| (entry.name.clone(), entry.meta.path.clone()))
+        .collect();
+    listed.sort();
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(temp_dir.path())
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    };
+    let mut expected: Vec<_> = paths[2..]
+        .iter()
+        .map(|path| (relative(path), path.clone()))
+        .collect();
+    expected.sort();
+    assert_eq!(listed, expected);
+
+    // Back to the regular listing, with the directory of the selection selected
+    let index = harness
+        .state()
+        .tab_manager
+        .get_entry_index_by_path(&paths[4])
+        .unwrap();
+    harness.state_mut().set_selection(index);
+    press_alt_f(&mut harness);
+    let app = harness.state();
+    assert!(app.flatten.is_none());
+    let tab = app.tab_manager.current_tab_ref();
+    assert_eq!(tab.entries.len(), 2);
+    assert_eq!(tab.selected_entry().unwrap().meta.path, paths[0]);
+}
+
+#[test]
+fn test_flatten_ends_when_leaving_the_directory() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("dir/inner.txt"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+
+    press_alt_f(&mut harness);
+    assert!(harness.state().flatten.is_some());
+    harness.state_mut().navigate_to_dir(paths[0].clone());
+    harness.step();
+    assert!(harness.state().flatten.is_none());
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().entries.len(),
+        1
+    );
+}
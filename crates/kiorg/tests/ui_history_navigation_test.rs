@@ -2,8 +2,11 @@
 mod ui_test_helpers;
 
 use egui::Key;
+use std::path::PathBuf;
 use tempfile::tempdir;
-use ui_test_helpers::{cmd_modifiers, create_harness, create_test_files, ctrl_modifiers};
+use ui_test_helpers::{
+    TestHarness, cmd_modifiers, create_harness, create_test_files, ctrl_modifiers, shift_modifiers,
+};
 
 /// Test for directory history navigation with keyboard shortcuts
 #[test]
@@ -240,3 +243,71 @@ fn test_directory_history_navigation() {
         );
     }
 }
+
+/// Current directory and selected entry of the current tab
+fn selected(harness: &TestHarness<'_>) -> (PathBuf, Option<PathBuf>) {
+    let tab = harness.state().tab_manager.current_tab_ref();
+    (
+        tab.current_path.clone(),
+        tab.selected_entry().map(|entry| entry.meta.path.clone()),
+    )
+}
+
+/// Test for jumping back and forth between selection positions
+#[test]
+fn test_jump_list_navigation() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("dir1"),
+        temp_dir.path().join("file1.txt"),
+        temp_dir.path().join("file2.txt"),
+    ]);
+    create_test_files(&[test_files[0].join("nested.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    let alt = egui::Modifiers {
+        alt: true,
+        ..Default::default()
+    };
+    // Jumping to the last entry remembers the first one
+    harness.key_press_modifiers(shift_modifiers(), Key::G);
+    harness.step();
+    assert_eq!(selected(&harness).1.as_ref(), Some(&test_files[2]));
+
+    harness.key_press_modifiers(alt, Key::O);
+    harness.step();
+    assert_eq!(selected(&harness).1.as_ref(), Some(&test_files[0]));
+
+    harness.key_press_modifiers(alt, Key::I);
+    harness.step();
+    assert_eq!(selected(&harness).1.as_ref(), Some(&test_files[2]));
+
+    // Jumping back out of a directory restores the selection in the parent
+    harness.key_press_modifiers(shift_modifiers(), Key::G);
+    harness.step();
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .selected_index = 0;
+    harness.key_press(Key::L);
+    harness.step();
+    assert_eq!(selected(&harness).0, test_files[0]);
+
+    harness.key_press_modifiers(alt, Key::O);
+    harness.step();
+    assert_eq!(
+        selected(&harness),
+        (temp_dir.path().to_path_buf(), Some(test_files[0].clone()))
+    );
+
+    harness.key_press_modifiers(alt, Key::I);
+    harness.step();
+    assert_eq!(
+        selected(&harness),
+        (
+            test_files[0].clone(),
+            Some(test_files[0].join("nested.txt"))
+        )
+    );
+}
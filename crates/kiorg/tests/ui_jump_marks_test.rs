@@ -0,0 +1,92 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_jump_back_to_mark() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("a.txt"),
+        temp_dir.path().join("b.txt"),
+        temp_dir.path().join("dir/c.txt"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+
+    // Mark b.txt as `a`
+    {
+        let tab = harness.state_mut().tab_manager.current_tab_mut();
+        let index = tab.get_index_by_path(&paths[2]).unwrap();
+        tab.update_selection(index);
+    }
+    harness.key_press(Key::M);
+    harness.key_press(Key::A);
+    harness.step();
+    let mark = harness.state().jump_marks.get('a').cloned().unwrap();
+    assert_eq!(mark.dir, temp_dir.path());
+    assert_eq!(mark.selected.as_ref(), Some(&paths[2]));
+
+    harness.state_mut().navigate_to_dir(paths[0].clone());
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().current_path,
+        paths[0]
+    );
+
+    // Jumping to a mark that isn't set stays put
+    harness.key_press(Key::Quote);
+    harness.key_press(Key::Z);
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().current_path,
+        paths[0]
+    );
+
+    harness.key_press(Key::Quote);
+    harness.key_press(Key::A);
+    harness.step();
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.current_path, temp_dir.path());
+    assert_eq!(tab.selected_entry().unwrap().meta.path, paths[2]);
+}
+
+#[test]
+fn test_marks_popup_jumps_by_letter() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("dir/c.txt"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+
+    harness.state_mut().navigate_to_dir(paths[0].clone());
+    harness.step();
+    harness.key_press(Key::M);
+    harness.key_press(Key::B);
+    harness.step();
+    harness
+        .state_mut()
+        .navigate_to_dir(temp_dir.path().to_path_buf());
+    harness.step();
+
+    harness.key_press(Key::G);
+    harness.key_press(Key::M);
+    harness.step();
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::JumpMarks)
+    ));
+
+    // Typing the letter of a mark in the popup jumps to it
+    harness.key_press(Key::B);
+    harness.step();
+    let app = harness.state();
+    assert!(app.show_popup.is_none());
+    let tab = app.tab_manager.current_tab_ref();
+    assert_eq!(tab.current_path, paths[0]);
+    assert_eq!(tab.selected_entry().unwrap().meta.path, paths[1]);
+}
@@ -0,0 +1,140 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[cfg(unix)]
+fn alt_modifiers() -> egui::Modifiers {
+    egui::Modifiers {
+        alt: true,
+        ..Default::default()
+    }
+}
+
+fn alt_shift_modifiers() -> egui::Modifiers {
+    egui::Modifiers {
+        alt: true,
+        shift: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_paste_as_links() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("dest"),
+        temp_dir.path().join("file.txt"),
+    ]);
+    std::fs::write(&test_files[1], "content").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+
+    // Copy file.txt, then enter dest
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .selected_index = 1;
+    harness.step();
+    harness.key_press(Key::Y);
+    harness.step();
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .selected_index = 0;
+    harness.step();
+    harness.key_press(Key::L);
+    harness.step();
+
+    // Paste as hard link, the clipboard is kept for another paste
+    harness.key_press_modifiers(alt_shift_modifiers(), Key::P);
+    harness.step();
+    let hardlink = test_files[0].join("file.txt");
+    assert_eq!(std::fs::read_to_string(&hardlink).unwrap(), "content");
+    assert!(!hardlink.is_symlink());
+    assert!(harness.state().clipboard.is_some());
+
+    #[cfg(unix)]
+    {
+        harness.key_press_modifiers(alt_modifiers(), Key::P);
+        harness.step();
+        let symlink = test_files[0].join("file_1.txt");
+        assert_eq!(std::fs::read_link(&symlink).unwrap(), test_files[1]);
+
+        // The new link is selected, undo removes it and keeps the target
+        let tab = harness.state().tab_manager.current_tab_ref();
+        assert_eq!(tab.selected_entry().unwrap().meta.path, symlink);
+        harness.key_press(Key::U);
+        harness.step();
+        assert!(symlink.symlink_metadata().is_err());
+        assert!(test_files[1].exists());
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_create_symlink_to_selected() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[temp_dir.path().join("file.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press_modifiers(alt_modifiers(), Key::L);
+    harness.step();
+
+    let symlink = temp_dir.path().join("file_1.txt");
+    assert_eq!(std::fs::read_link(&symlink).unwrap(), test_files[0]);
+    // The link is selected and renamed right away
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.selected_entry().unwrap().meta.path, symlink);
+    assert!(harness.state().inline_rename.is_some());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_follow_symlink_and_show_target() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("sub"),
+        temp_dir.path().join("sub/target.txt"),
+    ]);
+    let link = temp_dir.path().join("link");
+    std::os::unix::fs::symlink("sub/target.txt", &link).unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+    harness.state_mut().refresh_entries();
+    harness.step();
+    {
+        let tab = harness.state_mut().tab_manager.current_tab_mut();
+        let index = tab.get_index_by_path(&link).unwrap();
+        assert_eq!(
+            tab.entries[index].link_target(),
+            Some(std::path::Path::new("sub/target.txt"))
+        );
+        tab.update_selection(index);
+    }
+
+    harness.key_press(Key::G);
+    harness.key_press(Key::R);
+    harness.step();
+    assert!(matches!(
+        &harness.state().show_popup,
+        Some(kiorg::ui::popup::PopupType::GenericMessage(_, message))
+            if message.contains("sub/target.txt")
+    ));
+    harness.state_mut().show_popup = None;
+
+    // Following the link selects the target in its directory
+    harness.key_press(Key::G);
+    harness.key_press(Key::F);
+    harness.step();
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.current_path, test_files[0].canonicalize().unwrap());
+    assert_eq!(
+        tab.selected_entry().unwrap().meta.path,
+        test_files[1].canonicalize().unwrap()
+    );
+}
@@ -5,6 +5,7 @@ use egui::Key;
 use kiorg::models::preview_content::PreviewContent;
 use kiorg::open_wrap::{
     acquire_open_test_lock, clear_open_calls, get_open_that_calls, get_open_with_calls,
+    get_set_default_calls, get_terminal_calls,
 };
 use kiorg::ui::popup::PopupType;
 use std::path::PathBuf;
@@ -664,10 +665,14 @@ fn test_ui_navigation_open_with_app_selection() {
                     AppInfo {
                         name: "App 1".to_string(),
                         path: "app1".to_string(),
+                        id: "app1.desktop".to_string(),
+                        ..Default::default()
                     },
                     AppInfo {
                         name: "App 2".to_string(),
                         path: "app2".to_string(),
+                        id: "app2.desktop".to_string(),
+                        ..Default::default()
                     },
                 ],
                 apps_loaded: true,
@@ -707,6 +712,114 @@ fn test_ui_navigation_open_with_app_selection() {
     );
 }
 
+#[test]
+fn test_ui_navigation_open_with_set_default_app() {
+    let temp_dir = tempdir().unwrap();
+    let test_file = temp_dir.path().join("test.txt");
+    std::fs::write(&test_file, "content").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+
+    harness.key_press_modifiers(shift_modifiers(), Key::O);
+    harness.step();
+
+    {
+        use kiorg::ui::popup::open_with::OpenWithUiState;
+        use mimeapps::AppInfo;
+
+        harness.ctx.data_mut(|d| {
+            let state = OpenWithUiState {
+                apps: vec![AppInfo {
+                    name: "App 1".to_string(),
+                    path: "app1".to_string(),
+                    id: "app1.desktop".to_string(),
+                    ..Default::default()
+                }],
+                apps_loaded: true,
+                ..Default::default()
+            };
+            d.insert_temp(egui::Id::new("open_with_ui_state"), state);
+        });
+    }
+    harness.step();
+
+    let (open_with_calls, set_default_calls) = {
+        let _lock = acquire_open_test_lock();
+        clear_open_calls();
+
+        // Shift+Enter also makes the app the default for the file type
+        harness.key_press_modifiers(shift_modifiers(), Key::Enter);
+        harness.step();
+
+        wait_for_condition(|| {
+            harness.step();
+            !get_open_with_calls().is_empty()
+        });
+
+        (get_open_with_calls(), get_set_default_calls())
+    };
+
+    assert_eq!(open_with_calls.len(), 1);
+    assert_eq!(open_with_calls[0].app.as_deref(), Some("app1"));
+    assert_eq!(set_default_calls.len(), 1);
+    assert_eq!(set_default_calls[0].path, test_file.as_os_str());
+    assert_eq!(set_default_calls[0].app.as_deref(), Some("app1.desktop"));
+}
+
+#[test]
+fn test_ui_navigation_open_with_terminal_app() {
+    let temp_dir = tempdir().unwrap();
+    let test_file = temp_dir.path().join("test.txt");
+    std::fs::write(&test_file, "content").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+
+    harness.key_press_modifiers(shift_modifiers(), Key::O);
+    harness.step();
+
+    {
+        use kiorg::ui::popup::open_with::OpenWithUiState;
+        use mimeapps::AppInfo;
+
+        harness.ctx.data_mut(|d| {
+            let state = OpenWithUiState {
+                apps: vec![AppInfo {
+                    name: "Vim".to_string(),
+                    path: "vim %F".to_string(),
+                    id: "vim.desktop".to_string(),
+                    terminal: true,
+                    ..Default::default()
+                }],
+                apps_loaded: true,
+                ..Default::default()
+            };
+            d.insert_temp(egui::Id::new("open_with_ui_state"), state);
+        });
+    }
+    harness.step();
+
+    let (open_with_calls, terminal_calls) = {
+        let _lock = acquire_open_test_lock();
+        clear_open_calls();
+
+        harness.key_press(Key::Enter);
+        harness.step();
+
+        wait_for_condition(|| {
+            harness.step();
+            !get_terminal_calls().is_empty()
+        });
+
+        (get_open_with_calls(), get_terminal_calls())
+    };
+
+    // Console applications run in a terminal instead of being launched directly
+    assert!(open_with_calls.is_empty());
+    assert_eq!(terminal_calls.len(), 1);
+    assert_eq!(terminal_calls[0].path, test_file.as_os_str());
+    assert_eq!(terminal_calls[0].app.as_deref(), Some("vim %F"));
+}
+
 #[test]
 fn test_ui_navigation_open_with_empty_command() {
     // Create a temporary directory for testing
@@ -930,3 +1043,53 @@ fn test_ui_navigation_page_navigation() {
         "Ctrl+U should work like page up, from {after_ctrl_d} to {after_ctrl_u}"
     );
 }
+
+#[test]
+fn test_movement_with_count() {
+    let temp_dir = tempdir().unwrap();
+    let names: Vec<PathBuf> = (0..12)
+        .map(|i| temp_dir.path().join(format!("file{i:02}.txt")))
+        .collect();
+    create_test_files(&names);
+
+    let mut harness = create_harness(&temp_dir);
+
+    // 5j moves down five entries
+    harness.key_press(Key::Num5);
+    harness.key_press(Key::J);
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().selected_index,
+        5
+    );
+
+    // 2k moves up two entries
+    harness.key_press(Key::Num2);
+    harness.key_press(Key::K);
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().selected_index,
+        3
+    );
+
+    // A count past the end stops at the last entry
+    harness.key_press(Key::Num9);
+    harness.key_press(Key::Num9);
+    harness.key_press(Key::J);
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().selected_index,
+        11
+    );
+
+    // 10gg jumps to the tenth entry
+    harness.key_press(Key::Num1);
+    harness.key_press(Key::Num0);
+    harness.key_press(Key::G);
+    harness.key_press(Key::G);
+    harness.step();
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().selected_index,
+        9
+    );
+}
@@ -92,6 +92,7 @@ fn test_paste_copy_clears_marked_entries() {
     // Paste the copied files
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify marked entries are cleared after paste operation
     {
@@ -188,7 +189,7 @@ fn test_paste_cut_clears_marked_entries() {
     // Paste the cut files
     harness.key_press(Key::P);
     harness.step();
-    harness.step();
+    harness.wait_for_transfers();
 
     // Verify marked entries are cleared after paste operation
     {
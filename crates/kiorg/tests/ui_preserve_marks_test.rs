@@ -0,0 +1,104 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::config::Marks;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_refresh_drops_marks_of_removed_entries() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("file1.txt"),
+        temp_dir.path().join("file2.txt"),
+        temp_dir.path().join("file3.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    {
+        let tab = harness.state_mut().tab_manager.current_tab_mut();
+        tab.marked_entries.insert(test_files[0].clone());
+        tab.marked_entries.insert(test_files[1].clone());
+    }
+
+    // file1.txt is renamed outside kiorg
+    std::fs::rename(&test_files[0], temp_dir.path().join("renamed.txt")).unwrap();
+    harness.state_mut().refresh_entries();
+    harness.step();
+
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert!(!tab.marked_entries.contains(&test_files[0]));
+    assert!(tab.marked_entries.contains(&test_files[1]));
+    assert_eq!(tab.marked_entries.len(), 1);
+}
+
+#[test]
+fn test_marks_cleared_when_leaving_directory() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("file1.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+    assert!(
+        harness
+            .state()
+            .tab_manager
+            .current_tab_ref()
+            .marked_entries
+            .contains(&test_files[1])
+    );
+
+    harness.state_mut().navigate_to_dir(test_files[0].clone());
+    harness.step();
+    assert!(
+        harness
+            .state()
+            .tab_manager
+            .current_tab_ref()
+            .marked_entries
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_marks_staged_across_directories() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("file1.txt"),
+    ]);
+    let nested = create_test_files(&[test_files[0].join("nested.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.state_mut().config.marks = Some(Marks {
+        keep_across_directories: true,
+    });
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+
+    // Mark an entry of another directory too
+    harness.state_mut().navigate_to_dir(test_files[0].clone());
+    harness.step();
+    harness.key_press(Key::Space);
+    harness.step();
+
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert!(tab.marked_entries.contains(&test_files[1]));
+    assert!(tab.marked_entries.contains(&nested[0]));
+    assert_eq!(tab.marked_elsewhere_count(), 1);
+
+    // A refresh only reconciles the marks of the current directory
+    harness.state_mut().refresh_entries();
+    harness.step();
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.marked_entries.len(), 2);
+}
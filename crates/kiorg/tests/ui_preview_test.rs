@@ -5,8 +5,9 @@ use egui::Key;
 use kiorg::models::preview_content::PreviewContent;
 use tempfile::tempdir;
 use ui_test_helpers::{
-    create_harness, create_test_image, create_test_pdf, create_test_tar, create_test_video,
-    create_test_zip, wait_for_condition, wait_for_condition_with_timeout,
+    create_harness, create_harness_with_config_dir, create_test_image, create_test_pdf,
+    create_test_tar, create_test_video, create_test_zip, ctrl_shift_modifiers, shift_modifiers,
+    wait_for_condition, wait_for_condition_with_timeout,
 };
 
 /// Test for text preview of regular text files
@@ -103,6 +104,143 @@ fn test_binary_file_preview() {
     }
 }
 
+/// Test that disabled file types show a placeholder until loaded on request
+#[test]
+fn test_disabled_type_preview_is_deferred() {
+    let temp_dir = tempdir().unwrap();
+    let text_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&text_path, "Deferred text content").unwrap();
+
+    let config_temp_dir = tempdir().unwrap();
+    std::fs::write(
+        config_temp_dir.path().join("config.toml"),
+        "[preview]\ndisabled_types = [\"TXT\"]\n",
+    )
+    .unwrap();
+
+    let mut harness = create_harness_with_config_dir(&temp_dir, config_temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Deferred { path, .. }) if path == &text_path
+        )
+    }));
+
+    // Loading on request ignores the limits
+    let ctx = harness.ctx.clone();
+    kiorg::ui::preview::load_deferred(harness.state_mut(), &ctx, text_path);
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Text(text)) if text.contains("Deferred text content")
+        )
+    }));
+}
+
+/// Test that files in untrusted directories show only static metadata
+#[test]
+fn test_untrusted_dir_safe_preview() {
+    let temp_dir = tempdir().unwrap();
+    let text_path = temp_dir.path().join("script.html");
+    std::fs::write(&text_path, "<script>alert(1)</script>").unwrap();
+
+    let config_temp_dir = tempdir().unwrap();
+    std::fs::write(
+        config_temp_dir.path().join("config.toml"),
+        format!(
+            "[preview]\nuntrusted_dirs = ['{}']\n",
+            temp_dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let mut harness = create_harness_with_config_dir(&temp_dir, config_temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Deferred { path, reason })
+                if path == &text_path && reason.contains("untrusted") && reason.contains("Size: 25 B")
+        )
+    }));
+}
+
+/// Test that safe preview can be toggled for all files
+#[test]
+fn test_toggle_safe_preview() {
+    let temp_dir = tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("notes.txt"), "Plain text content").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Text(_) | PreviewContent::HighlightedCode { .. })
+        )
+    }));
+
+    harness.key_press_modifiers(ctrl_shift_modifiers(), Key::S);
+    assert!(harness.state().safe_preview);
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Deferred { reason, .. }) if reason.contains("Safe preview is enabled")
+        )
+    }));
+
+    harness.key_press_modifiers(ctrl_shift_modifiers(), Key::S);
+    assert!(!harness.state().safe_preview);
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Text(_) | PreviewContent::HighlightedCode { .. })
+        )
+    }));
+}
+
+#[test]
+fn test_text_view_options_are_saved() {
+    let temp_dir = tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("notes.txt"), "one\ntwo\nthree\n").unwrap();
+
+    let config_temp_dir = tempdir().unwrap();
+    let config_path = config_temp_dir.path().join("config.toml");
+    let mut harness = create_harness_with_config_dir(&temp_dir, config_temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+    assert!(wait_for_condition(|| {
+        harness.step();
+        matches!(
+            harness.state().preview_content.as_ref(),
+            Some(PreviewContent::Text(_) | PreviewContent::HighlightedCode { .. })
+        )
+    }));
+
+    harness.key_press_modifiers(shift_modifiers(), Key::K);
+    harness.step();
+    harness.get_by_label("Line numbers").click();
+    harness.step();
+
+    let preview = harness.state().config.preview.clone().unwrap();
+    assert_eq!(preview.line_numbers, Some(true));
+    assert_eq!(preview.wrap_lines, Some(false));
+    let saved = std::fs::read_to_string(config_path).unwrap();
+    assert!(saved.contains("line_numbers = true"));
+}
+
 /// Test for directory preview
 #[test]
 fn test_directory_preview() {
@@ -0,0 +1,56 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::profile::ProfileState;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+fn switch_profile(harness: &mut ui_test_helpers::TestHarness<'_>, query: &str) {
+    harness.state_mut().show_popup = Some(PopupType::Profiles(ProfileState {
+        query: query.to_string(),
+        selected_index: 0,
+    }));
+    harness.step();
+    harness.key_press(Key::Enter);
+    harness.step();
+    harness.step();
+}
+
+#[test]
+fn test_switch_profile_isolates_bookmarks() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[temp_dir.path().join("dir1")]);
+
+    let mut harness = create_harness(&temp_dir);
+    let default_config_dir = harness.state().config_dir_override.clone().unwrap();
+    harness
+        .state_mut()
+        .bookmarks
+        .push(kiorg::models::bookmark::Bookmark::new(
+            temp_dir.path().join("dir1"),
+        ));
+    assert_eq!(harness.state().profile, None);
+
+    // Creates the profile and switches to it
+    switch_profile(&mut harness, "work");
+    assert!(harness.state().show_popup.is_none());
+    assert_eq!(harness.state().profile.as_deref(), Some("work"));
+    assert_eq!(
+        harness.state().config_dir_override,
+        Some(default_config_dir.join("profiles").join("work"))
+    );
+    assert!(harness.state().bookmarks.is_empty());
+    assert!(default_config_dir.join("profiles").join("work").is_dir());
+
+    // The state of the default profile was saved when switching away from it
+    assert!(default_config_dir.join("state.json").is_file());
+
+    switch_profile(&mut harness, "default");
+    assert_eq!(harness.state().profile, None);
+    assert_eq!(
+        harness.state().config_dir_override,
+        Some(default_config_dir)
+    );
+}
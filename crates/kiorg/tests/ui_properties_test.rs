@@ -0,0 +1,36 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::{Key, Modifiers};
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_properties_popup_shows_selected_entry() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[temp_dir.path().join("a.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press_modifiers(Modifiers::ALT, Key::Enter);
+    harness.step();
+
+    match &harness.state().show_popup {
+        Some(PopupType::Properties(properties)) => {
+            assert_eq!(properties.path, paths[0]);
+            assert!(properties.rows.iter().any(|(label, _)| *label == "Size"));
+            #[cfg(unix)]
+            assert!(
+                properties
+                    .rows
+                    .iter()
+                    .any(|(label, _)| *label == "Size on disk")
+            );
+        }
+        other => panic!("Properties popup should be open, got {other:?}"),
+    }
+
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+}
@@ -12,7 +12,9 @@ mod ui_test_helpers;
 use egui::Key;
 use kiorg::ui::popup::PopupType;
 use tempfile::tempdir;
-use ui_test_helpers::{create_harness, create_test_files, ctrl_modifiers, wait_for_condition};
+use ui_test_helpers::{
+    create_harness, create_test_files, ctrl_modifiers, shift_modifiers, wait_for_condition,
+};
 
 #[test]
 fn test_crash_reproduction_filtered_deletion() {
@@ -57,7 +59,7 @@ fn test_crash_reproduction_filtered_deletion() {
     }
 
     // Step 3: Delete all selected entries - this triggers the bug
-    harness.key_press(Key::D);
+    harness.key_press_modifiers(shift_modifiers(), Key::D);
     harness.step();
 
     // Verify we're in the initial confirmation state
@@ -584,3 +584,57 @@ fn test_fs_notify_preserves_search_query() {
         "BUG: New file 'newfile.txt' should not appear in filtered results for query 'test'"
     );
 }
+
+#[test]
+fn test_search_query_filters() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[
+        temp_dir.path().join("docs"),
+        temp_dir.path().join("report.pdf"),
+        temp_dir.path().join("notes.txt"),
+    ]);
+    let mut large = File::create(temp_dir.path().join("report-full.pdf")).unwrap();
+    large.write_all(&vec![0u8; 4096]).unwrap();
+    drop(large);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::Slash);
+    harness.step();
+
+    let filtered_names = |harness: &ui_test_helpers::TestHarness<'_>| {
+        let tab = harness.state().tab_manager.current_tab_ref();
+        let mut names: Vec<String> = tab
+            .get_cached_filtered_entries()
+            .iter()
+            .map(|&index| tab.entries[index].name.clone())
+            .collect();
+        names.sort();
+        names
+    };
+
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text("ext:pdf".to_string()));
+    harness.step();
+    assert_eq!(
+        filtered_names(&harness),
+        vec!["report-full.pdf", "report.pdf"]
+    );
+
+    // Filters combine with each other and with the name query
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text(" size>1k full".to_string()));
+    harness.step();
+    assert_eq!(filtered_names(&harness), vec!["report-full.pdf"]);
+
+    let query = Some("type:dir modified<1d".to_string());
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .update_filtered_cache(&query, true, false);
+    assert_eq!(filtered_names(&harness), vec!["docs"]);
+}
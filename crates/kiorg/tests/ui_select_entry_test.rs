@@ -186,6 +186,7 @@ fn test_select_entry_with_operations() {
     // Paste the selected entries
     harness.key_press(Key::P);
     harness.step();
+    harness.wait_for_transfers();
 
     // Verify all three entries were copied to the subdirectory
     assert!(
@@ -212,3 +213,31 @@ fn test_select_entry_with_operations() {
         "Original file2.txt should still exist"
     );
 }
+
+#[test]
+fn test_select_entry_with_count() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("file1.txt"),
+        temp_dir.path().join("file2.txt"),
+        temp_dir.path().join("file3.txt"),
+        temp_dir.path().join("file4.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+
+    // 3 Space marks the first three entries and selects the last of them
+    harness.key_press(Key::Num3);
+    harness.key_press(Key::Space);
+    harness.step();
+
+    let tab = harness.state().tab_manager.current_tab_ref();
+    for file in &test_files[..3] {
+        assert!(
+            tab.marked_entries.contains(file),
+            "{file:?} should be marked"
+        );
+    }
+    assert!(!tab.marked_entries.contains(&test_files[3]));
+    assert_eq!(tab.selected_index, 2);
+}
@@ -0,0 +1,50 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::models::jump_marks::JumpMarks;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::session::SessionState;
+use tempfile::tempdir;
+use ui_test_helpers::{TestHarness, create_harness, create_test_files};
+
+fn select_session(harness: &mut TestHarness<'_>, query: &str) {
+    harness.state_mut().show_popup = Some(PopupType::Sessions(SessionState {
+        query: query.to_string(),
+        selected_index: 0,
+    }));
+    harness.step();
+    harness.key_press(Key::Enter);
+    harness.step();
+}
+
+#[test]
+fn test_save_and_restore_session() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("project"),
+        temp_dir.path().join("other"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+    let config_dir = harness.state().config_dir_override.clone().unwrap();
+
+    harness.state_mut().navigate_to_dir(paths[0].clone());
+    harness.state_mut().set_jump_mark('p');
+    harness.step();
+
+    // A new name saves the current tabs and marks
+    select_session(&mut harness, "work");
+    assert!(harness.state().show_popup.is_none());
+    assert_eq!(harness.state().session.as_deref(), Some("work"));
+    assert!(config_dir.join("sessions").join("work.json").is_file());
+
+    harness.state_mut().navigate_to_dir(paths[1].clone());
+    harness.state_mut().jump_marks = JumpMarks::default();
+    harness.step();
+
+    // An existing name restores it
+    select_session(&mut harness, "work");
+    let app = harness.state();
+    assert_eq!(app.tab_manager.current_tab_ref().current_path, paths[0]);
+    assert_eq!(app.jump_marks.get('p').unwrap().dir, paths[0]);
+}
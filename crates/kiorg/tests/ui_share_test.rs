@@ -0,0 +1,63 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+#[test]
+fn test_share_popup_uses_marked_entries() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[
+        temp_dir.path().join("a.txt"),
+        temp_dir.path().join("b.txt"),
+        temp_dir.path().join("c.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    {
+        let tab = harness.state_mut().tab_manager.current_tab_mut();
+        tab.marked_entries.insert(paths[2].clone());
+        tab.marked_entries.insert(paths[0].clone());
+    }
+    kiorg::ui::popup::share::open(harness.state_mut());
+    harness.step();
+
+    match &harness.state().show_popup {
+        Some(PopupType::Share(state)) => {
+            assert_eq!(state.paths, vec![paths[0].clone(), paths[2].clone()]);
+            #[cfg(target_os = "linux")]
+            assert!(state.targets.iter().any(|target| target.name == "Email"));
+        }
+        other => panic!("Share popup should be open, got {other:?}"),
+    }
+
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.state().show_popup.is_none());
+}
+
+#[test]
+fn test_share_shortcut_uses_selected_entry() {
+    let temp_dir = tempdir().unwrap();
+    let paths = create_test_files(&[temp_dir.path().join("a.txt"), temp_dir.path().join("b.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press_modifiers(
+        egui::Modifiers {
+            alt: true,
+            shift: true,
+            ..Default::default()
+        },
+        Key::S,
+    );
+    harness.step();
+
+    match &harness.state().show_popup {
+        Some(PopupType::Share(state)) => assert_eq!(state.paths, vec![paths[1].clone()]),
+        other => panic!("Share popup should be open, got {other:?}"),
+    }
+}
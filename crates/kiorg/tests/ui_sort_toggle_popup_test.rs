@@ -2,6 +2,7 @@
 mod ui_test_helpers;
 
 use egui::Key;
+use kiorg::models::group_by::GroupBy;
 use kiorg::models::tab::{SortColumn, SortOrder};
 use kiorg::ui::popup::PopupType;
 use tempfile::tempdir;
@@ -369,3 +370,123 @@ fn test_sort_toggle_popup_preserves_selection() {
         // The selection might have moved due to sort order change, but should still be valid
     }
 }
+
+#[test]
+fn test_sort_toggle_popup_extension_and_grouping() {
+    let temp_dir = tempdir().unwrap();
+
+    create_test_files(&[
+        temp_dir.path().join("b.txt"),
+        temp_dir.path().join("a.rs"),
+        temp_dir.path().join("c.md"),
+        temp_dir.path().join("d.rs"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+
+    harness.key_press(Key::Comma);
+    harness.step();
+
+    // Sort by extension, descending first
+    harness.key_press(Key::E);
+    harness.step();
+    {
+        let tab_manager = &harness.state().tab_manager;
+        assert_eq!(tab_manager.sort_column, SortColumn::Extension);
+        assert_eq!(tab_manager.sort_order, SortOrder::Descending);
+        let names: Vec<_> = tab_manager
+            .current_tab_ref()
+            .entries
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["b.txt", "d.rs", "a.rs", "c.md"]);
+    }
+
+    // Group by type shows a header above each extension
+    harness.key_press(Key::G);
+    harness.step();
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert_eq!(harness.state().tab_manager.group_by, GroupBy::Type);
+    assert!(harness.query_by_label("RS").is_some());
+    assert!(harness.query_by_label("TXT").is_some());
+
+    // Groups are in order, entries keep their sort order within a group and
+    // the selected entry stays selected
+    {
+        let tab = harness.state().tab_manager.current_tab_ref();
+        let names: Vec<_> = tab.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["c.md", "d.rs", "a.rs", "b.txt"]);
+        assert_eq!(tab.selected_entry().unwrap().name, "b.txt");
+    }
+
+    // Navigation moves between entries, skipping the headers
+    harness.key_press(Key::K);
+    harness.step();
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.selected_entry().unwrap().name, "a.rs");
+}
+
+#[test]
+fn test_sort_toggle_popup_group_by_date_and_letter() {
+    let temp_dir = tempdir().unwrap();
+
+    create_test_files(&[
+        temp_dir.path().join("apple.txt"),
+        temp_dir.path().join("banana.txt"),
+        temp_dir.path().join("avocado.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+
+    harness.key_press(Key::Comma);
+    harness.step();
+
+    // Cycle through the groupings: type, then date
+    harness.key_press(Key::G);
+    harness.step();
+    harness.key_press(Key::G);
+    harness.step();
+    assert_eq!(harness.state().tab_manager.group_by, GroupBy::Date);
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.query_by_label("Today").is_some());
+
+    // Then first letter, keyboard navigation follows the listed order
+    harness.key_press(Key::Comma);
+    harness.step();
+    harness.key_press(Key::G);
+    harness.step();
+    assert_eq!(harness.state().tab_manager.group_by, GroupBy::Letter);
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(harness.query_by_label("A").is_some());
+    assert!(harness.query_by_label("B").is_some());
+
+    let names: Vec<_> = harness
+        .state()
+        .tab_manager
+        .current_tab_ref()
+        .entries
+        .iter()
+        .map(|e| e.name.clone())
+        .collect();
+    assert_eq!(names[2], "banana.txt");
+    for _ in 0..2 {
+        harness.key_press(Key::J);
+        harness.step();
+    }
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.selected_entry().unwrap().name, "banana.txt");
+
+    // Going back to no grouping removes the headers
+    harness.key_press(Key::Comma);
+    harness.step();
+    harness.key_press(Key::G);
+    harness.step();
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert_eq!(harness.state().tab_manager.group_by, GroupBy::None);
+    assert!(harness.query_by_label("B").is_none());
+}
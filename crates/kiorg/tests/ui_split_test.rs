@@ -0,0 +1,84 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::split;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files, wait_for_condition};
+
+#[test]
+fn test_split_and_join_file() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[temp_dir.path().join("big.bin")]);
+    let content: Vec<u8> = (0..2500u32).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&test_files[0], &content).unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+
+    // Split into 1 KiB parts
+    split::open(harness.state_mut());
+    harness.step();
+    match &mut harness.state_mut().show_popup {
+        Some(PopupType::Split(state)) => state.input = "1K".to_string(),
+        other => panic!("Expected split popup, got {other:?}"),
+    }
+    harness.key_press(Key::Enter);
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().show_popup.is_none()
+    });
+
+    let first_part = temp_dir.path().join("big.bin.001");
+    assert_eq!(std::fs::metadata(&first_part).unwrap().len(), 1024);
+    assert!(temp_dir.path().join("big.bin.003").exists());
+    assert!(temp_dir.path().join("big.bin.sha256").exists());
+
+    // Join the parts back after the original is gone
+    std::fs::remove_file(&test_files[0]).unwrap();
+    harness.state_mut().refresh_entries();
+    let index = harness
+        .state()
+        .tab_manager
+        .current_tab_ref()
+        .entries
+        .iter()
+        .position(|e| e.meta.path == first_part)
+        .unwrap();
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .selected_index = index;
+    harness.step();
+
+    let ctx = harness.ctx.clone();
+    split::start_join_selected(harness.state_mut(), &ctx);
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().show_popup.is_none()
+    });
+    assert_eq!(std::fs::read(&test_files[0]).unwrap(), content);
+}
+
+#[test]
+fn test_split_rejects_invalid_input() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[temp_dir.path().join("file.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    split::open(harness.state_mut());
+    harness.step();
+    match &mut harness.state_mut().show_popup {
+        Some(PopupType::Split(state)) => state.input = "lots".to_string(),
+        other => panic!("Expected split popup, got {other:?}"),
+    }
+    harness.key_press(Key::Enter);
+    harness.step();
+
+    // The prompt stays open to fix the input
+    assert!(matches!(
+        harness.state().show_popup,
+        Some(PopupType::Split(_))
+    ));
+}
@@ -0,0 +1,44 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::open_wrap::{acquire_open_test_lock, clear_open_calls, get_system_terminal_calls};
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+fn alt_shift() -> egui::Modifiers {
+    egui::Modifiers {
+        alt: true,
+        shift: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_open_system_terminal() {
+    let _lock = acquire_open_test_lock();
+    clear_open_calls();
+
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("dir"),
+        temp_dir.path().join("file.txt"),
+    ]);
+    let mut harness = create_harness(&temp_dir);
+
+    // The highlighted directory is opened
+    harness.key_press_modifiers(alt_shift(), Key::T);
+    harness.step();
+    let calls = get_system_terminal_calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].path, test_files[0].as_os_str());
+
+    // A highlighted file opens the current directory
+    harness.key_press(Key::J);
+    harness.step();
+    harness.key_press_modifiers(alt_shift(), Key::T);
+    harness.step();
+    let calls = get_system_terminal_calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].path, temp_dir.path().as_os_str());
+}
@@ -72,8 +72,11 @@ fn test_teleport_nonexistent_directory_shows_error_and_removes_from_history() {
 
     // The search results should not include the deleted directory since it was removed
     // from the visit history and get_search_results filters out non-existent paths
-    let search_results =
-        kiorg::ui::popup::teleport::get_search_results("", &harness.state().visit_history);
+    let search_results = kiorg::ui::popup::teleport::get_search_results(
+        "",
+        &harness.state().visit_history,
+        &kiorg::config::History::default(),
+    );
 
     // The deleted directory should not appear in search results
     let contains_deleted_dir = search_results
@@ -84,3 +87,51 @@ fn test_teleport_nonexistent_directory_shows_error_and_removes_from_history() {
         "Deleted directory should not appear in teleport search results"
     );
 }
+
+#[test]
+fn test_teleport_ranks_by_frecency() {
+    let temp_dir = tempdir().unwrap();
+    let often_long_ago = temp_dir.path().join("project_old");
+    let recent = temp_dir.path().join("project_new");
+    std::fs::create_dir(&often_long_ago).unwrap();
+    std::fs::create_dir(&recent).unwrap();
+
+    let now = kiorg::visit_history::now_ts();
+    let day = 24 * 60 * 60;
+    let mut history = std::collections::HashMap::new();
+    for (path, accessed_ts, count) in [(&often_long_ago, now - 90 * day, 50), (&recent, now, 3)] {
+        history.insert(
+            path.clone(),
+            kiorg::visit_history::VisitHistoryEntry {
+                path: path.clone(),
+                accessed_ts,
+                count,
+            },
+        );
+    }
+
+    let paths = |query: &str, history_config: &kiorg::config::History| {
+        kiorg::ui::popup::teleport::get_search_results(query, &history, history_config)
+            .into_iter()
+            .map(|result| result.item.entry.path)
+            .collect::<Vec<_>>()
+    };
+
+    // Many visits months ago count less than a few recent ones
+    let default_config = kiorg::config::History::default();
+    assert_eq!(
+        paths("", &default_config),
+        vec![recent.clone(), often_long_ago.clone()]
+    );
+    assert_eq!(
+        paths("project", &default_config),
+        vec![recent.clone(), often_long_ago.clone()]
+    );
+
+    // Unless visits decay slowly
+    let slow_decay = kiorg::config::History {
+        frecency_half_life_days: Some(365.0),
+        ..kiorg::config::History::default()
+    };
+    assert_eq!(paths("", &slow_decay), vec![often_long_ago, recent]);
+}
@@ -84,3 +84,58 @@ fn test_ctrl_h_toggle_hidden_files() {
         assert_eq!(tab.entries.len(), 1);
     }
 }
+
+#[cfg(not(windows))]
+#[test]
+fn test_hidden_files_toggled_per_tab() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[
+        temp_dir.path().join("visible_file.txt"),
+        temp_dir.path().join(".hidden_file.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    // A second tab in the same directory
+    harness.key_press(Key::T);
+    harness.step();
+
+    // Ctrl+H only shows hidden files in the second tab
+    harness.key_press_modifiers(ctrl_modifiers(), Key::H);
+    harness.step();
+    {
+        let tab_manager = &harness.state().tab_manager;
+        assert_eq!(tab_manager.current_tab_ref().show_hidden, Some(true));
+        assert_eq!(tab_manager.current_tab_ref().entries.len(), 2);
+        assert_eq!(tab_manager.tab_ref(0).show_hidden, None);
+    }
+
+    harness.state_mut().tab_manager.switch_to_tab(0);
+    harness.state_mut().refresh_entries();
+    harness.step();
+    assert!(!harness.state().tab_manager.show_hidden);
+    assert_eq!(
+        harness.state().tab_manager.current_tab_ref().entries.len(),
+        1
+    );
+
+    // The tab setting is saved with the tabs
+    let state = harness.state().tab_manager.to_state();
+    let restored = kiorg::models::tab::TabManager::from_state(state);
+    assert_eq!(restored.tab_ref(1).show_hidden, Some(true));
+
+    // Alt+H changes the default, used by tabs without their own setting
+    harness.key_press_modifiers(
+        egui::Modifiers {
+            alt: true,
+            ..Default::default()
+        },
+        Key::H,
+    );
+    harness.step();
+    {
+        let tab_manager = &harness.state().tab_manager;
+        assert!(tab_manager.show_hidden);
+        assert_eq!(tab_manager.current_tab_ref().show_hidden, None);
+        assert_eq!(tab_manager.current_tab_ref().entries.len(), 2);
+    }
+}
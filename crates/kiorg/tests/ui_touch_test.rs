@@ -0,0 +1,84 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::touch::Timestamp;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+fn alt() -> egui::Modifiers {
+    egui::Modifiers {
+        alt: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_touch_selected_entry_with_custom_time() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[temp_dir.path().join("a.txt")]);
+    std::fs::write(&test_files[0], "content").unwrap();
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press_modifiers(alt(), Key::T);
+    harness.step();
+
+    let time = Timestamp {
+        year: 2020,
+        month: 1,
+        day: 2,
+        hour: 3,
+        minute: 4,
+        second: 5,
+    };
+    match &mut harness.state_mut().show_popup {
+        Some(PopupType::Touch(state)) => {
+            assert_eq!(state.name, "a.txt");
+            assert!(state.targets.is_empty());
+            state.set_time = true;
+            state.time = time;
+        }
+        other => panic!("Expected touch popup, got {other:?}"),
+    }
+
+    harness.key_press(Key::Enter);
+    harness.step();
+
+    assert!(harness.state().show_popup.is_none());
+    let modified = std::fs::metadata(&test_files[0])
+        .unwrap()
+        .modified()
+        .unwrap();
+    assert_eq!(modified, time.to_system_time().unwrap());
+    assert_eq!(std::fs::read_to_string(&test_files[0]).unwrap(), "content");
+}
+
+#[test]
+fn test_touch_creates_missing_file() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[temp_dir.path().join("a.txt")]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press_modifiers(alt(), Key::T);
+    harness.step();
+    match &mut harness.state_mut().show_popup {
+        Some(PopupType::Touch(state)) => state.name.clear(),
+        other => panic!("Expected touch popup, got {other:?}"),
+    }
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text("new.txt".to_string()));
+    harness.step();
+    harness.key_press(Key::Enter);
+    harness.step();
+
+    let new_file = temp_dir.path().join("new.txt");
+    assert!(new_file.is_file());
+    assert_eq!(std::fs::metadata(&new_file).unwrap().len(), 0);
+
+    // The new file is selected
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.selected_entry().unwrap().meta.path, new_file);
+}
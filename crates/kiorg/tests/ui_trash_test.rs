@@ -0,0 +1,103 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use kiorg::ui::popup::PopupType;
+use kiorg::ui::popup::delete::DeleteConfirmState;
+use tempfile::tempdir;
+use ui_test_helpers::{
+    create_harness, create_test_files, ctrl_shift_modifiers, wait_for_condition,
+};
+
+#[test]
+fn test_trash_and_restore_file() {
+    let temp_dir = tempdir().unwrap();
+    let test_files = create_test_files(&[
+        temp_dir.path().join("keep.txt"),
+        temp_dir.path().join("trash_me.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+
+    // Select trash_me.txt
+    harness
+        .state_mut()
+        .tab_manager
+        .current_tab_mut()
+        .selected_index = 1;
+    harness.step();
+
+    // Press 'd' to move the file to the trash
+    harness.key_press(Key::D);
+    harness.step();
+
+    match &harness.state().show_popup {
+        Some(PopupType::Delete(state, _)) => {
+            assert_eq!(*state, DeleteConfirmState::Trash);
+        }
+        other => panic!("Expected trash confirmation popup, got {other:?}"),
+    }
+
+    // A single confirmation is enough to trash
+    harness.key_press(Key::Enter);
+    wait_for_condition(|| {
+        harness.step();
+        harness.state().show_popup.is_none()
+    });
+    assert!(!test_files[1].exists(), "File should be moved to the trash");
+    assert!(test_files[0].exists(), "Other files should be kept");
+
+    // Open the trash popup, the trashed file is listed
+    harness.key_press_modifiers(ctrl_shift_modifiers(), Key::T);
+    harness.step();
+    match &harness.state().show_popup {
+        Some(PopupType::Trash(state)) => {
+            assert!(
+                state
+                    .items
+                    .iter()
+                    .any(|item| item.original_path.as_deref() == Some(test_files[1].as_path())),
+                "Trashed file should be listed, got {:?}",
+                state.items
+            );
+        }
+        other => panic!("Expected trash popup, got {other:?}"),
+    }
+
+    // Filter down to the file and restore it
+    harness.state_mut().show_popup = match harness.state_mut().show_popup.take() {
+        Some(PopupType::Trash(mut state)) => {
+            state.query = "trash_me".to_string();
+            Some(PopupType::Trash(state))
+        }
+        other => other,
+    };
+    harness.step();
+    harness.key_press(Key::Enter);
+    harness.step();
+
+    assert!(test_files[1].exists(), "File should be restored");
+    match &harness.state().show_popup {
+        Some(PopupType::Trash(state)) => {
+            assert!(
+                !state
+                    .items
+                    .iter()
+                    .any(|item| item.original_path.as_deref() == Some(test_files[1].as_path())),
+                "Restored file should no longer be listed"
+            );
+        }
+        other => panic!("Trash popup should stay open, got {other:?}"),
+    }
+
+    // The restored file shows up in the listing again
+    assert!(
+        harness
+            .state()
+            .tab_manager
+            .current_tab_ref()
+            .entries
+            .iter()
+            .any(|e| e.meta.path == test_files[1])
+    );
+}
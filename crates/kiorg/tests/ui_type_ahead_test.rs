@@ -0,0 +1,86 @@
+#[path = "mod/ui_test_helpers.rs"]
+mod ui_test_helpers;
+
+use egui::Key;
+use tempfile::tempdir;
+use ui_test_helpers::{create_harness, create_test_files};
+
+fn visible_names(harness: &ui_test_helpers::TestHarness<'_>) -> Vec<String> {
+    let tab = harness.state().tab_manager.current_tab_ref();
+    let mut names: Vec<String> = tab
+        .get_cached_filtered_entries()
+        .iter()
+        .map(|&index| tab.entries[index].name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_type_ahead_filter() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[
+        temp_dir.path().join("apple.txt"),
+        temp_dir.path().join("Banana.txt"),
+        temp_dir.path().join("cherry.md"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::F);
+    harness.step();
+    assert!(harness.state().type_ahead.typing);
+    assert!(!harness.state().search_bar.active());
+
+    // Typed letters narrow the list instead of triggering shortcuts
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text("AN".to_string()));
+    harness.step();
+    assert_eq!(visible_names(&harness), vec!["Banana.txt"]);
+    let tab = harness.state().tab_manager.current_tab_ref();
+    assert_eq!(tab.entries[tab.selected_index].name, "Banana.txt");
+
+    harness.key_press(Key::Backspace);
+    harness.step();
+    assert_eq!(visible_names(&harness), vec!["Banana.txt", "apple.txt"]);
+
+    // Enter keeps the filter while shortcuts work again
+    harness.key_press(Key::Enter);
+    harness.step();
+    assert!(!harness.state().type_ahead.typing);
+    assert_eq!(harness.state().type_ahead.query.as_deref(), Some("A"));
+    assert_eq!(visible_names(&harness).len(), 2);
+
+    // Escape clears it
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(!harness.state().type_ahead.active());
+    assert_eq!(visible_names(&harness).len(), 3);
+}
+
+#[test]
+fn test_type_ahead_escape_while_typing() {
+    let temp_dir = tempdir().unwrap();
+    create_test_files(&[
+        temp_dir.path().join("one.txt"),
+        temp_dir.path().join("two.txt"),
+    ]);
+
+    let mut harness = create_harness(&temp_dir);
+    harness.key_press(Key::F);
+    harness.step();
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Text("one".to_string()));
+    harness.step();
+    assert_eq!(visible_names(&harness), vec!["one.txt"]);
+
+    harness.key_press(Key::Escape);
+    harness.step();
+    assert!(!harness.state().type_ahead.active());
+    assert_eq!(visible_names(&harness).len(), 2);
+    // Escape only cleared the filter, kiorg is still running
+    assert!(harness.state().show_popup.is_none());
+}
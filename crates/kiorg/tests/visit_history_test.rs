@@ -1,6 +1,8 @@
 use kiorg::app::Kiorg;
+use kiorg::config::History;
 use kiorg::visit_history::{
-    VisitHistoryEntry, load_visit_history, save_visit_history, update_visit_history,
+    VisitHistoryEntry, load_file_history, load_visit_history, prune_history, record_visit,
+    save_file_history, save_visit_history, update_visit_history,
 };
 use std::collections::HashMap;
 use std::fs;
@@ -623,3 +625,100 @@ fn test_navigate_to_nonexistent_directory_removes_from_history() {
     // Note: In a real UI test, we would check if toasts contain the error message
     // For now, we just verify the navigation didn't succeed
 }
+
+#[test]
+fn test_prune_history_drops_least_recently_used() {
+    let mut history = HashMap::new();
+    for (name, accessed_ts) in [("a", 300), ("b", 100), ("c", 200)] {
+        let path = PathBuf::from(format!("/test/{name}"));
+        history.insert(
+            path.clone(),
+            VisitHistoryEntry {
+                path,
+                accessed_ts,
+                count: 1,
+            },
+        );
+    }
+
+    prune_history(&mut history, 2);
+    assert_eq!(history.len(), 2);
+    assert!(!history.contains_key(&PathBuf::from("/test/b")));
+
+    prune_history(&mut history, 5);
+    assert_eq!(history.len(), 2);
+}
+
+#[test]
+fn test_record_visit_respects_exclusions_and_cap() {
+    let history_config = History {
+        exclude: vec![PathBuf::from("/secret")],
+        max_entries: Some(1),
+        frecency_half_life_days: None,
+    };
+    let mut history = HashMap::new();
+
+    assert!(!record_visit(
+        &mut history,
+        &PathBuf::from("/secret/notes.txt"),
+        &history_config
+    ));
+    assert!(history.is_empty());
+
+    assert!(record_visit(
+        &mut history,
+        &PathBuf::from("/secretary/notes.txt"),
+        &history_config
+    ));
+    assert!(record_visit(
+        &mut history,
+        &PathBuf::from("/public/a.txt"),
+        &history_config
+    ));
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn test_file_history_is_stored_separately() {
+    let temp_dir = tempdir().unwrap();
+    let config_dir = temp_dir.path().to_path_buf();
+
+    let file = PathBuf::from("/test/report.pdf");
+    let mut history = HashMap::new();
+    update_visit_history(&mut history, &file);
+    save_file_history(&history, Some(&config_dir)).unwrap();
+
+    let loaded = load_file_history(Some(&config_dir)).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[&file].count, 1);
+    assert!(load_visit_history(Some(&config_dir)).unwrap().is_empty());
+}
+
+#[test]
+fn test_frecency_decays_with_age() {
+    let entry = VisitHistoryEntry {
+        path: PathBuf::from("/test/a"),
+        accessed_ts: 1_000_000,
+        count: 8,
+    };
+    let day = 24.0 * 60.0 * 60.0;
+    assert_eq!(entry.frecency(1_000_000, day), 8.0);
+    assert_eq!(entry.frecency(1_000_000 + 86_400, day), 4.0);
+    assert_eq!(entry.frecency(1_000_000 + 3 * 86_400, day), 1.0);
+    // Clock going backwards doesn't boost the entry
+    assert_eq!(entry.frecency(0, day), 8.0);
+
+    let history_config = History {
+        frecency_half_life_days: Some(2.0),
+        ..History::default()
+    };
+    assert_eq!(history_config.frecency_half_life_secs(), 2.0 * day);
+    let invalid = History {
+        frecency_half_life_days: Some(0.0),
+        ..History::default()
+    };
+    assert_eq!(
+        invalid.frecency_half_life_secs(),
+        History::DEFAULT_FRECENCY_HALF_LIFE_DAYS * day
+    );
+}
@@ -31,16 +31,16 @@ impl PluginHandler for DemoPlugin {
                         uid: kiorg_plugin::uuid::Uuid::new_v4().to_string(),
                     },
                 )),
-                kiorg_plugin::Component::Table(kiorg_plugin::TableComponent {
-                    headers: Some(vec!["Property".to_string(), "Value".to_string()]),
-                    rows: vec![
+                kiorg_plugin::Component::Table(kiorg_plugin::TableComponent::new(
+                    Some(vec!["Property".to_string(), "Value".to_string()]),
+                    vec![
                         vec![
                             "Plugin Name".to_string(),
                             env!("CARGO_PKG_NAME").to_string(),
                         ],
                         vec!["Plugin Version".to_string(), self.metadata.version.clone()],
                     ],
-                }),
+                )),
             ],
         }
     }
@@ -63,6 +63,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 preview: Some(PreviewCapability {
                     file_pattern: r"^kiorg$".to_string(), // Match files named "kiorg"
                 }),
+                ..Default::default()
             },
         },
     }
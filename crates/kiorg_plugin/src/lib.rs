@@ -7,19 +7,30 @@ use bytecheck::CheckBytes;
 use rkyv::{bytecheck, Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use serde::{Deserialize, Serialize};
 use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 pub use uuid;
 pub use uuid::Uuid;
 
 /// Protocol version for compatibility checking
 /// Major version changes indicate incompatible protocol changes
-pub const PROTOCOL_VERSION: &str = "0.0.1";
+pub const PROTOCOL_VERSION: &str = "0.1.0";
 
 /// Check if the provided engine version is compatible with this plugin library version
+///
+/// Like semver, breaking changes bump the first non-zero component, so `0.x`
+/// versions must match up to the minor version.
 pub fn check_compatibility(engine_version: &str) -> bool {
-    let engine_major = engine_version.split('.').next().unwrap_or("0");
-    let my_major = PROTOCOL_VERSION.split('.').next().unwrap_or("0");
+    breaking_version(engine_version) == breaking_version(PROTOCOL_VERSION)
+}
 
-    engine_major == my_major
+/// Components of a version that change with breaking changes
+fn breaking_version(version: &str) -> (&str, &str) {
+    let mut components = version.split('.');
+    let major = components.next().unwrap_or("0");
+    let minor = components.next().unwrap_or("0");
+    (major, if major == "0" { minor } else { "" })
 }
 
 /// Unique identifier for plugin calls - serialized as bytes for efficiency
@@ -66,10 +77,13 @@ pub type StreamId = Uuid;
 pub type HelloMessage = PluginMetadata;
 
 /// Plugin capabilities
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PluginCapabilities {
     /// Preview rendering capabilities
     pub preview: Option<PreviewCapability>,
+    /// Long-running operations on a batch of files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bulk_operations: Vec<BulkOperationCapability>,
 }
 
 /// Preview rendering capability
@@ -79,6 +93,18 @@ pub struct PreviewCapability {
     pub file_pattern: String,
 }
 
+/// An operation the plugin can run on the selected files, e.g. converting
+/// images to another format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkOperationCapability {
+    /// Identifier sent back in [`EngineCommand::BulkOperation`]
+    pub id: String,
+    /// Label shown in the context menu
+    pub label: String,
+    /// Regex pattern the file names must match, any file if not set
+    pub file_pattern: Option<String>,
+}
+
 /// Commands that can be sent from engine to plugin
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "_T")]
@@ -89,6 +115,14 @@ pub enum EngineCommand {
     Preview { path: String, available_width: f32 },
     /// Preview popup command - takes a file path and available width
     PreviewPopup { path: String, available_width: f32 },
+    /// Run a bulk operation on the given files, answered by any number of
+    /// [`PluginResponse::Progress`] followed by the final response
+    BulkOperation {
+        operation: String,
+        paths: Vec<String>,
+    },
+    /// Cancel the running bulk operation, this command has no response
+    Cancel,
 }
 
 /// Message sent from engine to plugin
@@ -115,6 +149,14 @@ pub enum PluginResponse {
     },
     /// Error response for reporting issues back to the engine
     Error { message: String },
+    /// Progress of a running bulk operation
+    Progress {
+        done: u64,
+        total: u64,
+        message: Option<String>,
+    },
+    /// Bulk operation finished, with a summary shown to the user
+    BulkOperationDone { message: String },
 }
 
 /// Component types for rich preview
@@ -180,15 +222,147 @@ pub enum ImageSource {
         /// unique identifier for the image
         uid: String,
     },
+    /// Raw RGBA8 pixels in a shared memory file, see [`ImageSource::shared`]
+    ///
+    /// Large images like video frames skip the PNG encoding and the copy
+    /// through stdout. The engine removes the file once it's loaded.
+    Shared {
+        /// File holding `width * height * 4` bytes
+        path: String,
+        width: u32,
+        height: u32,
+        /// unique identifier for the image
+        uid: String,
+    },
 }
 
+/// File name prefix of shared images, the engine only reads and removes these files
+const SHARED_IMAGE_PREFIX: &str = "kiorg_image_";
+
+/// Directory of shared image files, backed by memory where the OS provides one
+pub fn shared_image_dir() -> PathBuf {
+    let shm = Path::new("/dev/shm");
+    if cfg!(target_os = "linux") && shm.is_dir() {
+        shm.to_path_buf()
+    } else {
+        std::env::temp_dir()
+    }
+}
+
+impl ImageSource {
+    /// Write RGBA8 pixels to a shared image file
+    pub fn shared(
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        uid: impl Into<String>,
+    ) -> io::Result<Self> {
+        if rgba.len() as u64 != u64::from(width) * u64::from(height) * 4 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "pixel data doesn't match the image size",
+            ));
+        }
+        let path = shared_image_dir().join(format!("{SHARED_IMAGE_PREFIX}{}", Uuid::new_v4()));
+        std::fs::write(&path, rgba)?;
+        Ok(Self::Shared {
+            path: path.to_string_lossy().into_owned(),
+            width,
+            height,
+            uid: uid.into(),
+        })
+    }
+}
+
+/// Read and remove a shared image file written by [`ImageSource::shared`]
+///
+/// Only files written by plugins are accepted, other paths are rejected
+/// without being touched.
+pub fn take_shared_image(path: &str, width: u32, height: u32) -> io::Result<Vec<u8>> {
+    let path = Path::new(path);
+    let is_shared_image = path.parent() == Some(shared_image_dir().as_path())
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(SHARED_IMAGE_PREFIX));
+    if !is_shared_image {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("not a shared image file: {}", path.display()),
+        ));
+    }
+
+    let data = std::fs::read(path);
+    let _ = std::fs::remove_file(path);
+    let data = data?;
+    if data.len() as u64 != u64::from(width) * u64::from(height) * 4 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("shared image is not {width}x{height} RGBA"),
+        ));
+    }
+    Ok(data)
+}
+
+/// Horizontal alignment of the cells in a table column
 #[derive(
-    Debug, Clone, Serialize, Deserialize, Archive, RkyvSerialize, RkyvDeserialize, CheckBytes,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+    CheckBytes,
+)]
+#[repr(u8)]
+pub enum ColumnAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    Archive,
+    RkyvSerialize,
+    RkyvDeserialize,
+    CheckBytes,
 )]
 
 pub struct TableComponent {
     pub headers: Option<Vec<String>>,
     pub rows: Vec<Vec<String>>,
+    /// Alignment of each column, columns without one are left aligned
+    #[serde(default)]
+    pub alignments: Vec<ColumnAlign>,
+    /// Show the cells in a monospace font, e.g. for hashes or offsets
+    #[serde(default)]
+    pub monospace: bool,
+}
+
+impl TableComponent {
+    pub fn new(headers: Option<Vec<String>>, rows: Vec<Vec<String>>) -> Self {
+        Self {
+            headers,
+            rows,
+            ..Default::default()
+        }
+    }
+
+    /// Alignment of a column
+    pub fn alignment(&self, column: usize) -> ColumnAlign {
+        self.alignments.get(column).copied().unwrap_or_default()
+    }
 }
 
 /// Plugin metadata
@@ -221,6 +395,19 @@ pub trait PluginHandler {
     fn on_preview_popup(&mut self, path: &str, available_width: f32) -> PluginResponse {
         self.on_preview(path, available_width)
     }
+    /// Run a bulk operation declared in the capabilities
+    ///
+    /// Report progress through `progress` and stop early once it's cancelled.
+    fn on_bulk_operation(
+        &mut self,
+        operation: &str,
+        _paths: &[String],
+        _progress: &mut BulkProgress,
+    ) -> PluginResponse {
+        PluginResponse::Error {
+            message: format!("Unsupported bulk operation: {operation}"),
+        }
+    }
     fn metadata(&self) -> PluginMetadata;
 
     fn run(mut self)
@@ -240,46 +427,39 @@ pub trait PluginHandler {
     /// This function will read messages from stdin and dispatch them to the handler.
     /// It will exit when stdin is closed (host process exited) or on communication error.
     fn run_plugin_loop(&mut self) {
-        loop {
-            match read_message() {
-                Ok(message) => {
-                    let response = match message.command {
-                        EngineCommand::Hello { protocol_version } => {
-                            self.on_hello(&protocol_version)
-                        }
-                        EngineCommand::Preview {
-                            path,
-                            available_width,
-                        } => self.on_preview(&path, available_width),
-                        EngineCommand::PreviewPopup {
-                            path,
-                            available_width,
-                        } => self.on_preview_popup(&path, available_width),
-                    };
-
-                    if send_message(&response).is_err() {
-                        // Failed to send response, host probably disconnected
-                        break;
+        // Messages are read on their own thread so a cancel can reach a
+        // running bulk operation
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let messages = spawn_message_reader(cancelled.clone());
+
+        for message in messages {
+            let response = match message {
+                Ok(message) => match message.command {
+                    EngineCommand::Hello { protocol_version } => self.on_hello(&protocol_version),
+                    EngineCommand::Preview {
+                        path,
+                        available_width,
+                    } => self.on_preview(&path, available_width),
+                    EngineCommand::PreviewPopup {
+                        path,
+                        available_width,
+                    } => self.on_preview_popup(&path, available_width),
+                    EngineCommand::BulkOperation { operation, paths } => {
+                        let mut progress = BulkProgress::new(&cancelled);
+                        self.on_bulk_operation(&operation, &paths, &mut progress)
                     }
-                }
-                Err(e) => {
-                    // Check if the error is a clean shutdown (UnexpectedEof)
-                    if let Some(io_err) = e.downcast_ref::<io::Error>() {
-                        if io_err.kind() == io::ErrorKind::UnexpectedEof {
-                            break;
-                        }
-                    }
-
-                    let error_msg = format!("Invalid command received: {}", e);
+                    // Handled by the reader thread
+                    EngineCommand::Cancel => continue,
+                },
+                Err(error_msg) => {
                     eprintln!("{}", error_msg);
-
-                    // Try to send the error back to the engine
-                    let error_response = PluginResponse::Error { message: error_msg };
-                    if send_message(&error_response).is_err() {
-                        eprintln!("Failed to send error response to engine");
-                        std::process::exit(-2);
-                    }
+                    PluginResponse::Error { message: error_msg }
                 }
+            };
+
+            if send_message(&response).is_err() {
+                // Failed to send response, host probably disconnected
+                break;
             }
         }
     }
@@ -311,6 +491,12 @@ pub trait PluginHandler {
             } else {
                 println!("  No preview support");
             }
+            for operation in &metadata.capabilities.bulk_operations {
+                println!("  Bulk Operation: {} ({})", operation.label, operation.id);
+                if let Some(pattern) = &operation.file_pattern {
+                    println!("    File Pattern: {}", pattern);
+                }
+            }
             println!();
             println!("To install this plugin:");
             println!("  1. Copy the plugin binary into the plugins directory under kiorg's config directory.");
@@ -334,6 +520,77 @@ pub trait PluginHandler {
     }
 }
 
+/// Progress reporting and cancellation for a running bulk operation
+pub struct BulkProgress<'a> {
+    cancelled: &'a AtomicBool,
+}
+
+impl<'a> BulkProgress<'a> {
+    pub fn new(cancelled: &'a AtomicBool) -> Self {
+        Self { cancelled }
+    }
+
+    /// Send the progress to the engine, `message` usually names the file being processed
+    pub fn report(
+        &mut self,
+        done: u64,
+        total: u64,
+        message: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        send_message(&PluginResponse::Progress {
+            done,
+            total,
+            message,
+        })
+    }
+
+    /// Whether the user cancelled the operation
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Read messages from stdin on a separate thread
+///
+/// Cancel commands set `cancelled` instead of being forwarded, the flag is
+/// cleared when a new bulk operation starts. The channel closes once stdin
+/// is closed.
+fn spawn_message_reader(
+    cancelled: Arc<AtomicBool>,
+) -> mpsc::Receiver<Result<EngineMessage, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || loop {
+        let message = match read_message() {
+            Ok(message) => match message.command {
+                EngineCommand::Cancel => {
+                    cancelled.store(true, Ordering::Relaxed);
+                    continue;
+                }
+                EngineCommand::BulkOperation { .. } => {
+                    cancelled.store(false, Ordering::Relaxed);
+                    Ok(message)
+                }
+                _ => Ok(message),
+            },
+            Err(e) => {
+                // Check if the error is a clean shutdown (UnexpectedEof)
+                if let Some(io_err) = e.downcast_ref::<io::Error>() {
+                    if io_err.kind() == io::ErrorKind::UnexpectedEof {
+                        // Stop any running operation, nobody is waiting for it
+                        cancelled.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                }
+                Err(format!("Invalid command received: {}", e))
+            }
+        };
+        if tx.send(message).is_err() {
+            break;
+        }
+    });
+    rx
+}
+
 /// Read a MessagePack message from stdin
 pub fn read_message() -> Result<EngineMessage, Box<dyn std::error::Error>> {
     let stdin = io::stdin();
@@ -424,6 +681,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shared_image_round_trip() {
+        let rgba = vec![255u8; 2 * 3 * 4];
+        let source = ImageSource::shared(&rgba, 2, 3, "uid").unwrap();
+        let ImageSource::Shared {
+            path,
+            width,
+            height,
+            uid,
+        } = source
+        else {
+            panic!("expected a shared image");
+        };
+        assert_eq!((width, height, uid.as_str()), (2, 3, "uid"));
+
+        assert_eq!(take_shared_image(&path, width, height).unwrap(), rgba);
+        // The file is removed once taken
+        assert!(!Path::new(&path).exists());
+
+        assert!(ImageSource::shared(&rgba, 3, 3, "uid").is_err());
+    }
+
+    #[test]
+    fn test_take_shared_image_rejects_other_files() {
+        let file = tempfile_in_shared_dir("not_kiorg_image");
+        let result = take_shared_image(file.to_str().unwrap(), 1, 1);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert!(file.exists());
+        std::fs::remove_file(file).unwrap();
+    }
+
+    fn tempfile_in_shared_dir(prefix: &str) -> PathBuf {
+        let path = shared_image_dir().join(format!("{prefix}_{}", Uuid::new_v4()));
+        std::fs::write(&path, [0u8; 4]).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_check_compatibility() {
+        assert!(check_compatibility(PROTOCOL_VERSION));
+        assert!(check_compatibility("0.1.7"));
+        // Engines without shared images
+        assert!(!check_compatibility("0.0.1"));
+        assert!(!check_compatibility("1.1.0"));
+        assert_eq!(breaking_version("2.3.1"), ("2", ""));
+    }
+
     #[test]
     fn test_plugin_response_serialization() {
         let resp = PluginResponse::Preview {
@@ -460,9 +764,32 @@ mod tests {
         assert_eq!(bytes, expected, "PluginResponse bytes mismatch");
     }
 
+    #[test]
+    fn test_table_without_hints_deserialization() {
+        // Tables sent by plugins built before alignment and monospace hints
+        #[derive(Serialize)]
+        struct OldTable {
+            headers: Option<Vec<String>>,
+            rows: Vec<Vec<String>>,
+        }
+        let bytes = rmp_serde::to_vec_named(&OldTable {
+            headers: None,
+            rows: vec![vec!["a".to_string(), "1".to_string()]],
+        })
+        .unwrap();
+
+        let table: TableComponent = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(table.rows.len(), 1);
+        assert!(!table.monospace);
+        assert_eq!(table.alignment(1), ColumnAlign::Left);
+    }
+
     #[test]
     fn test_plugin_hello_response_serialization() {
-        let caps = PluginCapabilities { preview: None };
+        let caps = PluginCapabilities {
+            preview: None,
+            ..Default::default()
+        };
         let msg = PluginMetadata {
             name: "Test Plugin".to_string(),
             version: "1.0.0".to_string(),
@@ -513,7 +840,10 @@ mod tests {
 
     #[test]
     fn test_plugin_version_incompatible_response_serialization() {
-        let caps = PluginCapabilities { preview: None };
+        let caps = PluginCapabilities {
+            preview: None,
+            ..Default::default()
+        };
         let meta = PluginMetadata {
             name: "Test Plugin".to_string(),
             version: "1.0.0".to_string(),
@@ -569,4 +899,68 @@ mod tests {
             "PluginResponse::VersionIncompatible bytes mismatch"
         );
     }
+
+    #[test]
+    fn test_bulk_operation_round_trip() {
+        let message = EngineMessage {
+            id: CallId::new(),
+            command: EngineCommand::BulkOperation {
+                operation: "to_jpeg".to_string(),
+                paths: vec!["/tmp/a.heic".to_string(), "/tmp/b.heic".to_string()],
+            },
+        };
+        let bytes = rmp_serde::to_vec_named(&message).unwrap();
+        let decoded: EngineMessage = rmp_serde::from_slice(&bytes).unwrap();
+        let EngineCommand::BulkOperation { operation, paths } = decoded.command else {
+            panic!("expected a bulk operation");
+        };
+        assert_eq!(operation, "to_jpeg");
+        assert_eq!(paths.len(), 2);
+
+        let resp = PluginResponse::Progress {
+            done: 1,
+            total: 2,
+            message: Some("a.heic".to_string()),
+        };
+        let bytes = rmp_serde::to_vec_named(&resp).unwrap();
+        let decoded: PluginResponse = rmp_serde::from_slice(&bytes).unwrap();
+        assert!(matches!(
+            decoded,
+            PluginResponse::Progress { done: 1, total: 2, message: Some(m) } if m == "a.heic"
+        ));
+    }
+
+    #[test]
+    fn test_bulk_operation_capabilities() {
+        let caps = PluginCapabilities {
+            preview: None,
+            bulk_operations: vec![BulkOperationCapability {
+                id: "to_jpeg".to_string(),
+                label: "Convert to JPEG".to_string(),
+                file_pattern: Some(r"(?i)\.heic$".to_string()),
+            }],
+        };
+        let bytes = rmp_serde::to_vec_named(&caps).unwrap();
+        let decoded: PluginCapabilities = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.bulk_operations.len(), 1);
+        assert_eq!(decoded.bulk_operations[0].label, "Convert to JPEG");
+
+        // Capabilities of plugins built before bulk operations
+        #[derive(Serialize)]
+        struct OldCapabilities {
+            preview: Option<PreviewCapability>,
+        }
+        let bytes = rmp_serde::to_vec_named(&OldCapabilities { preview: None }).unwrap();
+        let decoded: PluginCapabilities = rmp_serde::from_slice(&bytes).unwrap();
+        assert!(decoded.bulk_operations.is_empty());
+    }
+
+    #[test]
+    fn test_bulk_progress_cancelled() {
+        let cancelled = AtomicBool::new(false);
+        let progress = BulkProgress::new(&cancelled);
+        assert!(!progress.is_cancelled());
+        cancelled.store(true, Ordering::Relaxed);
+        assert!(progress.is_cancelled());
+    }
 }
@@ -1,4 +1,5 @@
-//! Cross-platform library for looking up registered applications for a given file path.
+//! Cross-platform library for looking up registered applications for a given file path,
+//! and for changing the default one.
 //!
 //! This crate supports Linux (XDG specification), macOS (NSWorkspace), and
 //! provides a placeholder for Windows.
@@ -6,15 +7,54 @@
 //! # Specification
 //! <https://specifications.freedesktop.org/mime-apps-spec/mime-apps-spec-latest.html>
 
+use std::path::PathBuf;
+
+/// The icon of an application.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppIcon {
+    /// An image file, PNG or SVG.
+    Path(PathBuf),
+    /// Pixels in RGBA order, row by row, without premultiplied alpha.
+    Rgba {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    },
+}
+
+/// An additional way to launch an application, e.g. opening a new window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppAction {
+    /// The identifier of the action in the desktop file.
+    pub id: String,
+    /// The user-visible name of the action.
+    pub name: String,
+    /// The command to launch the action with.
+    pub path: String,
+}
+
 /// Information about an application that can open a file.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct AppInfo {
     /// The absolute path to the application executable or bundle.
     pub path: String,
     /// The user-visible name of the application.
     pub name: String,
+    /// The platform identifier of the application, the desktop file ID on
+    /// Linux and the bundle identifier on macOS.
+    pub id: String,
+    /// The icon of the application, if it could be found.
+    pub icon: Option<AppIcon>,
+    /// Whether the application runs in a terminal, without a window of its own.
+    pub terminal: bool,
+    /// The desktop actions of the application on Linux, empty elsewhere.
+    pub actions: Vec<AppAction>,
 }
 
+mod sniff;
+
+pub use sniff::{SniffedType, sniff_bytes, sniff_file_type};
+
 #[cfg(target_os = "linux")]
 mod linux_impl;
 
@@ -26,6 +66,14 @@ mod linux_impl;
 #[cfg(target_os = "linux")]
 pub use linux_impl::get_apps_for_file_linux as get_apps_for_file;
 
+/// Makes `app` the default application for the type of the file at the given path.
+///
+/// On Linux, it sets the default in `$XDG_CONFIG_HOME/mimeapps.list`.
+/// On macOS, it registers the bundle with LaunchServices.
+/// On Windows, it opens the system "Open with" dialog to let the user pick the default.
+#[cfg(target_os = "linux")]
+pub use linux_impl::set_default_app_for_file_linux as set_default_app_for_file;
+
 #[cfg(target_os = "macos")]
 mod macos_impl;
 
@@ -37,6 +85,14 @@ mod macos_impl;
 #[cfg(target_os = "macos")]
 pub use macos_impl::get_apps_for_file_macos as get_apps_for_file;
 
+/// Makes `app` the default application for the type of the file at the given path.
+///
+/// On Linux, it sets the default in `$XDG_CONFIG_HOME/mimeapps.list`.
+/// On macOS, it registers the bundle with LaunchServices.
+/// On Windows, it opens the system "Open with" dialog to let the user pick the default.
+#[cfg(target_os = "macos")]
+pub use macos_impl::set_default_app_for_file_macos as set_default_app_for_file;
+
 #[cfg(target_os = "windows")]
 mod windows_impl;
 
@@ -47,3 +103,11 @@ mod windows_impl;
 /// On Windows, it is currently a placeholder and returns an empty list.
 #[cfg(target_os = "windows")]
 pub use windows_impl::get_apps_for_file_windows as get_apps_for_file;
+
+/// Makes `app` the default application for the type of the file at the given path.
+///
+/// On Linux, it sets the default in `$XDG_CONFIG_HOME/mimeapps.list`.
+/// On macOS, it registers the bundle with LaunchServices.
+/// On Windows, it opens the system "Open with" dialog to let the user pick the default.
+#[cfg(target_os = "windows")]
+pub use windows_impl::set_default_app_for_file_windows as set_default_app_for_file;
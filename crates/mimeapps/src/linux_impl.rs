@@ -1,7 +1,7 @@
 /// Reference:
 ///  https://specifications.freedesktop.org/mime-apps/latest/index.html
 ///  https://wiki.archlinux.org/title/XDG_MIME_Applications
-use super::AppInfo;
+use super::{AppAction, AppIcon, AppInfo};
 use file_type::FileType;
 use std::collections::HashSet;
 use std::fs;
@@ -243,12 +243,81 @@ fn find_desktop_file(desktop_name: &str) -> Option<PathBuf> {
     None
 }
 
-/// Parse a desktop file to extract Name and Exec fields
-fn parse_desktop_file(path: &Path) -> Option<AppInfo> {
+/// Icon theme subdirectories to look into, sizes close to how icons are
+/// displayed come first
+const ICON_THEME_SUBDIRS: &[&str] = &[
+    "48x48/apps",
+    "64x64/apps",
+    "32x32/apps",
+    "128x128/apps",
+    "256x256/apps",
+    "scalable/apps",
+];
+const ICON_EXTENSIONS: &[&str] = &["png", "svg"];
+
+/// Find the file of an icon name in the hicolor theme, or among the
+/// unthemed icons of `pixmap_dirs`
+fn find_icon_in(name: &str, icon_dirs: &[PathBuf], pixmap_dirs: &[PathBuf]) -> Option<PathBuf> {
+    // Icon names aren't supposed to have an extension, but some do
+    let name = ICON_EXTENSIONS
+        .iter()
+        .find_map(|ext| name.strip_suffix(&format!(".{ext}")))
+        .unwrap_or(name);
+    let themed = ICON_THEME_SUBDIRS.iter().flat_map(|subdir| {
+        icon_dirs
+            .iter()
+            .map(move |dir| dir.join("hicolor").join(subdir))
+    });
+    themed
+        .chain(pixmap_dirs.iter().cloned())
+        .flat_map(|dir| {
+            ICON_EXTENSIONS
+                .iter()
+                .map(move |ext| dir.join(format!("{name}.{ext}")))
+        })
+        .find(|path| path.is_file())
+}
+
+/// Resolve the Icon field of a desktop file, either an absolute path or an
+/// icon name looked up in the XDG icon directories
+fn resolve_icon(icon: &str) -> Option<AppIcon> {
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return path.is_file().then(|| AppIcon::Path(path.to_path_buf()));
+    }
+
+    let mut icon_dirs = vec![get_xdg_data_home().join("icons")];
+    if let Some(home) = dirs::home_dir() {
+        icon_dirs.push(home.join(".icons"));
+    }
+    icon_dirs.extend(get_xdg_data_dirs().iter().map(|dir| dir.join("icons")));
+    let pixmap_dirs = [PathBuf::from("/usr/share/pixmaps")];
+    find_icon_in(icon, &icon_dirs, &pixmap_dirs).map(AppIcon::Path)
+}
+
+/// Parse a desktop file to extract Name, Exec and Icon fields
+fn parse_desktop_file(path: &Path, desktop_id: &str) -> Option<AppInfo> {
     let content = fs::read_to_string(path).ok()?;
+    parse_desktop_entry(&content, desktop_id)
+}
+
+/// Section of a desktop file being parsed
+enum DesktopSection {
+    Entry,
+    Action(String),
+    Other,
+}
+
+/// Parse the content of a desktop file, including the actions it lists
+fn parse_desktop_entry(content: &str, desktop_id: &str) -> Option<AppInfo> {
     let mut name = None;
     let mut exec = None;
-    let mut in_desktop_entry = false;
+    let mut icon = None;
+    let mut terminal = false;
+    let mut action_ids = Vec::new();
+    // Name and Exec of each [Desktop Action id] section
+    let mut action_fields: Vec<(String, Option<String>, Option<String>)> = Vec::new();
+    let mut section = DesktopSection::Other;
 
     for line in content.lines() {
         let line = line.trim();
@@ -258,28 +327,85 @@ fn parse_desktop_file(path: &Path) -> Option<AppInfo> {
             continue;
         }
 
-        // Track if we're in [Desktop Entry] section
+        // Track which section we're in
         if line.starts_with('[') && line.ends_with(']') {
-            in_desktop_entry = line == "[Desktop Entry]";
-            continue;
-        }
-
-        if !in_desktop_entry {
+            section = if line == "[Desktop Entry]" {
+                DesktopSection::Entry
+            } else if let Some(id) = line
+                .strip_prefix("[Desktop Action ")
+                .and_then(|rest| rest.strip_suffix(']'))
+            {
+                action_fields.push((id.to_string(), None, None));
+                DesktopSection::Action(id.to_string())
+            } else {
+                DesktopSection::Other
+            };
             continue;
         }
 
-        if line.starts_with("Name=") && name.is_none() {
-            name = Some(line[5..].to_string());
-        }
-        if line.starts_with("Exec=") {
-            exec = Some(line[5..].to_string());
+        match &section {
+            DesktopSection::Entry => {
+                if line.starts_with("Name=") && name.is_none() {
+                    name = Some(line[5..].to_string());
+                }
+                if line.starts_with("Exec=") {
+                    exec = Some(line[5..].to_string());
+                }
+                if let Some(value) = line.strip_prefix("Icon=") {
+                    icon = Some(value.trim().to_string());
+                }
+                if let Some(value) = line.strip_prefix("Terminal=") {
+                    terminal = value.trim() == "true";
+                }
+                if let Some(value) = line.strip_prefix("Actions=") {
+                    action_ids = value
+                        .split(';')
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+            }
+            DesktopSection::Action(id) => {
+                let Some((_, action_name, action_exec)) =
+                    action_fields.iter_mut().rev().find(|(a, _, _)| a == id)
+                else {
+                    continue;
+                };
+                if let Some(value) = line.strip_prefix("Name=")
+                    && action_name.is_none()
+                {
+                    *action_name = Some(value.to_string());
+                }
+                if let Some(value) = line.strip_prefix("Exec=") {
+                    *action_exec = Some(value.to_string());
+                }
+            }
+            DesktopSection::Other => {}
         }
     }
 
+    // Only the actions listed in the entry count, in the order listed
+    let actions = action_ids
+        .iter()
+        .filter_map(|id| {
+            let (_, name, exec) = action_fields.iter().find(|(a, _, _)| a == id)?;
+            Some(AppAction {
+                id: id.clone(),
+                name: name.clone()?,
+                path: exec.clone()?,
+            })
+        })
+        .collect();
+
     match (name, exec) {
         (Some(n), Some(e)) => Some(AppInfo {
             path: e, // Keep full exec command for launching
             name: n,
+            id: desktop_id.to_string(),
+            icon: icon.as_deref().and_then(resolve_icon),
+            terminal,
+            actions,
         }),
         _ => None,
     }
@@ -310,14 +436,14 @@ fn process_desktop_files(
         if blacklist.contains(filename_str.as_ref()) {
             continue;
         }
-        if results.iter().any(|r| r.name == *filename_str) {
+        if results.iter().any(|r| r.id == *filename_str) {
             continue;
         }
         if !desktop_file_has_mimetype(&path, mimetype) {
             continue;
         }
 
-        if let Some(app_info) = parse_desktop_file(&path) {
+        if let Some(app_info) = parse_desktop_file(&path, &filename_str) {
             results.push(app_info);
         }
     }
@@ -333,13 +459,13 @@ fn process_added_associations(
         if blacklist.contains(&desktop_name) {
             continue;
         }
-        if results.iter().any(|r| r.name == desktop_name) {
+        if results.iter().any(|r| r.id == desktop_name) {
             continue;
         }
 
         if let Some(desktop_path) = find_desktop_file(&desktop_name) {
-            if let Some(mut app_info) = parse_desktop_file(&desktop_path) {
-                // Store the desktop filename for deduplication
+            if let Some(mut app_info) = parse_desktop_file(&desktop_path, &desktop_name) {
+                // Show the desktop filename to tell apart apps with the same name
                 app_info.name = format!("{} ({})", app_info.name, desktop_name);
                 results.push(app_info);
             }
@@ -371,16 +497,198 @@ fn is_data_directory(dir: &Path) -> bool {
     dir.starts_with(&data_home) || data_dirs.iter().any(|d| dir.starts_with(d))
 }
 
+/// Determine the MIME type of a file from its content
+///
+/// Text files and other types `file_type` can't identify, e.g. without an
+/// extension, fall back to sniffing their magic bytes.
+fn file_mimetype(path: &Path) -> String {
+    FileType::try_from_file(path)
+        .ok()
+        .and_then(|ft| ft.media_types().first().copied())
+        .filter(|mimetype| *mimetype != "application/octet-stream")
+        .or_else(|| crate::sniff_file_type(path).map(|sniffed| sniffed.mime_type))
+        .unwrap_or("application/octet-stream")
+        .to_string()
+}
+
+/// Lines of a mimeapps.list section, `header` is `None` for the lines
+/// before the first section
+struct Section {
+    header: Option<String>,
+    lines: Vec<String>,
+}
+
+fn split_sections(content: &str) -> Vec<Section> {
+    let mut sections = vec![Section {
+        header: None,
+        lines: Vec::new(),
+    }];
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            sections.push(Section {
+                header: Some(trimmed.to_string()),
+                lines: Vec::new(),
+            });
+        } else if let Some(section) = sections.last_mut() {
+            section.lines.push(line.to_string());
+        }
+    }
+    sections
+}
+
+/// Find a section by header, appending it if missing
+fn section_mut<'a>(sections: &'a mut Vec<Section>, header: &str) -> &'a mut Section {
+    let index = match sections
+        .iter()
+        .position(|s| s.header.as_deref() == Some(header))
+    {
+        Some(index) => index,
+        None => {
+            // Keep a blank line between sections
+            if let Some(last) = sections.last_mut()
+                && last
+                    .lines
+                    .last()
+                    .is_some_and(|line| !line.trim().is_empty())
+            {
+                last.lines.push(String::new());
+            }
+            sections.push(Section {
+                header: Some(header.to_string()),
+                lines: Vec::new(),
+            });
+            sections.len() - 1
+        }
+    };
+    &mut sections[index]
+}
+
+fn entry_mimetype(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    line.split_once('=').map(|(key, _)| key.trim())
+}
+
+/// Apps listed for `mimetype` in a section
+fn entry_apps(section: &Section, mimetype: &str) -> Vec<String> {
+    section
+        .lines
+        .iter()
+        .filter(|line| entry_mimetype(line) == Some(mimetype))
+        .filter_map(|line| line.split_once('=').map(|(_, value)| value))
+        .flat_map(|value| value.split(';'))
+        .map(str::trim)
+        .filter(|app| !app.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Replace the entry of `mimetype` in a section, removing it if `apps` is empty
+fn set_entry(section: &mut Section, mimetype: &str, apps: &[String]) {
+    let position = section
+        .lines
+        .iter()
+        .position(|line| entry_mimetype(line) == Some(mimetype));
+    section
+        .lines
+        .retain(|line| entry_mimetype(line) != Some(mimetype));
+    if apps.is_empty() {
+        return;
+    }
+
+    let entry = format!("{}={};", mimetype, apps.join(";"));
+    let index = position.unwrap_or_else(|| {
+        // Append after the last entry, before the blank lines separating sections
+        section
+            .lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |i| i + 1)
+    });
+    section.lines.insert(index, entry);
+}
+
+/// Make `desktop_id` the default application of `mimetype` in the content of
+/// a mimeapps.list file
+///
+/// The app is also listed first in the added associations, and no longer in
+/// the removed ones. Other entries and comments are kept as they are.
+fn set_default_in_mimeapps(content: &str, mimetype: &str, desktop_id: &str) -> String {
+    let mut sections = split_sections(content);
+
+    let default = section_mut(&mut sections, "[Default Applications]");
+    set_entry(default, mimetype, &[desktop_id.to_string()]);
+
+    let added = section_mut(&mut sections, "[Added Associations]");
+    let mut apps = vec![desktop_id.to_string()];
+    apps.extend(
+        entry_apps(added, mimetype)
+            .into_iter()
+            .filter(|app| app != desktop_id),
+    );
+    set_entry(added, mimetype, &apps);
+
+    if let Some(removed) = sections
+        .iter_mut()
+        .find(|s| s.header.as_deref() == Some("[Removed Associations]"))
+    {
+        let apps: Vec<String> = entry_apps(removed, mimetype)
+            .into_iter()
+            .filter(|app| app != desktop_id)
+            .collect();
+        set_entry(removed, mimetype, &apps);
+    }
+
+    let mut output = String::new();
+    for section in sections {
+        if let Some(header) = section.header {
+            output.push_str(&header);
+            output.push('\n');
+        }
+        for line in section.lines {
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Set the default application for a file in the user's mimeapps.list
+///
+/// The desktop file ID of `app` becomes the default for the MIME type of the
+/// file, taking precedence over system-wide associations.
+pub fn set_default_app_for_file_linux(path: &Path, app: &AppInfo) -> std::io::Result<()> {
+    if app.id.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} has no desktop file", app.name),
+        ));
+    }
+    let mimetype = file_mimetype(path);
+    let mimeapps_path = get_xdg_config_home().join("mimeapps.list");
+    let content = match fs::read_to_string(&mimeapps_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    if let Some(parent) = mimeapps_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(
+        &mimeapps_path,
+        set_default_in_mimeapps(&content, &mimetype, &app.id),
+    )
+}
+
 /// Get applications for a file by looking up its MIME type on Linux
 ///
 /// This function implements the XDG MIME apps specification for finding applications
 /// associated with a file based on its MIME type.
 pub fn get_apps_for_file_linux(path: &Path) -> Vec<AppInfo> {
-    // Determine MIME type
-    let mimetype = FileType::try_from_file(path)
-        .ok()
-        .and_then(|ft| ft.media_types().first().map(|s| s.to_string()))
-        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let mimetype = file_mimetype(path);
 
     let mut results = Vec::new();
     let mut blacklist = HashSet::new();
@@ -550,6 +858,163 @@ application/pdf=xpdf.desktop;
         let _ = fs::remove_file(temp_file);
     }
 
+    #[test]
+    fn test_set_default_in_mimeapps() {
+        let content = r#"# Managed by hand
+[Default Applications]
+application/pdf=evince.desktop
+text/plain=gedit.desktop
+
+[Added Associations]
+application/pdf=okular.desktop;evince.desktop;
+
+[Removed Associations]
+application/pdf=xpdf.desktop;evince.desktop;
+"#;
+        let updated = set_default_in_mimeapps(content, "application/pdf", "evince.desktop");
+        assert_eq!(
+            updated,
+            r#"# Managed by hand
+[Default Applications]
+application/pdf=evince.desktop;
+text/plain=gedit.desktop
+
+[Added Associations]
+application/pdf=evince.desktop;okular.desktop;
+
+[Removed Associations]
+application/pdf=xpdf.desktop;
+"#
+        );
+
+        // Missing entries are appended to their section
+        let updated = set_default_in_mimeapps(content, "image/png", "gthumb.desktop");
+        assert_eq!(
+            updated,
+            r#"# Managed by hand
+[Default Applications]
+application/pdf=evince.desktop
+text/plain=gedit.desktop
+image/png=gthumb.desktop;
+
+[Added Associations]
+application/pdf=okular.desktop;evince.desktop;
+image/png=gthumb.desktop;
+
+[Removed Associations]
+application/pdf=xpdf.desktop;evince.desktop;
+"#
+        );
+
+        // Missing sections are created
+        let updated = set_default_in_mimeapps("", "image/png", "gthumb.desktop");
+        assert_eq!(
+            updated,
+            "[Default Applications]\nimage/png=gthumb.desktop;\n\n\
+             [Added Associations]\nimage/png=gthumb.desktop;\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_desktop_entry_actions() {
+        let content = r#"[Desktop Entry]
+Name=Firefox
+Exec=firefox %u
+Actions=new-window;new-private-window;missing-exec;
+
+[Desktop Action new-private-window]
+Name=New Private Window
+Name[de]=Neues privates Fenster
+Exec=firefox --private-window %u
+
+[Desktop Action new-window]
+Name=New Window
+Exec=firefox --new-window %u
+
+[Desktop Action missing-exec]
+Name=Broken
+
+[Desktop Action unlisted]
+Name=Unlisted
+Exec=firefox --unlisted
+"#;
+        let app = parse_desktop_entry(content, "firefox.desktop").unwrap();
+        assert_eq!(app.name, "Firefox");
+        assert_eq!(app.path, "firefox %u");
+        assert_eq!(app.id, "firefox.desktop");
+        assert!(!app.terminal);
+        assert_eq!(
+            app.actions,
+            vec![
+                AppAction {
+                    id: "new-window".to_string(),
+                    name: "New Window".to_string(),
+                    path: "firefox --new-window %u".to_string(),
+                },
+                AppAction {
+                    id: "new-private-window".to_string(),
+                    name: "New Private Window".to_string(),
+                    path: "firefox --private-window %u".to_string(),
+                },
+            ]
+        );
+
+        // Action sections don't override the entry
+        let app = parse_desktop_entry(
+            "[Desktop Action a]\nName=A\nExec=a\n[Desktop Entry]\nName=App\nExec=app\n",
+            "app.desktop",
+        )
+        .unwrap();
+        assert_eq!(app.name, "App");
+        assert!(app.actions.is_empty());
+
+        let app = parse_desktop_entry(
+            "[Desktop Entry]\nName=htop\nExec=htop\nTerminal=true\n",
+            "htop.desktop",
+        )
+        .unwrap();
+        assert!(app.terminal);
+    }
+
+    #[test]
+    fn test_find_icon_in() {
+        let temp_dir = std::env::temp_dir().join("test_find_icon_in");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let icons = temp_dir.join("icons");
+        let pixmaps = temp_dir.join("pixmaps");
+        for dir in [
+            "hicolor/32x32/apps",
+            "hicolor/48x48/apps",
+            "hicolor/scalable/apps",
+        ] {
+            fs::create_dir_all(icons.join(dir)).unwrap();
+        }
+        fs::create_dir_all(&pixmaps).unwrap();
+        fs::write(icons.join("hicolor/32x32/apps/viewer.png"), "").unwrap();
+        fs::write(icons.join("hicolor/48x48/apps/viewer.png"), "").unwrap();
+        fs::write(icons.join("hicolor/scalable/apps/editor.svg"), "").unwrap();
+        fs::write(pixmaps.join("legacy.png"), "").unwrap();
+
+        let icon_dirs = [icons.clone()];
+        let pixmap_dirs = [pixmaps.clone()];
+        assert_eq!(
+            find_icon_in("viewer", &icon_dirs, &pixmap_dirs),
+            Some(icons.join("hicolor/48x48/apps/viewer.png"))
+        );
+        assert_eq!(
+            find_icon_in("editor", &icon_dirs, &pixmap_dirs),
+            Some(icons.join("hicolor/scalable/apps/editor.svg"))
+        );
+        assert_eq!(
+            find_icon_in("legacy.png", &icon_dirs, &pixmap_dirs),
+            Some(pixmaps.join("legacy.png"))
+        );
+        assert_eq!(find_icon_in("missing", &icon_dirs, &pixmap_dirs), None);
+
+        // Clean up
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
     #[test]
     fn test_parse_mimeapps_file_multiple_apps() {
         let mimeapps_content = r#"[Added Associations]
@@ -1,9 +1,29 @@
-use super::AppInfo;
+use super::{AppIcon, AppInfo};
+use objc2::AllocAnyThread;
 use objc2::rc::Retained;
-use objc2_app_kit::NSWorkspace;
-use objc2_foundation::{NSArray, NSFileManager, NSString, NSURL};
+use objc2_app_kit::{NSBitmapImageRep, NSDeviceRGBColorSpace, NSGraphicsContext, NSWorkspace};
+use objc2_foundation::{
+    NSArray, NSBundle, NSError, NSFileManager, NSPoint, NSRect, NSSize, NSString, NSURL,
+};
+use std::ffi::c_void;
 use std::path::Path;
 
+/// Width and height of the rendered application icons
+const ICON_SIZE: isize = 32;
+
+/// `kLSRolesAll`, handle the content type in every role
+const LS_ROLES_ALL: u32 = 0xFFFF_FFFF;
+
+#[link(name = "CoreServices", kind = "framework")]
+unsafe extern "C" {
+    // CFStringRef arguments, NSString is toll-free bridged
+    fn LSSetDefaultRoleHandlerForContentType(
+        content_type: *const c_void,
+        role: u32,
+        handler_bundle_id: *const c_void,
+    ) -> i32;
+}
+
 pub fn get_apps_for_file_macos(path: &Path) -> Vec<AppInfo> {
     let Some(path_str) = path.to_str() else {
         return Vec::new();
@@ -22,11 +42,110 @@ pub fn get_apps_for_file_macos(path: &Path) -> Vec<AppInfo> {
             .iter()
             .filter_map(|url| {
                 let path = url.path()?;
+                let id = NSBundle::bundleWithURL(&url)
+                    .and_then(|bundle| bundle.bundleIdentifier())
+                    .map(|id| id.to_string())
+                    .unwrap_or_default();
                 Some(AppInfo {
                     path: path.to_string(),
                     name: file_manager.displayNameAtPath(&path).to_string(),
+                    id,
+                    icon: app_icon(&workspace, &path),
+                    terminal: false,
+                    actions: Vec::new(),
                 })
             })
             .collect()
     }
 }
+
+/// Render the Finder icon of an application bundle
+unsafe fn app_icon(workspace: &NSWorkspace, path: &NSString) -> Option<AppIcon> {
+    unsafe {
+        let image = workspace.iconForFile(path);
+        let rep = NSBitmapImageRep::initWithBitmapDataPlanes_pixelsWide_pixelsHigh_bitsPerSample_samplesPerPixel_hasAlpha_isPlanar_colorSpaceName_bytesPerRow_bitsPerPixel(
+            NSBitmapImageRep::alloc(),
+            std::ptr::null_mut(),
+            ICON_SIZE,
+            ICON_SIZE,
+            8,
+            4,
+            true,
+            false,
+            NSDeviceRGBColorSpace,
+            ICON_SIZE * 4,
+            32,
+        )?;
+        let context = NSGraphicsContext::graphicsContextWithBitmapImageRep(&rep)?;
+        NSGraphicsContext::saveGraphicsState_class();
+        NSGraphicsContext::setCurrentContext(Some(&context));
+        let size = ICON_SIZE as f64;
+        image.drawInRect(NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(size, size)));
+        NSGraphicsContext::restoreGraphicsState_class();
+
+        let data = rep.bitmapData();
+        if data.is_null() {
+            return None;
+        }
+        let mut pixels =
+            std::slice::from_raw_parts(data, (ICON_SIZE * ICON_SIZE * 4) as usize).to_vec();
+        // Bitmaps have premultiplied alpha
+        for pixel in pixels.chunks_exact_mut(4) {
+            let alpha = pixel[3] as u32;
+            if alpha > 0 && alpha < 255 {
+                for channel in &mut pixel[..3] {
+                    *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+                }
+            }
+        }
+        Some(AppIcon::Rgba {
+            width: ICON_SIZE as u32,
+            height: ICON_SIZE as u32,
+            pixels,
+        })
+    }
+}
+
+/// Register `app` with LaunchServices as the default handler of the file's
+/// uniform type identifier
+pub fn set_default_app_for_file_macos(path: &Path, app: &AppInfo) -> std::io::Result<()> {
+    let Some(path_str) = path.to_str() else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Path is not valid UTF-8",
+        ));
+    };
+    if app.id.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} has no bundle identifier", app.name),
+        ));
+    }
+    unsafe {
+        let workspace = NSWorkspace::sharedWorkspace();
+        let content_type: Result<Retained<NSString>, Retained<NSError>> = objc2::msg_send![
+            &workspace,
+            typeOfFile: &*NSString::from_str(path_str),
+            error: _
+        ];
+        let content_type = content_type.map_err(|e| {
+            std::io::Error::other(format!(
+                "Failed to determine the file type: {}",
+                e.localizedDescription()
+            ))
+        })?;
+        let bundle_id = NSString::from_str(&app.id);
+
+        let status = LSSetDefaultRoleHandlerForContentType(
+            Retained::as_ptr(&content_type).cast(),
+            LS_ROLES_ALL,
+            Retained::as_ptr(&bundle_id).cast(),
+        );
+        if status != 0 {
+            return Err(std::io::Error::other(format!(
+                "LaunchServices failed to set the default application (error {status})"
+            )));
+        }
+    }
+    Ok(())
+}
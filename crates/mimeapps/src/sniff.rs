@@ -0,0 +1,244 @@
+//! Content-based detection of file types from their magic bytes.
+//!
+//! Used as a fallback for files the platform can't type, e.g. files without
+//! an extension like `README` or downloaded blobs.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a file to detect its type
+const SNIFF_LEN: usize = 8192;
+
+/// A file type detected from the content of a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SniffedType {
+    /// The MIME type, e.g. `image/png`.
+    pub mime_type: &'static str,
+    /// The usual extension of files of this type, without the leading dot.
+    pub extension: &'static str,
+}
+
+const fn sniffed(mime_type: &'static str, extension: &'static str) -> Option<SniffedType> {
+    Some(SniffedType {
+        mime_type,
+        extension,
+    })
+}
+
+/// Magic bytes at the start of a file, checked in order
+const SIGNATURES: &[(&[u8], &str, &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png", "png"),
+    (b"\xff\xd8\xff", "image/jpeg", "jpg"),
+    (b"GIF87a", "image/gif", "gif"),
+    (b"GIF89a", "image/gif", "gif"),
+    (b"II*\0", "image/tiff", "tif"),
+    (b"MM\0*", "image/tiff", "tif"),
+    (b"%PDF-", "application/pdf", "pdf"),
+    (b"\x1f\x8b", "application/gzip", "gz"),
+    (b"BZh", "application/x-bzip2", "bz2"),
+    (b"\xfd7zXZ\0", "application/x-xz", "xz"),
+    (b"\x28\xb5\x2f\xfd", "application/zstd", "zst"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed", "7z"),
+    (b"Rar!\x1a\x07", "application/vnd.rar", "rar"),
+    (b"\x7fELF", "application/x-executable", "elf"),
+    (b"\xfe\xed\xfa\xce", "application/x-mach-binary", "dylib"),
+    (b"\xfe\xed\xfa\xcf", "application/x-mach-binary", "dylib"),
+    (b"\xce\xfa\xed\xfe", "application/x-mach-binary", "dylib"),
+    (b"\xcf\xfa\xed\xfe", "application/x-mach-binary", "dylib"),
+    (b"\0asm", "application/wasm", "wasm"),
+    (b"OggS", "audio/ogg", "ogg"),
+    (b"fLaC", "audio/flac", "flac"),
+    (b"ID3", "audio/mpeg", "mp3"),
+    (b"SQLite format 3\0", "application/vnd.sqlite3", "sqlite"),
+];
+
+/// Detect the type of the file at `path` from its first bytes.
+///
+/// Returns `None` for empty or unreadable files, and for binary content of
+/// an unknown type.
+pub fn sniff_file_type(path: &Path) -> Option<SniffedType> {
+    let file = File::open(path).ok()?;
+    let mut bytes = Vec::with_capacity(SNIFF_LEN);
+    file.take(SNIFF_LEN as u64).read_to_end(&mut bytes).ok()?;
+    sniff_bytes(&bytes)
+}
+
+/// Detect the type of a file from its first bytes, see [`sniff_file_type`].
+pub fn sniff_bytes(bytes: &[u8]) -> Option<SniffedType> {
+    if bytes.is_empty() {
+        return None;
+    }
+    if let Some((_, mime_type, extension)) = SIGNATURES
+        .iter()
+        .find(|(magic, _, _)| bytes.starts_with(magic))
+    {
+        return sniffed(mime_type, extension);
+    }
+    sniff_container(bytes).or_else(|| sniff_text(bytes))
+}
+
+/// Formats identified by more than a fixed prefix
+fn sniff_container(bytes: &[u8]) -> Option<SniffedType> {
+    if bytes.starts_with(b"PK\x03\x04") {
+        // EPUB requires an uncompressed `mimetype` file first in the archive
+        return if bytes.get(30..58) == Some(b"mimetypeapplication/epub+zip") {
+            sniffed("application/epub+zip", "epub")
+        } else {
+            sniffed("application/zip", "zip")
+        };
+    }
+    if bytes.starts_with(b"RIFF") {
+        return match bytes.get(8..12)? {
+            b"WEBP" => sniffed("image/webp", "webp"),
+            b"WAVE" => sniffed("audio/wav", "wav"),
+            b"AVI " => sniffed("video/x-msvideo", "avi"),
+            _ => None,
+        };
+    }
+    if bytes.get(4..8) == Some(b"ftyp") {
+        return match bytes.get(8..12)? {
+            b"heic" | b"heix" | b"mif1" => sniffed("image/heic", "heic"),
+            b"avif" => sniffed("image/avif", "avif"),
+            b"qt  " => sniffed("video/quicktime", "mov"),
+            b"M4A " => sniffed("audio/mp4", "m4a"),
+            _ => sniffed("video/mp4", "mp4"),
+        };
+    }
+    if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+        let header = &bytes[..bytes.len().min(64)];
+        return if header.windows(4).any(|w| w == b"webm") {
+            sniffed("video/webm", "webm")
+        } else {
+            sniffed("video/x-matroska", "mkv")
+        };
+    }
+    if bytes.get(257..262) == Some(b"ustar") {
+        return sniffed("application/x-tar", "tar");
+    }
+    // BMP has a short magic, also check the reserved header bytes are zero
+    if bytes.starts_with(b"BM") && bytes.get(6..10) == Some(&[0, 0, 0, 0]) {
+        return sniffed("image/bmp", "bmp");
+    }
+    if bytes.starts_with(b"MZ") {
+        return sniffed("application/vnd.microsoft.portable-executable", "exe");
+    }
+    // MPEG audio frame sync without an ID3 tag
+    if bytes.len() >= 2 && bytes[0] == 0xff && bytes[1] & 0xe0 == 0xe0 {
+        return sniffed("audio/mpeg", "mp3");
+    }
+    None
+}
+
+/// Markup, scripts and plain text
+fn sniff_text(bytes: &[u8]) -> Option<SniffedType> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        // The read may have cut a character in half
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&bytes[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+
+    if let Some(shebang) = text.strip_prefix("#!") {
+        let interpreter = shebang.lines().next().unwrap_or_default();
+        let interpreter = interpreter
+            .split_whitespace()
+            .find(|word| !word.ends_with("/env") && !word.starts_with('-'))
+            .and_then(|word| word.rsplit('/').next())
+            .unwrap_or_default();
+        return match interpreter {
+            i if i.starts_with("python") => sniffed("text/x-python", "py"),
+            "sh" | "bash" | "zsh" | "dash" | "ksh" => sniffed("application/x-shellscript", "sh"),
+            i if i.starts_with("perl") => sniffed("text/x-perl", "pl"),
+            i if i.starts_with("ruby") => sniffed("text/x-ruby", "rb"),
+            "node" | "deno" | "bun" => sniffed("text/javascript", "js"),
+            _ => sniffed("text/plain", "txt"),
+        };
+    }
+
+    let start = text.trim_start_matches('\u{feff}').trim_start();
+    let head: String = start.chars().take(256).collect::<String>().to_lowercase();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        return sniffed("text/html", "html");
+    }
+    if head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg")) {
+        return sniffed("image/svg+xml", "svg");
+    }
+    if head.starts_with("<?xml") {
+        return sniffed("application/xml", "xml");
+    }
+    sniffed("text/plain", "txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sniffed_extension(bytes: &[u8]) -> Option<&'static str> {
+        sniff_bytes(bytes).map(|t| t.extension)
+    }
+
+    #[test]
+    fn test_sniff_magic_bytes() {
+        assert_eq!(
+            sniff_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some(SniffedType {
+                mime_type: "image/png",
+                extension: "png"
+            })
+        );
+        assert_eq!(sniffed_extension(b"%PDF-1.7\n"), Some("pdf"));
+        assert_eq!(sniffed_extension(b"\x7fELF\x02\x01\x01"), Some("elf"));
+        assert_eq!(sniffed_extension(b"RIFF\0\0\0\0WEBPVP8 "), Some("webp"));
+        assert_eq!(sniffed_extension(b"\0\0\0\x18ftypmp42"), Some("mp4"));
+        assert_eq!(sniffed_extension(b"\0\0\0\x18ftypavif"), Some("avif"));
+        assert_eq!(sniffed_extension(b"PK\x03\x04\x14\0\0\0"), Some("zip"));
+
+        let mut epub = b"PK\x03\x04".to_vec();
+        epub.resize(30, 0);
+        epub.extend_from_slice(b"mimetypeapplication/epub+zip");
+        assert_eq!(sniffed_extension(&epub), Some("epub"));
+
+        let mut tar = vec![b'a'; 257];
+        tar.extend_from_slice(b"ustar\0");
+        tar.push(0);
+        assert_eq!(sniffed_extension(&tar), Some("tar"));
+    }
+
+    #[test]
+    fn test_sniff_text() {
+        assert_eq!(sniffed_extension(b"# Project\n\nSome notes"), Some("txt"));
+        assert_eq!(
+            sniffed_extension(b"#!/usr/bin/env python3\nprint()"),
+            Some("py")
+        );
+        assert_eq!(sniffed_extension(b"#!/bin/bash -e\necho"), Some("sh"));
+        assert_eq!(
+            sniffed_extension(b"\xef\xbb\xbf  <!DOCTYPE html><html>"),
+            Some("html")
+        );
+        assert_eq!(
+            sniffed_extension(b"<?xml version=\"1.0\"?>\n<svg xmlns=\"\">"),
+            Some("svg")
+        );
+        assert_eq!(
+            sniffed_extension(b"<?xml version=\"1.0\"?><a/>"),
+            Some("xml")
+        );
+        // A multi-byte character cut at the end of the read
+        assert_eq!(
+            sniffed_extension("caf\u{e9}".as_bytes()[..4].as_ref()),
+            Some("txt")
+        );
+    }
+
+    #[test]
+    fn test_sniff_unknown() {
+        assert_eq!(sniff_bytes(b""), None);
+        assert_eq!(sniff_bytes(b"\x01\x02\0\x03"), None);
+        assert_eq!(sniff_bytes(b"\xc3\x28 invalid utf-8"), None);
+    }
+}
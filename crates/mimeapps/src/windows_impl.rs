@@ -1,8 +1,21 @@
 use super::AppInfo;
 use std::path::Path;
+use std::process::Command;
 
 pub fn get_apps_for_file_windows(_path: &Path) -> Vec<AppInfo> {
     // TODO: Implement Windows application lookup
     // This could use Windows Registry to find file associations
     Vec::new()
 }
+
+/// Let the user choose the default application in the system "Open with" dialog
+///
+/// Windows protects the user choice of default applications from being set
+/// programmatically, so `app` is only a suggestion the dialog can't preselect.
+pub fn set_default_app_for_file_windows(path: &Path, _app: &AppInfo) -> std::io::Result<()> {
+    Command::new("rundll32.exe")
+        .arg("shell32.dll,OpenAs_RunDLL")
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+}
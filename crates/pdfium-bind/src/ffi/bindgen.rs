@@ -8,19 +8,38 @@ mod bindgen_incl {
 
 // symbols shared by both dynamic and static builds
 pub use bindgen_incl::{
-    FPDFBitmap_BGRA, FPDF_ANNOT, FPDF_DOCUMENT, FPDF_ERR_FILE, FPDF_ERR_FORMAT, FPDF_ERR_PAGE,
-    FPDF_ERR_PASSWORD, FPDF_ERR_SECURITY, FPDF_ERR_SUCCESS, FPDF_ERR_UNKNOWN, FPDF_LCD_TEXT,
-    FPDF_PRINTING,
+    FPDFBitmap_BGR, FPDFBitmap_BGRA, FPDFBitmap_BGRx, FPDFBitmap_Gray, FORMTYPE_ACRO_FORM,
+    FORMTYPE_XFA_FOREGROUND, FORMTYPE_XFA_FULL, FPDF_ACTION, FPDF_ANNOT, FPDF_ANNOTATION,
+    FPDF_ANNOT_WIDGET, FPDF_BITMAP, FPDF_BOOKMARK, FPDF_DEST, FPDF_DOCUMENT, FPDF_ERR_FILE,
+    FPDF_ERR_FORMAT, FPDF_ERR_PAGE, FPDF_ERR_PASSWORD, FPDF_ERR_SECURITY, FPDF_ERR_SUCCESS,
+    FPDF_ERR_UNKNOWN, FPDF_FORMFIELD_CHECKBOX, FPDF_FORMFIELD_COMBOBOX, FPDF_FORMFIELD_LISTBOX,
+    FPDF_FORMFIELD_PUSHBUTTON, FPDF_FORMFIELD_RADIOBUTTON, FPDF_FORMFIELD_SIGNATURE,
+    FPDF_FORMFIELD_TEXTFIELD, FPDF_FORMFILLINFO, FPDF_FORMHANDLE, FPDF_LCD_TEXT, FPDF_LINK,
+    FPDF_MATCHCASE, FPDF_MATCHWHOLEWORD, FPDF_PAGE, FPDF_PAGEOBJECT, FPDF_PAGEOBJ_FORM,
+    FPDF_PAGEOBJ_IMAGE, FPDF_PRINTING, FPDF_TEXTPAGE, FS_MATRIX, FS_RECTF, PDFACTION_GOTO,
+    PDFACTION_URI,
 };
 
 #[cfg(not(feature = "static"))]
-pub use bindgen_incl::{FPDF_BITMAP, FPDF_BYTESTRING, FPDF_PAGE};
+pub use bindgen_incl::{FPDF_BOOL, FPDF_BYTESTRING, FPDF_SCHHANDLE, FPDF_WCHAR, FPDF_WIDESTRING};
 
 // in static build, reuse bindgen symbols directly
 #[cfg(feature = "static")]
 pub use bindgen_incl::{
-    FPDFBitmap_CreateEx, FPDFBitmap_Destroy, FPDFBitmap_FillRect, FPDF_CloseDocument,
-    FPDF_ClosePage, FPDF_GetFileVersion, FPDF_GetLastError, FPDF_GetMetaText, FPDF_GetPageCount,
-    FPDF_GetPageHeightF, FPDF_GetPageWidthF, FPDF_InitLibrary, FPDF_LoadDocument, FPDF_LoadPage,
-    FPDF_RenderPageBitmap,
+    FPDFAction_GetDest, FPDFAction_GetType, FPDFAction_GetURIPath, FPDFAnnot_GetFormFieldName,
+    FPDFAnnot_GetFormFieldType, FPDFAnnot_GetSubtype, FPDFBitmap_CreateEx, FPDFBitmap_Destroy,
+    FPDFBitmap_FillRect, FPDFBitmap_GetBuffer, FPDFBitmap_GetFormat, FPDFBitmap_GetHeight,
+    FPDFBitmap_GetStride, FPDFBitmap_GetWidth, FPDFBookmark_GetAction, FPDFBookmark_GetDest,
+    FPDFBookmark_GetFirstChild, FPDFBookmark_GetNextSibling, FPDFBookmark_GetTitle,
+    FPDFDOC_ExitFormFillEnvironment, FPDFDOC_InitFormFillEnvironment, FPDFDest_GetDestPageIndex,
+    FPDFFormObj_CountObjects, FPDFFormObj_GetObject, FPDFImageObj_GetBitmap, FPDFLink_Enumerate,
+    FPDFLink_GetAction, FPDFLink_GetAnnotRect, FPDFLink_GetDest, FPDFPageObj_GetType,
+    FPDFPage_CloseAnnot, FPDFPage_CountObjects, FPDFPage_GetAnnot, FPDFPage_GetAnnotCount,
+    FPDFPage_GetObject, FPDFPage_GetRotation, FPDFText_ClosePage, FPDFText_CountChars,
+    FPDFText_CountRects, FPDFText_FindClose, FPDFText_FindNext, FPDFText_FindStart,
+    FPDFText_GetCharBox, FPDFText_GetRect, FPDFText_GetSchCount, FPDFText_GetSchResultIndex,
+    FPDFText_GetUnicode, FPDFText_LoadPage, FPDF_CloseDocument, FPDF_ClosePage,
+    FPDF_GetFileVersion, FPDF_GetFormType, FPDF_GetLastError, FPDF_GetMetaText, FPDF_GetPageCount,
+    FPDF_GetPageHeightF, FPDF_GetPageLabel, FPDF_GetPageWidthF, FPDF_InitLibrary,
+    FPDF_LoadDocument, FPDF_LoadPage, FPDF_RenderPageBitmap, FPDF_RenderPageBitmapWithMatrix,
 };
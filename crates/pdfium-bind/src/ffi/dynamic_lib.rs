@@ -106,6 +106,8 @@ dylib_cfn!(FPDF_GetPageWidthF(page: FPDF_PAGE) -> f32);
 
 dylib_cfn!(FPDF_GetPageHeightF(page: FPDF_PAGE) -> f32);
 
+dylib_cfn!(FPDFPage_GetRotation(page: FPDF_PAGE) -> std::os::raw::c_int);
+
 dylib_cfn!(FPDF_ClosePage(page: FPDF_PAGE));
 
 dylib_cfn!(FPDFBitmap_CreateEx(
@@ -136,7 +138,174 @@ dylib_cfn!(FPDF_RenderPageBitmap(
     flags: std::os::raw::c_int,
 ));
 
+dylib_cfn!(FPDF_RenderPageBitmapWithMatrix(
+    bitmap: FPDF_BITMAP,
+    page: FPDF_PAGE,
+    matrix: *const FS_MATRIX,
+    clipping: *const FS_RECTF,
+    flags: std::os::raw::c_int,
+));
+
 dylib_cfn!(FPDFBitmap_Destroy(bitmap: FPDF_BITMAP));
 
 dylib_cfn!(FPDF_CloseDocument(document: FPDF_DOCUMENT));
+
+dylib_cfn!(FPDFText_LoadPage(page: FPDF_PAGE) -> FPDF_TEXTPAGE);
+
+dylib_cfn!(FPDFText_ClosePage(text_page: FPDF_TEXTPAGE));
+
+dylib_cfn!(FPDFText_CountChars(text_page: FPDF_TEXTPAGE) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFText_GetUnicode(
+    text_page: FPDF_TEXTPAGE,
+    index: std::os::raw::c_int,
+) -> std::os::raw::c_uint);
+
+dylib_cfn!(FPDFText_GetCharBox(
+    text_page: FPDF_TEXTPAGE,
+    index: std::os::raw::c_int,
+    left: *mut f64,
+    right: *mut f64,
+    bottom: *mut f64,
+    top: *mut f64,
+) -> FPDF_BOOL);
 dylib_cfn!(FPDF_GetLastError() -> std::os::raw::c_ulong);
+
+dylib_cfn!(FPDFText_FindStart(
+    text_page: FPDF_TEXTPAGE,
+    findwhat: FPDF_WIDESTRING,
+    flags: std::os::raw::c_ulong,
+    start_index: std::os::raw::c_int,
+) -> FPDF_SCHHANDLE);
+
+dylib_cfn!(FPDFText_FindNext(handle: FPDF_SCHHANDLE) -> FPDF_BOOL);
+
+dylib_cfn!(FPDFText_GetSchResultIndex(handle: FPDF_SCHHANDLE) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFText_GetSchCount(handle: FPDF_SCHHANDLE) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFText_FindClose(handle: FPDF_SCHHANDLE));
+
+dylib_cfn!(FPDFText_CountRects(
+    text_page: FPDF_TEXTPAGE,
+    start_index: std::os::raw::c_int,
+    count: std::os::raw::c_int,
+) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFText_GetRect(
+    text_page: FPDF_TEXTPAGE,
+    rect_index: std::os::raw::c_int,
+    left: *mut f64,
+    top: *mut f64,
+    right: *mut f64,
+    bottom: *mut f64,
+) -> FPDF_BOOL);
+
+dylib_cfn!(FPDFBookmark_GetFirstChild(
+    document: FPDF_DOCUMENT,
+    bookmark: FPDF_BOOKMARK,
+) -> FPDF_BOOKMARK);
+
+dylib_cfn!(FPDFBookmark_GetNextSibling(
+    document: FPDF_DOCUMENT,
+    bookmark: FPDF_BOOKMARK,
+) -> FPDF_BOOKMARK);
+
+dylib_cfn!(FPDFBookmark_GetTitle(
+    bookmark: FPDF_BOOKMARK,
+    buffer: *mut std::os::raw::c_void,
+    buflen: std::os::raw::c_ulong,
+) -> std::os::raw::c_ulong);
+
+dylib_cfn!(FPDFBookmark_GetDest(document: FPDF_DOCUMENT, bookmark: FPDF_BOOKMARK) -> FPDF_DEST);
+
+dylib_cfn!(FPDFBookmark_GetAction(bookmark: FPDF_BOOKMARK) -> FPDF_ACTION);
+
+dylib_cfn!(FPDFAction_GetDest(document: FPDF_DOCUMENT, action: FPDF_ACTION) -> FPDF_DEST);
+
+dylib_cfn!(FPDFDest_GetDestPageIndex(
+    document: FPDF_DOCUMENT,
+    dest: FPDF_DEST,
+) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDF_GetPageLabel(
+    document: FPDF_DOCUMENT,
+    page_index: std::os::raw::c_int,
+    buffer: *mut std::os::raw::c_void,
+    buflen: std::os::raw::c_ulong,
+) -> std::os::raw::c_ulong);
+
+dylib_cfn!(FPDFLink_Enumerate(
+    page: FPDF_PAGE,
+    start_pos: *mut std::os::raw::c_int,
+    link_annot: *mut FPDF_LINK,
+) -> FPDF_BOOL);
+
+dylib_cfn!(FPDFLink_GetDest(document: FPDF_DOCUMENT, link: FPDF_LINK) -> FPDF_DEST);
+
+dylib_cfn!(FPDFLink_GetAction(link: FPDF_LINK) -> FPDF_ACTION);
+
+dylib_cfn!(FPDFLink_GetAnnotRect(link_annot: FPDF_LINK, rect: *mut FS_RECTF) -> FPDF_BOOL);
+
+dylib_cfn!(FPDFAction_GetType(action: FPDF_ACTION) -> std::os::raw::c_ulong);
+
+dylib_cfn!(FPDFAction_GetURIPath(
+    document: FPDF_DOCUMENT,
+    action: FPDF_ACTION,
+    buffer: *mut std::os::raw::c_void,
+    buflen: std::os::raw::c_ulong,
+) -> std::os::raw::c_ulong);
+
+dylib_cfn!(FPDF_GetFormType(document: FPDF_DOCUMENT) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFDOC_InitFormFillEnvironment(
+    document: FPDF_DOCUMENT,
+    form_info: *mut FPDF_FORMFILLINFO,
+) -> FPDF_FORMHANDLE);
+
+dylib_cfn!(FPDFDOC_ExitFormFillEnvironment(form: FPDF_FORMHANDLE));
+
+dylib_cfn!(FPDFPage_GetAnnotCount(page: FPDF_PAGE) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFPage_GetAnnot(page: FPDF_PAGE, index: std::os::raw::c_int) -> FPDF_ANNOTATION);
+
+dylib_cfn!(FPDFPage_CloseAnnot(annot: FPDF_ANNOTATION));
+
+dylib_cfn!(FPDFAnnot_GetSubtype(annot: FPDF_ANNOTATION) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFAnnot_GetFormFieldName(
+    form: FPDF_FORMHANDLE,
+    annot: FPDF_ANNOTATION,
+    buffer: *mut FPDF_WCHAR,
+    buflen: std::os::raw::c_ulong,
+) -> std::os::raw::c_ulong);
+
+dylib_cfn!(FPDFAnnot_GetFormFieldType(
+    form: FPDF_FORMHANDLE,
+    annot: FPDF_ANNOTATION,
+) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFPage_CountObjects(page: FPDF_PAGE) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFPage_GetObject(page: FPDF_PAGE, index: std::os::raw::c_int) -> FPDF_PAGEOBJECT);
+
+dylib_cfn!(FPDFPageObj_GetType(page_object: FPDF_PAGEOBJECT) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFFormObj_CountObjects(form_object: FPDF_PAGEOBJECT) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFFormObj_GetObject(
+    form_object: FPDF_PAGEOBJECT,
+    index: std::os::raw::c_ulong,
+) -> FPDF_PAGEOBJECT);
+
+dylib_cfn!(FPDFImageObj_GetBitmap(image_object: FPDF_PAGEOBJECT) -> FPDF_BITMAP);
+
+dylib_cfn!(FPDFBitmap_GetFormat(bitmap: FPDF_BITMAP) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFBitmap_GetWidth(bitmap: FPDF_BITMAP) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFBitmap_GetHeight(bitmap: FPDF_BITMAP) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFBitmap_GetStride(bitmap: FPDF_BITMAP) -> std::os::raw::c_int);
+
+dylib_cfn!(FPDFBitmap_GetBuffer(bitmap: FPDF_BITMAP) -> *mut std::os::raw::c_void);
@@ -8,6 +8,9 @@ use std::path::Path;
 use std::sync::Once;
 
 mod ffi;
+mod worker;
+
+pub use worker::{RenderKind, RenderRequest, RenderResponse, RenderWorker, RenderedBitmap};
 
 static PDFIUM_INIT: Once = Once::new();
 
@@ -42,6 +45,153 @@ fn fpdf_wstr_to_string(fpdf_wstr_bytes: *mut u8, len_bytes: usize) -> Option<Str
     String::from_utf16(actual_slice).ok()
 }
 
+/// Read a UTF-16LE string from a PDFium getter taking a buffer and its
+/// length in bytes, and returning the length of the string in bytes
+fn read_wstr(
+    get: impl Fn(*mut c_void, std::os::raw::c_ulong) -> std::os::raw::c_ulong,
+) -> Option<String> {
+    let len_bytes = get(std::ptr::null_mut(), 0) as usize;
+    if len_bytes <= 2 {
+        return None;
+    }
+    let mut buffer = vec![0u8; len_bytes];
+    let len_bytes = get(
+        buffer.as_mut_ptr() as *mut c_void,
+        buffer.len() as std::os::raw::c_ulong,
+    ) as usize;
+    if len_bytes > buffer.len() {
+        return None;
+    }
+    fpdf_wstr_to_string(buffer.as_mut_ptr(), len_bytes)
+}
+
+const RENDER_FLAGS: i32 = (ffi::FPDF_LCD_TEXT | ffi::FPDF_PRINTING | ffi::FPDF_ANNOT) as i32;
+
+/// Create a BGRA bitmap filled with `background`, draw on it with `render`
+/// and return its RGBA pixels
+fn render_bitmap(
+    width: i32,
+    height: i32,
+    background: PdfBackground,
+    render: impl FnOnce(ffi::FPDF_BITMAP),
+) -> Result<Vec<u8>, String> {
+    if width <= 0 || height <= 0 {
+        return Err(format!(
+            "Invalid page dimensions: width={} height={}",
+            width, height
+        ));
+    }
+
+    let stride = width * 4;
+    let buffer_size = stride as usize * height as usize;
+    let mut pixel_data = Vec::new();
+    pixel_data
+        .try_reserve_exact(buffer_size)
+        .map_err(|_| "Failed to allocate bitmap buffer".to_string())?;
+    pixel_data.resize(buffer_size, 0u8);
+
+    let bitmap = unsafe {
+        ffi::FPDFBitmap_CreateEx(
+            width,
+            height,
+            ffi::FPDFBitmap_BGRA as i32,
+            pixel_data.as_mut_ptr() as *mut c_void,
+            stride,
+        )
+    };
+    if bitmap.is_null() {
+        return Err("Failed to create bitmap".to_string());
+    }
+
+    unsafe {
+        ffi::FPDFBitmap_FillRect(bitmap, 0, 0, width, height, background.argb());
+    }
+    render(bitmap);
+    unsafe {
+        ffi::FPDFBitmap_Destroy(bitmap);
+    }
+
+    // Pdfium outputs BGRA, but we need RGBA for egui.
+    // Swap B (index 0) and R (index 2) for each pixel.
+    for pixel in pixel_data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    Ok(pixel_data)
+}
+
+/// Add the images among `objects` to `images`, descending into form objects
+fn collect_images(objects: &[ffi::FPDF_PAGEOBJECT], depth: usize, images: &mut Vec<PdfImage>) {
+    for &object in objects.iter().filter(|object| !object.is_null()) {
+        match unsafe { ffi::FPDFPageObj_GetType(object) } as u32 {
+            ffi::FPDF_PAGEOBJ_IMAGE => {
+                let bitmap = unsafe { ffi::FPDFImageObj_GetBitmap(object) };
+                if bitmap.is_null() {
+                    continue;
+                }
+                images.extend(bitmap_pixels(bitmap));
+                unsafe { ffi::FPDFBitmap_Destroy(bitmap) };
+            }
+            ffi::FPDF_PAGEOBJ_FORM if depth < MAX_FORM_OBJECT_DEPTH => {
+                let count = unsafe { ffi::FPDFFormObj_CountObjects(object) }.max(0);
+                let children: Vec<_> = (0..count)
+                    .map(|index| unsafe {
+                        ffi::FPDFFormObj_GetObject(object, index as std::os::raw::c_ulong)
+                    })
+                    .collect();
+                collect_images(&children, depth + 1, images);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// RGBA pixels of a bitmap PDFium created, e.g. the bitmap of an image object
+fn bitmap_pixels(bitmap: ffi::FPDF_BITMAP) -> Option<PdfImage> {
+    let (format, width, height, stride, buffer) = unsafe {
+        (
+            ffi::FPDFBitmap_GetFormat(bitmap) as u32,
+            ffi::FPDFBitmap_GetWidth(bitmap),
+            ffi::FPDFBitmap_GetHeight(bitmap),
+            ffi::FPDFBitmap_GetStride(bitmap),
+            ffi::FPDFBitmap_GetBuffer(bitmap) as *const u8,
+        )
+    };
+    let bytes_per_pixel = match format {
+        ffi::FPDFBitmap_Gray => 1,
+        ffi::FPDFBitmap_BGR => 3,
+        ffi::FPDFBitmap_BGRx | ffi::FPDFBitmap_BGRA => 4,
+        _ => return None,
+    };
+    if buffer.is_null() || width <= 0 || height <= 0 || stride < width * bytes_per_pixel {
+        return None;
+    }
+    let (width_px, bytes_per_pixel) = (width as usize, bytes_per_pixel as usize);
+    let data = unsafe { std::slice::from_raw_parts(buffer, stride as usize * height as usize) };
+
+    let mut pixels = Vec::new();
+    pixels
+        .try_reserve_exact(width_px * height as usize * 4)
+        .ok()?;
+    for row in data.chunks_exact(stride as usize) {
+        for pixel in row[..width_px * bytes_per_pixel].chunks_exact(bytes_per_pixel) {
+            match format {
+                ffi::FPDFBitmap_Gray => {
+                    pixels.extend_from_slice(&[pixel[0], pixel[0], pixel[0], 255])
+                }
+                ffi::FPDFBitmap_BGRA => {
+                    pixels.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]])
+                }
+                _ => pixels.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255]),
+            }
+        }
+    }
+    Some(PdfImage {
+        pixels,
+        width,
+        height,
+    })
+}
+
 fn get_last_error_message() -> String {
     let error_code = unsafe { ffi::FPDF_GetLastError() as u32 };
     match error_code {
@@ -56,6 +206,330 @@ fn get_last_error_message() -> String {
     }
 }
 
+/// Error opening a PDF document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenError {
+    /// The document is encrypted and the password is missing or incorrect
+    Password,
+    Other(String),
+}
+
+impl std::fmt::Display for OpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Password => write!(f, "Password required or incorrect password"),
+            Self::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for OpenError {}
+
+impl From<OpenError> for String {
+    fn from(error: OpenError) -> Self {
+        error.to_string()
+    }
+}
+
+/// A character of a page's text layer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfTextChar {
+    pub ch: char,
+    /// Bounding box as `[left, top, right, bottom]` in points, relative to the
+    /// top-left corner of the page. `None` for characters generated by PDFium,
+    /// like the line breaks between text lines.
+    pub rect: Option<[f32; 4]>,
+}
+
+/// Text layer of a page
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfPageText {
+    /// Page width in points
+    pub width: f32,
+    /// Page height in points
+    pub height: f32,
+    pub chars: Vec<PdfTextChar>,
+}
+
+impl PdfPageText {
+    /// Text of the characters in `range`, with PDFium's CRLF line breaks normalized
+    pub fn text(&self, range: std::ops::Range<usize>) -> String {
+        let end = range.end.min(self.chars.len());
+        let start = range.start.min(end);
+        self.chars[start..end]
+            .iter()
+            .map(|c| c.ch)
+            .filter(|&ch| ch != '\r')
+            .collect()
+    }
+
+    /// Index of the character whose box contains the point or is the closest to it
+    ///
+    /// `x` and `y` are in points relative to the top-left corner of the page.
+    pub fn char_at(&self, x: f32, y: f32) -> Option<usize> {
+        let distance = |[left, top, right, bottom]: [f32; 4]| {
+            let dx = (left - x).max(x - right).max(0.0);
+            let dy = (top - y).max(y - bottom).max(0.0);
+            // Prefer characters on the same line
+            dx + dy * 4.0
+        };
+        self.chars
+            .iter()
+            .enumerate()
+            .filter_map(|(index, c)| c.rect.map(|rect| (index, distance(rect))))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+}
+
+/// How [`PdfDocument::search`] matches the search term
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PdfSearchOptions {
+    pub match_case: bool,
+    /// Only match the term as a whole word
+    pub whole_word: bool,
+}
+
+/// An occurrence of a search term
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfMatch {
+    /// Index of the page
+    pub page: isize,
+    /// Character index range, matching the indexes of [`PdfDocument::page_text`]
+    pub chars: std::ops::Range<usize>,
+    /// Areas covered by the match as `[left, top, right, bottom]` in points,
+    /// relative to the top-left corner of the page. A match spanning several
+    /// lines has a rect per line.
+    pub rects: Vec<[f32; 4]>,
+}
+
+/// An entry of the document outline, also known as bookmarks or table of contents
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PdfOutlineItem {
+    pub title: String,
+    /// Index of the target page, `None` if the entry doesn't point to a page
+    /// of the document, e.g. for links to websites
+    pub page: Option<isize>,
+    pub children: Vec<PdfOutlineItem>,
+}
+
+/// Where a link points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfLinkTarget {
+    /// Index of a page of the document
+    Page(isize),
+    Uri(String),
+}
+
+/// A link annotation of a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfLink {
+    /// Clickable area as `[left, top, right, bottom]` in points, relative to
+    /// the top-left corner of the page
+    pub rect: [f32; 4],
+    pub target: PdfLinkTarget,
+}
+
+/// An image embedded in a page, at its own resolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfImage {
+    /// RGBA pixels, without the soft mask the page may draw the image with
+    pub pixels: Vec<u8>,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Kind of interactive form of a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFormType {
+    None,
+    AcroForm,
+    /// XFA form, which replaces the content of the pages
+    XfaFull,
+    /// XFA form drawn over static page content
+    XfaForeground,
+}
+
+impl PdfFormType {
+    /// Whether the document has a form, fillable or not
+    pub fn has_form(self) -> bool {
+        self != Self::None
+    }
+}
+
+/// Type of a form field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFormFieldType {
+    PushButton,
+    CheckBox,
+    RadioButton,
+    ComboBox,
+    ListBox,
+    Text,
+    Signature,
+    Unknown,
+}
+
+impl PdfFormFieldType {
+    fn from_ffi(field_type: i32) -> Self {
+        match field_type as u32 {
+            ffi::FPDF_FORMFIELD_PUSHBUTTON => Self::PushButton,
+            ffi::FPDF_FORMFIELD_CHECKBOX => Self::CheckBox,
+            ffi::FPDF_FORMFIELD_RADIOBUTTON => Self::RadioButton,
+            ffi::FPDF_FORMFIELD_COMBOBOX => Self::ComboBox,
+            ffi::FPDF_FORMFIELD_LISTBOX => Self::ListBox,
+            ffi::FPDF_FORMFIELD_TEXTFIELD => Self::Text,
+            ffi::FPDF_FORMFIELD_SIGNATURE => Self::Signature,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PushButton => "Button",
+            Self::CheckBox => "Check box",
+            Self::RadioButton => "Radio button",
+            Self::ComboBox => "Combo box",
+            Self::ListBox => "List box",
+            Self::Text => "Text",
+            Self::Signature => "Signature",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
+/// A field of an interactive form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfFormField {
+    /// Fully qualified name, e.g. `address.city`
+    pub name: String,
+    pub field_type: PdfFormFieldType,
+    /// Index of the first page showing the field
+    pub page: isize,
+}
+
+/// Clockwise rotation of a page in quarter turns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PdfRotation {
+    #[default]
+    None,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl PdfRotation {
+    /// Rotation by `turns` clockwise quarter turns, negative turns rotate
+    /// counter-clockwise
+    pub fn from_quarter_turns(turns: i32) -> Self {
+        match turns.rem_euclid(4) {
+            0 => Self::None,
+            1 => Self::Cw90,
+            2 => Self::Cw180,
+            _ => Self::Cw270,
+        }
+    }
+
+    pub fn quarter_turns(self) -> i32 {
+        self as i32
+    }
+
+    pub fn degrees(self) -> i32 {
+        self.quarter_turns() * 90
+    }
+
+    /// This rotation turned a further quarter clockwise
+    pub fn clockwise(self) -> Self {
+        Self::from_quarter_turns(self.quarter_turns() + 1)
+    }
+
+    /// This rotation turned a further quarter counter-clockwise
+    pub fn counter_clockwise(self) -> Self {
+        Self::from_quarter_turns(self.quarter_turns() - 1)
+    }
+
+    /// Width and height of a `width` x `height` page once rotated
+    pub fn rotated_size(self, width: f32, height: f32) -> (f32, f32) {
+        match self {
+            Self::None | Self::Cw180 => (width, height),
+            Self::Cw90 | Self::Cw270 => (height, width),
+        }
+    }
+
+    /// Where a point of a `width` x `height` page ends up once rotated
+    ///
+    /// Points are relative to the top-left corner, before and after rotating.
+    pub fn rotate_point(self, x: f32, y: f32, width: f32, height: f32) -> (f32, f32) {
+        match self {
+            Self::None => (x, y),
+            Self::Cw90 => (height - y, x),
+            Self::Cw180 => (width - x, height - y),
+            Self::Cw270 => (y, width - x),
+        }
+    }
+
+    /// Inverse of [`PdfRotation::rotate_point`], mapping a point of the
+    /// rotated page back to the `width` x `height` page
+    pub fn unrotate_point(self, x: f32, y: f32, width: f32, height: f32) -> (f32, f32) {
+        match self {
+            Self::None => (x, y),
+            Self::Cw90 => (y, height - x),
+            Self::Cw180 => (width - x, height - y),
+            Self::Cw270 => (width - y, x),
+        }
+    }
+
+    /// Where a `[left, top, right, bottom]` rect of a `width` x `height` page
+    /// ends up once rotated
+    pub fn rotate_rect(self, rect: [f32; 4], width: f32, height: f32) -> [f32; 4] {
+        let [left, top, right, bottom] = rect;
+        let (x1, y1) = self.rotate_point(left, top, width, height);
+        let (x2, y2) = self.rotate_point(right, bottom, width, height);
+        [x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)]
+    }
+}
+
+/// What pages are rendered on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PdfBackground {
+    /// An opaque `[r, g, b]` color, e.g. to match a dark theme
+    Color([u8; 3]),
+    /// Nothing, the pixels keep the alpha of the page content
+    Transparent,
+}
+
+impl PdfBackground {
+    pub const WHITE: Self = Self::Color([255, 255, 255]);
+
+    /// Fill color for PDFium
+    fn argb(self) -> std::os::raw::c_uint {
+        match self {
+            Self::Color([r, g, b]) => u32::from_be_bytes([0xFF, r, g, b]),
+            Self::Transparent => 0,
+        }
+    }
+}
+
+impl Default for PdfBackground {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+/// How [`PdfDocument::render_page_with`] renders a page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PdfRenderOptions {
+    /// Rotation on top of the page's own
+    pub rotation: PdfRotation,
+    pub background: PdfBackground,
+}
+
+/// Outlines deeper than this are most likely malformed
+const MAX_OUTLINE_DEPTH: usize = 64;
+
+/// Form objects nested deeper than this are most likely malformed
+const MAX_FORM_OBJECT_DEPTH: usize = 32;
+
 unsafe impl Send for PdfDocument {}
 
 pub struct PdfDocument {
@@ -63,15 +537,22 @@ pub struct PdfDocument {
 }
 
 impl PdfDocument {
-    pub fn open(path: &Path) -> Result<Self, String> {
+    pub fn open(path: &Path) -> Result<Self, OpenError> {
+        Self::open_with_password(path, None)
+    }
+
+    /// Open a document, decrypting it with `password` if it's encrypted
+    pub fn open_with_password(path: &Path, password: Option<&str>) -> Result<Self, OpenError> {
         PDFIUM_INIT.call_once(|| unsafe {
             ffi::FPDF_InitLibrary();
         });
         // PDFium requires an absolute path. canonicalize() provides this but also adds \\?\ prefix on Windows.
         let abs_path = path
             .canonicalize()
-            .map_err(|e| format!("Failed to canonicalize path: {e}"))?;
-        let path_str = abs_path.to_str().ok_or("Invalid UTF-8 in path")?;
+            .map_err(|e| OpenError::Other(format!("Failed to canonicalize path: {e}")))?;
+        let path_str = abs_path
+            .to_str()
+            .ok_or_else(|| OpenError::Other("Invalid UTF-8 in path".to_string()))?;
 
         #[cfg(target_os = "windows")]
         let c_path = {
@@ -88,13 +569,24 @@ impl PdfDocument {
         #[cfg(not(target_os = "windows"))]
         let c_path = CString::new(path_str).unwrap();
 
-        let doc = unsafe { ffi::FPDF_LoadDocument(c_path.as_ptr(), std::ptr::null()) };
+        let c_password = password
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| OpenError::Password)?;
+        let password_ptr = c_password
+            .as_ref()
+            .map_or(std::ptr::null(), |password| password.as_ptr());
+
+        let doc = unsafe { ffi::FPDF_LoadDocument(c_path.as_ptr(), password_ptr) };
         if doc.is_null() {
-            Err(format!(
+            if unsafe { ffi::FPDF_GetLastError() as u32 } == ffi::FPDF_ERR_PASSWORD {
+                return Err(OpenError::Password);
+            }
+            Err(OpenError::Other(format!(
                 "Failed to load PDF document {}: {}",
                 path.display(),
                 get_last_error_message()
-            ))
+            )))
         } else {
             Ok(Self { doc })
         }
@@ -170,103 +662,558 @@ impl PdfDocument {
             None
         }
     }
-    pub fn render_page(&self, page_number: isize, dpi: f32) -> Result<(Vec<u8>, i32, i32), String> {
-        let page = unsafe { ffi::FPDF_LoadPage(self.doc, page_number as i32) };
-        if page.is_null() {
-            return Err(format!("Failed to load page {}", page_number));
-        }
+    /// Label of a page, e.g. "iv" for a page in a roman numbered preface
+    ///
+    /// Returns `None` if the document doesn't define page labels, in which
+    /// case pages are usually shown with their number.
+    pub fn page_label(&self, page_number: isize) -> Option<String> {
+        read_wstr(|buffer, len| unsafe {
+            ffi::FPDF_GetPageLabel(self.doc, page_number as i32, buffer, len)
+        })
+    }
 
-        let page_width = unsafe { ffi::FPDF_GetPageWidthF(page) };
-        let page_height = unsafe { ffi::FPDF_GetPageHeightF(page) };
+    /// Outline of the document, empty if it has none
+    pub fn outline(&self) -> Vec<PdfOutlineItem> {
+        let mut visited = std::collections::HashSet::new();
+        self.outline_children(std::ptr::null_mut(), 0, &mut visited)
+    }
 
-        let width = (page_width * dpi / 72.0).round() as i32;
-        let height = (page_height * dpi / 72.0).round() as i32;
+    fn outline_children(
+        &self,
+        parent: ffi::FPDF_BOOKMARK,
+        depth: usize,
+        visited: &mut std::collections::HashSet<usize>,
+    ) -> Vec<PdfOutlineItem> {
+        let mut items = Vec::new();
+        if depth >= MAX_OUTLINE_DEPTH {
+            return items;
+        }
+        let mut bookmark = unsafe { ffi::FPDFBookmark_GetFirstChild(self.doc, parent) };
+        // Malformed outlines can have cycles
+        while !bookmark.is_null() && visited.insert(bookmark as usize) {
+            let title = read_wstr(|buffer, len| unsafe {
+                ffi::FPDFBookmark_GetTitle(bookmark, buffer, len)
+            })
+            .unwrap_or_default();
+            items.push(PdfOutlineItem {
+                title,
+                page: self.bookmark_page(bookmark),
+                children: self.outline_children(bookmark, depth + 1, visited),
+            });
+            bookmark = unsafe { ffi::FPDFBookmark_GetNextSibling(self.doc, bookmark) };
+        }
+        items
+    }
 
-        if width <= 0 || height <= 0 {
-            unsafe {
-                ffi::FPDF_ClosePage(page);
+    /// Target page of a bookmark, either from its destination or its GoTo action
+    fn bookmark_page(&self, bookmark: ffi::FPDF_BOOKMARK) -> Option<isize> {
+        let mut dest = unsafe { ffi::FPDFBookmark_GetDest(self.doc, bookmark) };
+        if dest.is_null() {
+            let action = unsafe { ffi::FPDFBookmark_GetAction(bookmark) };
+            if action.is_null() {
+                return None;
             }
-            return Err(format!(
-                "Invalid page dimensions: width={} height={}",
-                width, height
-            ));
+            dest = unsafe { ffi::FPDFAction_GetDest(self.doc, action) };
         }
+        self.dest_page(dest)
+    }
 
-        let stride = width * 4;
-        let buffer_size = (stride * height) as usize;
+    fn dest_page(&self, dest: ffi::FPDF_DEST) -> Option<isize> {
+        if dest.is_null() {
+            return None;
+        }
+        let page = unsafe { ffi::FPDFDest_GetDestPageIndex(self.doc, dest) };
+        (page >= 0).then_some(page as isize)
+    }
 
-        let bitmap_buffer = unsafe {
-            std::alloc::alloc(std::alloc::Layout::from_size_align(buffer_size, 4).unwrap())
-        };
-        if bitmap_buffer.is_null() {
-            unsafe {
-                ffi::FPDF_ClosePage(page);
+    /// Links of a page, in the order they appear in the page
+    ///
+    /// Only links to pages of the document and to URIs are returned, other
+    /// actions like launching applications are left out.
+    pub fn page_links(&self, page_number: isize) -> Result<Vec<PdfLink>, String> {
+        self.with_page(page_number, |page| {
+            let height = unsafe { ffi::FPDF_GetPageHeightF(page) };
+            let mut links = Vec::new();
+            let mut start_pos = 0;
+            let mut link: ffi::FPDF_LINK = std::ptr::null_mut();
+            while unsafe { ffi::FPDFLink_Enumerate(page, &mut start_pos, &mut link) } != 0 {
+                let mut rect = ffi::FS_RECTF {
+                    left: 0.0,
+                    top: 0.0,
+                    right: 0.0,
+                    bottom: 0.0,
+                };
+                if unsafe { ffi::FPDFLink_GetAnnotRect(link, &mut rect) } == 0 {
+                    continue;
+                }
+                let Some(target) = self.link_target(link) else {
+                    continue;
+                };
+                // PDF coordinates start from the bottom-left corner of the page
+                let (top, bottom) = (rect.top.max(rect.bottom), rect.top.min(rect.bottom));
+                links.push(PdfLink {
+                    rect: [
+                        rect.left.min(rect.right),
+                        height - top,
+                        rect.left.max(rect.right),
+                        height - bottom,
+                    ],
+                    target,
+                });
             }
-            return Err("Failed to allocate bitmap buffer".to_string());
+            links
+        })
+    }
+
+    /// Target of a link, either from its destination or its action
+    fn link_target(&self, link: ffi::FPDF_LINK) -> Option<PdfLinkTarget> {
+        let dest = unsafe { ffi::FPDFLink_GetDest(self.doc, link) };
+        if let Some(page) = self.dest_page(dest) {
+            return Some(PdfLinkTarget::Page(page));
         }
 
-        let bitmap = unsafe {
-            ffi::FPDFBitmap_CreateEx(
-                width,
-                height,
-                ffi::FPDFBitmap_BGRA as i32,
-                bitmap_buffer as *mut c_void,
-                stride,
-            )
-        };
-        if bitmap.is_null() {
-            unsafe {
-                std::alloc::dealloc(
-                    bitmap_buffer,
-                    std::alloc::Layout::from_size_align(buffer_size, 4).unwrap(),
-                );
-                ffi::FPDF_ClosePage(page);
+        let action = unsafe { ffi::FPDFLink_GetAction(link) };
+        if action.is_null() {
+            return None;
+        }
+        match unsafe { ffi::FPDFAction_GetType(action) } as u32 {
+            ffi::PDFACTION_GOTO => {
+                let dest = unsafe { ffi::FPDFAction_GetDest(self.doc, action) };
+                self.dest_page(dest).map(PdfLinkTarget::Page)
             }
-            return Err("Failed to create bitmap".to_string());
+            ffi::PDFACTION_URI => self.action_uri(action).map(PdfLinkTarget::Uri),
+            _ => None,
         }
+    }
 
-        // Fill bitmap with white
-        unsafe {
-            ffi::FPDFBitmap_FillRect(bitmap, 0, 0, width, height, 0xFFFFFFFF);
+    /// URI of a URI action, PDFium returns it as a NUL terminated byte string
+    fn action_uri(&self, action: ffi::FPDF_ACTION) -> Option<String> {
+        let len = unsafe { ffi::FPDFAction_GetURIPath(self.doc, action, std::ptr::null_mut(), 0) }
+            as usize;
+        if len <= 1 {
+            return None;
+        }
+        let mut buffer = vec![0u8; len];
+        let len = unsafe {
+            ffi::FPDFAction_GetURIPath(
+                self.doc,
+                action,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len() as std::os::raw::c_ulong,
+            )
+        } as usize;
+        buffer.truncate(len.min(buffer.len()));
+        while buffer.last() == Some(&0) {
+            buffer.pop();
         }
+        (!buffer.is_empty()).then(|| String::from_utf8_lossy(&buffer).into_owned())
+    }
 
-        unsafe {
-            ffi::FPDF_RenderPageBitmap(
-                bitmap,
-                page,
-                0, // start_x
-                0, // start_y
-                width,
-                height,
-                0,                                                                  // rotate_flag
-                (ffi::FPDF_LCD_TEXT | ffi::FPDF_PRINTING | ffi::FPDF_ANNOT) as i32, // flags
-            );
+    /// Images embedded in a page, in drawing order
+    ///
+    /// Images drawn inside form objects are included. An image drawn several
+    /// times is returned each time.
+    pub fn page_images(&self, page_number: isize) -> Result<Vec<PdfImage>, String> {
+        self.with_page(page_number, |page| {
+            let objects: Vec<_> = (0..unsafe { ffi::FPDFPage_CountObjects(page) })
+                .map(|index| unsafe { ffi::FPDFPage_GetObject(page, index) })
+                .collect();
+            let mut images = Vec::new();
+            collect_images(&objects, 0, &mut images);
+            images
+        })
+    }
+
+    /// Kind of interactive form of the document
+    pub fn form_type(&self) -> PdfFormType {
+        match unsafe { ffi::FPDF_GetFormType(self.doc) } as u32 {
+            ffi::FORMTYPE_ACRO_FORM => PdfFormType::AcroForm,
+            ffi::FORMTYPE_XFA_FULL => PdfFormType::XfaFull,
+            ffi::FORMTYPE_XFA_FOREGROUND => PdfFormType::XfaForeground,
+            _ => PdfFormType::None,
         }
+    }
 
-        let mut pixel_data = vec![0u8; buffer_size];
-        unsafe {
-            std::ptr::copy_nonoverlapping(bitmap_buffer, pixel_data.as_mut_ptr(), buffer_size);
+    /// Fields of the document form, in page order
+    ///
+    /// Widgets sharing a field, like the buttons of a radio group, are listed
+    /// once. Fields only defined in XFA data are not listed.
+    pub fn form_fields(&self) -> Vec<PdfFormField> {
+        if !self.form_type().has_form() {
+            return Vec::new();
+        }
+        // PDFium keeps a pointer to the info for the lifetime of the handle.
+        // Leaving all callbacks unset is fine for reading fields.
+        let mut form_info: Box<ffi::FPDF_FORMFILLINFO> = Box::new(unsafe { std::mem::zeroed() });
+        form_info.version = 1;
+        let form = unsafe { ffi::FPDFDOC_InitFormFillEnvironment(self.doc, &mut *form_info) };
+        if form.is_null() {
+            return Vec::new();
         }
 
-        // Pdfium outputs BGRA, but we need RGBA for egui.
-        // Swap B (index 0) and R (index 2) for each pixel.
-        for i in (0..buffer_size).step_by(4) {
-            pixel_data.swap(i, i + 2);
+        let mut fields = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for page_number in 0..self.page_count() {
+            let _ = self.with_page(page_number, |page| {
+                let count = unsafe { ffi::FPDFPage_GetAnnotCount(page) };
+                for index in 0..count {
+                    let annot = unsafe { ffi::FPDFPage_GetAnnot(page, index) };
+                    if annot.is_null() {
+                        continue;
+                    }
+                    if unsafe { ffi::FPDFAnnot_GetSubtype(annot) } as u32 == ffi::FPDF_ANNOT_WIDGET
+                    {
+                        let name = read_wstr(|buffer, len| unsafe {
+                            ffi::FPDFAnnot_GetFormFieldName(form, annot, buffer.cast(), len)
+                        })
+                        .unwrap_or_default();
+                        if seen.insert(name.clone()) {
+                            let field_type =
+                                unsafe { ffi::FPDFAnnot_GetFormFieldType(form, annot) };
+                            fields.push(PdfFormField {
+                                name,
+                                field_type: PdfFormFieldType::from_ffi(field_type),
+                                page: page_number,
+                            });
+                        }
+                    }
+                    unsafe { ffi::FPDFPage_CloseAnnot(annot) };
+                }
+            });
         }
 
+        unsafe { ffi::FPDFDOC_ExitFormFillEnvironment(form) };
+        fields
+    }
+
+    /// Size of a page in points as `(width, height)`
+    pub fn page_size(&self, page_number: isize) -> Result<(f32, f32), String> {
+        self.with_page(page_number, |page| unsafe {
+            (
+                ffi::FPDF_GetPageWidthF(page),
+                ffi::FPDF_GetPageHeightF(page),
+            )
+        })
+    }
+
+    /// Rotation the document applies to a page, e.g. for scanned pages
+    ///
+    /// It's already part of [`PdfDocument::page_size`] and the rendered pages.
+    pub fn page_rotation(&self, page_number: isize) -> Result<PdfRotation, String> {
+        self.with_page(page_number, |page| {
+            PdfRotation::from_quarter_turns(unsafe { ffi::FPDFPage_GetRotation(page) })
+        })
+    }
+
+    pub fn render_page(&self, page_number: isize, dpi: f32) -> Result<(Vec<u8>, i32, i32), String> {
+        self.render_page_rotated(page_number, dpi, PdfRotation::None)
+    }
+
+    /// Render a page turned by `rotation` on top of its own rotation, e.g. to
+    /// straighten pages scanned sideways
+    ///
+    /// The width and height are swapped for quarter turns.
+    pub fn render_page_rotated(
+        &self,
+        page_number: isize,
+        dpi: f32,
+        rotation: PdfRotation,
+    ) -> Result<(Vec<u8>, i32, i32), String> {
+        let options = PdfRenderOptions {
+            rotation,
+            ..Default::default()
+        };
+        self.render_page_with(page_number, dpi, options)
+    }
+
+    /// Render a page with a rotation and background other than the defaults
+    pub fn render_page_with(
+        &self,
+        page_number: isize,
+        dpi: f32,
+        options: PdfRenderOptions,
+    ) -> Result<(Vec<u8>, i32, i32), String> {
+        let PdfRenderOptions {
+            rotation,
+            background,
+        } = options;
+        self.with_page(page_number, |page| {
+            let (page_width, page_height) = rotation
+                .rotated_size(unsafe { ffi::FPDF_GetPageWidthF(page) }, unsafe {
+                    ffi::FPDF_GetPageHeightF(page)
+                });
+
+            let width = (page_width * dpi / 72.0).round() as i32;
+            let height = (page_height * dpi / 72.0).round() as i32;
+
+            let pixel_data = render_bitmap(width, height, background, |bitmap| unsafe {
+                ffi::FPDF_RenderPageBitmap(
+                    bitmap,
+                    page,
+                    0, // start_x
+                    0, // start_y
+                    width,
+                    height,
+                    rotation.quarter_turns(),
+                    RENDER_FLAGS,
+                );
+            })?;
+            Ok((pixel_data, width, height))
+        })?
+    }
+
+    /// Render only the `rect` region of a page
+    ///
+    /// `rect` is `[left, top, right, bottom]` in points relative to the top-left
+    /// corner of the page, like [`PdfTextChar::rect`], and `scale` is the number
+    /// of pixels per point. This keeps the bitmap as small as the visible area
+    /// when zooming into a page, where rendering the whole page at the same
+    /// scale would need a huge bitmap.
+    ///
+    /// Returns the RGBA pixels with the bitmap width and height.
+    pub fn render_page_region(
+        &self,
+        page_number: isize,
+        rect: [f32; 4],
+        scale: f32,
+    ) -> Result<(Vec<u8>, i32, i32), String> {
+        self.render_page_region_with(page_number, rect, scale, PdfBackground::WHITE)
+    }
+
+    /// [`PdfDocument::render_page_region`] on another background
+    pub fn render_page_region_with(
+        &self,
+        page_number: isize,
+        rect: [f32; 4],
+        scale: f32,
+        background: PdfBackground,
+    ) -> Result<(Vec<u8>, i32, i32), String> {
+        let [left, top, right, bottom] = rect;
+        let width = ((right - left) * scale).round() as i32;
+        let height = ((bottom - top) * scale).round() as i32;
+
+        self.with_page(page_number, |page| {
+            // PDFium maps the page to points with a top-left origin before
+            // applying the matrix, so only scaling and moving the region to the
+            // bitmap origin are needed.
+            let matrix = ffi::FS_MATRIX {
+                a: scale,
+                b: 0.0,
+                c: 0.0,
+                d: scale,
+                e: -left * scale,
+                f: -top * scale,
+            };
+            let clipping = ffi::FS_RECTF {
+                left: 0.0,
+                top: 0.0,
+                right: width as f32,
+                bottom: height as f32,
+            };
+            let pixel_data = render_bitmap(width, height, background, |bitmap| unsafe {
+                ffi::FPDF_RenderPageBitmapWithMatrix(
+                    bitmap,
+                    page,
+                    &matrix,
+                    &clipping,
+                    RENDER_FLAGS,
+                );
+            })?;
+            Ok((pixel_data, width, height))
+        })?
+    }
+
+    /// Run `f` with a loaded page
+    fn with_page<T>(
+        &self,
+        page_number: isize,
+        f: impl FnOnce(ffi::FPDF_PAGE) -> T,
+    ) -> Result<T, String> {
+        let page = unsafe { ffi::FPDF_LoadPage(self.doc, page_number as i32) };
+        if page.is_null() {
+            return Err(format!("Failed to load page {}", page_number));
+        }
+        let result = f(page);
         unsafe {
-            ffi::FPDFBitmap_Destroy(bitmap);
-            std::alloc::dealloc(
-                bitmap_buffer,
-                std::alloc::Layout::from_size_align(buffer_size, 4).unwrap(),
-            );
             ffi::FPDF_ClosePage(page);
         }
+        Ok(result)
+    }
 
-        Ok((pixel_data, width, height))
+    /// Run `f` with the text page of a page, along with the page width and height
+    fn with_text_page<T>(
+        &self,
+        page_number: isize,
+        f: impl FnOnce(ffi::FPDF_TEXTPAGE, f32, f32) -> T,
+    ) -> Result<T, String> {
+        self.with_page(page_number, |page| {
+            let width = unsafe { ffi::FPDF_GetPageWidthF(page) };
+            let height = unsafe { ffi::FPDF_GetPageHeightF(page) };
+
+            let text_page = unsafe { ffi::FPDFText_LoadPage(page) };
+            if text_page.is_null() {
+                return Err(format!("Failed to load text of page {}", page_number));
+            }
+
+            let result = f(text_page, width, height);
+
+            unsafe {
+                ffi::FPDFText_ClosePage(text_page);
+            }
+            Ok(result)
+        })?
+    }
+
+    /// Extract the text layer of a page
+    pub fn page_text(&self, page_number: isize) -> Result<PdfPageText, String> {
+        self.with_text_page(page_number, |text_page, width, height| {
+            let count = unsafe { ffi::FPDFText_CountChars(text_page) }.max(0);
+            let mut chars = Vec::with_capacity(count as usize);
+            for index in 0..count {
+                // Keep invalid characters so indexes match PDFium's, e.g. for search results
+                let code = unsafe { ffi::FPDFText_GetUnicode(text_page, index) };
+                let ch = char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER);
+
+                let (mut left, mut right, mut bottom, mut top) = (0.0, 0.0, 0.0, 0.0);
+                let has_box = unsafe {
+                    ffi::FPDFText_GetCharBox(
+                        text_page,
+                        index,
+                        &mut left,
+                        &mut right,
+                        &mut bottom,
+                        &mut top,
+                    )
+                } != 0;
+                // PDF coordinates start from the bottom-left corner of the page
+                let rect = (has_box && right > left && top > bottom).then(|| {
+                    [
+                        left as f32,
+                        height - top as f32,
+                        right as f32,
+                        height - bottom as f32,
+                    ]
+                });
+                chars.push(PdfTextChar { ch, rect });
+            }
+
+            PdfPageText {
+                width,
+                height,
+                chars,
+            }
+        })
+    }
+
+    /// Find all occurrences of `query` in a page
+    ///
+    /// Returns the character index ranges of the matches, matching the
+    /// indexes of [`PdfDocument::page_text`].
+    pub fn find_text(
+        &self,
+        page_number: isize,
+        query: &str,
+        match_case: bool,
+    ) -> Result<Vec<std::ops::Range<usize>>, String> {
+        let options = PdfSearchOptions {
+            match_case,
+            ..Default::default()
+        };
+        let matches = self.search_page(page_number, query, options)?;
+        Ok(matches.into_iter().map(|m| m.chars).collect())
+    }
+
+    /// Find all occurrences of `term` in the document, in page order
+    ///
+    /// Pages that fail to load are skipped.
+    pub fn search(&self, term: &str, options: PdfSearchOptions) -> Vec<PdfMatch> {
+        (0..self.page_count())
+            .filter_map(|page| self.search_page(page, term, options).ok())
+            .flatten()
+            .collect()
+    }
+
+    /// Find all occurrences of `term` in a page
+    pub fn search_page(
+        &self,
+        page_number: isize,
+        term: &str,
+        options: PdfSearchOptions,
+    ) -> Result<Vec<PdfMatch>, String> {
+        if term.is_empty() {
+            return Ok(Vec::new());
+        }
+        let wide: Vec<u16> = term.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut flags = 0;
+        if options.match_case {
+            flags |= ffi::FPDF_MATCHCASE;
+        }
+        if options.whole_word {
+            flags |= ffi::FPDF_MATCHWHOLEWORD;
+        }
+
+        self.with_text_page(page_number, |text_page, _, height| {
+            let mut matches = Vec::new();
+            let handle = unsafe {
+                ffi::FPDFText_FindStart(text_page, wide.as_ptr(), flags as std::os::raw::c_ulong, 0)
+            };
+            if handle.is_null() {
+                return matches;
+            }
+            while unsafe { ffi::FPDFText_FindNext(handle) } != 0 {
+                let start = unsafe { ffi::FPDFText_GetSchResultIndex(handle) };
+                let count = unsafe { ffi::FPDFText_GetSchCount(handle) };
+                if start >= 0 && count > 0 {
+                    matches.push(PdfMatch {
+                        page: page_number,
+                        chars: start as usize..(start + count) as usize,
+                        rects: text_rects(text_page, start, count, height),
+                    });
+                }
+            }
+            unsafe {
+                ffi::FPDFText_FindClose(handle);
+            }
+            matches
+        })
     }
 }
 
+/// Rects covering `count` characters from `start`, one per line, relative to
+/// the top-left corner of the page
+fn text_rects(
+    text_page: ffi::FPDF_TEXTPAGE,
+    start: std::os::raw::c_int,
+    count: std::os::raw::c_int,
+    height: f32,
+) -> Vec<[f32; 4]> {
+    // Also prepares the rects read by FPDFText_GetRect
+    let rect_count = unsafe { ffi::FPDFText_CountRects(text_page, start, count) };
+    (0..rect_count.max(0))
+        .filter_map(|index| {
+            let (mut left, mut top, mut right, mut bottom) = (0.0, 0.0, 0.0, 0.0);
+            let ok = unsafe {
+                ffi::FPDFText_GetRect(
+                    text_page,
+                    index,
+                    &mut left,
+                    &mut top,
+                    &mut right,
+                    &mut bottom,
+                )
+            } != 0;
+            ok.then(|| {
+                [
+                    left as f32,
+                    height - top as f32,
+                    right as f32,
+                    height - bottom as f32,
+                ]
+            })
+        })
+        .collect()
+}
+
 impl Drop for PdfDocument {
     fn drop(&mut self) {
         unsafe {
@@ -307,13 +1254,329 @@ mod tests {
         assert_eq!(title, long_title);
     }
 
+    #[test]
+    fn test_page_text() {
+        use std::io::Write;
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+
+        let stream = "BT /F1 24 Tf 72 700 Td (Hello) Tj ET";
+        let pdf_content = format!(
+            "%PDF-1.4\n1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >>\nendobj\n\
+             4 0 obj\n<< /Length {} >>\nstream\n{}\nendstream\nendobj\n\
+             5 0 obj\n<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>\nendobj\n\
+             trailer\n<< /Root 1 0 R /Size 6 >>\n\
+             %%EOF",
+            stream.len(),
+            stream
+        );
+        temp_file.write_all(pdf_content.as_bytes()).unwrap();
+
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        let text = doc.page_text(0).expect("Failed to extract text");
+        assert_eq!(text.width, 612.0);
+        assert_eq!(text.text(0..text.chars.len()), "Hello");
+        assert_eq!(text.text(1..3), "el");
+
+        // Boxes use top-left origin, the baseline is 92pt from the top
+        let [left, top, _, bottom] = text.chars[0].rect.expect("H has a box");
+        assert!(left >= 72.0);
+        assert!(top < 92.0 && bottom <= 92.5);
+        assert_eq!(text.char_at(left + 1.0, top + 1.0), Some(0));
+        assert_eq!(text.char_at(600.0, top + 1.0), Some(4));
+
+        assert_eq!(doc.find_text(0, "LL", false).unwrap(), vec![2..4]);
+        assert!(doc.find_text(0, "LL", true).unwrap().is_empty());
+        assert!(doc.find_text(0, "", false).unwrap().is_empty());
+
+        let matches = doc.search("ll", PdfSearchOptions::default());
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].page, matches[0].chars.clone()), (0, 2..4));
+        let [[match_left, match_top, match_right, match_bottom]] = matches[0].rects[..] else {
+            panic!("match should have a single rect: {:?}", matches[0].rects);
+        };
+        assert!(match_left > left && match_right > match_left);
+        assert!(match_top < 92.0 && match_bottom > match_top);
+
+        let whole_word = PdfSearchOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        assert!(doc.search("hell", whole_word).is_empty());
+        assert_eq!(doc.search("hello", whole_word).len(), 1);
+    }
+
+    #[test]
+    fn test_render_page_region() {
+        use std::io::Write;
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+
+        // Black 100pt square in the top-left corner of the page
+        let stream = "0 0 0 rg 0 692 100 100 re f";
+        let pdf_content = format!(
+            "%PDF-1.4\n1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R >>\nendobj\n\
+             4 0 obj\n<< /Length {} >>\nstream\n{}\nendstream\nendobj\n\
+             trailer\n<< /Root 1 0 R /Size 5 >>\n\
+             %%EOF",
+            stream.len(),
+            stream
+        );
+        temp_file.write_all(pdf_content.as_bytes()).unwrap();
+
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        assert_eq!(doc.page_size(0).unwrap(), (612.0, 792.0));
+        let pixel = |pixels: &[u8], width: i32, x: i32, y: i32| {
+            let offset = ((y * width + x) * 4) as usize;
+            pixels[offset..offset + 4].to_vec()
+        };
+
+        let (pixels, width, height) = doc
+            .render_page_region(0, [50.0, 50.0, 150.0, 150.0], 4.0)
+            .expect("Failed to render region");
+        assert_eq!((width, height), (400, 400));
+        assert_eq!(pixels.len(), 400 * 400 * 4);
+        // (60, 60) is inside the square, (140, 140) is outside
+        assert_eq!(pixel(&pixels, width, 40, 40), vec![0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, width, 360, 360), vec![255, 255, 255, 255]);
+
+        assert!(doc
+            .render_page_region(0, [10.0, 10.0, 10.0, 20.0], 2.0)
+            .is_err());
+
+        // Turned clockwise, the square moves to the top-right corner
+        assert_eq!(doc.page_rotation(0).unwrap(), PdfRotation::None);
+        let (pixels, width, height) = doc
+            .render_page_rotated(0, 72.0, PdfRotation::Cw90)
+            .expect("Failed to render rotated page");
+        assert_eq!((width, height), (792, 612));
+        assert_eq!(pixel(&pixels, width, 750, 50), vec![0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, width, 50, 50), vec![255, 255, 255, 255]);
+
+        // Only the background changes on other backgrounds
+        let dark = PdfRenderOptions {
+            background: PdfBackground::Color([10, 20, 30]),
+            ..Default::default()
+        };
+        let (pixels, width, _) = doc.render_page_with(0, 72.0, dark).unwrap();
+        assert_eq!(pixel(&pixels, width, 50, 50), vec![0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, width, 300, 300), vec![10, 20, 30, 255]);
+        let (pixels, width, _) = doc
+            .render_page_region_with(
+                0,
+                [50.0, 50.0, 150.0, 150.0],
+                1.0,
+                PdfBackground::Transparent,
+            )
+            .unwrap();
+        assert_eq!(pixel(&pixels, width, 10, 10), vec![0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, width, 90, 90)[3], 0);
+    }
+
+    #[test]
+    fn test_rotation() {
+        assert_eq!(PdfRotation::from_quarter_turns(5), PdfRotation::Cw90);
+        assert_eq!(PdfRotation::from_quarter_turns(-1), PdfRotation::Cw270);
+        assert_eq!(PdfRotation::Cw270.clockwise(), PdfRotation::None);
+        assert_eq!(PdfRotation::None.counter_clockwise(), PdfRotation::Cw270);
+        assert_eq!(PdfRotation::Cw180.degrees(), 180);
+        assert_eq!(PdfRotation::Cw90.rotated_size(600.0, 800.0), (800.0, 600.0));
+
+        // The top-left 100pt square of a 600x800 page
+        let square = [0.0, 0.0, 100.0, 100.0];
+        let rotated = [
+            (PdfRotation::None, [0.0, 0.0, 100.0, 100.0]),
+            (PdfRotation::Cw90, [700.0, 0.0, 800.0, 100.0]),
+            (PdfRotation::Cw180, [500.0, 700.0, 600.0, 800.0]),
+            (PdfRotation::Cw270, [0.0, 500.0, 100.0, 600.0]),
+        ];
+        for (rotation, expected) in rotated {
+            assert_eq!(rotation.rotate_rect(square, 600.0, 800.0), expected);
+            let (x, y) = rotation.rotate_point(10.0, 20.0, 600.0, 800.0);
+            assert_eq!(
+                rotation.unrotate_point(x, y, 600.0, 800.0),
+                (10.0, 20.0),
+                "{rotation:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_outline_and_page_labels() {
+        use std::io::Write;
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+
+        // Two pages, the first labeled "i" and the second "1", with an outline
+        // entry per page and a nested entry for the second one
+        let pdf_content = "%PDF-1.4\n\
+             1 0 obj\n<< /Type /Catalog /Pages 2 0 R /Outlines 5 0 R \
+             /PageLabels << /Nums [0 << /S /r >> 1 << /S /D >>] >> >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+             4 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+             5 0 obj\n<< /Type /Outlines /First 6 0 R /Last 7 0 R /Count 3 >>\nendobj\n\
+             6 0 obj\n<< /Title (Preface) /Parent 5 0 R /Next 7 0 R /Dest [3 0 R /Fit] >>\nendobj\n\
+             7 0 obj\n<< /Title (Chapter 1) /Parent 5 0 R /Prev 6 0 R /First 8 0 R /Last 8 0 R \
+             /Count 1 /A << /S /GoTo /D [4 0 R /Fit] >> >>\nendobj\n\
+             8 0 obj\n<< /Title (Website) /Parent 7 0 R /A << /S /URI /URI (https://example.com) >> >>\nendobj\n\
+             trailer\n<< /Root 1 0 R /Size 9 >>\n\
+             %%EOF";
+        temp_file.write_all(pdf_content.as_bytes()).unwrap();
+
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        assert_eq!(doc.page_label(0).as_deref(), Some("i"));
+        assert_eq!(doc.page_label(1).as_deref(), Some("1"));
+        assert_eq!(
+            doc.outline(),
+            vec![
+                PdfOutlineItem {
+                    title: "Preface".to_string(),
+                    page: Some(0),
+                    children: Vec::new(),
+                },
+                PdfOutlineItem {
+                    title: "Chapter 1".to_string(),
+                    page: Some(1),
+                    children: vec![PdfOutlineItem {
+                        title: "Website".to_string(),
+                        page: None,
+                        children: Vec::new(),
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_page_links() {
+        use std::io::Write;
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+
+        // A link to the second page and one to a website on the first page
+        let pdf_content = "%PDF-1.4\n\
+             1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Annots [5 0 R 6 0 R] >>\nendobj\n\
+             4 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+             5 0 obj\n<< /Type /Annot /Subtype /Link /Rect [72 692 172 712] \
+             /Dest [4 0 R /Fit] >>\nendobj\n\
+             6 0 obj\n<< /Type /Annot /Subtype /Link /Rect [72 600 272 620] \
+             /A << /S /URI /URI (https://example.com) >> >>\nendobj\n\
+             trailer\n<< /Root 1 0 R /Size 7 >>\n\
+             %%EOF";
+        temp_file.write_all(pdf_content.as_bytes()).unwrap();
+
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        assert_eq!(
+            doc.page_links(0).unwrap(),
+            vec![
+                PdfLink {
+                    rect: [72.0, 80.0, 172.0, 100.0],
+                    target: PdfLinkTarget::Page(1),
+                },
+                PdfLink {
+                    rect: [72.0, 172.0, 272.0, 192.0],
+                    target: PdfLinkTarget::Uri("https://example.com".to_string()),
+                },
+            ]
+        );
+        assert!(doc.page_links(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_form_fields() {
+        use std::io::Write;
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+
+        // A text field and a radio group with two buttons on the first page
+        let pdf_content = "%PDF-1.4\n\
+             1 0 obj\n<< /Type /Catalog /Pages 2 0 R /AcroForm << /Fields [5 0 R 6 0 R] >> >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Annots [5 0 R 7 0 R 8 0 R] >>\nendobj\n\
+             4 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+             5 0 obj\n<< /Type /Annot /Subtype /Widget /FT /Tx /T (name) \
+             /Rect [72 692 272 712] /P 3 0 R >>\nendobj\n\
+             6 0 obj\n<< /FT /Btn /Ff 49152 /T (choice) /Kids [7 0 R 8 0 R] >>\nendobj\n\
+             7 0 obj\n<< /Type /Annot /Subtype /Widget /Parent 6 0 R \
+             /Rect [72 600 92 620] /P 3 0 R >>\nendobj\n\
+             8 0 obj\n<< /Type /Annot /Subtype /Widget /Parent 6 0 R \
+             /Rect [100 600 120 620] /P 3 0 R >>\nendobj\n\
+             trailer\n<< /Root 1 0 R /Size 9 >>\n\
+             %%EOF";
+        temp_file.write_all(pdf_content.as_bytes()).unwrap();
+
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        assert_eq!(doc.form_type(), PdfFormType::AcroForm);
+        assert_eq!(
+            doc.form_fields(),
+            vec![
+                PdfFormField {
+                    name: "name".to_string(),
+                    field_type: PdfFormFieldType::Text,
+                    page: 0,
+                },
+                PdfFormField {
+                    name: "choice".to_string(),
+                    field_type: PdfFormFieldType::RadioButton,
+                    page: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_page_images() {
+        use std::io::Write;
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+
+        // A red and green 2x1 image drawn on the first page
+        let content = "q 100 0 0 50 72 600 cm /Im1 Do Q";
+        let mut pdf_content = format!(
+            "%PDF-1.4\n\
+             1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R 6 0 R] /Count 2 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Resources << /XObject << /Im1 5 0 R >> >> /Contents 4 0 R >>\nendobj\n\
+             4 0 obj\n<< /Length {} >>\nstream\n{content}\nendstream\nendobj\n\
+             5 0 obj\n<< /Type /XObject /Subtype /Image /Width 2 /Height 1 \
+             /ColorSpace /DeviceRGB /BitsPerComponent 8 /Length 6 >>\nstream\n",
+            content.len()
+        )
+        .into_bytes();
+        pdf_content.extend_from_slice(&[255, 0, 0, 0, 255, 0]);
+        pdf_content.extend_from_slice(
+            b"\nendstream\nendobj\n\
+              6 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+              trailer\n<< /Root 1 0 R /Size 7 >>\n\
+              %%EOF",
+        );
+        temp_file.write_all(&pdf_content).unwrap();
+
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        assert_eq!(
+            doc.page_images(0).unwrap(),
+            vec![PdfImage {
+                pixels: vec![255, 0, 0, 255, 0, 255, 0, 255],
+                width: 2,
+                height: 1,
+            }]
+        );
+        assert!(doc.page_images(1).unwrap().is_empty());
+        assert!(doc.page_images(2).is_err());
+    }
+
     #[test]
     fn test_open_non_existent_file() {
         let temp_dir = tempfile::tempdir().unwrap();
         let non_existent_file = temp_dir.path().join("non_existent.pdf");
         let result = PdfDocument::open(&non_existent_file);
         assert!(result.is_err());
-        let err = result.err().unwrap();
+        let err = result.err().unwrap().to_string();
         assert!(
             err.contains("No such file or directory")
                 || err.contains("File not found or could not be opened")
@@ -327,7 +1590,7 @@ mod tests {
         temp_file.write_all(b"not a pdf").unwrap();
         let result = PdfDocument::open(temp_file.path());
         assert!(result.is_err());
-        let err = result.err().unwrap();
+        let err = result.err().unwrap().to_string();
         assert!(err.contains("File not in PDF format or corrupted"));
     }
 }
@@ -0,0 +1,294 @@
+//! Rendering pages on a background thread
+//!
+//! Rendering a large page can take long enough to drop frames, so a
+//! [`RenderWorker`] renders on its own thread and hands back the bitmaps
+//! through a channel. Flipping through pages quickly submits a request per
+//! page, each request replaces the pending one of the same kind so only the
+//! page the user stopped at gets rendered.
+
+use crate::{PdfBackground, PdfDocument, PdfRenderOptions, PdfRotation};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::thread;
+
+/// What to render of a page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderKind {
+    /// The whole page at `dpi`, see [`PdfDocument::render_page_rotated`]
+    Page { dpi: f32, rotation: PdfRotation },
+    /// A region of the page, see [`PdfDocument::render_page_region`]
+    Region { rect: [f32; 4], scale: f32 },
+}
+
+impl RenderKind {
+    /// Requests of the same kind supersede each other
+    fn slot(&self) -> usize {
+        match self {
+            Self::Page { .. } => 0,
+            Self::Region { .. } => 1,
+        }
+    }
+}
+
+/// A page to render
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderRequest {
+    pub page: isize,
+    pub kind: RenderKind,
+    pub background: PdfBackground,
+}
+
+/// RGBA pixels of a rendered page or region
+#[derive(Debug)]
+pub struct RenderedBitmap {
+    pub pixels: Vec<u8>,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Result of a request, identified by the id [`RenderWorker::submit`] returned
+#[derive(Debug)]
+pub struct RenderResponse {
+    pub id: u64,
+    pub request: RenderRequest,
+    pub result: Result<RenderedBitmap, String>,
+}
+
+#[derive(Default)]
+struct Queue {
+    pending: Vec<(u64, RenderRequest)>,
+    /// Latest request of each kind, results of older ones are dropped
+    latest: [Option<u64>; 2],
+    next_id: u64,
+    shutdown: bool,
+}
+
+type Shared = Arc<(Mutex<Queue>, Condvar)>;
+
+/// Renders pages of a document on a dedicated thread
+///
+/// The thread stops once the worker is dropped and the render in progress,
+/// if any, finished.
+pub struct RenderWorker {
+    shared: Shared,
+    responses: mpsc::Receiver<RenderResponse>,
+}
+
+impl RenderWorker {
+    /// Start rendering pages of `doc`, `on_rendered` is called whenever a
+    /// response is ready, e.g. to wake up the UI
+    pub fn new(doc: Arc<Mutex<PdfDocument>>, on_rendered: impl Fn() + Send + 'static) -> Self {
+        let shared = Shared::default();
+        let (sender, responses) = mpsc::channel();
+        let thread_shared = shared.clone();
+        thread::spawn(move || run(&thread_shared, &doc, &sender, &on_rendered));
+        Self { shared, responses }
+    }
+
+    /// Queue a request, replacing the pending request of the same kind
+    ///
+    /// Returns the id of the response.
+    pub fn submit(&self, request: RenderRequest) -> u64 {
+        let (queue, condvar) = &*self.shared;
+        let mut queue = lock(queue);
+        let id = queue.next_id;
+        queue.next_id += 1;
+        let slot = request.kind.slot();
+        queue
+            .pending
+            .retain(|(_, pending)| pending.kind.slot() != slot);
+        queue.pending.push((id, request));
+        queue.latest[slot] = Some(id);
+        condvar.notify_one();
+        id
+    }
+
+    /// Drop a request, its response isn't sent even if it's being rendered
+    pub fn cancel(&self, id: u64) {
+        let mut queue = lock(&self.shared.0);
+        queue.pending.retain(|(pending, _)| *pending != id);
+        for latest in &mut queue.latest {
+            if *latest == Some(id) {
+                *latest = None;
+            }
+        }
+    }
+
+    /// Drop all requests
+    pub fn cancel_all(&self) {
+        let mut queue = lock(&self.shared.0);
+        queue.pending.clear();
+        queue.latest = [None; 2];
+    }
+
+    /// Next response, without waiting
+    pub fn try_recv(&self) -> Option<RenderResponse> {
+        self.responses.try_recv().ok()
+    }
+}
+
+impl Drop for RenderWorker {
+    fn drop(&mut self) {
+        let (queue, condvar) = &*self.shared;
+        lock(queue).shutdown = true;
+        condvar.notify_one();
+    }
+}
+
+/// Lock the queue, it stays consistent even if a thread panicked holding it
+fn lock(queue: &Mutex<Queue>) -> std::sync::MutexGuard<'_, Queue> {
+    queue.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn run(
+    shared: &Shared,
+    doc: &Mutex<PdfDocument>,
+    sender: &mpsc::Sender<RenderResponse>,
+    on_rendered: &dyn Fn(),
+) {
+    let (queue, condvar) = &**shared;
+    loop {
+        let (id, request) = {
+            let mut queue = lock(queue);
+            while queue.pending.is_empty() && !queue.shutdown {
+                queue = condvar.wait(queue).unwrap_or_else(|e| e.into_inner());
+            }
+            if queue.shutdown {
+                return;
+            }
+            queue.pending.remove(0)
+        };
+
+        let result = doc
+            .lock()
+            .map_err(|_| "Failed to lock PDF document".to_string())
+            .and_then(|doc| render(&doc, request));
+
+        // Superseded or cancelled while rendering
+        if lock(queue).latest[request.kind.slot()] != Some(id) {
+            continue;
+        }
+        let response = RenderResponse {
+            id,
+            request,
+            result,
+        };
+        if sender.send(response).is_err() {
+            return;
+        }
+        on_rendered();
+    }
+}
+
+fn render(doc: &PdfDocument, request: RenderRequest) -> Result<RenderedBitmap, String> {
+    let (pixels, width, height) = match request.kind {
+        RenderKind::Page { dpi, rotation } => {
+            let options = PdfRenderOptions {
+                rotation,
+                background: request.background,
+            };
+            doc.render_page_with(request.page, dpi, options)?
+        }
+        RenderKind::Region { rect, scale } => {
+            doc.render_page_region_with(request.page, rect, scale, request.background)?
+        }
+    };
+    Ok(RenderedBitmap {
+        pixels,
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+
+    fn open_test_document() -> (tempfile::NamedTempFile, PdfDocument) {
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+        let pdf_content = "%PDF-1.4\n1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+             2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+             trailer\n<< /Root 1 0 R /Size 4 >>\n\
+             %%EOF";
+        temp_file.write_all(pdf_content.as_bytes()).unwrap();
+        let doc = PdfDocument::open(temp_file.path()).expect("Failed to open PDF");
+        (temp_file, doc)
+    }
+
+    fn wait_for_response(worker: &RenderWorker) -> Option<RenderResponse> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if let Some(response) = worker.try_recv() {
+                return Some(response);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        None
+    }
+
+    #[test]
+    fn test_render_worker() {
+        let (_file, doc) = open_test_document();
+        let worker = RenderWorker::new(Arc::new(Mutex::new(doc)), || {});
+
+        let page = RenderRequest {
+            page: 0,
+            kind: RenderKind::Page {
+                dpi: 72.0,
+                rotation: PdfRotation::None,
+            },
+            background: PdfBackground::WHITE,
+        };
+        let id = worker.submit(page);
+        let response = wait_for_response(&worker).expect("page should be rendered");
+        assert_eq!(response.id, id);
+        assert_eq!(response.request, page);
+        let bitmap = response.result.unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (612, 792));
+        assert_eq!(bitmap.pixels.len(), 612 * 792 * 4);
+
+        // Only the last of the requests of a kind is answered
+        let region = RenderRequest {
+            page: 0,
+            kind: RenderKind::Region {
+                rect: [0.0, 0.0, 100.0, 50.0],
+                scale: 2.0,
+            },
+            background: PdfBackground::Transparent,
+        };
+        for dpi in [36.0, 48.0, 60.0] {
+            worker.submit(RenderRequest {
+                page: 0,
+                kind: RenderKind::Page {
+                    dpi,
+                    rotation: PdfRotation::Cw90,
+                },
+                background: PdfBackground::Color([30, 30, 30]),
+            });
+        }
+        let last = worker.submit(page);
+        let region_id = worker.submit(region);
+        let mut ids = Vec::new();
+        while let Some(response) = wait_for_response(&worker) {
+            ids.push(response.id);
+            if ids.len() == 2 {
+                break;
+            }
+        }
+        ids.sort_unstable();
+        assert_eq!(ids, vec![last, region_id]);
+
+        // Cancelled requests aren't answered
+        let cancelled = worker.submit(page);
+        worker.cancel(cancelled);
+        thread::sleep(Duration::from_millis(200));
+        assert!(worker.try_recv().is_none());
+
+        let error = worker.submit(RenderRequest { page: 5, ..page });
+        let response = wait_for_response(&worker).expect("error should be sent");
+        assert_eq!(response.id, error);
+        assert!(response.result.is_err());
+    }
+}
@@ -1,13 +1,22 @@
 //! HEIF/HEIC preview plugin for kiorg
 //!
-//! This plugin uses libheif-rs to decode HEIF/HEIC images and render them as PNG previews.
+//! This plugin uses libheif-rs to decode HEIF/HEIC images and passes the pixels
+//! to kiorg through shared memory, falling back to PNG previews. Selected
+//! files can also be converted to JPEG as a bulk operation.
 
 use kiorg_plugin::{
-    Component, ImageComponent, ImageFormat, ImageSource, PluginCapabilities, PluginHandler,
-    PluginMetadata, PluginResponse, PreviewCapability, TableComponent, TitleComponent,
+    BulkOperationCapability, BulkProgress, Component, ImageComponent, ImageFormat, ImageSource,
+    PluginCapabilities, PluginHandler, PluginMetadata, PluginResponse, PreviewCapability,
+    TableComponent, TitleComponent,
 };
 use libheif_rs::{Channel, ColorSpace, HeifContext, LibHeif, RgbChroma};
-use std::io::Cursor;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+const HEIF_FILE_PATTERN: &str = r"(?i)\.(heif|heic)$";
+/// Bulk operation converting the selected files to JPEG
+const TO_JPEG_OPERATION: &str = "to_jpeg";
+const JPEG_QUALITY: u8 = 90;
 
 struct HeifPlugin {
     metadata: PluginMetadata,
@@ -15,7 +24,7 @@ struct HeifPlugin {
 
 struct HeifData {
     filename: String,
-    png_data: Vec<u8>,
+    image: ImageSource,
     metadata_rows: Vec<Vec<String>>,
 }
 
@@ -28,17 +37,10 @@ impl PluginHandler for HeifPlugin {
                         text: data.filename,
                     }),
                     Component::Image(ImageComponent {
-                        source: ImageSource::Bytes {
-                            format: ImageFormat::Png,
-                            data: data.png_data,
-                            uid: path.to_string(),
-                        },
+                        source: data.image,
                         interactive: false,
                     }),
-                    Component::Table(TableComponent {
-                        headers: None,
-                        rows: data.metadata_rows,
-                    }),
+                    Component::Table(TableComponent::new(None, data.metadata_rows)),
                 ],
             },
             Err(e) => PluginResponse::Error {
@@ -51,11 +53,7 @@ impl PluginHandler for HeifPlugin {
         match self.process_heif(path, None) {
             Ok(data) => PluginResponse::Preview {
                 components: vec![Component::Image(ImageComponent {
-                    source: ImageSource::Bytes {
-                        format: ImageFormat::Png,
-                        data: data.png_data,
-                        uid: path.to_string(),
-                    },
+                    source: data.image,
                     interactive: true,
                 })],
             },
@@ -65,11 +63,144 @@ impl PluginHandler for HeifPlugin {
         }
     }
 
+    fn on_bulk_operation(
+        &mut self,
+        operation: &str,
+        paths: &[String],
+        progress: &mut BulkProgress,
+    ) -> PluginResponse {
+        if operation != TO_JPEG_OPERATION {
+            return PluginResponse::Error {
+                message: format!("Unsupported bulk operation: {}", operation),
+            };
+        }
+
+        let total = paths.len() as u64;
+        let mut converted = 0;
+        let mut failed = 0;
+        for (i, path) in paths.iter().enumerate() {
+            if progress.is_cancelled() {
+                break;
+            }
+            let name = Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned());
+            if progress.report(i as u64, total, name).is_err() {
+                break;
+            }
+            match convert_to_jpeg(path) {
+                Ok(_) => converted += 1,
+                Err(e) => {
+                    eprintln!("Failed to convert {}: {}", path, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        let mut message = format!("Converted {} of {} files to JPEG", converted, total);
+        if failed > 0 {
+            message.push_str(&format!(", {} failed", failed));
+        }
+        PluginResponse::BulkOperationDone { message }
+    }
+
     fn metadata(&self) -> PluginMetadata {
         self.metadata.clone()
     }
 }
 
+/// Share the raw pixels with kiorg, or send a PNG if shared memory isn't available
+fn to_image_source(
+    dynamic_image: image::DynamicImage,
+    uid: &str,
+) -> Result<ImageSource, Box<dyn std::error::Error>> {
+    let rgba = dynamic_image.to_rgba8();
+    let share_error = match ImageSource::shared(rgba.as_raw(), rgba.width(), rgba.height(), uid) {
+        Ok(source) => return Ok(source),
+        Err(e) => e,
+    };
+    let mut png_data = Vec::new();
+    dynamic_image
+        .write_to(&mut Cursor::new(&mut png_data), image::ImageFormat::Png)
+        .map_err(|e| {
+            format!(
+                "Failed to share image ({}) and to encode it as PNG: {}",
+                share_error, e
+            )
+        })?;
+    Ok(ImageSource::Bytes {
+        format: ImageFormat::Png,
+        data: png_data,
+        uid: uid.to_string(),
+    })
+}
+
+/// Pack the decoded RGB pixels into an image buffer
+fn to_dynamic_image(
+    image: &libheif_rs::Image,
+) -> Result<image::DynamicImage, Box<dyn std::error::Error>> {
+    let width = image.width();
+    let height = image.height();
+
+    let planes = image.planes();
+    let interleaved_plane = planes.interleaved.ok_or("No interleaved plane found")?;
+    let data = interleaved_plane.data;
+    let stride = interleaved_plane.stride;
+
+    // Handle stride if necessary (if stride != width * 3)
+    let packed_data = if stride == (width * 3) as usize {
+        data.to_vec()
+    } else {
+        let mut buffer = Vec::with_capacity((width * height * 3) as usize);
+        for y in 0..height {
+            let start = (y as usize) * stride;
+            let end = start + (width as usize) * 3;
+            buffer.extend_from_slice(&data[start..end]);
+        }
+        buffer
+    };
+
+    // Create image buffer from raw data
+    let buffer = image::RgbImage::from_raw(width, height, packed_data)
+        .ok_or("Failed to create image buffer")?;
+    Ok(image::DynamicImage::ImageRgb8(buffer))
+}
+
+fn write_jpeg(
+    image: &image::DynamicImage,
+    file: std::fs::File,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = std::io::BufWriter::new(file);
+    image.write_with_encoder(image::codecs::jpeg::JpegEncoder::new_with_quality(
+        &mut writer,
+        JPEG_QUALITY,
+    ))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Convert a HEIF file to a JPEG next to it, returning the new path
+fn convert_to_jpeg(path: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let target = Path::new(path).with_extension("jpg");
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()).into());
+    }
+
+    let lib_heif = LibHeif::new();
+    let ctx = HeifContext::read_from_file(path)?;
+    let handle = ctx.primary_image_handle()?;
+    let image = lib_heif.decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)?;
+    let dynamic_image = to_dynamic_image(&image)?;
+
+    let file = std::fs::File::create_new(&target)?;
+    let result = write_jpeg(&dynamic_image, file);
+    if result.is_err() {
+        let _ = std::fs::remove_file(&target);
+    }
+    result?;
+    Ok(target)
+}
+
 impl HeifPlugin {
     fn process_heif(
         &self,
@@ -80,34 +211,13 @@ impl HeifPlugin {
         let ctx = HeifContext::read_from_file(path)?;
         let handle = ctx.primary_image_handle()?;
 
-        // Decode the image
+        // Decode the image, libheif rotates and mirrors it upright as the
+        // container's orientation says
         let image = lib_heif.decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)?;
 
         let width = image.width();
         let height = image.height();
-
-        let planes = image.planes();
-        let interleaved_plane = planes.interleaved.ok_or("No interleaved plane found")?;
-        let data = interleaved_plane.data;
-        let stride = interleaved_plane.stride;
-
-        // Handle stride if necessary (if stride != width * 3)
-        let packed_data = if stride == (width * 3) as usize {
-            data.to_vec()
-        } else {
-            let mut buffer = Vec::with_capacity((width * height * 3) as usize);
-            for y in 0..height {
-                let start = (y as usize) * stride;
-                let end = start + (width as usize) * 3;
-                buffer.extend_from_slice(&data[start..end]);
-            }
-            buffer
-        };
-
-        // Create image buffer from raw data
-        let buffer = image::RgbImage::from_raw(width, height, packed_data)
-            .ok_or("Failed to create image buffer")?;
-        let mut dynamic_image = image::DynamicImage::ImageRgb8(buffer);
+        let mut dynamic_image = to_dynamic_image(&image)?;
 
         // Resize if the image is wider than available width
         if let Some(available_width) = available_width {
@@ -122,10 +232,7 @@ impl HeifPlugin {
             }
         }
 
-        // Encode to PNG
-        let mut png_data = Vec::new();
-        let mut cursor = Cursor::new(&mut png_data);
-        dynamic_image.write_to(&mut cursor, image::ImageFormat::Png)?;
+        let image_source = to_image_source(dynamic_image, path)?;
 
         // Get some metadata for the table
         let mut metadata_rows = vec![
@@ -196,7 +303,7 @@ impl HeifPlugin {
 
         Ok(HeifData {
             filename,
-            png_data,
+            image: image_source,
             metadata_rows,
         })
     }
@@ -211,8 +318,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             homepage: None,
             capabilities: PluginCapabilities {
                 preview: Some(PreviewCapability {
-                    file_pattern: r"(?i)\.(heif|heic)$".to_string(),
+                    file_pattern: HEIF_FILE_PATTERN.to_string(),
                 }),
+                bulk_operations: vec![BulkOperationCapability {
+                    id: TO_JPEG_OPERATION.to_string(),
+                    label: "Convert to JPEG".to_string(),
+                    file_pattern: Some(HEIF_FILE_PATTERN.to_string()),
+                }],
             },
         },
     }